@@ -19,7 +19,7 @@
 //! checks.
 
 use bp_parachains::SubmitParachainHeadsInfo;
-use bp_relayers::ExplicitOrAccountParams;
+use bp_relayers::{ExplicitOrAccountParams, RelayerOffence, SlashReason, SlashedCall};
 use bp_runtime::Parachain;
 use pallet_bridge_grandpa::{
 	BridgedBlockNumber, CallSubType as GrandpaCallSubType, SubmitFinalityProofHelper,
@@ -101,17 +101,21 @@ where
 		// we are only interested in associated pallet submissions
 		let Some(bundled_block_number) = bundled_block_number else { return };
 		// we are only interested in failed or unneeded transactions
-		let has_failed =
-			has_failed || !SubmitFinalityProofHelper::<T, I>::was_successful(bundled_block_number);
-
-		if !has_failed {
+		let reason = if has_failed {
+			SlashReason::DispatchFailed
+		} else if !SubmitFinalityProofHelper::<T, I>::was_successful(bundled_block_number) {
+			SlashReason::NoExpectedEffects
+		} else {
 			return
-		}
+		};
 
 		// let's slash registered relayer
 		RelayersPallet::<T>::slash_and_deregister(
 			relayer,
 			ExplicitOrAccountParams::Explicit(SlashAccount::get()),
+			RelayerOffence::InvalidFinalityProof,
+			SlashedCall::FinalityProof,
+			reason,
 		);
 	}
 }
@@ -172,17 +176,21 @@ where
 		// we are only interested in associated pallet submissions
 		let Some(update) = maybe_update else { return };
 		// we are only interested in failed or unneeded transactions
-		let has_failed = has_failed ||
-			!SubmitParachainHeadsHelper::<T, ParachainsInstance>::was_successful(&update);
-
-		if !has_failed {
+		let reason = if has_failed {
+			SlashReason::DispatchFailed
+		} else if !SubmitParachainHeadsHelper::<T, ParachainsInstance>::was_successful(&update) {
+			SlashReason::NoExpectedEffects
+		} else {
 			return
-		}
+		};
 
 		// let's slash registered relayer
 		RelayersPallet::<T>::slash_and_deregister(
 			relayer,
 			ExplicitOrAccountParams::Explicit(SlashAccount::get()),
+			RelayerOffence::InvalidParachainHeads,
+			SlashedCall::ParachainHeads(update.para_id.0),
+			reason,
 		);
 	}
 }
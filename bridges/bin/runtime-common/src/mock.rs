@@ -214,6 +214,10 @@ impl pallet_bridge_relayers::Config for TestRuntime {
 	type StakeAndSlash = TestStakeAndSlash;
 	type WeightInfo = ();
 	type LaneId = TestLaneIdType;
+	type SlashAmount = bp_relayers::SlashWholeStake;
+	type SlashAppealPeriod = ConstU32<0>;
+	type SlashAppealOrigin = frame_system::EnsureRoot<ThisChainAccountId>;
+	type SlashRecordsToKeep = ConstU32<1024>;
 }
 
 /// Dummy message dispatcher.
@@ -25,6 +25,8 @@ use bp_messages::*;
 use bp_runtime::{
 	decl_bridge_finality_runtime_apis, decl_bridge_messages_runtime_apis, Chain, ChainId, Parachain,
 };
+use bp_xcm_bridge_hub::decl_xcm_bridge_hub_runtime_apis;
+use bp_xcm_bridge_hub_router::decl_xcm_bridge_hub_router_runtime_apis;
 use codec::{Decode, Encode};
 use frame_support::{
 	dispatch::DispatchClass,
@@ -100,6 +102,8 @@ pub const WITH_BRIDGE_ROCOCO_TO_BULLETIN_MESSAGES_PALLET_INDEX: u8 = 61;
 
 decl_bridge_finality_runtime_apis!(bridge_hub_rococo);
 decl_bridge_messages_runtime_apis!(bridge_hub_rococo, LegacyLaneId);
+decl_xcm_bridge_hub_runtime_apis!(bridge_hub_rococo, LegacyLaneId);
+decl_xcm_bridge_hub_router_runtime_apis!(bridge_hub_rococo);
 
 frame_support::parameter_types! {
 	/// The XCM fee that is paid for executing XCM program (with `ExportMessage` instruction) at the Rococo
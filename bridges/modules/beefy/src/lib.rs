@@ -90,9 +90,9 @@ pub type ImportedCommitment<T, I> = bp_beefy::ImportedCommitment<
 pub struct ImportedCommitmentsInfoData<BlockNumber> {
 	/// Best known block number, provided in a BEEFY commitment. However this is not
 	/// the best proven block. The best proven block is this block's parent.
-	best_block_number: BlockNumber,
+	pub best_block_number: BlockNumber,
 	/// The head of the `ImportedBlockNumbers` ring buffer.
-	next_block_number_index: u32,
+	pub next_block_number_index: u32,
 }
 
 #[frame_support::pallet(dev_mode)]
@@ -240,6 +240,13 @@ fn total_messages_in_delivery_proof_does_not_affect_proof_size<W: WeightInfoExt>
 
 	ensure_weight_components_are_not_zero(weight_when_1k_messages_confirmed);
 	ensure_weight_components_are_not_zero(weight_when_2k_messages_confirmed);
+
+	// the runtime may need to touch some storage for every confirmed message - we exclude it
+	// from this check
+	let weight_when_1k_messages_confirmed = weight_when_1k_messages_confirmed
+		.saturating_sub(W::receive_messages_delivery_proof_messages_overhead_from_runtime(1024));
+	let weight_when_2k_messages_confirmed = weight_when_2k_messages_confirmed
+		.saturating_sub(W::receive_messages_delivery_proof_messages_overhead_from_runtime(2048));
 	ensure_proof_size_is_the_same(
 		weight_when_1k_messages_confirmed,
 		weight_when_2k_messages_confirmed,
@@ -295,6 +302,14 @@ pub trait WeightInfoExt: WeightInfo {
 	/// extensions that are declared at runtime level.
 	fn receive_messages_delivery_proof_overhead_from_runtime() -> Weight;
 
+	/// Extra weight that is added to the `receive_messages_delivery_proof` call weight by the
+	/// runtime `OnMessagesDelivered` handler, when given number of messages is confirmed.
+	fn receive_messages_delivery_proof_messages_overhead_from_runtime(
+		_messages: MessageNonce,
+	) -> Weight {
+		Weight::zero()
+	}
+
 	// Functions that are directly mapped to extrinsics weights.
 
 	/// Weight of message delivery extrinsic.
@@ -338,6 +353,10 @@ pub trait WeightInfoExt: WeightInfo {
 			Self::receive_messages_delivery_proof_overhead_from_runtime();
 		let messages_overhead =
 			Self::receive_messages_delivery_proof_messages_overhead(relayers_state.total_messages);
+		let messages_overhead_from_runtime =
+			Self::receive_messages_delivery_proof_messages_overhead_from_runtime(
+				relayers_state.total_messages,
+			);
 		let relayers_overhead = Self::receive_messages_delivery_proof_relayers_overhead(
 			relayers_state.unrewarded_relayer_entries,
 		);
@@ -352,6 +371,7 @@ pub trait WeightInfoExt: WeightInfo {
 		transaction_overhead
 			.saturating_add(transaction_overhead_from_runtime)
 			.saturating_add(messages_overhead)
+			.saturating_add(messages_overhead_from_runtime)
 			.saturating_add(relayers_overhead)
 			.saturating_add(proof_size_overhead)
 	}
@@ -20,9 +20,10 @@
 
 use crate::*;
 
-use bp_relayers::RewardsAccountOwner;
-use frame_benchmarking::{benchmarks_instance_pallet, whitelisted_caller};
-use frame_system::RawOrigin;
+use bp_relayers::{RelayerOffence, RewardsAccountOwner};
+use frame_benchmarking::{benchmarks_instance_pallet, whitelisted_caller, BenchmarkError};
+use frame_support::traits::EnsureOrigin;
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use sp_runtime::traits::One;
 
 /// Reward amount that is (hopefully) is larger than existential deposit across all chains.
@@ -46,6 +47,54 @@ pub trait Config<I: 'static = ()>: crate::Config<I> {
 	fn deposit_account(account: Self::AccountId, balance: Self::Reward);
 }
 
+/// Insert slash record with given identifier and status.
+fn insert_slash_record<T: Config<I>, I: 'static>(
+	relayer: &T::AccountId,
+	slash_id: SlashId,
+	amount: T::Reward,
+	status: SlashStatus<BlockNumberFor<T>>,
+) {
+	let lane = T::bench_lane_id();
+	let beneficiary = RewardsAccountParams::new(lane, *b"test", RewardsAccountOwner::ThisChain);
+	T::prepare_rewards_account(beneficiary, Zero::zero());
+
+	Slashes::<T, I>::insert(
+		relayer,
+		slash_id,
+		SlashRecord {
+			evidence: SlashEvidence {
+				offence: RelayerOffence::FailedMessagesDelivery,
+				call: SlashedCall::MessagesDelivery(lane),
+				reason: SlashReason::DispatchFailed,
+				block_number: Zero::zero(),
+				extrinsic_index: None,
+			},
+			amount,
+			beneficiary: beneficiary.into(),
+			status,
+		},
+	);
+	SlashOwners::<T, I>::insert(slash_id, relayer);
+}
+
+/// Prepare pending slash of the relayer stake.
+///
+/// The slash is not among the `SlashRecordsToKeep` latest slashes, so its record is removed
+/// when the slash is resolved.
+fn prepare_pending_slash<T: Config<I>, I: 'static>(
+	relayer: &T::AccountId,
+	appeal_till: BlockNumberFor<T>,
+) -> SlashId {
+	let stake = crate::Pallet::<T, I>::required_stake();
+	T::deposit_account(relayer.clone(), stake);
+	T::StakeAndSlash::reserve(relayer, stake).unwrap();
+
+	let slash_id = 0;
+	insert_slash_record::<T, I>(relayer, slash_id, stake, SlashStatus::Pending { appeal_till });
+	NextSlashId::<T, I>::put(SlashId::from(T::SlashRecordsToKeep::get()) + 1);
+	slash_id
+}
+
 benchmarks_instance_pallet! {
 	// Benchmark `claim_rewards` call.
 	claim_rewards {
@@ -97,6 +146,8 @@ benchmarks_instance_pallet! {
 	// Benchmark `slash_and_deregister` method of the pallet. We are adding this weight to
 	// the weight of message delivery call if `RefundBridgedParachainMessages` signed extension
 	// is deployed at runtime level.
+	//
+	// The worst case is when the record of resolved slash is pruned by the new slash.
 	slash_and_deregister {
 		// prepare and register relayer account
 		let relayer: T::AccountId = whitelisted_caller();
@@ -111,11 +162,24 @@ benchmarks_instance_pallet! {
 		let lane = T::bench_lane_id();
 		let slash_destination = RewardsAccountParams::new(lane, *b"test", RewardsAccountOwner::ThisChain);
 		T::prepare_rewards_account(slash_destination, Zero::zero());
+
+		// insert resolved slash record that is pruned
+		let pruned_slash_id = 0;
+		insert_slash_record::<T, I>(&relayer, pruned_slash_id, Zero::zero(), SlashStatus::Applied);
+		NextSlashId::<T, I>::put(SlashId::from(T::SlashRecordsToKeep::get()));
 	}: {
-		crate::Pallet::<T, I>::slash_and_deregister(&relayer, slash_destination.into())
+		crate::Pallet::<T, I>::slash_and_deregister(
+			&relayer,
+			slash_destination.into(),
+			RelayerOffence::FailedMessagesDelivery,
+			SlashedCall::MessagesDelivery(lane),
+			SlashReason::DispatchFailed,
+		)
 	}
 	verify {
 		assert!(!crate::Pallet::<T, I>::is_registration_active(&relayer));
+		assert!(Slashes::<T, I>::get(&relayer, pruned_slash_id).is_none());
+		assert!(Slashes::<T, I>::contains_key(&relayer, SlashId::from(T::SlashRecordsToKeep::get())));
 	}
 
 	// Benchmark `register_relayer_reward` method of the pallet. We are adding this weight to
@@ -133,5 +197,41 @@ benchmarks_instance_pallet! {
 		assert_eq!(RelayerRewards::<T, I>::get(relayer, &account_params), Some(One::one()));
 	}
 
+	// Benchmark `appeal_slash` call.
+	appeal_slash {
+		let relayer: T::AccountId = whitelisted_caller();
+		let slash_id =
+			prepare_pending_slash::<T, I>(&relayer, frame_system::Pallet::<T>::block_number());
+	}: _(RawOrigin::Signed(relayer.clone()), slash_id)
+	verify {
+		assert_eq!(
+			Slashes::<T, I>::get(&relayer, slash_id).map(|slash| slash.status),
+			Some(SlashStatus::Appealed),
+		);
+	}
+
+	// Benchmark `resolve_slash_appeal` call.
+	resolve_slash_appeal {
+		let relayer: T::AccountId = whitelisted_caller();
+		let slash_id =
+			prepare_pending_slash::<T, I>(&relayer, frame_system::Pallet::<T>::block_number());
+		let origin = T::SlashAppealOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, relayer.clone(), slash_id, false)
+	verify {
+		assert!(Slashes::<T, I>::get(&relayer, slash_id).is_none());
+	}
+
+	// Benchmark `apply_slash` call.
+	apply_slash {
+		let relayer: T::AccountId = whitelisted_caller();
+		let appeal_till = frame_system::Pallet::<T>::block_number();
+		let slash_id = prepare_pending_slash::<T, I>(&relayer, appeal_till);
+		frame_system::Pallet::<T>::set_block_number(appeal_till.saturating_add(One::one()));
+	}: _(RawOrigin::Signed(relayer.clone()), relayer.clone(), slash_id)
+	verify {
+		assert!(Slashes::<T, I>::get(&relayer, slash_id).is_none());
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime)
 }
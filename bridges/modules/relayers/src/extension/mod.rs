@@ -26,8 +26,8 @@ use crate::{Config as RelayersConfig, Pallet as RelayersPallet, WeightInfoExt, L
 
 use bp_messages::{ChainWithMessages, MessageNonce};
 use bp_relayers::{
-	ExplicitOrAccountParams, ExtensionCallData, ExtensionCallInfo, ExtensionConfig,
-	RewardsAccountOwner, RewardsAccountParams,
+	ExplicitOrAccountParams, ExtensionCallData, ExtensionCallInfo, ExtensionConfig, RelayerOffence,
+	RewardsAccountOwner, RewardsAccountParams, SlashReason, SlashedCall,
 };
 use bp_runtime::{Chain, RangeInclusiveExt, StaticStrProvider};
 use codec::{Decode, Encode};
@@ -103,7 +103,7 @@ pub enum RelayerAccountAction<AccountId, Reward, LaneId> {
 	/// Reward the relayer.
 	Reward(AccountId, RewardsAccountParams<LaneId>, Reward),
 	/// Slash the relayer.
-	Slash(AccountId, RewardsAccountParams<LaneId>),
+	Slash(AccountId, RewardsAccountParams<LaneId>, RelayerOffence, SlashReason),
 }
 
 /// A signed extension, built around `pallet-bridge-relayers`.
@@ -210,9 +210,18 @@ where
 		// - when relayer is registered after `validate` is called and priority is not boosted:
 		//   relayer should be ready for slashing after registration.
 		let may_slash_relayer = Self::bundled_messages_for_priority_boost(&call_info).is_some();
-		let slash_relayer_if_delivery_result = may_slash_relayer
-			.then(|| RelayerAccountAction::Slash(relayer.clone(), reward_account_params))
-			.unwrap_or(RelayerAccountAction::None);
+		let slash_relayer_if_delivery_result = |offence, reason| {
+			may_slash_relayer
+				.then(|| {
+					RelayerAccountAction::Slash(
+						relayer.clone(),
+						reward_account_params,
+						offence,
+						reason,
+					)
+				})
+				.unwrap_or(RelayerAccountAction::None)
+		};
 
 		// We don't refund anything if the transaction has failed.
 		if let Err(e) = result {
@@ -224,13 +233,19 @@ where
 				relayer,
 				e,
 			);
-			return slash_relayer_if_delivery_result
+			return slash_relayer_if_delivery_result(
+				RelayerOffence::FailedMessagesDelivery,
+				SlashReason::DispatchError(*e),
+			)
 		}
 
 		// check whether the call has succeeded
 		let mut call_data = ExtensionCallData::default();
 		if !C::check_call_result(&call_info, &mut call_data, &relayer) {
-			return slash_relayer_if_delivery_result
+			return slash_relayer_if_delivery_result(
+				RelayerOffence::UnsuccessfulMessagesDelivery,
+				SlashReason::NoExpectedEffects,
+			)
 		}
 
 		// regarding the tip - refund that happens here (at this side of the bridge) isn't the whole
@@ -393,11 +408,16 @@ where
 					relayer,
 				);
 			},
-			RelayerAccountAction::Slash(relayer, slash_account) =>
+			RelayerAccountAction::Slash(relayer, slash_account, offence, reason) => {
+				let call = SlashedCall::MessagesDelivery(*slash_account.lane_id());
 				RelayersPallet::<R>::slash_and_deregister(
 					&relayer,
 					ExplicitOrAccountParams::Params(slash_account),
-				),
+					offence,
+					call,
+					reason,
+				)
+			},
 		}
 
 		Ok(Weight::zero())
@@ -1995,21 +2015,27 @@ mod tests {
 				run_analyze_call_result(all_finality_pre_dispatch_data(), Ok(())),
 				RelayerAccountAction::Slash(
 					relayer_account_at_this_chain(),
-					MsgProofsRewardsAccount::get()
+					MsgProofsRewardsAccount::get(),
+					RelayerOffence::UnsuccessfulMessagesDelivery,
+					SlashReason::NoExpectedEffects,
 				),
 			);
 			assert_eq!(
 				run_analyze_call_result(parachain_finality_pre_dispatch_data(), Ok(())),
 				RelayerAccountAction::Slash(
 					relayer_account_at_this_chain(),
-					MsgProofsRewardsAccount::get()
+					MsgProofsRewardsAccount::get(),
+					RelayerOffence::UnsuccessfulMessagesDelivery,
+					SlashReason::NoExpectedEffects,
 				),
 			);
 			assert_eq!(
 				run_analyze_call_result(delivery_pre_dispatch_data(), Ok(())),
 				RelayerAccountAction::Slash(
 					relayer_account_at_this_chain(),
-					MsgProofsRewardsAccount::get()
+					MsgProofsRewardsAccount::get(),
+					RelayerOffence::UnsuccessfulMessagesDelivery,
+					SlashReason::NoExpectedEffects,
 				),
 			);
 
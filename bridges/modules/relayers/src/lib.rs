@@ -16,19 +16,31 @@
 
 //! Runtime module that is used to store relayer rewards and (in the future) to
 //! coordinate relations between relayers.
+//!
+//! Registered relayers get priority boost for their transactions, but they risk being slashed
+//! if their transactions are invalid. Every slash is recorded along with the evidence and may
+//! be queried using the [`bp_relayers::RelayersSlashesApi`]. If `SlashAppealPeriod` is non-zero,
+//! slashed funds stay reserved for that period, so that the relayer may appeal the slash to the
+//! `SlashAppealOrigin`. Records of resolved slashes are kept in the storage until they are not
+//! among the `SlashRecordsToKeep` latest slashes, so even immediately applied slashes may be
+//! queried.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
 use bp_relayers::{
-	ExplicitOrAccountParams, PaymentProcedure, Registration, RelayerRewardsKeyProvider,
-	RewardsAccountParams, StakeAndSlash,
+	ExplicitOrAccountParams, PaymentProcedure, Registration, RelayerOffence,
+	RelayerRewardsKeyProvider, RewardsAccountParams, SlashEvidence, SlashId, SlashReason,
+	SlashRecord, SlashStatus, SlashedCall, StakeAndSlash,
 };
 use bp_runtime::StorageDoubleMapKeyProvider;
 use frame_support::fail;
 use sp_arithmetic::traits::{AtLeast32BitUnsigned, Zero};
-use sp_runtime::{traits::CheckedSub, Saturating};
-use sp_std::marker::PhantomData;
+use sp_runtime::{
+	traits::{CheckedSub, Convert},
+	Saturating,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 pub use pallet::*;
 pub use payment_adapter::DeliveryConfirmationPaymentsAdapter;
@@ -63,6 +75,14 @@ pub mod pallet {
 		<T as Config<I>>::LaneId,
 	>;
 
+	/// Relayer slash record for given configuration.
+	pub type SlashRecordOf<T, I> = SlashRecord<
+		<T as frame_system::Config>::AccountId,
+		BlockNumberFor<T>,
+		<T as Config<I>>::Reward,
+		<T as Config<I>>::LaneId,
+	>;
+
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
 		/// The overarching event type.
@@ -82,12 +102,32 @@ pub mod pallet {
 		type WeightInfo: WeightInfoExt;
 		/// Lane identifier type.
 		type LaneId: LaneIdType + Send + Sync;
+		/// Amount of the relayer stake that is slashed for given offence. If `None` is returned,
+		/// the whole stake is slashed. The rest of the stake is returned to the relayer.
+		type SlashAmount: Convert<RelayerOffence, Option<Self::Reward>>;
+		/// Number of blocks, during which the slashed relayer may appeal the slash. Slashed funds
+		/// stay reserved at the relayer account during this period. If it is zero, slashed funds
+		/// are moved to the beneficiary immediately.
+		type SlashAppealPeriod: Get<BlockNumberFor<Self>>;
+		/// Origin that is allowed to resolve slash appeals.
+		type SlashAppealOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Number of the latest slashes, which records are kept in the storage after they are
+		/// resolved. Records of unresolved slashes are kept until they are resolved. Must be
+		/// non-zero.
+		type SlashRecordsToKeep: Get<u32>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::storage_version(migration::STORAGE_VERSION)]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn integrity_test() {
+			assert!(!T::SlashRecordsToKeep::get().is_zero(), "SlashRecordsToKeep must be non-zero",);
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Claim accumulated rewards.
@@ -228,6 +268,110 @@ pub mod pallet {
 				},
 			)
 		}
+
+		/// Appeal the pending slash.
+		///
+		/// Appealed slash is not applied until it is resolved by the `SlashAppealOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::appeal_slash())]
+		pub fn appeal_slash(origin: OriginFor<T>, slash_id: SlashId) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+
+			Slashes::<T, I>::try_mutate(&relayer, slash_id, |maybe_slash| -> DispatchResult {
+				let slash = maybe_slash.as_mut().ok_or(Error::<T, I>::UnknownSlash)?;
+				match slash.status {
+					SlashStatus::Pending { appeal_till }
+						if appeal_till >= frame_system::Pallet::<T>::block_number() => {},
+					SlashStatus::Pending { .. } => fail!(Error::<T, I>::SlashAppealPeriodIsOver),
+					SlashStatus::Appealed | SlashStatus::Applied | SlashStatus::Cancelled =>
+						fail!(Error::<T, I>::SlashIsNotPending),
+				}
+				slash.status = SlashStatus::Appealed;
+
+				log::trace!(
+					target: LOG_TARGET,
+					"Relayer {:?} has appealed slash {}",
+					relayer,
+					slash_id,
+				);
+				Self::deposit_event(Event::<T, I>::SlashAppealed {
+					relayer: relayer.clone(),
+					slash_id,
+				});
+
+				Ok(())
+			})
+		}
+
+		/// Resolve the slash appeal.
+		///
+		/// If `cancel` is `true`, slashed funds are released. Otherwise, they are moved to the
+		/// slash beneficiary. Pending slashes may be resolved as well. The slash record is
+		/// removed from the storage if it is not among the `SlashRecordsToKeep` latest slashes.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::resolve_slash_appeal())]
+		pub fn resolve_slash_appeal(
+			origin: OriginFor<T>,
+			relayer: T::AccountId,
+			slash_id: SlashId,
+			cancel: bool,
+		) -> DispatchResult {
+			T::SlashAppealOrigin::ensure_origin(origin)?;
+
+			let slash =
+				Slashes::<T, I>::get(&relayer, slash_id).ok_or(Error::<T, I>::UnknownSlash)?;
+			ensure!(!slash.status.is_resolved(), Error::<T, I>::SlashIsNotPending);
+			if cancel {
+				Self::do_unreserve(&relayer, slash.amount)?;
+				Self::resolve_slash(&relayer, slash_id, slash, SlashStatus::Cancelled);
+
+				Self::deposit_event(Event::<T, I>::SlashCancelled {
+					relayer: relayer.clone(),
+					slash_id,
+				});
+			} else {
+				Self::do_slash(&relayer, slash.beneficiary.clone(), slash.amount);
+				Self::resolve_slash(&relayer, slash_id, slash, SlashStatus::Applied);
+
+				Self::deposit_event(Event::<T, I>::SlashApplied {
+					relayer: relayer.clone(),
+					slash_id,
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Apply the pending slash, which appeal period is over.
+		///
+		/// This call may be submitted by anyone. The slash record is removed from the storage if it
+		/// is not among the `SlashRecordsToKeep` latest slashes.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::apply_slash())]
+		pub fn apply_slash(
+			origin: OriginFor<T>,
+			relayer: T::AccountId,
+			slash_id: SlashId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let slash =
+				Slashes::<T, I>::get(&relayer, slash_id).ok_or(Error::<T, I>::UnknownSlash)?;
+			match slash.status {
+				SlashStatus::Pending { appeal_till }
+					if appeal_till < frame_system::Pallet::<T>::block_number() => {},
+				SlashStatus::Pending { .. } => fail!(Error::<T, I>::SlashAppealPeriodIsNotOver),
+				SlashStatus::Appealed | SlashStatus::Applied | SlashStatus::Cancelled =>
+					fail!(Error::<T, I>::SlashIsNotPending),
+			}
+
+			Self::do_slash(&relayer, slash.beneficiary.clone(), slash.amount);
+			Self::resolve_slash(&relayer, slash_id, slash, SlashStatus::Applied);
+
+			Self::deposit_event(Event::<T, I>::SlashApplied { relayer: relayer.clone(), slash_id });
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -258,12 +402,20 @@ pub mod pallet {
 			true
 		}
 
-		/// Slash and `deregister` relayer. This function slashes all staked balance.
+		/// Slash and `deregister` relayer. This function slashes the `SlashAmount` of the staked
+		/// balance for given offence and releases the rest of the stake.
+		///
+		/// The offence evidence is deposited in the event and the slash is recorded. If
+		/// `SlashAppealPeriod` is non-zero, slashed funds stay reserved until the slash is
+		/// resolved. Otherwise, the slash is applied immediately.
 		///
 		/// It may fail inside, but error is swallowed and we only log it.
 		pub fn slash_and_deregister(
 			relayer: &T::AccountId,
 			slash_destination: ExplicitOrAccountParams<T::AccountId, T::LaneId>,
+			offence: RelayerOffence,
+			call: SlashedCall<T::LaneId>,
+			reason: SlashReason,
 		) {
 			let registration = match RegisteredRelayers::<T, I>::take(relayer) {
 				Some(registration) => registration,
@@ -278,47 +430,99 @@ pub mod pallet {
 				},
 			};
 
-			match T::StakeAndSlash::repatriate_reserved(
+			// slash amount can't be larger than the stake
+			let amount = T::SlashAmount::convert(offence)
+				.map(|amount| amount.min(registration.stake))
+				.unwrap_or(registration.stake);
+			let to_unreserve = registration.stake.saturating_sub(amount);
+			if !to_unreserve.is_zero() {
+				// error is logged inside, so we may ignore it here
+				let _ = Self::do_unreserve(relayer, to_unreserve);
+			}
+
+			let slash_id = NextSlashId::<T, I>::mutate(|next_slash_id| {
+				let slash_id = *next_slash_id;
+				*next_slash_id = next_slash_id.wrapping_add(1);
+				slash_id
+			});
+			let block_number = frame_system::Pallet::<T>::block_number();
+			let evidence = SlashEvidence {
+				offence,
+				call,
+				reason,
+				block_number,
+				extrinsic_index: frame_system::Pallet::<T>::extrinsic_index(),
+			};
+
+			let appeal_period = T::SlashAppealPeriod::get();
+			let status = if appeal_period.is_zero() {
+				Self::do_slash(relayer, slash_destination.clone(), amount);
+				SlashStatus::Applied
+			} else {
+				SlashStatus::Pending { appeal_till: block_number.saturating_add(appeal_period) }
+			};
+
+			Self::prune_slash_record(slash_id);
+			Slashes::<T, I>::insert(
 				relayer,
-				slash_destination.clone(),
-				registration.stake,
-			) {
-				Ok(failed_to_slash) if failed_to_slash.is_zero() => {
-					log::trace!(
-						target: crate::LOG_TARGET,
-						"Relayer account {:?} has been slashed for {:?}. Funds were deposited to {:?}",
-						relayer,
-						registration.stake,
-						slash_destination,
-					);
-				},
-				Ok(failed_to_slash) => {
-					log::trace!(
-						target: crate::LOG_TARGET,
-						"Relayer account {:?} has been partially slashed for {:?}. Funds were deposited to {:?}. \
-						Failed to slash: {:?}",
-						relayer,
-						registration.stake,
-						slash_destination,
-						failed_to_slash,
-					);
-				},
-				Err(e) => {
-					// TODO: document this. Where?
+				slash_id,
+				SlashRecord { evidence, amount, beneficiary: slash_destination, status },
+			);
+			SlashOwners::<T, I>::insert(slash_id, relayer);
+
+			Self::deposit_event(Event::<T, I>::SlashedAndDeregistered {
+				relayer: relayer.clone(),
+				registration,
+				slash_id,
+				evidence,
+			});
+		}
 
-					// it may fail if there's no beneficiary account. For us it means that this
-					// account must exists before we'll deploy the bridge
-					log::debug!(
-						target: crate::LOG_TARGET,
-						"Failed to slash relayer account {:?}: {:?}. Maybe beneficiary account doesn't exist? \
-						Beneficiary: {:?}, amount: {:?}, failed to slash: {:?}",
-						relayer,
-						e,
-						slash_destination,
-						registration.stake,
-						registration.stake,
-					);
-				},
+		/// Returns all unresolved and recently resolved slashes of given relayer.
+		pub fn relayer_slashes(relayer: &T::AccountId) -> Vec<(SlashId, SlashRecordOf<T, I>)> {
+			Slashes::<T, I>::iter_prefix(relayer).collect()
+		}
+
+		/// Change status of the resolved slash.
+		///
+		/// The slash record is removed if it is not among the `SlashRecordsToKeep` latest
+		/// slashes.
+		fn resolve_slash(
+			relayer: &T::AccountId,
+			slash_id: SlashId,
+			mut slash: SlashRecordOf<T, I>,
+			status: SlashStatus<BlockNumberFor<T>>,
+		) {
+			let oldest_kept_slash_id =
+				NextSlashId::<T, I>::get().saturating_sub(T::SlashRecordsToKeep::get().into());
+			if slash_id >= oldest_kept_slash_id {
+				slash.status = status;
+				Slashes::<T, I>::insert(relayer, slash_id, slash);
+			} else {
+				Slashes::<T, I>::remove(relayer, slash_id);
+				SlashOwners::<T, I>::remove(slash_id);
+			}
+		}
+
+		/// Remove record of the resolved slash, which is no longer among the `SlashRecordsToKeep`
+		/// latest slashes after the slash with given identifier is recorded.
+		///
+		/// Records of unresolved slashes are removed when they are resolved.
+		fn prune_slash_record(new_slash_id: SlashId) {
+			let slash_id = match new_slash_id.checked_sub(T::SlashRecordsToKeep::get().into()) {
+				Some(slash_id) => slash_id,
+				None => return,
+			};
+			let relayer = match SlashOwners::<T, I>::get(slash_id) {
+				Some(relayer) => relayer,
+				None => return,
+			};
+			let is_resolved = Slashes::<T, I>::get(&relayer, slash_id)
+				.map(|slash| slash.status.is_resolved())
+				.unwrap_or(true);
+			if is_resolved {
+				Slashes::<T, I>::remove(&relayer, slash_id);
+				SlashOwners::<T, I>::remove(slash_id);
 			}
 		}
 
@@ -391,6 +595,55 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Move given amount of reserved funds from relayer account to the `slash_destination`.
+		///
+		/// It may fail inside, but error is swallowed and we only log it.
+		fn do_slash(
+			relayer: &T::AccountId,
+			slash_destination: ExplicitOrAccountParams<T::AccountId, T::LaneId>,
+			amount: T::Reward,
+		) {
+			match T::StakeAndSlash::repatriate_reserved(relayer, slash_destination.clone(), amount)
+			{
+				Ok(failed_to_slash) if failed_to_slash.is_zero() => {
+					log::trace!(
+						target: crate::LOG_TARGET,
+						"Relayer account {:?} has been slashed for {:?}. Funds were deposited to {:?}",
+						relayer,
+						amount,
+						slash_destination,
+					);
+				},
+				Ok(failed_to_slash) => {
+					log::trace!(
+						target: crate::LOG_TARGET,
+						"Relayer account {:?} has been partially slashed for {:?}. Funds were deposited to {:?}. \
+						Failed to slash: {:?}",
+						relayer,
+						amount,
+						slash_destination,
+						failed_to_slash,
+					);
+				},
+				Err(e) => {
+					// TODO: document this. Where?
+
+					// it may fail if there's no beneficiary account. For us it means that this
+					// account must exists before we'll deploy the bridge
+					log::debug!(
+						target: crate::LOG_TARGET,
+						"Failed to slash relayer account {:?}: {:?}. Maybe beneficiary account doesn't exist? \
+						Beneficiary: {:?}, amount: {:?}, failed to slash: {:?}",
+						relayer,
+						e,
+						slash_destination,
+						amount,
+						amount,
+					);
+				},
+			}
+		}
 	}
 
 	#[pallet::event]
@@ -432,6 +685,31 @@ pub mod pallet {
 			relayer: T::AccountId,
 			/// Registration that was removed.
 			registration: Registration<BlockNumberFor<T>, T::Reward>,
+			/// Identifier of the slash.
+			slash_id: SlashId,
+			/// Evidence of the relayer offence.
+			evidence: SlashEvidence<BlockNumberFor<T>, T::LaneId>,
+		},
+		/// Relayer has appealed the slash.
+		SlashAppealed {
+			/// Slashed relayer account.
+			relayer: T::AccountId,
+			/// Identifier of the slash.
+			slash_id: SlashId,
+		},
+		/// Slashed funds have been moved to the slash beneficiary.
+		SlashApplied {
+			/// Slashed relayer account.
+			relayer: T::AccountId,
+			/// Identifier of the slash.
+			slash_id: SlashId,
+		},
+		/// Slash has been cancelled and slashed funds have been released.
+		SlashCancelled {
+			/// Slashed relayer account.
+			relayer: T::AccountId,
+			/// Identifier of the slash.
+			slash_id: SlashId,
 		},
 	}
 
@@ -454,6 +732,14 @@ pub mod pallet {
 		NotRegistered,
 		/// Failed to `deregister` relayer, because lease is still active.
 		RegistrationIsStillActive,
+		/// There's no slash with given identifier.
+		UnknownSlash,
+		/// The slash has already been appealed or resolved.
+		SlashIsNotPending,
+		/// The slash can't be appealed, because its appeal period is over.
+		SlashAppealPeriodIsOver,
+		/// The slash can't be applied, because its appeal period is not over yet.
+		SlashAppealPeriodIsNotOver,
 	}
 
 	/// Map of the relayer => accumulated reward.
@@ -484,6 +770,31 @@ pub mod pallet {
 		Registration<BlockNumberFor<T>, T::Reward>,
 		OptionQuery,
 	>;
+
+	/// Identifier of the next relayer slash.
+	#[pallet::storage]
+	pub type NextSlashId<T: Config<I>, I: 'static = ()> = StorageValue<_, SlashId, ValueQuery>;
+
+	/// Unresolved and recently resolved slashes of relayers.
+	///
+	/// Every slash requires relayer to deregister and lose (part of) its stake, so the number
+	/// of unresolved records is limited by the economic cost of offences. Records of resolved
+	/// slashes are removed once they are not among the `SlashRecordsToKeep` latest slashes.
+	#[pallet::storage]
+	pub type Slashes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Identity,
+		SlashId,
+		SlashRecordOf<T, I>,
+		OptionQuery,
+	>;
+
+	/// Relayer accounts of slashes, which records are stored in the `Slashes` map.
+	#[pallet::storage]
+	pub type SlashOwners<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, SlashId, T::AccountId, OptionQuery>;
 }
 
 #[cfg(test)]
@@ -943,4 +1254,265 @@ mod tests {
 			assert!(Pallet::<TestRuntime>::is_registration_active(&REGISTER_RELAYER));
 		});
 	}
+
+	fn register_and_slash(offence: RelayerOffence) -> SlashId {
+		Balances::mint_into(&REGULAR_RELAYER, ExistentialDeposit::get()).unwrap();
+		assert_ok!(Pallet::<TestRuntime>::register(RuntimeOrigin::signed(REGISTER_RELAYER), 150));
+		Pallet::<TestRuntime>::slash_and_deregister(
+			&REGISTER_RELAYER,
+			ExplicitOrAccountParams::Explicit(REGULAR_RELAYER),
+			offence,
+			SlashedCall::MessagesDelivery(test_lane_id()),
+			SlashReason::DispatchError(DispatchError::BadOrigin),
+		);
+		NextSlashId::<TestRuntime>::get() - 1
+	}
+
+	#[test]
+	fn slash_and_deregister_deposits_slash_evidence() {
+		run_test(|| {
+			get_ready_for_events();
+
+			let free_balance = Balances::free_balance(REGISTER_RELAYER);
+			let slash_id = register_and_slash(RelayerOffence::FailedMessagesDelivery);
+
+			let evidence = SlashEvidence {
+				offence: RelayerOffence::FailedMessagesDelivery,
+				call: SlashedCall::MessagesDelivery(test_lane_id()),
+				reason: SlashReason::DispatchError(DispatchError::BadOrigin),
+				block_number: 1,
+				extrinsic_index: None,
+			};
+			assert!(Pallet::<TestRuntime>::registered_relayer(REGISTER_RELAYER).is_none());
+			// slash is applied immediately, but it is still recorded
+			assert_eq!(
+				Pallet::<TestRuntime>::relayer_slashes(&REGISTER_RELAYER),
+				vec![(
+					slash_id,
+					SlashRecord {
+						evidence,
+						amount: Stake::get(),
+						beneficiary: ExplicitOrAccountParams::Explicit(REGULAR_RELAYER),
+						status: SlashStatus::Applied,
+					}
+				)],
+			);
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), 0);
+			assert_eq!(Balances::free_balance(REGISTER_RELAYER), free_balance - Stake::get());
+			assert_eq!(
+				Balances::free_balance(REGULAR_RELAYER),
+				ExistentialDeposit::get() + Stake::get()
+			);
+
+			assert_eq!(
+				System::<TestRuntime>::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::BridgeRelayers(Event::SlashedAndDeregistered {
+						relayer: REGISTER_RELAYER,
+						registration: Registration { valid_till: 150, stake: Stake::get() },
+						slash_id,
+						evidence,
+					}),
+					topics: vec![],
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn slash_and_deregister_slashes_configured_amount() {
+		run_test(|| {
+			SlashAppealPeriod::set(&10);
+
+			let free_balance = Balances::free_balance(REGISTER_RELAYER);
+			let slash_id = register_and_slash(RelayerOffence::InvalidFinalityProof);
+			assert_eq!(
+				Slashes::<TestRuntime>::get(REGISTER_RELAYER, slash_id).map(|slash| slash.amount),
+				Some(Stake::get() / 2),
+			);
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), Stake::get() / 2);
+
+			System::<TestRuntime>::set_block_number(12);
+			assert_ok!(Pallet::<TestRuntime>::apply_slash(
+				RuntimeOrigin::signed(REGULAR_RELAYER),
+				REGISTER_RELAYER,
+				slash_id
+			));
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), 0);
+			assert_eq!(Balances::free_balance(REGISTER_RELAYER), free_balance - Stake::get() / 2);
+			assert_eq!(
+				Balances::free_balance(REGULAR_RELAYER),
+				ExistentialDeposit::get() + Stake::get() / 2
+			);
+		});
+	}
+
+	#[test]
+	fn pending_slash_is_applied_after_appeal_period() {
+		run_test(|| {
+			SlashAppealPeriod::set(&10);
+			System::<TestRuntime>::set_block_number(1);
+
+			let slash_id = register_and_slash(RelayerOffence::FailedMessagesDelivery);
+			assert_eq!(
+				Slashes::<TestRuntime>::get(REGISTER_RELAYER, slash_id).map(|slash| slash.status),
+				Some(SlashStatus::Pending { appeal_till: 11 }),
+			);
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), Stake::get());
+
+			System::<TestRuntime>::set_block_number(11);
+			assert_noop!(
+				Pallet::<TestRuntime>::apply_slash(
+					RuntimeOrigin::signed(REGULAR_RELAYER),
+					REGISTER_RELAYER,
+					slash_id
+				),
+				Error::<TestRuntime>::SlashAppealPeriodIsNotOver,
+			);
+
+			System::<TestRuntime>::set_block_number(12);
+			assert_noop!(
+				Pallet::<TestRuntime>::appeal_slash(
+					RuntimeOrigin::signed(REGISTER_RELAYER),
+					slash_id
+				),
+				Error::<TestRuntime>::SlashAppealPeriodIsOver,
+			);
+			assert_ok!(Pallet::<TestRuntime>::apply_slash(
+				RuntimeOrigin::signed(REGULAR_RELAYER),
+				REGISTER_RELAYER,
+				slash_id
+			));
+			assert_eq!(
+				Slashes::<TestRuntime>::get(REGISTER_RELAYER, slash_id).map(|slash| slash.status),
+				Some(SlashStatus::Applied),
+			);
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), 0);
+			assert_eq!(
+				Balances::free_balance(REGULAR_RELAYER),
+				ExistentialDeposit::get() + Stake::get()
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::apply_slash(
+					RuntimeOrigin::signed(REGULAR_RELAYER),
+					REGISTER_RELAYER,
+					slash_id
+				),
+				Error::<TestRuntime>::SlashIsNotPending,
+			);
+		});
+	}
+
+	#[test]
+	fn appealed_slash_may_be_cancelled() {
+		run_test(|| {
+			SlashAppealPeriod::set(&10);
+			System::<TestRuntime>::set_block_number(1);
+
+			let free_balance = Balances::free_balance(REGISTER_RELAYER);
+			let slash_id = register_and_slash(RelayerOffence::FailedMessagesDelivery);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::appeal_slash(
+					RuntimeOrigin::signed(REGULAR_RELAYER),
+					slash_id
+				),
+				Error::<TestRuntime>::UnknownSlash,
+			);
+			assert_ok!(Pallet::<TestRuntime>::appeal_slash(
+				RuntimeOrigin::signed(REGISTER_RELAYER),
+				slash_id
+			));
+			assert_eq!(
+				Slashes::<TestRuntime>::get(REGISTER_RELAYER, slash_id).map(|slash| slash.status),
+				Some(SlashStatus::Appealed),
+			);
+
+			// appealed slash is not applied after appeal period
+			System::<TestRuntime>::set_block_number(12);
+			assert_noop!(
+				Pallet::<TestRuntime>::apply_slash(
+					RuntimeOrigin::signed(REGULAR_RELAYER),
+					REGISTER_RELAYER,
+					slash_id
+				),
+				Error::<TestRuntime>::SlashIsNotPending,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::resolve_slash_appeal(
+					RuntimeOrigin::signed(REGISTER_RELAYER),
+					REGISTER_RELAYER,
+					slash_id,
+					true
+				),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::resolve_slash_appeal(
+				RuntimeOrigin::root(),
+				REGISTER_RELAYER,
+				slash_id,
+				true
+			));
+			assert_eq!(
+				Slashes::<TestRuntime>::get(REGISTER_RELAYER, slash_id).map(|slash| slash.status),
+				Some(SlashStatus::Cancelled),
+			);
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), 0);
+			assert_eq!(Balances::free_balance(REGISTER_RELAYER), free_balance);
+			assert_eq!(Balances::free_balance(REGULAR_RELAYER), ExistentialDeposit::get());
+
+			assert_noop!(
+				Pallet::<TestRuntime>::resolve_slash_appeal(
+					RuntimeOrigin::root(),
+					REGISTER_RELAYER,
+					slash_id,
+					false
+				),
+				Error::<TestRuntime>::SlashIsNotPending,
+			);
+		});
+	}
+
+	#[test]
+	fn only_latest_resolved_slashes_are_recorded() {
+		run_test(|| {
+			// slash #0 is pending
+			SlashAppealPeriod::set(&10);
+			System::<TestRuntime>::set_block_number(1);
+			let pending_slash_id = register_and_slash(RelayerOffence::FailedMessagesDelivery);
+
+			// slashes #1, #2 and #3 are applied immediately
+			SlashAppealPeriod::set(&0);
+			let slash_ids = (0..3)
+				.map(|_| register_and_slash(RelayerOffence::FailedMessagesDelivery))
+				.collect::<Vec<_>>();
+
+			// record of the slash #1 is pruned and the pending slash is kept
+			assert_eq!(
+				Pallet::<TestRuntime>::relayer_slashes(&REGISTER_RELAYER)
+					.into_iter()
+					.map(|(slash_id, slash)| (slash_id, slash.status))
+					.collect::<Vec<_>>(),
+				vec![
+					(pending_slash_id, SlashStatus::Pending { appeal_till: 11 }),
+					(slash_ids[1], SlashStatus::Applied),
+					(slash_ids[2], SlashStatus::Applied),
+				],
+			);
+			assert!(!SlashOwners::<TestRuntime>::contains_key(slash_ids[0]));
+
+			// record of the pending slash is removed when it is resolved
+			System::<TestRuntime>::set_block_number(12);
+			assert_ok!(Pallet::<TestRuntime>::apply_slash(
+				RuntimeOrigin::signed(REGULAR_RELAYER),
+				REGISTER_RELAYER,
+				pending_slash_id
+			));
+			assert!(Slashes::<TestRuntime>::get(REGISTER_RELAYER, pending_slash_id).is_none());
+			assert!(!SlashOwners::<TestRuntime>::contains_key(pending_slash_id));
+		});
+	}
 }
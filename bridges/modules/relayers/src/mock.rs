@@ -25,7 +25,8 @@ use bp_messages::{
 };
 use bp_parachains::SingleParaStoredHeaderDataBuilder;
 use bp_relayers::{
-	PayRewardFromAccount, PaymentProcedure, RewardsAccountOwner, RewardsAccountParams,
+	PayRewardFromAccount, PaymentProcedure, RelayerOffence, RewardsAccountOwner,
+	RewardsAccountParams,
 };
 use bp_runtime::{messages::MessageDispatchResult, Chain, ChainId, Parachain};
 use codec::Encode;
@@ -34,10 +35,11 @@ use frame_support::{
 	traits::fungible::Mutate,
 	weights::{ConstantMultiplier, IdentityFee, RuntimeDbWeight, Weight},
 };
+use frame_system::EnsureRoot;
 use pallet_transaction_payment::Multiplier;
 use sp_core::{ConstU64, ConstU8, H256};
 use sp_runtime::{
-	traits::{BlakeTwo256, ConstU32},
+	traits::{BlakeTwo256, ConstU32, Convert},
 	BuildStorage, FixedPointNumber, Perquintill, StateVersion,
 };
 
@@ -195,6 +197,7 @@ parameter_types! {
 	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
 	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000u128);
 	pub MaximumMultiplier: Multiplier = sp_runtime::traits::Bounded::max_value();
+	pub storage SlashAppealPeriod: ThisChainBlockNumber = 0;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
@@ -284,6 +287,10 @@ impl pallet_bridge_relayers::Config for TestRuntime {
 	type StakeAndSlash = TestStakeAndSlash;
 	type WeightInfo = ();
 	type LaneId = TestLaneIdType;
+	type SlashAmount = TestSlashAmount;
+	type SlashAppealPeriod = SlashAppealPeriod;
+	type SlashAppealOrigin = EnsureRoot<ThisChainAccountId>;
+	type SlashRecordsToKeep = ConstU32<2>;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -339,6 +346,19 @@ impl PaymentProcedure<ThisChainAccountId, ThisChainBalance> for TestPaymentProce
 	}
 }
 
+/// Slash amount provider that slashes half of the stake for invalid finality proofs and the
+/// whole stake for other offences.
+pub struct TestSlashAmount;
+
+impl Convert<RelayerOffence, Option<ThisChainBalance>> for TestSlashAmount {
+	fn convert(offence: RelayerOffence) -> Option<ThisChainBalance> {
+		match offence {
+			RelayerOffence::InvalidFinalityProof => Some(Stake::get() / 2),
+			_ => None,
+		}
+	}
+}
+
 /// Dummy message dispatcher.
 pub struct DummyMessageDispatch;
 
@@ -14,28 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Autogenerated weights for pallet_bridge_relayers
+//! Weights for pallet_bridge_relayers
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-28, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `covid`, CPU: `11th Gen Intel(R) Core(TM) i7-11800H @ 2.30GHz`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// target/release/rip-bridge-node
-// benchmark
-// pallet
-// --chain=dev
-// --steps=50
-// --repeat=20
-// --pallet=pallet_bridge_relayers
-// --extrinsic=*
-// --execution=wasm
-// --wasm-execution=Compiled
-// --heap-pages=4096
-// --output=./modules/relayers/src/weights.rs
-// --template=./.maintain/bridge-weight-template.hbs
+//! Weights of `claim_rewards`, `register`, `deregister` and `register_relayer_reward` have been
+//! generated by the benchmark CLI on 2023-04-28 (STEPS: `50`, REPEAT: `20`, CHAIN: `dev`).
+//! Storage accesses and proof sizes of `slash_and_deregister`, `appeal_slash`,
+//! `resolve_slash_appeal` and `apply_slash` follow their benchmarks, but execution times of
+//! these calls have not been measured yet.
 
 #![allow(clippy::all)]
 #![allow(unused_parens)]
@@ -55,6 +40,9 @@ pub trait WeightInfo {
 	fn deregister() -> Weight;
 	fn slash_and_deregister() -> Weight;
 	fn register_relayer_reward() -> Weight;
+	fn appeal_slash() -> Weight;
+	fn resolve_slash_appeal() -> Weight;
+	fn apply_slash() -> Weight;
 }
 
 /// Weights for `pallet_bridge_relayers` that are generated using one of the Bridge testnets.
@@ -126,23 +114,36 @@ impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
 	/// Proof: BridgeRelayers RegisteredRelayers (max_values: None, max_size: Some(64), added: 2539,
 	/// mode: MaxEncodedLen)
 	///
+	/// Storage: BridgeRelayers NextSlashId (r:1 w:1)
+	///
+	/// Proof: BridgeRelayers NextSlashId (max_values: Some(1), max_size: Some(8), added: 503,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers SlashOwners (r:1 w:2)
+	///
+	/// Proof: BridgeRelayers SlashOwners (max_values: None, max_size: Some(40), added: 2515,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers Slashes (r:1 w:2)
+	///
+	/// Proof: BridgeRelayers Slashes (max_values: None, max_size: Some(165), added: 2640,
+	/// mode: MaxEncodedLen)
+	///
 	/// Storage: Balances Reserves (r:1 w:1)
 	///
 	/// Proof: Balances Reserves (max_values: None, max_size: Some(849), added: 3324, mode:
 	/// MaxEncodedLen)
 	///
-	/// Storage: System Account (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
 	///
 	/// Proof: System Account (max_values: None, max_size: Some(104), added: 2579, mode:
 	/// MaxEncodedLen)
 	fn slash_and_deregister() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `380`
-		//  Estimated: `11412`
-		// Minimum execution time: 42_358 nanoseconds.
-		Weight::from_parts(43_539_000, 11412)
-			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+		//  Estimated: `6148`
+		Weight::from_parts(62_000_000, 6148)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
 	/// Storage: BridgeRelayers RelayerRewards (r:1 w:1)
 	///
@@ -157,6 +158,79 @@ impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: BridgeRelayers Slashes (r:1 w:1)
+	///
+	/// Proof: BridgeRelayers Slashes (max_values: None, max_size: Some(165), added: 2640,
+	/// mode: MaxEncodedLen)
+	fn appeal_slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `3630`
+		Weight::from_parts(16_000_000, 3630)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: BridgeRelayers Slashes (r:1 w:1)
+	///
+	/// Proof: BridgeRelayers Slashes (max_values: None, max_size: Some(165), added: 2640,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers NextSlashId (r:1 w:0)
+	///
+	/// Proof: BridgeRelayers NextSlashId (max_values: Some(1), max_size: Some(8), added: 503,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers SlashOwners (r:0 w:1)
+	///
+	/// Proof: BridgeRelayers SlashOwners (max_values: None, max_size: Some(40), added: 2515,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: Balances Reserves (r:1 w:1)
+	///
+	/// Proof: Balances Reserves (max_values: None, max_size: Some(849), added: 3324, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:2 w:2)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(104), added: 2579, mode:
+	/// MaxEncodedLen)
+	fn resolve_slash_appeal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `6148`
+		Weight::from_parts(58_000_000, 6148)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: BridgeRelayers Slashes (r:1 w:1)
+	///
+	/// Proof: BridgeRelayers Slashes (max_values: None, max_size: Some(165), added: 2640,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers NextSlashId (r:1 w:0)
+	///
+	/// Proof: BridgeRelayers NextSlashId (max_values: Some(1), max_size: Some(8), added: 503,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers SlashOwners (r:0 w:1)
+	///
+	/// Proof: BridgeRelayers SlashOwners (max_values: None, max_size: Some(40), added: 2515,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: Balances Reserves (r:1 w:1)
+	///
+	/// Proof: Balances Reserves (max_values: None, max_size: Some(849), added: 3324, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:2 w:2)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(104), added: 2579, mode:
+	/// MaxEncodedLen)
+	fn apply_slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `6148`
+		Weight::from_parts(58_000_000, 6148)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -225,23 +299,36 @@ impl WeightInfo for () {
 	/// Proof: BridgeRelayers RegisteredRelayers (max_values: None, max_size: Some(64), added: 2539,
 	/// mode: MaxEncodedLen)
 	///
+	/// Storage: BridgeRelayers NextSlashId (r:1 w:1)
+	///
+	/// Proof: BridgeRelayers NextSlashId (max_values: Some(1), max_size: Some(8), added: 503,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers SlashOwners (r:1 w:2)
+	///
+	/// Proof: BridgeRelayers SlashOwners (max_values: None, max_size: Some(40), added: 2515,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers Slashes (r:1 w:2)
+	///
+	/// Proof: BridgeRelayers Slashes (max_values: None, max_size: Some(165), added: 2640,
+	/// mode: MaxEncodedLen)
+	///
 	/// Storage: Balances Reserves (r:1 w:1)
 	///
 	/// Proof: Balances Reserves (max_values: None, max_size: Some(849), added: 3324, mode:
 	/// MaxEncodedLen)
 	///
-	/// Storage: System Account (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
 	///
 	/// Proof: System Account (max_values: None, max_size: Some(104), added: 2579, mode:
 	/// MaxEncodedLen)
 	fn slash_and_deregister() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `380`
-		//  Estimated: `11412`
-		// Minimum execution time: 42_358 nanoseconds.
-		Weight::from_parts(43_539_000, 11412)
-			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+		//  Estimated: `6148`
+		Weight::from_parts(62_000_000, 6148)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
 	}
 	/// Storage: BridgeRelayers RelayerRewards (r:1 w:1)
 	///
@@ -256,4 +343,77 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: BridgeRelayers Slashes (r:1 w:1)
+	///
+	/// Proof: BridgeRelayers Slashes (max_values: None, max_size: Some(165), added: 2640,
+	/// mode: MaxEncodedLen)
+	fn appeal_slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `3630`
+		Weight::from_parts(16_000_000, 3630)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: BridgeRelayers Slashes (r:1 w:1)
+	///
+	/// Proof: BridgeRelayers Slashes (max_values: None, max_size: Some(165), added: 2640,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers NextSlashId (r:1 w:0)
+	///
+	/// Proof: BridgeRelayers NextSlashId (max_values: Some(1), max_size: Some(8), added: 503,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers SlashOwners (r:0 w:1)
+	///
+	/// Proof: BridgeRelayers SlashOwners (max_values: None, max_size: Some(40), added: 2515,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: Balances Reserves (r:1 w:1)
+	///
+	/// Proof: Balances Reserves (max_values: None, max_size: Some(849), added: 3324, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:2 w:2)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(104), added: 2579, mode:
+	/// MaxEncodedLen)
+	fn resolve_slash_appeal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `6148`
+		Weight::from_parts(58_000_000, 6148)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: BridgeRelayers Slashes (r:1 w:1)
+	///
+	/// Proof: BridgeRelayers Slashes (max_values: None, max_size: Some(165), added: 2640,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers NextSlashId (r:1 w:0)
+	///
+	/// Proof: BridgeRelayers NextSlashId (max_values: Some(1), max_size: Some(8), added: 503,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeRelayers SlashOwners (r:0 w:1)
+	///
+	/// Proof: BridgeRelayers SlashOwners (max_values: None, max_size: Some(40), added: 2515,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: Balances Reserves (r:1 w:1)
+	///
+	/// Proof: Balances Reserves (max_values: None, max_size: Some(849), added: 3324, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:2 w:2)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(104), added: 2579, mode:
+	/// MaxEncodedLen)
+	fn apply_slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `6148`
+		Weight::from_parts(58_000_000, 6148)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
 }
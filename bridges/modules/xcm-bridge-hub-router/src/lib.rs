@@ -30,7 +30,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-pub use bp_xcm_bridge_hub_router::XcmChannelStatusProvider;
+pub use bp_xcm_bridge_hub_router::{BridgeState, XcmChannelStatusProvider};
 use codec::Encode;
 use frame_support::traits::Get;
 use sp_runtime::{FixedPointNumber, FixedU128, Saturating};
@@ -187,6 +187,16 @@ pub mod pallet {
 		StorageValue<_, FixedU128, ValueQuery, InitialFactor>;
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Returns current state of the bridge, served by this pallet.
+		pub fn bridge_state() -> BridgeState {
+			BridgeState {
+				delivery_fee_factor: Self::delivery_fee_factor(),
+				is_congested: T::LocalXcmChannelManager::is_congested(
+					&T::SiblingBridgeHubLocation::get(),
+				),
+			}
+		}
+
 		/// Called when new message is sent (queued to local outbound XCM queue) over the bridge.
 		pub(crate) fn on_message_sent_to_bridge(message_size: u32) {
 			// if outbound channel is not congested, do nothing
@@ -434,6 +444,23 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn bridge_state_reports_fee_factor_and_congestion() {
+		run_test(|| {
+			assert_eq!(XcmBridgeHubRouter::bridge_state(), BridgeState::default());
+
+			DeliveryFeeFactor::<TestRuntime, ()>::put(FixedU128::from_rational(125, 100));
+			TestLocalXcmChannelManager::make_congested(&SiblingBridgeHubLocation::get());
+			assert_eq!(
+				XcmBridgeHubRouter::bridge_state(),
+				BridgeState {
+					delivery_fee_factor: FixedU128::from_rational(125, 100),
+					is_congested: true,
+				},
+			);
+		})
+	}
+
 	#[test]
 	fn fee_factor_is_not_decreased_from_on_initialize_when_xcm_channel_is_congested() {
 		run_test(|| {
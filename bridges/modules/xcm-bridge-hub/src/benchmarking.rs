@@ -0,0 +1,219 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! XCM bridge hub pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{exporter::max_confirmed_messages, *};
+
+use bp_messages::OutboundLaneData;
+use frame_benchmarking::{account, benchmarks_instance_pallet, BenchmarkError};
+use frame_support::traits::{
+	fungible::{Inspect, Unbalanced},
+	tokens::Precision,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_bridge_messages::StoredMessagePayload;
+
+/// Lane id to use in benchmarks.
+fn bench_lane_id<T: Config<I>, I: 'static>() -> T::LaneId {
+	T::LaneId::default()
+}
+
+/// Insert closed bridge with given number of queued outbound messages.
+fn prepare_closed_bridge<T: Config<I>, I: 'static>(
+	messages: MessageNonce,
+	closed_at: BlockNumberFor<T>,
+) -> (BridgeId, BridgeOf<T, I>) {
+	let lane_id = bench_lane_id::<T, I>();
+	let bridge_origin_relative_location = Location::new(1, [Parachain(1000)]);
+	let bridge_origin_universal_location: InteriorLocation =
+		[GlobalConsensus(ByGenesis([0; 32])), Parachain(1000)].into();
+	let bridge_destination_universal_location: InteriorLocation =
+		[GlobalConsensus(ByGenesis([1; 32])), Parachain(1000)].into();
+	let bridge_id =
+		BridgeId::new(&bridge_origin_universal_location, &bridge_destination_universal_location);
+	let bridge = BridgeOf::<T, I> {
+		bridge_origin_relative_location: Box::new(bridge_origin_relative_location.into()),
+		bridge_origin_universal_location: Box::new(bridge_origin_universal_location.into()),
+		bridge_destination_universal_location: Box::new(
+			bridge_destination_universal_location.into(),
+		),
+		state: BridgeState::Closed,
+		bridge_owner_account: account("bridge_owner", 0, 0),
+		deposit: Zero::zero(),
+		lane_id,
+	};
+	Bridges::<T, I>::insert(bridge_id, bridge.clone());
+	LaneToBridge::<T, I>::insert(lane_id, bridge_id);
+	ClosingBridges::<T, I>::insert(bridge_id, closed_at);
+
+	let lanes_manager = LanesManagerOf::<T, I>::new();
+	lanes_manager.create_inbound_lane(lane_id).expect("lane is not yet created");
+	let mut outbound_lane =
+		lanes_manager.create_outbound_lane(lane_id).expect("lane is not yet created");
+	for _ in 0..messages {
+		let nonce = outbound_lane.send_message(
+			StoredMessagePayload::<T, T::BridgeMessagesPalletInstance>::try_from(sp_std::vec![42])
+				.expect("message is small enough"),
+		);
+		Pallet::<T, I>::note_enqueued_message(lane_id, nonce);
+	}
+	outbound_lane.set_state(LaneState::Closed);
+
+	(bridge_id, bridge)
+}
+
+/// Returns origin, that is allowed to open bridges and bridge locations of the bridge that it
+/// may open. The bridge owner account has enough funds to pay the bridge deposit.
+fn prepare_bridge_opener<T: Config<I>, I: 'static>(
+) -> Result<(T::RuntimeOrigin, Box<VersionedInteriorLocation>, Box<BridgeLocations>), BenchmarkError>
+{
+	let origin = T::BenchmarkHelper::open_bridge_origin().ok_or(BenchmarkError::Weightless)?;
+	let bridge_destination_universal_location: InteriorLocation =
+		[GlobalConsensus(Pallet::<T, I>::bridged_network_id()?), Parachain(2075)].into();
+	let bridge_destination_universal_location =
+		Box::new(VersionedInteriorLocation::from(bridge_destination_universal_location));
+	let locations = Pallet::<T, I>::bridge_locations_from_origin(
+		origin.clone(),
+		bridge_destination_universal_location.clone(),
+	)?;
+
+	let bridge_owner_account = T::BridgeOriginAccountIdConverter::convert_location(
+		locations.bridge_origin_relative_location(),
+	)
+	.ok_or(BenchmarkError::Stop("Failed to convert bridge origin location to account"))?;
+	let balance = T::BridgeDeposit::get()
+		.saturating_add(T::Currency::minimum_balance())
+		.saturating_mul(2u32.into());
+	T::Currency::increase_balance(&bridge_owner_account, balance, Precision::BestEffort)?;
+
+	Ok((origin, bridge_destination_universal_location, locations))
+}
+
+benchmarks_instance_pallet! {
+	open_bridge {
+		let (origin, bridge_destination_universal_location, locations) =
+			prepare_bridge_opener::<T, I>()?;
+	}: _<T::RuntimeOrigin>(origin, bridge_destination_universal_location)
+	verify {
+		assert_eq!(
+			Pallet::<T, I>::bridge(locations.bridge_id()).map(|bridge| bridge.state),
+			Some(BridgeState::Opening),
+		);
+	}
+
+	// Benchmark closing the bridge with `n` queued outbound messages. All messages are pruned
+	// and the bridge is removed from the storage.
+	close_bridge {
+		let n in 0 .. 1024;
+
+		let (origin, bridge_destination_universal_location, locations) =
+			prepare_bridge_opener::<T, I>()?;
+		let lane_id = locations
+			.calculate_lane_id(xcm::latest::VERSION)
+			.map_err(|_| BenchmarkError::Stop("Failed to compute lane id"))?;
+		Pallet::<T, I>::do_open_bridge(locations.clone(), lane_id, true)?;
+
+		let mut outbound_lane = LanesManagerOf::<T, I>::new()
+			.active_outbound_lane(lane_id)
+			.map_err(|_| BenchmarkError::Stop("Outbound lane is not opened"))?;
+		for _ in 0..n {
+			let nonce = outbound_lane.send_message(
+				StoredMessagePayload::<T, T::BridgeMessagesPalletInstance>::try_from(
+					sp_std::vec![42],
+				)
+				.expect("message is small enough"),
+			);
+			Pallet::<T, I>::note_enqueued_message(lane_id, nonce);
+		}
+	}: _<T::RuntimeOrigin>(origin, bridge_destination_universal_location, n as MessageNonce)
+	verify {
+		assert!(!Bridges::<T, I>::contains_key(locations.bridge_id()));
+		assert!(!ClosingBridges::<T, I>::contains_key(locations.bridge_id()));
+	}
+
+	note_enqueued_message {
+		let lane_id = bench_lane_id::<T, I>();
+	}: {
+		Pallet::<T, I>::note_enqueued_message(lane_id, 1);
+	}
+	verify {
+		assert!(OutboundMessagesEnqueuedAt::<T, I>::contains_key(lane_id, 1));
+	}
+
+	// Benchmark `forget_delivered_messages` when `n` messages are confirmed.
+	//
+	// Every confirmed message causes a read of small storage entry, so the `MaxEncodedLen` proof
+	// size estimation would be too pessimistic here.
+	#[pov_mode = Measured]
+	forget_delivered_messages {
+		let n in 1 .. max_confirmed_messages::<T, I>() as u32;
+
+		let lane_id = bench_lane_id::<T, I>();
+		let latest_received_nonce = n as MessageNonce;
+		pallet_bridge_messages::OutboundLanes::<T, T::BridgeMessagesPalletInstance>::insert(
+			lane_id,
+			OutboundLaneData {
+				oldest_unpruned_nonce: 1,
+				latest_received_nonce,
+				latest_generated_nonce: latest_received_nonce,
+				state: LaneState::Opened,
+			},
+		);
+		for nonce in 1..=latest_received_nonce {
+			Pallet::<T, I>::note_enqueued_message(lane_id, nonce);
+		}
+	}: {
+		Pallet::<T, I>::forget_delivered_messages(lane_id);
+	}
+	verify {
+		assert!(!OutboundMessagesEnqueuedAt::<T, I>::contains_key(lane_id, latest_received_nonce));
+	}
+
+	// Benchmark `is_closing_bridge_drained` check for the bridge that is not yet drained.
+	#[pov_mode = Measured]
+	check_closing_bridge {
+		prepare_closed_bridge::<T, I>(1, frame_system::Pallet::<T>::block_number());
+	}: {
+		let (bridge_id, closed_at) =
+			ClosingBridges::<T, I>::iter().next().expect("bridge is closing");
+		let bridge = Pallet::<T, I>::bridge(&bridge_id).expect("bridge exists");
+		assert!(!Pallet::<T, I>::is_closing_bridge_drained(&bridge, closed_at));
+	}
+
+	// Benchmark pruning of the closed bridge with `n` undelivered messages.
+	#[pov_mode = Measured]
+	prune_closing_bridge {
+		let n in 0 .. 1024;
+
+		let (bridge_id, bridge) = prepare_closed_bridge::<T, I>(n as MessageNonce, Zero::zero());
+		frame_system::Pallet::<T>::set_block_number(T::ClosingBridgeDrainPeriod::get());
+	}: {
+		assert!(Pallet::<T, I>::is_closing_bridge_drained(&bridge, Zero::zero()));
+		assert_eq!(
+			Pallet::<T, I>::do_prune_closed_bridge(bridge_id, bridge, MessageNonce::MAX),
+			Ok(n as MessageNonce),
+		);
+	}
+	verify {
+		assert!(!Bridges::<T, I>::contains_key(bridge_id));
+		assert!(!ClosingBridges::<T, I>::contains_key(bridge_id));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime)
+}
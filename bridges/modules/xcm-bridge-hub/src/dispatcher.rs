@@ -25,7 +25,7 @@ use crate::{Config, Pallet, LOG_TARGET};
 
 use bp_messages::target_chain::{DispatchMessage, MessageDispatch};
 use bp_runtime::messages::MessageDispatchResult;
-use bp_xcm_bridge_hub::{LocalXcmChannelManager, XcmAsPlainPayload};
+use bp_xcm_bridge_hub::{BridgeHandshake, BridgeState, LocalXcmChannelManager, XcmAsPlainPayload};
 use codec::{Decode, Encode};
 use frame_support::{weights::Weight, CloneNoBound, EqNoBound, PartialEqNoBound};
 use pallet_bridge_messages::{Config as BridgeMessagesConfig, WeightInfoExt};
@@ -76,6 +76,8 @@ where
 		}
 	}
 
+	/// Dispatches the handshake of the bridged side if the bridge is `Opening`. Otherwise, the
+	/// message is passed to the XCM-level blob dispatcher.
 	fn dispatch(
 		message: DispatchMessage<Self::DispatchPayload, Self::LaneId>,
 	) -> MessageDispatchResult<Self::DispatchLevelResult> {
@@ -94,6 +96,31 @@ where
 				}
 			},
 		};
+
+		// the first message of the `Opening` bridge is the handshake of the bridged side
+		if let Some((bridge_id, bridge)) = Pallet::<T, I>::bridge_by_lane_id(&message.key.lane_id)
+			.filter(|(_, bridge)| bridge.state == BridgeState::Opening)
+		{
+			let dispatch_level_result = match BridgeHandshake::decode(&mut &payload[..]) {
+				Ok(handshake) =>
+					if Pallet::<T, I>::receive_bridge_handshake(bridge_id, bridge, handshake) {
+						XcmBlobMessageDispatchResult::Dispatched
+					} else {
+						XcmBlobMessageDispatchResult::NotDispatched(None)
+					},
+				Err(e) => {
+					log::error!(
+						target: LOG_TARGET,
+						"dispatch - failed to decode bridge handshake: {e:?} for lane_id: {:?} and message_nonce: {:?}",
+						message.key.lane_id,
+						message.key.nonce
+					);
+					XcmBlobMessageDispatchResult::InvalidPayload
+				},
+			};
+			return MessageDispatchResult { unspent_weight: Weight::zero(), dispatch_level_result }
+		}
+
 		let dispatch_level_result = match T::BlobDispatcher::dispatch_blob(payload) {
 			Ok(_) => {
 				log::debug!(
@@ -121,10 +148,10 @@ where
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{mock::*, Bridges, LaneToBridge, LanesManagerOf};
+	use crate::{mock::*, Bridges, Event, LaneToBridge, LanesManagerOf};
 
 	use bp_messages::{target_chain::DispatchMessageData, LaneIdType, MessageKey};
-	use bp_xcm_bridge_hub::{Bridge, BridgeLocations, BridgeState};
+	use bp_xcm_bridge_hub::{Bridge, BridgeLocations};
 	use frame_support::assert_ok;
 	use pallet_bridge_messages::InboundLaneStorage;
 	use xcm_executor::traits::ConvertLocation;
@@ -251,6 +278,107 @@ mod tests {
 		});
 	}
 
+	fn handshake_message(handshake: BridgeHandshake) -> DispatchMessage<Vec<u8>, TestLaneIdType> {
+		DispatchMessage {
+			key: MessageKey { lane_id: bridge().1, nonce: 1 },
+			data: DispatchMessageData { payload: Ok(handshake.encode()) },
+		}
+	}
+
+	fn bridged_handshake() -> BridgeHandshake {
+		let (bridge, _) = bridge();
+		BridgeHandshake {
+			bridge_origin_universal_location: Box::new(
+				bridge.bridge_destination_universal_location().clone().into(),
+			),
+			bridge_destination_universal_location: Box::new(
+				bridge.bridge_origin_universal_location().clone().into(),
+			),
+		}
+	}
+
+	fn run_test_with_opening_bridge(test: impl FnOnce()) {
+		run_test_with_opened_bridge(|| {
+			Bridges::<TestRuntime, ()>::mutate_extant(bridge().0.bridge_id(), |bridge| {
+				bridge.state = BridgeState::Opening;
+			});
+			test();
+		});
+	}
+
+	#[test]
+	fn handshake_of_bridged_side_opens_bridge() {
+		run_test_with_opening_bridge(|| {
+			System::set_block_number(1);
+			System::reset_events();
+
+			assert_eq!(
+				XcmOverBridge::dispatch(handshake_message(bridged_handshake())),
+				MessageDispatchResult {
+					unspent_weight: Weight::zero(),
+					dispatch_level_result: XcmBlobMessageDispatchResult::Dispatched,
+				},
+			);
+			assert!(!TestBlobDispatcher::is_dispatched());
+			assert_eq!(
+				Bridges::<TestRuntime, ()>::get(bridge().0.bridge_id()).map(|b| b.state),
+				Some(BridgeState::Opened),
+			);
+			assert_eq!(
+				System::events().last().map(|r| r.event.clone()),
+				Some(RuntimeEvent::XcmOverBridge(Event::BridgeReady {
+					bridge_id: *bridge().0.bridge_id(),
+					lane_id: bridge().1,
+				})),
+			);
+		});
+	}
+
+	#[test]
+	fn handshake_with_unexpected_endpoints_is_not_dispatched() {
+		run_test_with_opening_bridge(|| {
+			// the handshake that we have sent ourselves is not valid at our side
+			let mut handshake = bridged_handshake();
+			core::mem::swap(
+				&mut handshake.bridge_origin_universal_location,
+				&mut handshake.bridge_destination_universal_location,
+			);
+
+			assert_eq!(
+				XcmOverBridge::dispatch(handshake_message(handshake)),
+				MessageDispatchResult {
+					unspent_weight: Weight::zero(),
+					dispatch_level_result: XcmBlobMessageDispatchResult::NotDispatched(None),
+				},
+			);
+			assert_eq!(
+				Bridges::<TestRuntime, ()>::get(bridge().0.bridge_id()).map(|b| b.state),
+				Some(BridgeState::Opening),
+			);
+		});
+	}
+
+	#[test]
+	fn invalid_handshake_is_not_dispatched() {
+		run_test_with_opening_bridge(|| {
+			let mut message = handshake_message(bridged_handshake());
+			message.data.payload = Ok(vec![42]);
+
+			assert_eq!(
+				XcmOverBridge::dispatch(message),
+				MessageDispatchResult {
+					unspent_weight: Weight::zero(),
+					dispatch_level_result: XcmBlobMessageDispatchResult::InvalidPayload,
+				},
+			);
+			assert!(!TestBlobDispatcher::is_dispatched());
+			assert_eq!(
+				Bridges::<TestRuntime, ()>::get(bridge().0.bridge_id()).map(|b| b.state),
+				Some(BridgeState::Opening),
+			);
+		});
+	}
+
 	#[test]
 	fn message_is_dispatched_when_we_have_decoded_message() {
 		run_test(|| {
@@ -20,18 +20,18 @@
 //!
 //! This code is executed at the source bridge hub.
 
-use crate::{Config, Pallet, LOG_TARGET};
+use crate::{Config, Pallet, WeightInfo, LOG_TARGET};
 
-use crate::{BridgeOf, Bridges};
+use crate::{BridgeOf, Bridges, OutboundMessagesEnqueuedAt};
 
 use bp_messages::{
 	source_chain::{MessagesBridge, OnMessagesDelivered},
-	MessageNonce,
+	ChainWithMessages, MessageNonce,
 };
 use bp_xcm_bridge_hub::{BridgeId, BridgeState, LocalXcmChannelManager, XcmAsPlainPayload};
-use frame_support::{ensure, traits::Get};
+use frame_support::{ensure, traits::Get, weights::Weight};
 use pallet_bridge_messages::{
-	Config as BridgeMessagesConfig, Error, Pallet as BridgeMessagesPallet,
+	BridgedChainOf, Config as BridgeMessagesConfig, Error, Pallet as BridgeMessagesPallet,
 };
 use xcm::prelude::*;
 use xcm_builder::{HaulBlob, HaulBlobError, HaulBlobExporter};
@@ -55,6 +55,11 @@ pub type PalletAsHaulBlobExporter<T, I> = HaulBlobExporter<
 /// An easy way to access associated messages pallet.
 type MessagesPallet<T, I> = BridgeMessagesPallet<T, <T as Config<I>>::BridgeMessagesPalletInstance>;
 
+/// Maximal number of messages that may be confirmed by a single delivery confirmation.
+pub(crate) fn max_confirmed_messages<T: Config<I>, I: 'static>() -> MessageNonce {
+	BridgedChainOf::<T, T::BridgeMessagesPalletInstance>::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX
+}
+
 impl<T: Config<I>, I: 'static> ExportXcm for Pallet<T, I>
 where
 	T: BridgeMessagesConfig<T::BridgeMessagesPalletInstance, OutboundPayload = XcmAsPlainPayload>,
@@ -139,6 +144,16 @@ where
 			);
 			SendError::NotApplicable
 		})?;
+		// messages may not be exported until the bridged side opens the bridge too
+		if bridge.state == BridgeState::Opening {
+			log::error!(
+				target: LOG_TARGET,
+				"The bridge {:?} is not opened by the bridged side yet, lane_id: {:?}",
+				locations.bridge_id(),
+				bridge.lane_id,
+			);
+			return Err(SendError::Transport("BridgeIsOpening"))
+		}
 
 		// check if we are able to route the message. We use existing `HaulBlobExporter` for that.
 		// It will make all required changes and will encode message properly, so that the
@@ -191,6 +206,9 @@ where
 			artifacts.nonce,
 		);
 
+		// remember when the message has been enqueued
+		Self::note_enqueued_message(bridge.lane_id, artifacts.nonce);
+
 		// maybe we need switch to congested state
 		Self::on_bridge_message_enqueued(bridge_id, bridge, artifacts.enqueued_messages);
 
@@ -200,6 +218,7 @@ where
 
 impl<T: Config<I>, I: 'static> OnMessagesDelivered<T::LaneId> for Pallet<T, I> {
 	fn on_messages_delivered(lane_id: T::LaneId, enqueued_messages: MessageNonce) {
+		Self::forget_delivered_messages(lane_id);
 		Self::on_bridge_messages_delivered(lane_id, enqueued_messages);
 	}
 }
@@ -272,6 +291,56 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		});
 	}
 
+	/// Returns weight of the bookkeeping that we perform when message is exported.
+	///
+	/// It must be added to the weight of the `ExportMessage` instruction.
+	pub fn message_exported_weight() -> Weight {
+		T::WeightInfo::note_enqueued_message()
+	}
+
+	/// Returns weight of the bookkeeping that we perform when delivery of given number of
+	/// messages is confirmed.
+	///
+	/// It must be added to the weight of the messages delivery confirmation transaction.
+	pub fn messages_delivered_weight(messages: MessageNonce) -> Weight {
+		let messages = sp_std::cmp::min(messages, max_confirmed_messages::<T, I>());
+		T::WeightInfo::forget_delivered_messages(messages.try_into().unwrap_or(u32::MAX))
+	}
+
+	/// Remember the block at which the message has been enqueued.
+	pub(crate) fn note_enqueued_message(lane_id: T::LaneId, nonce: MessageNonce) {
+		OutboundMessagesEnqueuedAt::<T, I>::insert(
+			lane_id,
+			nonce,
+			frame_system::Pallet::<T>::block_number(),
+		);
+	}
+
+	/// Forget blocks at which delivered messages have been enqueued.
+	///
+	/// The single delivery confirmation may not confirm more than
+	/// `MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX` messages of the bridged chain, so we never
+	/// visit more entries than that.
+	pub(crate) fn forget_delivered_messages(lane_id: T::LaneId) {
+		let latest_received_nonce =
+			match pallet_bridge_messages::OutboundLanes::<T, T::BridgeMessagesPalletInstance>::get(
+				lane_id,
+			) {
+				Some(outbound_lane_data) => outbound_lane_data.latest_received_nonce,
+				None => return,
+			};
+
+		// entries for previously confirmed messages are already removed, so we may stop at the
+		// first missing entry
+		let oldest_nonce = latest_received_nonce.saturating_sub(max_confirmed_messages::<T, I>());
+		let mut nonce = latest_received_nonce;
+		while nonce > oldest_nonce &&
+			OutboundMessagesEnqueuedAt::<T, I>::take(lane_id, nonce).is_some()
+		{
+			nonce -= 1;
+		}
+	}
+
 	/// Must be called whenever we receive a message delivery confirmation.
 	fn on_bridge_messages_delivered(lane_id: T::LaneId, enqueued_messages: MessageNonce) {
 		// if the bridge queue is still congested, we don't want to do anything
@@ -469,6 +538,32 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn exporter_rejects_messages_while_bridge_is_opening() {
+		run_test(|| {
+			let (locations, lane_id) = open_lane();
+			Bridges::<TestRuntime, ()>::mutate_extant(locations.bridge_id(), |bridge| {
+				bridge.state = BridgeState::Opening;
+			});
+
+			assert_eq!(
+				export_xcm::<XcmOverBridge>(
+					BridgedRelayNetwork::get(),
+					0,
+					locations.bridge_origin_universal_location().clone(),
+					locations.bridge_destination_universal_location().clone(),
+					vec![Instruction::ClearOrigin].into(),
+				),
+				Err(SendError::Transport("BridgeIsOpening")),
+			);
+			assert!(LanesManagerOf::<TestRuntime, ()>::new()
+				.active_outbound_lane(lane_id)
+				.unwrap()
+				.queued_messages()
+				.is_empty());
+		});
+	}
+
 	#[test]
 	fn exporter_does_not_suspend_the_bridge_if_outbound_bridge_queue_is_not_congested() {
 		run_test(|| {
@@ -559,6 +654,63 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn enqueue_blocks_are_tracked_until_messages_are_delivered() {
+		run_test(|| {
+			System::set_block_number(1);
+			let (bridge_id, lane_id) = open_lane_and_send_regular_message();
+			System::set_block_number(3);
+			open_lane_and_send_regular_message();
+			System::set_block_number(10);
+
+			assert_eq!(OutboundMessagesEnqueuedAt::<TestRuntime, ()>::get(lane_id, 1), Some(1));
+			assert_eq!(OutboundMessagesEnqueuedAt::<TestRuntime, ()>::get(lane_id, 2), Some(3));
+			let health = XcmOverBridge::bridge_health(&XcmOverBridge::bridge(&bridge_id).unwrap());
+			assert_eq!(health.outbound_queue_depth, 2);
+			assert_eq!(health.oldest_undelivered_nonce, Some(1));
+			assert_eq!(health.oldest_undelivered_message_age, Some(9));
+
+			// confirm delivery of the first message
+			pallet_bridge_messages::OutboundLanes::<TestRuntime, ()>::mutate(lane_id, |data| {
+				data.as_mut().unwrap().latest_received_nonce = 1;
+			});
+			XcmOverBridge::on_messages_delivered(lane_id, 1);
+
+			assert_eq!(OutboundMessagesEnqueuedAt::<TestRuntime, ()>::get(lane_id, 1), None);
+			assert_eq!(OutboundMessagesEnqueuedAt::<TestRuntime, ()>::get(lane_id, 2), Some(3));
+			let health = XcmOverBridge::bridge_health(&XcmOverBridge::bridge(&bridge_id).unwrap());
+			assert_eq!(health.outbound_queue_depth, 1);
+			assert_eq!(health.oldest_undelivered_nonce, Some(2));
+			assert_eq!(health.oldest_undelivered_message_age, Some(7));
+		});
+	}
+
+	#[test]
+	fn forget_delivered_messages_visits_limited_number_of_entries() {
+		run_test(|| {
+			let (_, lane_id) = open_lane_and_send_regular_message();
+			let max_confirmed_messages = max_confirmed_messages::<TestRuntime, ()>();
+			let latest_received_nonce = max_confirmed_messages + 2;
+			for nonce in 1..=latest_received_nonce {
+				XcmOverBridge::note_enqueued_message(lane_id, nonce);
+			}
+
+			// confirm delivery of more messages than the single confirmation may carry
+			pallet_bridge_messages::OutboundLanes::<TestRuntime, ()>::mutate(lane_id, |data| {
+				data.as_mut().unwrap().latest_received_nonce = latest_received_nonce;
+			});
+			XcmOverBridge::on_messages_delivered(lane_id, 0);
+
+			assert_eq!(OutboundMessagesEnqueuedAt::<TestRuntime, ()>::get(lane_id, 1), Some(0));
+			assert_eq!(OutboundMessagesEnqueuedAt::<TestRuntime, ()>::get(lane_id, 2), Some(0));
+			assert_eq!(OutboundMessagesEnqueuedAt::<TestRuntime, ()>::get(lane_id, 3), None);
+			assert_eq!(
+				OutboundMessagesEnqueuedAt::<TestRuntime, ()>::get(lane_id, latest_received_nonce),
+				None,
+			);
+		});
+	}
+
 	#[test]
 	fn export_fails_if_argument_is_missing() {
 		run_test(|| {
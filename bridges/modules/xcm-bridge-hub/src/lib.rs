@@ -36,6 +36,11 @@
 //!   - `bridge_origin_universal_location`, `bridge_destination_universal_location` is converted to
 //!     the `Versioned*` structs
 //!
+//! `LegacyLaneId` identifiers of dynamic bridges are the first four bytes of the `HashedLaneId`
+//! of the same endpoints. Both bridge hubs are computing the same identifier, so no lane
+//! negotiation is needed. The handshake message (see below) makes sure that both sides have
+//! agreed on the same endpoints for the lane.
+//!
 //! `LaneId` is expected to never change because:
 //! - We need the same `LaneId` on both sides of the bridge, as `LaneId` is part of the message key
 //!   proofs.
@@ -91,15 +96,26 @@
 //!
 //! 3) the sibling parachain opens the bridge by sending XCM `Transact` instruction with the
 //!    `open_bridge` call. The `BridgeDeposit` amount is reserved on the sovereign account of
-//!    sibling parachain;
+//!    sibling parachain. The bridge is `Opening` and the handshake message is queued at its
+//!    outbound lane. Messages may not be exported over the `Opening` bridge;
+//!
+//! 4) at the other side of the bridge, the same thing (1, 2, 3) happens. Once the lane is opened at
+//!    both sides, relayers deliver handshakes. When a bridge hub receives the handshake of the
+//!    bridged side, it switches the bridge to the `Opened` state and deposits the `BridgeReady`
+//!    event. The handshake is always the first message of the lane, so the parachain may start
+//!    sending messages as soon as its end of the bridge is ready - they are delivered after the
+//!    handshake.
 //!
-//! 4) at the other side of the bridge, the same thing (1, 2, 3) happens. Parachains that need to
-//!    connect over the bridge need to coordinate the moment when they start sending messages over
-//!    the bridge. Otherwise they may lose messages and/or bundled assets;
+//!    The current status of the bridge (including its `LaneId`) may be queried using the
+//!    `To<BridgedChain>XcmBridgeHubApi::bridge_status` runtime API at both sides;
 //!
 //! 5) when either side wants to close the bridge, it sends the XCM `Transact` with the
 //!    `close_bridge` call. The bridge is closed immediately if there are no queued messages.
-//!    Otherwise, the owner must repeat the `close_bridge` call to prune all queued messages first.
+//!    Otherwise, the outbound lane stops accepting new messages and queued messages are given
+//!    `ClosingBridgeDrainPeriod` blocks to be delivered. The owner may repeat the `close_bridge`
+//!    call to prune queued messages earlier. Once the bridge is drained (or the drain period is
+//!    over), it is pruned when the bridge hub has some spare block weight and the bridge deposit is
+//!    released.
 //!
 //! The pallet doesn't provide any mechanism for graceful closure, because it always involves
 //! some contract between two connected chains and the bridge hub knows nothing about that. It
@@ -123,7 +139,7 @@
 //!    Parachain(1234))` ``` xcm::Transact( origin_kind: OriginKind::Xcm,
 //!    XcmOverBridgeHubKusama::open_bridge( VersionedInteriorLocation::V4([GlobalConsensus(Kusama),
 //!    Parachain(4567)].into()), ); ) ```
-//! 3. Check the stored bridge metadata and generated `LaneId`.
+//! 3. Check the stored bridge metadata and generated `LaneId`. The bridge is `Opening`.
 //! 4. The Kusama local sibling parachain `Location::new(1, Parachain(4567))` must send some KSMs to
 //!    its sovereign account
 //! on BridgeHubKusama to cover `BridgeDeposit`, fees for `Transact`, and the existential deposit.
@@ -131,10 +147,11 @@
 //!    Parachain(4567))` ``` xcm::Transact( origin_kind: OriginKind::Xcm,
 //!    XcmOverBridgeHubKusama::open_bridge(
 //!    VersionedInteriorLocation::V4([GlobalConsensus(Polkadot), Parachain(1234)].into()), ); ) ```
-//! 6. Check the stored bridge metadata and generated `LaneId`.
+//! 6. Check the stored bridge metadata and generated `LaneId`. The bridge is `Opening`.
 //! 7. Both `LaneId`s from steps 3 and 6 must be the same (see above _Concept of `lane` and
 //!    `LaneId`_).
-//! 8. Run the bridge messages relayer for `LaneId`.
+//! 8. Run the bridge messages relayer for `LaneId`. Once handshakes are delivered, both bridge hubs
+//!    deposit the `BridgeReady` event and the bridge is `Opened` at both sides.
 //! 9. Send messages from both sides.
 //!
 //! The opening bridge holds the configured `BridgeDeposit` from the origin's sovereign account, but
@@ -145,12 +162,17 @@
 
 use bp_messages::{LaneState, MessageNonce};
 use bp_runtime::{AccountIdOf, BalanceOf, RangeInclusiveExt};
-pub use bp_xcm_bridge_hub::{Bridge, BridgeId, BridgeState};
+pub use bp_xcm_bridge_hub::{
+	Bridge, BridgeHandshake, BridgeHealth, BridgeId, BridgeState, BridgeStatus,
+};
 use bp_xcm_bridge_hub::{BridgeLocations, BridgeLocationsError, LocalXcmChannelManager};
+use codec::Encode;
 use frame_support::{traits::fungible::MutateHold, DefaultNoBound};
 use frame_system::Config as SystemConfig;
-use pallet_bridge_messages::{Config as BridgeMessagesConfig, LanesManagerError};
-use sp_runtime::traits::Zero;
+use pallet_bridge_messages::{
+	Config as BridgeMessagesConfig, LanesManagerError, StoredMessagePayload,
+};
+use sp_runtime::traits::{Saturating, Zero};
 use sp_std::{boxed::Box, vec::Vec};
 use xcm::prelude::*;
 use xcm_builder::DispatchBlob;
@@ -160,15 +182,33 @@ pub use bp_xcm_bridge_hub::XcmAsPlainPayload;
 pub use dispatcher::XcmBlobMessageDispatchResult;
 pub use exporter::PalletAsHaulBlobExporter;
 pub use pallet::*;
+pub use weights::WeightInfo;
 
+pub mod benchmarking;
 mod dispatcher;
 mod exporter;
 pub mod migration;
 mod mock;
+pub mod weights;
 
 /// The target that will be used when publishing logs related to this pallet.
 pub const LOG_TARGET: &str = "runtime::bridge-xcm";
 
+/// Helper trait for benchmarking the pallet calls.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<RuntimeOrigin> {
+	/// Returns origin of the sibling chain that is allowed to open bridges, or `None` if bridges
+	/// may not be opened with the `open_bridge` call.
+	fn open_bridge_origin() -> Option<RuntimeOrigin>;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl<RuntimeOrigin> BenchmarkHelper<RuntimeOrigin> for () {
+	fn open_bridge_origin() -> Option<RuntimeOrigin> {
+		None
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -194,6 +234,8 @@ pub mod pallet {
 		/// The overarching event type.
 		type RuntimeEvent: From<Event<Self, I>>
 			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Benchmarks results from runtime we're plugged into.
+		type WeightInfo: WeightInfo;
 
 		/// Runtime's universal location.
 		type UniversalLocation: Get<InteriorLocation>;
@@ -239,15 +281,26 @@ pub mod pallet {
 		/// Do not hold `Self::BridgeDeposit` for the location of `Self::OpenBridgeOrigin`.
 		/// For example, it is possible to make an exception for a system parachain or relay.
 		type AllowWithoutBridgeDeposit: Contains<Location>;
+		/// Number of blocks, during which the closed bridge is waiting for its queued outbound
+		/// messages to be delivered and for the inbound messages from the bridged side to be
+		/// confirmed. Messages that are still undelivered after this period are pruned.
+		#[pallet::constant]
+		type ClosingBridgeDrainPeriod: Get<BlockNumberFor<Self>>;
 
 		/// Local XCM channel manager.
 		type LocalXcmChannelManager: LocalXcmChannelManager;
 		/// XCM-level dispatcher for inbound bridge messages.
 		type BlobDispatcher: DispatchBlob;
+
+		/// Helper for benchmarking the `open_bridge` and `close_bridge` calls.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<<Self as SystemConfig>::RuntimeOrigin>;
 	}
 
 	/// An alias for the bridge metadata.
 	pub type BridgeOf<T, I> = Bridge<ThisChainOf<T, I>, LaneIdOf<T, I>>;
+	/// An alias for the bridge health.
+	pub type BridgeHealthOf<T, I> = BridgeHealth<BlockNumberFor<T>, LaneIdOf<T, I>>;
 	/// An alias for this chain.
 	pub type ThisChainOf<T, I> =
 		pallet_bridge_messages::ThisChainOf<T, <T as Config<I>>::BridgeMessagesPalletInstance>;
@@ -272,6 +325,10 @@ pub mod pallet {
 			)
 		}
 
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::prune_closing_bridge(remaining_weight)
+		}
+
 		#[cfg(feature = "try-runtime")]
 		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
 			Self::do_try_state()
@@ -289,10 +346,13 @@ pub mod pallet {
 		/// The `BridgeDeposit` amount is reserved on the caller account. This deposit
 		/// is unreserved after bridge is closed.
 		///
-		/// The states after this call: bridge is `Opened`, outbound lane is `Opened`, inbound lane
+		/// The handshake message is sent to the bridged side of the bridge. The bridge is switched
+		/// to the `Opened` state when the handshake of the bridged side is received.
+		///
+		/// The states after this call: bridge is `Opening`, outbound lane is `Opened`, inbound lane
 		/// is `Opened`.
 		#[pallet::call_index(0)]
-		#[pallet::weight(Weight::zero())] // TODO:(bridges-v2) - https://github.com/paritytech/parity-bridges-common/issues/3046 - add benchmarks impl
+		#[pallet::weight(T::WeightInfo::open_bridge())]
 		pub fn open_bridge(
 			origin: OriginFor<T>,
 			bridge_destination_universal_location: Box<VersionedInteriorLocation>,
@@ -309,7 +369,8 @@ pub mod pallet {
 				Error::<T, I>::BridgeLocations(e)
 			})?;
 
-			Self::do_open_bridge(locations, lane_id, true)
+			Self::open_bridge_in_state(locations.clone(), lane_id, true, BridgeState::Opening)?;
+			Self::send_bridge_handshake(&locations, lane_id, xcm_version)
 		}
 
 		/// Try to close the bridge.
@@ -325,12 +386,17 @@ pub mod pallet {
 		/// The number of messages that we may prune in a single call is limited by the
 		/// `may_prune_messages` argument. If there are more messages in the queue, the method
 		/// prunes exactly `may_prune_messages` and exits early. The caller may call it again
-		/// until outbound queue is depleted and get his funds back.
+		/// until outbound queue is depleted and get his funds back. If the owner does not prune
+		/// messages, they are given `T::ClosingBridgeDrainPeriod` blocks to be delivered to the
+		/// bridged side. Inbound lane is kept opened for the same period, so that the owner of the
+		/// other side of the bridge may close it in a coordinated way.
 		///
-		/// The states after this call: everything is either `Closed`, or purged from the
-		/// runtime storage.
+		/// The states after this call: bridge and outbound lane are `Closed` (or purged from the
+		/// runtime storage).
 		#[pallet::call_index(1)]
-		#[pallet::weight(Weight::zero())] // TODO:(bridges-v2) - https://github.com/paritytech/parity-bridges-common/issues/3046 - add benchmarks impl
+		#[pallet::weight(T::WeightInfo::close_bridge(
+			(*may_prune_messages).try_into().unwrap_or(u32::MAX),
+		))]
 		pub fn close_bridge(
 			origin: OriginFor<T>,
 			bridge_destination_universal_location: Box<VersionedInteriorLocation>,
@@ -340,121 +406,87 @@ pub mod pallet {
 			let locations =
 				Self::bridge_locations_from_origin(origin, bridge_destination_universal_location)?;
 
-			// TODO: https://github.com/paritytech/parity-bridges-common/issues/1760 - may do refund here, if
-			// bridge/lanes are already closed + for messages that are not pruned
+			Self::do_close_bridge(locations, may_prune_messages)
+		}
+	}
 
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Close bridge and prune at most `may_prune_messages` of its queued outbound messages.
+		pub fn do_close_bridge(
+			locations: Box<BridgeLocations>,
+			may_prune_messages: MessageNonce,
+		) -> DispatchResult {
 			// update bridge metadata - this also guarantees that the bridge is in the proper state
-			let bridge =
-				Bridges::<T, I>::try_mutate_exists(locations.bridge_id(), |bridge| match bridge {
+			let bridge_id = *locations.bridge_id();
+			let (bridge, is_closing) =
+				Bridges::<T, I>::try_mutate_exists(bridge_id, |bridge| match bridge {
 					Some(bridge) => {
+						let is_closing = bridge.state != BridgeState::Closed;
 						bridge.state = BridgeState::Closed;
-						Ok(bridge.clone())
+						Ok((bridge.clone(), is_closing))
 					},
 					None => Err(Error::<T, I>::UnknownBridge),
 				})?;
 
-			// close inbound and outbound lanes
-			let lanes_manager = LanesManagerOf::<T, I>::new();
-			let mut inbound_lane = lanes_manager
-				.any_state_inbound_lane(bridge.lane_id)
-				.map_err(Error::<T, I>::LanesManager)?;
-			let mut outbound_lane = lanes_manager
-				.any_state_outbound_lane(bridge.lane_id)
-				.map_err(Error::<T, I>::LanesManager)?;
-
-			// now prune queued messages
-			let mut pruned_messages = 0;
-			for _ in outbound_lane.queued_messages() {
-				if pruned_messages == may_prune_messages {
-					break
-				}
-
-				outbound_lane.remove_oldest_unpruned_message();
-				pruned_messages += 1;
+			// stop accepting new outbound messages and start draining the bridge
+			if is_closing {
+				LanesManagerOf::<T, I>::new()
+					.any_state_outbound_lane(bridge.lane_id)
+					.map_err(Error::<T, I>::LanesManager)?
+					.set_state(LaneState::Closed);
+				ClosingBridges::<T, I>::insert(
+					bridge_id,
+					frame_system::Pallet::<T>::block_number(),
+				);
 			}
 
-			// if there are outbound messages in the queue, just update states and early exit
-			if !outbound_lane.queued_messages().is_empty() {
-				// update lanes state. Under normal circumstances, following calls shall never fail
-				inbound_lane.set_state(LaneState::Closed);
-				outbound_lane.set_state(LaneState::Closed);
+			let lane_id = bridge.lane_id;
+			let pruned_messages =
+				Self::do_prune_closed_bridge(bridge_id, bridge, may_prune_messages)?;
 
-				// write something to log
-				let enqueued_messages = outbound_lane.queued_messages().saturating_len();
+			// deposit the `ClosingBridge` event (only once - when we start closing the bridge)
+			if is_closing && Bridges::<T, I>::contains_key(bridge_id) {
+				let enqueued_messages = LanesManagerOf::<T, I>::new()
+					.any_state_outbound_lane(lane_id)
+					.map(|lane| lane.queued_messages().saturating_len())
+					.unwrap_or(0);
 				log::trace!(
 					target: LOG_TARGET,
-					"Bridge {:?} between {:?} and {:?} is closing lane_id: {:?}. {} messages remaining",
-					locations.bridge_id(),
-					locations.bridge_origin_universal_location(),
-					locations.bridge_destination_universal_location(),
-					bridge.lane_id,
+					"Bridge {:?} is closing lane_id: {:?}. {} messages remaining",
+					bridge_id,
+					lane_id,
 					enqueued_messages,
 				);
 
-				// deposit the `ClosingBridge` event
 				Self::deposit_event(Event::<T, I>::ClosingBridge {
-					bridge_id: *locations.bridge_id(),
-					lane_id: bridge.lane_id.into(),
+					bridge_id,
+					lane_id: lane_id.into(),
 					pruned_messages,
 					enqueued_messages,
 				});
-
-				return Ok(())
 			}
 
-			// else we have pruned all messages, so lanes and the bridge itself may gone
-			inbound_lane.purge();
-			outbound_lane.purge();
-			Bridges::<T, I>::remove(locations.bridge_id());
-			LaneToBridge::<T, I>::remove(bridge.lane_id);
-
-			// return deposit
-			let released_deposit = T::Currency::release(
-				&HoldReason::BridgeDeposit.into(),
-				&bridge.bridge_owner_account,
-				bridge.deposit,
-				Precision::BestEffort,
-			)
-			.inspect_err(|e| {
-				// we can't do anything here - looks like funds have been (partially) unreserved
-				// before by someone else. Let's not fail, though - it'll be worse for the caller
-				log::error!(
-					target: LOG_TARGET,
-					"Failed to unreserve during the bridge {:?} closure with error: {e:?}",
-					locations.bridge_id(),
-				);
-			})
-			.ok()
-			.unwrap_or(BalanceOf::<ThisChainOf<T, I>>::zero());
-
-			// write something to log
-			log::trace!(
-				target: LOG_TARGET,
-				"Bridge {:?} between {:?} and {:?} has closed lane_id: {:?}, the bridge deposit {released_deposit:?} was returned",
-				locations.bridge_id(),
-				bridge.lane_id,
-				locations.bridge_origin_universal_location(),
-				locations.bridge_destination_universal_location(),
-			);
-
-			// deposit the `BridgePruned` event
-			Self::deposit_event(Event::<T, I>::BridgePruned {
-				bridge_id: *locations.bridge_id(),
-				lane_id: bridge.lane_id.into(),
-				bridge_deposit: released_deposit,
-				pruned_messages,
-			});
-
 			Ok(())
 		}
-	}
 
-	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Open bridge for lane.
+		///
+		/// The bridge is `Opened` immediately, without exchanging handshakes with the bridged side.
+		/// It is used for bridges that are opened at both sides by other means (e.g. at genesis).
 		pub fn do_open_bridge(
 			locations: Box<BridgeLocations>,
 			lane_id: T::LaneId,
 			create_lanes: bool,
+		) -> Result<(), DispatchError> {
+			Self::open_bridge_in_state(locations, lane_id, create_lanes, BridgeState::Opened)
+		}
+
+		/// Open bridge for lane and set its initial state.
+		fn open_bridge_in_state(
+			locations: Box<BridgeLocations>,
+			lane_id: T::LaneId,
+			create_lanes: bool,
+			state: BridgeState,
 		) -> Result<(), DispatchError> {
 			// reserve balance on the origin's sovereign account (if needed)
 			let bridge_owner_account = T::BridgeOriginAccountIdConverter::convert_location(
@@ -499,7 +531,7 @@ pub mod pallet {
 						bridge_destination_universal_location: Box::new(
 							locations.bridge_destination_universal_location().clone().into(),
 						),
-						state: BridgeState::Opened,
+						state,
 						bridge_owner_account,
 						deposit,
 						lane_id,
@@ -549,6 +581,282 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Send the handshake message to the bridged side of the `Opening` bridge.
+		///
+		/// It is the first message at the outbound lane of the bridge, so it is delivered before
+		/// any other message. Bridge locations are sent using given XCM version, which is the
+		/// version that has been used to compute the lane identifier.
+		fn send_bridge_handshake(
+			locations: &BridgeLocations,
+			lane_id: T::LaneId,
+			xcm_version: XcmVersion,
+		) -> DispatchResult {
+			let into_version = |location: &InteriorLocation| {
+				VersionedInteriorLocation::from(location.clone())
+					.into_version(xcm_version)
+					.map(Box::new)
+					.map_err(|_| Error::<T, I>::UnsupportedXcmVersion)
+			};
+			let handshake = BridgeHandshake {
+				bridge_origin_universal_location: into_version(
+					locations.bridge_origin_universal_location(),
+				)?,
+				bridge_destination_universal_location: into_version(
+					locations.bridge_destination_universal_location(),
+				)?,
+			};
+
+			// the bridged side is dispatching inbound payloads as `XcmAsPlainPayload`
+			let payload = StoredMessagePayload::<T, T::BridgeMessagesPalletInstance>::try_from(
+				XcmAsPlainPayload::from(handshake.encode()).encode(),
+			)
+			.map_err(|_| Error::<T, I>::FailedToSendBridgeHandshake)?;
+			let nonce = LanesManagerOf::<T, I>::new()
+				.active_outbound_lane(lane_id)
+				.map_err(Error::<T, I>::LanesManager)?
+				.send_message(payload);
+			Self::note_enqueued_message(lane_id, nonce);
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Bridge {:?} has sent the handshake with nonce {nonce} over lane_id: {lane_id:?}",
+				locations.bridge_id(),
+			);
+
+			Ok(())
+		}
+
+		/// Process the handshake that we have received from the bridged side of the `Opening`
+		/// bridge.
+		///
+		/// Returns true if the bridge is now `Opened`.
+		pub(crate) fn receive_bridge_handshake(
+			bridge_id: BridgeId,
+			bridge: BridgeOf<T, I>,
+			handshake: BridgeHandshake,
+		) -> bool {
+			// endpoints of the bridged side are mirrored endpoints of our side
+			let is_same_location = |ours: &VersionedInteriorLocation,
+			                        theirs: VersionedInteriorLocation| {
+				match (ours.try_as::<InteriorLocation>(), InteriorLocation::try_from(theirs)) {
+					(Ok(ours), Ok(theirs)) => *ours == theirs,
+					_ => false,
+				}
+			};
+			let is_valid_handshake = is_same_location(
+				&bridge.bridge_origin_universal_location,
+				*handshake.bridge_destination_universal_location,
+			) && is_same_location(
+				&bridge.bridge_destination_universal_location,
+				*handshake.bridge_origin_universal_location,
+			);
+			if !is_valid_handshake {
+				log::error!(
+					target: LOG_TARGET,
+					"Bridge {:?} has received handshake with unexpected endpoints over lane_id: {:?}",
+					bridge_id,
+					bridge.lane_id,
+				);
+				return false
+			}
+
+			Bridges::<T, I>::mutate_extant(bridge_id, |bridge| {
+				bridge.state = BridgeState::Opened;
+			});
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Bridge {:?} is ready, lane_id: {:?}",
+				bridge_id,
+				bridge.lane_id,
+			);
+
+			Self::deposit_event(Event::<T, I>::BridgeReady {
+				bridge_id,
+				lane_id: bridge.lane_id.into(),
+			});
+
+			true
+		}
+
+		/// Prune at most `may_prune_messages` outbound messages of the closed bridge.
+		///
+		/// If there are no more queued messages, bridge lanes are purged, the bridge is removed
+		/// from the storage and its deposit is released. Otherwise, the bridge stays in the
+		/// `ClosingBridges` set and its remaining messages are pruned later.
+		///
+		/// Returns the number of pruned messages.
+		pub fn do_prune_closed_bridge(
+			bridge_id: BridgeId,
+			bridge: BridgeOf<T, I>,
+			may_prune_messages: MessageNonce,
+		) -> Result<MessageNonce, DispatchError> {
+			let lanes_manager = LanesManagerOf::<T, I>::new();
+			let inbound_lane = lanes_manager
+				.any_state_inbound_lane(bridge.lane_id)
+				.map_err(Error::<T, I>::LanesManager)?;
+			let mut outbound_lane = lanes_manager
+				.any_state_outbound_lane(bridge.lane_id)
+				.map_err(Error::<T, I>::LanesManager)?;
+
+			// now prune queued messages
+			let mut pruned_messages = 0;
+			for nonce in outbound_lane.queued_messages() {
+				if pruned_messages == may_prune_messages {
+					break
+				}
+
+				outbound_lane.remove_oldest_unpruned_message();
+				OutboundMessagesEnqueuedAt::<T, I>::remove(bridge.lane_id, nonce);
+				pruned_messages += 1;
+			}
+
+			// if there are outbound messages in the queue, early exit. Remaining messages will
+			// be delivered or pruned when we'll have some spare block weight
+			if !outbound_lane.queued_messages().is_empty() {
+				return Ok(pruned_messages)
+			}
+
+			// else we have pruned all messages, so lanes and the bridge itself may gone
+			inbound_lane.purge();
+			outbound_lane.purge();
+			Bridges::<T, I>::remove(bridge_id);
+			LaneToBridge::<T, I>::remove(bridge.lane_id);
+			ClosingBridges::<T, I>::remove(bridge_id);
+
+			// return deposit
+			let released_deposit = T::Currency::release(
+				&HoldReason::BridgeDeposit.into(),
+				&bridge.bridge_owner_account,
+				bridge.deposit,
+				Precision::BestEffort,
+			)
+			.inspect_err(|e| {
+				// we can't do anything here - looks like funds have been (partially) unreserved
+				// before by someone else. Let's not fail, though - it'll be worse for the caller
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to unreserve during the bridge {:?} closure with error: {e:?}",
+					bridge_id,
+				);
+			})
+			.ok()
+			.unwrap_or(BalanceOf::<ThisChainOf<T, I>>::zero());
+
+			// write something to log
+			log::trace!(
+				target: LOG_TARGET,
+				"Bridge {:?} has closed lane_id: {:?}, the bridge deposit {released_deposit:?} was returned",
+				bridge_id,
+				bridge.lane_id,
+			);
+
+			// deposit the `BridgePruned` event
+			Self::deposit_event(Event::<T, I>::BridgePruned {
+				bridge_id,
+				lane_id: bridge.lane_id.into(),
+				bridge_deposit: released_deposit,
+				pruned_messages,
+			});
+
+			Ok(pruned_messages)
+		}
+
+		/// Prune messages of the closing bridge, using at most `remaining_weight`.
+		///
+		/// The bridge is only pruned when all its outbound messages are delivered and all
+		/// inbound messages are confirmed, or when its drain period has ended. At most one
+		/// bridge is pruned per call.
+		///
+		/// Returns the weight, used by this call.
+		pub fn prune_closing_bridge(remaining_weight: Weight) -> Weight {
+			let check_weight = T::WeightInfo::check_closing_bridge();
+			let base_prune_weight = T::WeightInfo::prune_closing_bridge(0);
+			let weight_per_message =
+				T::WeightInfo::prune_closing_bridge(1).saturating_sub(base_prune_weight);
+
+			let mut used_weight = Weight::zero();
+			let mut invalid_bridges = Vec::new();
+			for (bridge_id, closed_at) in ClosingBridges::<T, I>::iter() {
+				let required_weight =
+					used_weight.saturating_add(check_weight).saturating_add(base_prune_weight);
+				if remaining_weight.any_lt(required_weight) {
+					break
+				}
+				used_weight.saturating_accrue(check_weight);
+
+				let bridge = match Self::bridge(&bridge_id) {
+					Some(bridge) if bridge.state == BridgeState::Closed => bridge,
+					_ => {
+						// should never happen, but let's be safe here
+						invalid_bridges.push(bridge_id);
+						continue
+					},
+				};
+				if !Self::is_closing_bridge_drained(&bridge, closed_at) {
+					continue
+				}
+
+				let may_prune_messages = remaining_weight
+					.saturating_sub(used_weight)
+					.saturating_sub(base_prune_weight)
+					.checked_div_per_component(&weight_per_message)
+					.unwrap_or(MessageNonce::MAX);
+				match Self::do_prune_closed_bridge(bridge_id, bridge, may_prune_messages) {
+					Ok(pruned_messages) =>
+						used_weight.saturating_accrue(T::WeightInfo::prune_closing_bridge(
+							pruned_messages.try_into().unwrap_or(u32::MAX),
+						)),
+					Err(e) => {
+						log::error!(
+							target: LOG_TARGET,
+							"Failed to prune messages of the closing bridge {:?}: {:?}",
+							bridge_id,
+							e,
+						);
+						invalid_bridges.push(bridge_id);
+						used_weight.saturating_accrue(base_prune_weight);
+					},
+				}
+				break
+			}
+
+			for bridge_id in invalid_bridges {
+				ClosingBridges::<T, I>::remove(bridge_id);
+				used_weight.saturating_accrue(T::DbWeight::get().writes(1));
+			}
+
+			used_weight
+		}
+
+		/// Returns true if the closing bridge may be pruned.
+		///
+		/// It happens when all its outbound messages are delivered and all inbound messages
+		/// are confirmed by the bridged side, or when its drain period has ended.
+		pub(crate) fn is_closing_bridge_drained(
+			bridge: &BridgeOf<T, I>,
+			closed_at: BlockNumberFor<T>,
+		) -> bool {
+			let now = frame_system::Pallet::<T>::block_number();
+			if now.saturating_sub(closed_at) >= T::ClosingBridgeDrainPeriod::get() {
+				return true
+			}
+
+			let lanes_manager = LanesManagerOf::<T, I>::new();
+			let is_outbound_lane_drained = lanes_manager
+				.any_state_outbound_lane(bridge.lane_id)
+				.map(|lane| lane.queued_messages().is_empty())
+				.unwrap_or(true);
+			let is_inbound_lane_drained = pallet_bridge_messages::InboundLanes::<
+				T,
+				T::BridgeMessagesPalletInstance,
+			>::get(bridge.lane_id)
+			.map(|inbound_lane_data| inbound_lane_data.relayers.is_empty())
+			.unwrap_or(true);
+
+			is_outbound_lane_drained && is_inbound_lane_drained
+		}
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -597,11 +905,90 @@ pub mod pallet {
 			LaneToBridge::<T, I>::get(lane_id)
 				.and_then(|bridge_id| Self::bridge(&bridge_id).map(|bridge| (bridge_id, bridge)))
 		}
+
+		/// Return status of the bridge between given endpoints.
+		///
+		/// If the bridge is not opened yet, the returned lane identifier is the one that will
+		/// be used by the bridge. Bridge hubs at both sides of the bridge are computing the same
+		/// identifier, so it may be used to check that both sides are ready to exchange messages.
+		pub fn bridge_status(
+			bridge_origin_relative_location: Location,
+			bridge_destination_universal_location: VersionedInteriorLocation,
+		) -> Result<BridgeStatus<T::LaneId>, sp_runtime::DispatchError> {
+			let xcm_version = bridge_destination_universal_location.identify_version();
+			let locations = Self::bridge_locations(
+				bridge_origin_relative_location,
+				bridge_destination_universal_location
+					.try_into()
+					.map_err(|_| Error::<T, I>::UnsupportedXcmVersion)?,
+			)?;
+			let bridge_id = *locations.bridge_id();
+
+			Ok(match Self::bridge(&bridge_id) {
+				Some(bridge) =>
+					BridgeStatus { bridge_id, lane_id: bridge.lane_id, state: Some(bridge.state) },
+				None => BridgeStatus {
+					bridge_id,
+					lane_id: locations
+						.calculate_lane_id(xcm_version)
+						.map_err(Error::<T, I>::BridgeLocations)?,
+					state: None,
+				},
+			})
+		}
+
+		/// Return health of the given bridge.
+		pub fn bridge_health(bridge: &BridgeOf<T, I>) -> BridgeHealthOf<T, I> {
+			let outbound_lane_data = pallet_bridge_messages::OutboundLanes::<
+				T,
+				T::BridgeMessagesPalletInstance,
+			>::get(bridge.lane_id)
+			.unwrap_or_default();
+			let undelivered_messages = outbound_lane_data.latest_received_nonce.saturating_add(1)..=
+				outbound_lane_data.latest_generated_nonce;
+			let oldest_undelivered_nonce =
+				(!undelivered_messages.is_empty()).then_some(*undelivered_messages.start());
+			let oldest_undelivered_message_age = oldest_undelivered_nonce
+				.and_then(|nonce| OutboundMessagesEnqueuedAt::<T, I>::get(bridge.lane_id, nonce))
+				.map(|enqueued_at| {
+					frame_system::Pallet::<T>::block_number().saturating_sub(enqueued_at)
+				});
+
+			let unrewarded_relayer_entries = pallet_bridge_messages::InboundLanes::<
+				T,
+				T::BridgeMessagesPalletInstance,
+			>::get(bridge.lane_id)
+			.map(|inbound_lane_data| inbound_lane_data.relayers.len() as u32)
+			.unwrap_or(0);
+
+			let is_congested = bridge
+				.bridge_origin_relative_location
+				.try_as()
+				.map(T::LocalXcmChannelManager::is_congested)
+				.unwrap_or(false);
+
+			BridgeHealth {
+				lane_id: bridge.lane_id,
+				state: bridge.state,
+				is_congested,
+				outbound_queue_depth: undelivered_messages.saturating_len(),
+				oldest_undelivered_nonce,
+				oldest_undelivered_message_age,
+				unrewarded_relayer_entries,
+			}
+		}
+
+		/// Return health of all registered bridges.
+		pub fn bridges_health() -> Vec<(BridgeId, BridgeHealthOf<T, I>)> {
+			Bridges::<T, I>::iter()
+				.map(|(bridge_id, bridge)| (bridge_id, Self::bridge_health(&bridge)))
+				.collect()
+		}
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Returns some `NetworkId` if contains `GlobalConsensus` junction.
-		fn bridged_network_id() -> Result<NetworkId, sp_runtime::DispatchError> {
+		pub(crate) fn bridged_network_id() -> Result<NetworkId, sp_runtime::DispatchError> {
 			match T::BridgedNetwork::get().take_first_interior() {
 				Some(GlobalConsensus(network)) => Ok(network),
 				_ => Err(Error::<T, I>::BridgeLocations(
@@ -720,6 +1107,23 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type LaneToBridge<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Identity, T::LaneId, BridgeId>;
+	/// Closed bridges that still have some queued outbound messages, mapped to the number of
+	/// block at which they have been closed.
+	///
+	/// These bridges are pruned when there's some spare block weight and all their messages are
+	/// delivered (or their drain period has ended). When all messages are pruned, the bridge is
+	/// removed and its deposit is released.
+	#[pallet::storage]
+	pub type ClosingBridges<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, BridgeId, BlockNumberFor<T>>;
+
+	/// Numbers of blocks at which undelivered outbound messages have been enqueued.
+	///
+	/// Entries are inserted when the message is exported to the bridged network and removed
+	/// once its delivery is confirmed (or when the message is pruned during bridge closure).
+	#[pallet::storage]
+	pub type OutboundMessagesEnqueuedAt<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Identity, T::LaneId, Twox64Concat, MessageNonce, BlockNumberFor<T>>;
 
 	#[pallet::genesis_config]
 	#[derive(DefaultNoBound)]
@@ -805,6 +1209,14 @@ pub mod pallet {
 			/// Number of pruned messages during the close call.
 			pruned_messages: MessageNonce,
 		},
+		/// The handshake of the bridged side has been received and the bridge may be used to
+		/// send messages.
+		BridgeReady {
+			/// Bridge identifier.
+			bridge_id: BridgeId,
+			/// Lane identifier.
+			lane_id: T::LaneId,
+		},
 	}
 
 	#[pallet::error]
@@ -827,6 +1239,8 @@ pub mod pallet {
 		FailedToReserveBridgeDeposit,
 		/// The version of XCM location argument is unsupported.
 		UnsupportedXcmVersion,
+		/// The handshake message can't be sent to the bridged side.
+		FailedToSendBridgeHandshake,
 	}
 }
 
@@ -836,7 +1250,11 @@ mod tests {
 	use bp_messages::LaneIdType;
 	use mock::*;
 
-	use frame_support::{assert_err, assert_noop, assert_ok, traits::fungible::Mutate, BoundedVec};
+	use frame_support::{
+		assert_err, assert_noop, assert_ok,
+		traits::{fungible::Mutate, Hooks},
+		BoundedVec,
+	};
 	use frame_system::{EventRecord, Phase};
 	use sp_runtime::TryRuntimeError;
 
@@ -1160,7 +1578,7 @@ mod tests {
 						bridge_destination_universal_location: Box::new(
 							locations.bridge_destination_universal_location().clone().into(),
 						),
-						state: BridgeState::Opened,
+						state: BridgeState::Opening,
 						bridge_owner_account: bridge_owner_account.clone(),
 						deposit: expected_deposit,
 						lane_id
@@ -1174,6 +1592,29 @@ mod tests {
 					lanes_manager.active_outbound_lane(lane_id).map(|l| l.state()),
 					Ok(LaneState::Opened)
 				);
+				// the handshake is the only queued message
+				assert_eq!(
+					lanes_manager
+						.active_outbound_lane(lane_id)
+						.map(|l| l.queued_messages().checked_len()),
+					Ok(Some(1))
+				);
+				let handshake = BridgeHandshake {
+					bridge_origin_universal_location: Box::new(
+						locations.bridge_origin_universal_location().clone().into(),
+					),
+					bridge_destination_universal_location: Box::new(
+						locations.bridge_destination_universal_location().clone().into(),
+					),
+				};
+				assert_eq!(
+					pallet_bridge_messages::OutboundMessages::<TestRuntime, ()>::get(
+						bp_messages::MessageKey { lane_id, nonce: 1 }
+					)
+					.map(|payload| payload.into_inner()),
+					Some(handshake.encode().encode()),
+				);
+				assert!(OutboundMessagesEnqueuedAt::<TestRuntime, ()>::contains_key(lane_id, 1));
 				assert_eq!(
 					LaneToBridge::<TestRuntime, ()>::get(lane_id),
 					Some(*locations.bridge_id())
@@ -1303,8 +1744,9 @@ mod tests {
 				16,
 			),);
 
-			// as a result, the bridge and lanes are switched to the `Closed` state, some messages
-			// are pruned, but funds are not unreserved
+			// as a result, the bridge and outbound lane are switched to the `Closed` state, some
+			// messages are pruned, but funds are not unreserved. Inbound lane is kept opened
+			// until the bridge is pruned
 			let lanes_manager = LanesManagerOf::<TestRuntime, ()>::new();
 			assert_eq!(
 				Bridges::<TestRuntime, ()>::get(locations.bridge_id()).map(|b| b.state),
@@ -1312,7 +1754,7 @@ mod tests {
 			);
 			assert_eq!(
 				lanes_manager.any_state_inbound_lane(bridge.lane_id).unwrap().state(),
-				LaneState::Closed
+				LaneState::Opened
 			);
 			assert_eq!(
 				lanes_manager.any_state_outbound_lane(bridge.lane_id).unwrap().state(),
@@ -1360,7 +1802,7 @@ mod tests {
 			);
 			assert_eq!(
 				lanes_manager.any_state_inbound_lane(bridge.lane_id).unwrap().state(),
-				LaneState::Closed
+				LaneState::Opened
 			);
 			assert_eq!(
 				lanes_manager.any_state_outbound_lane(bridge.lane_id).unwrap().state(),
@@ -1380,18 +1822,16 @@ mod tests {
 			);
 			assert_eq!(Balances::free_balance(&bridge.bridge_owner_account), free_balance);
 			assert_eq!(Balances::reserved_balance(&bridge.bridge_owner_account), reserved_balance);
+			// the `ClosingBridge` event is only emitted once
 			assert_eq!(
-				System::events().last(),
-				Some(&EventRecord {
-					phase: Phase::Initialization,
-					event: RuntimeEvent::XcmOverBridge(Event::ClosingBridge {
-						bridge_id: *locations.bridge_id(),
-						lane_id: bridge.lane_id.into(),
-						pruned_messages: 8,
-						enqueued_messages: 8,
-					}),
-					topics: vec![],
-				}),
+				System::events()
+					.into_iter()
+					.filter(|r| matches!(
+						r.event,
+						RuntimeEvent::XcmOverBridge(Event::ClosingBridge { .. })
+					))
+					.count(),
+				1,
 			);
 
 			// now call the `close_bridge` again that will prune all remaining messages and the
@@ -1437,6 +1877,221 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn closing_bridge_is_pruned_on_idle() {
+		run_test(|| {
+			let origin = OpenBridgeOrigin::sibling_parachain_origin();
+			let expected_deposit = BridgeDeposit::get();
+			let (bridge, locations) = mock_open_bridge_from(origin.clone(), expected_deposit);
+			System::set_block_number(1);
+
+			// remember owner balances
+			let free_balance = Balances::free_balance(&bridge.bridge_owner_account);
+			let reserved_balance = Balances::reserved_balance(&bridge.bridge_owner_account);
+
+			// enqueue some messages and close the bridge without pruning them
+			for _ in 0..32 {
+				enqueue_message(bridge.lane_id);
+			}
+			assert_ok!(XcmOverBridge::close_bridge(
+				origin,
+				Box::new(locations.bridge_destination_universal_location().clone().into()),
+				0,
+			));
+			assert!(ClosingBridges::<TestRuntime, ()>::contains_key(locations.bridge_id()));
+
+			// if there's no spare weight, nothing is pruned
+			assert_eq!(XcmOverBridge::on_idle(1, Weight::zero()), Weight::zero());
+			assert_eq!(
+				Bridges::<TestRuntime, ()>::get(locations.bridge_id()).map(|b| b.state),
+				Some(BridgeState::Closed)
+			);
+
+			// while the drain period is not over and messages are still queued, nothing is pruned
+			XcmOverBridge::on_idle(1, Weight::MAX);
+			assert_eq!(
+				Bridges::<TestRuntime, ()>::get(locations.bridge_id()).map(|b| b.state),
+				Some(BridgeState::Closed)
+			);
+			assert!(ClosingBridges::<TestRuntime, ()>::contains_key(locations.bridge_id()));
+
+			// when the drain period is over, the bridge is pruned and deposit is released
+			let drained_at = 1 + ClosingBridgeDrainPeriod::get();
+			System::set_block_number(drained_at);
+			XcmOverBridge::on_idle(drained_at, Weight::MAX);
+			assert_eq!(Bridges::<TestRuntime, ()>::get(locations.bridge_id()), None);
+			assert_eq!(LaneToBridge::<TestRuntime, ()>::get(bridge.lane_id), None);
+			assert!(!ClosingBridges::<TestRuntime, ()>::contains_key(locations.bridge_id()));
+			assert_eq!(
+				Balances::free_balance(&bridge.bridge_owner_account),
+				free_balance + expected_deposit
+			);
+			assert_eq!(
+				Balances::reserved_balance(&bridge.bridge_owner_account),
+				reserved_balance - expected_deposit
+			);
+			assert_eq!(
+				System::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: RuntimeEvent::XcmOverBridge(Event::BridgePruned {
+						bridge_id: *locations.bridge_id(),
+						lane_id: bridge.lane_id.into(),
+						bridge_deposit: expected_deposit,
+						pruned_messages: 32,
+					}),
+					topics: vec![],
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn drained_closing_bridge_is_pruned_before_drain_period_ends() {
+		run_test(|| {
+			let origin = OpenBridgeOrigin::sibling_parachain_origin();
+			let (bridge, locations) = mock_open_bridge_from(origin.clone(), BridgeDeposit::get());
+			System::set_block_number(1);
+
+			// enqueue some messages and close the bridge
+			for _ in 0..4 {
+				enqueue_message(bridge.lane_id);
+			}
+			assert_ok!(XcmOverBridge::close_bridge(
+				origin,
+				Box::new(locations.bridge_destination_universal_location().clone().into()),
+				0,
+			));
+
+			// while messages are queued, the bridge is kept
+			XcmOverBridge::on_idle(2, Weight::MAX);
+			assert!(Bridges::<TestRuntime, ()>::contains_key(locations.bridge_id()));
+
+			// simulate delivery and confirmation of all queued messages
+			pallet_bridge_messages::OutboundLanes::<TestRuntime, ()>::mutate(
+				bridge.lane_id,
+				|data| {
+					let data = data.as_mut().unwrap();
+					for nonce in data.oldest_unpruned_nonce..=data.latest_generated_nonce {
+						pallet_bridge_messages::OutboundMessages::<TestRuntime, ()>::remove(
+							bp_messages::MessageKey { lane_id: bridge.lane_id, nonce },
+						);
+					}
+					data.latest_received_nonce = data.latest_generated_nonce;
+					data.oldest_unpruned_nonce = data.latest_generated_nonce + 1;
+				},
+			);
+
+			// now the bridge is drained and is pruned
+			XcmOverBridge::on_idle(2, Weight::MAX);
+			assert_eq!(Bridges::<TestRuntime, ()>::get(locations.bridge_id()), None);
+			assert!(!ClosingBridges::<TestRuntime, ()>::contains_key(locations.bridge_id()));
+			assert!(matches!(
+				System::events().last().map(|e| &e.event),
+				Some(RuntimeEvent::XcmOverBridge(Event::BridgePruned { pruned_messages: 0, .. }))
+			));
+		});
+	}
+
+	#[test]
+	fn closing_bridge_is_not_pruned_while_inbound_messages_are_unconfirmed() {
+		run_test(|| {
+			let origin = OpenBridgeOrigin::sibling_parachain_origin();
+			let (bridge, locations) = mock_open_bridge_from(origin.clone(), BridgeDeposit::get());
+			System::set_block_number(1);
+
+			// there's an unconfirmed inbound message
+			pallet_bridge_messages::InboundLanes::<TestRuntime, ()>::mutate(
+				bridge.lane_id,
+				|data| {
+					let data = data.as_mut().unwrap();
+					data.relayers.push_back(bp_messages::UnrewardedRelayer {
+						relayer: AccountId::from([1u8; 32]),
+						messages: bp_messages::DeliveredMessages::new(1),
+					});
+				},
+			);
+			assert_ok!(XcmOverBridge::close_bridge(
+				origin,
+				Box::new(locations.bridge_destination_universal_location().clone().into()),
+				0,
+			));
+
+			// the bridge is kept until the confirmation arrives
+			XcmOverBridge::on_idle(2, Weight::MAX);
+			assert!(Bridges::<TestRuntime, ()>::contains_key(locations.bridge_id()));
+
+			// .. or until the drain period is over
+			let drained_at = 1 + ClosingBridgeDrainPeriod::get();
+			System::set_block_number(drained_at);
+			XcmOverBridge::on_idle(drained_at, Weight::MAX);
+			assert_eq!(Bridges::<TestRuntime, ()>::get(locations.bridge_id()), None);
+		});
+	}
+
+	#[test]
+	fn migration_to_v1_registers_closed_bridges() {
+		use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+		run_test(|| {
+			let origin = OpenBridgeOrigin::sibling_parachain_origin();
+			let (_, locations) = mock_open_bridge_from(origin, BridgeDeposit::get());
+			System::set_block_number(7);
+
+			// bridges closed by the previous version are not registered in `ClosingBridges`
+			Bridges::<TestRuntime, ()>::mutate(locations.bridge_id(), |bridge| {
+				bridge.as_mut().unwrap().state = BridgeState::Closed;
+			});
+
+			migration::v1::UncheckedMigrationV0ToV1::<TestRuntime, ()>::on_runtime_upgrade();
+			assert_eq!(ClosingBridges::<TestRuntime, ()>::get(locations.bridge_id()), Some(7));
+		});
+	}
+
+	#[test]
+	fn bridge_status_works() {
+		run_test(|| {
+			let origin = OpenBridgeOrigin::sibling_parachain_origin();
+			let bridge_origin_relative_location = SiblingLocation::get();
+			let bridge_destination_universal_location =
+				VersionedInteriorLocation::from(bridged_asset_hub_universal_location());
+			let locations = XcmOverBridge::bridge_locations(
+				bridge_origin_relative_location.clone(),
+				bridged_asset_hub_universal_location(),
+			)
+			.unwrap();
+			let expected_lane_id: TestLaneIdType =
+				locations.calculate_lane_id(xcm::latest::VERSION).unwrap();
+
+			// before the bridge is opened, we still know its lane
+			assert_eq!(
+				XcmOverBridge::bridge_status(
+					bridge_origin_relative_location.clone(),
+					bridge_destination_universal_location.clone(),
+				),
+				Ok(BridgeStatus {
+					bridge_id: *locations.bridge_id(),
+					lane_id: expected_lane_id,
+					state: None,
+				}),
+			);
+
+			// after the bridge is opened, the same lane is used
+			mock_open_bridge_from(origin, BridgeDeposit::get());
+			assert_eq!(
+				XcmOverBridge::bridge_status(
+					bridge_origin_relative_location,
+					bridge_destination_universal_location,
+				),
+				Ok(BridgeStatus {
+					bridge_id: *locations.bridge_id(),
+					lane_id: expected_lane_id,
+					state: Some(BridgeState::Opened),
+				}),
+			);
+		});
+	}
+
 	#[test]
 	fn do_try_state_works() {
 		let bridge_origin_relative_location = SiblingLocation::get();
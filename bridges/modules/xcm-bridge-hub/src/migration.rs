@@ -24,7 +24,7 @@ use frame_support::{
 use xcm::prelude::{InteriorLocation, Location};
 
 /// The in-code storage version.
-pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 /// This migration does not modify storage but can be used to open a bridge and link it to the
 /// specified LaneId. This is useful when we want to open a bridge and use a custom LaneId instead
@@ -143,3 +143,73 @@ impl<
 		Ok(())
 	}
 }
+
+/// Migration from the storage version 0 to 1.
+///
+/// Before version 1, bridges, closed with queued outbound messages, have been left in the
+/// `BridgeState::Closed` state until the owner called `close_bridge` again. This migration
+/// registers all such bridges in the `ClosingBridges` map, so that they're drained and pruned
+/// on idle. The drain period of every such bridge starts at the migration block.
+pub mod v1 {
+	use super::*;
+	use crate::{BridgeState, Bridges, ClosingBridges};
+	use frame_support::traits::UncheckedOnRuntimeUpgrade;
+	use sp_std::marker::PhantomData;
+
+	/// Migrates the pallet storage to v1.
+	pub struct UncheckedMigrationV0ToV1<T, I>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for UncheckedMigrationV0ToV1<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut weight = T::DbWeight::get().reads(1);
+
+			for (bridge_id, bridge) in Bridges::<T, I>::iter() {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				if bridge.state != BridgeState::Closed {
+					continue
+				}
+
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				if ClosingBridges::<T, I>::contains_key(bridge_id) {
+					continue
+				}
+
+				log::info!(
+					target: LOG_TARGET,
+					"MigrationToV1 - registering closed bridge {bridge_id:?} with lane {:?}",
+					bridge.lane_id,
+				);
+				ClosingBridges::<T, I>::insert(bridge_id, now);
+				weight.saturating_accrue(T::DbWeight::get().writes(1));
+			}
+
+			weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: sp_std::vec::Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+			for (bridge_id, bridge) in Bridges::<T, I>::iter() {
+				frame_support::ensure!(
+					bridge.state != BridgeState::Closed ||
+						ClosingBridges::<T, I>::contains_key(bridge_id),
+					"Closed bridge is not registered in the ClosingBridges map!"
+				);
+			}
+
+			log::info!(target: LOG_TARGET, "MigrationToV1 - migrated all closed bridges");
+			Ok(())
+		}
+	}
+
+	/// [`UncheckedMigrationV0ToV1`] wrapped in a
+	/// [`VersionedMigration`](frame_support::migrations::VersionedMigration), ensuring the
+	/// migration is only performed when on-chain version is 0.
+	pub type MigrationToV1<T, I> = frame_support::migrations::VersionedMigration<
+		0,
+		1,
+		UncheckedMigrationV0ToV1<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
@@ -163,6 +163,7 @@ parameter_types! {
 	pub const NonBridgedRelayNetwork: NetworkId = NetworkId::ByGenesis(ROCOCO_GENESIS_HASH);
 
 	pub const BridgeDeposit: Balance = 100_000;
+	pub const ClosingBridgeDrainPeriod: u64 = 16;
 
 	// configuration for pallet_xcm_bridge_hub_router
 	pub BridgeHubLocation: Location = Here.into();
@@ -186,6 +187,7 @@ pub fn bridged_asset_hub_universal_location() -> InteriorLocation {
 
 impl pallet_xcm_bridge_hub::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
 
 	type UniversalLocation = UniversalLocation;
 	type BridgedNetwork = BridgedRelayNetworkLocation;
@@ -202,10 +204,14 @@ impl pallet_xcm_bridge_hub::Config for TestRuntime {
 	type Currency = Balances;
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type AllowWithoutBridgeDeposit = Equals<ParentRelayChainLocation>;
+	type ClosingBridgeDrainPeriod = ClosingBridgeDrainPeriod;
 
 	type LocalXcmChannelManager = TestLocalXcmChannelManager;
 
 	type BlobDispatcher = TestBlobDispatcher;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = TestBenchmarkHelper;
 }
 
 impl pallet_xcm_bridge_hub_router::Config<()> for TestRuntime {
@@ -356,6 +362,17 @@ impl OpenBridgeOrigin {
 	}
 }
 
+/// Benchmark helper that opens bridges from the sibling parachain.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::BenchmarkHelper<RuntimeOrigin> for TestBenchmarkHelper {
+	fn open_bridge_origin() -> Option<RuntimeOrigin> {
+		Some(OpenBridgeOrigin::sibling_parachain_origin())
+	}
+}
+
 impl EnsureOrigin<RuntimeOrigin> for OpenBridgeOrigin {
 	type Success = Location;
 
@@ -555,10 +572,14 @@ impl MessageDispatch for TestMessageDispatch {
 	}
 }
 
-/// Run pallet test.
-pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
 	sp_io::TestExternalities::new(
 		frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap(),
 	)
-	.execute_with(test)
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
 }
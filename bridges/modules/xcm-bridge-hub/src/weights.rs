@@ -0,0 +1,479 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for pallet_xcm_bridge_hub.
+//!
+//! Here are weights of the pallet calls and of the bookkeeping, that the pallet performs when
+//! messages are exported to or confirmed by the bridged network and when closed bridges are
+//! pruned.
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_xcm_bridge_hub.
+pub trait WeightInfo {
+	fn open_bridge() -> Weight;
+	fn close_bridge(n: u32) -> Weight;
+	fn note_enqueued_message() -> Weight;
+	fn forget_delivered_messages(n: u32) -> Weight;
+	fn check_closing_bridge() -> Weight;
+	fn prune_closing_bridge(n: u32) -> Weight;
+}
+
+/// Weights for `pallet_xcm_bridge_hub` that are generated using one of the Bridge testnets.
+///
+/// Those weights are test only and must never be used in production. They are not produced by
+/// the benchmark CLI yet - they are estimated using storage accesses of the correspondent
+/// benchmarks.
+pub struct BridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	///
+	/// Proof: XcmOverBridge Bridges (max_values: None, max_size: None, mode: Measured)
+	///
+	/// Storage: Balances Holds (r:1 w:1)
+	///
+	/// Proof: Balances Holds (max_values: None, max_size: Some(949), added: 3424, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:1 w:1)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge LaneToBridge (r:1 w:1)
+	///
+	/// Proof: XcmOverBridge LaneToBridge (max_values: None, max_size: Some(64), added: 2539,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages InboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages InboundLanes (max_values: None, max_size: Some(49208), added: 51683,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages OutboundLanes (max_values: None, max_size: Some(73), added: 2548,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	///
+	/// Proof: BridgeMessages OutboundMessages (max_values: None, max_size: Some(65596), added:
+	/// 68071, mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge OutboundMessagesEnqueuedAt (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge OutboundMessagesEnqueuedAt (max_values: None, max_size: Some(52),
+	/// added: 2527, mode: MaxEncodedLen)
+	fn open_bridge() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1015`
+		//  Estimated: `52673`
+		Weight::from_parts(72_000_000, 52673)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	///
+	/// Proof: XcmOverBridge Bridges (max_values: None, max_size: None, mode: Measured)
+	///
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages OutboundLanes (max_values: None, max_size: Some(73), added: 2548,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages InboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages InboundLanes (max_values: None, max_size: Some(49208), added: 51683,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge ClosingBridges (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge ClosingBridges (max_values: None, max_size: Some(36), added: 2511,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:n)
+	///
+	/// Proof: BridgeMessages OutboundMessages (max_values: None, max_size: Some(65596), added:
+	/// 68071, mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge OutboundMessagesEnqueuedAt (r:0 w:n)
+	///
+	/// Proof: XcmOverBridge OutboundMessagesEnqueuedAt (max_values: None, max_size: Some(52),
+	/// added: 2527, mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge LaneToBridge (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge LaneToBridge (max_values: None, max_size: Some(64), added: 2539,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: Balances Holds (r:1 w:1)
+	///
+	/// Proof: Balances Holds (max_values: None, max_size: Some(949), added: 3424, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:1 w:1)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	///
+	/// The range of component `n` is `[0, 1024]`.
+	fn close_bridge(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1484 + n * (45 ±0)`
+		//  Estimated: `52673`
+		Weight::from_parts(80_000_000, 52673)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: XcmOverBridge OutboundMessagesEnqueuedAt (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge OutboundMessagesEnqueuedAt (max_values: None, max_size: Some(52),
+	/// added: 2527, mode: MaxEncodedLen)
+	fn note_enqueued_message() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		Weight::from_parts(3_884_000, 0).saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:0)
+	///
+	/// Proof: BridgeMessages OutboundLanes (max_values: None, max_size: Some(73), added: 2548,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge OutboundMessagesEnqueuedAt (r:n+1 w:n)
+	///
+	/// Proof: XcmOverBridge OutboundMessagesEnqueuedAt (max_values: None, max_size: Some(52),
+	/// added: 2527, mode: Measured)
+	///
+	/// The range of component `n` is `[1, 4096]`.
+	fn forget_delivered_messages(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `133 + n * (52 ±0)`
+		//  Estimated: `3538 + n * (79 ±0)`
+		Weight::from_parts(7_245_000, 3538)
+			.saturating_add(Weight::from_parts(2_912_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 79).saturating_mul(n.into()))
+	}
+	/// Storage: XcmOverBridge ClosingBridges (r:1 w:0)
+	///
+	/// Proof: XcmOverBridge ClosingBridges (max_values: None, max_size: Some(36), added: 2511,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge Bridges (r:1 w:0)
+	///
+	/// Proof: XcmOverBridge Bridges (max_values: None, max_size: None, mode: Measured)
+	///
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:0)
+	///
+	/// Proof: BridgeMessages OutboundLanes (max_values: None, max_size: Some(73), added: 2548,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages InboundLanes (r:1 w:0)
+	///
+	/// Proof: BridgeMessages InboundLanes (max_values: None, max_size: Some(49208), added: 51683,
+	/// mode: MaxEncodedLen)
+	fn check_closing_bridge() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1117`
+		//  Estimated: `52673`
+		Weight::from_parts(21_405_000, 52673).saturating_add(T::DbWeight::get().reads(4_u64))
+	}
+	/// Storage: BridgeMessages InboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages InboundLanes (max_values: None, max_size: Some(49208), added: 51683,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages OutboundLanes (max_values: None, max_size: Some(73), added: 2548,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:n)
+	///
+	/// Proof: BridgeMessages OutboundMessages (max_values: None, max_size: Some(65596), added:
+	/// 68071, mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge OutboundMessagesEnqueuedAt (r:0 w:n)
+	///
+	/// Proof: XcmOverBridge OutboundMessagesEnqueuedAt (max_values: None, max_size: Some(52),
+	/// added: 2527, mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge Bridges (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge Bridges (max_values: None, max_size: None, mode: Measured)
+	///
+	/// Storage: XcmOverBridge LaneToBridge (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge LaneToBridge (max_values: None, max_size: Some(64), added: 2539,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge ClosingBridges (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge ClosingBridges (max_values: None, max_size: Some(36), added: 2511,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: Balances Holds (r:1 w:1)
+	///
+	/// Proof: Balances Holds (max_values: None, max_size: Some(949), added: 3424, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:1 w:1)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	///
+	/// The range of component `n` is `[0, 1024]`.
+	fn prune_closing_bridge(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1484 + n * (45 ±0)`
+		//  Estimated: `52673`
+		Weight::from_parts(63_714_000, 52673)
+			.saturating_add(Weight::from_parts(5_126_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	///
+	/// Proof: XcmOverBridge Bridges (max_values: None, max_size: None, mode: Measured)
+	///
+	/// Storage: Balances Holds (r:1 w:1)
+	///
+	/// Proof: Balances Holds (max_values: None, max_size: Some(949), added: 3424, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:1 w:1)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge LaneToBridge (r:1 w:1)
+	///
+	/// Proof: XcmOverBridge LaneToBridge (max_values: None, max_size: Some(64), added: 2539,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages InboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages InboundLanes (max_values: None, max_size: Some(49208), added: 51683,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages OutboundLanes (max_values: None, max_size: Some(73), added: 2548,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:1)
+	///
+	/// Proof: BridgeMessages OutboundMessages (max_values: None, max_size: Some(65596), added:
+	/// 68071, mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge OutboundMessagesEnqueuedAt (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge OutboundMessagesEnqueuedAt (max_values: None, max_size: Some(52),
+	/// added: 2527, mode: MaxEncodedLen)
+	fn open_bridge() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1015`
+		//  Estimated: `52673`
+		Weight::from_parts(72_000_000, 52673)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: XcmOverBridge Bridges (r:1 w:1)
+	///
+	/// Proof: XcmOverBridge Bridges (max_values: None, max_size: None, mode: Measured)
+	///
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages OutboundLanes (max_values: None, max_size: Some(73), added: 2548,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages InboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages InboundLanes (max_values: None, max_size: Some(49208), added: 51683,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge ClosingBridges (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge ClosingBridges (max_values: None, max_size: Some(36), added: 2511,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:n)
+	///
+	/// Proof: BridgeMessages OutboundMessages (max_values: None, max_size: Some(65596), added:
+	/// 68071, mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge OutboundMessagesEnqueuedAt (r:0 w:n)
+	///
+	/// Proof: XcmOverBridge OutboundMessagesEnqueuedAt (max_values: None, max_size: Some(52),
+	/// added: 2527, mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge LaneToBridge (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge LaneToBridge (max_values: None, max_size: Some(64), added: 2539,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: Balances Holds (r:1 w:1)
+	///
+	/// Proof: Balances Holds (max_values: None, max_size: Some(949), added: 3424, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:1 w:1)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	///
+	/// The range of component `n` is `[0, 1024]`.
+	fn close_bridge(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1484 + n * (45 ±0)`
+		//  Estimated: `52673`
+		Weight::from_parts(80_000_000, 52673)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: XcmOverBridge OutboundMessagesEnqueuedAt (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge OutboundMessagesEnqueuedAt (max_values: None, max_size: Some(52),
+	/// added: 2527, mode: MaxEncodedLen)
+	fn note_enqueued_message() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		Weight::from_parts(3_884_000, 0).saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:0)
+	///
+	/// Proof: BridgeMessages OutboundLanes (max_values: None, max_size: Some(73), added: 2548,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge OutboundMessagesEnqueuedAt (r:n+1 w:n)
+	///
+	/// Proof: XcmOverBridge OutboundMessagesEnqueuedAt (max_values: None, max_size: Some(52),
+	/// added: 2527, mode: Measured)
+	///
+	/// The range of component `n` is `[1, 4096]`.
+	fn forget_delivered_messages(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `133 + n * (52 ±0)`
+		//  Estimated: `3538 + n * (79 ±0)`
+		Weight::from_parts(7_245_000, 3538)
+			.saturating_add(Weight::from_parts(2_912_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 79).saturating_mul(n.into()))
+	}
+	/// Storage: XcmOverBridge ClosingBridges (r:1 w:0)
+	///
+	/// Proof: XcmOverBridge ClosingBridges (max_values: None, max_size: Some(36), added: 2511,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge Bridges (r:1 w:0)
+	///
+	/// Proof: XcmOverBridge Bridges (max_values: None, max_size: None, mode: Measured)
+	///
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:0)
+	///
+	/// Proof: BridgeMessages OutboundLanes (max_values: None, max_size: Some(73), added: 2548,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages InboundLanes (r:1 w:0)
+	///
+	/// Proof: BridgeMessages InboundLanes (max_values: None, max_size: Some(49208), added: 51683,
+	/// mode: MaxEncodedLen)
+	fn check_closing_bridge() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1117`
+		//  Estimated: `52673`
+		Weight::from_parts(21_405_000, 52673).saturating_add(RocksDbWeight::get().reads(4_u64))
+	}
+	/// Storage: BridgeMessages InboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages InboundLanes (max_values: None, max_size: Some(49208), added: 51683,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages OutboundLanes (r:1 w:1)
+	///
+	/// Proof: BridgeMessages OutboundLanes (max_values: None, max_size: Some(73), added: 2548,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: BridgeMessages OutboundMessages (r:0 w:n)
+	///
+	/// Proof: BridgeMessages OutboundMessages (max_values: None, max_size: Some(65596), added:
+	/// 68071, mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge OutboundMessagesEnqueuedAt (r:0 w:n)
+	///
+	/// Proof: XcmOverBridge OutboundMessagesEnqueuedAt (max_values: None, max_size: Some(52),
+	/// added: 2527, mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge Bridges (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge Bridges (max_values: None, max_size: None, mode: Measured)
+	///
+	/// Storage: XcmOverBridge LaneToBridge (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge LaneToBridge (max_values: None, max_size: Some(64), added: 2539,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: XcmOverBridge ClosingBridges (r:0 w:1)
+	///
+	/// Proof: XcmOverBridge ClosingBridges (max_values: None, max_size: Some(36), added: 2511,
+	/// mode: MaxEncodedLen)
+	///
+	/// Storage: Balances Holds (r:1 w:1)
+	///
+	/// Proof: Balances Holds (max_values: None, max_size: Some(949), added: 3424, mode:
+	/// MaxEncodedLen)
+	///
+	/// Storage: System Account (r:1 w:1)
+	///
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode:
+	/// MaxEncodedLen)
+	///
+	/// The range of component `n` is `[0, 1024]`.
+	fn prune_closing_bridge(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1484 + n * (45 ±0)`
+		//  Estimated: `52673`
+		Weight::from_parts(63_714_000, 52673)
+			.saturating_add(Weight::from_parts(5_126_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+}
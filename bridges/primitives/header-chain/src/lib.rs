@@ -43,6 +43,7 @@ pub use call_info::{BridgeGrandpaCall, BridgeGrandpaCallOf, SubmitFinalityProofI
 mod call_info;
 
 pub mod justification;
+pub mod replay;
 pub mod storage_keys;
 
 /// Header chain error.
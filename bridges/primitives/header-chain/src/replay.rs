@@ -0,0 +1,300 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline replay of GRANDPA finality proofs against the bridge header chain state.
+//!
+//! The replay performs the same checks as the `submit_finality_proof` call of the
+//! `pallet-bridge-grandpa`, but without any runtime. It may be used to find out why
+//! some finality proof has been (or would be) rejected by the pallet.
+
+use crate::{
+	justification::{verify_justification, GrandpaJustification, JustificationVerificationError},
+	submit_finality_proof_limits_extras, AuthoritySet, ChainWithGrandpa, GrandpaConsensusLogReader,
+	InitializationData,
+};
+
+use bp_runtime::{BlockNumberOf, HashOf, HeaderId, HeaderOf};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_consensus_grandpa::SetId;
+use sp_runtime::{
+	traits::{CheckedSub, Header as HeaderT, Zero},
+	RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+/// Finality proof of the bridged header, exported from the bridged chain node.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ReplayedFinalityProof<Header: HeaderT> {
+	/// The header that is finalized by the `justification`.
+	pub header: Header,
+	/// The justification of the `header`.
+	pub justification: GrandpaJustification<Header>,
+	/// The `current_set_id` argument of the `submit_finality_proof_ex` call. `None` for the
+	/// `submit_finality_proof` call.
+	pub current_set_id: Option<SetId>,
+	/// Whether the submitter expects free execution of the `submit_finality_proof_ex` call.
+	/// If `true`, the proof is rejected if it can't be imported for free.
+	pub is_free_execution_expected: bool,
+}
+
+/// Runtime configuration and per-block state of the `pallet-bridge-grandpa`, used by the replay.
+#[derive(Clone, Copy, Decode, Default, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ReplayParams {
+	/// Value of the `FreeHeadersInterval` of the pallet configuration.
+	pub free_headers_interval: Option<u32>,
+	/// Value of the `FreeHeadersRemaining` of the pallet in the block, where the proofs are
+	/// submitted. It is `MaxFreeHeadersPerBlock` at the beginning of the block and `None` when
+	/// the proofs are validated outside of the block, e.g. by the transaction pool.
+	pub free_headers_remaining: Option<u32>,
+}
+
+impl ReplayParams {
+	// the `SubmitFinalityProofHelper::has_free_header_slots`
+	fn has_free_header_slots(&self) -> bool {
+		self.free_headers_remaining.unwrap_or(u32::MAX) > 0
+	}
+
+	// the `on_free_header_imported`
+	fn on_free_header_imported(&mut self) {
+		if let Some(ref mut free_headers_remaining) = self.free_headers_remaining {
+			*free_headers_remaining = free_headers_remaining.saturating_sub(1);
+		}
+	}
+}
+
+/// State of the bridge header chain, as it would be stored by the `pallet-bridge-grandpa`.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct HeaderChainState<Hash, Number> {
+	/// Best finalized header of the bridged chain.
+	pub best_finalized: HeaderId<Hash, Number>,
+	/// Current authority set of the bridged chain.
+	pub authority_set: AuthoritySet,
+}
+
+impl<H: HeaderT> From<InitializationData<H>> for HeaderChainState<H::Hash, H::Number> {
+	fn from(data: InitializationData<H>) -> Self {
+		HeaderChainState {
+			best_finalized: HeaderId(*data.header.number(), data.header.hash()),
+			authority_set: AuthoritySet::new(data.authority_list, data.set_id),
+		}
+	}
+}
+
+/// Error of the finality proof replay. Every variant corresponds to a check of the
+/// `submit_finality_proof` call.
+#[derive(Eq, PartialEq, RuntimeDebug)]
+pub enum ReplayError<Number> {
+	/// The header is not better than the best finalized header.
+	OldHeader {
+		/// Number of the best finalized header.
+		best_finalized: Number,
+		/// Number of the replayed header.
+		header: Number,
+	},
+	/// The `current_set_id` of the call doesn't match the current authority set id.
+	InvalidAuthoritySetId {
+		/// Id of the current authority set.
+		expected: SetId,
+		/// The `current_set_id` of the call.
+		actual: SetId,
+	},
+	/// The free execution is expected, but no more free headers may be imported in the block.
+	FreeHeadersLimitExceded,
+	/// The free execution is expected, but the header is not mandatory and the difference
+	/// between best finalized and replayed header numbers is below the free headers interval.
+	BelowFreeHeaderInterval {
+		/// Number of headers between the best finalized header and the replayed header.
+		improved_by: Number,
+		/// The free headers interval.
+		free_headers_interval: u32,
+	},
+	/// The free execution is expected, but the call doesn't fit the limits.
+	HeaderOverflowLimits {
+		/// If true, the call weight is larger than the expected.
+		is_weight_limit_exceeded: bool,
+		/// Extra size (in bytes) of the call.
+		extra_size: u32,
+	},
+	/// The justification is invalid.
+	InvalidJustification(JustificationVerificationError),
+	/// The header contains a forced change or a scheduled change with non-zero delay.
+	UnsupportedScheduledChange,
+	/// The header enacts authority set, that has too many authorities.
+	TooManyAuthoritiesInSet {
+		/// Number of authorities in the new set.
+		authorities: u32,
+	},
+}
+
+/// Result of the successful finality proof replay.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct ReplayOutcome<Number> {
+	/// Number of headers between the previous best finalized header and the replayed header.
+	pub improved_by: Number,
+	/// If true, the call weight is larger than the expected. Submitter would pay for extra
+	/// weight.
+	pub is_weight_limit_exceeded: bool,
+	/// Extra size (in bytes) of the call. Submitter would pay for extra bytes.
+	pub extra_size: u32,
+	/// If true, the submitter would be refunded the call fee.
+	pub is_free: bool,
+	/// New authority set, enacted by the header.
+	pub new_authority_set: Option<AuthoritySet>,
+}
+
+/// Report of the replay of multiple finality proofs.
+#[derive(Eq, PartialEq, RuntimeDebug)]
+pub struct ReplayReport<Hash, Number> {
+	/// Outcomes of successfully replayed proofs.
+	pub outcomes: Vec<ReplayOutcome<Number>>,
+	/// Index and the error of the first rejected proof. Proofs after that are not replayed.
+	pub failure: Option<(usize, ReplayError<Number>)>,
+	/// Final state of the bridge header chain.
+	pub state: HeaderChainState<Hash, Number>,
+	/// Final runtime state of the pallet.
+	pub params: ReplayParams,
+}
+
+/// Replay finality proof against the header chain `state` and the pallet `params`. If the proof is
+/// accepted, the `state` and `params` are updated. Otherwise, they are left unchanged.
+pub fn replay_finality_proof<C: ChainWithGrandpa>(
+	state: &mut HeaderChainState<HashOf<C>, BlockNumberOf<C>>,
+	params: &mut ReplayParams,
+	proof: &ReplayedFinalityProof<HeaderOf<C>>,
+) -> Result<ReplayOutcome<BlockNumberOf<C>>, ReplayError<BlockNumberOf<C>>> {
+	let (hash, number) = (proof.header.hash(), *proof.header.number());
+
+	// the `SubmitFinalityProofHelper::check_obsolete`
+	let improved_by = match number.checked_sub(&state.best_finalized.number()) {
+		Some(improved_by) if improved_by > Zero::zero() => improved_by,
+		_ =>
+			return Err(ReplayError::OldHeader {
+				best_finalized: state.best_finalized.number(),
+				header: number,
+			}),
+	};
+	if let Some(current_set_id) = proof.current_set_id {
+		if current_set_id != state.authority_set.set_id {
+			return Err(ReplayError::InvalidAuthoritySetId {
+				expected: state.authority_set.set_id,
+				actual: current_set_id,
+			})
+		}
+	}
+
+	// the `SubmitFinalityProofHelper::check_obsolete_from_extension`
+	let extras = submit_finality_proof_limits_extras::<C>(&proof.header, &proof.justification);
+	let fits_limits = !extras.is_weight_limit_exceeded && extras.extra_size == 0;
+	let is_below_free_headers_interval = |free_headers_interval: u32| {
+		!extras.is_mandatory_finality_target && improved_by < free_headers_interval.into()
+	};
+	if proof.is_free_execution_expected {
+		if !params.has_free_header_slots() {
+			return Err(ReplayError::FreeHeadersLimitExceded)
+		}
+		if let Some(free_headers_interval) = params.free_headers_interval {
+			if is_below_free_headers_interval(free_headers_interval) {
+				return Err(ReplayError::BelowFreeHeaderInterval {
+					improved_by,
+					free_headers_interval,
+				})
+			}
+		}
+		if !fits_limits {
+			return Err(ReplayError::HeaderOverflowLimits {
+				is_weight_limit_exceeded: extras.is_weight_limit_exceeded,
+				extra_size: extras.extra_size,
+			})
+		}
+	}
+
+	// the `verify_justification`
+	let context = state
+		.authority_set
+		.clone()
+		.try_into()
+		.map_err(ReplayError::InvalidJustification)?;
+	verify_justification::<HeaderOf<C>>((hash, number), &context, &proof.justification)
+		.map_err(ReplayError::InvalidJustification)?;
+
+	// the `try_enact_authority_change`
+	let digest = proof.header.digest();
+	if GrandpaConsensusLogReader::<BlockNumberOf<C>>::find_forced_change(digest).is_some() {
+		return Err(ReplayError::UnsupportedScheduledChange)
+	}
+	let new_authority_set =
+		match GrandpaConsensusLogReader::<BlockNumberOf<C>>::find_scheduled_change(digest) {
+			Some(change) => {
+				if !change.delay.is_zero() {
+					return Err(ReplayError::UnsupportedScheduledChange)
+				}
+				let authorities = change.next_authorities.len() as u32;
+				if authorities > C::MAX_AUTHORITIES_COUNT {
+					return Err(ReplayError::TooManyAuthoritiesInSet { authorities })
+				}
+				Some(AuthoritySet::new(change.next_authorities, state.authority_set.set_id + 1))
+			},
+			None => None,
+		};
+
+	// the `may_refund_call_fee`
+	let is_free = params.free_headers_remaining.unwrap_or(0) > 0 &&
+		fits_limits &&
+		(extras.is_mandatory_finality_target ||
+			params
+				.free_headers_interval
+				.map_or(false, |interval| !is_below_free_headers_interval(interval)));
+
+	// the `insert_header`
+	state.best_finalized = HeaderId(number, hash);
+	if let Some(ref new_authority_set) = new_authority_set {
+		state.authority_set = new_authority_set.clone();
+	}
+	if is_free {
+		params.on_free_header_imported();
+	}
+
+	Ok(ReplayOutcome {
+		improved_by,
+		is_weight_limit_exceeded: extras.is_weight_limit_exceeded,
+		extra_size: extras.extra_size,
+		is_free,
+		new_authority_set,
+	})
+}
+
+/// Replay finality proofs against the header chain, starting from the `initial_state` and the
+/// pallet `initial_params`. Stops at the first rejected proof.
+pub fn replay_finality_proofs<C: ChainWithGrandpa>(
+	initial_state: HeaderChainState<HashOf<C>, BlockNumberOf<C>>,
+	initial_params: ReplayParams,
+	proofs: &[ReplayedFinalityProof<HeaderOf<C>>],
+) -> ReplayReport<HashOf<C>, BlockNumberOf<C>> {
+	let mut state = initial_state;
+	let mut params = initial_params;
+	let mut outcomes = Vec::with_capacity(proofs.len());
+	for (index, proof) in proofs.iter().enumerate() {
+		match replay_finality_proof::<C>(&mut state, &mut params, proof) {
+			Ok(outcome) => outcomes.push(outcome),
+			Err(error) =>
+				return ReplayReport { outcomes, failure: Some((index, error)), state, params },
+		}
+	}
+
+	ReplayReport { outcomes, failure: None, state, params }
+}
@@ -0,0 +1,284 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the offline replay of GRANDPA finality proofs.
+
+use bp_header_chain::{
+	justification::{JustificationVerificationError, PrecommitError},
+	replay::{
+		replay_finality_proofs, HeaderChainState, ReplayError, ReplayParams, ReplayedFinalityProof,
+	},
+	AuthoritySet, ChainWithGrandpa,
+};
+use bp_runtime::{Chain, ChainId, HeaderId};
+use bp_test_utils::*;
+use codec::Encode;
+use frame_support::weights::Weight;
+use sp_consensus_grandpa::{ConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID};
+use sp_runtime::{
+	traits::{BlakeTwo256, Header as HeaderT},
+	DigestItem, MultiSignature, StateVersion,
+};
+
+type TestHeader = sp_runtime::testing::Header;
+
+struct TestChain;
+
+impl Chain for TestChain {
+	const ID: ChainId = *b"test";
+
+	type BlockNumber = u64;
+	type Hash = sp_core::H256;
+	type Hasher = BlakeTwo256;
+	type Header = TestHeader;
+	type AccountId = u64;
+	type Balance = u64;
+	type Nonce = u64;
+	type Signature = MultiSignature;
+
+	const STATE_VERSION: StateVersion = StateVersion::V1;
+
+	fn max_extrinsic_size() -> u32 {
+		0
+	}
+	fn max_extrinsic_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+impl ChainWithGrandpa for TestChain {
+	const WITH_CHAIN_GRANDPA_PALLET_NAME: &'static str = "Test";
+	const MAX_AUTHORITIES_COUNT: u32 = 3;
+	const REASONABLE_HEADERS_IN_JUSTIFICATION_ANCESTRY: u32 = 2;
+	const MAX_MANDATORY_HEADER_SIZE: u32 = 100_000;
+	const AVERAGE_HEADER_SIZE: u32 = 1_024;
+}
+
+fn initial_state() -> HeaderChainState<sp_core::H256, u64> {
+	let header = test_header::<TestHeader>(0);
+	HeaderChainState {
+		best_finalized: HeaderId(0, header.hash()),
+		authority_set: AuthoritySet::new(authority_list(), TEST_GRANDPA_SET_ID),
+	}
+}
+
+fn finality_proof(header: TestHeader, set_id: u64) -> ReplayedFinalityProof<TestHeader> {
+	let justification = make_justification_for_header(JustificationGeneratorParams {
+		header: header.clone(),
+		set_id,
+		..Default::default()
+	});
+	ReplayedFinalityProof {
+		header,
+		justification,
+		current_set_id: None,
+		is_free_execution_expected: false,
+	}
+}
+
+fn header_with_scheduled_change(number: u64, delay: u64, authorities: u16) -> TestHeader {
+	let mut header = test_header::<TestHeader>(number);
+	let next_authorities = accounts(authorities).into_iter().map(|a| (a.into(), 1)).collect();
+	header.digest_mut().push(DigestItem::Consensus(
+		GRANDPA_ENGINE_ID,
+		ConsensusLog::ScheduledChange(ScheduledChange { next_authorities, delay }).encode(),
+	));
+	header
+}
+
+#[test]
+fn valid_finality_proofs_are_replayed() {
+	let proofs = vec![
+		finality_proof(test_header(1), TEST_GRANDPA_SET_ID),
+		finality_proof(header_with_scheduled_change(5, 0, 3), TEST_GRANDPA_SET_ID),
+		finality_proof(test_header(8), TEST_GRANDPA_SET_ID + 1),
+	];
+
+	let report =
+		replay_finality_proofs::<TestChain>(initial_state(), ReplayParams::default(), &proofs);
+	assert_eq!(report.failure, None);
+	assert_eq!(report.outcomes.len(), 3);
+	assert_eq!(report.outcomes[1].improved_by, 4);
+	assert_eq!(
+		report.outcomes[1].new_authority_set.as_ref().map(|set| set.set_id),
+		Some(TEST_GRANDPA_SET_ID + 1),
+	);
+	assert_eq!(report.state.best_finalized, HeaderId(8, test_header::<TestHeader>(8).hash()));
+	assert_eq!(report.state.authority_set.set_id, TEST_GRANDPA_SET_ID + 1);
+}
+
+#[test]
+fn replay_stops_at_obsolete_header() {
+	let proofs = vec![
+		finality_proof(test_header(2), TEST_GRANDPA_SET_ID),
+		finality_proof(test_header(1), TEST_GRANDPA_SET_ID),
+		finality_proof(test_header(3), TEST_GRANDPA_SET_ID),
+	];
+
+	let report =
+		replay_finality_proofs::<TestChain>(initial_state(), ReplayParams::default(), &proofs);
+	assert_eq!(report.outcomes.len(), 1);
+	assert_eq!(report.failure, Some((1, ReplayError::OldHeader { best_finalized: 2, header: 1 })));
+	assert_eq!(report.state.best_finalized.number(), 2);
+}
+
+#[test]
+fn replay_reports_invalid_justification() {
+	// justification is signed by the next authority set
+	let proofs = vec![finality_proof(test_header(1), TEST_GRANDPA_SET_ID + 1)];
+	let report =
+		replay_finality_proofs::<TestChain>(initial_state(), ReplayParams::default(), &proofs);
+	assert_eq!(
+		report.failure,
+		Some((
+			0,
+			ReplayError::InvalidJustification(JustificationVerificationError::Precommit(
+				PrecommitError::InvalidAuthoritySignature
+			))
+		)),
+	);
+	assert_eq!(report.state, initial_state());
+
+	// justification of another header
+	let mut proof = finality_proof(test_header(1), TEST_GRANDPA_SET_ID);
+	proof.header = test_header(2);
+	let report =
+		replay_finality_proofs::<TestChain>(initial_state(), ReplayParams::default(), &[proof]);
+	assert_eq!(
+		report.failure,
+		Some((
+			0,
+			ReplayError::InvalidJustification(
+				JustificationVerificationError::InvalidJustificationTarget
+			)
+		)),
+	);
+}
+
+#[test]
+fn replay_reports_unsupported_authority_set_changes() {
+	let proofs = vec![finality_proof(header_with_scheduled_change(1, 1, 3), TEST_GRANDPA_SET_ID)];
+	let report =
+		replay_finality_proofs::<TestChain>(initial_state(), ReplayParams::default(), &proofs);
+	assert_eq!(report.failure, Some((0, ReplayError::UnsupportedScheduledChange)));
+
+	let proofs = vec![finality_proof(header_with_scheduled_change(1, 0, 4), TEST_GRANDPA_SET_ID)];
+	let report =
+		replay_finality_proofs::<TestChain>(initial_state(), ReplayParams::default(), &proofs);
+	assert_eq!(report.failure, Some((0, ReplayError::TooManyAuthoritiesInSet { authorities: 4 })));
+}
+
+#[test]
+fn replay_reports_free_headers_overflowing_limits() {
+	let header = test_header::<TestHeader>(1);
+	let justification = make_justification_for_header(JustificationGeneratorParams {
+		header: header.clone(),
+		ancestors: TestChain::REASONABLE_HEADERS_IN_JUSTIFICATION_ANCESTRY + 1,
+		..Default::default()
+	});
+	let mut proof = ReplayedFinalityProof {
+		header,
+		justification,
+		current_set_id: Some(TEST_GRANDPA_SET_ID),
+		is_free_execution_expected: true,
+	};
+
+	let report = replay_finality_proofs::<TestChain>(
+		initial_state(),
+		ReplayParams::default(),
+		&[proof.clone()],
+	);
+	assert_eq!(
+		report.failure,
+		Some((
+			0,
+			ReplayError::HeaderOverflowLimits { is_weight_limit_exceeded: true, extra_size: 0 }
+		)),
+	);
+
+	proof.is_free_execution_expected = false;
+	let report =
+		replay_finality_proofs::<TestChain>(initial_state(), ReplayParams::default(), &[proof]);
+	assert_eq!(report.failure, None);
+	assert!(report.outcomes[0].is_weight_limit_exceeded);
+}
+
+#[test]
+fn replay_reports_invalid_authority_set_id() {
+	let mut proof = finality_proof(test_header(1), TEST_GRANDPA_SET_ID);
+	proof.current_set_id = Some(TEST_GRANDPA_SET_ID + 1);
+
+	let report =
+		replay_finality_proofs::<TestChain>(initial_state(), ReplayParams::default(), &[proof]);
+	assert_eq!(
+		report.failure,
+		Some((
+			0,
+			ReplayError::InvalidAuthoritySetId {
+				expected: TEST_GRANDPA_SET_ID,
+				actual: TEST_GRANDPA_SET_ID + 1
+			}
+		)),
+	);
+	assert_eq!(report.state, initial_state());
+}
+
+#[test]
+fn replay_checks_free_headers_limits() {
+	let free_proof = |header, set_id| {
+		let mut proof = finality_proof(header, set_id);
+		proof.current_set_id = Some(set_id);
+		proof.is_free_execution_expected = true;
+		proof
+	};
+	let params = ReplayParams { free_headers_interval: Some(5), free_headers_remaining: Some(2) };
+
+	// non-mandatory header below the free headers interval
+	let report = replay_finality_proofs::<TestChain>(
+		initial_state(),
+		params,
+		&[free_proof(test_header(3), TEST_GRANDPA_SET_ID)],
+	);
+	assert_eq!(
+		report.failure,
+		Some((
+			0,
+			ReplayError::BelowFreeHeaderInterval { improved_by: 3, free_headers_interval: 5 }
+		)),
+	);
+	assert_eq!(report.params, params);
+
+	// mandatory header below the free headers interval and non-mandatory header at the free
+	// headers interval are imported for free, using all free slots
+	let proofs = vec![
+		free_proof(header_with_scheduled_change(1, 0, 3), TEST_GRANDPA_SET_ID),
+		free_proof(test_header(6), TEST_GRANDPA_SET_ID + 1),
+		free_proof(test_header(11), TEST_GRANDPA_SET_ID + 1),
+	];
+	let report = replay_finality_proofs::<TestChain>(initial_state(), params, &proofs);
+	assert_eq!(report.outcomes.len(), 2);
+	assert!(report.outcomes.iter().all(|outcome| outcome.is_free));
+	assert_eq!(report.failure, Some((2, ReplayError::FreeHeadersLimitExceded)));
+	assert_eq!(report.params.free_headers_remaining, Some(0));
+
+	// headers, that are not expected to be free, don't use free slots
+	let mut proof = free_proof(test_header(3), TEST_GRANDPA_SET_ID);
+	proof.is_free_execution_expected = false;
+	let report = replay_finality_proofs::<TestChain>(initial_state(), params, &[proof]);
+	assert_eq!(report.failure, None);
+	assert!(!report.outcomes[0].is_free);
+	assert_eq!(report.params, params);
+}
@@ -21,3 +21,4 @@ mod justification {
 }
 
 mod implementation_match;
+mod replay;
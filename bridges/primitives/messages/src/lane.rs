@@ -65,9 +65,16 @@ pub struct LegacyLaneId(pub [u8; 4]);
 
 impl LaneIdType for LegacyLaneId {
 	/// Create lane identifier from two locations.
-	fn try_new<T: Ord + Encode>(_endpoint1: T, _endpoint2: T) -> Result<Self, ()> {
-		// we don't support this for `LegacyLaneId`, because it was hard-coded before
-		Err(())
+	///
+	/// Lanes of static bridges have been hard-coded before. Lanes of dynamic bridges are
+	/// using first four bytes of the [`HashedLaneId`] of the same endpoints, so both sides of
+	/// the bridge are computing the same identifier. Such short identifiers may collide with
+	/// identifiers of other lanes, so the caller must ensure that the lane does not exist yet.
+	fn try_new<T: Ord + Encode>(endpoint1: T, endpoint2: T) -> Result<Self, ()> {
+		let hashed_lane_id = HashedLaneId::try_new(endpoint1, endpoint2)?;
+		let mut lane_id = [0u8; 4];
+		lane_id.copy_from_slice(&hashed_lane_id.inner().as_bytes()[..4]);
+		Ok(Self(lane_id))
 	}
 }
 
@@ -292,6 +299,13 @@ mod tests {
 		assert_eq!(nonce_end, expected_nonce_end);
 	}
 
+	#[test]
+	fn legacy_lane_id_is_prefix_of_hashed_lane_id() {
+		let hashed_lane_id = HashedLaneId::try_new(1, 2).unwrap();
+		let legacy_lane_id = LegacyLaneId::try_new(2, 1).unwrap();
+		assert_eq!(legacy_lane_id.as_ref(), &hashed_lane_id.inner().as_bytes()[..4]);
+	}
+
 	#[test]
 	fn hashed_lane_id_is_generated_using_ordered_endpoints() {
 		assert_eq!(HashedLaneId::try_new(1, 2).unwrap(), HashedLaneId::try_new(2, 1).unwrap());
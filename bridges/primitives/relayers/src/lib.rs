@@ -24,6 +24,10 @@ pub use extension::{
 	RuntimeWithUtilityPallet,
 };
 pub use registration::{ExplicitOrAccountParams, Registration, StakeAndSlash};
+pub use slashing::{
+	PermissionlessLanesRelayersSlashesApi, RelayerOffence, RelayersSlashesApi, SlashEvidence,
+	SlashId, SlashReason, SlashRecord, SlashStatus, SlashWholeStake, SlashedCall,
+};
 
 use bp_runtime::{ChainId, StorageDoubleMapKeyProvider};
 use frame_support::{traits::tokens::Preservation, Blake2_128Concat, Identity};
@@ -37,6 +41,7 @@ use sp_std::{fmt::Debug, marker::PhantomData};
 
 mod extension;
 mod registration;
+mod slashing;
 
 /// The owner of the sovereign account that should pay the rewards.
 ///
@@ -47,7 +47,7 @@ use sp_runtime::{
 };
 
 /// Either explicit account reference or `RewardsAccountParams`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
 pub enum ExplicitOrAccountParams<AccountId, LaneId: Decode + Encode> {
 	/// Explicit account reference.
 	Explicit(AccountId),
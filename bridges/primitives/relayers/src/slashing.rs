@@ -0,0 +1,171 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Records of registered relayers slashes.
+//!
+//! Registered relayer is slashed when its priority-boosted transaction has failed or has
+//! not caused expected side effects. Every slash is recorded together with the evidence,
+//! so that relayer operators are able to find out why their stake has been slashed.
+//!
+//! Slashed funds may be held for some period, during which the relayer may appeal the
+//! slash. Appeals are resolved by the governance. Records of resolved (applied or cancelled)
+//! slashes are kept in the runtime storage for some time, so that they may be queried even
+//! if slashed funds are moved to the beneficiary immediately. Older records may only be found
+//! in the runtime events.
+
+use crate::ExplicitOrAccountParams;
+
+use codec::{Codec, Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Convert, DispatchError};
+use sp_std::vec::Vec;
+
+/// Identifier of the relayer slash.
+pub type SlashId = u64;
+
+/// Offence of the registered relayer.
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+pub enum RelayerOffence {
+	/// Priority-boosted message delivery transaction has failed.
+	FailedMessagesDelivery,
+	/// Priority-boosted message delivery transaction has succeeded, but it has not caused
+	/// expected side effects (e.g. not all declared messages have been delivered or bundled
+	/// finality proof has been rejected).
+	UnsuccessfulMessagesDelivery,
+	/// Priority-boosted finality proof submission has failed or has been obsolete.
+	InvalidFinalityProof,
+	/// Priority-boosted parachain heads submission has failed or has been obsolete.
+	InvalidParachainHeads,
+}
+
+/// The call that the relayer is slashed for.
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+pub enum SlashedCall<LaneId> {
+	/// Message delivery call at given lane, optionally batched with finality calls.
+	MessagesDelivery(LaneId),
+	/// Bridged chain finality proof submission.
+	FinalityProof,
+	/// Heads submission of the bridged parachain with given identifier.
+	ParachainHeads(u32),
+}
+
+/// The reason why the offending call is considered failed.
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+pub enum SlashReason {
+	/// The call dispatch has failed with given error.
+	DispatchError(DispatchError),
+	/// The call dispatch has failed, but the error is unknown.
+	DispatchFailed,
+	/// The call has been dispatched successfully, but it has not updated the runtime storage
+	/// as expected (e.g. bundled header has been obsolete or not all messages have been
+	/// delivered).
+	NoExpectedEffects,
+}
+
+/// Evidence of the relayer offence.
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+pub struct SlashEvidence<BlockNumber, LaneId> {
+	/// The offence that the relayer is slashed for.
+	pub offence: RelayerOffence,
+	/// The offending call.
+	pub call: SlashedCall<LaneId>,
+	/// The reason why the call is considered failed.
+	pub reason: SlashReason,
+	/// Number of the block, where the offending transaction has been dispatched.
+	pub block_number: BlockNumber,
+	/// Index of the offending transaction within the block, if known.
+	pub extrinsic_index: Option<u32>,
+}
+
+/// Status of the relayer slash.
+#[derive(Copy, Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+pub enum SlashStatus<BlockNumber> {
+	/// Slashed funds are held at the relayer account. The relayer may appeal the slash until
+	/// `appeal_till` block (inclusive). After that, anyone may apply the slash.
+	Pending {
+		/// The last block, where the relayer may appeal the slash.
+		appeal_till: BlockNumber,
+	},
+	/// The relayer has appealed the slash. Slashed funds are held at the relayer account until
+	/// the governance resolves the appeal.
+	Appealed,
+	/// Slashed funds have been moved to the slash beneficiary.
+	Applied,
+	/// The slash has been cancelled and slashed funds have been released.
+	Cancelled,
+}
+
+impl<BlockNumber> SlashStatus<BlockNumber> {
+	/// Returns true if the slash is either applied or cancelled.
+	pub fn is_resolved(&self) -> bool {
+		matches!(self, SlashStatus::Applied | SlashStatus::Cancelled)
+	}
+}
+
+/// Record of the relayer slash.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+pub struct SlashRecord<AccountId, BlockNumber, Balance, LaneId: Decode + Encode> {
+	/// Evidence of the relayer offence.
+	pub evidence: SlashEvidence<BlockNumber, LaneId>,
+	/// Slashed amount.
+	pub amount: Balance,
+	/// Account that receives slashed funds.
+	pub beneficiary: ExplicitOrAccountParams<AccountId, LaneId>,
+	/// Current status of the slash.
+	pub status: SlashStatus<BlockNumber>,
+}
+
+/// Slash amount provider that slashes the whole relayer stake for any offence.
+pub struct SlashWholeStake;
+
+impl<Balance> Convert<RelayerOffence, Option<Balance>> for SlashWholeStake {
+	fn convert(_offence: RelayerOffence) -> Option<Balance> {
+		None
+	}
+}
+
+/// Declares runtime API for querying slashes of relayers, registered at some instance of the
+/// relayers pallet.
+macro_rules! decl_relayers_slashes_runtime_api {
+	($(#[$attr:meta])* $api:ident) => {
+		sp_api::decl_runtime_apis! {
+			$(#[$attr])*
+			pub trait $api<AccountId, BlockNumber, Balance, LaneId> where
+				AccountId: Codec,
+				BlockNumber: Codec,
+				Balance: Codec,
+				LaneId: Codec,
+			{
+				/// Returns all unresolved and recently resolved slashes of the given relayer.
+				fn relayer_slashes(
+					relayer: AccountId,
+				) -> Vec<(SlashId, SlashRecord<AccountId, BlockNumber, Balance, LaneId>)>;
+			}
+		}
+	};
+}
+
+decl_relayers_slashes_runtime_api!(
+	/// API for querying slashes of relayers, registered at the relayers pallet instance that
+	/// serves lanes with legacy identifiers.
+	RelayersSlashesApi
+);
+
+decl_relayers_slashes_runtime_api!(
+	/// API for querying slashes of relayers, registered at the relayers pallet instance that
+	/// serves permissionless lanes.
+	PermissionlessLanesRelayersSlashesApi
+);
@@ -57,6 +57,42 @@ impl Default for BridgeState {
 	}
 }
 
+/// Convenience macro that declares `pallet-xcm-bridge-hub-router` runtime apis and related
+/// constants for a chain. This includes:
+/// - chain-specific bridge runtime APIs:
+///     - `To<ThisChain>XcmBridgeHubRouterApi`
+/// - constants that are stringified names of runtime API methods:
+///     - `TO_<THIS_CHAIN>_XCM_ROUTER_BRIDGE_STATE_METHOD`
+/// The name of the chain has to be specified in snake case (e.g. `bridge_hub_polkadot`).
+#[macro_export]
+macro_rules! decl_xcm_bridge_hub_router_runtime_apis {
+	($chain: ident) => {
+		bp_runtime::paste::item! {
+			mod [<$chain _xcm_bridge_hub_router_api>] {
+				use super::*;
+
+				/// Name of the `To<ThisChain>XcmBridgeHubRouterApi::bridge_state` runtime method.
+				pub const [<TO_ $chain:upper _XCM_ROUTER_BRIDGE_STATE_METHOD>]: &str =
+					stringify!([<To $chain:camel XcmBridgeHubRouterApi_bridge_state>]);
+
+				sp_api::decl_runtime_apis! {
+					/// API for querying state of the router, that sends messages to this chain.
+					///
+					/// This API is implemented by chains that are sending messages to this chain
+					/// over their sibling bridge hub, not by this chain's runtime itself.
+					pub trait [<To $chain:camel XcmBridgeHubRouterApi>] {
+						/// Returns current delivery fee factor and congestion status of the
+						/// local XCM channel with the sibling bridge hub.
+						fn bridge_state() -> bp_xcm_bridge_hub_router::BridgeState;
+					}
+				}
+			}
+
+			pub use [<$chain _xcm_bridge_hub_router_api>]::*;
+		}
+	};
+}
+
 /// A minimized version of `pallet-xcm-bridge-hub-router::Call` that can be used without a runtime.
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo)]
 #[allow(non_camel_case_types)]
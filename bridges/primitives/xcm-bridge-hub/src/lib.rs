@@ -19,7 +19,7 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bp_messages::LaneIdType;
+use bp_messages::{LaneIdType, MessageNonce};
 use bp_runtime::{AccountIdOf, BalanceOf, Chain};
 pub use call_info::XcmBridgeHubCall;
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -148,6 +148,13 @@ pub enum BridgeState {
 	/// Bridge is closed. Associated lanes are also closed.
 	/// After all outbound messages will be pruned, the bridge will vanish without any traces.
 	Closed,
+	/// Bridge is opened at this side, but we are waiting for the bridged side to open it too.
+	/// Associated lanes are opened.
+	///
+	/// The only queued outbound message is the [`BridgeHandshake`] that we have sent to the
+	/// bridged side. Messages may not be exported over the bridge until we receive the handshake
+	/// of the bridged side.
+	Opening,
 }
 
 /// Bridge metadata.
@@ -178,6 +185,115 @@ pub struct Bridge<ThisChain: Chain, LaneId: LaneIdType> {
 	pub lane_id: LaneId,
 }
 
+/// The first message that is sent over the lane of the bridge, opened with the `open_bridge` call.
+///
+/// The bridged side switches its end of the bridge from the [`BridgeState::Opening`] to the
+/// [`BridgeState::Opened`] state when it receives this message. Endpoints are given from the
+/// sender point of view, so the receiver checks that they are the mirrored endpoints of its
+/// own end of the bridge.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub struct BridgeHandshake {
+	/// Universal location of the bridge endpoint at the sending side.
+	pub bridge_origin_universal_location: Box<VersionedInteriorLocation>,
+	/// Universal location of the bridge endpoint at the receiving side.
+	pub bridge_destination_universal_location: Box<VersionedInteriorLocation>,
+}
+
+/// Status of the bridge between two endpoints.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub struct BridgeStatus<LaneId> {
+	/// Bridge identifier.
+	pub bridge_id: BridgeId,
+	/// The lane that is used by the bridge. If the bridge is not opened yet, it is the lane
+	/// that will be used once it is opened.
+	pub lane_id: LaneId,
+	/// Current bridge state. It is `None` if the bridge is not opened yet or if it has been
+	/// already closed and pruned.
+	pub state: Option<BridgeState>,
+}
+
+/// Health of the bridge, opened by the `pallet-xcm-bridge-hub`.
+///
+/// It combines the bridge metadata with the state of its lanes at the messages pallet.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub struct BridgeHealth<BlockNumber, LaneId> {
+	/// The lane, used by the bridge.
+	pub lane_id: LaneId,
+	/// Current bridge state. If it is `Suspended`, the bridge origin has been asked to stop
+	/// sending messages over the bridge.
+	pub state: BridgeState,
+	/// Whether the local XCM channel with the bridge origin is currently congested.
+	pub is_congested: bool,
+	/// Number of outbound messages that are not yet delivered to the bridged chain.
+	pub outbound_queue_depth: MessageNonce,
+	/// Nonce of the oldest outbound message that is not yet delivered to the bridged chain.
+	pub oldest_undelivered_nonce: Option<MessageNonce>,
+	/// Number of blocks since the oldest undelivered message has been enqueued. It is `None`
+	/// if there are no undelivered messages or if the message has been enqueued before we
+	/// have started tracking that.
+	pub oldest_undelivered_message_age: Option<BlockNumber>,
+	/// Number of unrewarded relayer entries at the inbound lane of the bridge.
+	pub unrewarded_relayer_entries: u32,
+}
+
+// Re-export to avoid including xcm dependency everywhere.
+#[doc(hidden)]
+pub mod __private {
+	pub use xcm;
+}
+
+/// Convenience macro that declares `pallet-xcm-bridge-hub` runtime apis and related constants
+/// for a chain. This includes:
+/// - chain-specific bridge runtime APIs:
+///     - `To<ThisChain>XcmBridgeHubApi<BlockNumber>`
+/// - constants that are stringified names of runtime API methods:
+///     - `TO_<THIS_CHAIN>_XCM_BRIDGES_METHOD`
+///     - `TO_<THIS_CHAIN>_XCM_BRIDGE_STATUS_METHOD`
+/// The name of the chain has to be specified in snake case (e.g. `bridge_hub_polkadot`).
+#[macro_export]
+macro_rules! decl_xcm_bridge_hub_runtime_apis {
+	($chain: ident, $lane_id_type:ty) => {
+		bp_runtime::paste::item! {
+			mod [<$chain _xcm_bridge_hub_api>] {
+				use super::*;
+
+				/// Name of the `To<ThisChain>XcmBridgeHubApi::bridges` runtime method.
+				pub const [<TO_ $chain:upper _XCM_BRIDGES_METHOD>]: &str =
+					stringify!([<To $chain:camel XcmBridgeHubApi_bridges>]);
+
+				/// Name of the `To<ThisChain>XcmBridgeHubApi::bridge_status` runtime method.
+				pub const [<TO_ $chain:upper _XCM_BRIDGE_STATUS_METHOD>]: &str =
+					stringify!([<To $chain:camel XcmBridgeHubApi_bridge_status>]);
+
+				sp_api::decl_runtime_apis! {
+					/// API for querying health of bridges, opened with this chain.
+					///
+					/// This API is implemented by bridge hubs that are bridged with this chain, not by
+					/// this chain's runtime itself.
+					pub trait [<To $chain:camel XcmBridgeHubApi>]<BlockNumber> where
+						BlockNumber: bp_runtime::__private::codec::Codec,
+					{
+						/// Returns health of all bridges that are opened with this chain.
+						fn bridges() -> sp_std::vec::Vec<(
+							bp_xcm_bridge_hub::BridgeId,
+							bp_xcm_bridge_hub::BridgeHealth<BlockNumber, $lane_id_type>,
+						)>;
+
+						/// Returns status of the bridge between given endpoints. It returns `None`
+						/// if the bridge between given endpoints can't be opened.
+						fn bridge_status(
+							bridge_origin_relative_location: bp_xcm_bridge_hub::__private::xcm::VersionedLocation,
+							bridge_destination_universal_location: bp_xcm_bridge_hub::__private::xcm::VersionedInteriorLocation,
+						) -> Option<bp_xcm_bridge_hub::BridgeStatus<$lane_id_type>>;
+					}
+				}
+			}
+
+			pub use [<$chain _xcm_bridge_hub_api>]::*;
+		}
+	};
+}
+
 /// Locations of bridge endpoints at both sides of the bridge.
 #[derive(Clone, RuntimeDebug, PartialEq, Eq)]
 pub struct BridgeLocations {
@@ -17,6 +17,7 @@
 use crate::calls::UtilityCall;
 
 use crate::SimpleRuntimeVersion;
+use bp_beefy::ChainWithBeefy as ChainWithBeefyBase;
 use bp_header_chain::ChainWithGrandpa as ChainWithGrandpaBase;
 use bp_messages::ChainWithMessages as ChainWithMessagesBase;
 use bp_runtime::{
@@ -101,11 +102,32 @@ pub trait ChainWithGrandpa: Chain + ChainWithGrandpaBase {
 	/// Keep in mind that this method is normally provided by the other chain, which is
 	/// bridged with this chain.
 	const SYNCED_HEADERS_GRANDPA_INFO_METHOD: &'static str;
+	/// Name of the offences pallet (used in `construct_runtime` macro call) at this chain.
+	///
+	/// It is used to check whether an equivocation has already been reported.
+	const OFFENCES_PALLET_NAME: &'static str;
+	/// Name of the session pallet (used in `construct_runtime` macro call) at this chain.
+	///
+	/// It is used to find the account of the equivocating GRANDPA authority.
+	const SESSION_PALLET_NAME: &'static str;
 
 	/// The type of the key owner proof used by the grandpa engine.
 	type KeyOwnerProof: Decode + TypeInfo + Send;
 }
 
+/// Substrate-based chain that is using BEEFY finality (with MMR).
+pub trait ChainWithBeefy: Chain + ChainWithBeefyBase {
+	/// Name of the bridge BEEFY pallet (used in `construct_runtime` macro call) that is deployed
+	/// at some other chain to bridge with this `ChainWithBeefy`.
+	///
+	/// We assume that all chains that are bridging with this `ChainWithBeefy` are using
+	/// the same name.
+	const WITH_CHAIN_BEEFY_PALLET_NAME: &'static str;
+
+	/// The type of the key owner proof used by the BEEFY pallet.
+	type KeyOwnerProof: Decode + TypeInfo + Send;
+}
+
 /// Substrate-based parachain from minimal relay-client point of view.
 pub trait Parachain: Chain + ParachainBase {}
 
@@ -129,6 +151,18 @@ pub trait ChainWithMessages: Chain + ChainWithMessagesBase {
 	const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str;
 }
 
+/// Substrate-based chain that is bridged with other chains using the `pallet-xcm-bridge-hub`.
+pub trait ChainWithXcmBridgeHub: ChainWithMessages {
+	/// Name of the `To<ChainWithXcmBridgeHub>XcmBridgeHubApi::bridges` runtime API method.
+	/// The method is provided by the bridge hub runtime that is bridged with this chain.
+	const TO_CHAIN_XCM_BRIDGES_METHOD: &'static str;
+
+	/// Name of the `To<ChainWithXcmBridgeHub>XcmBridgeHubRouterApi::bridge_state` runtime API
+	/// method. The method is provided by runtimes that are sending messages to this chain over
+	/// their sibling bridge hub.
+	const TO_CHAIN_XCM_ROUTER_BRIDGE_STATE_METHOD: &'static str;
+}
+
 /// Call type used by the chain.
 pub type CallOf<C> = <C as Chain>::Call;
 /// Transaction status of the chain.
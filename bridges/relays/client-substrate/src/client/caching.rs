@@ -20,9 +20,10 @@
 use crate::{
 	client::{Client, SubscriptionBroadcaster},
 	error::{Error, Result},
-	AccountIdOf, AccountKeyPairOf, BlockNumberOf, Chain, ChainWithGrandpa, ChainWithTransactions,
-	HashOf, HeaderIdOf, HeaderOf, NonceOf, SignedBlockOf, SimpleRuntimeVersion, Subscription,
-	TransactionTracker, UnsignedTransaction, ANCIENT_BLOCK_THRESHOLD,
+	AccountIdOf, AccountKeyPairOf, BalanceOf, BlockNumberOf, Chain, ChainWithBeefy,
+	ChainWithGrandpa, ChainWithTransactions, HashOf, HeaderIdOf, HeaderOf, MmrLeavesProof, NonceOf,
+	SignedBlockOf, SimpleRuntimeVersion, Subscription, TransactionTracker, UnsignedTransaction,
+	ANCIENT_BLOCK_THRESHOLD,
 };
 use std::{cmp::Ordering, future::Future, task::Poll};
 
@@ -355,7 +356,10 @@ impl<C: Chain, B: Client<C>> Client<C> for CachingClient<C, B> {
 			.await
 	}
 
-	async fn subscribe_beefy_finality_justifications(&self) -> Result<Subscription<Bytes>> {
+	async fn subscribe_beefy_finality_justifications(&self) -> Result<Subscription<Bytes>>
+	where
+		C: ChainWithBeefy,
+	{
 		self.subscribe_finality_justifications(
 			&self.data.beefy_justifications,
 			self.backend.subscribe_beefy_finality_justifications(),
@@ -363,6 +367,28 @@ impl<C: Chain, B: Client<C>> Client<C> for CachingClient<C, B> {
 		.await
 	}
 
+	async fn generate_beefy_key_ownership_proof(
+		&self,
+		at: HashOf<C>,
+		set_id: sp_consensus_beefy::ValidatorSetId,
+		authority_id: bp_beefy::BeefyAuthorityIdOf<C>,
+	) -> Result<Option<sp_consensus_beefy::OpaqueKeyOwnershipProof>>
+	where
+		C: ChainWithBeefy,
+	{
+		self.backend.generate_beefy_key_ownership_proof(at, set_id, authority_id).await
+	}
+
+	async fn generate_mmr_leaf_proof(
+		&self,
+		block_number: BlockNumberOf<C>,
+		best_known_block_number: Option<BlockNumberOf<C>>,
+	) -> Result<MmrLeavesProof<HashOf<C>>> {
+		self.backend
+			.generate_mmr_leaf_proof(block_number, best_known_block_number)
+			.await
+	}
+
 	async fn token_decimals(&self) -> Result<Option<u64>> {
 		self.backend.token_decimals().await
 	}
@@ -392,6 +418,24 @@ impl<C: Chain, B: Client<C>> Client<C> for CachingClient<C, B> {
 		.await
 	}
 
+	async fn raw_storage_values(
+		&self,
+		at: HashOf<C>,
+		storage_keys: Vec<StorageKey>,
+	) -> Result<Vec<Option<StorageData>>> {
+		self.backend.raw_storage_values(at, storage_keys).await
+	}
+
+	async fn storage_keys_paged(
+		&self,
+		at: HashOf<C>,
+		prefix: StorageKey,
+		count: u32,
+		start_key: Option<StorageKey>,
+	) -> Result<Vec<StorageKey>> {
+		self.backend.storage_keys_paged(at, prefix, count, start_key).await
+	}
+
 	async fn pending_extrinsics(&self) -> Result<Vec<Bytes>> {
 		self.backend.pending_extrinsics().await
 	}
@@ -447,6 +491,14 @@ impl<C: Chain, B: Client<C>> Client<C> for CachingClient<C, B> {
 		self.backend.estimate_extrinsic_weight(at, transaction).await
 	}
 
+	async fn estimate_extrinsic_fee<SignedTransaction: Encode + Send + 'static>(
+		&self,
+		at: HashOf<C>,
+		transaction: SignedTransaction,
+	) -> Result<BalanceOf<C>> {
+		self.backend.estimate_extrinsic_fee(at, transaction).await
+	}
+
 	async fn raw_state_call<Args: Encode + Send>(
 		&self,
 		at: HashOf<C>,
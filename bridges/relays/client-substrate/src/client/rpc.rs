@@ -21,8 +21,8 @@ use crate::{
 	client::{
 		rpc_api::{
 			SubstrateAuthorClient, SubstrateBeefyClient, SubstrateChainClient,
-			SubstrateFrameSystemClient, SubstrateGrandpaClient, SubstrateStateClient,
-			SubstrateSystemClient,
+			SubstrateFrameSystemClient, SubstrateGrandpaClient, SubstrateMmrClient,
+			SubstrateStateClient, SubstrateSystemClient,
 		},
 		subscription::{StreamDescription, Subscription},
 		Client,
@@ -30,9 +30,9 @@ use crate::{
 	error::{Error, Result},
 	guard::Environment,
 	transaction_stall_timeout, AccountIdOf, AccountKeyPairOf, BalanceOf, BlockNumberOf, Chain,
-	ChainRuntimeVersion, ChainWithGrandpa, ChainWithTransactions, ConnectionParams, HashOf,
-	HeaderIdOf, HeaderOf, NonceOf, SignParam, SignedBlockOf, SimpleRuntimeVersion,
-	TransactionTracker, UnsignedTransaction,
+	ChainRuntimeVersion, ChainWithBeefy, ChainWithGrandpa, ChainWithTransactions, ConnectionParams,
+	HashOf, HeaderIdOf, HeaderOf, MmrLeavesProof, NonceOf, SignParam, SignedBlockOf,
+	SimpleRuntimeVersion, TransactionTracker, UnsignedTransaction,
 };
 
 use async_std::sync::{Arc, Mutex, RwLock};
@@ -58,7 +58,7 @@ use sp_runtime::{
 };
 use sp_trie::StorageProof;
 use sp_version::RuntimeVersion;
-use std::{cmp::Ordering, future::Future, marker::PhantomData};
+use std::{cmp::Ordering, collections::HashMap, future::Future, marker::PhantomData};
 
 const MAX_SUBSCRIPTION_CAPACITY: usize = 4096;
 
@@ -66,6 +66,7 @@ const SUB_API_TXPOOL_VALIDATE_TRANSACTION: &str = "TaggedTransactionQueue_valida
 const SUB_API_TX_PAYMENT_QUERY_INFO: &str = "TransactionPaymentApi_query_info";
 const SUB_API_GRANDPA_GENERATE_KEY_OWNERSHIP_PROOF: &str =
 	"GrandpaApi_generate_key_ownership_proof";
+const SUB_API_BEEFY_GENERATE_KEY_OWNERSHIP_PROOF: &str = "BeefyApi_generate_key_ownership_proof";
 
 /// Client implementation that connects to the Substrate node over `ws`/`wss` connection
 /// and is using RPC methods to get required data and submit transactions.
@@ -425,13 +426,52 @@ impl<C: Chain> Client<C> for RpcClient<C> {
 		.await
 	}
 
-	async fn subscribe_beefy_finality_justifications(&self) -> Result<Subscription<Bytes>> {
+	async fn subscribe_beefy_finality_justifications(&self) -> Result<Subscription<Bytes>>
+	where
+		C: ChainWithBeefy,
+	{
 		self.subscribe_finality_justifications("BEEFY", move |client| async move {
 			SubstrateBeefyClient::<C>::subscribe_justifications(&*client).await
 		})
 		.await
 	}
 
+	async fn generate_beefy_key_ownership_proof(
+		&self,
+		at: HashOf<C>,
+		set_id: sp_consensus_beefy::ValidatorSetId,
+		authority_id: bp_beefy::BeefyAuthorityIdOf<C>,
+	) -> Result<Option<sp_consensus_beefy::OpaqueKeyOwnershipProof>>
+	where
+		C: ChainWithBeefy,
+	{
+		self.state_call(
+			at,
+			SUB_API_BEEFY_GENERATE_KEY_OWNERSHIP_PROOF.into(),
+			(set_id, authority_id),
+		)
+		.await
+	}
+
+	async fn generate_mmr_leaf_proof(
+		&self,
+		block_number: BlockNumberOf<C>,
+		best_known_block_number: Option<BlockNumberOf<C>>,
+	) -> Result<MmrLeavesProof<HashOf<C>>> {
+		self.jsonrpsee_execute(move |client| async move {
+			SubstrateMmrClient::<C>::generate_proof(
+				&*client,
+				vec![block_number],
+				best_known_block_number,
+				None,
+			)
+			.await
+			.map_err(Into::into)
+		})
+		.await
+		.map_err(|e| Error::failed_to_generate_mmr_proof::<C>(block_number, e))
+	}
+
 	async fn token_decimals(&self) -> Result<Option<u64>> {
 		self.jsonrpsee_execute(move |client| async move {
 			let system_properties = SubstrateSystemClient::<C>::properties(&*client).await?;
@@ -475,6 +515,53 @@ impl<C: Chain> Client<C> for RpcClient<C> {
 		.map_err(|e| Error::failed_to_read_storage_value::<C>(at, storage_key, e))
 	}
 
+	async fn raw_storage_values(
+		&self,
+		at: HashOf<C>,
+		storage_keys: Vec<StorageKey>,
+	) -> Result<Vec<Option<StorageData>>> {
+		let cloned_storage_keys = storage_keys.clone();
+		let change_sets = self
+			.jsonrpsee_execute(move |client| async move {
+				Ok(SubstrateStateClient::<C>::query_storage_at(
+					&*client,
+					cloned_storage_keys,
+					Some(at),
+				)
+				.await?)
+			})
+			.await
+			.map_err(|e| Error::failed_to_read_storage_values::<C>(at, storage_keys.clone(), e))?;
+
+		let mut values = change_sets
+			.into_iter()
+			.flat_map(|change_set| change_set.changes)
+			.collect::<HashMap<_, _>>();
+		Ok(storage_keys.iter().map(|key| values.remove(key).flatten()).collect())
+	}
+
+	async fn storage_keys_paged(
+		&self,
+		at: HashOf<C>,
+		prefix: StorageKey,
+		count: u32,
+		start_key: Option<StorageKey>,
+	) -> Result<Vec<StorageKey>> {
+		let cloned_prefix = prefix.clone();
+		self.jsonrpsee_execute(move |client| async move {
+			Ok(SubstrateStateClient::<C>::storage_keys_paged(
+				&*client,
+				Some(cloned_prefix),
+				count,
+				start_key,
+				Some(at),
+			)
+			.await?)
+		})
+		.await
+		.map_err(|e| Error::failed_to_read_storage_value::<C>(at, prefix, e))
+	}
+
 	async fn pending_extrinsics(&self) -> Result<Vec<Bytes>> {
 		self.jsonrpsee_execute(move |client| async move {
 			Ok(SubstrateAuthorClient::<C>::pending_extrinsics(&*client).await?)
@@ -620,6 +707,19 @@ impl<C: Chain> Client<C> for RpcClient<C> {
 		Ok(dispatch_info.weight)
 	}
 
+	async fn estimate_extrinsic_fee<SignedTransaction: Encode + Send + 'static>(
+		&self,
+		at: HashOf<C>,
+		transaction: SignedTransaction,
+	) -> Result<BalanceOf<C>> {
+		let transaction_len = transaction.encoded_size() as u32;
+		let dispatch_info: RuntimeDispatchInfo<BalanceOf<C>> = self
+			.state_call(at, SUB_API_TX_PAYMENT_QUERY_INFO.into(), (transaction, transaction_len))
+			.await?;
+
+		Ok(dispatch_info.partial_fee)
+	}
+
 	async fn raw_state_call<Args: Encode + Send>(
 		&self,
 		at: HashOf<C>,
@@ -16,13 +16,13 @@
 
 //! The most generic Substrate node RPC interface.
 
-use crate::{Chain, ChainWithGrandpa, TransactionStatusOf};
+use crate::{Chain, ChainWithBeefy, ChainWithGrandpa, MmrLeavesProof, TransactionStatusOf};
 
 use jsonrpsee::proc_macros::rpc;
 use pallet_transaction_payment_rpc_runtime_api::FeeDetails;
 use sc_rpc_api::{state::ReadProof, system::Health};
 use sp_core::{
-	storage::{StorageData, StorageKey},
+	storage::{StorageChangeSet, StorageData, StorageKey},
 	Bytes,
 };
 use sp_rpc::number::NumberOrHex;
@@ -105,6 +105,22 @@ pub(crate) trait SubstrateState<C> {
 		key: StorageKey,
 		at_block: Option<C::Hash>,
 	) -> RpcResult<Option<StorageData>>;
+	/// Get values of multiple runtime storage keys.
+	#[method(name = "queryStorageAt")]
+	async fn query_storage_at(
+		&self,
+		keys: Vec<StorageKey>,
+		at_block: Option<C::Hash>,
+	) -> RpcResult<Vec<StorageChangeSet<C::Hash>>>;
+	/// Get runtime storage keys with given prefix, paginated.
+	#[method(name = "getKeysPaged")]
+	async fn storage_keys_paged(
+		&self,
+		prefix: Option<StorageKey>,
+		count: u32,
+		start_key: Option<StorageKey>,
+		at_block: Option<C::Hash>,
+	) -> RpcResult<Vec<StorageKey>>;
 	/// Get proof of the runtime storage value.
 	#[method(name = "getReadProof")]
 	async fn prove_storage(
@@ -122,15 +138,27 @@ pub(crate) trait SubstrateGrandpa<C> {
 	async fn subscribe_justifications(&self);
 }
 
-// TODO: Use `ChainWithBeefy` instead of `Chain` after #1606 is merged
 /// RPC methods of Substrate `beefy` namespace, that we are using.
-#[rpc(client, client_bounds(C: Chain), namespace = "beefy")]
+#[rpc(client, client_bounds(C: ChainWithBeefy), namespace = "beefy")]
 pub(crate) trait SubstrateBeefy<C> {
 	/// Subscribe to BEEFY justifications.
 	#[subscription(name = "subscribeJustifications", unsubscribe = "unsubscribeJustifications", item = Bytes)]
 	async fn subscribe_justifications(&self);
 }
 
+/// RPC methods of Substrate `mmr` namespace, that we are using.
+#[rpc(client, client_bounds(C: Chain), namespace = "mmr")]
+pub(crate) trait SubstrateMmr<C> {
+	/// Generate MMR proof for the leaves, corresponding to given block numbers.
+	#[method(name = "generateProof")]
+	async fn generate_proof(
+		&self,
+		block_numbers: Vec<C::BlockNumber>,
+		best_known_block_number: Option<C::BlockNumber>,
+		at: Option<C::Hash>,
+	) -> RpcResult<MmrLeavesProof<C::Hash>>;
+}
+
 /// RPC methods of Substrate `system` frame pallet, that we are using.
 #[rpc(client, client_bounds(C: Chain), namespace = "system")]
 pub(crate) trait SubstrateFrameSystem<C> {
@@ -16,9 +16,9 @@
 
 use crate::{
 	error::{Error, Result},
-	AccountIdOf, AccountKeyPairOf, BlockNumberOf, Chain, ChainWithGrandpa, ChainWithTransactions,
-	HashOf, HeaderIdOf, HeaderOf, NonceOf, SignedBlockOf, SimpleRuntimeVersion, Subscription,
-	TransactionTracker, UnsignedTransaction,
+	AccountIdOf, AccountKeyPairOf, BalanceOf, BlockNumberOf, Chain, ChainWithBeefy,
+	ChainWithGrandpa, ChainWithTransactions, HashOf, HeaderIdOf, HeaderOf, MmrLeavesProof, NonceOf,
+	SignedBlockOf, SimpleRuntimeVersion, Subscription, TransactionTracker, UnsignedTransaction,
 };
 
 use async_trait::async_trait;
@@ -93,7 +93,27 @@ pub trait Client<C: Chain>: 'static + Send + Sync + Clone + Debug {
 	) -> Result<Option<sp_consensus_grandpa::OpaqueKeyOwnershipProof>>;
 
 	/// Subscribe to BEEFY finality justifications.
-	async fn subscribe_beefy_finality_justifications(&self) -> Result<Subscription<Bytes>>;
+	async fn subscribe_beefy_finality_justifications(&self) -> Result<Subscription<Bytes>>
+	where
+		C: ChainWithBeefy;
+	/// Generates a proof of key ownership for the given BEEFY authority in the given set.
+	async fn generate_beefy_key_ownership_proof(
+		&self,
+		at: HashOf<C>,
+		set_id: sp_consensus_beefy::ValidatorSetId,
+		authority_id: bp_beefy::BeefyAuthorityIdOf<C>,
+	) -> Result<Option<sp_consensus_beefy::OpaqueKeyOwnershipProof>>
+	where
+		C: ChainWithBeefy;
+	/// Generates MMR proof for the leaf, that has been appended at given block.
+	///
+	/// The proof is generated against the MMR root at `best_known_block_number` (if specified)
+	/// or at the best block of the chain.
+	async fn generate_mmr_leaf_proof(
+		&self,
+		block_number: BlockNumberOf<C>,
+		best_known_block_number: Option<BlockNumberOf<C>>,
+	) -> Result<MmrLeavesProof<HashOf<C>>>;
 
 	/// Return `tokenDecimals` property from the set of chain properties.
 	async fn token_decimals(&self) -> Result<Option<u64>>;
@@ -155,6 +175,24 @@ pub trait Client<C: Chain>: 'static + Send + Sync + Clone + Debug {
 		self.storage_value(at, T::final_key(pallet_prefix, key1, key2)).await
 	}
 
+	/// Read raw values of multiple runtime storage keys in a single request. Values are returned
+	/// in the order of `storage_keys`.
+	async fn raw_storage_values(
+		&self,
+		at: HashOf<C>,
+		storage_keys: Vec<StorageKey>,
+	) -> Result<Vec<Option<StorageData>>>;
+
+	/// Read at most `count` runtime storage keys with given `prefix`, starting after the
+	/// `start_key` (if specified).
+	async fn storage_keys_paged(
+		&self,
+		at: HashOf<C>,
+		prefix: StorageKey,
+		count: u32,
+		start_key: Option<StorageKey>,
+	) -> Result<Vec<StorageKey>>;
+
 	/// Returns pending extrinsics from transaction pool.
 	async fn pending_extrinsics(&self) -> Result<Vec<Bytes>>;
 	/// Submit unsigned extrinsic for inclusion in a block.
@@ -202,6 +240,12 @@ pub trait Client<C: Chain>: 'static + Send + Sync + Clone + Debug {
 		at: HashOf<C>,
 		transaction: SignedTransaction,
 	) -> Result<Weight>;
+	/// Returns the fee of the given transaction, excluding the tip.
+	async fn estimate_extrinsic_fee<SignedTransaction: Encode + Send + 'static>(
+		&self,
+		at: HashOf<C>,
+		transaction: SignedTransaction,
+	) -> Result<BalanceOf<C>>;
 
 	/// Execute runtime call at given block.
 	async fn raw_state_call<Args: Encode + Send>(
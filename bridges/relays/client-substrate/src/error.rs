@@ -122,6 +122,18 @@ pub enum Error {
 		/// Underlying error.
 		error: Box<Error>,
 	},
+	/// Failed to read multiple storage values at given chain.
+	#[error("Failed to read storage values {keys:?} at {chain}: {error:?}.")]
+	FailedToReadStorageValues {
+		/// Name of the chain where the error has happened.
+		chain: String,
+		/// Hash of the block we've tried to read values from.
+		hash: String,
+		/// Runtime storage keys.
+		keys: Vec<StorageKey>,
+		/// Underlying error.
+		error: Box<Error>,
+	},
 	/// Failed to read runtime version of given chain.
 	#[error("Failed to read runtime version of {chain}: {error:?}.")]
 	FailedToReadRuntimeVersion {
@@ -172,6 +184,16 @@ pub enum Error {
 		/// Underlying error.
 		error: Box<Error>,
 	},
+	/// Failed to generate MMR leaf proof.
+	#[error("Failed to generate MMR proof for {chain} block {block_number}: {error:?}.")]
+	FailedToGenerateMmrProof {
+		/// Name of the chain where the error has happened.
+		chain: String,
+		/// Number of the block, for which we have tried to generate the proof.
+		block_number: String,
+		/// Underlying error.
+		error: Box<Error>,
+	},
 	/// Failed to subscribe to GRANDPA justifications stream.
 	#[error("Failed to subscribe to {chain} best headers: {error:?}.")]
 	FailedToSubscribeBestHeaders {
@@ -271,11 +293,13 @@ impl Error {
 			Self::FailedToReadHeaderByHash { ref error, .. } => Some(&**error),
 			Self::FailedToReadBlockByHash { ref error, .. } => Some(&**error),
 			Self::FailedToReadStorageValue { ref error, .. } => Some(&**error),
+			Self::FailedToReadStorageValues { ref error, .. } => Some(&**error),
 			Self::FailedToReadRuntimeVersion { ref error, .. } => Some(&**error),
 			Self::FailedToGetPendingExtrinsics { ref error, .. } => Some(&**error),
 			Self::FailedToSubmitTransaction { ref error, .. } => Some(&**error),
 			Self::FailedStateCall { ref error, .. } => Some(&**error),
 			Self::FailedToProveStorage { ref error, .. } => Some(&**error),
+			Self::FailedToGenerateMmrProof { ref error, .. } => Some(&**error),
 			Self::FailedToGetSystemHealth { ref error, .. } => Some(&**error),
 			Self::FailedToSubscribeBestHeaders { ref error, .. } => Some(&**error),
 			Self::FailedToSubscribeFinalizedHeaders { ref error, .. } => Some(&**error),
@@ -329,6 +353,20 @@ impl Error {
 		Error::FailedToReadRuntimeVersion { chain: C::NAME.into(), error: e.boxed() }
 	}
 
+	/// Constructs `FailedToReadStorageValues` variant.
+	pub fn failed_to_read_storage_values<C: Chain>(
+		at: HashOf<C>,
+		keys: Vec<StorageKey>,
+		e: Error,
+	) -> Self {
+		Error::FailedToReadStorageValues {
+			chain: C::NAME.into(),
+			hash: format!("{at}"),
+			keys,
+			error: e.boxed(),
+		}
+	}
+
 	/// Constructs `FailedToReadStorageValue` variant.
 	pub fn failed_to_read_storage_value<C: Chain>(
 		at: HashOf<C>,
@@ -369,6 +407,18 @@ impl Error {
 		}
 	}
 
+	/// Constructs `FailedToGenerateMmrProof` variant.
+	pub fn failed_to_generate_mmr_proof<C: Chain>(
+		block_number: BlockNumberOf<C>,
+		e: Error,
+	) -> Self {
+		Error::FailedToGenerateMmrProof {
+			chain: C::NAME.into(),
+			block_number: format!("{block_number:?}"),
+			error: e.boxed(),
+		}
+	}
+
 	/// Constructs `FailedToProveStorage` variant.
 	pub fn failed_to_prove_storage<C: Chain>(
 		at: HashOf<C>,
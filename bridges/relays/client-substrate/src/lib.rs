@@ -33,11 +33,11 @@ use std::time::Duration;
 
 pub use crate::{
 	chain::{
-		AccountKeyPairOf, BlockWithJustification, CallOf, Chain, ChainWithBalances,
+		AccountKeyPairOf, BlockWithJustification, CallOf, Chain, ChainWithBalances, ChainWithBeefy,
 		ChainWithGrandpa, ChainWithMessages, ChainWithRuntimeVersion, ChainWithTransactions,
-		ChainWithUtilityPallet, FullRuntimeUtilityPallet, MockedRuntimeUtilityPallet, Parachain,
-		RelayChain, SignParam, SignedBlockOf, TransactionStatusOf, UnsignedTransaction,
-		UtilityPallet,
+		ChainWithUtilityPallet, ChainWithXcmBridgeHub, FullRuntimeUtilityPallet,
+		MockedRuntimeUtilityPallet, Parachain, RelayChain, SignParam, SignedBlockOf,
+		TransactionStatusOf, UnsignedTransaction, UtilityPallet,
 	},
 	client::{
 		is_ancient_block, rpc_with_caching as new, ChainRuntimeVersion, Client,
@@ -53,6 +53,7 @@ pub use bp_runtime::{
 	HeaderOf, NonceOf, Parachain as ParachainBase, SignatureOf, TransactionEra, TransactionEraOf,
 	UnderlyingChainProvider,
 };
+pub use mmr_rpc::LeavesProof as MmrLeavesProof;
 
 /// Substrate-over-websocket connection params.
 #[derive(Debug, Clone)]
@@ -317,7 +317,7 @@ mod tests {
 			..Default::default()
 		};
 		let mut reporter =
-			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new();
+			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new(None);
 
 		let block_checker = BlockChecker::new(10);
 		assert!(block_checker
@@ -359,7 +359,7 @@ mod tests {
 		};
 		let mut source_client = TestSourceClient { ..Default::default() };
 		let mut reporter =
-			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new();
+			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new(None);
 
 		let block_checker = BlockChecker::new(10);
 		assert!(block_checker
@@ -385,7 +385,7 @@ mod tests {
 		};
 		let mut source_client = TestSourceClient { ..Default::default() };
 		let mut reporter =
-			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new();
+			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new(None);
 
 		// NonConnection error
 		let block_checker = BlockChecker::new(10);
@@ -430,7 +430,7 @@ mod tests {
 		};
 		let mut source_client = TestSourceClient { ..Default::default() };
 		let mut reporter =
-			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new();
+			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new(None);
 
 		// NonConnection error
 		let block_checker = BlockChecker::new(10);
@@ -15,8 +15,8 @@
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	handle_client_error, reporter::EquivocationsReporter, EquivocationDetectionPipeline,
-	SourceClient, TargetClient,
+	handle_client_error, reporter::EquivocationsReporter, EquivocationDetectionMetrics,
+	EquivocationDetectionPipeline, SourceClient, TargetClient,
 };
 
 use crate::block_checker::BlockChecker;
@@ -89,7 +89,7 @@ impl<P: EquivocationDetectionPipeline, SC: SourceClient<P>, TC: TargetClient<P>>
 			// Make sure that we are connected to the source finality proofs stream.
 			self.ensure_finality_proofs_stream().await;
 			// Check the status of the pending equivocation reports
-			self.reporter.process_pending_reports().await;
+			self.reporter.process_pending_reports(&self.source_client).await;
 
 			// Update blocks range.
 			if let Some(block_number) = self.best_finalized_target_block_number().await {
@@ -129,6 +129,7 @@ impl<P: EquivocationDetectionPipeline, SC: SourceClient<P>, TC: TargetClient<P>>
 		source_client: SC,
 		target_client: TC,
 		tick: Duration,
+		metrics: Option<EquivocationDetectionMetrics>,
 		exit_signal: impl Future<Output = ()>,
 	) -> Result<(), FailedClient> {
 		let mut equivocation_detection_loop = Self {
@@ -136,7 +137,7 @@ impl<P: EquivocationDetectionPipeline, SC: SourceClient<P>, TC: TargetClient<P>>
 			target_client,
 			from_block_num: None,
 			until_block_num: None,
-			reporter: EquivocationsReporter::<P, SC>::new(),
+			reporter: EquivocationsReporter::<P, SC>::new(metrics),
 			finality_proofs_stream: FinalityProofsStream::new(),
 			finality_proofs_buf: FinalityProofsBuf::new(vec![]),
 		};
@@ -146,6 +147,11 @@ impl<P: EquivocationDetectionPipeline, SC: SourceClient<P>, TC: TargetClient<P>>
 	}
 }
 
+/// Returns the prefix of the equivocations detection loop metrics.
+pub fn metrics_prefix<P: EquivocationDetectionPipeline>() -> String {
+	format!("{}_to_{}_EquivocationDetection", P::SOURCE_NAME, P::TARGET_NAME)
+}
+
 /// Spawn the equivocations detection loop.
 pub async fn run<P: EquivocationDetectionPipeline>(
 	source_client: impl SourceClient<P>,
@@ -157,19 +163,18 @@ pub async fn run<P: EquivocationDetectionPipeline>(
 	let exit_signal = exit_signal.shared();
 	relay_utils::relay_loop(source_client, target_client)
 		.with_metrics(metrics_params)
+		.loop_metric(EquivocationDetectionMetrics::new(Some(&metrics_prefix::<P>()))?)?
 		.expose()
 		.await?
-		.run(
-			format!("{}_to_{}_EquivocationDetection", P::SOURCE_NAME, P::TARGET_NAME),
-			move |source_client, target_client, _metrics| {
-				EquivocationDetectionLoop::run(
-					source_client,
-					target_client,
-					tick,
-					exit_signal.clone(),
-				)
-			},
-		)
+		.run(metrics_prefix::<P>(), move |source_client, target_client, metrics| {
+			EquivocationDetectionLoop::run(
+				source_client,
+				target_client,
+				tick,
+				metrics,
+				exit_signal.clone(),
+			)
+		})
 		.await
 }
 
@@ -16,6 +16,7 @@
 
 mod block_checker;
 mod equivocation_loop;
+mod metrics;
 mod mock;
 mod reporter;
 
@@ -23,9 +24,10 @@ use async_trait::async_trait;
 use bp_header_chain::{FinalityProof, FindEquivocations};
 use finality_relay::{FinalityPipeline, SourceClientBase};
 use relay_utils::{relay_loop::Client as RelayClient, MaybeConnectionError, TransactionTracker};
-use std::{fmt::Debug, time::Duration};
+use std::{fmt::Debug, hash::Hash, time::Duration};
 
-pub use equivocation_loop::run;
+pub use equivocation_loop::{metrics_prefix, run};
+pub use metrics::EquivocationDetectionMetrics;
 
 #[cfg(not(test))]
 const RECONNECT_DELAY: Duration = relay_utils::relay_loop::RECONNECT_DELAY;
@@ -38,7 +40,7 @@ pub trait EquivocationDetectionPipeline: FinalityPipeline {
 	/// The context needed for validating finality proofs.
 	type FinalityVerificationContext: Debug + Send;
 	/// The type of the equivocation proof.
-	type EquivocationProof: Clone + Debug + Send + Sync;
+	type EquivocationProof: Clone + Debug + Send + Sync + EquivocationOffence;
 	/// The equivocations finder.
 	type EquivocationsFinder: FindEquivocations<
 		Self::FinalityProof,
@@ -47,6 +49,42 @@ pub trait EquivocationDetectionPipeline: FinalityPipeline {
 	>;
 }
 
+/// Offence that is proven by the equivocation proof.
+pub trait EquivocationOffence {
+	/// Unique identifier of the offence. The same offence is reported at most once.
+	type OffenceId: Clone + Debug + Eq + Hash + Send + Sync;
+
+	/// Returns unique identifier of the offence.
+	fn offence_id(&self) -> Self::OffenceId;
+	/// Returns identifier of the authority set, where the offence has happened.
+	fn set_id(&self) -> u64;
+	/// Returns the offender representation, used to label the metrics.
+	fn offender_label(&self) -> String;
+}
+
+impl<Hash, Number> EquivocationOffence for sp_consensus_grandpa::EquivocationProof<Hash, Number> {
+	type OffenceId = (
+		sp_consensus_grandpa::SetId,
+		sp_consensus_grandpa::RoundNumber,
+		sp_consensus_grandpa::AuthorityId,
+	);
+
+	fn offence_id(&self) -> Self::OffenceId {
+		(self.set_id(), self.round(), self.offender().clone())
+	}
+
+	fn set_id(&self) -> u64 {
+		sp_consensus_grandpa::EquivocationProof::set_id(self)
+	}
+
+	fn offender_label(&self) -> String {
+		format!("{:?}", self.offender())
+	}
+}
+
+type OffenceIdOf<P> =
+	<<P as EquivocationDetectionPipeline>::EquivocationProof as EquivocationOffence>::OffenceId;
+
 type HeaderFinalityInfo<P> = bp_header_chain::HeaderFinalityInfo<
 	<P as FinalityPipeline>::FinalityProof,
 	<P as EquivocationDetectionPipeline>::FinalityVerificationContext,
@@ -58,6 +96,14 @@ pub trait SourceClient<P: EquivocationDetectionPipeline>: SourceClientBase<P> {
 	/// Transaction tracker to track submitted transactions.
 	type TransactionTracker: TransactionTracker;
 
+	/// Returns `true` if the offence, proved by the given equivocation, is already known to
+	/// the source chain at the given block.
+	async fn is_equivocation_reported(
+		&self,
+		at: P::Hash,
+		equivocation: &P::EquivocationProof,
+	) -> Result<bool, Self::Error>;
+
 	/// Report equivocation.
 	async fn report_equivocation(
 		&self,
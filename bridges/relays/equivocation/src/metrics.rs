@@ -0,0 +1,61 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Metrics for the equivocations detection loop.
+
+use relay_utils::metrics::{
+	metric_name, register, CounterVec, Metric, Opts, PrometheusError, Registry, U64,
+};
+
+/// Equivocations detection loop metrics.
+#[derive(Clone)]
+pub struct EquivocationDetectionMetrics {
+	/// Number of equivocations, reported to the source chain, by offender.
+	reported_equivocations: CounterVec<U64>,
+}
+
+impl EquivocationDetectionMetrics {
+	/// Create and register equivocations detection loop metrics.
+	pub fn new(prefix: Option<&str>) -> Result<Self, PrometheusError> {
+		Ok(EquivocationDetectionMetrics {
+			reported_equivocations: CounterVec::new(
+				Opts::new(
+					metric_name(prefix, "reported_equivocations"),
+					"Number of equivocations reported to the source chain, by offender",
+				),
+				&["authority"],
+			)?,
+		})
+	}
+
+	/// Note that the equivocation of the given offender has been reported.
+	pub fn note_reported_equivocation(&self, offender: &str) {
+		self.reported_equivocations.with_label_values(&[offender]).inc();
+	}
+
+	/// Returns number of reported equivocations of the given offender.
+	#[cfg(test)]
+	pub(crate) fn reported_equivocations(&self, offender: &str) -> u64 {
+		self.reported_equivocations.with_label_values(&[offender]).get()
+	}
+}
+
+impl Metric for EquivocationDetectionMetrics {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.reported_equivocations.clone(), registry)?;
+		Ok(())
+	}
+}
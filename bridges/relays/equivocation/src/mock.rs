@@ -16,7 +16,10 @@
 
 #![cfg(test)]
 
-use crate::{EquivocationDetectionPipeline, HeaderFinalityInfo, SourceClient, TargetClient};
+use crate::{
+	EquivocationDetectionPipeline, EquivocationOffence, HeaderFinalityInfo, SourceClient,
+	TargetClient,
+};
 use async_trait::async_trait;
 use bp_header_chain::{FinalityProof, FindEquivocations};
 use finality_relay::{FinalityPipeline, SourceClientBase};
@@ -26,7 +29,7 @@ use relay_utils::{
 	TransactionTracker,
 };
 use std::{
-	collections::HashMap,
+	collections::{HashMap, HashSet},
 	pin::Pin,
 	sync::{Arc, Mutex},
 	time::Duration,
@@ -36,6 +39,25 @@ pub type TestSourceHashAndNumber = u64;
 pub type TestTargetNumber = u64;
 pub type TestEquivocationProof = &'static str;
 
+impl EquivocationOffence for TestEquivocationProof {
+	type OffenceId = &'static str;
+
+	fn offence_id(&self) -> Self::OffenceId {
+		self
+	}
+
+	fn set_id(&self) -> u64 {
+		self.split('-')
+			.next()
+			.and_then(|set_id| set_id.parse().ok())
+			.unwrap_or_default()
+	}
+
+	fn offender_label(&self) -> String {
+		self.to_string()
+	}
+}
+
 pub const TEST_RECONNECT_DELAY: Duration = Duration::from_secs(0);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -120,6 +142,9 @@ pub struct TestSourceClient {
 	pub finality_proofs: Arc<Mutex<Vec<TestFinalityProof>>>,
 	pub reported_equivocations:
 		Arc<Mutex<HashMap<TestSourceHashAndNumber, Vec<TestEquivocationProof>>>>,
+	pub known_offences: Arc<Mutex<HashSet<TestEquivocationProof>>>,
+	pub report_transaction_status:
+		TrackedTransactionStatus<HeaderId<TestSourceHashAndNumber, TestSourceHashAndNumber>>,
 }
 
 impl Default for TestSourceClient {
@@ -128,6 +153,8 @@ impl Default for TestSourceClient {
 			num_reconnects: 0,
 			finality_proofs: Arc::new(Mutex::new(vec![])),
 			reported_equivocations: Arc::new(Mutex::new(Default::default())),
+			known_offences: Arc::new(Mutex::new(Default::default())),
+			report_transaction_status: TestTransactionTracker::default().0,
 		}
 	}
 }
@@ -179,6 +206,14 @@ impl TransactionTracker for TestTransactionTracker {
 impl SourceClient<TestEquivocationDetectionPipeline> for TestSourceClient {
 	type TransactionTracker = TestTransactionTracker;
 
+	async fn is_equivocation_reported(
+		&self,
+		_at: TestSourceHashAndNumber,
+		equivocation: &TestEquivocationProof,
+	) -> Result<bool, Self::Error> {
+		Ok(self.known_offences.lock().unwrap().contains(equivocation))
+	}
+
 	async fn report_equivocation(
 		&self,
 		at: TestSourceHashAndNumber,
@@ -191,7 +226,7 @@ impl SourceClient<TestEquivocationDetectionPipeline> for TestSourceClient {
 			.or_default()
 			.push(equivocation);
 
-		Ok(TestTransactionTracker::default())
+		Ok(TestTransactionTracker(self.report_transaction_status.clone()))
 	}
 }
 
@@ -16,49 +16,139 @@
 
 //! Helper struct used for submitting finality reports and tracking their status.
 
-use crate::{EquivocationDetectionPipeline, SourceClient};
+use crate::{
+	EquivocationDetectionMetrics, EquivocationDetectionPipeline, EquivocationOffence, OffenceIdOf,
+	SourceClient,
+};
 
 use futures::FutureExt;
-use relay_utils::{TrackedTransactionFuture, TrackedTransactionStatus, TransactionTracker};
+use relay_utils::{
+	MaybeConnectionError, TrackedTransactionFuture, TrackedTransactionStatus, TransactionTracker,
+};
 use std::{
+	collections::{BTreeMap, HashSet},
 	future::poll_fn,
 	task::{Context, Poll},
 };
 
+/// Number of the most recent authority sets, for which we remember reported offences.
+///
+/// Offences of older sets are checked using the source chain storage.
+const MAX_TRACKED_AUTHORITY_SETS: usize = 4;
+
+/// Submitted equivocation report that is not yet finalized.
+struct PendingReport<'a, P: EquivocationDetectionPipeline, SC: SourceClient<P>> {
+	/// Source block, at which the report has been generated.
+	at: P::Hash,
+	/// Reported equivocation.
+	equivocation: P::EquivocationProof,
+	/// Report transaction tracker.
+	tracker: TrackedTransactionFuture<'a, SC::TransactionTracker>,
+}
+
 pub struct EquivocationsReporter<'a, P: EquivocationDetectionPipeline, SC: SourceClient<P>> {
-	pending_reports: Vec<TrackedTransactionFuture<'a, SC::TransactionTracker>>,
+	pending_reports: Vec<PendingReport<'a, P, SC>>,
+	lost_reports: Vec<(P::Hash, P::EquivocationProof)>,
+	reported_offences: BTreeMap<u64, HashSet<OffenceIdOf<P>>>,
+	metrics: Option<EquivocationDetectionMetrics>,
 }
 
 impl<'a, P: EquivocationDetectionPipeline, SC: SourceClient<P>> EquivocationsReporter<'a, P, SC> {
-	pub fn new() -> Self {
-		Self { pending_reports: vec![] }
+	pub fn new(metrics: Option<EquivocationDetectionMetrics>) -> Self {
+		Self {
+			pending_reports: vec![],
+			lost_reports: vec![],
+			reported_offences: BTreeMap::new(),
+			metrics,
+		}
 	}
 
 	/// Submit a `report_equivocation()` transaction to the source chain.
 	///
-	/// We store the transaction tracker for future monitoring.
+	/// We store the transaction tracker for future monitoring. Offences that have already been
+	/// reported (by us or by someone else) or that are being reported are ignored.
 	pub async fn submit_report(
 		&mut self,
 		source_client: &SC,
 		at: P::Hash,
 		equivocation: P::EquivocationProof,
 	) -> Result<(), SC::Error> {
-		let pending_report = source_client.report_equivocation(at, equivocation).await?;
-		self.pending_reports.push(pending_report.wait());
+		let offence_id = equivocation.offence_id();
+		if self.is_known_offence(&equivocation) {
+			log::debug!(
+				target: "bridge",
+				"Equivocation {offence_id:?} has already been reported to {}",
+				P::SOURCE_NAME,
+			);
+			return Ok(())
+		}
+
+		if source_client.is_equivocation_reported(at, &equivocation).await? {
+			log::debug!(
+				target: "bridge",
+				"Equivocation {offence_id:?} is already known to {}",
+				P::SOURCE_NAME,
+			);
+			self.note_reported_offence(&equivocation);
+			return Ok(())
+		}
+
+		let pending_report = source_client.report_equivocation(at, equivocation.clone()).await?;
+		self.pending_reports.push(PendingReport {
+			at,
+			equivocation,
+			tracker: pending_report.wait(),
+		});
 
 		Ok(())
 	}
 
+	/// Returns true if the offence has already been reported or is being reported.
+	fn is_known_offence(&self, equivocation: &P::EquivocationProof) -> bool {
+		let offence_id = equivocation.offence_id();
+		let is_reported = self
+			.reported_offences
+			.get(&equivocation.set_id())
+			.map(|offences| offences.contains(&offence_id))
+			.unwrap_or(false);
+		is_reported ||
+			self.pending_reports
+				.iter()
+				.any(|pending_report| pending_report.equivocation.offence_id() == offence_id)
+	}
+
+	/// Remember that the offence has been reported.
+	///
+	/// Only offences of the `MAX_TRACKED_AUTHORITY_SETS` most recent sets are remembered.
+	fn note_reported_offence(&mut self, equivocation: &P::EquivocationProof) {
+		self.reported_offences
+			.entry(equivocation.set_id())
+			.or_default()
+			.insert(equivocation.offence_id());
+		while self.reported_offences.len() > MAX_TRACKED_AUTHORITY_SETS {
+			self.reported_offences.pop_first();
+		}
+	}
+
 	fn do_process_pending_reports(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+		let mut finalized_reports = vec![];
+		let lost_reports = &mut self.lost_reports;
 		self.pending_reports.retain_mut(|pending_report| {
-			match pending_report.poll_unpin(cx) {
+			match pending_report.tracker.poll_unpin(cx) {
 				Poll::Ready(tx_status) => {
 					match tx_status {
 						TrackedTransactionStatus::Lost => {
-							log::error!(target: "bridge", "Equivocation report tx was lost");
+							log::error!(
+								target: "bridge",
+								"Equivocation {:?} report tx was lost. Going to resubmit",
+								pending_report.equivocation.offence_id(),
+							);
+							lost_reports
+								.push((pending_report.at, pending_report.equivocation.clone()));
 						},
 						TrackedTransactionStatus::Finalized(id) => {
 							log::error!(target: "bridge", "Equivocation report tx was finalized in source block {id:?}");
+							finalized_reports.push(pending_report.equivocation.clone());
 						},
 					}
 
@@ -72,13 +162,36 @@ impl<'a, P: EquivocationDetectionPipeline, SC: SourceClient<P>> EquivocationsRep
 			}
 		});
 
+		for equivocation in finalized_reports {
+			self.note_reported_offence(&equivocation);
+			if let Some(ref metrics) = self.metrics {
+				metrics.note_reported_equivocation(&equivocation.offender_label());
+			}
+		}
+
 		Poll::Ready(())
 	}
 
-	/// Iterate through all the pending `report_equivocation()` transactions
-	/// and log the ones that finished.
-	pub async fn process_pending_reports(&mut self) {
-		poll_fn(|cx| self.do_process_pending_reports(cx)).await
+	/// Iterate through all the pending `report_equivocation()` transactions, log the ones
+	/// that finished and resubmit the lost ones.
+	pub async fn process_pending_reports(&mut self, source_client: &SC) {
+		poll_fn(|cx| self.do_process_pending_reports(cx)).await;
+
+		for (at, equivocation) in std::mem::take(&mut self.lost_reports) {
+			if let Err(e) = self.submit_report(source_client, at, equivocation.clone()).await {
+				log::error!(
+					target: "bridge",
+					"Failed to resubmit equivocation {:?} report to {}: {e:?}",
+					equivocation.offence_id(),
+					P::SOURCE_NAME,
+				);
+
+				// we'll retry after reconnect
+				if e.is_connection_error() {
+					self.lost_reports.push((at, equivocation));
+				}
+			}
+		}
 	}
 }
 
@@ -87,43 +200,133 @@ mod tests {
 	use super::*;
 	use crate::mock::*;
 	use relay_utils::HeaderId;
-	use std::sync::Mutex;
+	use std::{collections::HashMap, sync::Mutex};
 
 	#[async_std::test]
 	async fn process_pending_reports_works() {
 		let polled_reports = Mutex::new(vec![]);
 		let finished_reports = Mutex::new(vec![]);
 
+		let source_client = TestSourceClient::default();
 		let mut reporter =
-			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient> {
-				pending_reports: vec![
-					Box::pin(async {
-						polled_reports.lock().unwrap().push(1);
-						finished_reports.lock().unwrap().push(1);
-						TrackedTransactionStatus::Finalized(HeaderId(1, 1))
-					}),
-					Box::pin(async {
-						polled_reports.lock().unwrap().push(2);
-						finished_reports.lock().unwrap().push(2);
-						TrackedTransactionStatus::Finalized(HeaderId(2, 2))
-					}),
-					Box::pin(async {
-						polled_reports.lock().unwrap().push(3);
-						std::future::pending::<()>().await;
-						finished_reports.lock().unwrap().push(3);
-						TrackedTransactionStatus::Finalized(HeaderId(3, 3))
-					}),
-					Box::pin(async {
-						polled_reports.lock().unwrap().push(4);
-						finished_reports.lock().unwrap().push(4);
-						TrackedTransactionStatus::Finalized(HeaderId(4, 4))
-					}),
-				],
-			};
-
-		reporter.process_pending_reports().await;
+			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new(None);
+		reporter.pending_reports = vec![
+			PendingReport {
+				at: 1,
+				equivocation: "1-1",
+				tracker: Box::pin(async {
+					polled_reports.lock().unwrap().push(1);
+					finished_reports.lock().unwrap().push(1);
+					TrackedTransactionStatus::Finalized(HeaderId(1, 1))
+				}),
+			},
+			PendingReport {
+				at: 2,
+				equivocation: "1-2",
+				tracker: Box::pin(async {
+					polled_reports.lock().unwrap().push(2);
+					finished_reports.lock().unwrap().push(2);
+					TrackedTransactionStatus::Finalized(HeaderId(2, 2))
+				}),
+			},
+			PendingReport {
+				at: 3,
+				equivocation: "1-3",
+				tracker: Box::pin(async {
+					polled_reports.lock().unwrap().push(3);
+					std::future::pending::<()>().await;
+					finished_reports.lock().unwrap().push(3);
+					TrackedTransactionStatus::Finalized(HeaderId(3, 3))
+				}),
+			},
+			PendingReport {
+				at: 4,
+				equivocation: "1-4",
+				tracker: Box::pin(async {
+					polled_reports.lock().unwrap().push(4);
+					finished_reports.lock().unwrap().push(4);
+					TrackedTransactionStatus::Finalized(HeaderId(4, 4))
+				}),
+			},
+		];
+
+		reporter.process_pending_reports(&source_client).await;
 		assert_eq!(*polled_reports.lock().unwrap(), vec![1, 2, 3, 4]);
 		assert_eq!(*finished_reports.lock().unwrap(), vec![1, 2, 4]);
 		assert_eq!(reporter.pending_reports.len(), 1);
+		assert_eq!(
+			reporter.reported_offences,
+			BTreeMap::from([(1, HashSet::from(["1-1", "1-2", "1-4"]))])
+		);
+	}
+
+	#[async_std::test]
+	async fn already_reported_offences_are_ignored() {
+		let source_client = TestSourceClient::default();
+		let metrics = EquivocationDetectionMetrics::new(None).unwrap();
+		let mut reporter = EquivocationsReporter::<
+			TestEquivocationDetectionPipeline,
+			TestSourceClient,
+		>::new(Some(metrics.clone()));
+
+		// offence that is being reported is not reported again
+		reporter.submit_report(&source_client, 1, "1-1").await.unwrap();
+		reporter.submit_report(&source_client, 1, "1-2").await.unwrap();
+		reporter.submit_report(&source_client, 2, "1-1").await.unwrap();
+		assert_eq!(reporter.pending_reports.len(), 2);
+		assert_eq!(metrics.reported_equivocations("1-1"), 0);
+
+		// offence that has been reported is not reported again
+		reporter.process_pending_reports(&source_client).await;
+		reporter.submit_report(&source_client, 3, "1-1").await.unwrap();
+		assert_eq!(reporter.pending_reports.len(), 0);
+
+		// offence that is known to the source chain is not reported
+		source_client.known_offences.lock().unwrap().insert("1-3");
+		reporter.submit_report(&source_client, 3, "1-3").await.unwrap();
+		assert_eq!(reporter.pending_reports.len(), 0);
+
+		assert_eq!(
+			*source_client.reported_equivocations.lock().unwrap(),
+			HashMap::from([(1, vec!["1-1", "1-2"])])
+		);
+		assert_eq!(metrics.reported_equivocations("1-1"), 1);
+		assert_eq!(metrics.reported_equivocations("1-2"), 1);
+		assert_eq!(metrics.reported_equivocations("1-3"), 0);
+	}
+
+	#[async_std::test]
+	async fn lost_reports_are_resubmitted() {
+		let source_client = TestSourceClient {
+			report_transaction_status: TrackedTransactionStatus::Lost,
+			..Default::default()
+		};
+		let mut reporter =
+			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new(None);
+
+		reporter.submit_report(&source_client, 1, "1-1").await.unwrap();
+		reporter.process_pending_reports(&source_client).await;
+
+		assert_eq!(
+			*source_client.reported_equivocations.lock().unwrap(),
+			HashMap::from([(1, vec!["1-1", "1-1"])])
+		);
+		assert_eq!(reporter.pending_reports.len(), 1);
+		assert!(reporter.reported_offences.is_empty());
+	}
+
+	#[test]
+	fn reported_offences_of_old_sets_are_forgotten() {
+		let mut reporter =
+			EquivocationsReporter::<TestEquivocationDetectionPipeline, TestSourceClient>::new(None);
+
+		for set_id in 1..=MAX_TRACKED_AUTHORITY_SETS + 1 {
+			let equivocation: &'static str = Box::leak(format!("{set_id}-1").into_boxed_str());
+			reporter.note_reported_offence(&equivocation);
+		}
+
+		assert_eq!(reporter.reported_offences.len(), MAX_TRACKED_AUTHORITY_SETS);
+		assert!(!reporter.is_known_offence(&"1-1"));
+		assert!(reporter.is_known_offence(&"2-1"));
 	}
 }
@@ -0,0 +1,170 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! BEEFY equivocations detection.
+//!
+//! BEEFY validators are equivocating if they are signing different commitments for the same
+//! block. If this happens, the bridge BEEFY pallet at the target chain may be forced to
+//! import commitment that doesn't belong to the canonical source chain. The relay detects such
+//! situations by comparing the source chain commitments with what has been imported by the
+//! target chain and by comparing commitments that are received from the source node.
+
+use bp_beefy::{BeefyAuthorityId, Commitment, MmrHashOf, SignedCommitment, MMR_ROOT_PAYLOAD_ID};
+use codec::Encode;
+use relay_substrate_client::ChainWithBeefy;
+use sp_consensus_beefy::{check_commitment_signature, DoubleVotingProof, VoteMessage};
+use sp_runtime::{traits::Hash, RuntimeAppPublic};
+
+/// Returns MMR root that has been signed by the BEEFY commitment.
+pub fn commitment_mmr_root<C: ChainWithBeefy, Number>(
+	commitment: &Commitment<Number>,
+) -> Option<MmrHashOf<C>> {
+	commitment.payload.get_decoded(&MMR_ROOT_PAYLOAD_ID)
+}
+
+/// Find BEEFY validators that have signed both given commitments and return proofs of
+/// their double votes.
+///
+/// Both commitments must be signed by the same validator set `validators`, ordered the same
+/// way as the signatures in the commitments. If commitments are for different blocks, are
+/// signed by different validator sets or have the same payload, there's no equivocation and
+/// empty vector is returned.
+///
+/// Only votes with valid signatures are included in the result, so it is safe to report all
+/// returned proofs.
+pub fn find_double_votes<Number, Id, MsgHash>(
+	validators: &[Id],
+	first: &SignedCommitment<Number, <Id as RuntimeAppPublic>::Signature>,
+	second: &SignedCommitment<Number, <Id as RuntimeAppPublic>::Signature>,
+) -> Vec<DoubleVotingProof<Number, Id, <Id as RuntimeAppPublic>::Signature>>
+where
+	Number: Clone + Encode + PartialEq,
+	Id: BeefyAuthorityId<MsgHash> + Clone,
+	MsgHash: Hash,
+{
+	if first.commitment.block_number != second.commitment.block_number ||
+		first.commitment.validator_set_id != second.commitment.validator_set_id ||
+		first.commitment.payload == second.commitment.payload
+	{
+		return Vec::new()
+	}
+
+	validators
+		.iter()
+		.zip(first.signatures.iter().zip(second.signatures.iter()))
+		.filter_map(|(id, signatures)| match signatures {
+			(Some(first_signature), Some(second_signature)) =>
+				Some((id, first_signature, second_signature)),
+			_ => None,
+		})
+		.filter(|(id, first_signature, second_signature)| {
+			check_commitment_signature::<_, _, MsgHash>(&first.commitment, *id, first_signature) &&
+				check_commitment_signature::<_, _, MsgHash>(
+					&second.commitment,
+					*id,
+					second_signature,
+				)
+		})
+		.map(|(id, first_signature, second_signature)| DoubleVotingProof {
+			first: VoteMessage {
+				commitment: first.commitment.clone(),
+				id: id.clone(),
+				signature: first_signature.clone(),
+			},
+			second: VoteMessage {
+				commitment: second.commitment.clone(),
+				id: id.clone(),
+				signature: second_signature.clone(),
+			},
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_beefy::{BeefyPayload, EcdsaValidatorId};
+	use sp_consensus_beefy::test_utils::Keyring;
+	use sp_runtime::traits::Keccak256;
+
+	type TestKeyring = Keyring<EcdsaValidatorId>;
+
+	fn commitment(mmr_root: [u8; 32]) -> Commitment<u32> {
+		Commitment {
+			payload: BeefyPayload::from_single_entry(MMR_ROOT_PAYLOAD_ID, mmr_root.encode()),
+			block_number: 42,
+			validator_set_id: 7,
+		}
+	}
+
+	fn sign(
+		commitment: Commitment<u32>,
+		signers: &[Option<TestKeyring>],
+	) -> SignedCommitment<u32, <EcdsaValidatorId as RuntimeAppPublic>::Signature> {
+		let encoded_commitment = commitment.encode();
+		SignedCommitment {
+			commitment,
+			signatures: signers
+				.iter()
+				.map(|signer| signer.map(|signer| signer.sign(&encoded_commitment)))
+				.collect(),
+		}
+	}
+
+	fn validators() -> Vec<EcdsaValidatorId> {
+		vec![TestKeyring::Alice.public(), TestKeyring::Bob.public(), TestKeyring::Charlie.public()]
+	}
+
+	#[test]
+	fn find_double_votes_works() {
+		let first = sign(
+			commitment([1; 32]),
+			&[Some(TestKeyring::Alice), Some(TestKeyring::Bob), Some(TestKeyring::Charlie)],
+		);
+		let second =
+			sign(commitment([2; 32]), &[None, Some(TestKeyring::Bob), Some(TestKeyring::Charlie)]);
+
+		let proofs = find_double_votes::<_, _, Keccak256>(&validators(), &first, &second);
+		assert_eq!(
+			proofs.iter().map(|proof| proof.offender_id().clone()).collect::<Vec<_>>(),
+			vec![TestKeyring::Bob.public(), TestKeyring::Charlie.public()],
+		);
+		assert!(proofs.iter().all(|proof| {
+			sp_consensus_beefy::check_double_voting_proof::<_, _, Keccak256>(proof)
+		}));
+	}
+
+	#[test]
+	fn find_double_votes_ignores_same_payload_and_invalid_signatures() {
+		let first =
+			sign(commitment([1; 32]), &[Some(TestKeyring::Alice), Some(TestKeyring::Bob), None]);
+		let second =
+			sign(commitment([1; 32]), &[Some(TestKeyring::Alice), Some(TestKeyring::Bob), None]);
+		assert!(find_double_votes::<_, _, Keccak256>(&validators(), &first, &second).is_empty());
+
+		// Bob's signature is replaced with Alice's one in the second commitment
+		let mut second =
+			sign(commitment([2; 32]), &[Some(TestKeyring::Alice), Some(TestKeyring::Bob), None]);
+		second.signatures[1] = second.signatures[0].clone();
+		assert_eq!(
+			find_double_votes::<_, _, Keccak256>(&validators(), &first, &second)
+				.into_iter()
+				.map(|proof| proof.offender_id().clone())
+				.collect::<Vec<_>>(),
+			vec![TestKeyring::Alice.public()],
+		);
+	}
+}
@@ -0,0 +1,110 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Metrics of the BEEFY finality synchronization loop.
+
+use finality_relay::SyncLoopMetrics;
+use relay_utils::{
+	metrics::{metric_name, register, Counter, IntGauge, Metric, PrometheusError, Registry, U64},
+	UniqueSaturatedInto,
+};
+
+/// BEEFY sync loop metrics.
+#[derive(Clone)]
+pub struct BeefySyncLoopMetrics {
+	/// Generic headers sync metrics: best block numbers at the source and at the target and
+	/// the flag that is set when target is following a different fork.
+	sync: SyncLoopMetrics,
+	/// Id of the BEEFY validator set that is expected to sign next commitment at the target.
+	target_validator_set_id: IntGauge,
+	/// Number of commitments submitted to the target chain.
+	submitted_commitments: Counter<U64>,
+	/// Number of detected BEEFY equivocations.
+	detected_equivocations: Counter<U64>,
+	/// Number of BEEFY double votes reported to the source chain.
+	reported_equivocations: Counter<U64>,
+}
+
+impl BeefySyncLoopMetrics {
+	/// Create BEEFY sync loop metrics.
+	pub fn new(prefix: Option<&str>) -> Result<Self, PrometheusError> {
+		Ok(BeefySyncLoopMetrics {
+			sync: SyncLoopMetrics::new(prefix, "source", "source_at_target")?,
+			target_validator_set_id: IntGauge::new(
+				metric_name(prefix, "source_beefy_validator_set_id_at_target"),
+				"Id of the source BEEFY validator set that is known to the target",
+			)?,
+			submitted_commitments: Counter::new(
+				metric_name(prefix, "submitted_beefy_commitments"),
+				"Number of BEEFY commitments submitted to the target",
+			)?,
+			detected_equivocations: Counter::new(
+				metric_name(prefix, "detected_beefy_equivocations"),
+				"Number of BEEFY equivocations detected by the relay",
+			)?,
+			reported_equivocations: Counter::new(
+				metric_name(prefix, "reported_beefy_equivocations"),
+				"Number of BEEFY double votes reported to the source by the relay",
+			)?,
+		})
+	}
+
+	/// Update best block number at source.
+	pub fn update_best_block_at_source<Number: UniqueSaturatedInto<u64>>(&self, number: Number) {
+		self.sync.update_best_block_at_source(number)
+	}
+
+	/// Update best block number at target.
+	pub fn update_best_block_at_target<Number: UniqueSaturatedInto<u64>>(&self, number: Number) {
+		self.sync.update_best_block_at_target(number)
+	}
+
+	/// Update using-same-fork flag.
+	pub fn update_using_same_fork(&self, using_same_fork: bool) {
+		self.sync.update_using_same_fork(using_same_fork)
+	}
+
+	/// Update id of the validator set, known to the target.
+	pub fn update_target_validator_set_id(&self, validator_set_id: u64) {
+		self.target_validator_set_id.set(validator_set_id)
+	}
+
+	/// Note that the commitment has been submitted to the target.
+	pub fn note_submitted_commitment(&self) {
+		self.submitted_commitments.inc()
+	}
+
+	/// Note that new equivocations have been detected.
+	pub fn note_detected_equivocations(&self, count: u64) {
+		self.detected_equivocations.inc_by(count)
+	}
+
+	/// Note that the double vote has been reported to the source.
+	pub fn note_reported_equivocation(&self) {
+		self.reported_equivocations.inc()
+	}
+}
+
+impl Metric for BeefySyncLoopMetrics {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		self.sync.register(registry)?;
+		register(self.target_validator_set_id.clone(), registry)?;
+		register(self.submitted_commitments.clone(), registry)?;
+		register(self.detected_equivocations.clone(), registry)?;
+		register(self.reported_equivocations.clone(), registry)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,316 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types and functions intended to ease adding of new Substrate -> Substrate
+//! BEEFY finality synchronization pipelines.
+//!
+//! Unlike GRANDPA justifications, BEEFY signed commitments are not proving source headers
+//! directly. Instead, they are signing the root of the source chain MMR. So apart from the
+//! commitment itself, the relay submits the MMR leaf, corresponding to the commitment block,
+//! and the proof of this leaf, generated by the `pallet-mmr` RPC of the source node.
+//!
+//! If the relay is given a source chain signer, it also reports BEEFY validators that it has
+//! caught signing conflicting commitments to the BEEFY pallet at the source chain.
+
+use crate::{
+	beefy::{source::SubstrateBeefySource, target::SubstrateBeefyTarget},
+	finality::source::RequiredHeaderNumberRef,
+	TransactionParams,
+};
+
+use async_trait::async_trait;
+use bp_beefy::{
+	BeefyAuthorityIdOf, BeefyAuthoritySetOf, BeefyMmrLeafOf, BeefySignedCommitmentOf,
+	BeefyValidatorSignatureOf, MmrProofOf,
+};
+use pallet_beefy::{Call as BeefyCall, Config as BeefyConfig};
+use pallet_bridge_beefy::{
+	BridgedMmrHashing, Call as BridgeBeefyCall, Config as BridgeBeefyConfig,
+};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, CallOf, Chain, ChainWithBeefy,
+	ChainWithTransactions, Client, HashOf,
+};
+use sp_consensus_beefy::DoubleVotingProof;
+use sp_core::Pair;
+use sp_runtime::traits::{Block, Header};
+use std::{fmt::Debug, marker::PhantomData};
+
+pub mod equivocation;
+pub mod metrics;
+pub mod source;
+pub mod sync_loop;
+pub mod target;
+
+/// Default limit of recent BEEFY commitments, kept by the relay.
+///
+/// BEEFY commitments are normally generated for a small fraction of source chain blocks, so
+/// this limit is more than enough to cover finality delays that we may meet in practice.
+pub(crate) const RECENT_COMMITMENTS_LIMIT: usize = 1024;
+
+/// Signed BEEFY commitment of the pipeline source chain.
+pub type BeefySignedCommitment<P> =
+	BeefySignedCommitmentOf<<P as SubstrateBeefySyncPipeline>::SourceChain>;
+/// BEEFY validator set of the pipeline source chain.
+pub type BeefyValidatorSet<P> = BeefyAuthoritySetOf<<P as SubstrateBeefySyncPipeline>::SourceChain>;
+/// BEEFY MMR leaf of the pipeline source chain.
+pub type BeefyMmrLeaf<P> = BeefyMmrLeafOf<<P as SubstrateBeefySyncPipeline>::SourceChain>;
+/// BEEFY MMR leaf proof of the pipeline source chain.
+pub type BeefyMmrProof<P> = MmrProofOf<<P as SubstrateBeefySyncPipeline>::SourceChain>;
+/// Proof of BEEFY validator of the pipeline source chain signing conflicting commitments.
+pub type BeefyDoubleVotingProof<P> = DoubleVotingProof<
+	BlockNumberOf<<P as SubstrateBeefySyncPipeline>::SourceChain>,
+	BeefyAuthorityIdOf<<P as SubstrateBeefySyncPipeline>::SourceChain>,
+	BeefyValidatorSignatureOf<<P as SubstrateBeefySyncPipeline>::SourceChain>,
+>;
+/// BEEFY key owner proof of the pipeline source chain.
+pub type BeefyKeyOwnerProof<P> =
+	<<P as SubstrateBeefySyncPipeline>::SourceChain as ChainWithBeefy>::KeyOwnerProof;
+
+/// Substrate -> Substrate BEEFY finality synchronization pipeline.
+#[async_trait]
+pub trait SubstrateBeefySyncPipeline: 'static + Clone + Debug + Send + Sync {
+	/// BEEFY commitments of this chain are submitted to the `TargetChain`. Double votes of its
+	/// validators are reported to this chain.
+	type SourceChain: ChainWithBeefy + ChainWithTransactions;
+	/// BEEFY commitments of the `SourceChain` are submitted to this chain.
+	type TargetChain: ChainWithTransactions;
+
+	/// How submit commitment call is built?
+	type SubmitBeefyCommitmentCallBuilder: SubmitBeefyCommitmentCallBuilder<Self>;
+	/// How report double voting call is built?
+	type ReportDoubleVotingCallBuilder: ReportDoubleVotingCallBuilder<Self>;
+
+	/// Add relay guards if required.
+	async fn start_relay_guards(
+		target_client: &impl Client<Self::TargetChain>,
+		enable_version_guard: bool,
+	) -> relay_substrate_client::Result<()> {
+		if enable_version_guard {
+			relay_substrate_client::guard::abort_on_spec_version_change(
+				target_client.clone(),
+				target_client.simple_runtime_version().await?.spec_version,
+			);
+		}
+		Ok(())
+	}
+}
+
+/// Different ways of building `submit_commitment` calls.
+pub trait SubmitBeefyCommitmentCallBuilder<P: SubstrateBeefySyncPipeline> {
+	/// Given signed BEEFY commitment, the validator set that has signed it, MMR leaf of
+	/// the commitment block and the leaf proof, build call of `submit_commitment` function
+	/// of bridge BEEFY module at the target chain.
+	fn build_submit_commitment_call(
+		commitment: BeefySignedCommitment<P>,
+		validator_set: BeefyValidatorSet<P>,
+		mmr_leaf: BeefyMmrLeaf<P>,
+		mmr_proof: BeefyMmrProof<P>,
+	) -> CallOf<P::TargetChain>;
+}
+
+/// Building `submit_commitment` call when you have direct access to the target
+/// chain runtime.
+pub struct DirectSubmitBeefyCommitmentCallBuilder<P, R, I> {
+	_phantom: PhantomData<(P, R, I)>,
+}
+
+impl<P, R, I> SubmitBeefyCommitmentCallBuilder<P>
+	for DirectSubmitBeefyCommitmentCallBuilder<P, R, I>
+where
+	P: SubstrateBeefySyncPipeline,
+	R: BridgeBeefyConfig<I>,
+	I: 'static,
+	R::BridgedChain: bp_beefy::ChainWithBeefy<
+		BlockNumber = BlockNumberOf<P::SourceChain>,
+		Hash = HashOf<P::SourceChain>,
+		MmrHash = bp_beefy::MmrHashOf<P::SourceChain>,
+		AuthorityId = bp_beefy::BeefyAuthorityIdOf<P::SourceChain>,
+		BeefyMmrLeafExtra = bp_beefy::BeefyMmrLeafExtraOf<P::SourceChain>,
+	>,
+	BridgedMmrHashing<R, I>: 'static + Send + Sync,
+	CallOf<P::TargetChain>: From<BridgeBeefyCall<R, I>>,
+{
+	fn build_submit_commitment_call(
+		commitment: BeefySignedCommitment<P>,
+		validator_set: BeefyValidatorSet<P>,
+		mmr_leaf: BeefyMmrLeaf<P>,
+		mmr_proof: BeefyMmrProof<P>,
+	) -> CallOf<P::TargetChain> {
+		BridgeBeefyCall::<R, I>::submit_commitment {
+			commitment,
+			validator_set,
+			mmr_leaf: Box::new(mmr_leaf),
+			mmr_proof,
+		}
+		.into()
+	}
+}
+
+/// Macro that generates `SubmitBeefyCommitmentCallBuilder` implementation for the case when
+/// you only have an access to the mocked version of target chain runtime. In this case you
+/// should provide "name" of the call variant for the bridge BEEFY calls and the "name" of
+/// the variant for the `submit_commitment` call within that first option.
+#[rustfmt::skip]
+#[macro_export]
+macro_rules! generate_submit_beefy_commitment_call_builder {
+	($pipeline:ident, $mocked_builder:ident, $bridge_beefy:path, $submit_commitment:path) => {
+		pub struct $mocked_builder;
+
+		impl $crate::beefy::SubmitBeefyCommitmentCallBuilder<$pipeline>
+			for $mocked_builder
+		{
+			fn build_submit_commitment_call(
+				commitment: $crate::beefy::BeefySignedCommitment<$pipeline>,
+				validator_set: $crate::beefy::BeefyValidatorSet<$pipeline>,
+				mmr_leaf: $crate::beefy::BeefyMmrLeaf<$pipeline>,
+				mmr_proof: $crate::beefy::BeefyMmrProof<$pipeline>,
+			) -> relay_substrate_client::CallOf<
+				<$pipeline as $crate::beefy::SubstrateBeefySyncPipeline>::TargetChain
+			> {
+				bp_runtime::paste::item! {
+					$bridge_beefy($submit_commitment {
+						commitment: commitment,
+						validator_set: validator_set,
+						mmr_leaf: Box::new(mmr_leaf),
+						mmr_proof: mmr_proof,
+					})
+				}
+			}
+		}
+	};
+}
+
+/// Different ways of building `report_double_voting` calls.
+pub trait ReportDoubleVotingCallBuilder<P: SubstrateBeefySyncPipeline> {
+	/// Given proof of validator signing conflicting commitments and the proof that the key
+	/// belongs to the validator, build call of `report_double_voting` function of the BEEFY
+	/// module at the source chain.
+	fn build_report_double_voting_call(
+		double_voting_proof: BeefyDoubleVotingProof<P>,
+		key_owner_proof: BeefyKeyOwnerProof<P>,
+	) -> CallOf<P::SourceChain>;
+}
+
+/// Building `report_double_voting` call when you have direct access to the source
+/// chain runtime.
+pub struct DirectReportDoubleVotingCallBuilder<P, R> {
+	_phantom: PhantomData<(P, R)>,
+}
+
+impl<P, R> ReportDoubleVotingCallBuilder<P> for DirectReportDoubleVotingCallBuilder<P, R>
+where
+	P: SubstrateBeefySyncPipeline,
+	R: BeefyConfig<
+		BeefyId = BeefyAuthorityIdOf<P::SourceChain>,
+		KeyOwnerProof = BeefyKeyOwnerProof<P>,
+	>,
+	<R::Block as Block>::Header: Header<Number = BlockNumberOf<P::SourceChain>>,
+	CallOf<P::SourceChain>: From<BeefyCall<R>>,
+{
+	fn build_report_double_voting_call(
+		double_voting_proof: BeefyDoubleVotingProof<P>,
+		key_owner_proof: BeefyKeyOwnerProof<P>,
+	) -> CallOf<P::SourceChain> {
+		BeefyCall::<R>::report_double_voting {
+			equivocation_proof: Box::new(double_voting_proof),
+			key_owner_proof,
+		}
+		.into()
+	}
+}
+
+/// Macro that generates `ReportDoubleVotingCallBuilder` implementation for the case when
+/// you only have an access to the mocked version of source chain runtime. In this case you
+/// should provide "name" of the call variant for the BEEFY calls and the "name" of
+/// the variant for the `report_double_voting` call within that first option.
+#[rustfmt::skip]
+#[macro_export]
+macro_rules! generate_report_double_voting_call_builder {
+	($pipeline:ident, $mocked_builder:ident, $beefy:path, $report_double_voting:path) => {
+		pub struct $mocked_builder;
+
+		impl $crate::beefy::ReportDoubleVotingCallBuilder<$pipeline>
+			for $mocked_builder
+		{
+			fn build_report_double_voting_call(
+				double_voting_proof: $crate::beefy::BeefyDoubleVotingProof<$pipeline>,
+				key_owner_proof: $crate::beefy::BeefyKeyOwnerProof<$pipeline>,
+			) -> relay_substrate_client::CallOf<
+				<$pipeline as $crate::beefy::SubstrateBeefySyncPipeline>::SourceChain
+			> {
+				bp_runtime::paste::item! {
+					$beefy($report_double_voting {
+						equivocation_proof: Box::new(double_voting_proof),
+						key_owner_proof: key_owner_proof,
+					})
+				}
+			}
+		}
+	};
+}
+
+/// Returns sync parameters of the BEEFY sync loop, that is relaying commitments from
+/// `P::SourceChain` to `P::TargetChain`.
+pub(crate) fn sync_params<P: SubstrateBeefySyncPipeline>() -> sync_loop::BeefySyncParams {
+	sync_loop::BeefySyncParams {
+		tick: std::cmp::max(
+			P::SourceChain::AVERAGE_BLOCK_INTERVAL,
+			P::TargetChain::AVERAGE_BLOCK_INTERVAL,
+		),
+		recent_commitments_limit: RECENT_COMMITMENTS_LIMIT,
+	}
+}
+
+/// Run Substrate-to-Substrate BEEFY finality sync loop.
+///
+/// If `required_block_number` is `Some(_)`, the loop works in on-demand mode: it only submits
+/// commitments that are required to keep the bridge alive (i.e. commitments that are handing
+/// over to the next BEEFY validator set) and commitments that are proving the required block.
+///
+/// If `source_transaction_params` is `Some(_)`, double votes of the source chain BEEFY
+/// validators are reported to the source chain.
+pub async fn run<P: SubstrateBeefySyncPipeline>(
+	source_client: impl Client<P::SourceChain>,
+	target_client: impl Client<P::TargetChain>,
+	required_block_number: Option<RequiredHeaderNumberRef<P::SourceChain>>,
+	source_transaction_params: Option<TransactionParams<AccountKeyPairOf<P::SourceChain>>>,
+	target_transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+	metrics_params: relay_utils::metrics::MetricsParams,
+) -> anyhow::Result<()>
+where
+	AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	log::info!(
+		target: "bridge",
+		"Starting {} -> {} BEEFY finality relay{}",
+		P::SourceChain::NAME,
+		P::TargetChain::NAME,
+		if required_block_number.is_some() { " in on-demand mode" } else { "" },
+	);
+
+	sync_loop::run(
+		SubstrateBeefySource::<P, _>::new(source_client, source_transaction_params),
+		SubstrateBeefyTarget::<P, _>::new(target_client, target_transaction_params),
+		sync_params::<P>(),
+		required_block_number,
+		metrics_params,
+		futures::future::pending(),
+	)
+	.await
+	.map_err(|e| anyhow::format_err!("{}", e))
+}
@@ -0,0 +1,380 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Substrate client as Substrate BEEFY commitments source.
+
+use crate::{
+	beefy::{
+		BeefyDoubleVotingProof, BeefyKeyOwnerProof, BeefyMmrLeaf, BeefyMmrProof,
+		BeefySignedCommitment, BeefyValidatorSet, ReportDoubleVotingCallBuilder,
+		SubstrateBeefySyncPipeline,
+	},
+	TransactionParams,
+};
+
+use async_trait::async_trait;
+use bp_beefy::{SignedCommitment, ValidatorSetId, BEEFY_ENGINE_ID};
+use bp_runtime::TransactionEra;
+use codec::Decode;
+use futures::{future::try_join_all, stream::unfold, Stream, StreamExt};
+use num_traits::One;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, BlockWithJustification, Chain, Client, Error,
+	HashOf, TransactionTracker, UnsignedTransaction,
+};
+use relay_utils::relay_loop::Client as RelayClient;
+use scale_info::TypeInfo;
+use sp_consensus_beefy::VersionedFinalityProof;
+use sp_core::Pair;
+use std::{ops::ControlFlow, pin::Pin};
+
+/// Name of the runtime API method that is returning the BEEFY validator set.
+const BEEFY_API_VALIDATOR_SET_METHOD: &str = "BeefyApi_validator_set";
+
+/// Number of source blocks that are read concurrently when looking for persisted commitments.
+const COMMITMENTS_SCAN_BATCH: usize = 16;
+
+/// BEEFY signed commitments stream.
+pub type BeefyCommitmentsStream<P> = Pin<Box<dyn Stream<Item = BeefySignedCommitment<P>> + Send>>;
+
+/// Substrate node as BEEFY commitments source.
+pub struct SubstrateBeefySource<P: SubstrateBeefySyncPipeline, SourceClnt> {
+	client: SourceClnt,
+	transaction_params: Option<TransactionParams<AccountKeyPairOf<P::SourceChain>>>,
+}
+
+impl<P: SubstrateBeefySyncPipeline, SourceClnt: Client<P::SourceChain>>
+	SubstrateBeefySource<P, SourceClnt>
+{
+	/// Create new BEEFY commitments source using given client.
+	///
+	/// If `transaction_params` is `None`, the source can't be used to report double votes.
+	pub fn new(
+		client: SourceClnt,
+		transaction_params: Option<TransactionParams<AccountKeyPairOf<P::SourceChain>>>,
+	) -> Self {
+		SubstrateBeefySource { client, transaction_params }
+	}
+
+	/// Returns reference to the underlying RPC client.
+	pub fn client(&self) -> &SourceClnt {
+		&self.client
+	}
+
+	/// Returns best finalized block number.
+	pub async fn best_finalized_block_number(
+		&self,
+	) -> Result<BlockNumberOf<P::SourceChain>, Error> {
+		// we **CAN** continue to relay commitments if source node is out of sync, because
+		// target node may be missing commitments that are already available at the source
+		self.client.best_finalized_header_number().await
+	}
+
+	/// Returns hash of the canonical source block with given number.
+	pub async fn block_hash(
+		&self,
+		block_number: BlockNumberOf<P::SourceChain>,
+	) -> Result<HashOf<P::SourceChain>, Error> {
+		self.client.header_hash_by_number(block_number).await
+	}
+
+	/// Subscribe to new BEEFY signed commitments.
+	pub async fn commitments(&self) -> Result<BeefyCommitmentsStream<P>, Error> {
+		Ok(unfold(
+			self.client.subscribe_beefy_finality_justifications().await?,
+			move |mut subscription| async move {
+				loop {
+					let next_justification = subscription.next().await?;
+					match decode_commitment::<P>(&next_justification) {
+						Ok(commitment) => return Some((commitment, subscription)),
+						Err(err) => {
+							log::error!(
+								target: "bridge",
+								"Failed to decode commitment from the {} BEEFY justifications stream: {:?}",
+								P::SourceChain::NAME,
+								err,
+							);
+							continue
+						},
+					}
+				}
+			},
+		)
+		.boxed())
+	}
+
+	/// Returns BEEFY commitment, persisted in the canonical source block with given number.
+	///
+	/// BEEFY justifications are only persisted for blocks that have been finalized by BEEFY,
+	/// so `None` is returned for all other blocks.
+	pub async fn commitment_at(
+		&self,
+		block_number: BlockNumberOf<P::SourceChain>,
+	) -> Result<Option<BeefySignedCommitment<P>>, Error> {
+		let block_hash = self.block_hash(block_number).await?;
+		let signed_block = self.client.block_by_hash(block_hash).await?;
+		signed_block
+			.justification(BEEFY_ENGINE_ID)
+			.map(|raw_justification| decode_commitment::<P>(raw_justification))
+			.transpose()
+	}
+
+	/// Returns BEEFY validator set that is active at the canonical source block with given
+	/// number.
+	pub async fn validator_set(
+		&self,
+		block_number: BlockNumberOf<P::SourceChain>,
+	) -> Result<BeefyValidatorSet<P>, Error> {
+		let block_hash = self.block_hash(block_number).await?;
+		self.client
+			.state_call::<_, Option<BeefyValidatorSet<P>>>(
+				block_hash,
+				BEEFY_API_VALIDATOR_SET_METHOD.into(),
+				(),
+			)
+			.await?
+			.ok_or_else(|| {
+				Error::Custom(format!(
+					"BEEFY validator set is not available at {} block {:?}",
+					P::SourceChain::NAME,
+					block_number,
+				))
+			})
+	}
+
+	/// Returns MMR leaf, appended at the canonical source block with given number and its
+	/// proof, generated against MMR root at the same block.
+	///
+	/// The MMR root at the block is what BEEFY validators are signing, so the leaf and proof
+	/// may be verified using the signed commitment for this block.
+	pub async fn mmr_leaf_and_proof(
+		&self,
+		block_number: BlockNumberOf<P::SourceChain>,
+	) -> Result<(BeefyMmrLeaf<P>, BeefyMmrProof<P>), Error> {
+		let leaves_proof =
+			self.client.generate_mmr_leaf_proof(block_number, Some(block_number)).await?;
+
+		// leaves are encoded as `Vec<EncodableOpaqueLeaf>` and every `EncodableOpaqueLeaf` is
+		// just a wrapper over the encoded leaf
+		let mut encoded_leaves = Vec::<Vec<u8>>::decode(&mut &leaves_proof.leaves[..])?;
+		if encoded_leaves.len() != 1 {
+			return Err(Error::Custom(format!(
+				"Expected single MMR leaf for {} block {:?}, got {}",
+				P::SourceChain::NAME,
+				block_number,
+				encoded_leaves.len(),
+			)))
+		}
+		let mmr_leaf = BeefyMmrLeaf::<P>::decode(&mut &encoded_leaves.remove(0)[..])?;
+		let mmr_proof = BeefyMmrProof::<P>::decode(&mut &leaves_proof.proof[..])?;
+
+		Ok((mmr_leaf, mmr_proof))
+	}
+
+	/// Finds the first BEEFY commitment in the `[from; to]` range of canonical source blocks.
+	pub async fn find_first_commitment(
+		&self,
+		from: BlockNumberOf<P::SourceChain>,
+		to: BlockNumberOf<P::SourceChain>,
+	) -> Result<Option<BeefySignedCommitment<P>>, Error> {
+		let mut current = from;
+		while current <= to {
+			if let Some(commitment) = self.commitment_at(current).await? {
+				return Ok(Some(commitment))
+			}
+			current = current + One::one();
+		}
+
+		Ok(None)
+	}
+
+	/// Finds the best BEEFY commitment, signed by validator set with given id, in the
+	/// `[from; to]` range of canonical source blocks.
+	///
+	/// Blocks are read backwards from `to`, [`COMMITMENTS_SCAN_BATCH`] blocks at a time, until
+	/// the commitment is found or a commitment of the older validator set is met. This may still
+	/// read many blocks, so it should only be used when the relay has missed commitments of the
+	/// given validator set (e.g. when it has been restarted).
+	pub async fn find_commitment_signed_by(
+		&self,
+		validator_set_id: ValidatorSetId,
+		from: BlockNumberOf<P::SourceChain>,
+		to: BlockNumberOf<P::SourceChain>,
+	) -> Result<Option<BeefySignedCommitment<P>>, Error> {
+		let mut next = Some(to);
+		loop {
+			let batch = std::iter::from_fn(|| {
+				let current = next.filter(|current| *current >= from)?;
+				next = (current > from).then(|| current - One::one());
+				Some(current)
+			})
+			.take(COMMITMENTS_SCAN_BATCH)
+			.collect::<Vec<_>>();
+			if batch.is_empty() {
+				return Ok(None)
+			}
+
+			let commitments =
+				try_join_all(batch.into_iter().map(|number| self.commitment_at(number))).await?;
+			if let ControlFlow::Break(commitment) =
+				find_signed_by(commitments.into_iter().flatten(), validator_set_id)
+			{
+				return Ok(commitment)
+			}
+		}
+	}
+
+	/// Reports the validator that has signed both commitments of the double voting proof to the
+	/// BEEFY pallet at the source chain.
+	///
+	/// Returns `None` if the source has been created without transaction parameters.
+	pub async fn report_double_voting(
+		&self,
+		double_voting_proof: BeefyDoubleVotingProof<P>,
+	) -> Result<Option<TransactionTracker<P::SourceChain, SourceClnt>>, Error>
+	where
+		AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
+	{
+		let Some(ref transaction_params) = self.transaction_params else { return Ok(None) };
+
+		let at = self.client.best_finalized_header_hash().await?;
+		let set_id = double_voting_proof.set_id();
+		let offender = double_voting_proof.offender_id().clone();
+		let opaque_key_owner_proof = self
+			.client
+			.generate_beefy_key_ownership_proof(at, set_id, offender.clone())
+			.await?
+			.ok_or_else(|| {
+				Error::Custom(format!(
+					"Couldn't get BEEFY key ownership proof from {} at block: {at:?} \
+					for offender: {offender:?}, set_id: {set_id}",
+					P::SourceChain::NAME,
+				))
+			})?;
+		let key_owner_proof: BeefyKeyOwnerProof<P> =
+			opaque_key_owner_proof.decode().ok_or_else(|| {
+				Error::Custom(format!(
+					"Couldn't decode BEEFY `OpaqueKeyOwnershipProof` from {} to `{:?}` for \
+					offender: {offender:?}, set_id: {set_id}, at block: {at:?}",
+					P::SourceChain::NAME,
+					<BeefyKeyOwnerProof<P> as TypeInfo>::type_info().path,
+				))
+			})?;
+
+		let mortality = transaction_params.mortality;
+		let call = P::ReportDoubleVotingCallBuilder::build_report_double_voting_call(
+			double_voting_proof,
+			key_owner_proof,
+		);
+		self.client
+			.submit_and_watch_signed_extrinsic(
+				&transaction_params.signer,
+				move |best_block_id, transaction_nonce| {
+					Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
+						.era(TransactionEra::new(best_block_id, mortality)))
+				},
+			)
+			.await
+			.map(Some)
+	}
+}
+
+impl<P: SubstrateBeefySyncPipeline, SourceClnt: Clone> Clone
+	for SubstrateBeefySource<P, SourceClnt>
+{
+	fn clone(&self) -> Self {
+		SubstrateBeefySource {
+			client: self.client.clone(),
+			transaction_params: self.transaction_params.clone(),
+		}
+	}
+}
+
+#[async_trait]
+impl<P: SubstrateBeefySyncPipeline, SourceClnt: Client<P::SourceChain>> RelayClient
+	for SubstrateBeefySource<P, SourceClnt>
+{
+	type Error = Error;
+
+	async fn reconnect(&mut self) -> Result<(), Error> {
+		self.client.reconnect().await
+	}
+}
+
+/// Decode BEEFY signed commitment from the encoded BEEFY justification.
+fn decode_commitment<P: SubstrateBeefySyncPipeline>(
+	raw_justification: &[u8],
+) -> Result<BeefySignedCommitment<P>, Error> {
+	let VersionedFinalityProof::V1(commitment) = VersionedFinalityProof::<
+		BlockNumberOf<P::SourceChain>,
+		bp_beefy::BeefyValidatorSignatureOf<P::SourceChain>,
+	>::decode(&mut &raw_justification[..])?;
+	Ok(commitment)
+}
+
+/// Looks for the commitment signed by the validator set with given id among the commitments,
+/// persisted in a batch of consecutive source blocks, ordered from the best block.
+///
+/// Breaks with the commitment if it is found, or with `None` if the batch reaches commitments of
+/// the older validator sets, because all commitments of the older blocks are signed by them too.
+fn find_signed_by<Number, Signature>(
+	commitments: impl IntoIterator<Item = SignedCommitment<Number, Signature>>,
+	validator_set_id: ValidatorSetId,
+) -> ControlFlow<Option<SignedCommitment<Number, Signature>>> {
+	for commitment in commitments {
+		if commitment.commitment.validator_set_id == validator_set_id {
+			return ControlFlow::Break(Some(commitment))
+		}
+		if commitment.commitment.validator_set_id < validator_set_id {
+			return ControlFlow::Break(None)
+		}
+	}
+	ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_beefy::{BeefyPayload, Commitment, MMR_ROOT_PAYLOAD_ID};
+
+	fn commitment(block_number: u32, validator_set_id: u64) -> SignedCommitment<u32, ()> {
+		SignedCommitment {
+			commitment: Commitment {
+				payload: BeefyPayload::from_single_entry(MMR_ROOT_PAYLOAD_ID, vec![]),
+				block_number,
+				validator_set_id,
+			},
+			signatures: vec![],
+		}
+	}
+
+	#[test]
+	fn find_signed_by_selects_best_commitment_of_validator_set() {
+		// commitments of newer sets are skipped
+		assert_eq!(
+			find_signed_by(vec![commitment(40, 3), commitment(30, 2), commitment(20, 2)], 2),
+			ControlFlow::Break(Some(commitment(30, 2))),
+		);
+		// there can't be commitments of the set before commitments of the older set
+		assert_eq!(
+			find_signed_by(vec![commitment(40, 3), commitment(30, 1), commitment(20, 2)], 2),
+			ControlFlow::Break(None),
+		);
+		// the search continues with the older blocks
+		assert_eq!(find_signed_by(vec![commitment(40, 3)], 2), ControlFlow::Continue(()));
+		assert_eq!(find_signed_by(Vec::new(), 2), ControlFlow::Continue(()));
+	}
+}
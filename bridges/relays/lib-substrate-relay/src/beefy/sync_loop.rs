@@ -0,0 +1,818 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! BEEFY finality synchronization loop.
+//!
+//! The loop follows BEEFY signed commitments of the source chain and submits them to the bridge
+//! BEEFY pallet at the target chain. The pallet is only able to verify commitments that are
+//! signed by the validator set it knows about, and it learns about the next validator set from
+//! the MMR leaf of every imported commitment. So the loop always selects the best known
+//! commitment, signed by the validator set that is currently known to the target.
+
+use crate::{
+	beefy::{
+		equivocation::{commitment_mmr_root, find_double_votes},
+		metrics::BeefySyncLoopMetrics,
+		source::{BeefyCommitmentsStream, SubstrateBeefySource},
+		target::SubstrateBeefyTarget,
+		BeefySignedCommitment, SubstrateBeefySyncPipeline,
+	},
+	finality::source::RequiredHeaderNumberRef,
+};
+
+use bp_beefy::{BeefyCommitmentHasher, SignedCommitment, ValidatorSetId};
+use futures::{future::Fuse, select, Future, FutureExt, StreamExt};
+use num_traits::{One, Saturating};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, Chain, Client, Error as SubstrateError,
+	TransactionTracker as SubstrateTransactionTracker,
+};
+use relay_utils::{
+	metrics::MetricsParams, relay_loop::RECONNECT_DELAY, FailedClient, MaybeConnectionError,
+	TrackedTransactionStatus, TransactionTracker,
+};
+use sp_core::Pair;
+use std::{
+	collections::BTreeMap,
+	time::{Duration, Instant},
+};
+
+/// BEEFY finality synchronization loop parameters.
+#[derive(Debug, Clone)]
+pub struct BeefySyncParams {
+	/// Interval at which we check updates on both clients. Normally should be larger than
+	/// `min(source_block_time, target_block_time)`.
+	pub tick: Duration,
+	/// Number of recent BEEFY commitments to keep in internal buffer between loop iterations.
+	pub recent_commitments_limit: usize,
+}
+
+/// Returns prefix of the BEEFY sync loop metrics.
+pub fn metrics_prefix<P: SubstrateBeefySyncPipeline>() -> String {
+	format!("{}_to_{}_BEEFY_Sync", P::SourceChain::NAME, P::TargetChain::NAME)
+}
+
+/// Error that may happen inside BEEFY synchronization loop.
+#[derive(Debug)]
+enum Error<Number> {
+	/// Source client request has failed with given error.
+	Source(SubstrateError),
+	/// Target client request has failed with given error.
+	Target(SubstrateError),
+	/// `submit_commitment` transaction failed.
+	CommitmentSubmissionTxFailed {
+		#[allow(dead_code)]
+		submitted_number: Number,
+		#[allow(dead_code)]
+		best_number_at_target: Number,
+	},
+	/// `submit_commitment` transaction lost.
+	CommitmentSubmissionTxLost,
+}
+
+impl<Number> Error<Number> {
+	fn fail_if_connection_error(&self) -> Result<(), FailedClient> {
+		match *self {
+			Error::Source(ref error) if error.is_connection_error() => Err(FailedClient::Source),
+			Error::Target(ref error) if error.is_connection_error() => Err(FailedClient::Target),
+			_ => Ok(()),
+		}
+	}
+}
+
+/// BEEFY sync information.
+#[derive(Debug, Clone, PartialEq)]
+struct SyncInfo<Number> {
+	/// Best finalized block at the source client.
+	best_number_at_source: Number,
+	/// Number of the best source block, for which the commitment has been imported by the
+	/// target client.
+	best_number_at_target: Number,
+	/// Id of the validator set, that is expected to sign next commitment at the target.
+	validator_set_id_at_target: ValidatorSetId,
+}
+
+/// Buffer of recent BEEFY commitments, received from the source node.
+struct CommitmentsBuf<Number, Signature> {
+	commitments: BTreeMap<Number, SignedCommitment<Number, Signature>>,
+}
+
+impl<Number: Copy + Ord, Signature: Clone> CommitmentsBuf<Number, Signature> {
+	/// Create new empty buffer.
+	fn new() -> Self {
+		CommitmentsBuf { commitments: BTreeMap::new() }
+	}
+
+	/// Returns buffered commitment for given block.
+	fn get(&self, number: Number) -> Option<&SignedCommitment<Number, Signature>> {
+		self.commitments.get(&number)
+	}
+
+	/// Insert new commitment into the buffer.
+	///
+	/// If there's already a different commitment for the same block, signed by the same
+	/// validator set, it is returned. This means that (some of) validators are equivocating.
+	fn insert(
+		&mut self,
+		commitment: SignedCommitment<Number, Signature>,
+	) -> Option<SignedCommitment<Number, Signature>> {
+		let number = commitment.commitment.block_number;
+		match self.commitments.get(&number) {
+			Some(existing) if existing.commitment == commitment.commitment => None,
+			Some(existing)
+				if existing.commitment.validator_set_id ==
+					commitment.commitment.validator_set_id =>
+				Some(existing.clone()),
+			_ => {
+				self.commitments.insert(number, commitment);
+				None
+			},
+		}
+	}
+
+	/// Remove commitments for blocks that are older than `oldest_to_keep` and keep the buffer
+	/// size under `limit`.
+	fn prune(&mut self, oldest_to_keep: Number, limit: usize) {
+		self.commitments = self.commitments.split_off(&oldest_to_keep);
+		while self.commitments.len() > limit {
+			self.commitments.pop_first();
+		}
+	}
+
+	/// Returns the best commitment for the block after `after`, signed by the validator set
+	/// with given id.
+	fn best_signed_by(
+		&self,
+		validator_set_id: ValidatorSetId,
+		after: Number,
+	) -> Option<&SignedCommitment<Number, Signature>> {
+		self.commitments
+			.values()
+			.rev()
+			.take_while(|commitment| commitment.commitment.block_number > after)
+			.find(|commitment| commitment.commitment.validator_set_id == validator_set_id)
+	}
+
+	/// Returns the first commitment, signed by validator set that is newer than the validator
+	/// set with given id.
+	fn first_signed_by_newer_set(
+		&self,
+		validator_set_id: ValidatorSetId,
+	) -> Option<&SignedCommitment<Number, Signature>> {
+		self.commitments
+			.values()
+			.find(|commitment| commitment.commitment.validator_set_id > validator_set_id)
+	}
+}
+
+/// Returns true if the commitment should be submitted to the target chain.
+///
+/// In on-demand mode (`required_number` is `Some(_)`), we only submit commitments that are
+/// handing over to the next validator set and commitments that are required to prove the
+/// `required_number` block. Keep in mind that the commitment for block `N` is only proving
+/// its parent (`N - 1`).
+fn is_submission_required<Number: Copy + Ord>(
+	info: &SyncInfo<Number>,
+	is_mandatory: bool,
+	required_number: Option<Number>,
+) -> bool {
+	match required_number {
+		Some(required_number) => is_mandatory || required_number >= info.best_number_at_target,
+		None => true,
+	}
+}
+
+/// BEEFY synchronization loop state.
+struct BeefySyncLoop<P: SubstrateBeefySyncPipeline, SourceClnt, TargetClnt> {
+	source: SubstrateBeefySource<P, SourceClnt>,
+	target: SubstrateBeefyTarget<P, TargetClnt>,
+
+	sync_params: BeefySyncParams,
+	required_number: Option<RequiredHeaderNumberRef<P::SourceChain>>,
+	metrics: Option<BeefySyncLoopMetrics>,
+
+	progress: (Instant, Option<BlockNumberOf<P::SourceChain>>),
+	commitments_stream: Option<BeefyCommitmentsStream<P>>,
+	commitments_buf: CommitmentsBuf<
+		BlockNumberOf<P::SourceChain>,
+		bp_beefy::BeefyValidatorSignatureOf<P::SourceChain>,
+	>,
+	best_submitted_number: Option<BlockNumberOf<P::SourceChain>>,
+	last_checked_number_at_target: Option<BlockNumberOf<P::SourceChain>>,
+}
+
+impl<P, SourceClnt, TargetClnt> BeefySyncLoop<P, SourceClnt, TargetClnt>
+where
+	P: SubstrateBeefySyncPipeline,
+	SourceClnt: Client<P::SourceChain>,
+	TargetClnt: Client<P::TargetChain>,
+	AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	fn new(
+		source: SubstrateBeefySource<P, SourceClnt>,
+		target: SubstrateBeefyTarget<P, TargetClnt>,
+		sync_params: BeefySyncParams,
+		required_number: Option<RequiredHeaderNumberRef<P::SourceChain>>,
+		metrics: Option<BeefySyncLoopMetrics>,
+	) -> Self {
+		BeefySyncLoop {
+			source,
+			target,
+			sync_params,
+			required_number,
+			metrics,
+			progress: (Instant::now(), None),
+			commitments_stream: None,
+			commitments_buf: CommitmentsBuf::new(),
+			best_submitted_number: None,
+			last_checked_number_at_target: None,
+		}
+	}
+
+	/// Read sync information from both clients.
+	async fn sync_info(
+		&self,
+	) -> Result<SyncInfo<BlockNumberOf<P::SourceChain>>, Error<BlockNumberOf<P::SourceChain>>> {
+		let best_number_at_source =
+			self.source.best_finalized_block_number().await.map_err(Error::Source)?;
+
+		// we can't continue to relay commitments if target node is out of sync, because
+		// it may have already received (some of) commitments that we're going to relay
+		self.target.client().ensure_synced().await.map_err(Error::Target)?;
+		// we can't relay commitments if bridge pallet at target chain is halted
+		self.target.ensure_pallet_active().await.map_err(Error::Target)?;
+
+		let at = self.target.client().best_header_hash().await.map_err(Error::Target)?;
+		let best_number_at_target =
+			self.target.best_imported_block_number(at).await.map_err(Error::Target)?;
+		let validator_set_id_at_target =
+			self.target.current_authority_set_info(at).await.map_err(Error::Target)?.id;
+
+		Ok(SyncInfo { best_number_at_source, best_number_at_target, validator_set_id_at_target })
+	}
+
+	fn update_metrics(&self, info: &SyncInfo<BlockNumberOf<P::SourceChain>>) {
+		if let Some(ref metrics) = self.metrics {
+			metrics.update_best_block_at_source(info.best_number_at_source);
+			metrics.update_best_block_at_target(info.best_number_at_target);
+			metrics.update_target_validator_set_id(info.validator_set_id_at_target);
+		}
+	}
+
+	fn update_progress(&mut self, info: &SyncInfo<BlockNumberOf<P::SourceChain>>) {
+		let (prev_time, prev_best_number_at_target) = self.progress;
+		let now = Instant::now();
+
+		let needs_update = now - prev_time > Duration::from_secs(10) ||
+			prev_best_number_at_target != Some(info.best_number_at_target);
+		if !needs_update {
+			return
+		}
+
+		log::info!(
+			target: "bridge",
+			"Synced BEEFY commitments of {} up to block {:?} of {:?} (validator set: {})",
+			P::SourceChain::NAME,
+			info.best_number_at_target,
+			info.best_number_at_source,
+			info.validator_set_id_at_target,
+		);
+
+		self.progress = (now, Some(info.best_number_at_target))
+	}
+
+	/// Read all commitments that are available in the commitments stream, checking them for
+	/// equivocations.
+	async fn read_commitments(&mut self) -> Result<(), Error<BlockNumberOf<P::SourceChain>>> {
+		if self.commitments_stream.is_none() {
+			self.commitments_stream = Some(self.source.commitments().await.map_err(Error::Source)?);
+		}
+
+		let mut conflicting_commitments = Vec::new();
+		while let Some(stream) = self.commitments_stream.as_mut() {
+			match stream.next().now_or_never() {
+				Some(Some(commitment)) => {
+					if let Some(existing) = self.commitments_buf.insert(commitment.clone()) {
+						conflicting_commitments.push((existing, commitment));
+					}
+				},
+				Some(None) => {
+					// the subscription has been closed - we'll resubscribe at next iteration
+					self.commitments_stream = None;
+				},
+				None => break,
+			}
+		}
+
+		for (existing, commitment) in conflicting_commitments {
+			self.report_double_votes(existing, commitment).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Report BEEFY validators that have signed both commitments.
+	async fn report_double_votes(
+		&self,
+		first: BeefySignedCommitment<P>,
+		second: BeefySignedCommitment<P>,
+	) -> Result<(), Error<BlockNumberOf<P::SourceChain>>> {
+		let block_number = first.commitment.block_number;
+		let validator_set = self.source.validator_set(block_number).await.map_err(Error::Source)?;
+		let double_votes = find_double_votes::<_, _, BeefyCommitmentHasher<P::SourceChain>>(
+			validator_set.validators(),
+			&first,
+			&second,
+		);
+		if double_votes.is_empty() {
+			return Ok(())
+		}
+
+		if let Some(ref metrics) = self.metrics {
+			metrics.note_detected_equivocations(double_votes.len() as u64);
+		}
+		for double_vote in double_votes {
+			log::error!(
+				target: "bridge",
+				"Detected BEEFY equivocation at {} block {:?}: validator {:?} from set {} \
+				has signed different commitments: {:?}",
+				P::SourceChain::NAME,
+				block_number,
+				double_vote.offender_id(),
+				double_vote.set_id(),
+				double_vote,
+			);
+
+			// failing to report one double vote shouldn't prevent us from reporting others
+			let offender = double_vote.offender_id().clone();
+			match self.source.report_double_voting(double_vote).await {
+				Ok(Some(_)) => {
+					log::info!(
+						target: "bridge",
+						"Reported BEEFY double vote of validator {:?} to {}",
+						offender,
+						P::SourceChain::NAME,
+					);
+					if let Some(ref metrics) = self.metrics {
+						metrics.note_reported_equivocation();
+					}
+				},
+				Ok(None) => (),
+				Err(error) => {
+					log::error!(
+						target: "bridge",
+						"Failed to report BEEFY double vote of validator {:?} to {}: {:?}",
+						offender,
+						P::SourceChain::NAME,
+						error,
+					);
+					if error.is_connection_error() {
+						return Err(Error::Source(error))
+					}
+				},
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Check that the best commitment, imported by the target chain, matches the source
+	/// chain.
+	///
+	/// Returns false if the target chain has imported commitment that is not signing the
+	/// canonical source chain.
+	async fn check_best_imported_commitment(
+		&mut self,
+		info: &SyncInfo<BlockNumberOf<P::SourceChain>>,
+	) -> Result<bool, Error<BlockNumberOf<P::SourceChain>>> {
+		let best_number_at_target = info.best_number_at_target;
+		if self.last_checked_number_at_target == Some(best_number_at_target) {
+			return Ok(true)
+		}
+
+		let at = self.target.client().best_header_hash().await.map_err(Error::Target)?;
+		let imported = match self
+			.target
+			.imported_commitment(at, best_number_at_target)
+			.await
+			.map_err(Error::Target)?
+		{
+			Some(imported) => imported,
+			// the pallet has been initialized, but no commitments have been imported yet
+			None => {
+				self.last_checked_number_at_target = Some(best_number_at_target);
+				return Ok(true)
+			},
+		};
+
+		// check that the block that is proved by the commitment is canonical
+		let (parent_number, parent_hash) = imported.parent_number_and_hash;
+		let canonical_parent_hash =
+			self.source.block_hash(parent_number).await.map_err(Error::Source)?;
+		let is_using_same_fork = parent_hash == canonical_parent_hash;
+		if let Some(ref metrics) = self.metrics {
+			metrics.update_using_same_fork(is_using_same_fork);
+		}
+
+		// check that the imported MMR root has been signed by the canonical commitment
+		let canonical_commitment = match self.commitments_buf.get(best_number_at_target) {
+			Some(commitment) => Some(commitment.clone()),
+			None =>
+				self.source.commitment_at(best_number_at_target).await.map_err(Error::Source)?,
+		};
+		let canonical_mmr_root = canonical_commitment.and_then(|commitment| {
+			commitment_mmr_root::<P::SourceChain, _>(&commitment.commitment)
+		});
+		let is_mmr_root_canonical =
+			canonical_mmr_root.map(|mmr_root| mmr_root == imported.mmr_root).unwrap_or(true);
+
+		if !is_using_same_fork || !is_mmr_root_canonical {
+			log::error!(
+				target: "bridge",
+				"Detected BEEFY equivocation: {} has imported commitment for {} block {:?} that \
+				is not signing the canonical chain. Imported parent: {:?}, canonical parent hash: \
+				{:?}. Imported MMR root: {:?}, canonical MMR root: {:?}",
+				P::TargetChain::NAME,
+				P::SourceChain::NAME,
+				best_number_at_target,
+				(parent_number, parent_hash),
+				canonical_parent_hash,
+				imported.mmr_root,
+				canonical_mmr_root,
+			);
+			if let Some(ref metrics) = self.metrics {
+				metrics.note_detected_equivocations(1);
+			}
+		}
+
+		self.last_checked_number_at_target = Some(best_number_at_target);
+		Ok(is_using_same_fork && is_mmr_root_canonical)
+	}
+
+	/// Select commitment to submit to the target chain.
+	async fn select_commitment_to_submit(
+		&mut self,
+		info: &SyncInfo<BlockNumberOf<P::SourceChain>>,
+	) -> Result<Option<BeefySignedCommitment<P>>, Error<BlockNumberOf<P::SourceChain>>> {
+		self.commitments_buf
+			.prune(info.best_number_at_target, self.sync_params.recent_commitments_limit);
+
+		// if source has moved to the next validator set, the target won't be able to verify
+		// its commitments until we submit commitment, signed by the current validator set
+		let first_signed_by_newer_set = self
+			.commitments_buf
+			.first_signed_by_newer_set(info.validator_set_id_at_target)
+			.map(|commitment| commitment.commitment.block_number);
+		let is_mandatory = first_signed_by_newer_set.is_some();
+		let required_number = match self.required_number {
+			Some(ref required_number) => Some(*required_number.lock().await),
+			None => None,
+		};
+		if !is_submission_required(info, is_mandatory, required_number) {
+			return Ok(None)
+		}
+
+		let commitment = self
+			.commitments_buf
+			.best_signed_by(info.validator_set_id_at_target, info.best_number_at_target)
+			.cloned();
+		match (commitment, first_signed_by_newer_set) {
+			(Some(commitment), _) => Ok(Some(commitment)),
+			(None, Some(first_signed_by_newer_set)) => {
+				// we have missed commitments of the current validator set (e.g. the relay has
+				// been restarted) => let's look for persisted commitments in source blocks
+				let scan_range = (
+					info.best_number_at_target + One::one(),
+					first_signed_by_newer_set.saturating_sub(One::one()),
+				);
+				log::info!(
+					target: "bridge",
+					"Looking for {} BEEFY commitment, signed by validator set {} in the range {:?}",
+					P::SourceChain::NAME,
+					info.validator_set_id_at_target,
+					scan_range,
+				);
+				self.source
+					.find_commitment_signed_by(
+						info.validator_set_id_at_target,
+						scan_range.0,
+						scan_range.1,
+					)
+					.await
+					.map_err(Error::Source)
+			},
+			(None, None) => Ok(None),
+		}
+	}
+
+	/// Submit commitment to the target chain.
+	async fn submit_commitment(
+		&self,
+		commitment: BeefySignedCommitment<P>,
+	) -> Result<
+		SubstrateTransactionTracker<P::TargetChain, TargetClnt>,
+		Error<BlockNumberOf<P::SourceChain>>,
+	> {
+		let block_number = commitment.commitment.block_number;
+		let validator_set = self.source.validator_set(block_number).await.map_err(Error::Source)?;
+		if validator_set.id() != commitment.commitment.validator_set_id {
+			return Err(Error::Source(SubstrateError::Custom(format!(
+				"BEEFY commitment for {} block {:?} is signed by validator set {}, but validator \
+				set {} is active at this block",
+				P::SourceChain::NAME,
+				block_number,
+				commitment.commitment.validator_set_id,
+				validator_set.id(),
+			))))
+		}
+		let (mmr_leaf, mmr_proof) =
+			self.source.mmr_leaf_and_proof(block_number).await.map_err(Error::Source)?;
+
+		log::debug!(
+			target: "bridge",
+			"Going to submit BEEFY commitment for {} block {:?} (validator set: {}) to {}",
+			P::SourceChain::NAME,
+			block_number,
+			validator_set.id(),
+			P::TargetChain::NAME,
+		);
+
+		let tracker = self
+			.target
+			.submit_commitment(commitment, validator_set, mmr_leaf, mmr_proof)
+			.await
+			.map_err(Error::Target)?;
+		if let Some(ref metrics) = self.metrics {
+			metrics.note_submitted_commitment();
+		}
+
+		Ok(tracker)
+	}
+
+	async fn run_iteration(
+		&mut self,
+	) -> Result<
+		Option<(
+			SubstrateTransactionTracker<P::TargetChain, TargetClnt>,
+			BlockNumberOf<P::SourceChain>,
+		)>,
+		Error<BlockNumberOf<P::SourceChain>>,
+	> {
+		// read best block numbers from source and target nodes
+		let info = self.sync_info().await?;
+		self.update_metrics(&info);
+		self.update_progress(&info);
+
+		// read new commitments from the source node
+		self.read_commitments().await?;
+
+		// do not submit anything if target has imported commitment from the other fork
+		if !self.check_best_imported_commitment(&info).await? {
+			return Ok(None)
+		}
+
+		// if we have already submitted commitment, then we just need to wait for it
+		if Some(info.best_number_at_target) < self.best_submitted_number {
+			return Ok(None)
+		}
+
+		// submit new commitment if we have something new
+		match self.select_commitment_to_submit(&info).await? {
+			Some(commitment) => {
+				let block_number = commitment.commitment.block_number;
+				let tracker = self.submit_commitment(commitment).await?;
+				self.best_submitted_number = Some(block_number);
+				Ok(Some((tracker, block_number)))
+			},
+			None => Ok(None),
+		}
+	}
+
+	/// Run BEEFY sync loop until connection to one of nodes is lost.
+	async fn run_until_connection_lost(
+		mut self,
+		exit_signal: impl Future<Output = ()>,
+	) -> Result<(), FailedClient> {
+		let submission_tx_tracker = Fuse::terminated();
+		let exit_signal = exit_signal.fuse();
+		futures::pin_mut!(exit_signal, submission_tx_tracker);
+
+		loop {
+			// run loop iteration
+			let next_tick = match self.run_iteration().await {
+				Ok(Some((tracker, submitted_number))) => {
+					submission_tx_tracker.set(
+						track_submission::<P, _>(self.target.clone(), tracker, submitted_number)
+							.fuse(),
+					);
+					self.sync_params.tick
+				},
+				Ok(None) => self.sync_params.tick,
+				Err(error) => {
+					log::error!(target: "bridge", "BEEFY sync loop iteration has failed with error: {:?}", error);
+					error.fail_if_connection_error()?;
+					RECONNECT_DELAY
+				},
+			};
+
+			// wait till exit signal, or new source block
+			select! {
+				submission_result = submission_tx_tracker => {
+					if let Err(e) = submission_result {
+						log::error!(
+							target: "bridge",
+							"BEEFY commitment submission tx to {} has failed with error: {:?}.",
+							P::TargetChain::NAME,
+							e,
+						);
+						self.best_submitted_number = None;
+						e.fail_if_connection_error()?;
+					}
+				},
+				_ = async_std::task::sleep(next_tick).fuse() => {},
+				_ = exit_signal => return Ok(()),
+			}
+		}
+	}
+}
+
+/// Wait until the `submit_commitment` transaction is finalized and check that it has updated
+/// the best imported commitment.
+async fn track_submission<P: SubstrateBeefySyncPipeline, TargetClnt: Client<P::TargetChain>>(
+	target: SubstrateBeefyTarget<P, TargetClnt>,
+	tracker: SubstrateTransactionTracker<P::TargetChain, TargetClnt>,
+	submitted_number: BlockNumberOf<P::SourceChain>,
+) -> Result<(), Error<BlockNumberOf<P::SourceChain>>> {
+	match tracker.wait().await {
+		TrackedTransactionStatus::Finalized(_) => {
+			// The transaction has been finalized, but it may have been finalized in the
+			// "failed" state. So let's check if the block number was actually updated.
+			let at = target.client().best_header_hash().await.map_err(Error::Target)?;
+			let best_number_at_target =
+				target.best_imported_block_number(at).await.map_err(Error::Target)?;
+			if submitted_number > best_number_at_target {
+				return Err(Error::CommitmentSubmissionTxFailed {
+					submitted_number,
+					best_number_at_target,
+				})
+			}
+			Ok(())
+		},
+		TrackedTransactionStatus::Lost => Err(Error::CommitmentSubmissionTxLost),
+	}
+}
+
+/// Run BEEFY finality synchronization loop.
+pub async fn run<P, SourceClnt, TargetClnt>(
+	source: SubstrateBeefySource<P, SourceClnt>,
+	target: SubstrateBeefyTarget<P, TargetClnt>,
+	sync_params: BeefySyncParams,
+	required_number: Option<RequiredHeaderNumberRef<P::SourceChain>>,
+	metrics_params: MetricsParams,
+	exit_signal: impl Future<Output = ()> + 'static + Send,
+) -> Result<(), relay_utils::Error>
+where
+	P: SubstrateBeefySyncPipeline,
+	SourceClnt: Client<P::SourceChain>,
+	TargetClnt: Client<P::TargetChain>,
+	AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+{
+	let exit_signal = exit_signal.shared();
+	relay_utils::relay_loop(source, target)
+		.with_metrics(metrics_params)
+		.loop_metric(BeefySyncLoopMetrics::new(Some(&metrics_prefix::<P>()))?)?
+		.expose()
+		.await?
+		.run(metrics_prefix::<P>(), move |source, target, metrics| {
+			BeefySyncLoop::new(
+				source,
+				target,
+				sync_params.clone(),
+				required_number.clone(),
+				metrics,
+			)
+			.run_until_connection_lost(exit_signal.clone())
+		})
+		.await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_beefy::{
+		BeefyPayload, Commitment, EcdsaValidatorId, EcdsaValidatorSignature, MMR_ROOT_PAYLOAD_ID,
+	};
+	use codec::Encode;
+	use sp_consensus_beefy::test_utils::Keyring;
+	use sp_runtime::traits::Keccak256;
+
+	type TestKeyring = Keyring<EcdsaValidatorId>;
+
+	fn validators() -> Vec<EcdsaValidatorId> {
+		vec![TestKeyring::Alice.public(), TestKeyring::Bob.public(), TestKeyring::Charlie.public()]
+	}
+
+	/// Returns commitment, signed by Alice, Bob and Charlie.
+	fn commitment(
+		block_number: u32,
+		validator_set_id: u64,
+		mmr_root: u8,
+	) -> SignedCommitment<u32, EcdsaValidatorSignature> {
+		let commitment = Commitment {
+			payload: BeefyPayload::from_single_entry(MMR_ROOT_PAYLOAD_ID, [mmr_root; 32].encode()),
+			block_number,
+			validator_set_id,
+		};
+		let encoded_commitment = commitment.encode();
+		SignedCommitment {
+			commitment,
+			signatures: [TestKeyring::Alice, TestKeyring::Bob, TestKeyring::Charlie]
+				.into_iter()
+				.map(|signer| Some(signer.sign(&encoded_commitment)))
+				.collect(),
+		}
+	}
+
+	fn sync_info(best_number_at_target: u32, validator_set_id_at_target: u64) -> SyncInfo<u32> {
+		SyncInfo { best_number_at_source: 100, best_number_at_target, validator_set_id_at_target }
+	}
+
+	#[test]
+	fn commitments_buf_detects_conflicting_commitments() {
+		let mut buf = CommitmentsBuf::new();
+		assert_eq!(buf.insert(commitment(10, 1, 1)), None);
+		// the same commitment is simply ignored
+		assert_eq!(buf.insert(commitment(10, 1, 1)), None);
+		// different commitment for the same block and validator set is conflicting
+		assert_eq!(buf.insert(commitment(10, 1, 2)), Some(commitment(10, 1, 1)));
+		assert_eq!(buf.get(10), Some(&commitment(10, 1, 1)));
+	}
+
+	#[test]
+	fn conflicting_commitments_are_turned_into_double_voting_proofs() {
+		let mut buf = CommitmentsBuf::new();
+		assert_eq!(buf.insert(commitment(10, 1, 1)), None);
+		let existing = buf.insert(commitment(10, 1, 2)).unwrap();
+
+		// all validators have signed both commitments
+		let proofs =
+			find_double_votes::<_, _, Keccak256>(&validators(), &existing, &commitment(10, 1, 2));
+		assert_eq!(
+			proofs.iter().map(|proof| proof.offender_id().clone()).collect::<Vec<_>>(),
+			validators(),
+		);
+		assert!(proofs.iter().all(|proof| {
+			proof.set_id() == 1 &&
+				proof.round_number() == &10 &&
+				sp_consensus_beefy::check_double_voting_proof::<_, _, Keccak256>(proof)
+		}));
+
+		// commitments of different validator sets are not conflicting
+		assert_eq!(buf.insert(commitment(20, 1, 1)), None);
+		assert_eq!(buf.insert(commitment(20, 2, 2)), None);
+	}
+
+	#[test]
+	fn commitments_buf_selects_best_commitment_of_validator_set() {
+		let mut buf = CommitmentsBuf::new();
+		buf.insert(commitment(10, 1, 1));
+		buf.insert(commitment(20, 1, 2));
+		buf.insert(commitment(30, 2, 3));
+		buf.insert(commitment(40, 2, 4));
+
+		assert_eq!(buf.best_signed_by(1, 5), Some(&commitment(20, 1, 2)));
+		assert_eq!(buf.best_signed_by(1, 20), None);
+		assert_eq!(buf.best_signed_by(2, 20), Some(&commitment(40, 2, 4)));
+		assert_eq!(buf.first_signed_by_newer_set(1), Some(&commitment(30, 2, 3)));
+		assert_eq!(buf.first_signed_by_newer_set(2), None);
+
+		buf.prune(25, 1);
+		assert_eq!(buf.commitments.keys().cloned().collect::<Vec<_>>(), vec![40]);
+	}
+
+	#[test]
+	fn submission_is_required_in_on_demand_mode_only_when_needed() {
+		// without on-demand mode we submit every commitment
+		assert!(is_submission_required(&sync_info(10, 1), false, None));
+		// handover commitments are always submitted
+		assert!(is_submission_required(&sync_info(10, 1), true, Some(5)));
+		// commitment for block 10 only proves block 9, so block 10 is still required
+		assert!(is_submission_required(&sync_info(10, 1), false, Some(10)));
+		assert!(!is_submission_required(&sync_info(10, 1), false, Some(9)));
+	}
+}
@@ -0,0 +1,222 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Substrate client as Substrate BEEFY commitments target.
+
+use crate::{
+	beefy::{
+		BeefyMmrLeaf, BeefyMmrProof, BeefySignedCommitment, BeefyValidatorSet,
+		SubmitBeefyCommitmentCallBuilder, SubstrateBeefySyncPipeline,
+	},
+	TransactionParams,
+};
+
+use async_trait::async_trait;
+use bp_beefy::{BeefyAuthoritySetInfoOf, ImportedCommitment, MmrHashOf};
+use bp_runtime::{BasicOperatingMode, OperatingMode};
+use codec::Encode;
+use frame_support::Blake2_128Concat;
+use pallet_bridge_beefy::ImportedCommitmentsInfoData;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, ChainWithBeefy, Client, Error, HashOf,
+	TransactionEra, TransactionTracker, UnsignedTransaction,
+};
+use relay_utils::relay_loop::Client as RelayClient;
+use sp_core::{storage::StorageKey, Pair};
+
+/// Name of the `PalletOperatingMode` storage value.
+const PALLET_OPERATING_MODE_VALUE_NAME: &str = "PalletOperatingMode";
+/// Name of the `ImportedCommitmentsInfo` storage value.
+const IMPORTED_COMMITMENTS_INFO_VALUE_NAME: &str = "ImportedCommitmentsInfo";
+/// Name of the `ImportedCommitments` storage map.
+const IMPORTED_COMMITMENTS_MAP_NAME: &str = "ImportedCommitments";
+/// Name of the `CurrentAuthoritySetInfo` storage value.
+const CURRENT_AUTHORITY_SET_INFO_VALUE_NAME: &str = "CurrentAuthoritySetInfo";
+
+/// Commitment data, stored by the bridge BEEFY pallet at the target chain.
+pub type ImportedCommitmentOf<C> = ImportedCommitment<BlockNumberOf<C>, HashOf<C>, MmrHashOf<C>>;
+
+/// Substrate client as Substrate BEEFY commitments target.
+pub struct SubstrateBeefyTarget<P: SubstrateBeefySyncPipeline, TargetClnt> {
+	client: TargetClnt,
+	transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+}
+
+impl<P: SubstrateBeefySyncPipeline, TargetClnt: Client<P::TargetChain>>
+	SubstrateBeefyTarget<P, TargetClnt>
+{
+	/// Create new Substrate BEEFY commitments target.
+	pub fn new(
+		client: TargetClnt,
+		transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+	) -> Self {
+		SubstrateBeefyTarget { client, transaction_params }
+	}
+
+	/// Returns reference to the underlying RPC client.
+	pub fn client(&self) -> &TargetClnt {
+		&self.client
+	}
+
+	/// Ensure that the bridge pallet at target chain is active.
+	pub async fn ensure_pallet_active(&self) -> Result<(), Error> {
+		let at = self.client.best_header_hash().await?;
+		let is_halted = self
+			.client
+			.storage_value::<BasicOperatingMode>(
+				at,
+				pallet_storage_value_key::<P>(PALLET_OPERATING_MODE_VALUE_NAME),
+			)
+			.await?
+			.map(|operating_mode| operating_mode.is_halted())
+			.unwrap_or(false);
+		if is_halted {
+			return Err(Error::BridgePalletIsHalted)
+		}
+
+		let is_initialized = self
+			.client
+			.raw_storage_value(
+				at,
+				pallet_storage_value_key::<P>(IMPORTED_COMMITMENTS_INFO_VALUE_NAME),
+			)
+			.await?
+			.is_some();
+		if !is_initialized {
+			return Err(Error::BridgePalletIsNotInitialized)
+		}
+
+		Ok(())
+	}
+
+	/// Returns number of the best source block, for which the commitment has been imported
+	/// by the bridge pallet.
+	///
+	/// Keep in mind that the best block that is proved by this commitment is its parent.
+	pub async fn best_imported_block_number(
+		&self,
+		at: HashOf<P::TargetChain>,
+	) -> Result<BlockNumberOf<P::SourceChain>, Error> {
+		self.client
+			.storage_value::<ImportedCommitmentsInfoData<BlockNumberOf<P::SourceChain>>>(
+				at,
+				pallet_storage_value_key::<P>(IMPORTED_COMMITMENTS_INFO_VALUE_NAME),
+			)
+			.await?
+			.map(|info| info.best_block_number)
+			.ok_or(Error::BridgePalletIsNotInitialized)
+	}
+
+	/// Returns BEEFY authority set, that is expected to sign the next imported commitment.
+	pub async fn current_authority_set_info(
+		&self,
+		at: HashOf<P::TargetChain>,
+	) -> Result<BeefyAuthoritySetInfoOf<P::SourceChain>, Error> {
+		current_authority_set_info::<P, _>(&self.client, at).await
+	}
+
+	/// Returns data of the imported commitment for given source block.
+	pub async fn imported_commitment(
+		&self,
+		at: HashOf<P::TargetChain>,
+		block_number: BlockNumberOf<P::SourceChain>,
+	) -> Result<Option<ImportedCommitmentOf<P::SourceChain>>, Error> {
+		self.client
+			.storage_value::<ImportedCommitmentOf<P::SourceChain>>(
+				at,
+				bp_runtime::storage_map_final_key::<Blake2_128Concat>(
+					P::SourceChain::WITH_CHAIN_BEEFY_PALLET_NAME,
+					IMPORTED_COMMITMENTS_MAP_NAME,
+					&block_number.encode(),
+				),
+			)
+			.await
+	}
+
+	/// Submit signed BEEFY commitment to the target chain.
+	pub async fn submit_commitment(
+		&self,
+		commitment: BeefySignedCommitment<P>,
+		validator_set: BeefyValidatorSet<P>,
+		mmr_leaf: BeefyMmrLeaf<P>,
+		mmr_proof: BeefyMmrProof<P>,
+	) -> Result<TransactionTracker<P::TargetChain, TargetClnt>, Error>
+	where
+		AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+	{
+		let mortality = self.transaction_params.mortality;
+		let call = P::SubmitBeefyCommitmentCallBuilder::build_submit_commitment_call(
+			commitment,
+			validator_set,
+			mmr_leaf,
+			mmr_proof,
+		);
+		self.client
+			.submit_and_watch_signed_extrinsic(
+				&self.transaction_params.signer,
+				move |best_block_id, transaction_nonce| {
+					Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
+						.era(TransactionEra::new(best_block_id, mortality)))
+				},
+			)
+			.await
+	}
+}
+
+impl<P: SubstrateBeefySyncPipeline, TargetClnt: Clone> Clone
+	for SubstrateBeefyTarget<P, TargetClnt>
+{
+	fn clone(&self) -> Self {
+		SubstrateBeefyTarget {
+			client: self.client.clone(),
+			transaction_params: self.transaction_params.clone(),
+		}
+	}
+}
+
+#[async_trait]
+impl<P: SubstrateBeefySyncPipeline, TargetClnt: Client<P::TargetChain>> RelayClient
+	for SubstrateBeefyTarget<P, TargetClnt>
+{
+	type Error = Error;
+
+	async fn reconnect(&mut self) -> Result<(), Error> {
+		self.client.reconnect().await
+	}
+}
+
+/// Returns BEEFY authority set, that is expected to sign the next commitment, imported by the
+/// bridge BEEFY pallet at the target chain.
+pub async fn current_authority_set_info<
+	P: SubstrateBeefySyncPipeline,
+	TargetClnt: Client<P::TargetChain>,
+>(
+	client: &TargetClnt,
+	at: HashOf<P::TargetChain>,
+) -> Result<BeefyAuthoritySetInfoOf<P::SourceChain>, Error> {
+	Ok(client
+		.storage_value::<BeefyAuthoritySetInfoOf<P::SourceChain>>(
+			at,
+			pallet_storage_value_key::<P>(CURRENT_AUTHORITY_SET_INFO_VALUE_NAME),
+		)
+		.await?
+		.unwrap_or_default())
+}
+
+/// Returns storage key of the value, stored by the bridge BEEFY pallet at the target chain.
+fn pallet_storage_value_key<P: SubstrateBeefySyncPipeline>(value_name: &str) -> StorageKey {
+	bp_runtime::storage_value_key(P::SourceChain::WITH_CHAIN_BEEFY_PALLET_NAME, value_name)
+}
@@ -17,6 +17,7 @@
 //! Basic traits for exposing bridges in the CLI.
 
 use crate::{
+	beefy::SubstrateBeefySyncPipeline,
 	equivocation::SubstrateEquivocationDetectionPipeline,
 	finality::SubstrateFinalitySyncPipeline,
 	messages::{MessagesRelayLimits, SubstrateMessageLane},
@@ -24,7 +25,7 @@ use crate::{
 };
 use bp_parachains::{RelayBlockHash, RelayBlockHasher, RelayBlockNumber};
 use relay_substrate_client::{
-	Chain, ChainWithRuntimeVersion, ChainWithTransactions, Parachain, RelayChain,
+	Chain, ChainWithBeefy, ChainWithRuntimeVersion, ChainWithTransactions, Parachain, RelayChain,
 };
 
 /// Minimal bridge representation that can be used from the CLI.
@@ -46,6 +47,27 @@ pub trait RelayToRelayHeadersCliBridge: CliBridgeBase {
 	>;
 }
 
+/// Convenience trait that adds bounds to `CliBridgeBase`.
+pub trait RelayToRelayBeefyCliBridgeBase: CliBridgeBase {
+	/// The source chain with extra bounds.
+	type BoundedSource: ChainWithBeefy + ChainWithTransactions;
+}
+
+impl<T> RelayToRelayBeefyCliBridgeBase for T
+where
+	T: CliBridgeBase,
+	T::Source: ChainWithBeefy + ChainWithTransactions,
+{
+	type BoundedSource = T::Source;
+}
+
+/// Bridge representation that can be used from the CLI for relaying BEEFY commitments
+/// from a relay chain to a relay chain.
+pub trait RelayToRelayBeefyCliBridge: RelayToRelayBeefyCliBridgeBase {
+	/// BEEFY commitments synchronization pipeline.
+	type Beefy: SubstrateBeefySyncPipeline<SourceChain = Self::Source, TargetChain = Self::Target>;
+}
+
 /// Convenience trait that adds bounds to `CliBridgeBase`.
 pub trait RelayToRelayEquivocationDetectionCliBridgeBase: CliBridgeBase {
 	/// The source chain with extra bounds.
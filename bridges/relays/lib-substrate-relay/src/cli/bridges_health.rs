@@ -0,0 +1,111 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Primitives for exposing the bridges health dashboard in the CLI.
+
+use crate::cli::{bridge::*, chain_schema::*};
+
+use async_trait::async_trait;
+use bp_xcm_bridge_hub::{BridgeHealth, BridgeId};
+use bp_xcm_bridge_hub_router::BridgeState as RouterBridgeState;
+use relay_substrate_client::{
+	BlockNumberOf, ChainWithRuntimeVersion, ChainWithXcmBridgeHub, Client,
+};
+use std::fmt::Debug;
+use structopt::StructOpt;
+
+/// Bridges health dashboard params.
+#[derive(StructOpt)]
+pub struct BridgesHealthParams {
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	/// Also render the state of the `pallet-xcm-bridge-hub-router` at the sibling chain,
+	/// that is sending messages over the source bridge hub.
+	#[structopt(long)]
+	with_sibling: bool,
+	#[structopt(flatten)]
+	sibling: SiblingConnectionParams,
+}
+
+/// Trait used for rendering health of bridges, opened between source and target chains.
+#[async_trait]
+pub trait BridgesHealthRenderer: MessagesCliBridge
+where
+	Self::Target: ChainWithXcmBridgeHub,
+{
+	/// Sibling chain of the source bridge hub that sends messages over the bridge.
+	type SourceSibling: ChainWithRuntimeVersion;
+
+	/// Read and render health of all bridges with the target chain.
+	async fn render(data: BridgesHealthParams) -> anyhow::Result<()> {
+		let source_client = data.source.into_client::<Self::Source>().await?;
+		let at = source_client.best_header_hash().await?;
+		let bridges: Vec<(
+			BridgeId,
+			BridgeHealth<BlockNumberOf<Self::Source>, MessagesLaneIdOf<Self>>,
+		)> = source_client
+			.state_call(at, Self::Target::TO_CHAIN_XCM_BRIDGES_METHOD.into(), ())
+			.await?;
+
+		println!("Bridges with {} at {} block {:?}:", Self::Target::NAME, Self::Source::NAME, at);
+		if bridges.is_empty() {
+			println!("  no opened bridges");
+		}
+		for (bridge_id, health) in bridges {
+			println!("{}", format_bridge_health(bridge_id, &health));
+		}
+
+		if data.with_sibling {
+			let sibling_client = data.sibling.into_client::<Self::SourceSibling>().await?;
+			let at = sibling_client.best_header_hash().await?;
+			let state: RouterBridgeState = sibling_client
+				.state_call(at, Self::Target::TO_CHAIN_XCM_ROUTER_BRIDGE_STATE_METHOD.into(), ())
+				.await?;
+			println!(
+				"Router at {} block {:?}: delivery fee factor {}, congested: {}",
+				Self::SourceSibling::NAME,
+				at,
+				state.delivery_fee_factor,
+				state.is_congested,
+			);
+		}
+
+		Ok(())
+	}
+}
+
+/// Format health of the single bridge.
+fn format_bridge_health<BlockNumber: Debug, LaneId: Debug>(
+	bridge_id: BridgeId,
+	health: &BridgeHealth<BlockNumber, LaneId>,
+) -> String {
+	let oldest_undelivered =
+		match (health.oldest_undelivered_nonce, health.oldest_undelivered_message_age.as_ref()) {
+			(Some(nonce), Some(age)) => format!("{nonce} (enqueued {age:?} blocks ago)"),
+			(Some(nonce), None) => format!("{nonce} (age unknown)"),
+			(None, _) => "none".into(),
+		};
+	format!(
+		"  bridge {bridge_id:?} (lane {:?}): state {:?}, congested: {}, outbound queue: {} messages, \
+		oldest undelivered: {}, unrewarded relayer entries: {}",
+		health.lane_id,
+		health.state,
+		health.is_congested,
+		health.outbound_queue_depth,
+		oldest_undelivered,
+		health.unrewarded_relayer_entries,
+	)
+}
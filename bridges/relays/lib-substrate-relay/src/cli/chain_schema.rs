@@ -259,3 +259,5 @@ macro_rules! declare_chain_cli_schema {
 
 declare_chain_cli_schema!(Source, source);
 declare_chain_cli_schema!(Target, target);
+declare_chain_runtime_version_params_cli_schema!(Sibling, sibling);
+declare_chain_connection_params_cli_schema!(Sibling, sibling);
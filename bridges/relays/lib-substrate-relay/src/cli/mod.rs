@@ -22,13 +22,16 @@ use std::str::FromStr;
 use structopt::StructOpt;
 
 pub mod bridge;
+pub mod bridges_health;
 pub mod chain_schema;
 pub mod detect_equivocations;
 pub mod init_bridge;
+pub mod relay_beefy;
 pub mod relay_headers;
 pub mod relay_headers_and_messages;
 pub mod relay_messages;
 pub mod relay_parachains;
+pub mod replay_headers;
 
 /// The target that will be used when publishing logs related to this pallet.
 pub const LOG_TARGET: &str = "bridge";
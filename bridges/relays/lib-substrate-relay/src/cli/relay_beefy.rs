@@ -0,0 +1,84 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Primitives for exposing the BEEFY commitments relaying functionality in the CLI.
+
+use crate::{
+	beefy::{self, SubstrateBeefySyncPipeline},
+	cli::{bridge::*, chain_schema::*, PrometheusParams},
+};
+
+use async_trait::async_trait;
+use relay_substrate_client::{AccountIdOf, AccountKeyPairOf, ChainWithTransactions, Client};
+use relay_utils::metrics::{GlobalMetrics, StandaloneMetric};
+use sp_core::Pair;
+use structopt::StructOpt;
+
+/// BEEFY commitments relaying params.
+#[derive(StructOpt)]
+pub struct RelayBeefyCommitmentsParams {
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	// if a source signer is given, BEEFY validators signing conflicting commitments are reported
+	// to the source chain
+	#[structopt(flatten)]
+	source_sign: SourceSigningParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	#[structopt(flatten)]
+	target_sign: TargetSigningParams,
+	#[structopt(flatten)]
+	prometheus_params: PrometheusParams,
+}
+
+/// Trait used for relaying BEEFY commitments between 2 chains.
+#[async_trait]
+pub trait BeefyCommitmentsRelayer: RelayToRelayBeefyCliBridge
+where
+	Self::Source: ChainWithTransactions,
+	AccountIdOf<Self::Source>: From<<AccountKeyPairOf<Self::Source> as Pair>::Public>,
+	AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
+{
+	/// Relay BEEFY commitments.
+	async fn relay_beefy_commitments(data: RelayBeefyCommitmentsParams) -> anyhow::Result<()> {
+		let source_client = data.source.into_client::<Self::Source>().await?;
+		let target_client = data.target.into_client::<Self::Target>().await?;
+		let source_sign = &data.source_sign;
+		let source_transactions_params =
+			if source_sign.source_signer.is_some() || source_sign.source_signer_file.is_some() {
+				Some(source_sign.transaction_params::<Self::Source>()?)
+			} else {
+				None
+			};
+
+		let metrics_params: relay_utils::metrics::MetricsParams =
+			data.prometheus_params.into_metrics_params()?;
+		GlobalMetrics::new()?.register_and_spawn(&metrics_params.registry)?;
+
+		Self::Beefy::start_relay_guards(&target_client, target_client.can_start_version_guard())
+			.await?;
+
+		beefy::run::<Self::Beefy>(
+			source_client,
+			target_client,
+			None,
+			source_transactions_params,
+			data.target_sign.transaction_params::<Self::Target>()?,
+			metrics_params,
+		)
+		.await
+	}
+}
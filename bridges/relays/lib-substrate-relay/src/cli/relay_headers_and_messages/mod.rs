@@ -178,6 +178,7 @@ where
 			target_to_source_headers_relay: Some(target_to_source_headers_relay),
 			lane_id,
 			limits: maybe_limits,
+			delivery_coordination: None,
 			metrics_params: self.metrics_params.clone().disable(),
 		}
 	}
@@ -18,7 +18,7 @@
 
 use crate::{
 	cli::{bridge::*, chain_schema::*, HexLaneId, PrometheusParams},
-	messages::MessagesRelayParams,
+	messages::{DeliveryCoordinationParams, MessagesRelayParams},
 	TransactionParams,
 };
 
@@ -49,6 +49,14 @@ pub struct RelayMessagesParams {
 	target: TargetConnectionParams,
 	#[structopt(flatten)]
 	target_sign: TargetSigningParams,
+	/// Number of target chain blocks in the single delivery slot. If specified, the relay
+	/// takes turns with other relayers, registered at the target chain, to deliver messages.
+	#[structopt(long)]
+	delivery_slot_length: Option<u32>,
+	/// Name of the source chain runtime parameter with the reward, paid for every delivered
+	/// message. It is only used when `--delivery-slot-length` is specified.
+	#[structopt(long, default_value = "DeliveryRewardInBalance")]
+	delivery_reward_parameter: String,
 	#[structopt(flatten)]
 	prometheus_params: PrometheusParams,
 }
@@ -138,6 +146,12 @@ where
 			target_to_source_headers_relay: None,
 			lane_id,
 			limits: Self::maybe_messages_limits(),
+			delivery_coordination: data.delivery_slot_length.map(|slot_length| {
+				DeliveryCoordinationParams {
+					slot_length,
+					delivery_reward_parameter: data.delivery_reward_parameter,
+				}
+			}),
 			metrics_params: data.prometheus_params.into_metrics_params()?,
 		})
 		.await
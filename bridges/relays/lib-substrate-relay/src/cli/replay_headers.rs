@@ -0,0 +1,102 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Primitives for exposing the offline GRANDPA finality proofs replay in the CLI.
+
+use crate::cli::bridge::CliBridgeBase;
+
+use bp_header_chain::{
+	replay::{replay_finality_proofs, ReplayParams, ReplayedFinalityProof},
+	InitializationData,
+};
+use codec::Decode;
+use relay_substrate_client::{ChainWithGrandpa, HeaderOf};
+use sp_runtime::traits::Header as HeaderT;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Offline finality proofs replay params.
+#[derive(StructOpt)]
+pub struct ReplayHeadersParams {
+	/// Path to the file with hex-encoded SCALE-encoded tuple of the bridge initialization data
+	/// and the vector of finality proofs (`ReplayedFinalityProof`) to replay.
+	#[structopt(long)]
+	input: PathBuf,
+	/// The `FreeHeadersInterval` of the bridge GRANDPA pallet at the target chain. If not
+	/// specified, only mandatory headers may be imported for free.
+	#[structopt(long)]
+	free_headers_interval: Option<u32>,
+	/// The `MaxFreeHeadersPerBlock` of the bridge GRANDPA pallet at the target chain. If
+	/// specified, the proofs are replayed as if they were submitted in a single block. Otherwise,
+	/// they are replayed as if they were validated by the transaction pool.
+	#[structopt(long)]
+	max_free_headers_per_block: Option<u32>,
+}
+
+/// Trait used for replaying finality proofs of the source chain without any runtime.
+pub trait HeadersReplayer: CliBridgeBase
+where
+	Self::Source: ChainWithGrandpa,
+{
+	/// Replay finality proofs from the input file and print the report.
+	fn replay(data: ReplayHeadersParams) -> anyhow::Result<()> {
+		let input = std::fs::read_to_string(&data.input)?;
+		let input = hex::decode(input.trim().trim_start_matches("0x"))?;
+		let (init_data, proofs): (
+			InitializationData<HeaderOf<Self::Source>>,
+			Vec<ReplayedFinalityProof<HeaderOf<Self::Source>>>,
+		) = Decode::decode(&mut &input[..])?;
+
+		println!(
+			"Replaying {} {} finality proofs, starting from header {:?} and authority set {}",
+			proofs.len(),
+			Self::Source::NAME,
+			init_data.header.number(),
+			init_data.set_id,
+		);
+		let params = ReplayParams {
+			free_headers_interval: data.free_headers_interval,
+			free_headers_remaining: data.max_free_headers_per_block,
+		};
+		let report = replay_finality_proofs::<Self::Source>(init_data.into(), params, &proofs);
+		for (proof, outcome) in proofs.iter().zip(report.outcomes.iter()) {
+			println!(
+				"  header {:?} ({:?}): accepted, improved by {:?}, weight limit exceeded: {}, \
+				extra size: {}, free: {}, new authority set: {:?}",
+				proof.header.number(),
+				proof.header.hash(),
+				outcome.improved_by,
+				outcome.is_weight_limit_exceeded,
+				outcome.extra_size,
+				outcome.is_free,
+				outcome.new_authority_set.as_ref().map(|set| set.set_id),
+			);
+		}
+		if let Some((index, ref error)) = report.failure {
+			let header = &proofs[index].header;
+			println!("  header {:?} ({:?}): rejected: {:?}", header.number(), header.hash(), error);
+		}
+		println!(
+			"Resulting bridge state: best finalized header {:?}, authority set {}",
+			report.state.best_finalized, report.state.authority_set.set_id,
+		);
+
+		match report.failure {
+			Some((index, _)) => Err(anyhow::format_err!("Finality proof #{index} is rejected")),
+			None => Ok(()),
+		}
+	}
+}
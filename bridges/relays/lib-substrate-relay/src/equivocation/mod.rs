@@ -129,6 +129,12 @@ impl<P: SubstrateEquivocationDetectionPipeline> EquivocationDetectionPipeline
 }
 
 /// Different ways of building `report_equivocation` calls.
+///
+/// Reports are submitted as signed `report_equivocation` transactions, not as
+/// `report_equivocation_unsigned`. The `ValidateUnsigned` implementation of `pallet-grandpa`
+/// only accepts unsigned reports with the `Local` or `InBlock` transaction source, i.e. reports
+/// that are produced by the node of the source chain itself. Unsigned reports, sent by the relayer
+/// over RPC, have the `External` source and are rejected by the transaction pool.
 pub trait ReportEquivocationCallBuilder<P: SubstrateEquivocationDetectionPipeline> {
 	/// Build a `report_equivocation` call to be executed on the source chain.
 	fn build_report_equivocation_call(
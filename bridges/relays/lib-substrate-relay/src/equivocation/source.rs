@@ -90,6 +90,14 @@ impl<P: SubstrateEquivocationDetectionPipeline, SourceClnt: Client<P::SourceChai
 {
 	type TransactionTracker = TransactionTracker<P::SourceChain, SourceClnt>;
 
+	async fn is_equivocation_reported(
+		&self,
+		at: HashOf<P::SourceChain>,
+		equivocation: &EquivocationProofOf<P>,
+	) -> Result<bool, Self::Error> {
+		P::FinalityEngine::is_equivocation_reported(&self.client, at, equivocation).await
+	}
+
 	async fn report_equivocation(
 		&self,
 		at: HashOf<P::SourceChain>,
@@ -26,19 +26,68 @@ use bp_header_chain::{
 	AuthoritySet, ConsensusLogReader, FinalityProof, FindEquivocations, GrandpaConsensusLogReader,
 	HeaderFinalityInfo, HeaderGrandpaInfo, StoredHeaderGrandpaInfo, SubmitFinalityProofCallExtras,
 };
-use bp_runtime::{BasicOperatingMode, HeaderIdProvider, OperatingMode};
+use bp_runtime::{
+	BasicOperatingMode, HeaderIdProvider, OperatingMode, StorageDoubleMapKeyProvider,
+	StorageMapKeyProvider,
+};
 use codec::{Decode, Encode};
+use equivocation_detector::EquivocationOffence;
+use frame_support::Twox64Concat;
 use futures::stream::StreamExt;
 use num_traits::{One, Zero};
 use relay_substrate_client::{
-	BlockNumberOf, Chain, ChainWithGrandpa, Client, Error as SubstrateError, HashOf, HeaderOf,
-	Subscription,
+	AccountIdOf, BlockNumberOf, Chain, ChainWithGrandpa, Client, Error as SubstrateError, HashOf,
+	HeaderOf, Subscription,
 };
 use sp_consensus_grandpa::{AuthorityList as GrandpaAuthoritiesSet, GRANDPA_ENGINE_ID};
-use sp_core::{storage::StorageKey, Bytes};
+use sp_core::{
+	crypto::{ByteArray, KeyTypeId},
+	storage::StorageKey,
+	Bytes,
+};
 use sp_runtime::{scale_info::TypeInfo, traits::Header, ConsensusEngineId};
 use std::{fmt::Debug, marker::PhantomData};
 
+/// Kind of the GRANDPA equivocation offence, used by the offences pallet.
+const GRANDPA_EQUIVOCATION_OFFENCE_KIND: [u8; 16] = *b"grandpa:equivoca";
+
+/// `pallet_offences::ConcurrentReportsIndex` storage map.
+struct OffencesConcurrentReportsIndex<C>(PhantomData<C>);
+
+impl<C: Chain> StorageDoubleMapKeyProvider for OffencesConcurrentReportsIndex<C> {
+	const MAP_NAME: &'static str = "ConcurrentReportsIndex";
+
+	type Hasher1 = Twox64Concat;
+	type Key1 = [u8; 16];
+	type Hasher2 = Twox64Concat;
+	type Key2 = Vec<u8>;
+	type Value = Vec<HashOf<C>>;
+}
+
+/// `pallet_offences::Reports` storage map.
+///
+/// We only need the offender account, so the rest of `OffenceDetails` is not decoded.
+struct OffencesReports<C>(PhantomData<C>);
+
+impl<C: Chain> StorageMapKeyProvider for OffencesReports<C> {
+	const MAP_NAME: &'static str = "Reports";
+
+	type Hasher = Twox64Concat;
+	type Key = HashOf<C>;
+	type Value = AccountIdOf<C>;
+}
+
+/// `pallet_session::KeyOwner` storage map.
+struct SessionKeyOwner<C>(PhantomData<C>);
+
+impl<C: Chain> StorageMapKeyProvider for SessionKeyOwner<C> {
+	const MAP_NAME: &'static str = "KeyOwner";
+
+	type Hasher = Twox64Concat;
+	type Key = (KeyTypeId, Vec<u8>);
+	type Value = AccountIdOf<C>;
+}
+
 /// Finality engine, used by the Substrate chain.
 #[async_trait]
 pub trait Engine<C: Chain>: Send {
@@ -51,7 +100,7 @@ pub trait Engine<C: Chain>: Send {
 	/// The context needed for verifying finality proofs.
 	type FinalityVerificationContext: Debug + Send;
 	/// The type of the equivocation proof used by the consensus engine.
-	type EquivocationProof: Clone + Debug + Send + Sync;
+	type EquivocationProof: Clone + Debug + Send + Sync + EquivocationOffence;
 	/// The equivocations finder.
 	type EquivocationsFinder: FindEquivocations<
 		Self::FinalityProof,
@@ -148,6 +197,14 @@ pub trait Engine<C: Chain>: Send {
 		at: C::Hash,
 		equivocation: &Self::EquivocationProof,
 	) -> Result<Self::KeyOwnerProof, SubstrateError>;
+
+	/// Returns `true` if the offence, proved by the provided equivocation, is already known
+	/// to the source chain at the given block.
+	async fn is_equivocation_reported(
+		source_client: &impl Client<C>,
+		at: C::Hash,
+		equivocation: &Self::EquivocationProof,
+	) -> Result<bool, SubstrateError>;
 }
 
 /// GRANDPA finality engine.
@@ -437,4 +494,44 @@ impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 
 		Ok(key_owner_proof)
 	}
+
+	async fn is_equivocation_reported(
+		source_client: &impl Client<C>,
+		at: C::Hash,
+		equivocation: &Self::EquivocationProof,
+	) -> Result<bool, SubstrateError> {
+		let time_slot = (equivocation.set_id(), equivocation.round()).encode();
+		let report_ids = source_client
+			.storage_double_map_value::<OffencesConcurrentReportsIndex<C>>(
+				at,
+				C::OFFENCES_PALLET_NAME,
+				&GRANDPA_EQUIVOCATION_OFFENCE_KIND,
+				&time_slot,
+			)
+			.await?
+			.unwrap_or_default();
+		if report_ids.is_empty() {
+			return Ok(false)
+		}
+
+		let offender = source_client
+			.storage_map_value::<SessionKeyOwner<C>>(
+				at,
+				C::SESSION_PALLET_NAME,
+				&(sp_consensus_grandpa::KEY_TYPE, equivocation.offender().to_raw_vec()),
+			)
+			.await?;
+		let Some(offender) = offender else { return Ok(false) };
+
+		for report_id in report_ids {
+			let reported_offender = source_client
+				.storage_map_value::<OffencesReports<C>>(at, C::OFFENCES_PALLET_NAME, &report_id)
+				.await?;
+			if reported_offender.as_ref() == Some(&offender) {
+				return Ok(true)
+			}
+		}
+
+		Ok(false)
+	}
 }
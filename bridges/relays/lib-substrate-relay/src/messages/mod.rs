@@ -125,10 +125,30 @@ pub struct MessagesRelayParams<P: SubstrateMessageLane, SourceClnt, TargetClnt>
 	/// Messages relay limits. If not provided, the relay tries to determine it automatically,
 	/// using `TransactionPayment` pallet runtime API.
 	pub limits: Option<MessagesRelayLimits>,
+	/// Optional parameters of the messages delivery coordination with other relayers. If not
+	/// provided, the relay delivers messages independently of other relayers.
+	pub delivery_coordination: Option<DeliveryCoordinationParams>,
 	/// Metrics parameters.
 	pub metrics_params: MetricsParams,
 }
 
+/// Parameters of the messages delivery coordination with other relayers.
+///
+/// Relayers, registered in the `pallet-bridge-relayers` at the target chain, are taking turns
+/// to deliver messages. Every turn (slot) lasts for `slot_length` target chain blocks.
+#[derive(Clone, Debug)]
+pub struct DeliveryCoordinationParams {
+	/// Number of target chain blocks in the single delivery slot.
+	pub slot_length: u32,
+	/// Name of the source chain runtime parameter with the reward, paid for every delivered
+	/// message, e.g. `DeliveryRewardInBalance`.
+	///
+	/// The parameter is expected to be declared as `pub storage` in `parameter_types!`. If the
+	/// reward doesn't cover the fee of the delivery transaction or the lane rewards account at
+	/// the source chain can't pay it, the delivery is considered unprofitable.
+	pub delivery_reward_parameter: String,
+}
+
 /// Delivery transaction limits.
 pub struct MessagesRelayLimits {
 	/// Maximal number of messages in the delivery transaction.
@@ -286,6 +306,7 @@ where
 			relayer_id_at_source,
 			Some(params.target_transaction_params),
 			params.source_to_target_headers_relay,
+			params.delivery_coordination,
 		),
 		{
 			GlobalMetrics::new()?.register_and_spawn(&params.metrics_params.registry)?;
@@ -331,6 +352,7 @@ where
 			relayer_id_at_source,
 			Some(target_transaction_params),
 			None,
+			None,
 		),
 		at_source_block,
 		range,
@@ -371,6 +393,7 @@ where
 			relayer_id_at_source,
 			None,
 			None,
+			None,
 		),
 		at_target_block,
 	)
@@ -645,6 +668,22 @@ fn dummy_messages_delivery_transaction<P: SubstrateMessageLane, SourceClnt, Targ
 where
 	AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
 {
+	messages_delivery_transaction_template::<P>(
+		params.source_transaction_params.signer.public().into(),
+		params.target_transaction_params.signer.clone(),
+		messages,
+	)
+	.map_err(Into::into)
+}
+
+/// Returns dummy message delivery transaction of `messages` messages, signed by `signer`.
+///
+/// It is only good for weight and fee estimations.
+pub(crate) fn messages_delivery_transaction_template<P: SubstrateMessageLane>(
+	relayer_id_at_source: AccountIdOf<P::SourceChain>,
+	signer: AccountKeyPairOf<P::TargetChain>,
+	messages: u32,
+) -> Result<<P::TargetChain as ChainWithTransactions>::SignedTransaction, SubstrateError> {
 	// we don't care about any call values here, because all that the estimation RPC does
 	// is calls `GetDispatchInfo::get_dispatch_info` for the wrapped call. So we only are
 	// interested in values that affect call weight - e.g. number of messages and the
@@ -652,7 +691,7 @@ where
 
 	let dummy_messages_delivery_call =
 		P::ReceiveMessagesProofCallBuilder::build_receive_messages_proof_call(
-			params.source_transaction_params.signer.public().into(),
+			relayer_id_at_source,
 			(
 				Weight::zero(),
 				FromBridgedChainMessagesProof {
@@ -672,7 +711,7 @@ where
 			spec_version: 0,
 			transaction_version: 0,
 			genesis_hash: Default::default(),
-			signer: params.target_transaction_params.signer.clone(),
+			signer,
 		},
 		UnsignedTransaction {
 			call: EncodedOrDecodedCall::Decoded(dummy_messages_delivery_call),
@@ -681,7 +720,6 @@ where
 			era: TransactionEra::Immortal,
 		},
 	)
-	.map_err(Into::into)
 }
 
 #[cfg(test)]
@@ -20,37 +20,67 @@
 
 use crate::{
 	messages::{
+		messages_delivery_transaction_template,
 		source::{
 			ensure_messages_pallet_active, read_client_state_from_both_chains,
 			SubstrateMessagesProof,
 		},
-		BatchProofTransaction, MessageLaneAdapter, ReceiveMessagesProofCallBuilder,
-		SubstrateMessageLane,
+		BatchProofTransaction, DeliveryCoordinationParams, MessageLaneAdapter,
+		ReceiveMessagesProofCallBuilder, SubstrateMessageLane,
 	},
 	on_demand::OnDemandRelay,
 	proofs::to_raw_storage_proof,
 	TransactionParams,
 };
 
-use async_std::sync::Arc;
+use async_std::sync::{Arc, Mutex};
 use async_trait::async_trait;
 use bp_messages::{
 	source_chain::FromBridgedChainMessagesDeliveryProof, storage_keys::inbound_lane_data_key,
 	ChainWithMessages as _, LaneState, MessageNonce, UnrewardedRelayer, UnrewardedRelayersState,
 };
-use codec::Decode;
+use bp_relayers::{PayRewardFromAccount, Registration, RewardsAccountOwner, RewardsAccountParams};
+use codec::{Decode, Encode};
+use frame_support::{Blake2_128Concat, StorageHasher, Twox128};
+use frame_system::AccountInfo;
 use messages_relay::{
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
-	message_lane_loop::{NoncesSubmitArtifacts, TargetClient, TargetClientState},
+	message_lane_loop::{
+		NoncesSubmitArtifacts, RelayersCoordinationState, TargetClient, TargetClientState,
+	},
 };
+use pallet_balances::AccountData;
 use relay_substrate_client::{
-	AccountIdOf, AccountKeyPairOf, BalanceOf, CallOf, Chain, Client, Error as SubstrateError,
-	HashOf, TransactionEra, TransactionTracker, UnsignedTransaction,
+	AccountIdOf, AccountKeyPairOf, BalanceOf, BlockNumberOf, CallOf, Chain, Client,
+	Error as SubstrateError, HashOf, NonceOf, TransactionEra, TransactionTracker,
+	UnsignedTransaction,
 };
 use relay_utils::relay_loop::Client as RelayClient;
-use sp_core::Pair;
+use sp_core::{storage::StorageKey, Pair};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	SaturatedConversion,
+};
 use std::{collections::VecDeque, convert::TryFrom, ops::RangeInclusive};
 
+/// Name of the `RegisteredRelayers` storage map of the `pallet-bridge-relayers`.
+const REGISTERED_RELAYERS_MAP_NAME: &str = "RegisteredRelayers";
+/// Number of storage keys we are reading in a single `state_getKeysPaged` call.
+const STORAGE_KEYS_PAGE_SIZE: u32 = 256;
+/// Number of messages in the delivery transaction that is used to estimate profitability.
+const PROFITABILITY_ESTIMATION_MESSAGES: u32 = 1;
+
+/// Relayers coordination state, computed once per delivery slot.
+#[derive(Clone, Debug)]
+struct SlotCoordinationState<AccountId> {
+	/// Index of the delivery slot.
+	slot: u64,
+	/// Sorted accounts of relayers with active registration during the slot.
+	relayers: Vec<AccountId>,
+	/// Whether the delivery is expected to be profitable during the slot.
+	is_delivery_profitable: bool,
+}
+
 /// Message receiving proof returned by the target Substrate node.
 pub type SubstrateMessagesDeliveryProof<C, L> =
 	(UnrewardedRelayersState, FromBridgedChainMessagesDeliveryProof<HashOf<C>, L>);
@@ -103,6 +133,8 @@ pub struct SubstrateMessagesTarget<P: SubstrateMessageLane, SourceClnt, TargetCl
 	relayer_id_at_source: AccountIdOf<P::SourceChain>,
 	transaction_params: Option<TransactionParams<AccountKeyPairOf<P::TargetChain>>>,
 	source_to_target_headers_relay: Option<Arc<dyn OnDemandRelay<P::SourceChain, P::TargetChain>>>,
+	delivery_coordination: Option<DeliveryCoordinationParams>,
+	coordination_state: Arc<Mutex<Option<SlotCoordinationState<AccountIdOf<P::TargetChain>>>>>,
 }
 
 impl<P, SourceClnt, TargetClnt> SubstrateMessagesTarget<P, SourceClnt, TargetClnt>
@@ -120,6 +152,7 @@ where
 		source_to_target_headers_relay: Option<
 			Arc<dyn OnDemandRelay<P::SourceChain, P::TargetChain>>,
 		>,
+		delivery_coordination: Option<DeliveryCoordinationParams>,
 	) -> Self {
 		SubstrateMessagesTarget {
 			target_client,
@@ -128,6 +161,8 @@ where
 			relayer_id_at_source,
 			transaction_params,
 			source_to_target_headers_relay,
+			delivery_coordination,
+			coordination_state: Arc::new(Mutex::new(None)),
 		}
 	}
 
@@ -147,6 +182,59 @@ where
 			.await
 	}
 
+	/// Read accounts of all relayers that have active registration at the target chain.
+	///
+	/// Registration is considered active if it does not expire during the current delivery
+	/// slot. Returned accounts are sorted, so that all relayers are seeing the same schedule.
+	/// Registrations are read in batches of [`STORAGE_KEYS_PAGE_SIZE`].
+	async fn active_registered_relayers(
+		&self,
+		id: TargetHeaderIdOf<MessageLaneAdapter<P>>,
+		relayers_pallet_name: &str,
+		slot_end: BlockNumberOf<P::TargetChain>,
+	) -> Result<Vec<AccountIdOf<P::TargetChain>>, SubstrateError> {
+		let prefix =
+			bp_runtime::storage_value_key(relayers_pallet_name, REGISTERED_RELAYERS_MAP_NAME);
+		// every key is `prefix ++ blake2_128(account) ++ account`
+		let account_offset = prefix.0.len() + 16;
+
+		let mut relayers = Vec::new();
+		let mut start_key = None;
+		loop {
+			let keys = self
+				.target_client
+				.storage_keys_paged(id.hash(), prefix.clone(), STORAGE_KEYS_PAGE_SIZE, start_key)
+				.await?;
+			let is_last_page = keys.len() < STORAGE_KEYS_PAGE_SIZE as usize;
+			start_key = keys.last().cloned();
+
+			let registrations =
+				self.target_client.raw_storage_values(id.hash(), keys.clone()).await?;
+			for (key, registration) in keys.into_iter().zip(registrations) {
+				let Some(registration) = registration else { continue };
+				let registration = Registration::<
+					BlockNumberOf<P::TargetChain>,
+					BalanceOf<P::TargetChain>,
+				>::decode(&mut &registration.0[..])
+				.map_err(SubstrateError::ResponseParseFailed)?;
+				if registration.valid_till < slot_end {
+					continue
+				}
+
+				let relayer = AccountIdOf::<P::TargetChain>::decode(&mut &key.0[account_offset..])
+					.map_err(SubstrateError::ResponseParseFailed)?;
+				relayers.push(relayer);
+			}
+
+			if is_last_page {
+				break
+			}
+		}
+
+		relayers.sort_by_key(|relayer| relayer.encode());
+		Ok(relayers)
+	}
+
 	/// Ensure that the messages pallet at target chain is active.
 	async fn ensure_pallet_active(&self) -> Result<(), SubstrateError> {
 		ensure_messages_pallet_active::<P::TargetChain, P::SourceChain, _>(&self.target_client)
@@ -154,6 +242,101 @@ where
 	}
 }
 
+impl<P, SourceClnt, TargetClnt> SubstrateMessagesTarget<P, SourceClnt, TargetClnt>
+where
+	P: SubstrateMessageLane,
+	SourceClnt: Client<P::SourceChain>,
+	TargetClnt: Client<P::TargetChain>,
+	BalanceOf<P::SourceChain>: TryFrom<BalanceOf<P::TargetChain>>,
+{
+	/// Estimate whether the delivery is profitable for our relayer, using on-chain reward
+	/// parameters.
+	///
+	/// The fee of the delivery transaction with a single message, which is the least profitable
+	/// delivery, is estimated using the `TransactionPayment` runtime API. The source chain pays
+	/// the delivery reward for every message from its lane rewards account, once the delivery
+	/// is confirmed. That reward must cover the fee and the account must be able to pay it.
+	async fn is_delivery_profitable(
+		&self,
+		id: TargetHeaderIdOf<MessageLaneAdapter<P>>,
+		delivery_coordination: &DeliveryCoordinationParams,
+	) -> Result<bool, SubstrateError> {
+		// we can't sign delivery transactions, so we are not delivering anything
+		let Some(ref transaction_params) = self.transaction_params else { return Ok(false) };
+
+		let delivery_transaction = messages_delivery_transaction_template::<P>(
+			self.relayer_id_at_source.clone(),
+			transaction_params.signer.clone(),
+			PROFITABILITY_ESTIMATION_MESSAGES,
+		)?;
+		let delivery_fee = self
+			.target_client
+			.estimate_extrinsic_fee(id.hash(), delivery_transaction)
+			.await?;
+		// the reward is paid in source chain tokens
+		let Ok(delivery_fee) = BalanceOf::<P::SourceChain>::try_from(delivery_fee) else {
+			return Ok(false)
+		};
+
+		let at_source = self.source_client.best_finalized_header_hash().await?;
+		let rewards_account =
+			PayRewardFromAccount::<(), AccountIdOf<P::SourceChain>, P::LaneId>::rewards_account(
+				RewardsAccountParams::new(
+					self.lane_id,
+					P::TargetChain::ID,
+					RewardsAccountOwner::BridgedChain,
+				),
+			);
+		let rewards_balance =
+			free_balance::<P::SourceChain, _>(&self.source_client, at_source, &rewards_account)
+				.await?;
+		let reward_per_message = self
+			.source_client
+			.storage_value::<BalanceOf<P::SourceChain>>(
+				at_source,
+				parameter_storage_key(&delivery_coordination.delivery_reward_parameter),
+			)
+			.await?;
+
+		Ok(match reward_per_message {
+			Some(reward_per_message) => {
+				let reward =
+					reward_per_message.saturating_mul(PROFITABILITY_ESTIMATION_MESSAGES.into());
+				!reward.is_zero() && reward >= delivery_fee && rewards_balance >= reward
+			},
+			// the parameter has never been changed, so the runtime pays its compile-time default,
+			// which we can't read from the storage. We only check that the rewards account is
+			// able to cover the fee
+			None => !rewards_balance.is_zero() && rewards_balance >= delivery_fee,
+		})
+	}
+}
+
+/// Read free balance of the `account` at the given block.
+async fn free_balance<C: Chain, Clnt: Client<C>>(
+	client: &Clnt,
+	at: HashOf<C>,
+	account: &AccountIdOf<C>,
+) -> Result<BalanceOf<C>, SubstrateError> {
+	let account_info = client
+		.storage_value::<AccountInfo<NonceOf<C>, AccountData<BalanceOf<C>>>>(
+			at,
+			bp_runtime::storage_map_final_key::<Blake2_128Concat>(
+				"System",
+				"Account",
+				&account.encode(),
+			),
+		)
+		.await?;
+	Ok(account_info.map(|account_info| account_info.data.free).unwrap_or_default())
+}
+
+/// Returns storage key of the runtime parameter, declared with `parameter_types!` as
+/// `pub storage <name>: <type> = <default>`.
+fn parameter_storage_key(name: &str) -> StorageKey {
+	StorageKey(Twox128::hash(format!(":{name}:").as_bytes()).to_vec())
+}
+
 impl<P: SubstrateMessageLane, SourceClnt: Clone, TargetClnt: Clone> Clone
 	for SubstrateMessagesTarget<P, SourceClnt, TargetClnt>
 {
@@ -165,6 +348,8 @@ impl<P: SubstrateMessageLane, SourceClnt: Clone, TargetClnt: Clone> Clone
 			relayer_id_at_source: self.relayer_id_at_source.clone(),
 			transaction_params: self.transaction_params.clone(),
 			source_to_target_headers_relay: self.source_to_target_headers_relay.clone(),
+			delivery_coordination: self.delivery_coordination.clone(),
+			coordination_state: self.coordination_state.clone(),
 		}
 	}
 }
@@ -262,6 +447,68 @@ where
 		Ok((id, inbound_lane_data.into()))
 	}
 
+	async fn relayers_coordination_state(
+		&self,
+		id: TargetHeaderIdOf<MessageLaneAdapter<P>>,
+	) -> Result<
+		(TargetHeaderIdOf<MessageLaneAdapter<P>>, Option<RelayersCoordinationState>),
+		SubstrateError,
+	> {
+		let (delivery_coordination, relayers_pallet_name) = match (
+			self.delivery_coordination.as_ref(),
+			P::SourceChain::WITH_CHAIN_RELAYERS_PALLET_NAME,
+		) {
+			(Some(delivery_coordination), Some(relayers_pallet_name)) =>
+				(delivery_coordination, relayers_pallet_name),
+			_ => return Ok((id, None)),
+		};
+
+		let slot_length = std::cmp::max(delivery_coordination.slot_length, 1);
+		let block_number: u64 = id.number().saturated_into();
+		let slot = block_number / slot_length as u64;
+		let slot_end = (slot.saturating_add(1).saturating_mul(slot_length as u64)).saturated_into();
+
+		// registrations are checked against the end of the slot and the rewards are only
+		// estimated, so we only read them once per slot
+		let mut cached_state = self.coordination_state.lock().await;
+		let coordination_state = match cached_state.as_ref().filter(|state| state.slot == slot) {
+			Some(state) => state.clone(),
+			None => {
+				let state = SlotCoordinationState {
+					slot,
+					relayers: self
+						.active_registered_relayers(id, relayers_pallet_name, slot_end)
+						.await?,
+					is_delivery_profitable: self
+						.is_delivery_profitable(id, delivery_coordination)
+						.await?,
+				};
+				*cached_state = Some(state.clone());
+				state
+			},
+		};
+		let relayers = &coordination_state.relayers;
+		let relayer_id = self.transaction_params.as_ref().map(|transaction_params| {
+			AccountIdOf::<P::TargetChain>::from(transaction_params.signer.public())
+		});
+		let relayer_position = relayer_id.and_then(|relayer_id| {
+			relayers
+				.iter()
+				.position(|relayer| *relayer == relayer_id)
+				.map(|position| position as u32)
+		});
+
+		Ok((
+			id,
+			Some(RelayersCoordinationState {
+				slot,
+				relayers_count: relayers.len() as u32,
+				relayer_position,
+				is_delivery_profitable: coordination_state.is_delivery_profitable,
+			}),
+		))
+	}
+
 	async fn prove_messages_receiving(
 		&self,
 		id: TargetHeaderIdOf<MessageLaneAdapter<P>>,
@@ -407,4 +654,16 @@ mod tests {
 		assert_eq!(full.last_confirmed_nonce, decoded.last_confirmed_nonce);
 		assert_eq!(full.last_delivered_nonce(), decoded.last_delivered_nonce());
 	}
+
+	#[test]
+	fn parameter_storage_key_matches_runtime_parameter_key() {
+		frame_support::parameter_types! {
+			pub storage DeliveryRewardInBalance: u64 = 1_000_000;
+		}
+
+		assert_eq!(
+			parameter_storage_key("DeliveryRewardInBalance").0,
+			DeliveryRewardInBalance::key().to_vec(),
+		);
+	}
 }
@@ -0,0 +1,198 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! On-demand Substrate -> Substrate BEEFY finality relay.
+
+use crate::{
+	beefy::{
+		source::SubstrateBeefySource, target::current_authority_set_info,
+		SubmitBeefyCommitmentCallBuilder, SubstrateBeefySyncPipeline,
+	},
+	finality::source::RequiredHeaderNumberRef,
+	on_demand::OnDemandRelay,
+	TransactionParams,
+};
+
+use async_std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use bp_runtime::HeaderId;
+use num_traits::{One, Zero};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, CallOf, Chain, Client, Error as SubstrateError,
+	HeaderIdOf,
+};
+use relay_utils::{metrics::MetricsParams, relay_loop::Client as RelayClient};
+
+/// On-demand Substrate <-> Substrate BEEFY finality relay.
+///
+/// This relay is always relaying commitments that are handing over to the next BEEFY validator
+/// set. Other commitments are only relayed when some other relay (e.g. messages relay) needs
+/// it to continue its regular work.
+#[derive(Clone)]
+pub struct OnDemandBeefyRelay<P: SubstrateBeefySyncPipeline, SourceClnt, TargetClnt> {
+	/// Relay task name.
+	relay_task_name: String,
+	/// Shared reference to maximal required finalized block number.
+	required_block_number: RequiredHeaderNumberRef<P::SourceChain>,
+	/// Client of the source chain.
+	source_client: SourceClnt,
+	/// Client of the target chain.
+	target_client: TargetClnt,
+}
+
+impl<
+		P: SubstrateBeefySyncPipeline,
+		SourceClnt: Client<P::SourceChain>,
+		TargetClnt: Client<P::TargetChain>,
+	> OnDemandBeefyRelay<P, SourceClnt, TargetClnt>
+{
+	/// Create new on-demand BEEFY relay.
+	///
+	/// If `metrics_params` is `Some(_)`, the metrics of the BEEFY relay are registered.
+	/// Otherwise, all required metrics must be exposed outside of this method.
+	pub fn new(
+		source_client: SourceClnt,
+		target_client: TargetClnt,
+		target_transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+		metrics_params: Option<MetricsParams>,
+	) -> Self
+	where
+		AccountIdOf<P::SourceChain>:
+			From<<AccountKeyPairOf<P::SourceChain> as sp_core::Pair>::Public>,
+		AccountIdOf<P::TargetChain>:
+			From<<AccountKeyPairOf<P::TargetChain> as sp_core::Pair>::Public>,
+	{
+		let required_block_number = Arc::new(Mutex::new(Zero::zero()));
+		let this = OnDemandBeefyRelay {
+			relay_task_name: on_demand_beefy_relay_name::<P::SourceChain, P::TargetChain>(),
+			required_block_number: required_block_number.clone(),
+			source_client: source_client.clone(),
+			target_client: target_client.clone(),
+		};
+		async_std::task::spawn(async move {
+			let relay_task_name = on_demand_beefy_relay_name::<P::SourceChain, P::TargetChain>();
+			let result = crate::beefy::run::<P>(
+				source_client,
+				target_client,
+				Some(required_block_number),
+				None,
+				target_transaction_params,
+				metrics_params.unwrap_or_else(MetricsParams::disabled),
+			)
+			.await;
+
+			if let Err(e) = result {
+				log::error!(
+					target: "bridge",
+					"[{}] On-demand BEEFY relay has failed: {:?}",
+					relay_task_name,
+					e,
+				);
+			}
+		});
+
+		this
+	}
+}
+
+#[async_trait]
+impl<
+		P: SubstrateBeefySyncPipeline,
+		SourceClnt: Client<P::SourceChain>,
+		TargetClnt: Client<P::TargetChain>,
+	> OnDemandRelay<P::SourceChain, P::TargetChain> for OnDemandBeefyRelay<P, SourceClnt, TargetClnt>
+{
+	async fn reconnect(&self) -> Result<(), SubstrateError> {
+		// using clone is fine here (to avoid mut requirement), because clone on Client clones
+		// internal references
+		self.source_client.clone().reconnect().await?;
+		self.target_client.clone().reconnect().await
+	}
+
+	async fn require_more_headers(&self, required_header: BlockNumberOf<P::SourceChain>) {
+		let mut required_block_number = self.required_block_number.lock().await;
+		if required_header > *required_block_number {
+			log::trace!(
+				target: "bridge",
+				"[{}] More {} blocks required. Going to sync up to the {}",
+				self.relay_task_name,
+				P::SourceChain::NAME,
+				required_header,
+			);
+
+			*required_block_number = required_header;
+		}
+	}
+
+	async fn prove_header(
+		&self,
+		required_header: BlockNumberOf<P::SourceChain>,
+	) -> Result<(HeaderIdOf<P::SourceChain>, Vec<CallOf<P::TargetChain>>), SubstrateError> {
+		let beefy_source = SubstrateBeefySource::<P, _>::new(self.source_client.clone(), None);
+
+		// commitment for block `N` is proving block `N - 1`, so we need commitment for one of
+		// `required_header` descendants, signed by the validator set, known to the target
+		let at = self.target_client.best_header_hash().await?;
+		let validator_set_id =
+			current_authority_set_info::<P, _>(&self.target_client, at).await?.id;
+		let best_finalized_number = beefy_source.best_finalized_block_number().await?;
+		let commitment = beefy_source
+			.find_first_commitment(required_header + One::one(), best_finalized_number)
+			.await?
+			.filter(|commitment| commitment.commitment.validator_set_id == validator_set_id)
+			.ok_or_else(|| {
+				SubstrateError::Custom(format!(
+					"[{}] Unable to find {} BEEFY commitment, signed by validator set {} and \
+					proving block {:?}",
+					self.relay_task_name,
+					P::SourceChain::NAME,
+					validator_set_id,
+					required_header,
+				))
+			})?;
+
+		let block_number = commitment.commitment.block_number;
+		let validator_set = beefy_source.validator_set(block_number).await?;
+		let (mmr_leaf, mmr_proof) = beefy_source.mmr_leaf_and_proof(block_number).await?;
+		let (proved_number, proved_hash) = mmr_leaf.parent_number_and_hash;
+
+		log::debug!(
+			target: "bridge",
+			"[{}] Requested to prove {} head {:?}. Selected to prove {} head {:?} using \
+			BEEFY commitment for block {:?}",
+			self.relay_task_name,
+			P::SourceChain::NAME,
+			required_header,
+			P::SourceChain::NAME,
+			(proved_number, proved_hash),
+			block_number,
+		);
+
+		let call = P::SubmitBeefyCommitmentCallBuilder::build_submit_commitment_call(
+			commitment,
+			validator_set,
+			mmr_leaf,
+			mmr_proof,
+		);
+
+		Ok((HeaderId(proved_number, proved_hash), vec![call]))
+	}
+}
+
+/// On-demand BEEFY relay task name.
+fn on_demand_beefy_relay_name<SourceChain: Chain, TargetChain: Chain>() -> String {
+	format!("{}-to-{}-on-demand-beefy", SourceChain::NAME, TargetChain::NAME)
+}
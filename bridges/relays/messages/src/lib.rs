@@ -30,6 +30,7 @@ mod metrics;
 pub mod message_lane;
 pub mod message_lane_loop;
 
+mod message_race_coordination;
 mod message_race_delivery;
 mod message_race_limits;
 mod message_race_loop;
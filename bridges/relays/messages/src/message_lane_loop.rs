@@ -92,6 +92,38 @@ pub struct MessageDetails<SourceChainBalance> {
 pub type MessageDetailsMap<SourceChainBalance> =
 	BTreeMap<MessageNonce, MessageDetails<SourceChainBalance>>;
 
+/// State of the delivery coordination between relayers, registered at the target chain.
+///
+/// When several relayers are serving the same lane, they may coordinate delivery by taking
+/// turns. Every relayer, registered in the `pallet-bridge-relayers` at the target chain, is
+/// assigned a position in the schedule. The relayer is allowed to deliver messages during
+/// the slots, matching its position. Other relayers are only taking over if the lane has
+/// not been served for the whole round.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelayersCoordinationState {
+	/// Index of the current delivery slot.
+	pub slot: u64,
+	/// Number of relayers that are participating in coordinated delivery.
+	pub relayers_count: u32,
+	/// Position of our relayer in the schedule. It is `None` if our relayer has no active
+	/// registration at the target chain.
+	pub relayer_position: Option<u32>,
+	/// Whether the delivery transaction is expected to be profitable for our relayer. It is
+	/// estimated using on-chain reward parameters at both source and target chains.
+	pub is_delivery_profitable: bool,
+}
+
+impl RelayersCoordinationState {
+	/// Returns true if the current slot belongs to our relayer.
+	pub fn is_our_turn(&self) -> bool {
+		match self.relayer_position {
+			Some(relayer_position) if self.relayers_count != 0 =>
+				self.slot % self.relayers_count as u64 == relayer_position as u64,
+			_ => false,
+		}
+	}
+}
+
 /// Message delivery race proof parameters.
 #[derive(Debug, PartialEq, Eq)]
 pub struct MessageProofParameters {
@@ -211,6 +243,15 @@ pub trait TargetClient<P: MessageLane>: RelayClient {
 		id: TargetHeaderIdOf<P>,
 	) -> Result<(TargetHeaderIdOf<P>, UnrewardedRelayersState), Self::Error>;
 
+	/// Get state of the delivery coordination between relayers at given block.
+	///
+	/// Returns `None` if coordinated delivery is disabled. In this case, our relayer delivers
+	/// messages independently of other relayers.
+	async fn relayers_coordination_state(
+		&self,
+		id: TargetHeaderIdOf<P>,
+	) -> Result<(TargetHeaderIdOf<P>, Option<RelayersCoordinationState>), Self::Error>;
+
 	/// Prove messages receiving at given block.
 	async fn prove_messages_receiving(
 		&self,
@@ -897,6 +938,14 @@ pub(crate) mod tests {
 			Ok((id, data.target_latest_confirmed_received_nonce))
 		}
 
+		async fn relayers_coordination_state(
+			&self,
+			id: TargetHeaderIdOf<TestMessageLane>,
+		) -> Result<(TargetHeaderIdOf<TestMessageLane>, Option<RelayersCoordinationState>), TestError>
+		{
+			Ok((id, None))
+		}
+
 		async fn prove_messages_receiving(
 			&self,
 			id: TargetHeaderIdOf<TestMessageLane>,
@@ -0,0 +1,226 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Coordination of the message delivery between multiple relayers.
+//!
+//! Relayers, registered in the `pallet-bridge-relayers` at the target chain, are taking
+//! turns to deliver messages. This way they don't compete for the same messages and don't
+//! lose fees (and stakes) on rejected delivery transactions. If the relayer, whose turn it
+//! is, is offline, other relayers are taking over after a full round of turns.
+
+use crate::message_lane_loop::RelayersCoordinationState;
+
+use bp_messages::MessageNonce;
+
+/// Maximal number of slots we skip after our delivery transaction has collided with other
+/// relayer transaction.
+const MAX_COLLISION_BACKOFF_SLOTS: u64 = 16;
+
+/// Message delivery coordination state of our relayer.
+#[derive(Debug, Default)]
+pub struct DeliveryCoordination {
+	/// The latest known coordination state at the target chain. `None` if coordination is
+	/// disabled.
+	state: Option<RelayersCoordinationState>,
+	/// The latest known nonce at the target chain.
+	latest_nonce_at_target: Option<MessageNonce>,
+	/// Slot at which we have noticed that the lane is not served by any relayer.
+	stalled_since_slot: Option<u64>,
+	/// Number of our consecutive delivery transactions that have failed.
+	failed_submissions: u32,
+	/// Slot, starting from which we may submit delivery transactions again.
+	backoff_till_slot: Option<u64>,
+}
+
+impl DeliveryCoordination {
+	/// Called when coordination state and nonces at the target chain are updated.
+	///
+	/// `has_undelivered_messages` is `true` if there are messages at the source chain that
+	/// have not yet been delivered to the target chain.
+	pub fn target_state_updated(
+		&mut self,
+		state: Option<RelayersCoordinationState>,
+		latest_nonce_at_target: MessageNonce,
+		has_undelivered_messages: bool,
+	) {
+		let current_slot = state.as_ref().map(|state| state.slot);
+		let is_target_nonce_updated = self.latest_nonce_at_target != Some(latest_nonce_at_target);
+
+		self.stalled_since_slot = match (has_undelivered_messages, is_target_nonce_updated) {
+			(false, _) => None,
+			(true, true) => current_slot,
+			(true, false) => self.stalled_since_slot.or(current_slot),
+		};
+		self.latest_nonce_at_target = Some(latest_nonce_at_target);
+		self.state = state;
+	}
+
+	/// Called when our delivery transaction is mined or has failed.
+	pub fn submission_finished(&mut self, is_successful: bool) {
+		if is_successful {
+			self.failed_submissions = 0;
+			self.backoff_till_slot = None;
+			return
+		}
+
+		// we can't distinguish collisions from other failures here, but when all relayers
+		// are following the schedule, collisions are the most likely cause of failures
+		let current_slot = match self.state {
+			Some(ref state) => state.slot,
+			None => return,
+		};
+		self.failed_submissions = self.failed_submissions.saturating_add(1);
+		let backoff_slots = 1u64
+			.checked_shl(self.failed_submissions - 1)
+			.unwrap_or(MAX_COLLISION_BACKOFF_SLOTS)
+			.min(MAX_COLLISION_BACKOFF_SLOTS);
+		self.backoff_till_slot = Some(current_slot.saturating_add(backoff_slots));
+
+		log::warn!(
+			target: "bridge",
+			"Delivery transaction has failed at slot {}. Probably it has collided with other relayer \
+			transaction. Backing off for {} slots",
+			current_slot,
+			backoff_slots,
+		);
+	}
+
+	/// Returns true if our relayer is allowed to submit delivery transaction now.
+	pub fn is_delivery_allowed(&self) -> bool {
+		let state = match self.state {
+			Some(ref state) => state,
+			None => return true,
+		};
+
+		if !state.is_delivery_profitable {
+			log::trace!(target: "bridge", "Delivery is not profitable at slot {}", state.slot);
+			return false
+		}
+
+		if self
+			.backoff_till_slot
+			.map(|backoff_till_slot| state.slot < backoff_till_slot)
+			.unwrap_or(false)
+		{
+			return false
+		}
+
+		if state.relayers_count == 0 || state.is_our_turn() {
+			return true
+		}
+
+		// other relayer is not delivering messages for a full round => we take over. Relayers
+		// without registration are waiting for one extra slot, so that registered relayers
+		// are taking over first
+		let round_length =
+			state.relayers_count as u64 + if state.relayer_position.is_some() { 0 } else { 1 };
+		self.stalled_since_slot
+			.map(|stalled_since_slot| state.slot >= stalled_since_slot.saturating_add(round_length))
+			.unwrap_or(false)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn state(slot: u64, relayer_position: Option<u32>) -> RelayersCoordinationState {
+		RelayersCoordinationState {
+			slot,
+			relayers_count: 3,
+			relayer_position,
+			is_delivery_profitable: true,
+		}
+	}
+
+	#[test]
+	fn delivery_is_always_allowed_if_coordination_is_disabled() {
+		let mut coordination = DeliveryCoordination::default();
+		coordination.target_state_updated(None, 10, true);
+		assert!(coordination.is_delivery_allowed());
+
+		coordination.submission_finished(false);
+		assert!(coordination.is_delivery_allowed());
+	}
+
+	#[test]
+	fn relayer_delivers_at_its_turn() {
+		let mut coordination = DeliveryCoordination::default();
+		coordination.target_state_updated(Some(state(4, Some(1))), 10, true);
+		assert!(coordination.is_delivery_allowed());
+
+		coordination.target_state_updated(Some(state(5, Some(1))), 11, true);
+		assert!(!coordination.is_delivery_allowed());
+	}
+
+	#[test]
+	fn delivery_is_not_allowed_if_it_is_not_profitable() {
+		let mut coordination = DeliveryCoordination::default();
+		coordination.target_state_updated(
+			Some(RelayersCoordinationState { is_delivery_profitable: false, ..state(4, Some(1)) }),
+			10,
+			true,
+		);
+		assert!(!coordination.is_delivery_allowed());
+	}
+
+	#[test]
+	fn relayer_takes_over_stalled_lane() {
+		let mut coordination = DeliveryCoordination::default();
+		coordination.target_state_updated(Some(state(5, Some(1))), 10, true);
+		assert!(!coordination.is_delivery_allowed());
+		coordination.target_state_updated(Some(state(7, Some(1))), 10, true);
+		assert!(!coordination.is_delivery_allowed());
+		coordination.target_state_updated(Some(state(8, Some(1))), 10, true);
+		assert!(coordination.is_delivery_allowed());
+
+		// nonce has been updated => lane is served again
+		coordination.target_state_updated(Some(state(8, Some(1))), 11, true);
+		assert!(!coordination.is_delivery_allowed());
+	}
+
+	#[test]
+	fn unregistered_relayer_takes_over_after_registered_relayers() {
+		let mut coordination = DeliveryCoordination::default();
+		coordination.target_state_updated(Some(state(5, None)), 10, true);
+		coordination.target_state_updated(Some(state(8, None)), 10, true);
+		assert!(!coordination.is_delivery_allowed());
+		coordination.target_state_updated(Some(state(9, None)), 10, true);
+		assert!(coordination.is_delivery_allowed());
+	}
+
+	#[test]
+	fn relayer_backs_off_after_collisions() {
+		let mut coordination = DeliveryCoordination::default();
+		coordination.target_state_updated(Some(state(4, Some(1))), 10, true);
+		coordination.submission_finished(false);
+		assert_eq!(coordination.backoff_till_slot, Some(5));
+		coordination.submission_finished(false);
+		assert_eq!(coordination.backoff_till_slot, Some(6));
+		coordination.submission_finished(false);
+		assert_eq!(coordination.backoff_till_slot, Some(8));
+		assert!(!coordination.is_delivery_allowed());
+
+		for _ in 0..10 {
+			coordination.submission_finished(false);
+		}
+		assert_eq!(coordination.backoff_till_slot, Some(4 + MAX_COLLISION_BACKOFF_SLOTS));
+
+		coordination.submission_finished(true);
+		assert_eq!(coordination.backoff_till_slot, None);
+		assert!(coordination.is_delivery_allowed());
+	}
+}
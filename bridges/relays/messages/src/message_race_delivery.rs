@@ -25,9 +25,10 @@ use crate::{
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
 	message_lane_loop::{
 		MessageDeliveryParams, MessageDetailsMap, MessageProofParameters, NoncesSubmitArtifacts,
-		SourceClient as MessageLaneSourceClient, SourceClientState,
+		RelayersCoordinationState, SourceClient as MessageLaneSourceClient, SourceClientState,
 		TargetClient as MessageLaneTargetClient, TargetClientState,
 	},
+	message_race_coordination::DeliveryCoordination,
 	message_race_limits::{MessageRaceLimits, RelayMessagesBatchReference},
 	message_race_loop::{
 		MessageRace, NoncesRange, RaceState, RaceStrategy, SourceClient, SourceClientNonces,
@@ -68,6 +69,7 @@ pub async fn run<P: MessageLane>(
 			max_messages_size_in_single_batch: params.max_messages_size_in_single_batch,
 			latest_confirmed_nonces_at_source: VecDeque::new(),
 			target_nonces: None,
+			coordination: DeliveryCoordination::default(),
 			strategy: BasicStrategy::new(),
 		},
 	)
@@ -252,6 +254,8 @@ where
 			self.client.latest_confirmed_received_nonce(at_block).await?;
 		let (at_block, unrewarded_relayers) =
 			self.client.unrewarded_relayers_state(at_block).await?;
+		let (at_block, coordination_state) =
+			self.client.relayers_coordination_state(at_block).await?;
 
 		if update_metrics {
 			if let Some(metrics_msg) = self.metrics_msg.as_ref() {
@@ -267,6 +271,7 @@ where
 				nonces_data: DeliveryRaceTargetNoncesData {
 					confirmed_nonce: latest_confirmed_nonce,
 					unrewarded_relayers,
+					coordination_state,
 				},
 			},
 		))
@@ -294,6 +299,8 @@ struct DeliveryRaceTargetNoncesData {
 	confirmed_nonce: MessageNonce,
 	/// State of the unrewarded relayers set at the target node.
 	unrewarded_relayers: UnrewardedRelayersState,
+	/// State of the delivery coordination between relayers at the target node.
+	coordination_state: Option<RelayersCoordinationState>,
 }
 
 /// Messages delivery strategy.
@@ -313,6 +320,8 @@ struct MessageDeliveryStrategy<P: MessageLane> {
 	latest_confirmed_nonces_at_source: VecDeque<(SourceHeaderIdOf<P>, MessageNonce)>,
 	/// Target nonces available at the **best** block of the target chain.
 	target_nonces: Option<TargetClientNonces<DeliveryRaceTargetNoncesData>>,
+	/// Coordination of the delivery with other relayers.
+	coordination: DeliveryCoordination,
 	/// Basic delivery strategy.
 	strategy: MessageDeliveryStrategyBase<P>,
 }
@@ -339,6 +348,7 @@ impl<P: MessageLane> std::fmt::Debug for MessageDeliveryStrategy<P> {
 			.field("max_messages_size_in_single_batch", &self.max_messages_size_in_single_batch)
 			.field("latest_confirmed_nonces_at_source", &self.latest_confirmed_nonces_at_source)
 			.field("target_nonces", &self.target_nonces)
+			.field("coordination", &self.coordination)
 			.field("strategy", &self.strategy)
 			.finish()
 	}
@@ -384,6 +394,11 @@ where
 			return None
 		}
 
+		// if it is not our turn to deliver messages, do nothing
+		if !self.coordination.is_delivery_allowed() {
+			return None
+		}
+
 		let best_target_nonce = self.strategy.best_at_target()?;
 		let best_finalized_source_header_id_at_best_target =
 			race_state.best_finalized_source_header_id_at_best_target()?;
@@ -618,6 +633,17 @@ where
 	) {
 		// best target nonces must always be ge than finalized target nonces
 		let latest_nonce = nonces.latest_nonce;
+		let has_undelivered_messages = self
+			.strategy
+			.source_queue()
+			.back()
+			.map(|(_, range)| range.end() > latest_nonce)
+			.unwrap_or(false);
+		self.coordination.target_state_updated(
+			nonces.nonces_data.coordination_state.clone(),
+			latest_nonce,
+			has_undelivered_messages,
+		);
 		self.target_nonces = Some(nonces);
 
 		self.strategy.best_target_nonces_updated(
@@ -656,6 +682,15 @@ where
 		)
 	}
 
+	fn nonces_submission_finished(
+		&mut self,
+		nonces: RangeInclusive<MessageNonce>,
+		is_successful: bool,
+	) {
+		self.coordination.submission_finished(is_successful);
+		self.strategy.nonces_submission_finished(nonces, is_successful)
+	}
+
 	async fn select_nonces_to_deliver<RS: RaceState<SourceHeaderIdOf<P>, TargetHeaderIdOf<P>>>(
 		&self,
 		race_state: RS,
@@ -760,8 +795,10 @@ mod tests {
 						total_messages: 0,
 						last_delivered_nonce: 0,
 					},
+					coordination_state: None,
 				},
 			}),
+			coordination: DeliveryCoordination::default(),
 			strategy: BasicStrategy::new(),
 		};
 
@@ -829,6 +866,30 @@ mod tests {
 		);
 	}
 
+	#[async_std::test]
+	async fn message_delivery_strategy_selects_messages_only_at_relayer_turn() {
+		let (mut state, mut strategy) = prepare_strategy();
+
+		// it is not our turn to deliver messages
+		let mut target_nonces = strategy.target_nonces.clone().unwrap();
+		target_nonces.nonces_data.coordination_state = Some(RelayersCoordinationState {
+			slot: 10,
+			relayers_count: 3,
+			relayer_position: Some(0),
+			is_delivery_profitable: true,
+		});
+		strategy.best_target_nonces_updated(target_nonces.clone(), &mut state);
+		assert_eq!(strategy.select_nonces_to_deliver(state.clone()).await, None);
+
+		// and now it is our turn
+		target_nonces.nonces_data.coordination_state.as_mut().unwrap().slot = 12;
+		strategy.best_target_nonces_updated(target_nonces, &mut state);
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((20..=23), proof_parameters(false, 4)))
+		);
+	}
+
 	#[async_std::test]
 	async fn message_delivery_strategy_includes_outbound_state_proof_when_new_nonces_are_available()
 	{
@@ -1056,6 +1117,7 @@ mod tests {
 						total_messages: 4,
 						last_delivered_nonce: 23,
 					},
+					coordination_state: None,
 				},
 			},
 			&mut state,
@@ -1145,6 +1207,7 @@ mod tests {
 			max_messages_size_in_single_batch: 4,
 			latest_confirmed_nonces_at_source: VecDeque::new(),
 			target_nonces: None,
+			coordination: DeliveryCoordination::default(),
 			strategy: BasicStrategy::new(),
 		};
 
@@ -1211,6 +1274,7 @@ mod tests {
 		let target_nonces_data = DeliveryRaceTargetNoncesData {
 			confirmed_nonce: 19,
 			unrewarded_relayers: UnrewardedRelayersState::default(),
+			coordination_state: None,
 		};
 		let target_header_2 = header_id(2);
 		state.best_target_header_id = Some(target_header_2);
@@ -1268,6 +1332,7 @@ mod tests {
 						total_messages: occupied_message_slots,
 						..Default::default()
 					},
+					coordination_state: None,
 				},
 			};
 
@@ -1425,6 +1490,7 @@ mod tests {
 					total_messages: 2,
 					..Default::default()
 				},
+				coordination_state: None,
 			},
 		};
 		state.best_target_header_id = Some(header_id(2));
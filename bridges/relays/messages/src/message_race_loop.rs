@@ -210,6 +210,14 @@ pub trait RaceStrategy<SourceHeaderId, TargetHeaderId, Proof>: Debug {
 		nonces: TargetClientNonces<Self::TargetNoncesData>,
 		race_state: &mut RS,
 	);
+	/// Called when our transaction, submitting `nonces` to the target node, is either mined
+	/// and has updated target nonces (`is_successful` is `true`), or has failed or has been
+	/// lost (`is_successful` is `false`).
+	fn nonces_submission_finished(
+		&mut self,
+		nonces: RangeInclusive<MessageNonce>,
+		is_successful: bool,
+	);
 	/// Should return `Some(nonces)` if we need to deliver proof of `nonces` (and associated
 	/// data) from source to target node.
 	/// Additionally, parameters required to generate proof are returned.
@@ -549,7 +557,8 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 					(TrackedTransactionStatus::Finalized(at_block), Some(nonces_submitted)) => {
 						// our transaction has been mined, but was it successful or not? let's check the best
 						// nonce at the target node.
-						let _ = race_target.nonces(at_block, false)
+						let nonces_submitted = nonces_submitted.clone();
+						let submission_result = race_target.nonces(at_block, false)
 							.await
 							.map_err(|e| format!("failed to read nonces from target node: {e:?}"))
 							.and_then(|(_, nonces_at_target)| {
@@ -574,8 +583,13 @@ pub async fn run<P: MessageRace, SC: SourceClient<P>, TC: TargetClient<P>>(
 
 								race_state.reset_nonces_submitted();
 							});
+						strategy.nonces_submission_finished(nonces_submitted, submission_result.is_ok());
 					},
-					(TrackedTransactionStatus::Lost, _) => {
+					(TrackedTransactionStatus::Lost, nonces_submitted) => {
+						if let Some(nonces_submitted) = nonces_submitted {
+							strategy.nonces_submission_finished(nonces_submitted.clone(), false);
+						}
+
 						log::warn!(
 							target: "bridge",
 							"{} -> {} race transaction has been lost. State: {:?}. Strategy: {:?}",
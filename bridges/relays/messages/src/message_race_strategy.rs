@@ -326,6 +326,13 @@ where
 		));
 	}
 
+	fn nonces_submission_finished(
+		&mut self,
+		_nonces: RangeInclusive<MessageNonce>,
+		_is_successful: bool,
+	) {
+	}
+
 	async fn select_nonces_to_deliver<
 		RS: RaceState<
 			HeaderId<SourceHeaderHash, SourceHeaderNumber>,
@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use snowbridge_beacon_primitives::AncestryProofKind;
+
+sp_api::decl_runtime_apis! {
+	pub trait EthereumClientApi
+	{
+		/// Returns the kind of ancestry proof that is required to prove the beacon block at
+		/// `block_slot` against the latest finalized beacon block.
+		fn ancestry_proof_kind(block_slot: u64) -> Option<AncestryProofKind>;
+	}
+}
@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+//! Helpers for implementing runtime api
+
+use crate::{
+	config::SLOTS_PER_HISTORICAL_ROOT, Config, FinalizedBeaconState, LatestFinalizedBlockRoot,
+	Pallet,
+};
+use snowbridge_beacon_primitives::{historical::is_summarized, AncestryProofKind};
+
+/// Returns the kind of ancestry proof, that is required to prove the beacon block at `block_slot`
+/// against the latest finalized beacon block. Returns `None` if the block is not finalized yet,
+/// or if it may not be proven at all.
+pub fn ancestry_proof_kind<T>(block_slot: u64) -> Option<AncestryProofKind>
+where
+	T: Config,
+{
+	let finalized_block_root = LatestFinalizedBlockRoot::<T>::get();
+	let state = FinalizedBeaconState::<T>::get(finalized_block_root)?;
+	if block_slot >= state.slot {
+		return None
+	}
+
+	if block_slot + SLOTS_PER_HISTORICAL_ROOT as u64 >= state.slot {
+		Some(AncestryProofKind::BlockRoots { finalized_block_root })
+	} else if is_summarized(block_slot, Pallet::<T>::capella_slot(), state.slot) {
+		Some(AncestryProofKind::HistoricalSummaries { finalized_block_root })
+	} else {
+		None
+	}
+}
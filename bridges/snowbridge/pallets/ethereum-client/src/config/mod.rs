@@ -64,3 +64,6 @@ pub const SYNC_COMMITTEE_BITS_SIZE: usize = SYNC_COMMITTEE_SIZE / 8;
 pub const SLOTS_PER_HISTORICAL_ROOT: usize = 8192;
 /// The index of the block_roots field in the beacon state tree.
 pub const BLOCK_ROOT_AT_INDEX_DEPTH: usize = 13;
+/// The size of the proof of blocks older than `SLOTS_PER_HISTORICAL_ROOT`, based on the
+/// historical summaries of the beacon state.
+pub use snowbridge_beacon_primitives::historical::HISTORICAL_ANCESTRY_PROOF_DEPTH;
@@ -2,7 +2,10 @@
 // SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
 use super::*;
 use frame_support::ensure;
-use snowbridge_beacon_primitives::ExecutionProof;
+use snowbridge_beacon_primitives::{
+	historical::{compute_historical_ancestry_root, is_summarized},
+	ExecutionProof,
+};
 
 use snowbridge_core::inbound::{
 	VerificationError::{self, *},
@@ -131,7 +134,12 @@ impl<T: Config> Pallet<T> {
 	/// Verify that `block_root` is an ancestor of `finalized_block_root` Used to prove that
 	/// an execution header is an ancestor of a finalized header (i.e. the blocks are
 	/// on the same chain).
-	fn verify_ancestry_proof(
+	///
+	/// Blocks within the last `SLOTS_PER_HISTORICAL_ROOT` slots of the finalized block are proven
+	/// using its `block_roots` vector. Older blocks are proven using the `historical_summaries`
+	/// of the finalized beacon state, in which case the proof is chained up to
+	/// `finalized_block_root` itself.
+	pub(crate) fn verify_ancestry_proof(
 		block_root: H256,
 		block_slot: u64,
 		block_root_proof: &[H256],
@@ -142,6 +150,23 @@ impl<T: Config> Pallet<T> {
 
 		ensure!(block_slot < state.slot, Error::<T>::HeaderNotFinalized);
 
+		if block_root_proof.len() == config::HISTORICAL_ANCESTRY_PROOF_DEPTH {
+			ensure!(
+				is_summarized(block_slot, Self::capella_slot(), state.slot),
+				Error::<T>::HeaderNotFinalized
+			);
+			ensure!(
+				compute_historical_ancestry_root(
+					block_root,
+					block_slot,
+					Self::capella_slot(),
+					block_root_proof
+				) == Some(finalized_block_root),
+				Error::<T>::InvalidAncestryMerkleProof
+			);
+			return Ok(())
+		}
+
 		let index_in_array = block_slot % (SLOTS_PER_HISTORICAL_ROOT as u64);
 		let leaf_index = (SLOTS_PER_HISTORICAL_ROOT as u64) + index_in_array;
 
@@ -158,4 +183,9 @@ impl<T: Config> Pallet<T> {
 
 		Ok(())
 	}
+
+	/// Returns the first slot of the Capella fork, since which historical summaries are available.
+	pub(crate) fn capella_slot() -> u64 {
+		T::ForkVersions::get().capella.epoch * (config::SLOTS_PER_EPOCH as u64)
+	}
 }
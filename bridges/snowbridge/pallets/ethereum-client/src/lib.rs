@@ -15,8 +15,16 @@
 //! ## Consensus Updates
 //!
 //! * [`Call::submit`]: Submit a finalized beacon header with an optional sync committee update
+//!
+//! # Ancestry Proofs
+//!
+//! Execution headers are proven to be ancestors of an imported finalized beacon block, either
+//! using the `block_roots` vector of its beacon state (for the last `SLOTS_PER_HISTORICAL_ROOT`
+//! slots), or using its `historical_summaries` list (for older blocks since Capella). The
+//! [`api::ancestry_proof_kind`] runtime api helper tells relayers which proof is expected.
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod api;
 pub mod config;
 pub mod functions;
 pub mod impls;
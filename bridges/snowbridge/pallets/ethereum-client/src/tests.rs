@@ -970,3 +970,85 @@ fn verify_execution_proof_not_finalized() {
 		);
 	});
 }
+
+#[test]
+fn verify_historical_ancestry_proof() {
+	use snowbridge_beacon_primitives::{
+		historical::{historical_ancestry_proof, HistoricalSummary},
+		merkle_proof::{compute_merkle_root, merkleize},
+		AncestryProofKind,
+	};
+
+	let block_roots: Vec<H256> = (0..SLOTS_PER_HISTORICAL_ROOT as u64)
+		.map(|i| H256::from_low_u64_be(i + 1))
+		.collect();
+	let historical_summaries: Vec<HistoricalSummary> = (0..2)
+		.map(|i| HistoricalSummary {
+			block_summary_root: merkleize(&block_roots, 13),
+			state_summary_root: H256::repeat_byte(i),
+		})
+		.collect();
+	let summaries_roots: Vec<H256> =
+		historical_summaries.iter().map(HistoricalSummary::hash_tree_root).collect();
+	let mut summaries_length = H256::zero();
+	summaries_length.0[0] = 2;
+	let mut beacon_state_field_roots: Vec<H256> = (0..28).map(H256::from_low_u64_be).collect();
+	beacon_state_field_roots[27] =
+		compute_merkle_root(merkleize(&summaries_roots, 24), &[summaries_length], 0);
+	let finalized_header = BeaconHeader {
+		slot: 2 * SLOTS_PER_HISTORICAL_ROOT as u64 + 1,
+		proposer_index: 1,
+		parent_root: TEST_HASH.into(),
+		state_root: merkleize(&beacon_state_field_roots, 5),
+		body_root: TEST_HASH.into(),
+	};
+	let finalized_block_root = finalized_header.hash_tree_root().unwrap();
+
+	let block_slot = 100;
+	let block_root = block_roots[block_slot as usize];
+	let mut branch = historical_ancestry_proof(
+		block_slot,
+		0,
+		&block_roots,
+		&historical_summaries,
+		&beacon_state_field_roots,
+		&finalized_header,
+	)
+	.unwrap();
+
+	new_tester().execute_with(|| {
+		FinalizedBeaconState::<Test>::insert(
+			finalized_block_root,
+			CompactBeaconState { slot: finalized_header.slot, block_roots_root: H256::zero() },
+		);
+		LatestFinalizedBlockRoot::<Test>::set(finalized_block_root);
+
+		assert_eq!(
+			crate::api::ancestry_proof_kind::<Test>(block_slot),
+			Some(AncestryProofKind::HistoricalSummaries { finalized_block_root })
+		);
+		assert_eq!(
+			crate::api::ancestry_proof_kind::<Test>(finalized_header.slot - 1),
+			Some(AncestryProofKind::BlockRoots { finalized_block_root })
+		);
+		assert_eq!(crate::api::ancestry_proof_kind::<Test>(finalized_header.slot), None);
+
+		assert_ok!(EthereumBeaconClient::verify_ancestry_proof(
+			block_root,
+			block_slot,
+			&branch,
+			finalized_block_root
+		));
+
+		branch[0] = TEST_HASH.into();
+		assert_err!(
+			EthereumBeaconClient::verify_ancestry_proof(
+				block_root,
+				block_slot,
+				&branch,
+				finalized_block_root
+			),
+			Error::<Test>::InvalidAncestryMerkleProof
+		);
+	});
+}
@@ -5,7 +5,9 @@
 //! # Overview
 //!
 //! Receives messages emitted by the Gateway contract on Ethereum, whereupon they are verified,
-//! translated to XCM, and finally sent to their final destination parachain.
+//! translated to XCM, and finally sent to their final destination parachain. Besides the fixed
+//! set of Gateway commands, messages may carry an arbitrary XCM program, which is executed on
+//! behalf of the Ethereum sender.
 //!
 //! The message relayers are rewarded using native currency from the sovereign account of the
 //! destination parachain.
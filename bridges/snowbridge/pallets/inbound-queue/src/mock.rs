@@ -303,6 +303,32 @@ pub fn mock_event_log() -> Log {
     }
 }
 
+/// Event log with the given `payload` at the valid channel, ABI-encoded the same way as the
+/// Gateway contract does.
+pub fn mock_event_log_with_payload(payload: Vec<u8>) -> Log {
+	let mut data = Vec::new();
+	// nonce
+	data.extend_from_slice(&H256::from_low_u64_be(1).0);
+	// payload offset and length
+	data.extend_from_slice(&H256::from_low_u64_be(0x40).0);
+	data.extend_from_slice(&H256::from_low_u64_be(payload.len() as u64).0);
+	// payload, padded to the 32 bytes boundary
+	data.extend_from_slice(&payload);
+	data.resize(data.len() + (32 - payload.len() % 32) % 32, 0);
+
+	Log {
+		address: hex!("eda338e4dc46038493b885327842fd3e301cab39").into(),
+		topics: vec![
+			hex!("7153f9357c8ea496bba60bf82e67143e27b64462b49041f8e689e1b05728f84f").into(),
+			// channel id
+			hex!("c173fac324158e77fb5840738a1a541f633cbec8884c6a601c567d2b376a0539").into(),
+			// message id
+			hex!("5f7060e971b0dc81e63f0aa41831091847d97c1a4693ac450cc128c7214e65e0").into(),
+		],
+		data,
+	}
+}
+
 pub fn mock_event_log_invalid_channel() -> Log {
 	Log {
         address: hex!("eda338e4dc46038493b885327842fd3e301cab39").into(),
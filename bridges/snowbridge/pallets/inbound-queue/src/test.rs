@@ -5,9 +5,11 @@ use super::*;
 use frame_support::{assert_noop, assert_ok};
 use hex_literal::hex;
 use snowbridge_core::{inbound::Proof, ChannelId};
+use snowbridge_router_primitives::inbound::MessageV2;
 use sp_keyring::AccountKeyring as Keyring;
-use sp_runtime::DispatchError;
+use sp_runtime::{DispatchError, DispatchResult};
 use sp_std::convert::From;
+use xcm::{prelude::*, VersionedXcm};
 
 use crate::{Error, Event as InboundQueueEvent};
 
@@ -243,3 +245,57 @@ fn test_submit_no_funds_to_reward_relayers_and_ed_preserved() {
 		assert_eq!(amount, ExistentialDeposit::get());
 	});
 }
+
+fn submit_user_xcm(user_xcm: Vec<u8>) -> DispatchResult {
+	let relayer: AccountId = Keyring::Bob.into();
+	let payload = VersionedMessage::V2(MessageV2 {
+		chain_id: 11155111,
+		origin: H160::repeat_byte(42),
+		value: 1_000_000,
+		fee: SendTokenExecutionFee::get(),
+		xcm: user_xcm,
+	})
+	.encode();
+	let message = Message {
+		event_log: mock_event_log_with_payload(payload),
+		proof: Proof { receipt_proof: Default::default(), execution_proof: mock_execution_proof() },
+	};
+	InboundQueue::submit(RuntimeOrigin::signed(relayer), message)
+}
+
+#[test]
+fn test_submit_user_xcm_does_not_burn_fees() {
+	new_tester().execute_with(|| {
+		let user_xcm: Xcm<()> = vec![DepositAsset {
+			assets: Wild(AllCounted(1)),
+			beneficiary: Location::new(0, [AccountId32 { network: None, id: [1u8; 32] }]),
+		}]
+		.into();
+		assert_ok!(submit_user_xcm(VersionedXcm::from(user_xcm).encode()));
+
+		// execution fee on AssetHub is paid in Ether, so nothing is burned here
+		assert!(matches!(
+			last_events(1).pop(),
+			Some(RuntimeEvent::InboundQueue(InboundQueueEvent::MessageReceived {
+				nonce: 1,
+				fee_burned,
+				..
+			})) if fee_burned == 0
+		));
+	});
+}
+
+#[test]
+fn test_submit_disallowed_user_xcm_does_not_block_channel() {
+	new_tester().execute_with(|| {
+		let user_xcm: Xcm<()> =
+			vec![UnpaidExecution { weight_limit: Unlimited, check_origin: None }].into();
+		assert_ok!(submit_user_xcm(VersionedXcm::from(user_xcm).encode()));
+
+		// the message is accepted - the program is just not executed
+		assert!(matches!(
+			last_events(1).pop(),
+			Some(RuntimeEvent::InboundQueue(InboundQueueEvent::MessageReceived { nonce: 1, .. }))
+		));
+	});
+}
@@ -182,6 +182,41 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn register_sibling_token() -> Result<(), BenchmarkError> {
+		let origin_para_id = 2000;
+		let origin_location = Location::new(1, [Parachain(origin_para_id)]);
+		let origin = T::Helper::make_xcm_origin(origin_location.clone());
+		fund_sovereign_account::<T>(origin_para_id.into())?;
+
+		let token_location = origin_location
+			.clone()
+			.appended_with([PalletInstance(50), GeneralIndex(u128::MAX)])
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let asset = Box::new(VersionedLocation::from(token_location));
+		let asset_metadata = AssetMetadata {
+			name: vec![1u8; 32].try_into().unwrap(),
+			symbol: vec![1u8; 32].try_into().unwrap(),
+			decimals: 12,
+		};
+
+		// register another token, so that the message is appended to the existing page of the
+		// outbound queue
+		let other_token_location = origin_location
+			.appended_with([PalletInstance(50), GeneralIndex(0)])
+			.map_err(|_| BenchmarkError::Weightless)?;
+		SnowbridgeControl::<T>::do_register_token(
+			&other_token_location,
+			asset_metadata.clone(),
+			PaysFee::<T>::No,
+		)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, asset, asset_metadata);
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(
 		SnowbridgeControl,
 		crate::mock::new_test_ext(true),
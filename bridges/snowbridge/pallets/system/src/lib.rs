@@ -42,6 +42,11 @@
 //! prerequisite, the token should be registered first.
 //!
 //! * [`Call::register_token`]: Register a token location as a wrapped ERC20 contract on Ethereum.
+//! * [`Call::register_sibling_token`]: Register a token of a sibling parachain as a wrapped ERC20
+//!   contract on Ethereum.
+//!
+//! The `register_sibling_token` extrinsic should be called via an XCM `Transact` instruction from
+//! the sibling parachain, which owns the token.
 #![cfg_attr(not(feature = "std"), no_std)]
 #[cfg(test)]
 mod mock;
@@ -249,6 +254,7 @@ pub mod pallet {
 		InvalidTokenTransferFees,
 		InvalidPricingParameters,
 		InvalidUpgradeParameters,
+		TokenAlreadyRegistered,
 	}
 
 	/// The set of registered agents
@@ -635,6 +641,42 @@ pub mod pallet {
 				pays_fee: Pays::No,
 			})
 		}
+
+		/// Registers a Polkadot-native token of the sibling parachain as a wrapped ERC20 token on
+		/// Ethereum. The token must be located within the sibling parachain.
+		///
+		/// Fee required: Yes
+		///
+		/// - `origin`: Must be `Location` of a sibling parachain
+		/// - `location`: Location of the asset (relative to this chain)
+		/// - `metadata`: Metadata to include in the instantiated ERC20 contract on Ethereum
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::register_sibling_token())]
+		pub fn register_sibling_token(
+			origin: OriginFor<T>,
+			location: Box<VersionedLocation>,
+			metadata: AssetMetadata,
+		) -> DispatchResult {
+			let origin_location: Location = T::SiblingOrigin::ensure_origin(origin)?;
+
+			// Ensure that origin location is a sibling parachain
+			let (para_id, _) = ensure_sibling::<T>(&origin_location)?;
+
+			let location: Location =
+				(*location).try_into().map_err(|_| Error::<T>::UnsupportedLocationVersion)?;
+
+			// Siblings may only register their own tokens
+			ensure!(location.starts_with(&origin_location), Error::<T>::InvalidLocation);
+
+			let (_, token_id) = Self::foreign_token_id_of(&location)?;
+			ensure!(
+				!ForeignToNativeId::<T>::contains_key(token_id),
+				Error::<T>::TokenAlreadyRegistered
+			);
+
+			let pays_fee = PaysFee::<T>::Yes(sibling_sovereign_account::<T>(para_id));
+			Self::do_register_token(&location, metadata, pays_fee)
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -730,15 +772,7 @@ pub mod pallet {
 			metadata: AssetMetadata,
 			pays_fee: PaysFee<T>,
 		) -> Result<(), DispatchError> {
-			let ethereum_location = T::EthereumLocation::get();
-			// reanchor to Ethereum context
-			let location = location
-				.clone()
-				.reanchored(&ethereum_location, &T::UniversalLocation::get())
-				.map_err(|_| Error::<T>::LocationConversionFailed)?;
-
-			let token_id = TokenIdOf::convert_location(&location)
-				.ok_or(Error::<T>::LocationConversionFailed)?;
+			let (location, token_id) = Self::foreign_token_id_of(location)?;
 
 			if !ForeignToNativeId::<T>::contains_key(token_id) {
 				NativeToForeignId::<T>::insert(location.clone(), token_id);
@@ -760,6 +794,23 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Returns token location, reanchored to Ethereum context, and its ID on Ethereum.
+		pub(crate) fn foreign_token_id_of(
+			location: &Location,
+		) -> Result<(Location, TokenId), DispatchError> {
+			let ethereum_location = T::EthereumLocation::get();
+			// reanchor to Ethereum context
+			let location = location
+				.clone()
+				.reanchored(&ethereum_location, &T::UniversalLocation::get())
+				.map_err(|_| Error::<T>::LocationConversionFailed)?;
+
+			let token_id = TokenIdOf::convert_location(&location)
+				.ok_or(Error::<T>::LocationConversionFailed)?;
+
+			Ok((location, token_id))
+		}
 	}
 
 	impl<T: Config> StaticLookup for Pallet<T> {
@@ -744,3 +744,103 @@ fn register_ethereum_native_token_fails() {
 		);
 	});
 }
+
+#[test]
+fn register_sibling_token_succeeds() {
+	new_test_ext(true).execute_with(|| {
+		let origin_location = Location::new(1, [Parachain(1000)]);
+		let sovereign_account = sibling_sovereign_account::<Test>(1000.into());
+		let _ = Balances::mint_into(&sovereign_account, 10000);
+		let balance_before = Balances::balance(&sovereign_account);
+
+		// USDT
+		let location = Location::new(1, [Parachain(1000), PalletInstance(50), GeneralIndex(1984)]);
+		let reanchored = Location::new(
+			1,
+			[GlobalConsensus(Polkadot), Parachain(1000), PalletInstance(50), GeneralIndex(1984)],
+		);
+		let foreign: TokenId =
+			hex!("14b0579be12d7d7f9971f1d4b41f0e88384b9b74799b0150d4aa6cd01afb4444").into();
+
+		assert_ok!(EthereumSystem::register_sibling_token(
+			make_xcm_origin(origin_location),
+			Box::new(location.into()),
+			Default::default()
+		));
+
+		assert_eq!(NativeToForeignId::<Test>::get(reanchored.clone()), Some(foreign));
+		assert_eq!(ForeignToNativeId::<Test>::get(foreign), Some(reanchored.clone()));
+		System::assert_last_event(RuntimeEvent::EthereumSystem(Event::<Test>::RegisterToken {
+			location: reanchored.into(),
+			foreign_token_id: foreign,
+		}));
+
+		// sibling has paid for the registration
+		assert!(Balances::balance(&sovereign_account) < balance_before);
+	});
+}
+
+#[test]
+fn register_sibling_token_fails_for_tokens_of_other_consensus_systems() {
+	new_test_ext(true).execute_with(|| {
+		let sovereign_account = sibling_sovereign_account::<Test>(2000.into());
+		let _ = Balances::mint_into(&sovereign_account, 10000);
+
+		for location in [
+			// DOT
+			Location::parent(),
+			// USDT
+			Location::new(1, [Parachain(1000), PalletInstance(50), GeneralIndex(1984)]),
+			// KSM
+			Location::new(2, [GlobalConsensus(Kusama)]),
+		] {
+			assert_noop!(
+				EthereumSystem::register_sibling_token(
+					make_xcm_origin(Location::new(1, [Parachain(2000)])),
+					Box::new(location.into()),
+					Default::default()
+				),
+				Error::<Test>::InvalidLocation
+			);
+		}
+	});
+}
+
+#[test]
+fn register_sibling_token_fails_if_token_is_already_registered() {
+	new_test_ext(true).execute_with(|| {
+		let origin_location = Location::new(1, [Parachain(2000)]);
+		let sovereign_account = sibling_sovereign_account::<Test>(2000.into());
+		let _ = Balances::mint_into(&sovereign_account, 10000);
+
+		assert_ok!(EthereumSystem::register_sibling_token(
+			make_xcm_origin(origin_location.clone()),
+			Box::new(origin_location.clone().into()),
+			Default::default()
+		));
+		assert_noop!(
+			EthereumSystem::register_sibling_token(
+				make_xcm_origin(origin_location.clone()),
+				Box::new(origin_location.into()),
+				Default::default()
+			),
+			Error::<Test>::TokenAlreadyRegistered
+		);
+	});
+}
+
+#[test]
+fn register_sibling_token_with_signed_yields_bad_origin() {
+	new_test_ext(true).execute_with(|| {
+		let origin = RuntimeOrigin::signed([14; 32].into());
+		let location = Location::new(1, [Parachain(2000)]);
+		assert_noop!(
+			EthereumSystem::register_sibling_token(
+				origin,
+				Box::new(location.into()),
+				Default::default()
+			),
+			BadOrigin
+		);
+	});
+}
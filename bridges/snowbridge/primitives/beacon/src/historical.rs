@@ -0,0 +1,336 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2023 Snowfork <hello@snowfork.com>
+//! Proofs that a beacon block is an ancestor of a finalized beacon block, based on the
+//! `historical_summaries` of the finalized beacon state (available since Capella).
+//!
+//! The `block_roots` vector of the beacon state only covers the last `SLOTS_PER_HISTORICAL_ROOT`
+//! slots. Every `SLOTS_PER_HISTORICAL_ROOT` slots, the root of that vector is appended to the
+//! `historical_summaries` list, so any older block may still be proven using the following merkle
+//! branches, ordered from the leaf to the root:
+//!
+//! 1) block root in the `block_roots` vector, summarized by the historical summary;
+//! 2) `block_summary_root` in the `HistoricalSummary` container;
+//! 3) `HistoricalSummary` in the `historical_summaries` list;
+//! 4) `historical_summaries` in the finalized beacon state;
+//! 5) `state_root` in the finalized beacon block header.
+use crate::merkle_proof::{compute_merkle_root, generalized_index_length, subtree_index};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+
+#[cfg(feature = "std")]
+use crate::{
+	merkle_proof::{generate_merkle_branch, merkleize},
+	BeaconHeader,
+};
+
+/// The size of the `block_roots` vector of the beacon state.
+pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 8192;
+/// The depth of the `block_roots` vector of the beacon state.
+pub const BLOCK_ROOTS_VECTOR_DEPTH: usize = 13;
+/// get_generalized_index(HistoricalSummary, 'block_summary_root')
+pub const BLOCK_SUMMARY_ROOT_INDEX: usize = 2;
+/// The depth of the `historical_summaries` list, including the length mix-in
+/// (`HISTORICAL_ROOTS_LIMIT` is `2**24`).
+pub const HISTORICAL_SUMMARIES_LIST_DEPTH: usize = 25;
+/// get_generalized_index(BeaconState, 'historical_summaries')
+pub const HISTORICAL_SUMMARIES_INDEX: usize = 59;
+/// get_generalized_index(BeaconBlockHeader, 'state_root')
+pub const STATE_ROOT_INDEX: usize = 11;
+/// The number of leaves of the beacon block header tree.
+const BEACON_HEADER_DEPTH: usize = 3;
+/// The number of leaves of the beacon state tree.
+const BEACON_STATE_DEPTH: usize = generalized_index_length(HISTORICAL_SUMMARIES_INDEX);
+
+/// The size of the merkle branch of the historical ancestry proof.
+pub const HISTORICAL_ANCESTRY_PROOF_DEPTH: usize = BLOCK_ROOTS_VECTOR_DEPTH +
+	generalized_index_length(BLOCK_SUMMARY_ROOT_INDEX) +
+	HISTORICAL_SUMMARIES_LIST_DEPTH +
+	generalized_index_length(HISTORICAL_SUMMARIES_INDEX) +
+	generalized_index_length(STATE_ROOT_INDEX);
+
+/// Historical summary of the beacon state, covering `SLOTS_PER_HISTORICAL_ROOT` slots.
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct HistoricalSummary {
+	/// Root of the `block_roots` vector.
+	pub block_summary_root: H256,
+	/// Root of the `state_roots` vector.
+	pub state_summary_root: H256,
+}
+
+impl HistoricalSummary {
+	/// Compute hash tree root of the historical summary.
+	pub fn hash_tree_root(&self) -> H256 {
+		compute_merkle_root(self.block_summary_root, &[self.state_summary_root], 0)
+	}
+}
+
+/// Kind of the ancestry proof, that is required to prove that a beacon block is an ancestor of
+/// the latest finalized beacon block.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum AncestryProofKind {
+	/// Block is recent enough to be proven using the `block_roots` vector of the finalized beacon
+	/// state.
+	BlockRoots { finalized_block_root: H256 },
+	/// Block is only covered by the `historical_summaries` list of the finalized beacon state.
+	HistoricalSummaries { finalized_block_root: H256 },
+}
+
+/// Returns index of the historical summary that covers the block at `block_slot`.
+pub fn historical_summary_index(block_slot: u64, capella_slot: u64) -> Option<u64> {
+	(block_slot / SLOTS_PER_HISTORICAL_ROOT).checked_sub(capella_slot / SLOTS_PER_HISTORICAL_ROOT)
+}
+
+/// Returns true if the block at `block_slot` is covered by the `historical_summaries` of the
+/// beacon state at `state_slot`.
+pub fn is_summarized(block_slot: u64, capella_slot: u64, state_slot: u64) -> bool {
+	block_slot >= capella_slot &&
+		block_slot / SLOTS_PER_HISTORICAL_ROOT < state_slot / SLOTS_PER_HISTORICAL_ROOT
+}
+
+/// Compute root of the finalized beacon block from the `block_root` of its ancestor at
+/// `block_slot` and the historical ancestry proof.
+///
+/// Returns `None` if the proof has an unexpected size or the block is not summarized by any
+/// historical summary.
+pub fn compute_historical_ancestry_root(
+	block_root: H256,
+	block_slot: u64,
+	capella_slot: u64,
+	branch: &[H256],
+) -> Option<H256> {
+	if branch.len() != HISTORICAL_ANCESTRY_PROOF_DEPTH {
+		return None
+	}
+
+	let summary_index = historical_summary_index(block_slot, capella_slot)?;
+	if summary_index >= 1 << (HISTORICAL_SUMMARIES_LIST_DEPTH - 1) {
+		return None
+	}
+
+	let steps = [
+		((block_slot % SLOTS_PER_HISTORICAL_ROOT) as usize, BLOCK_ROOTS_VECTOR_DEPTH),
+		(
+			subtree_index(BLOCK_SUMMARY_ROOT_INDEX),
+			generalized_index_length(BLOCK_SUMMARY_ROOT_INDEX),
+		),
+		(summary_index as usize, HISTORICAL_SUMMARIES_LIST_DEPTH),
+		(
+			subtree_index(HISTORICAL_SUMMARIES_INDEX),
+			generalized_index_length(HISTORICAL_SUMMARIES_INDEX),
+		),
+		(subtree_index(STATE_ROOT_INDEX), generalized_index_length(STATE_ROOT_INDEX)),
+	];
+
+	let (mut root, mut branch) = (block_root, branch);
+	for (index, depth) in steps {
+		let (step_branch, rest) = branch.split_at(depth);
+		root = compute_merkle_root(root, step_branch, index);
+		branch = rest;
+	}
+	Some(root)
+}
+
+/// Construct the historical ancestry proof of the block at `block_slot`.
+///
+/// - `block_roots`: the `block_roots` vector, summarized by the historical summary of the block;
+/// - `historical_summaries`: the `historical_summaries` list of the finalized beacon state;
+/// - `beacon_state_field_roots`: hash tree roots of all fields of the finalized beacon state;
+/// - `finalized_header`: the finalized beacon block header.
+///
+/// Returns `None` if given data is inconsistent.
+#[cfg(feature = "std")]
+pub fn historical_ancestry_proof(
+	block_slot: u64,
+	capella_slot: u64,
+	block_roots: &[H256],
+	historical_summaries: &[HistoricalSummary],
+	beacon_state_field_roots: &[H256],
+	finalized_header: &BeaconHeader,
+) -> Option<Vec<H256>> {
+	if !is_summarized(block_slot, capella_slot, finalized_header.slot) {
+		return None
+	}
+	let summary_index = historical_summary_index(block_slot, capella_slot)? as usize;
+	let summary = historical_summaries.get(summary_index)?;
+	if merkleize(block_roots, BLOCK_ROOTS_VECTOR_DEPTH) != summary.block_summary_root {
+		return None
+	}
+
+	let summaries_roots: Vec<H256> =
+		historical_summaries.iter().map(HistoricalSummary::hash_tree_root).collect();
+	let summaries_length = {
+		let mut length = [0u8; 32];
+		length[..8].copy_from_slice(&(historical_summaries.len() as u64).to_le_bytes());
+		H256::from(length)
+	};
+	let summaries_root = compute_merkle_root(
+		merkleize(&summaries_roots, HISTORICAL_SUMMARIES_LIST_DEPTH - 1),
+		&[summaries_length],
+		0,
+	);
+	if beacon_state_field_roots.get(subtree_index(HISTORICAL_SUMMARIES_INDEX)) !=
+		Some(&summaries_root) ||
+		merkleize(beacon_state_field_roots, BEACON_STATE_DEPTH) != finalized_header.state_root
+	{
+		return None
+	}
+
+	let mut branch = Vec::with_capacity(HISTORICAL_ANCESTRY_PROOF_DEPTH);
+	branch.extend(generate_merkle_branch(
+		block_roots,
+		(block_slot % SLOTS_PER_HISTORICAL_ROOT) as usize,
+		BLOCK_ROOTS_VECTOR_DEPTH,
+	));
+	branch.push(summary.state_summary_root);
+	branch.extend(generate_merkle_branch(
+		&summaries_roots,
+		summary_index,
+		HISTORICAL_SUMMARIES_LIST_DEPTH - 1,
+	));
+	branch.push(summaries_length);
+	branch.extend(generate_merkle_branch(
+		beacon_state_field_roots,
+		subtree_index(HISTORICAL_SUMMARIES_INDEX),
+		BEACON_STATE_DEPTH,
+	));
+	branch.extend(generate_merkle_branch(
+		&beacon_header_field_roots(finalized_header),
+		subtree_index(STATE_ROOT_INDEX),
+		BEACON_HEADER_DEPTH,
+	));
+	Some(branch)
+}
+
+/// Returns hash tree roots of all fields of the beacon block header.
+#[cfg(feature = "std")]
+fn beacon_header_field_roots(header: &BeaconHeader) -> Vec<H256> {
+	let uint64_root = |value: u64| {
+		let mut root = [0u8; 32];
+		root[..8].copy_from_slice(&value.to_le_bytes());
+		H256::from(root)
+	};
+	vec![
+		uint64_root(header.slot),
+		uint64_root(header.proposer_index),
+		header.parent_root,
+		header.state_root,
+		header.body_root,
+	]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CAPELLA_SLOT: u64 = 2 * SLOTS_PER_HISTORICAL_ROOT;
+
+	fn historical_block_roots(summary_index: u64) -> Vec<H256> {
+		(0..SLOTS_PER_HISTORICAL_ROOT)
+			.map(|slot| H256::from_low_u64_be(summary_index * SLOTS_PER_HISTORICAL_ROOT + slot + 1))
+			.collect()
+	}
+
+	fn finalized_state_and_header(
+		summaries_count: u64,
+	) -> (Vec<HistoricalSummary>, Vec<H256>, BeaconHeader) {
+		let historical_summaries: Vec<HistoricalSummary> = (0..summaries_count)
+			.map(|summary_index| HistoricalSummary {
+				block_summary_root: merkleize(
+					&historical_block_roots(summary_index),
+					BLOCK_ROOTS_VECTOR_DEPTH,
+				),
+				state_summary_root: H256::repeat_byte(summary_index as u8),
+			})
+			.collect();
+		let summaries_roots: Vec<H256> =
+			historical_summaries.iter().map(HistoricalSummary::hash_tree_root).collect();
+		let mut summaries_length = [0u8; 32];
+		summaries_length[..8].copy_from_slice(&summaries_count.to_le_bytes());
+
+		let mut beacon_state_field_roots: Vec<H256> =
+			(0..28u64).map(H256::from_low_u64_be).collect();
+		beacon_state_field_roots[subtree_index(HISTORICAL_SUMMARIES_INDEX)] = compute_merkle_root(
+			merkleize(&summaries_roots, HISTORICAL_SUMMARIES_LIST_DEPTH - 1),
+			&[summaries_length.into()],
+			0,
+		);
+
+		let finalized_header = BeaconHeader {
+			slot: CAPELLA_SLOT + summaries_count * SLOTS_PER_HISTORICAL_ROOT + 10,
+			proposer_index: 42,
+			parent_root: H256::repeat_byte(1),
+			state_root: merkleize(&beacon_state_field_roots, BEACON_STATE_DEPTH),
+			body_root: H256::repeat_byte(2),
+		};
+
+		(historical_summaries, beacon_state_field_roots, finalized_header)
+	}
+
+	#[test]
+	fn historical_ancestry_proof_is_verified() {
+		let (historical_summaries, beacon_state_field_roots, finalized_header) =
+			finalized_state_and_header(3);
+		let finalized_block_root = finalized_header.hash_tree_root().unwrap();
+
+		let summary_index = 1;
+		let block_roots = historical_block_roots(summary_index);
+		let index_in_summary = 100;
+		let block_slot =
+			CAPELLA_SLOT + summary_index * SLOTS_PER_HISTORICAL_ROOT + index_in_summary;
+		let block_root = block_roots[index_in_summary as usize];
+
+		let branch = historical_ancestry_proof(
+			block_slot,
+			CAPELLA_SLOT,
+			&block_roots,
+			&historical_summaries,
+			&beacon_state_field_roots,
+			&finalized_header,
+		)
+		.unwrap();
+		assert_eq!(branch.len(), HISTORICAL_ANCESTRY_PROOF_DEPTH);
+
+		assert_eq!(
+			compute_historical_ancestry_root(block_root, block_slot, CAPELLA_SLOT, &branch),
+			Some(finalized_block_root),
+		);
+		assert_ne!(
+			compute_historical_ancestry_root(block_root, block_slot + 1, CAPELLA_SLOT, &branch),
+			Some(finalized_block_root),
+		);
+		assert_ne!(
+			compute_historical_ancestry_root(
+				H256::repeat_byte(42),
+				block_slot,
+				CAPELLA_SLOT,
+				&branch
+			),
+			Some(finalized_block_root),
+		);
+	}
+
+	#[test]
+	fn historical_ancestry_proof_is_not_constructed_for_unsummarized_blocks() {
+		let (historical_summaries, beacon_state_field_roots, finalized_header) =
+			finalized_state_and_header(3);
+
+		// block before Capella
+		assert!(!is_summarized(CAPELLA_SLOT - 1, CAPELLA_SLOT, finalized_header.slot));
+		// block within the latest (not yet summarized) period
+		let block_slot = CAPELLA_SLOT + 3 * SLOTS_PER_HISTORICAL_ROOT;
+		assert!(!is_summarized(block_slot, CAPELLA_SLOT, finalized_header.slot));
+		assert_eq!(
+			historical_ancestry_proof(
+				block_slot,
+				CAPELLA_SLOT,
+				&historical_block_roots(3),
+				&historical_summaries,
+				&beacon_state_field_roots,
+				&finalized_header,
+			),
+			None,
+		);
+	}
+}
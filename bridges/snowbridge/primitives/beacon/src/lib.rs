@@ -5,6 +5,7 @@
 pub mod bits;
 pub mod bls;
 pub mod config;
+pub mod historical;
 pub mod merkle_proof;
 pub mod receipt;
 pub mod ssz;
@@ -28,5 +29,6 @@ pub use bls::{
 	prepare_aggregate_signature, prepare_g1_pubkeys, AggregatePublicKey, AggregateSignature,
 	BlsError, PublicKeyPrepared, SignaturePrepared,
 };
+pub use historical::{AncestryProofKind, HistoricalSummary};
 pub use merkle_proof::verify_merkle_branch;
 pub use receipt::verify_receipt_proof;
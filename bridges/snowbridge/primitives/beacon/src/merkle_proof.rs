@@ -20,23 +20,63 @@ pub fn verify_merkle_branch(
 	root == compute_merkle_root(leaf, branch, index)
 }
 
-fn compute_merkle_root(leaf: H256, proof: &[H256], index: usize) -> H256 {
-	let mut value: [u8; 32] = leaf.into();
+/// Compute the merkle root from the `leaf` at the given `index` of the subtree and its `proof`.
+pub fn compute_merkle_root(leaf: H256, proof: &[H256], index: usize) -> H256 {
+	let mut value: H256 = leaf;
 	for (i, node) in proof.iter().enumerate() {
-		let mut data = [0u8; 64];
 		if generalized_index_bit(index, i) {
 			// right node
-			data[0..32].copy_from_slice(node.as_bytes());
-			data[32..64].copy_from_slice(&value);
-			value = sha2_256(&data);
+			value = hash_pair(*node, value);
 		} else {
 			// left node
-			data[0..32].copy_from_slice(&value);
-			data[32..64].copy_from_slice(node.as_bytes());
-			value = sha2_256(&data);
+			value = hash_pair(value, *node);
 		}
 	}
-	value.into()
+	value
+}
+
+/// Generate the merkle proof of the leaf at the given `index` of the tree of given `depth`. Missing
+/// leaves are considered to be zero.
+#[cfg(feature = "std")]
+pub fn generate_merkle_branch(leaves: &[H256], index: usize, depth: usize) -> Vec<H256> {
+	let mut branch = Vec::with_capacity(depth);
+	let mut layer = leaves.to_vec();
+	let mut index = index;
+	let mut zero_hash = H256::zero();
+	for _ in 0..depth {
+		branch.push(layer.get(index ^ 1).copied().unwrap_or(zero_hash));
+		layer = next_merkle_layer(&layer, zero_hash);
+		zero_hash = hash_pair(zero_hash, zero_hash);
+		index /= 2;
+	}
+	branch
+}
+
+/// Compute the root of the merkle tree of given `depth`. Missing leaves are considered to be zero.
+#[cfg(feature = "std")]
+pub fn merkleize(leaves: &[H256], depth: usize) -> H256 {
+	let mut layer = leaves.to_vec();
+	let mut zero_hash = H256::zero();
+	for _ in 0..depth {
+		layer = next_merkle_layer(&layer, zero_hash);
+		zero_hash = hash_pair(zero_hash, zero_hash);
+	}
+	layer.first().copied().unwrap_or(zero_hash)
+}
+
+#[cfg(feature = "std")]
+fn next_merkle_layer(layer: &[H256], zero_hash: H256) -> Vec<H256> {
+	layer
+		.chunks(2)
+		.map(|pair| hash_pair(pair[0], pair.get(1).copied().unwrap_or(zero_hash)))
+		.collect()
+}
+
+fn hash_pair(left: H256, right: H256) -> H256 {
+	let mut data = [0u8; 64];
+	data[0..32].copy_from_slice(left.as_bytes());
+	data[32..64].copy_from_slice(right.as_bytes());
+	sha2_256(&data).into()
 }
 
 /// Spec: <https://github.com/ethereum/consensus-specs/blob/fe9c1a8cbf0c2da8a4f349efdcd77dd7ac8445c4/ssz/merkle-proofs.md#get_generalized_index_bit>
@@ -5,26 +5,37 @@
 #[cfg(test)]
 mod tests;
 
-use codec::{Decode, Encode};
+use codec::{Decode, DecodeLimit, Encode};
 use core::marker::PhantomData;
-use frame_support::{traits::tokens::Balance as BalanceT, PalletError};
+use frame_support::{
+	traits::{tokens::Balance as BalanceT, ProcessMessageError},
+	weights::Weight,
+	PalletError,
+};
 use scale_info::TypeInfo;
 use snowbridge_core::TokenId;
 use sp_core::{Get, RuntimeDebug, H160, H256};
 use sp_io::hashing::blake2_256;
 use sp_runtime::{traits::MaybeEquivalence, MultiAddress};
 use sp_std::prelude::*;
-use xcm::prelude::{Junction::AccountKey20, *};
-use xcm_executor::traits::ConvertLocation;
+use xcm::{
+	prelude::{Junction::AccountKey20, *},
+	MAX_XCM_DECODE_DEPTH,
+};
+use xcm_executor::traits::{ConvertLocation, Properties, ShouldExecute};
 
 const MINIMUM_DEPOSIT: u128 = 1;
 
-/// Messages from Ethereum are versioned. This is because in future,
-/// we may want to evolve the protocol so that the ethereum side sends XCM messages directly.
-/// Instead having BridgeHub transcode the messages into XCM.
+/// Maximal number of assets that are deposited to the Ethereum sender on AssetHub after
+/// the user-provided XCM program of the [`MessageV2`] has been executed.
+const MAX_ASSETS_TO_REFUND: u32 = 8;
+
+/// Messages from Ethereum are versioned. V1 messages are transcoded into XCM by BridgeHub,
+/// while V2 messages carry an XCM program, provided by the user on the Ethereum side.
 #[derive(Clone, Encode, Decode, RuntimeDebug)]
 pub enum VersionedMessage {
 	V1(MessageV1),
+	V2(MessageV2),
 }
 
 /// For V1, the ethereum side sends messages which are transcoded into XCM. These messages are
@@ -37,6 +48,22 @@ pub struct MessageV1 {
 	pub command: Command,
 }
 
+/// For V2, the ethereum side sends an arbitrary XCM program which is executed on AssetHub on
+/// behalf of the Ethereum account that has submitted the message to the Gateway.
+#[derive(Clone, Encode, Decode, RuntimeDebug)]
+pub struct MessageV2 {
+	/// EIP-155 chain id of the origin Ethereum network
+	pub chain_id: u64,
+	/// The Ethereum account that has submitted the message to the Gateway
+	pub origin: H160,
+	/// Ether (in wei), locked in the Gateway and put into the holding register on AssetHub
+	pub value: u128,
+	/// Ether (in wei), locked in the Gateway to pay for the XCM execution on AssetHub
+	pub fee: u128,
+	/// SCALE-encoded `VersionedXcm<()>` to execute on AssetHub
+	pub xcm: Vec<u8>,
+}
+
 #[derive(Clone, Encode, Decode, RuntimeDebug)]
 pub enum Command {
 	/// Register a wrapped token on the AssetHub `ForeignAssets` pallet
@@ -204,6 +231,8 @@ where
 				amount,
 				fee,
 			),
+			V2(MessageV2 { chain_id, origin, value, fee, xcm }) =>
+				Ok(Self::convert_user_xcm(message_id, chain_id, origin, value, fee, xcm)),
 		}
 	}
 }
@@ -451,6 +480,170 @@ where
 		// teleport fees)
 		Ok((instructions.into(), asset_hub_fee.into()))
 	}
+
+	/// Constructs an XCM message destined for AssetHub that puts the Ether locked in the Gateway
+	/// into the holding register, buys execution with the `asset_hub_fee` part of it and then
+	/// executes the user-provided program with the origin of the Ethereum account that has sent
+	/// the message.
+	///
+	/// The execution fee is paid in bridged Ether, which AssetHub swaps to its native asset using
+	/// the Ether pool of the asset conversion pallet. The swap is done by the trader of
+	/// `BuyExecution`, because the barrier of AssetHub expects execution to be bought right after
+	/// the assets are deposited. Nothing is teleported, so no fee is burned on this chain.
+	///
+	/// If the user-provided program can't be decoded or isn't allowed by
+	/// [`is_allowed_xcm_from_ethereum`], it is not executed and the Ether is deposited to the
+	/// sender account on AssetHub. We can't reject such messages, because that would block the
+	/// whole channel.
+	fn convert_user_xcm(
+		message_id: H256,
+		chain_id: u64,
+		origin: H160,
+		value: u128,
+		asset_hub_fee: u128,
+		xcm: Vec<u8>,
+	) -> (Xcm<()>, Balance) {
+		let network = Ethereum { chain_id };
+		let ether_location = Location::new(2, [GlobalConsensus(network)]);
+		let ether: Asset = (ether_location.clone(), value.saturating_add(asset_hub_fee)).into();
+		let asset_hub_fee_asset: Asset = (ether_location, asset_hub_fee).into();
+		let sender = AccountKey20 { network: None, key: origin.into() };
+		let sender_location = Location::new(2, [GlobalConsensus(network), sender]);
+		let inbound_queue_pallet_index = InboundQueuePalletInstance::get();
+
+		let mut instructions = vec![
+			// Only our inbound-queue pallet is allowed to invoke `UniversalOrigin`.
+			DescendOrigin(PalletInstance(inbound_queue_pallet_index).into()),
+			// Change origin to the bridge.
+			UniversalOrigin(GlobalConsensus(network)),
+			// The Ether locked in the Gateway, including the execution fee.
+			ReserveAssetDeposited(ether.into()),
+			// Pay for execution with Ether, which is swapped to the native asset of AssetHub.
+			BuyExecution { fees: asset_hub_fee_asset, weight_limit: Unlimited },
+			// Deposit leftover fees, the Ether and any other assets to the sender, regardless of
+			// the user-provided program success or not.
+			SetAppendix(Xcm(vec![
+				RefundSurplus,
+				DepositAsset {
+					assets: Wild(AllCounted(MAX_ASSETS_TO_REFUND)),
+					beneficiary: sender_location,
+				},
+			])),
+			// Change origin to the Ethereum sender.
+			DescendOrigin(sender.into()),
+		];
+
+		match Self::decode_user_xcm(&xcm) {
+			Some(user_xcm) => instructions.extend(user_xcm.0),
+			None => log::warn!(
+				target: "snowbridge-router-primitives",
+				"Ignoring disallowed or undecodable XCM from Ethereum account {:?}, message {:?}",
+				origin,
+				message_id,
+			),
+		}
+
+		// Forward message id to Asset Hub.
+		instructions.push(SetTopic(message_id.into()));
+
+		// The fees are paid in Ether on AH, there is nothing to burn on this chain.
+		(instructions.into(), 0u128.into())
+	}
+
+	/// Decode the user-provided XCM program and ensure that it is allowed to execute it.
+	fn decode_user_xcm(encoded: &[u8]) -> Option<Xcm<()>> {
+		let versioned = VersionedXcm::<()>::decode_all_with_depth_limit(
+			MAX_XCM_DECODE_DEPTH,
+			&mut &encoded[..],
+		)
+		.ok()?;
+		let xcm = Xcm::<()>::try_from(versioned).ok()?;
+		is_allowed_xcm_from_ethereum(&xcm).then_some(xcm)
+	}
+}
+
+/// Returns true if the XCM program, provided by the Ethereum account, may be executed on AssetHub.
+///
+/// The program is executed after fees have been paid and after the origin has been changed to
+/// the Ethereum account. So it must not alter its origin, mint assets out of thin air, export
+/// messages to other consensus systems or ask for unpaid execution. `Transact` is only allowed
+/// with the `SovereignAccount` origin kind.
+pub fn is_allowed_xcm_from_ethereum<Call>(xcm: &Xcm<Call>) -> bool {
+	is_allowed_instructions_from_ethereum(&xcm.0)
+}
+
+fn is_allowed_instructions_from_ethereum<Call>(instructions: &[Instruction<Call>]) -> bool {
+	instructions.iter().all(|instruction| match instruction {
+		WithdrawAsset(..) |
+		TransferAsset { .. } |
+		DepositAsset { .. } |
+		ExchangeAsset { .. } |
+		BuyExecution { .. } |
+		PayFees { .. } |
+		RefundSurplus |
+		ClearOrigin |
+		ClearError |
+		ClearTransactStatus |
+		BurnAsset(..) |
+		ExpectAsset(..) |
+		ExpectOrigin(..) |
+		ExpectError(..) |
+		ExpectTransactStatus(..) |
+		Trap(..) => true,
+		TransferReserveAsset { xcm, .. } |
+		DepositReserveAsset { xcm, .. } |
+		InitiateReserveWithdraw { xcm, .. } |
+		InitiateTeleport { xcm, .. } => is_allowed_xcm_from_ethereum(xcm),
+		InitiateTransfer { remote_xcm, .. } => is_allowed_xcm_from_ethereum(remote_xcm),
+		Transact { origin_kind, .. } => *origin_kind == OriginKind::SovereignAccount,
+		SetErrorHandler(xcm) | SetAppendix(xcm) => is_allowed_xcm_from_ethereum(xcm),
+		_ => false,
+	})
+}
+
+/// Barrier that denies execution of XCM programs from Ethereum accounts, which are not allowed by
+/// [`is_allowed_xcm_from_ethereum`].
+///
+/// Messages from Ethereum are checked by the BridgeHub before they're sent to AssetHub. This
+/// barrier repeats the check at AssetHub, so that a compromised or misconfigured BridgeHub can't
+/// make AssetHub execute disallowed instructions on behalf of the Ethereum account. The
+/// program is everything that follows the `DescendOrigin` to the Ethereum account.
+pub struct DenyDisallowedXcmFromEthereum;
+impl ShouldExecute for DenyDisallowedXcmFromEthereum {
+	fn should_execute<RuntimeCall>(
+		_origin: &Location,
+		message: &mut [Instruction<RuntimeCall>],
+		_max_weight: Weight,
+		_properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		let Some(universal_origin_index) = message.iter().position(|instruction| {
+			matches!(instruction, UniversalOrigin(GlobalConsensus(Ethereum { .. })))
+		}) else {
+			return Ok(())
+		};
+		let Some(sender_origin_index) =
+			message[universal_origin_index..].iter().position(|instruction| {
+				matches!(
+					instruction,
+					DescendOrigin(interior) if matches!(interior.first(), Some(AccountKey20 { .. }))
+				)
+			})
+		else {
+			return Ok(())
+		};
+
+		let program = &message[universal_origin_index + sender_origin_index + 1..];
+		if !is_allowed_instructions_from_ethereum(program) {
+			log::trace!(
+				target: "xcm::barriers",
+				"DenyDisallowedXcmFromEthereum: disallowed program {:?}",
+				program,
+			);
+			return Err(ProcessMessageError::Unsupported)
+		}
+
+		Ok(())
+	}
 }
 
 pub struct EthereumLocationsConverterFor<AccountId>(PhantomData<AccountId>);
@@ -1,9 +1,14 @@
 use super::EthereumLocationsConverterFor;
-use crate::inbound::CallIndex;
-use frame_support::{assert_ok, parameter_types};
+use crate::inbound::{
+	is_allowed_xcm_from_ethereum, CallIndex, ConvertMessage, DenyDisallowedXcmFromEthereum,
+	MessageToXcm, MessageV2, VersionedMessage,
+};
+use codec::Encode;
+use frame_support::{assert_ok, parameter_types, traits::ProcessMessageError, weights::Weight};
 use hex_literal::hex;
-use xcm::prelude::*;
-use xcm_executor::traits::ConvertLocation;
+use sp_core::{H160, H256};
+use xcm::{prelude::*, VersionedXcm};
+use xcm_executor::traits::{ConvertLocation, Properties, ShouldExecute};
 
 const NETWORK: NetworkId = Ethereum { chain_id: 11155111 };
 
@@ -14,6 +19,39 @@ parameter_types! {
 	pub const CreateAssetExecutionFee: u128 = 123;
 	pub const CreateAssetDeposit: u128 = 891;
 	pub const SendTokenExecutionFee: u128 = 592;
+
+	pub const InboundQueuePalletInstance: u8 = 80;
+	pub UniversalLocation: InteriorLocation =
+		[GlobalConsensus(ByGenesis(WESTEND_GENESIS_HASH)), Parachain(1002)].into();
+	pub AssetHubFromEthereum: Location =
+		Location::new(1, [GlobalConsensus(ByGenesis(WESTEND_GENESIS_HASH)), Parachain(1000)]);
+}
+
+type TestMessageConverter = MessageToXcm<
+	CreateAssetCall,
+	CreateAssetDeposit,
+	InboundQueuePalletInstance,
+	[u8; 32],
+	u128,
+	(),
+	UniversalLocation,
+	AssetHubFromEthereum,
+>;
+
+const SENDER: [u8; 20] = [42u8; 20];
+
+fn convert_user_xcm(xcm: Vec<u8>) -> (Xcm<()>, u128) {
+	TestMessageConverter::convert(
+		H256::repeat_byte(1),
+		VersionedMessage::V2(MessageV2 {
+			chain_id: 11155111,
+			origin: H160::from(SENDER),
+			value: 1_000_000,
+			fee: SendTokenExecutionFee::get(),
+			xcm,
+		}),
+	)
+	.unwrap()
 }
 
 #[test]
@@ -81,3 +119,163 @@ fn test_reanchor_all_assets() {
 		assert_eq!(reanchored_asset_with_ethereum_context, asset.clone());
 	}
 }
+
+#[test]
+fn test_user_xcm_is_executed_on_behalf_of_sender() {
+	let beneficiary = Location::new(0, [AccountId32 { network: None, id: [1u8; 32] }]);
+	let user_xcm: Xcm<()> = vec![
+		WithdrawAsset((Location::parent(), 100u128).into()),
+		DepositAsset { assets: Wild(AllCounted(1)), beneficiary },
+	]
+	.into();
+
+	let (xcm, fee) = convert_user_xcm(VersionedXcm::from(user_xcm.clone()).encode());
+
+	// execution fee is paid in Ether on AssetHub, nothing is burned on the bridge hub
+	assert_eq!(fee, 0);
+	let ether_location = Location::new(2, [GlobalConsensus(NETWORK)]);
+	let ether: Asset =
+		(ether_location.clone(), 1_000_000u128 + SendTokenExecutionFee::get()).into();
+	let fee_asset: Asset = (ether_location, SendTokenExecutionFee::get()).into();
+	assert_eq!(xcm.0[1], UniversalOrigin(GlobalConsensus(NETWORK)));
+	assert_eq!(xcm.0[2], ReserveAssetDeposited(ether.into()));
+	assert_eq!(xcm.0[3], BuyExecution { fees: fee_asset, weight_limit: Unlimited });
+	// the origin is switched to the sender before the user program
+	assert_eq!(xcm.0[5], DescendOrigin(AccountKey20 { network: None, key: SENDER }.into()));
+	// user program follows and the message id is forwarded
+	assert_eq!(&xcm.0[6..8], &user_xcm.0[..]);
+	assert_eq!(xcm.0.last(), Some(&SetTopic(H256::repeat_byte(1).into())));
+}
+
+#[test]
+fn test_disallowed_user_xcm_is_not_executed() {
+	let disallowed_programs: Vec<Xcm<()>> = vec![
+		vec![UniversalOrigin(GlobalConsensus(Polkadot))].into(),
+		vec![DescendOrigin(Parachain(1000).into())].into(),
+		vec![AliasOrigin(Location::parent())].into(),
+		vec![ReceiveTeleportedAsset((Location::parent(), 100u128).into())].into(),
+		vec![ExportMessage { network: Kusama, destination: Here, xcm: Xcm::new() }].into(),
+		vec![UnpaidExecution { weight_limit: Unlimited, check_origin: None }].into(),
+		vec![Transact { origin_kind: OriginKind::Superuser, call: vec![].into() }].into(),
+		vec![SetAppendix(vec![DescendOrigin(Parachain(1000).into())].into())].into(),
+		vec![DepositReserveAsset {
+			assets: Wild(All),
+			dest: Location::new(1, [Parachain(2000)]),
+			xcm: vec![UnpaidExecution { weight_limit: Unlimited, check_origin: None }].into(),
+		}]
+		.into(),
+		vec![InitiateTeleport {
+			assets: Wild(All),
+			dest: Location::parent(),
+			xcm: vec![DescendOrigin(Parachain(1000).into())].into(),
+		}]
+		.into(),
+		vec![InitiateReserveWithdraw {
+			assets: Wild(All),
+			reserve: Location::parent(),
+			xcm: vec![Transact { origin_kind: OriginKind::Superuser, call: vec![].into() }].into(),
+		}]
+		.into(),
+		vec![TransferReserveAsset {
+			assets: (Location::parent(), 100u128).into(),
+			dest: Location::new(1, [Parachain(2000)]),
+			xcm: vec![AliasOrigin(Location::parent())].into(),
+		}]
+		.into(),
+		vec![InitiateTransfer {
+			destination: Location::new(1, [Parachain(2000)]),
+			remote_fees: None,
+			preserve_origin: true,
+			assets: vec![],
+			remote_xcm: vec![UniversalOrigin(GlobalConsensus(Polkadot))].into(),
+		}]
+		.into(),
+	];
+
+	for program in disallowed_programs {
+		assert!(!is_allowed_xcm_from_ethereum(&program));
+
+		// only fee payment and refund instructions are left
+		let (xcm, _) = convert_user_xcm(VersionedXcm::from(program).encode());
+		assert_eq!(xcm.0.len(), 7);
+	}
+
+	// undecodable program is not executed either
+	let (xcm, _) = convert_user_xcm(vec![42u8; 16]);
+	assert_eq!(xcm.0.len(), 7);
+}
+
+#[test]
+fn test_allowed_remote_xcm_from_ethereum_is_allowed() {
+	let beneficiary = Location::new(0, [AccountId32 { network: None, id: [1u8; 32] }]);
+	let program: Xcm<()> = vec![
+		WithdrawAsset((Location::parent(), 100u128).into()),
+		DepositReserveAsset {
+			assets: Wild(All),
+			dest: Location::new(1, [Parachain(2000)]),
+			xcm: vec![
+				BuyExecution { fees: (Location::parent(), 10u128).into(), weight_limit: Unlimited },
+				DepositAsset { assets: Wild(All), beneficiary },
+			]
+			.into(),
+		},
+	]
+	.into();
+	assert!(is_allowed_xcm_from_ethereum(&program));
+}
+
+#[test]
+fn test_barrier_denies_disallowed_xcm_from_ethereum() {
+	let bridge_hub = Location::new(1, [Parachain(1002)]);
+	let check = |user_xcm: Xcm<()>| {
+		let (mut xcm, _) = TestMessageConverter::convert(
+			H256::repeat_byte(1),
+			VersionedMessage::V2(MessageV2 {
+				chain_id: 11155111,
+				origin: H160::from(SENDER),
+				value: 1_000_000,
+				fee: SendTokenExecutionFee::get(),
+				xcm: vec![],
+			}),
+		)
+		.unwrap();
+		// emulate the bridge hub that doesn't check the user program
+		let topic = xcm.0.pop().unwrap();
+		xcm.0.extend(user_xcm.0);
+		xcm.0.push(topic);
+
+		DenyDisallowedXcmFromEthereum::should_execute(
+			&bridge_hub,
+			xcm.inner_mut(),
+			Weight::MAX,
+			&mut Properties { weight_credit: Weight::zero(), message_id: None },
+		)
+	};
+
+	assert_ok!(check(vec![WithdrawAsset((Location::parent(), 100u128).into())].into()));
+	assert_eq!(
+		check(vec![UniversalOrigin(GlobalConsensus(Polkadot))].into()),
+		Err(ProcessMessageError::Unsupported),
+	);
+	assert_eq!(
+		check(
+			vec![InitiateTeleport {
+				assets: Wild(All),
+				dest: Location::parent(),
+				xcm: vec![UnpaidExecution { weight_limit: Unlimited, check_origin: None }].into(),
+			}]
+			.into()
+		),
+		Err(ProcessMessageError::Unsupported),
+	);
+}
+
+#[test]
+fn test_transact_from_ethereum_sovereign_account_is_allowed() {
+	let program: Xcm<()> = vec![
+		Transact { origin_kind: OriginKind::SovereignAccount, call: vec![0, 1].into() },
+		SetErrorHandler(vec![RefundSurplus].into()),
+	]
+	.into();
+	assert!(is_allowed_xcm_from_ethereum(&program));
+}
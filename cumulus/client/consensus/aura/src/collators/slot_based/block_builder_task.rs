@@ -14,14 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
-use codec::{Codec, Encode};
+use codec::{Codec, Decode, Encode};
 
 use cumulus_client_collator::service::ServiceInterface as CollatorServiceInterface;
 use cumulus_client_consensus_common::{self as consensus_common, ParachainBlockImportMarker};
 use cumulus_client_consensus_proposer::ProposerInterface;
 use cumulus_primitives_aura::AuraUnincludedSegmentApi;
 use cumulus_primitives_core::{GetCoreSelectorApi, PersistedValidationData};
-use cumulus_relay_chain_interface::RelayChainInterface;
+use cumulus_relay_chain_interface::{call_runtime_api, RelayChainInterface};
 
 use polkadot_primitives::{
 	vstaging::{ClaimQueueOffset, CoreSelector, DEFAULT_CLAIM_QUEUE_OFFSET},
@@ -36,6 +36,7 @@ use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_application_crypto::AppPublic;
 use sp_blockchain::HeaderBackend;
 use sp_consensus_aura::{AuraApi, Slot};
+use sp_consensus_babe::BabeConfiguration;
 use sp_core::{crypto::Pair, U256};
 use sp_inherents::CreateInherentDataProviders;
 use sp_keystore::KeystorePtr;
@@ -43,7 +44,11 @@ use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Member, One};
 use sp_timestamp::Timestamp;
 use std::{collections::BTreeSet, sync::Arc, time::Duration};
 
-use super::CollatorMessage;
+use super::{
+	metrics::Metrics,
+	pacing::{BlockProductionPacer, BuiltBlocks},
+	CollatorMessage,
+};
 use crate::{
 	collator::{self as collator_util},
 	collators::{check_validation_code_or_log, cores_scheduled_for_para},
@@ -84,8 +89,11 @@ pub struct BuilderTaskParams<
 	pub proposer: Proposer,
 	/// The generic collator service used to plug into this consensus engine.
 	pub collator_service: CS,
-	/// The amount of time to spend authoring each block.
+	/// The maximum amount of time to spend authoring each block.
 	pub authoring_duration: Duration,
+	/// Whether to scale the budget of each block by the number of scheduled cores and the
+	/// observed backing latency.
+	pub pace_block_production: bool,
 	/// Channel to send built blocks to the collation task.
 	pub collator_sender: sc_utils::mpsc::TracingUnboundedSender<CollatorMessage<Block>>,
 	/// Drift every slot by this duration.
@@ -95,6 +103,8 @@ pub struct BuilderTaskParams<
 	/// likelihood of encountering unfavorable notification arrival timings (i.e. we don't want to
 	/// wait for relay chain notifications because we woke up too early).
 	pub slot_drift: Duration,
+	/// Metrics of the built blocks, if enabled.
+	pub metrics: Option<Metrics>,
 }
 
 #[derive(Debug)]
@@ -198,8 +208,10 @@ where
 			collator_sender,
 			code_hash_provider,
 			authoring_duration,
+			pace_block_production,
 			para_backend,
 			slot_drift,
+			metrics,
 		} = params;
 
 		let slot_timer = SlotTimer::<_, _, P>::new_with_drift(para_client.clone(), slot_drift);
@@ -219,6 +231,8 @@ where
 		};
 
 		let mut relay_chain_fetcher = RelayChainCachingFetcher::new(relay_client.clone(), para_id);
+		let mut pacer = BlockProductionPacer::new(authoring_duration, pace_block_production);
+		let mut built_blocks = BuiltBlocks::<Block>::new(metrics);
 
 		loop {
 			// We wait here until the next slot arrives.
@@ -257,6 +271,7 @@ where
 			let Ok(RelayChainData {
 				relay_parent_header,
 				max_pov_size,
+				relay_slot_duration,
 				scheduled_cores,
 				claimed_cores,
 			}) = relay_chain_fetcher
@@ -266,6 +281,20 @@ where
 				continue;
 			};
 
+			if !built_blocks.is_empty() {
+				follow_built_blocks(
+					&relay_client,
+					&*para_client,
+					para_id,
+					relay_parent,
+					*relay_slot_duration,
+					included_block,
+					&mut built_blocks,
+					&mut pacer,
+				)
+				.await;
+			}
+
 			if scheduled_cores.is_empty() {
 				tracing::debug!(target: LOG_TARGET, "Parachain not scheduled, skipping slot.");
 				continue;
@@ -376,15 +405,19 @@ where
 			)
 			.await;
 
-			let allowed_pov_size = if cfg!(feature = "full-pov-size") {
-				validation_data.max_pov_size
-			} else {
-				// Set the block limit to 50% of the maximum PoV size.
-				//
-				// TODO: If we got benchmarking that includes the proof size,
-				// we should be able to use the maximum pov size.
-				validation_data.max_pov_size / 2
-			} as usize;
+			let budget = pacer.budget(
+				*relay_slot_duration,
+				scheduled_cores.len(),
+				validation_data.max_pov_size,
+			);
+			built_blocks.note_budget(budget);
+			tracing::trace!(
+				target: crate::LOG_TARGET,
+				?core_index,
+				authoring_duration = ?budget.authoring_duration,
+				pov_size = budget.pov_size,
+				"Block budget."
+			);
 
 			let Ok(Some(candidate)) = collator
 				.build_block_and_import(
@@ -392,8 +425,8 @@ where
 					&slot_claim,
 					None,
 					(parachain_inherent_data, other_inherent_data),
-					authoring_duration,
-					allowed_pov_size,
+					budget.authoring_duration,
+					budget.pov_size,
 				)
 				.await
 			else {
@@ -402,6 +435,13 @@ where
 			};
 
 			let new_block_hash = candidate.block.header().hash();
+			built_blocks.note_built(
+				new_block_hash,
+				*candidate.block.header().number(),
+				*core_index,
+				candidate.block.encoded_size() + candidate.proof.encoded_size(),
+				budget,
+			);
 
 			// Announce the newly built block to our peers.
 			collator.collator_service().announce_block(new_block_hash, None);
@@ -420,6 +460,52 @@ where
 	}
 }
 
+/// Update the blocks we built with their inclusion and backing status as of `relay_parent`, feeding
+/// the time it took to back them into the pacer. Blocks not backed in time are given up on and
+/// reported to the pacer as well.
+async fn follow_built_blocks<Block, Client, RelayClient>(
+	relay_client: &RelayClient,
+	para_client: &Client,
+	para_id: ParaId,
+	relay_parent: RelayHash,
+	relay_slot_duration: Duration,
+	included_block: Block::Hash,
+	built_blocks: &mut BuiltBlocks<Block>,
+	pacer: &mut BlockProductionPacer,
+) where
+	Block: BlockT,
+	Client: HeaderBackend<Block>,
+	RelayClient: RelayChainInterface,
+{
+	if let Ok(Some(number)) = para_client.number(included_block) {
+		built_blocks.note_included(&included_block, number);
+	}
+
+	let candidates = match relay_client.candidates_pending_availability(relay_parent, para_id).await
+	{
+		Ok(candidates) => candidates,
+		Err(err) => {
+			tracing::debug!(target: crate::LOG_TARGET, ?err, "Unable to fetch candidates pending availability.");
+			Vec::new()
+		},
+	};
+
+	for candidate in candidates {
+		let Ok(header) = Block::Header::decode(&mut &candidate.commitments.head_data.0[..]) else {
+			continue
+		};
+
+		if let Some(latency) = built_blocks.note_backed(&header.hash()) {
+			pacer.note_backing_latency(latency, relay_slot_duration);
+		}
+	}
+
+	let timeout = BlockProductionPacer::unbacked_timeout(relay_slot_duration);
+	for _ in 0..built_blocks.note_unbacked_timeouts(timeout) {
+		pacer.note_unbacked();
+	}
+}
+
 /// Contains relay chain data necessary for parachain block building.
 #[derive(Clone)]
 struct RelayChainData {
@@ -429,6 +515,8 @@ struct RelayChainData {
 	pub scheduled_cores: Vec<CoreIndex>,
 	/// Maximum configured PoV size on the relay chain.
 	pub max_pov_size: u32,
+	/// The duration of a relay chain slot, as configured in the relay chain runtime.
+	pub relay_slot_duration: Duration,
 	/// The claimed cores at a relay parent.
 	pub claimed_cores: BTreeSet<CoreIndex>,
 }
@@ -505,10 +593,26 @@ where
 			},
 		};
 
+		let relay_slot_duration = match call_runtime_api::<BabeConfiguration>(
+			&self.relay_client,
+			"BabeApi_configuration",
+			relay_parent,
+			(),
+		)
+		.await
+		{
+			Ok(config) => config.slot_duration().as_duration(),
+			Err(err) => {
+				tracing::error!(target: crate::LOG_TARGET, ?err, "Failed to fetch the relay chain slot duration");
+				return Err(())
+			},
+		};
+
 		Ok(RelayChainData {
 			relay_parent_header,
 			scheduled_cores,
 			max_pov_size,
+			relay_slot_duration,
 			claimed_cores: BTreeSet::new(),
 		})
 	}
@@ -0,0 +1,144 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Metrics of the slot-based collator.
+
+use polkadot_primitives::CoreIndex;
+use prometheus_endpoint::{
+	exponential_buckets, register, CounterVec, Gauge, Histogram, HistogramOpts, Opts,
+	PrometheusError, Registry, F64, U64,
+};
+use std::time::Duration;
+
+/// Tracks the blocks built by the slot-based collator, from authoring to inclusion.
+#[derive(Clone)]
+pub(crate) struct Metrics {
+	blocks_built: CounterVec<U64>,
+	blocks_backed: CounterVec<U64>,
+	blocks_not_backed: CounterVec<U64>,
+	blocks_included: CounterVec<U64>,
+	backing_latency: Histogram,
+	pov_fullness: Histogram,
+	authoring_budget: Gauge<F64>,
+	pov_size_target: Gauge<U64>,
+}
+
+impl Metrics {
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			blocks_built: register(
+				CounterVec::new(
+					Opts::new(
+						"cumulus_slot_based_blocks_built_total",
+						"Number of blocks built by the slot-based collator, per core",
+					),
+					&["core"],
+				)?,
+				registry,
+			)?,
+			blocks_backed: register(
+				CounterVec::new(
+					Opts::new(
+						"cumulus_slot_based_blocks_backed_total",
+						"Number of blocks built by the slot-based collator which got backed, per core",
+					),
+					&["core"],
+				)?,
+				registry,
+			)?,
+			blocks_not_backed: register(
+				CounterVec::new(
+					Opts::new(
+						"cumulus_slot_based_blocks_not_backed_total",
+						"Number of blocks built by the slot-based collator which were not backed in time, per core",
+					),
+					&["core"],
+				)?,
+				registry,
+			)?,
+			blocks_included: register(
+				CounterVec::new(
+					Opts::new(
+						"cumulus_slot_based_blocks_included_total",
+						"Number of blocks built by the slot-based collator which got included, per core",
+					),
+					&["core"],
+				)?,
+				registry,
+			)?,
+			backing_latency: register(
+				Histogram::with_opts(HistogramOpts {
+					common_opts: Opts::new(
+						"cumulus_slot_based_backing_latency_seconds",
+						"Time between building a block and seeing it backed on the relay chain",
+					),
+					buckets: exponential_buckets(1.0, 1.5, 10)
+						.expect("function parameters are constant and always valid; qed"),
+				})?,
+				registry,
+			)?,
+			pov_fullness: register(
+				Histogram::with_opts(HistogramOpts {
+					common_opts: Opts::new(
+						"cumulus_slot_based_pov_fullness",
+						"Size of the proof of validity of built blocks relative to the targeted size",
+					),
+					buckets: vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0],
+				})?,
+				registry,
+			)?,
+			authoring_budget: register(
+				Gauge::new(
+					"cumulus_slot_based_authoring_budget_seconds",
+					"Time budget for authoring the next block",
+				)?,
+				registry,
+			)?,
+			pov_size_target: register(
+				Gauge::new(
+					"cumulus_slot_based_pov_size_target_bytes",
+					"Targeted proof of validity size of the next block",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	pub(crate) fn on_block_built(&self, core_index: CoreIndex, pov_size: usize, pov_target: usize) {
+		self.blocks_built.with_label_values(&[&core_index.0.to_string()]).inc();
+		if pov_target > 0 {
+			self.pov_fullness.observe(pov_size as f64 / pov_target as f64);
+		}
+	}
+
+	pub(crate) fn on_block_backed(&self, core_index: CoreIndex, latency: Duration) {
+		self.blocks_backed.with_label_values(&[&core_index.0.to_string()]).inc();
+		self.backing_latency.observe(latency.as_secs_f64());
+	}
+
+	pub(crate) fn on_block_not_backed(&self, core_index: CoreIndex) {
+		self.blocks_not_backed.with_label_values(&[&core_index.0.to_string()]).inc();
+	}
+
+	pub(crate) fn on_block_included(&self, core_index: CoreIndex) {
+		self.blocks_included.with_label_values(&[&core_index.0.to_string()]).inc();
+	}
+
+	pub(crate) fn on_budget(&self, authoring_duration: Duration, pov_size: usize) {
+		self.authoring_budget.set(authoring_duration.as_secs_f64());
+		self.pov_size_target.set(pov_size as u64);
+	}
+}
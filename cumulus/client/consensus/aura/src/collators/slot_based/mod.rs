@@ -27,6 +27,10 @@
 //! chain block. The collator implementation then expects that we have that many cores scheduled
 //! during the relay chain block. After the block is built, the block builder task sends it to
 //! the collation task which compresses it and submits it to the collation-generation subsystem.
+//!
+//! The time spent authoring each block and the size of its proof of validity are paced according
+//! to the number of cores scheduled for the para and to how fast the built blocks get backed, see
+//! [`pacing`].
 
 use codec::Codec;
 use consensus_common::ParachainCandidate;
@@ -53,12 +57,16 @@ use sp_inherents::CreateInherentDataProviders;
 use sp_keystore::KeystorePtr;
 use sp_runtime::traits::{Block as BlockT, Member};
 
+use prometheus_endpoint::Registry;
+
 use std::{sync::Arc, time::Duration};
 
 use self::{block_builder_task::run_block_builder, collation_task::run_collation_task};
 
 mod block_builder_task;
 mod collation_task;
+mod metrics;
+mod pacing;
 
 /// Parameters for [`run`].
 pub struct Params<BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS> {
@@ -86,13 +94,19 @@ pub struct Params<BI, CIDP, Client, Backend, RClient, CHP, Proposer, CS> {
 	pub proposer: Proposer,
 	/// The generic collator service used to plug into this consensus engine.
 	pub collator_service: CS,
-	/// The amount of time to spend authoring each block.
+	/// The maximum amount of time to spend authoring each block.
 	pub authoring_duration: Duration,
+	/// Whether to scale the time and PoV budget of each block by the number of cores scheduled
+	/// for the para and the observed backing latency. If disabled, every block gets the full
+	/// `authoring_duration` and PoV size.
+	pub pace_block_production: bool,
 	/// Whether we should reinitialize the collator config (i.e. we are transitioning to aura).
 	pub reinitialize: bool,
 	/// Drift slots by a fixed duration. This can be used to create more preferrable authoring
 	/// timings.
 	pub slot_drift: Duration,
+	/// The prometheus registry to report block production metrics to.
+	pub prometheus_registry: Option<Registry>,
 }
 
 /// Run aura-based block building and collation task.
@@ -136,6 +150,18 @@ where
 
 	let collation_task_fut = run_collation_task::<Block, _, _>(collator_task_params);
 
+	let metrics = params.prometheus_registry.as_ref().and_then(|registry| {
+		metrics::Metrics::register(registry)
+			.map_err(|err| {
+				tracing::warn!(
+					target: crate::LOG_TARGET,
+					?err,
+					"Failed to register slot-based collator metrics."
+				)
+			})
+			.ok()
+	});
+
 	let block_builder_params = block_builder_task::BuilderTaskParams {
 		create_inherent_data_providers: params.create_inherent_data_providers,
 		block_import: params.block_import,
@@ -148,8 +174,10 @@ where
 		proposer: params.proposer,
 		collator_service: params.collator_service,
 		authoring_duration: params.authoring_duration,
+		pace_block_production: params.pace_block_production,
 		collator_sender: tx,
 		slot_drift: params.slot_drift,
+		metrics,
 	};
 
 	let block_builder_fut =
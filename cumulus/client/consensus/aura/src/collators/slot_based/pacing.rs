@@ -0,0 +1,245 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pacing of block production under elastic scaling.
+//!
+//! The time spent authoring a block is bounded by the share of a relay chain slot each of the
+//! cores assigned to the para in the claim queue gets, and the proof of validity is allowed to
+//! grow up to the configured share of the maximum PoV size. Both are then scaled down when the
+//! blocks we build take longer than [`TARGET_BACKING_SLOTS`] relay chain slots to get backed, or
+//! are not backed at all within [`UNBACKED_TIMEOUT_SLOTS`], and recover once they are backed in
+//! time again.
+
+use polkadot_primitives::CoreIndex;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use std::{
+	collections::HashMap,
+	time::{Duration, Instant},
+};
+
+use super::metrics::Metrics;
+
+/// Blocks built on top of a relay parent are expected to be backed in one of the next two relay
+/// chain blocks.
+const TARGET_BACKING_SLOTS: u32 = 2;
+
+/// Blocks not backed within this many relay chain slots are assumed to never get backed.
+const UNBACKED_TIMEOUT_SLOTS: u32 = 4;
+
+/// The budget never shrinks below this share of the full budget.
+const MIN_BUDGET_FACTOR: f64 = 0.25;
+
+/// The budget is multiplied by this factor for every block backed too late.
+const BUDGET_DECREASE_FACTOR: f64 = 0.75;
+
+/// The share of the full budget recovered for every block backed in time.
+const BUDGET_INCREASE_STEP: f64 = 0.05;
+
+/// How many built blocks are tracked at most while waiting for them to be backed and included.
+const MAX_TRACKED_BLOCKS: usize = 64;
+
+/// The resources the next block may use.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BlockBudget {
+	/// The amount of time to spend authoring the block.
+	pub authoring_duration: Duration,
+	/// The size the proof of validity of the block may grow up to.
+	pub pov_size: usize,
+}
+
+/// Derives the [`BlockBudget`] of each block from the number of cores and the observed backing
+/// latency.
+pub(crate) struct BlockProductionPacer {
+	max_authoring_duration: Duration,
+	enabled: bool,
+	factor: f64,
+}
+
+impl BlockProductionPacer {
+	/// Create a new pacer, never allowing more than `max_authoring_duration` per block.
+	///
+	/// If `enabled` is `false`, every block gets the full budget regardless of the number of
+	/// cores and the observed backing latency.
+	pub fn new(max_authoring_duration: Duration, enabled: bool) -> Self {
+		Self { max_authoring_duration, enabled, factor: 1.0 }
+	}
+
+	/// Returns the budget of the next block, given the duration of a relay chain slot, the number
+	/// of cores scheduled for the para and the maximum PoV size configured on the relay chain.
+	pub fn budget(
+		&self,
+		relay_slot_duration: Duration,
+		scheduled_cores: usize,
+		max_pov_size: u32,
+	) -> BlockBudget {
+		let authoring_duration = if self.enabled {
+			let core_share = relay_slot_duration / scheduled_cores.max(1) as u32;
+			self.max_authoring_duration.min(core_share).mul_f64(self.factor)
+		} else {
+			self.max_authoring_duration
+		};
+
+		let max_pov_size = if cfg!(feature = "full-pov-size") {
+			max_pov_size
+		} else {
+			// Set the block limit to 50% of the maximum PoV size.
+			//
+			// TODO: If we got benchmarking that includes the proof size,
+			// we should be able to use the maximum pov size.
+			max_pov_size / 2
+		} as usize;
+		let pov_size = (max_pov_size as f64 * self.factor) as usize;
+
+		BlockBudget { authoring_duration, pov_size }
+	}
+
+	/// Note how long it took for one of our blocks to get backed, given the duration of a relay
+	/// chain slot.
+	pub fn note_backing_latency(&mut self, latency: Duration, relay_slot_duration: Duration) {
+		if latency > relay_slot_duration * TARGET_BACKING_SLOTS {
+			self.note_backed_too_late();
+		} else if self.enabled {
+			self.factor = (self.factor + BUDGET_INCREASE_STEP).min(1.0);
+		}
+	}
+
+	/// Note one of our blocks was not backed within [`Self::unbacked_timeout`].
+	pub fn note_unbacked(&mut self) {
+		self.note_backed_too_late();
+	}
+
+	/// Returns how long to wait for one of our blocks to get backed before giving up on it.
+	pub fn unbacked_timeout(relay_slot_duration: Duration) -> Duration {
+		relay_slot_duration * UNBACKED_TIMEOUT_SLOTS
+	}
+
+	fn note_backed_too_late(&mut self) {
+		if self.enabled {
+			self.factor = (self.factor * BUDGET_DECREASE_FACTOR).max(MIN_BUDGET_FACTOR);
+		}
+	}
+}
+
+struct BuiltBlock<Block: BlockT> {
+	number: NumberFor<Block>,
+	core_index: CoreIndex,
+	built_at: Instant,
+	backed: bool,
+}
+
+/// Follows the blocks we built until they are included.
+pub(crate) struct BuiltBlocks<Block: BlockT> {
+	blocks: HashMap<Block::Hash, BuiltBlock<Block>>,
+	metrics: Option<Metrics>,
+}
+
+impl<Block: BlockT> BuiltBlocks<Block> {
+	pub fn new(metrics: Option<Metrics>) -> Self {
+		Self { blocks: HashMap::new(), metrics }
+	}
+
+	/// Whether there are no built blocks left to follow.
+	pub fn is_empty(&self) -> bool {
+		self.blocks.is_empty()
+	}
+
+	/// Note a block we built for the given core.
+	pub fn note_built(
+		&mut self,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+		core_index: CoreIndex,
+		pov_size: usize,
+		budget: BlockBudget,
+	) {
+		if let Some(metrics) = &self.metrics {
+			metrics.on_block_built(core_index, pov_size, budget.pov_size);
+		}
+
+		if self.blocks.len() >= MAX_TRACKED_BLOCKS {
+			if let Some(oldest) = self
+				.blocks
+				.iter()
+				.min_by_key(|(_, block)| block.built_at)
+				.map(|(hash, _)| *hash)
+			{
+				self.blocks.remove(&oldest);
+			}
+		}
+
+		self.blocks.insert(
+			hash,
+			BuiltBlock { number, core_index, built_at: Instant::now(), backed: false },
+		);
+	}
+
+	/// Note a block was seen backed on the relay chain. Returns the time it took to get backed,
+	/// the first time this is noted for one of our blocks.
+	pub fn note_backed(&mut self, hash: &Block::Hash) -> Option<Duration> {
+		let block = self.blocks.get_mut(hash).filter(|block| !block.backed)?;
+		block.backed = true;
+
+		let latency = block.built_at.elapsed();
+		if let Some(metrics) = &self.metrics {
+			metrics.on_block_backed(block.core_index, latency);
+		}
+
+		Some(latency)
+	}
+
+	/// Stop following the blocks not backed within `timeout`. Returns how many were given up on.
+	pub fn note_unbacked_timeouts(&mut self, timeout: Duration) -> usize {
+		let metrics = self.metrics.as_ref();
+		let before = self.blocks.len();
+		self.blocks.retain(|_, block| {
+			if block.backed || block.built_at.elapsed() <= timeout {
+				return true
+			}
+
+			if let Some(metrics) = metrics {
+				metrics.on_block_not_backed(block.core_index);
+			}
+			false
+		});
+
+		before - self.blocks.len()
+	}
+
+	/// Note a block was included. Backed blocks we built below it are assumed to be included along
+	/// with it, while no block we built at its height or below is followed anymore.
+	pub fn note_included(&mut self, hash: &Block::Hash, number: NumberFor<Block>) {
+		let metrics = self.metrics.as_ref();
+		self.blocks.retain(|block_hash, block| {
+			if block.number > number {
+				return true
+			}
+
+			if block_hash == hash || block.backed {
+				if let Some(metrics) = metrics {
+					metrics.on_block_included(block.core_index);
+				}
+			}
+			false
+		});
+	}
+
+	/// Note the budget the next block is built with.
+	pub fn note_budget(&self, budget: BlockBudget) {
+		if let Some(metrics) = &self.metrics {
+			metrics.on_budget(budget.authoring_duration, budget.pov_size);
+		}
+	}
+}
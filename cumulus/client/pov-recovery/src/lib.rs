@@ -36,7 +36,9 @@
 //! 2. If between starting and firing the timer the block is imported, we skip the recovery of the
 //!    PoV.
 //!
-//! 3. If the timer fired we recover the PoV using the relay chain PoV recovery protocol.
+//! 3. If the timer fired we first try to fetch the block from parachain peers (see
+//!    [`peer_recovery`]). If no peer provides a block matching the candidate, we recover the PoV
+//!    using the relay chain PoV recovery protocol.
 //!
 //! 4a. After it is recovered, we restore the block and import it.
 //!
@@ -86,6 +88,9 @@ mod tests;
 mod active_candidate_recovery;
 use active_candidate_recovery::ActiveCandidateRecovery;
 
+pub mod peer_recovery;
+use peer_recovery::{ActivePeerRecovery, PeerRecoveryHandle};
+
 const LOG_TARGET: &str = "cumulus-pov-recovery";
 
 /// Test-friendly wrapper trait for the overseer handle.
@@ -225,6 +230,8 @@ pub struct PoVRecovery<Block: BlockT, PC, RC> {
 	/// available anymore in this map, it means that it was already imported.
 	candidate_recovery_queue: RecoveryQueue<Block>,
 	active_candidate_recovery: ActiveCandidateRecovery<Block>,
+	/// Recoveries of blocks from parachain peers, tried before the availability recovery.
+	active_peer_recovery: ActivePeerRecovery<Block>,
 	/// Blocks that wait that the parent is imported.
 	///
 	/// Uses parent -> blocks mapping.
@@ -260,6 +267,7 @@ where
 			candidates: HashMap::new(),
 			candidate_recovery_queue: RecoveryQueue::new(recovery_delay_range),
 			active_candidate_recovery: ActiveCandidateRecovery::new(recovery_handle),
+			active_peer_recovery: ActivePeerRecovery::new(),
 			waiting_for_parent: HashMap::new(),
 			parachain_client,
 			parachain_import_queue,
@@ -271,6 +279,16 @@ where
 		}
 	}
 
+	/// Fetch missing blocks from parachain peers before falling back to the availability
+	/// recovery.
+	pub fn with_peer_recovery(
+		mut self,
+		peer_recovery_handle: Arc<dyn PeerRecoveryHandle<Block>>,
+	) -> Self {
+		self.active_peer_recovery.set_handle(peer_recovery_handle);
+		self
+	}
+
 	/// Handle a new pending candidate.
 	fn handle_pending_candidate(
 		&mut self,
@@ -333,13 +351,50 @@ where
 	async fn recover_candidate(&mut self, block_hash: Block::Hash) {
 		match self.candidates.get(&block_hash) {
 			Some(candidate) if candidate.waiting_recovery => {
-				tracing::debug!(target: LOG_TARGET, ?block_hash, "Issuing recovery request");
-				self.active_candidate_recovery.recover_candidate(block_hash, candidate).await;
+				if self.active_peer_recovery.recover_block(block_hash, candidate) {
+					tracing::debug!(target: LOG_TARGET, ?block_hash, "Requesting block from peers");
+				} else {
+					tracing::debug!(target: LOG_TARGET, ?block_hash, "Issuing recovery request");
+					self.active_candidate_recovery.recover_candidate(block_hash, candidate).await;
+				}
 			},
 			_ => (),
 		}
 	}
 
+	/// Handle a block recovered from parachain peers.
+	///
+	/// Falls back to the availability recovery if no peer was able to provide the block. The block
+	/// is dropped if it was imported or finalized in the meantime.
+	async fn handle_block_recovered_from_peers(
+		&mut self,
+		block_hash: Block::Hash,
+		block: Option<Block>,
+	) {
+		match (block, self.candidates.get(&block_hash)) {
+			(Some(block), Some(candidate)) if candidate.waiting_recovery => {
+				self.candidates_in_retry.remove(&block_hash);
+				self.import_recovered_block(block_hash, block);
+			},
+			(Some(_), _) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					?block_hash,
+					"Block fetched from peers is not waiting for recovery anymore",
+				);
+			},
+			(None, Some(candidate)) if candidate.waiting_recovery => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					?block_hash,
+					"Unable to fetch block from peers, issuing recovery request",
+				);
+				self.active_candidate_recovery.recover_candidate(block_hash, candidate).await;
+			},
+			(None, _) => (),
+		}
+	}
+
 	/// Clear `waiting_for_parent` and `waiting_recovery` for the candidate with `hash`.
 	/// Also clears children blocks waiting for this parent.
 	fn reset_candidate(&mut self, hash: Block::Hash) {
@@ -402,8 +457,11 @@ where
 			},
 		};
 
-		let block = block_data.into_block();
+		self.import_recovered_block(block_hash, block_data.into_block());
+	}
 
+	/// Import a recovered `block` once its parent is known.
+	fn import_recovered_block(&mut self, block_hash: Block::Hash, block: Block) {
 		let parent = *block.header().parent_hash();
 
 		match self.parachain_client.block_status(parent) {
@@ -615,6 +673,11 @@ where
 				{
 					self.handle_candidate_recovered(block_hash, pov.as_deref()).await;
 				},
+				(block_hash, block) =
+					self.active_peer_recovery.wait_for_recovery().fuse() =>
+				{
+					self.handle_block_recovered_from_peers(block_hash, block).await;
+				},
 			}
 		}
 	}
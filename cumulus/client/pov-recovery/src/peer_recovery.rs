@@ -0,0 +1,485 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Recovery of parachain blocks from parachain peers.
+//!
+//! Recovering a block through the relay chain availability layer is expensive and stops working
+//! once the chunks are pruned. Other parachain nodes have often already imported the block we are
+//! missing, so we first ask them for it using a request-response protocol on the parachain
+//! network. Only if none of the peers can provide a valid block, we fall back to the availability
+//! recovery.
+//!
+//! A block fetched from a peer is only accepted if its header matches the head data hash committed
+//! to in the candidate descriptor and if its body matches the extrinsics root of the header. The
+//! peers are asked concurrently and only for a short time, so that unresponsive peers don't delay
+//! the availability recovery.
+
+use sc_client_api::BlockBackend;
+use sc_network::{
+	request_responses::{IfDisconnected, IncomingRequest, OutgoingResponse, RequestFailure},
+	service::traits::NetworkService,
+	NetworkBackend, PeerId, ProtocolName, ReputationChange, MAX_RESPONSE_SIZE,
+};
+use sc_network_sync::SyncingService;
+use sp_api::CallApiAt;
+use sp_runtime::{
+	traits::{Block as BlockT, Hash as HashT, Header as HeaderT, NumberFor},
+	StateVersion,
+};
+
+use polkadot_primitives::{Hash as PHash, HeadData};
+
+use codec::{Decode, Encode};
+use futures::{select, stream::FuturesUnordered, Future, FutureExt, StreamExt};
+use futures_timer::Delay;
+use rand::{seq::SliceRandom, thread_rng};
+
+use std::{marker::PhantomData, pin::Pin, sync::Arc, time::Duration};
+
+use crate::LOG_TARGET;
+
+/// The maximum number of peers we ask for a block before giving up.
+const MAX_PEERS_PER_RECOVERY: usize = 3;
+
+/// The maximum size of an encoded [`BlockRecoveryRequest`].
+const MAX_REQUEST_SIZE: u64 = 1024;
+
+/// Timeout for a single block request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Timeout for fetching a block from the peers, after which we fall back to the availability
+/// recovery.
+const PEER_RECOVERY_TIMEOUT: Duration = Duration::from_secs(3);
+
+mod rep {
+	use sc_network::ReputationChange as Rep;
+
+	/// Reputation change when a peer sent us a request we could not decode.
+	pub const INVALID_REQUEST: Rep = Rep::new(-(1 << 10), "Invalid block recovery request");
+
+	/// Reputation change when a peer sent us a response we could not decode.
+	pub const INVALID_RESPONSE: Rep = Rep::new(-(1 << 10), "Invalid block recovery response");
+
+	/// Reputation change when a peer sent us a block that does not match the candidate.
+	///
+	/// Not fatal, as the mismatch may also be caused by our view of the runtime, e.g. around a
+	/// runtime upgrade changing the extrinsics root state version.
+	pub const INVALID_BLOCK: Rep =
+		Rep::new(-(1 << 12), "Recovered block does not match the candidate");
+}
+
+/// Generate the name of the block recovery protocol from the genesis hash.
+pub fn protocol_name<Hash: AsRef<[u8]>>(genesis_hash: Hash) -> ProtocolName {
+	format!("/{}/pov-recovery/1", array_bytes::bytes2hex("", genesis_hash.as_ref())).into()
+}
+
+/// Request for the block with the given hash.
+///
+/// The response is the SCALE encoded `Option<Block>`, `None` if the peer doesn't know the block.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct BlockRecoveryRequest<Hash> {
+	/// Hash of the requested block.
+	pub block_hash: Hash,
+}
+
+/// Handler for incoming [`BlockRecoveryRequest`]s.
+///
+/// Serves the blocks known to the local client to other parachain nodes.
+pub struct BlockRecoveryRequestHandler<Block, Client> {
+	client: Arc<Client>,
+	request_receiver: async_channel::Receiver<IncomingRequest>,
+	_phantom: PhantomData<Block>,
+}
+
+impl<Block, Client> BlockRecoveryRequestHandler<Block, Client>
+where
+	Block: BlockT,
+	Client: BlockBackend<Block> + Send + Sync + 'static,
+{
+	/// Create a new [`BlockRecoveryRequestHandler`] and the configuration of the protocol that
+	/// needs to be registered with the parachain network.
+	pub fn new<N: NetworkBackend<Block, Block::Hash>>(
+		genesis_hash: Block::Hash,
+		client: Arc<Client>,
+		num_peer_hint: usize,
+	) -> (Self, N::RequestResponseProtocolConfig) {
+		// Reserve enough request slots for one request per peer when we are at the maximum
+		// number of peers.
+		let (tx, request_receiver) = async_channel::bounded(num_peer_hint.max(1));
+
+		let protocol_config = N::request_response_config(
+			protocol_name(genesis_hash),
+			Vec::new(),
+			MAX_REQUEST_SIZE,
+			MAX_RESPONSE_SIZE,
+			REQUEST_TIMEOUT,
+			Some(tx),
+		);
+
+		(Self { client, request_receiver, _phantom: PhantomData }, protocol_config)
+	}
+
+	/// Run the [`BlockRecoveryRequestHandler`].
+	pub async fn run(mut self) {
+		while let Some(IncomingRequest { peer, payload, pending_response }) =
+			self.request_receiver.next().await
+		{
+			let response = match BlockRecoveryRequest::<Block::Hash>::decode(&mut &payload[..]) {
+				Ok(request) => OutgoingResponse {
+					result: Ok(self.handle_request(peer, request).encode()),
+					reputation_changes: Vec::new(),
+					sent_feedback: None,
+				},
+				Err(error) => {
+					tracing::debug!(
+						target: LOG_TARGET,
+						?peer,
+						?error,
+						"Failed to decode block recovery request",
+					);
+
+					OutgoingResponse {
+						result: Err(()),
+						reputation_changes: vec![rep::INVALID_REQUEST],
+						sent_feedback: None,
+					}
+				},
+			};
+
+			if pending_response.send(response).is_err() {
+				tracing::debug!(
+					target: LOG_TARGET,
+					?peer,
+					"Failed to send block recovery response, request was canceled",
+				);
+			}
+		}
+	}
+
+	fn handle_request(
+		&self,
+		peer: PeerId,
+		request: BlockRecoveryRequest<Block::Hash>,
+	) -> Option<Block> {
+		let block_hash = request.block_hash;
+
+		match self.client.block(block_hash) {
+			Ok(block) => block.map(|signed_block| signed_block.block),
+			Err(error) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					?peer,
+					?block_hash,
+					?error,
+					"Failed to load block requested by peer",
+				);
+				None
+			},
+		}
+	}
+}
+
+/// Test-friendly abstraction over the parachain network used to fetch blocks from peers.
+#[async_trait::async_trait]
+pub trait PeerRecoveryHandle<Block: BlockT>: Send + Sync {
+	/// Returns the connected peers that may be able to serve the block at `block_number`.
+	async fn recovery_peers(&self, block_number: NumberFor<Block>) -> Vec<PeerId>;
+
+	/// Send a [`BlockRecoveryRequest`] for `block_hash` to `peer` and return the raw response.
+	async fn request_block(
+		&self,
+		peer: PeerId,
+		block_hash: Block::Hash,
+	) -> Result<Vec<u8>, RequestFailure>;
+
+	/// Report a peer that answered with an invalid response.
+	fn report_peer(&self, peer: PeerId, reputation_change: ReputationChange);
+
+	/// Returns the state version of the extrinsics root of the children of `parent_hash`, as
+	/// defined by the runtime at `parent_hash`.
+	///
+	/// Returns `None` if the runtime at `parent_hash` is not known.
+	fn extrinsics_root_state_version(&self, parent_hash: Block::Hash) -> Option<StateVersion>;
+}
+
+/// [`PeerRecoveryHandle`] backed by the parachain network and syncing service.
+pub struct NetworkPeerRecovery<Block: BlockT, Client> {
+	network: Arc<dyn NetworkService>,
+	sync_service: Arc<SyncingService<Block>>,
+	client: Arc<Client>,
+	protocol_name: ProtocolName,
+}
+
+impl<Block: BlockT, Client> NetworkPeerRecovery<Block, Client> {
+	/// Create a new instance.
+	///
+	/// The `genesis_hash` needs to match the one passed to
+	/// [`BlockRecoveryRequestHandler::new`] on the serving side.
+	pub fn new(
+		network: Arc<dyn NetworkService>,
+		sync_service: Arc<SyncingService<Block>>,
+		client: Arc<Client>,
+		genesis_hash: Block::Hash,
+	) -> Self {
+		Self { network, sync_service, client, protocol_name: protocol_name(genesis_hash) }
+	}
+}
+
+#[async_trait::async_trait]
+impl<Block, Client> PeerRecoveryHandle<Block> for NetworkPeerRecovery<Block, Client>
+where
+	Block: BlockT,
+	Client: CallApiAt<Block> + Send + Sync,
+{
+	async fn recovery_peers(&self, block_number: NumberFor<Block>) -> Vec<PeerId> {
+		self.sync_service
+			.peers_info()
+			.await
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|(_, info)| info.best_number >= block_number)
+			.map(|(peer, _)| peer)
+			.collect()
+	}
+
+	async fn request_block(
+		&self,
+		peer: PeerId,
+		block_hash: Block::Hash,
+	) -> Result<Vec<u8>, RequestFailure> {
+		self.network
+			.request(
+				peer,
+				self.protocol_name.clone(),
+				BlockRecoveryRequest { block_hash }.encode(),
+				None,
+				IfDisconnected::ImmediateError,
+			)
+			.await
+			.map(|(response, _)| response)
+	}
+
+	fn report_peer(&self, peer: PeerId, reputation_change: ReputationChange) {
+		self.network.report_peer(peer, reputation_change);
+	}
+
+	fn extrinsics_root_state_version(&self, parent_hash: Block::Hash) -> Option<StateVersion> {
+		match self.client.runtime_version_at(parent_hash) {
+			Ok(version) => Some(version.extrinsics_root_state_version()),
+			Err(error) => {
+				tracing::debug!(
+					target: LOG_TARGET,
+					?parent_hash,
+					?error,
+					"Failed to get the runtime version of the parent block",
+				);
+				None
+			},
+		}
+	}
+}
+
+/// Checks that `block` is the block committed to by a candidate with the given `para_head`.
+///
+/// The extrinsics root is computed with the given `state_version`. If it is unknown, a root
+/// computed with any of the state versions is accepted, as both are binding commitments to the
+/// extrinsics.
+pub(crate) fn verify_block<Block: BlockT>(
+	block: &Block,
+	block_hash: Block::Hash,
+	para_head: PHash,
+	state_version: Option<StateVersion>,
+) -> bool {
+	let header = block.header();
+
+	if header.hash() != block_hash || HeadData(header.encode()).hash() != para_head {
+		return false
+	}
+
+	let extrinsics = block.extrinsics().iter().map(Encode::encode).collect::<Vec<_>>();
+	let state_versions = match state_version {
+		Some(state_version) => vec![state_version],
+		None => vec![StateVersion::V0, StateVersion::V1],
+	};
+
+	state_versions.into_iter().any(|state_version| {
+		<<Block::Header as HeaderT>::Hashing as HashT>::ordered_trie_root(
+			extrinsics.clone(),
+			state_version,
+		) == *header.extrinsics_root()
+	})
+}
+
+/// Checks the response of `peer` to the request of the block with `block_hash`.
+fn check_response<Block: BlockT>(
+	handle: &dyn PeerRecoveryHandle<Block>,
+	peer: PeerId,
+	response: Result<Vec<u8>, RequestFailure>,
+	block_hash: Block::Hash,
+	para_head: PHash,
+	state_version: Option<StateVersion>,
+) -> Option<Block> {
+	let response = match response {
+		Ok(response) => response,
+		Err(error) => {
+			tracing::debug!(
+				target: LOG_TARGET,
+				?peer,
+				?block_hash,
+				?error,
+				"Block recovery request failed",
+			);
+			return None
+		},
+	};
+
+	match Option::<Block>::decode(&mut &response[..]) {
+		Ok(Some(block)) if verify_block(&block, block_hash, para_head, state_version) =>
+			return Some(block),
+		Ok(Some(_)) => {
+			tracing::warn!(
+				target: LOG_TARGET,
+				?peer,
+				?block_hash,
+				"Peer sent a block that does not match the candidate",
+			);
+			handle.report_peer(peer, rep::INVALID_BLOCK);
+		},
+		Ok(None) => {
+			tracing::debug!(target: LOG_TARGET, ?peer, ?block_hash, "Peer doesn't know the block");
+		},
+		Err(error) => {
+			tracing::debug!(
+				target: LOG_TARGET,
+				?peer,
+				?block_hash,
+				?error,
+				"Failed to decode block recovery response",
+			);
+			handle.report_peer(peer, rep::INVALID_RESPONSE);
+		},
+	}
+
+	None
+}
+
+/// Fetch the block with `block_hash` from up to [`MAX_PEERS_PER_RECOVERY`] random peers.
+///
+/// The peers are asked concurrently. Gives up after [`PEER_RECOVERY_TIMEOUT`].
+async fn fetch_block<Block: BlockT>(
+	handle: Arc<dyn PeerRecoveryHandle<Block>>,
+	block_hash: Block::Hash,
+	block_number: NumberFor<Block>,
+	parent_hash: Block::Hash,
+	para_head: PHash,
+) -> Option<Block> {
+	let state_version = handle.extrinsics_root_state_version(parent_hash);
+
+	let fetch = async {
+		let mut peers = handle.recovery_peers(block_number).await;
+		peers.shuffle(&mut thread_rng());
+
+		let mut requests = peers
+			.into_iter()
+			.take(MAX_PEERS_PER_RECOVERY)
+			.map(|peer| {
+				let handle = handle.clone();
+				async move { (peer, handle.request_block(peer, block_hash).await) }
+			})
+			.collect::<FuturesUnordered<_>>();
+
+		while let Some((peer, response)) = requests.next().await {
+			if let Some(block) =
+				check_response(&*handle, peer, response, block_hash, para_head, state_version)
+			{
+				return Some(block)
+			}
+		}
+
+		None
+	}
+	.fuse();
+	let timeout = Delay::new(PEER_RECOVERY_TIMEOUT).fuse();
+	futures::pin_mut!(fetch, timeout);
+
+	select! {
+		block = fetch => block,
+		_ = timeout => {
+			tracing::debug!(target: LOG_TARGET, ?block_hash, "Timed out fetching block from peers");
+			None
+		},
+	}
+}
+
+/// The active peer recovery.
+///
+/// This handles fetching blocks from parachain peers and tracks the active recoveries.
+pub(crate) struct ActivePeerRecovery<Block: BlockT> {
+	/// The recoveries that are currently being executed.
+	recoveries:
+		FuturesUnordered<Pin<Box<dyn Future<Output = (Block::Hash, Option<Block>)> + Send>>>,
+	peer_recovery_handle: Option<Arc<dyn PeerRecoveryHandle<Block>>>,
+}
+
+impl<Block: BlockT> ActivePeerRecovery<Block> {
+	pub fn new() -> Self {
+		Self { recoveries: Default::default(), peer_recovery_handle: None }
+	}
+
+	/// Set the handle used to reach the parachain peers.
+	pub fn set_handle(&mut self, peer_recovery_handle: Arc<dyn PeerRecoveryHandle<Block>>) {
+		self.peer_recovery_handle = Some(peer_recovery_handle);
+	}
+
+	/// Start fetching the block of the given `candidate` from parachain peers.
+	///
+	/// Returns `false` if peer recovery is disabled.
+	pub fn recover_block(
+		&mut self,
+		block_hash: Block::Hash,
+		candidate: &crate::Candidate<Block>,
+	) -> bool {
+		let Some(handle) = self.peer_recovery_handle.clone() else { return false };
+
+		let block_number = candidate.block_number;
+		let parent_hash = candidate.parent_hash;
+		let para_head = candidate.receipt.descriptor.para_head();
+
+		self.recoveries.push(
+			async move {
+				let block =
+					fetch_block(handle, block_hash, block_number, parent_hash, para_head).await;
+				(block_hash, block)
+			}
+			.boxed(),
+		);
+
+		true
+	}
+
+	/// Waits for the next recovery.
+	///
+	/// If the returned block is `None`, it means that none of the peers provided a valid block.
+	pub async fn wait_for_recovery(&mut self) -> (Block::Hash, Option<Block>) {
+		loop {
+			if let Some(res) = self.recoveries.next().await {
+				return res
+			} else {
+				futures::pending!()
+			}
+		}
+	}
+}
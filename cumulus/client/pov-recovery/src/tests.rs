@@ -18,7 +18,7 @@ use super::*;
 use assert_matches::assert_matches;
 use codec::{Decode, Encode};
 use cumulus_primitives_core::relay_chain::{
-	vstaging::CoreState, BlockId, CandidateCommitments, CandidateDescriptor, CoreIndex,
+	vstaging::CoreState, BlockId, CandidateCommitments, CandidateDescriptor, CoreIndex, HeadData,
 };
 use cumulus_relay_chain_interface::{
 	InboundDownwardMessage, InboundHrmpMessage, OccupiedCoreAssumption, PHash, PHeader,
@@ -37,9 +37,14 @@ use sc_client_api::{
 	FinalizeSummary, ImportNotifications, StorageEventStream, StorageKey,
 };
 use sc_consensus::import_queue::RuntimeOrigin;
+use sc_network::{request_responses::RequestFailure, PeerId, ReputationChange};
 use sc_utils::mpsc::{TracingUnboundedReceiver, TracingUnboundedSender};
 use sp_blockchain::Info;
-use sp_runtime::{generic::SignedBlock, Justifications};
+use sp_runtime::{
+	generic::SignedBlock,
+	traits::{BlakeTwo256, Hash as HashT},
+	Justifications, StateVersion,
+};
 use sp_version::RuntimeVersion;
 use std::{
 	borrow::Cow,
@@ -548,6 +553,86 @@ fn make_candidate_chain(candidate_number_range: Range<u32>) -> Vec<CommittedCand
 	candidates
 }
 
+/// Peer recovery handle answering every request with the next of the given responses.
+struct PeerRecoveryNetwork {
+	responses: Mutex<VecDeque<Vec<u8>>>,
+	reported_peers: Arc<Mutex<Vec<(PeerId, ReputationChange)>>>,
+}
+
+impl PeerRecoveryNetwork {
+	fn new(responses: Vec<Vec<u8>>) -> (Self, Arc<Mutex<Vec<(PeerId, ReputationChange)>>>) {
+		let reported_peers = Arc::new(Mutex::new(Vec::new()));
+		(
+			Self {
+				responses: Mutex::new(responses.into()),
+				reported_peers: reported_peers.clone(),
+			},
+			reported_peers,
+		)
+	}
+}
+
+#[async_trait::async_trait]
+impl PeerRecoveryHandle<Block> for PeerRecoveryNetwork {
+	async fn recovery_peers(&self, _: NumberFor<Block>) -> Vec<PeerId> {
+		(0..self.responses.lock().unwrap().len()).map(|_| PeerId::random()).collect()
+	}
+
+	async fn request_block(
+		&self,
+		_: PeerId,
+		_: <Block as BlockT>::Hash,
+	) -> Result<Vec<u8>, RequestFailure> {
+		self.responses.lock().unwrap().pop_front().ok_or(RequestFailure::Refused)
+	}
+
+	fn report_peer(&self, peer: PeerId, reputation_change: ReputationChange) {
+		self.reported_peers.lock().unwrap().push((peer, reputation_change));
+	}
+
+	fn extrinsics_root_state_version(&self, _: <Block as BlockT>::Hash) -> Option<StateVersion> {
+		Some(StateVersion::V0)
+	}
+}
+
+/// Returns a candidate whose descriptor commits to its head data and the matching block.
+fn make_peer_recoverable_candidate() -> (CommittedCandidateReceipt, Block) {
+	let collator = Sr25519Keyring::Ferdie;
+	let header = Header {
+		number: 1,
+		digest: Default::default(),
+		extrinsics_root: BlakeTwo256::ordered_trie_root(vec![], StateVersion::V0),
+		parent_hash: GENESIS_HASH,
+		state_root: Default::default(),
+	};
+	let head_data: HeadData = header.encode().into();
+
+	let candidate = CommittedCandidateReceipt {
+		descriptor: CandidateDescriptor {
+			para_id: ParaId::from(1000),
+			relay_parent: PHash::zero(),
+			collator: collator.public().into(),
+			persisted_validation_data_hash: PHash::zero(),
+			pov_hash: PHash::zero(),
+			erasure_root: PHash::zero(),
+			signature: collator.sign(&[0u8; 132]).into(),
+			para_head: head_data.hash(),
+			validation_code_hash: PHash::zero().into(),
+		}
+		.into(),
+		commitments: CandidateCommitments {
+			head_data,
+			upward_messages: vec![].try_into().expect("empty vec fits within bounds"),
+			new_validation_code: None,
+			horizontal_messages: vec![].try_into().expect("empty vec fits within bounds"),
+			processed_downward_messages: 0,
+			hrmp_watermark: 0_u32,
+		},
+	};
+
+	(candidate, Block::new(header, vec![]))
+}
+
 fn dummy_usage_info(finalized_number: u32) -> ClientInfo<Block> {
 	ClientInfo {
 		chain: Info {
@@ -1428,3 +1513,182 @@ async fn chained_recovery_child_succeeds_before_parent() {
 	// No more import requests received
 	assert_matches!(import_requests_rx.next().timeout(Duration::from_millis(100)).await, None);
 }
+
+#[tokio::test]
+async fn single_pending_candidate_recovered_from_peers() {
+	sp_tracing::init_for_tests();
+
+	let (recovery_subsystem_tx, mut recovery_subsystem_rx) =
+		AvailabilityRecoverySubsystemHandle::new();
+	let recovery_delay_range =
+		RecoveryDelayRange { min: Duration::from_millis(0), max: Duration::from_millis(10) };
+	let (_explicit_recovery_chan_tx, explicit_recovery_chan_rx) = mpsc::channel(10);
+	let (candidate, block) = make_peer_recoverable_candidate();
+	let header = block.header().clone();
+
+	let relay_chain_client = Relaychain::new(vec![(
+		PHeader {
+			parent_hash: PHash::from_low_u64_be(0),
+			number: 1,
+			state_root: PHash::random(),
+			extrinsics_root: PHash::random(),
+			digest: Default::default(),
+		},
+		vec![candidate],
+	)]);
+
+	let mut known_blocks = HashMap::new();
+	known_blocks.insert(GENESIS_HASH, BlockStatus::InChainWithState);
+	let (parachain_client, _import_notifications_tx, _finality_notifications_tx) =
+		ParachainClient::new(vec![dummy_usage_info(0)], Arc::new(Mutex::new(known_blocks)));
+	let (parachain_import_queue, mut import_requests_rx) = ParachainImportQueue::new();
+
+	// The first peer doesn't know the block, the second one provides it.
+	let (peer_recovery_network, reported_peers) =
+		PeerRecoveryNetwork::new(vec![None::<Block>.encode(), Some(block).encode()]);
+
+	let pov_recovery = PoVRecovery::<Block, _, _>::new(
+		Box::new(recovery_subsystem_tx),
+		recovery_delay_range,
+		Arc::new(parachain_client),
+		Box::new(parachain_import_queue),
+		relay_chain_client,
+		ParaId::new(1000),
+		explicit_recovery_chan_rx,
+		Arc::new(DummySyncOracle::default()),
+	)
+	.with_peer_recovery(Arc::new(peer_recovery_network));
+
+	task::spawn(pov_recovery.run());
+
+	// Received import request for the block fetched from the peer.
+	assert_matches!(import_requests_rx.next().await, Some(incoming_blocks) => {
+		assert_eq!(incoming_blocks.len(), 1);
+		assert_eq!(incoming_blocks[0].header, Some(header));
+	});
+
+	// The availability recovery was not used.
+	assert_matches!(recovery_subsystem_rx.next().timeout(Duration::from_millis(100)).await, None);
+	assert!(reported_peers.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn invalid_block_from_peer_falls_back_to_availability_recovery() {
+	sp_tracing::init_for_tests();
+
+	let (recovery_subsystem_tx, mut recovery_subsystem_rx) =
+		AvailabilityRecoverySubsystemHandle::new();
+	let recovery_delay_range =
+		RecoveryDelayRange { min: Duration::from_millis(0), max: Duration::from_millis(10) };
+	let (_explicit_recovery_chan_tx, explicit_recovery_chan_rx) = mpsc::channel(10);
+	let (candidate, block) = make_peer_recoverable_candidate();
+	let header = block.header().clone();
+	let candidate_hash = candidate.hash();
+
+	// A block whose header doesn't match the head data of the candidate.
+	let (_, extrinsics) = block.deconstruct();
+	let mut invalid_header = header.clone();
+	invalid_header.extrinsics_root = PHash::random();
+	let invalid_block = Block::new(invalid_header, extrinsics);
+
+	let relay_chain_client = Relaychain::new(vec![(
+		PHeader {
+			parent_hash: PHash::from_low_u64_be(0),
+			number: 1,
+			state_root: PHash::random(),
+			extrinsics_root: PHash::random(),
+			digest: Default::default(),
+		},
+		vec![candidate],
+	)]);
+
+	let mut known_blocks = HashMap::new();
+	known_blocks.insert(GENESIS_HASH, BlockStatus::InChainWithState);
+	let (parachain_client, _import_notifications_tx, _finality_notifications_tx) =
+		ParachainClient::new(vec![dummy_usage_info(0)], Arc::new(Mutex::new(known_blocks)));
+	let (parachain_import_queue, mut import_requests_rx) = ParachainImportQueue::new();
+
+	let (peer_recovery_network, reported_peers) =
+		PeerRecoveryNetwork::new(vec![Some(invalid_block).encode()]);
+
+	let pov_recovery = PoVRecovery::<Block, _, _>::new(
+		Box::new(recovery_subsystem_tx),
+		recovery_delay_range,
+		Arc::new(parachain_client),
+		Box::new(parachain_import_queue),
+		relay_chain_client,
+		ParaId::new(1000),
+		explicit_recovery_chan_rx,
+		Arc::new(DummySyncOracle::default()),
+	)
+	.with_peer_recovery(Arc::new(peer_recovery_network));
+
+	task::spawn(pov_recovery.run());
+
+	assert_matches!(
+		recovery_subsystem_rx.next().await,
+		Some(AvailabilityRecoveryMessage::RecoverAvailableData(
+			receipt,
+			session_index,
+			None,
+			None,
+			response_tx
+		)) => {
+			assert_eq!(receipt.hash(), candidate_hash);
+			assert_eq!(session_index, TEST_SESSION_INDEX);
+			response_tx.send(
+				Ok(
+					AvailableData {
+						pov: Arc::new(PoV {
+							block_data: ParachainBlockData::<Block>::new(
+								header.clone(),
+								vec![],
+								CompactProof {encoded_nodes: vec![]}
+							).encode().into()
+						}),
+						validation_data: dummy_pvd(),
+					}
+				)
+			).unwrap()
+		}
+	);
+
+	// The peer that sent the invalid block was reported.
+	assert_eq!(reported_peers.lock().unwrap().len(), 1);
+
+	// Received import request for the block recovered from availability.
+	assert_matches!(import_requests_rx.next().await, Some(incoming_blocks) => {
+		assert_eq!(incoming_blocks.len(), 1);
+		assert_eq!(incoming_blocks[0].header, Some(header));
+	});
+
+	// No import request received
+	assert_matches!(import_requests_rx.next().timeout(Duration::from_millis(100)).await, None);
+}
+
+#[test]
+fn verify_block_uses_extrinsics_root_state_version() {
+	use crate::peer_recovery::verify_block;
+	use cumulus_test_client::runtime::{RuntimeCall, SudoCall, UncheckedExtrinsic};
+
+	let extrinsics = vec![UncheckedExtrinsic::new_bare(RuntimeCall::Sudo(SudoCall::remove_key {}))];
+	let header = Header {
+		number: 1,
+		digest: Default::default(),
+		extrinsics_root: BlakeTwo256::ordered_trie_root(
+			extrinsics.iter().map(Encode::encode).collect(),
+			StateVersion::V1,
+		),
+		parent_hash: GENESIS_HASH,
+		state_root: Default::default(),
+	};
+	let para_head = HeadData(header.encode()).hash();
+	let block_hash = header.hash();
+	let block = Block::new(header, extrinsics);
+
+	assert!(verify_block(&block, block_hash, para_head, Some(StateVersion::V1)));
+	assert!(!verify_block(&block, block_hash, para_head, Some(StateVersion::V0)));
+	// Any state version is accepted if the runtime is not known.
+	assert!(verify_block(&block, block_hash, para_head, None));
+	assert!(!verify_block(&block, block_hash, PHash::random(), None));
+}
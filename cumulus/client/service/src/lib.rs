@@ -21,7 +21,10 @@
 use cumulus_client_cli::CollatorOptions;
 use cumulus_client_consensus_common::ParachainConsensus;
 use cumulus_client_network::{AssumeSybilResistance, RequireSecondedInBlockAnnounce};
-use cumulus_client_pov_recovery::{PoVRecovery, RecoveryDelayRange, RecoveryHandle};
+use cumulus_client_pov_recovery::{
+	peer_recovery::{BlockRecoveryRequestHandler, NetworkPeerRecovery},
+	PoVRecovery, RecoveryDelayRange, RecoveryHandle,
+};
 use cumulus_primitives_core::{CollectCollationInfo, ParaId};
 use cumulus_relay_chain_inprocess_interface::build_inprocess_relay_chain;
 use cumulus_relay_chain_interface::{RelayChainInterface, RelayChainResult};
@@ -43,7 +46,7 @@ use sc_network_transactions::TransactionsHandlerController;
 use sc_service::{Configuration, SpawnTaskHandle, TaskManager, WarpSyncConfig};
 use sc_telemetry::{log, TelemetryWorkerHandle};
 use sc_utils::mpsc::TracingUnboundedSender;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{CallApiAt, ProvideRuntimeApi};
 use sp_blockchain::{HeaderBackend, HeaderMetadata};
 use sp_core::{traits::SpawnNamed, Decode};
 use sp_runtime::traits::{Block as BlockT, BlockIdTo, Header};
@@ -106,6 +109,9 @@ pub struct StartRelayChainTasksParams<'a, Block: BlockT, Client, RCInterface> {
 	pub relay_chain_slot_duration: Duration,
 	pub recovery_handle: Box<dyn RecoveryHandle>,
 	pub sync_service: Arc<SyncingService<Block>>,
+	/// The parachain network, used to fetch missing blocks from parachain peers before falling
+	/// back to the relay chain availability recovery. Peer recovery is disabled if `None`.
+	pub network: Option<Arc<dyn NetworkService>>,
 }
 
 /// Parameters given to [`start_full_node`].
@@ -155,6 +161,7 @@ where
 		+ BlockBackend<Block>
 		+ BlockchainEvents<Block>
 		+ ProvideRuntimeApi<Block>
+		+ CallApiAt<Block>
 		+ 'static,
 	Client::Api: CollectCollationInfo<Block>,
 	for<'b> &'b Client: BlockImport<Block>,
@@ -177,6 +184,7 @@ where
 		relay_chain_slot_duration,
 		recovery_handle,
 		sync_service,
+		network: None,
 	})?;
 
 	#[allow(deprecated)]
@@ -214,6 +222,7 @@ pub fn start_relay_chain_tasks<Block, Client, Backend, RCInterface>(
 		relay_chain_slot_duration,
 		recovery_handle,
 		sync_service,
+		network,
 	}: StartRelayChainTasksParams<Block, Client, RCInterface>,
 ) -> sc_service::error::Result<()>
 where
@@ -224,6 +233,7 @@ where
 		+ Sync
 		+ BlockBackend<Block>
 		+ BlockchainEvents<Block>
+		+ CallApiAt<Block>
 		+ 'static,
 	for<'a> &'a Client: BlockImport<Block>,
 	Backend: BackendT<Block> + 'static,
@@ -267,7 +277,7 @@ where
 		DARecoveryProfile::Other(profile) => profile,
 	};
 
-	let pov_recovery = PoVRecovery::new(
+	let mut pov_recovery = PoVRecovery::new(
 		recovery_handle,
 		da_recovery_profile,
 		client.clone(),
@@ -275,9 +285,19 @@ where
 		relay_chain_interface.clone(),
 		para_id,
 		recovery_chan_rx,
-		sync_service,
+		sync_service.clone(),
 	);
 
+	if let Some(network) = network {
+		let genesis_hash = client.usage_info().chain.genesis_hash;
+		pov_recovery = pov_recovery.with_peer_recovery(Arc::new(NetworkPeerRecovery::new(
+			network,
+			sync_service,
+			client.clone(),
+			genesis_hash,
+		)));
+	}
+
 	task_manager
 		.spawn_essential_handle()
 		.spawn("cumulus-pov-recovery", None, pov_recovery.run());
@@ -311,6 +331,7 @@ where
 		+ Sync
 		+ BlockBackend<Block>
 		+ BlockchainEvents<Block>
+		+ CallApiAt<Block>
 		+ 'static,
 	for<'a> &'a Client: BlockImport<Block>,
 	Backend: BackendT<Block> + 'static,
@@ -327,6 +348,7 @@ where
 		recovery_handle,
 		sync_service,
 		da_recovery_profile: DARecoveryProfile::FullNode,
+		network: None,
 	})
 }
 
@@ -426,7 +448,7 @@ pub struct BuildNetworkParams<
 pub async fn build_network<'a, Block, Client, RCInterface, IQ, Network>(
 	BuildNetworkParams {
 		parachain_config,
-		net_config,
+		mut net_config,
 		client,
 		transaction_pool,
 		para_id,
@@ -492,6 +514,23 @@ where
 			Box::new(block_announce_validator) as Box<_>
 		},
 	};
+	// Serve our blocks to parachain peers recovering them, see
+	// `cumulus_client_pov_recovery::peer_recovery`.
+	let num_peer_hint = parachain_config.network.default_peers_set.in_peers as usize +
+		parachain_config.network.default_peers_set.out_peers as usize;
+	let (block_recovery_request_handler, block_recovery_protocol_config) =
+		BlockRecoveryRequestHandler::new::<Network>(
+			client.info().genesis_hash,
+			client.clone(),
+			num_peer_hint,
+		);
+	net_config.add_request_response_protocol(block_recovery_protocol_config);
+	spawn_handle.spawn(
+		"cumulus-block-recovery-request-handler",
+		Some("networking"),
+		block_recovery_request_handler.run(),
+	);
+
 	let metrics = Network::register_notification_metrics(
 		parachain_config.prometheus_config.as_ref().map(|config| &config.registry),
 	);
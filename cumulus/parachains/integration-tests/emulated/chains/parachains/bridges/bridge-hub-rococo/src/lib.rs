@@ -47,6 +47,7 @@ decl_test_parachains! {
 		pallets = {
 			PolkadotXcm: bridge_hub_rococo_runtime::PolkadotXcm,
 			Balances: bridge_hub_rococo_runtime::Balances,
+			XcmOverBridgeHubWestend: bridge_hub_rococo_runtime::XcmOverBridgeHubWestend,
 			EthereumSystem: bridge_hub_rococo_runtime::EthereumSystem,
 			EthereumInboundQueue: bridge_hub_rococo_runtime::EthereumInboundQueue,
 			EthereumOutboundQueue: bridge_hub_rococo_runtime::EthereumOutboundQueue,
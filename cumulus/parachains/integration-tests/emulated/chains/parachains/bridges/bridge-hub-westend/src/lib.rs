@@ -46,6 +46,7 @@ decl_test_parachains! {
 		pallets = {
 			PolkadotXcm: bridge_hub_westend_runtime::PolkadotXcm,
 			Balances: bridge_hub_westend_runtime::Balances,
+			XcmOverBridgeHubRococo: bridge_hub_westend_runtime::XcmOverBridgeHubRococo,
 			EthereumSystem: bridge_hub_westend_runtime::EthereumSystem,
 		}
 	},
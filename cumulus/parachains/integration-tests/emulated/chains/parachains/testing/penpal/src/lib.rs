@@ -25,8 +25,8 @@ use sp_core::Encode;
 // Cumulus
 use emulated_integration_tests_common::{
 	impl_accounts_helpers_for_parachain, impl_assert_events_helpers_for_parachain,
-	impl_assets_helpers_for_parachain, impl_foreign_assets_helpers_for_parachain,
-	impl_xcm_helpers_for_parachain,
+	impl_assets_helpers_for_parachain, impl_bridge_helpers_for_chain,
+	impl_foreign_assets_helpers_for_parachain, impl_xcm_helpers_for_parachain,
 	impls::{NetworkId, Parachain},
 	xcm_emulator::decl_test_parachains,
 };
@@ -97,3 +97,15 @@ impl_assets_helpers_for_parachain!(PenpalB);
 impl_foreign_assets_helpers_for_parachain!(PenpalB, xcm::latest::Location);
 impl_xcm_helpers_for_parachain!(PenpalA);
 impl_xcm_helpers_for_parachain!(PenpalB);
+impl_bridge_helpers_for_chain!(
+	PenpalA,
+	ParaPallet,
+	PolkadotXcm,
+	bp_bridge_hub_rococo::RuntimeCall::XcmOverBridgeHubWestend
+);
+impl_bridge_helpers_for_chain!(
+	PenpalB,
+	ParaPallet,
+	PolkadotXcm,
+	bp_bridge_hub_westend::RuntimeCall::XcmOverBridgeHubRococo
+);
@@ -64,7 +64,9 @@ use bp_messages::{
 	MessageKey, OutboundLaneData,
 };
 pub use bp_xcm_bridge_hub::XcmBridgeHubCall;
-use pallet_bridge_messages::{Config as BridgeMessagesConfig, LaneIdOf, OutboundLanes, Pallet};
+use pallet_bridge_messages::{
+	Config as BridgeMessagesConfig, InboundLanes, LaneIdOf, OutboundLanes, Pallet,
+};
 pub use pallet_bridge_messages::{
 	Instance1 as BridgeMessagesInstance1, Instance2 as BridgeMessagesInstance2,
 	Instance3 as BridgeMessagesInstance3,
@@ -75,6 +77,11 @@ pub struct BridgeHubMessageHandler<S, SI, T, TI> {
 	_marker: std::marker::PhantomData<(S, SI, T, TI)>,
 }
 
+thread_local! {
+	/// Whether the last dispatched bridged message has been accepted by the target chain.
+	static IS_LAST_MESSAGE_DELIVERED: std::cell::Cell<bool> = const { std::cell::Cell::new(true) };
+}
+
 struct LaneIdWrapper<LaneId>(LaneId);
 impl<LaneId: Encode> From<LaneIdWrapper<LaneId>> for BridgeLaneId {
 	fn from(lane_id: LaneIdWrapper<LaneId>) -> BridgeLaneId {
@@ -134,6 +141,15 @@ where
 		let nonce = message.nonce;
 		let payload = Ok(From::from(message.payload));
 
+		// The lane is not yet opened at the target chain (e.g. the bridge is opened by the
+		// source side only). The real relayer would wait until the lane is opened, so we keep
+		// the message at the source chain and retry later
+		let is_lane_opened = InboundLanes::<T, TI>::contains_key(lane_id);
+		IS_LAST_MESSAGE_DELIVERED.with(|is_delivered| is_delivered.set(is_lane_opened));
+		if !is_lane_opened {
+			return Ok(())
+		}
+
 		// Directly dispatch outbound messages assuming everything is correct
 		// and bypassing the `Relayers`  and `InboundLane` logic
 		let dispatch_result = TargetMessageDispatch::<T, TI>::dispatch(DispatchMessage {
@@ -154,6 +170,10 @@ where
 	}
 
 	fn notify_source_message_delivery(lane_id: BridgeLaneId) {
+		if !IS_LAST_MESSAGE_DELIVERED.with(|is_delivered| is_delivered.replace(true)) {
+			return
+		}
+
 		let lane_id: LaneIdOf<S, SI> = LaneIdWrapper::from(lane_id).0;
 		let data = OutboundLanes::<S, SI>::get(lane_id).unwrap();
 		let new_data = OutboundLaneData {
@@ -970,6 +990,45 @@ macro_rules! impl_bridge_helpers_for_chain {
 						Self::assert_xcm_pallet_sent();
 					});
 				}
+
+				/// Close bridge with `dest`.
+				pub fn close_bridge(
+					bridge_location: $crate::impls::Location,
+					bridge_destination_universal_location: $crate::impls::InteriorLocation,
+					may_prune_messages: u64,
+					maybe_paid: Option<($crate::impls::Asset, $crate::impls::AccountId)>
+				) {
+					<Self as $crate::impls::TestExt>::execute_with(|| {
+						use $crate::impls::{bx, Chain};
+						use $crate::impls::XcmBridgeHubCall;
+						use $crate::impls::Encode;
+
+						// important to use `root` and `OriginKind::Xcm`
+						let root_origin = <Self as Chain>::RuntimeOrigin::root();
+
+						// construct call
+						let call: $crate::impls::DoubleEncoded<()> = $runtime_call_wrapper(XcmBridgeHubCall::close_bridge {
+							bridge_destination_universal_location: bx!(
+								bridge_destination_universal_location.clone().into()
+							),
+							may_prune_messages,
+						}).encode().into();
+
+						let xcm = if let Some((fee_asset, beneficiary)) = maybe_paid {
+							$crate::impls::xcm_transact_paid_execution(call, $crate::impls::OriginKind::Xcm, fee_asset, beneficiary)
+						} else {
+							$crate::impls::xcm_transact_unpaid_execution(call, $crate::impls::OriginKind::Xcm)
+						};
+
+						// Send XCM `Transact` with `close_bridge` call
+						$crate::impls::assert_ok!(<Self as [<$chain $pallet>]>::$pallet_xcm::send(
+							root_origin,
+							bx!(bridge_location.into()),
+							bx!(xcm),
+						));
+						Self::assert_xcm_pallet_sent();
+					});
+				}
 			}
 		}
 	}
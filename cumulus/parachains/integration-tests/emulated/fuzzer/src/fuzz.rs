@@ -0,0 +1,72 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use arbitrary::Unstructured;
+use codec::{Decode, Encode};
+use emulated_integration_tests_fuzzer::{generator::FuzzInput, run_input};
+
+fn run(input: FuzzInput) {
+	#[cfg(not(fuzzing))]
+	println!("  input: {input:?}");
+
+	if let Err(violation) = run_input(&input) {
+		panic!(
+			"invariant violated: {violation:?}\nSCALE-encoded input, to be stored in `regressions/`: 0x{}",
+			hex::encode(input.encode()),
+		);
+	}
+}
+
+fn main() {
+	#[cfg(fuzzing)]
+	{
+		loop {
+			honggfuzz::fuzz!(|data: &[u8]| {
+				if let Ok(input) = Unstructured::new(data).arbitrary() {
+					run(input);
+				}
+			})
+		}
+	}
+	#[cfg(not(fuzzing))]
+	{
+		use std::{env, fs, fs::File, io::Read};
+		let args: Vec<_> = env::args().collect();
+		let md = fs::metadata(&args[1]).unwrap();
+		let all_files = match md.is_dir() {
+			true => fs::read_dir(&args[1])
+				.unwrap()
+				.map(|x| x.unwrap().path().to_str().unwrap().to_string())
+				.collect::<Vec<String>>(),
+			false => (args[1..]).to_vec(),
+		};
+		println!("All_files {:?}", all_files);
+		for argument in all_files {
+			println!("Now doing file {:?}", argument);
+			let mut buffer: Vec<u8> = Vec::new();
+			let mut f = File::open(&argument).unwrap();
+			f.read_to_end(&mut buffer).unwrap();
+			// stored regressions are SCALE-encoded, the fuzzer's own inputs are raw bytes
+			let input = if argument.ends_with(".scale") {
+				FuzzInput::decode(&mut &buffer[..]).ok()
+			} else {
+				Unstructured::new(&buffer).arbitrary().ok()
+			};
+			if let Some(input) = input {
+				run(input);
+			}
+		}
+	}
+}
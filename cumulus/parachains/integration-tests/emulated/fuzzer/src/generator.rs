@@ -0,0 +1,281 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of structurally valid XCM programs.
+//!
+//! Instead of decoding arbitrary bytes into an [`Xcm`] (which mostly yields programs rejected by
+//! the barrier before anything interesting happens), inputs are built from a small vocabulary of
+//! instructions operating on assets and locations that are known to the emulated network. This
+//! keeps the fuzzer busy in asset transactors, teleport/reserve filters and the asset trap.
+
+use arbitrary::Arbitrary;
+use codec::{Decode, Encode};
+use emulated_integration_tests_common::{
+	accounts::{ALICE, BOB},
+	xcm_emulator::{AccountId, Chain as EmulatedChain},
+	PENPAL_A_ID, USDT_ID,
+};
+use westend_system_emulated_network::WestendRelay;
+use xcm::latest::prelude::*;
+
+/// Maximal number of programs dispatched for a single fuzzer input.
+pub const MAX_PROGRAMS: usize = 8;
+/// Maximal nesting of remote programs, i.e. `InitiateTeleport { xcm: [InitiateTeleport ..] }`.
+pub const MAX_NESTING: usize = 3;
+
+/// Para id of Asset Hub Westend.
+const ASSET_HUB_ID: u32 = 1000;
+
+/// Identifies one of the chains of the emulated network.
+#[derive(Arbitrary, Clone, Copy, Debug, Decode, Encode, Eq, Ord, PartialEq, PartialOrd)]
+pub enum ChainId {
+	Relay,
+	AssetHub,
+	Penpal,
+}
+
+impl ChainId {
+	/// Location of `self` as seen from `here`.
+	pub fn location_from(self, here: ChainId) -> Location {
+		match (here, self) {
+			(here, dest) if here == dest => Location::here(),
+			(ChainId::Relay, ChainId::AssetHub) => Parachain(ASSET_HUB_ID).into(),
+			(ChainId::Relay, ChainId::Penpal) => Parachain(PENPAL_A_ID).into(),
+			(_, ChainId::Relay) => Location::parent(),
+			(_, ChainId::AssetHub) => Location::new(1, [Parachain(ASSET_HUB_ID)]),
+			(_, ChainId::Penpal) => Location::new(1, [Parachain(PENPAL_A_ID)]),
+		}
+	}
+}
+
+/// One of the well-known accounts, funded at genesis on every chain.
+#[derive(Arbitrary, Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+pub enum Account {
+	Alice,
+	Bob,
+}
+
+impl Account {
+	/// The seed the account is derived from.
+	pub fn seed(self) -> &'static str {
+		match self {
+			Account::Alice => ALICE,
+			Account::Bob => BOB,
+		}
+	}
+
+	/// The id of the account, which is the same on every chain of the network.
+	pub fn id(self) -> AccountId {
+		<WestendRelay as EmulatedChain>::account_id_of(self.seed())
+	}
+}
+
+/// An amount, biased towards the values where rounding and existential deposit issues show up.
+#[derive(Arbitrary, Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+pub enum Amount {
+	One,
+	Existential,
+	Small,
+	Large,
+	Max,
+	Exact(u64),
+}
+
+impl Amount {
+	pub fn value(self) -> u128 {
+		match self {
+			Amount::One => 1,
+			Amount::Existential => 1_000_000_000,
+			Amount::Small => 10_000_000_000,
+			Amount::Large => 1_000_000_000_000_000,
+			Amount::Max => u128::MAX,
+			Amount::Exact(amount) => amount.into(),
+		}
+	}
+}
+
+/// Assets the generated programs operate on.
+#[derive(Arbitrary, Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+pub enum AssetKind {
+	/// The relay chain native token.
+	Relay,
+	/// USDT, a trust-backed asset on Asset Hub.
+	Usdt,
+	/// An asset that is not registered anywhere.
+	Unknown(u8),
+}
+
+impl AssetKind {
+	/// Id of the asset as seen from `here`.
+	pub fn id(self, here: ChainId) -> AssetId {
+		let location = match self {
+			AssetKind::Relay => ChainId::Relay.location_from(here),
+			AssetKind::Usdt => ChainId::AssetHub
+				.location_from(here)
+				.appended_with([PalletInstance(50), GeneralIndex(USDT_ID.into())])
+				.expect("well within the junction limit; qed"),
+			AssetKind::Unknown(index) => ChainId::Penpal
+				.location_from(here)
+				.appended_with([GeneralIndex(index.into())])
+				.expect("well within the junction limit; qed"),
+		};
+		AssetId(location)
+	}
+}
+
+/// A fungible asset, as seen from the chain executing the program.
+#[derive(Arbitrary, Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct FuzzAsset {
+	pub kind: AssetKind,
+	pub amount: Amount,
+}
+
+impl FuzzAsset {
+	fn to_assets(self, here: ChainId) -> Assets {
+		Asset { id: self.kind.id(here), fun: Fungible(self.amount.value()) }.into()
+	}
+
+	fn to_filter(self, here: ChainId) -> AssetFilter {
+		Wild(AllOf { id: self.kind.id(here), fun: WildFungible })
+	}
+}
+
+/// The instruction vocabulary of the fuzzer.
+#[derive(Arbitrary, Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub enum FuzzInstruction {
+	WithdrawAsset(FuzzAsset),
+	ReceiveTeleportedAsset(FuzzAsset),
+	ReserveAssetDeposited(FuzzAsset),
+	ClaimAsset(FuzzAsset),
+	BuyExecution(FuzzAsset),
+	PayFees(FuzzAsset),
+	BurnAsset(FuzzAsset),
+	ExpectAsset(FuzzAsset),
+	DepositAsset(FuzzAsset, Account),
+	TransferAsset(FuzzAsset, Account),
+	InitiateTeleport(FuzzAsset, ChainId, Vec<FuzzInstruction>),
+	InitiateReserveWithdraw(FuzzAsset, ChainId, Vec<FuzzInstruction>),
+	DepositReserveAsset(FuzzAsset, ChainId, Vec<FuzzInstruction>),
+	TransferReserveAsset(FuzzAsset, ChainId, Vec<FuzzInstruction>),
+	SetAppendix(Vec<FuzzInstruction>),
+	SetErrorHandler(Vec<FuzzInstruction>),
+	RefundSurplus,
+	ClearError,
+	ClearOrigin,
+	Trap(u8),
+}
+
+/// How a program is dispatched on its chain.
+#[derive(Arbitrary, Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub enum Action {
+	/// `pallet_xcm::execute` by a signed origin.
+	Execute(Vec<FuzzInstruction>),
+	/// `pallet_xcm::send` by a signed origin to the given chain.
+	Send(ChainId, Vec<FuzzInstruction>),
+}
+
+/// A single program dispatched by `sender` on `chain`.
+#[derive(Arbitrary, Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct Program {
+	pub chain: ChainId,
+	pub sender: Account,
+	pub action: Action,
+}
+
+/// A fuzzer input: a sequence of programs dispatched one after another.
+///
+/// Inputs are SCALE-encodable, so that failing inputs can be stored and replayed as regression
+/// tests.
+#[derive(Arbitrary, Clone, Debug, Default, Decode, Encode, Eq, PartialEq)]
+pub struct FuzzInput {
+	pub programs: Vec<Program>,
+}
+
+impl FuzzInput {
+	/// The programs of this input, capped at [`MAX_PROGRAMS`].
+	pub fn programs(&self) -> &[Program] {
+		&self.programs[..self.programs.len().min(MAX_PROGRAMS)]
+	}
+}
+
+/// Builds the XCM for `instructions` executed on `here`.
+pub fn to_xcm(instructions: &[FuzzInstruction], here: ChainId) -> Xcm<()> {
+	to_xcm_nested(instructions, here, 0)
+}
+
+fn to_xcm_nested(instructions: &[FuzzInstruction], here: ChainId, depth: usize) -> Xcm<()> {
+	use FuzzInstruction as I;
+
+	let nested = |xcm: &[FuzzInstruction], chain: ChainId| {
+		if depth < MAX_NESTING {
+			to_xcm_nested(xcm, chain, depth + 1)
+		} else {
+			Xcm::new()
+		}
+	};
+	let beneficiary = |account: Account| -> Location {
+		AccountId32 { network: None, id: account.id().into() }.into()
+	};
+
+	Xcm(instructions
+		.iter()
+		.map(|instruction| match instruction {
+			I::WithdrawAsset(asset) => WithdrawAsset(asset.to_assets(here)),
+			I::ReceiveTeleportedAsset(asset) => ReceiveTeleportedAsset(asset.to_assets(here)),
+			I::ReserveAssetDeposited(asset) => ReserveAssetDeposited(asset.to_assets(here)),
+			I::ClaimAsset(asset) =>
+				ClaimAsset { assets: asset.to_assets(here), ticket: Location::here() },
+			I::BuyExecution(asset) => BuyExecution {
+				fees: Asset { id: asset.kind.id(here), fun: Fungible(asset.amount.value()) },
+				weight_limit: Unlimited,
+			},
+			I::PayFees(asset) => PayFees {
+				asset: Asset { id: asset.kind.id(here), fun: Fungible(asset.amount.value()) },
+			},
+			I::BurnAsset(asset) => BurnAsset(asset.to_assets(here)),
+			I::ExpectAsset(asset) => ExpectAsset(asset.to_assets(here)),
+			I::DepositAsset(asset, account) =>
+				DepositAsset { assets: asset.to_filter(here), beneficiary: beneficiary(*account) },
+			I::TransferAsset(asset, account) =>
+				TransferAsset { assets: asset.to_assets(here), beneficiary: beneficiary(*account) },
+			I::InitiateTeleport(asset, dest, xcm) => InitiateTeleport {
+				assets: asset.to_filter(here),
+				dest: dest.location_from(here),
+				xcm: nested(xcm, *dest),
+			},
+			I::InitiateReserveWithdraw(asset, reserve, xcm) => InitiateReserveWithdraw {
+				assets: asset.to_filter(here),
+				reserve: reserve.location_from(here),
+				xcm: nested(xcm, *reserve),
+			},
+			I::DepositReserveAsset(asset, dest, xcm) => DepositReserveAsset {
+				assets: asset.to_filter(here),
+				dest: dest.location_from(here),
+				xcm: nested(xcm, *dest),
+			},
+			I::TransferReserveAsset(asset, dest, xcm) => TransferReserveAsset {
+				assets: asset.to_assets(here),
+				dest: dest.location_from(here),
+				xcm: nested(xcm, *dest),
+			},
+			I::SetAppendix(xcm) => SetAppendix(nested(xcm, here)),
+			I::SetErrorHandler(xcm) => SetErrorHandler(nested(xcm, here)),
+			I::RefundSurplus => RefundSurplus,
+			I::ClearError => ClearError,
+			I::ClearOrigin => ClearOrigin,
+			I::Trap(code) => Trap((*code).into()),
+		})
+		.collect())
+}
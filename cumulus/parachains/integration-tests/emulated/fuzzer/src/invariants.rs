@@ -0,0 +1,244 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Invariants checked after every program dispatched by the fuzzer.
+
+use std::collections::BTreeMap;
+
+use crate::generator::{AssetKind, ChainId};
+use emulated_integration_tests_common::{
+	xcm_emulator::{AccountId, Chain, Parachain, RelayChain, TestExt},
+	USDT_ID,
+};
+use frame_support::traits::{fungible, fungibles};
+use sp_core::H256;
+use westend_system_emulated_network::{
+	asset_hub_westend_emulated_chain::{
+		asset_hub_westend_runtime, AssetHubWestendParaPallet as AssetHubWestendPallet,
+	},
+	penpal_emulated_chain::{penpal_runtime, PenpalAParaPallet as PenpalAPallet},
+	westend_emulated_chain::{westend_runtime, WestendRelayPallet as WestendPallet},
+	AssetHubWestendPara as AssetHubWestend, PenpalAPara as PenpalA, WestendRelay as Westend,
+};
+use xcm::latest::prelude::*;
+
+/// A violated invariant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+	/// An asset was created out of thin air, e.g. by a teleport that was not matched by a burn on
+	/// the source chain.
+	IssuanceIncreased { asset: AssetKind, before: NetworkValue, after: NetworkValue },
+	/// Assets trapped by one origin were claimed by another one.
+	TrappedAssetsStolen { hash: H256, trapped_by: Location, claimed_by: Location },
+	/// Assets were claimed without having been trapped.
+	UnknownTrapClaimed { hash: H256, claimed_by: Location },
+}
+
+/// Assets whose supply across the network is tracked: the relay chain token and USDT, a
+/// trust-backed asset on Asset Hub held as a foreign asset on Penpal.
+pub const TRACKED_ASSETS: [AssetKind; 2] = [AssetKind::Relay, AssetKind::Usdt];
+
+/// An asset held on each chain of the network, excluding the amounts backing teleports and
+/// reserve transfers elsewhere.
+///
+/// Teleports and reserve transfers move value between the chains, so only the sum is conserved.
+/// Fees and trapped assets may only ever decrease it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetworkValue {
+	pub relay: u128,
+	pub asset_hub: u128,
+	pub penpal: u128,
+}
+
+impl NetworkValue {
+	/// The value held across the whole network.
+	pub fn total(&self) -> u128 {
+		self.relay.saturating_add(self.asset_hub).saturating_add(self.penpal)
+	}
+
+	/// Reads the current value of `asset` from the emulated network.
+	///
+	/// Assets that are not in [`TRACKED_ASSETS`] are not registered anywhere, so their value is
+	/// always zero.
+	pub fn read(asset: AssetKind) -> Self {
+		match asset {
+			AssetKind::Relay => Self::read_relay_token(),
+			AssetKind::Usdt => Self::read_usdt(),
+			AssetKind::Unknown(_) => Self::default(),
+		}
+	}
+
+	fn read_relay_token() -> Self {
+		use fungible::Inspect as _;
+		use fungibles::Inspect as _;
+
+		let penpal_on_relay = Westend::sovereign_account_id_of_child_para(PenpalA::para_id());
+		let penpal_on_asset_hub = penpal_on_asset_hub();
+
+		let relay = Westend::ext_wrapper(|| {
+			type Balances = <Westend as WestendPallet>::Balances;
+			let check_account = westend_runtime::xcm_config::CheckAccount::get();
+			Balances::total_issuance()
+				.saturating_sub(Balances::total_balance(&check_account))
+				.saturating_sub(Balances::total_balance(&penpal_on_relay))
+		});
+		let asset_hub = AssetHubWestend::ext_wrapper(|| {
+			type Balances = <AssetHubWestend as AssetHubWestendPallet>::Balances;
+			Balances::total_issuance().saturating_sub(Balances::total_balance(&penpal_on_asset_hub))
+		});
+		let penpal = PenpalA::ext_wrapper(|| {
+			type ForeignAssets = <PenpalA as PenpalAPallet>::ForeignAssets;
+			ForeignAssets::total_issuance(Location::parent())
+		});
+
+		Self { relay, asset_hub, penpal }
+	}
+
+	fn read_usdt() -> Self {
+		use fungibles::Inspect as _;
+
+		let penpal_on_asset_hub = penpal_on_asset_hub();
+
+		// USDT is not known to the relay chain
+		let asset_hub = AssetHubWestend::ext_wrapper(|| {
+			type Assets = <AssetHubWestend as AssetHubWestendPallet>::Assets;
+			Assets::total_issuance(USDT_ID)
+				.saturating_sub(Assets::total_balance(USDT_ID, &penpal_on_asset_hub))
+		});
+		let penpal = PenpalA::ext_wrapper(|| {
+			type ForeignAssets = <PenpalA as PenpalAPallet>::ForeignAssets;
+			ForeignAssets::total_issuance(penpal_runtime::xcm_config::UsdtFromAssetHub::get())
+		});
+
+		Self { relay: 0, asset_hub, penpal }
+	}
+}
+
+fn penpal_on_asset_hub() -> AccountId {
+	AssetHubWestend::sovereign_account_id_of(AssetHubWestend::sibling_location_of(
+		PenpalA::para_id(),
+	))
+}
+
+/// Keeps track of the state needed to check the invariants over a whole fuzzer input.
+pub struct Invariants {
+	initial_values: Vec<(AssetKind, NetworkValue)>,
+	traps: BTreeMap<(ChainId, H256), Location>,
+}
+
+impl Invariants {
+	/// Starts tracking the invariants from the current state of the network.
+	pub fn new() -> Self {
+		let initial_values = TRACKED_ASSETS
+			.into_iter()
+			.map(|asset| (asset, NetworkValue::read(asset)))
+			.collect();
+		Self { initial_values, traps: BTreeMap::new() }
+	}
+
+	/// Checks all the invariants against the current state of the network.
+	///
+	/// `local_events` are the trap events of the program dispatched on `chain`, which the emulator
+	/// clears before returning from `execute_with`. The events of the messages processed since then
+	/// are read here.
+	pub fn check(&mut self, chain: ChainId, local_events: Vec<TrapEvent>) -> Result<(), Violation> {
+		self.note_trap_events(chain, local_events)?;
+		for chain in [ChainId::Relay, ChainId::AssetHub, ChainId::Penpal] {
+			self.note_trap_events(chain, trap_events_of(chain))?;
+		}
+		self.check_value_conservation()
+	}
+
+	fn check_value_conservation(&self) -> Result<(), Violation> {
+		for (asset, before) in &self.initial_values {
+			let after = NetworkValue::read(*asset);
+			if after.total() > before.total() {
+				return Err(Violation::IssuanceIncreased { asset: *asset, before: *before, after })
+			}
+		}
+		Ok(())
+	}
+
+	fn note_trap_events(
+		&mut self,
+		chain: ChainId,
+		events: Vec<TrapEvent>,
+	) -> Result<(), Violation> {
+		// assets are trapped and claimed on the same chain, so the chain is part of the key
+		for event in events {
+			match event {
+				TrapEvent::Trapped { hash, origin } => {
+					self.traps.insert((chain, hash), origin);
+				},
+				TrapEvent::Claimed { hash, origin } => match self.traps.get(&(chain, hash)) {
+					Some(trapped_by) if *trapped_by == origin => {},
+					Some(trapped_by) =>
+						return Err(Violation::TrappedAssetsStolen {
+							hash,
+							trapped_by: trapped_by.clone(),
+							claimed_by: origin,
+						}),
+					None => return Err(Violation::UnknownTrapClaimed { hash, claimed_by: origin }),
+				},
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Trap related `pallet-xcm` events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrapEvent {
+	Trapped { hash: H256, origin: Location },
+	Claimed { hash: H256, origin: Location },
+}
+
+/// Returns the trap events deposited on `chain`.
+///
+/// Must be called within the externalities of `chain`.
+pub fn trap_events(chain: ChainId) -> Vec<TrapEvent> {
+	match chain {
+		ChainId::Relay => extract_trap_events::<westend_runtime::Runtime>(Westend::events()),
+		ChainId::AssetHub =>
+			extract_trap_events::<asset_hub_westend_runtime::Runtime>(AssetHubWestend::events()),
+		ChainId::Penpal => extract_trap_events::<penpal_runtime::Runtime>(PenpalA::events()),
+	}
+}
+
+fn trap_events_of(chain: ChainId) -> Vec<TrapEvent> {
+	match chain {
+		ChainId::Relay => Westend::ext_wrapper(|| trap_events(chain)),
+		ChainId::AssetHub => AssetHubWestend::ext_wrapper(|| trap_events(chain)),
+		ChainId::Penpal => PenpalA::ext_wrapper(|| trap_events(chain)),
+	}
+}
+
+fn extract_trap_events<Runtime: pallet_xcm::Config>(
+	events: Vec<<Runtime as frame_system::Config>::RuntimeEvent>,
+) -> Vec<TrapEvent>
+where
+	<Runtime as frame_system::Config>::RuntimeEvent: TryInto<pallet_xcm::Event<Runtime>>,
+{
+	events
+		.into_iter()
+		.filter_map(|event| match event.try_into().ok()? {
+			pallet_xcm::Event::AssetsTrapped { hash, origin, .. } =>
+				Some(TrapEvent::Trapped { hash, origin }),
+			pallet_xcm::Event::AssetsClaimed { hash, origin, .. } =>
+				Some(TrapEvent::Claimed { hash, origin }),
+			_ => None,
+		})
+		.collect()
+}
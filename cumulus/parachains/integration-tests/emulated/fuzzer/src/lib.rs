@@ -0,0 +1,113 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fuzzing harness for XCM over the emulated Westend system chains.
+//!
+//! Every input is a sequence of [`generator::Program`]s, each dispatched through `pallet-xcm` by
+//! a signed account on the relay chain, Asset Hub or Penpal. The messages they send are routed
+//! and executed by `xcm-emulator`, and the [`invariants`] are checked after every program.
+
+pub mod generator;
+pub mod invariants;
+
+use frame_support::traits::IntegrityTest;
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::{TryState, TryStateSelect::All};
+use westend_system_emulated_network::{
+	asset_hub_westend_emulated_chain::{
+		asset_hub_westend_runtime, AssetHubWestendParaPallet as AssetHubWestendPallet,
+	},
+	penpal_emulated_chain::{penpal_runtime, PenpalAParaPallet as PenpalAPallet},
+	westend_emulated_chain::{westend_runtime, WestendRelayPallet as WestendPallet},
+	AssetHubWestendPara as AssetHubWestend, PenpalAPara as PenpalA, WestendMockNet,
+	WestendRelay as Westend,
+};
+use xcm::prelude::*;
+
+use emulated_integration_tests_common::xcm_emulator::{Chain, Network, TestExt};
+use generator::{to_xcm, Action, ChainId, FuzzInput, Program};
+use invariants::{trap_events, Invariants, TrapEvent, Violation};
+
+/// Dispatches `$program` through the `pallet-xcm` instance `$xcm_pallet` of `$chain`, returning
+/// the trap events deposited while doing so.
+macro_rules! dispatch {
+	($chain:ident, $xcm_pallet:ident, $pallet_trait:ident, $program:expr) => {{
+		let program: &Program = $program;
+		$chain::execute_with(|| {
+			type XcmPallet = <$chain as $pallet_trait>::$xcm_pallet;
+			let origin = <$chain as Chain>::RuntimeOrigin::signed(program.sender.id());
+			// the dispatch result is irrelevant, only the state it leaves behind is checked
+			match &program.action {
+				Action::Execute(instructions) => {
+					let _ = XcmPallet::execute(
+						origin,
+						Box::new(VersionedXcm::from(to_xcm(instructions, program.chain).into())),
+						Weight::MAX,
+					);
+				},
+				Action::Send(dest, instructions) => {
+					let _ = XcmPallet::send(
+						origin,
+						Box::new(VersionedLocation::from(dest.location_from(program.chain))),
+						Box::new(VersionedXcm::from(to_xcm(instructions, *dest))),
+					);
+				},
+			}
+			trap_events(program.chain)
+		})
+	}};
+}
+
+/// Runs `input` on a freshly reset network.
+///
+/// Returns the first violated invariant, if any. Panics raised by the runtimes are not caught.
+pub fn run_input(input: &FuzzInput) -> Result<(), Violation> {
+	WestendMockNet::reset();
+	let mut invariants = Invariants::new();
+
+	for program in input.programs() {
+		let events: Vec<TrapEvent> = match program.chain {
+			ChainId::Relay => dispatch!(Westend, XcmPallet, WestendPallet, program),
+			ChainId::AssetHub =>
+				dispatch!(AssetHubWestend, PolkadotXcm, AssetHubWestendPallet, program),
+			ChainId::Penpal => dispatch!(PenpalA, PolkadotXcm, PenpalAPallet, program),
+		};
+		invariants.check(program.chain, events)?;
+		check_pallet_invariants();
+	}
+
+	Ok(())
+}
+
+/// Runs the integrity tests, and the `try_state` hooks with the `try-runtime` feature, of all
+/// pallets on all chains.
+fn check_pallet_invariants() {
+	Westend::ext_wrapper(|| {
+		#[cfg(feature = "try-runtime")]
+		westend_runtime::AllPalletsWithSystem::try_state(Default::default(), All).unwrap();
+		westend_runtime::AllPalletsWithSystem::integrity_test();
+	});
+	AssetHubWestend::ext_wrapper(|| {
+		#[cfg(feature = "try-runtime")]
+		asset_hub_westend_runtime::AllPalletsWithSystem::try_state(Default::default(), All)
+			.unwrap();
+		asset_hub_westend_runtime::AllPalletsWithSystem::integrity_test();
+	});
+	PenpalA::ext_wrapper(|| {
+		#[cfg(feature = "try-runtime")]
+		penpal_runtime::AllPalletsWithSystem::try_state(Default::default(), All).unwrap();
+		penpal_runtime::AllPalletsWithSystem::integrity_test();
+	});
+}
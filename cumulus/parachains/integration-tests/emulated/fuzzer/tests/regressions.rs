@@ -0,0 +1,126 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Replays the inputs stored in `regressions/`, together with a few handcrafted seeds.
+
+use std::{fs, path::PathBuf};
+
+use codec::Decode;
+use emulated_integration_tests_fuzzer::{
+	generator::{
+		Account, Action, Amount, AssetKind, ChainId, FuzzAsset, FuzzInput, FuzzInstruction as I,
+		Program,
+	},
+	run_input,
+};
+
+fn wnd(amount: Amount) -> FuzzAsset {
+	FuzzAsset { kind: AssetKind::Relay, amount }
+}
+
+fn program(chain: ChainId, sender: Account, action: Action) -> Program {
+	Program { chain, sender, action }
+}
+
+#[test]
+fn stored_regressions_hold() {
+	let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("regressions");
+	let mut replayed = 0;
+	for entry in fs::read_dir(dir).unwrap() {
+		let path = entry.unwrap().path();
+		if path.extension().map_or(true, |extension| extension != "scale") {
+			continue
+		}
+		let bytes = fs::read(&path).unwrap();
+		let input = FuzzInput::decode(&mut &bytes[..])
+			.unwrap_or_else(|e| panic!("{} is not a valid input: {e:?}", path.display()));
+		assert_eq!(run_input(&input), Ok(()), "regression {} failed", path.display());
+		replayed += 1;
+	}
+	assert!(replayed > 0, "no regression was replayed");
+}
+
+#[test]
+fn teleport_round_trip_holds() {
+	let teleport_back = vec![
+		I::BuyExecution(wnd(Amount::Small)),
+		I::InitiateTeleport(
+			wnd(Amount::Large),
+			ChainId::Relay,
+			vec![
+				I::BuyExecution(wnd(Amount::Small)),
+				I::DepositAsset(wnd(Amount::Max), Account::Bob),
+			],
+		),
+	];
+	let input = FuzzInput {
+		programs: vec![
+			program(
+				ChainId::Relay,
+				Account::Alice,
+				Action::Execute(vec![
+					I::WithdrawAsset(wnd(Amount::Large)),
+					I::InitiateTeleport(wnd(Amount::Max), ChainId::AssetHub, teleport_back),
+				]),
+			),
+			// receiving assets that were never sent must not mint anything
+			program(
+				ChainId::Relay,
+				Account::Bob,
+				Action::Send(
+					ChainId::AssetHub,
+					vec![
+						I::ReceiveTeleportedAsset(wnd(Amount::Large)),
+						I::DepositAsset(wnd(Amount::Max), Account::Bob),
+					],
+				),
+			),
+		],
+	};
+	assert_eq!(run_input(&input), Ok(()));
+}
+
+#[test]
+fn trapped_assets_cannot_be_claimed_by_others() {
+	let input = FuzzInput {
+		programs: vec![
+			// trap some WND on Asset Hub, as Alice
+			program(
+				ChainId::AssetHub,
+				Account::Alice,
+				Action::Execute(vec![I::WithdrawAsset(wnd(Amount::Small)), I::Trap(1)]),
+			),
+			// Bob attempts to claim them
+			program(
+				ChainId::AssetHub,
+				Account::Bob,
+				Action::Execute(vec![
+					I::ClaimAsset(wnd(Amount::Small)),
+					I::DepositAsset(wnd(Amount::Max), Account::Bob),
+				]),
+			),
+			// Alice claims them back
+			program(
+				ChainId::AssetHub,
+				Account::Alice,
+				Action::Execute(vec![
+					I::ClaimAsset(wnd(Amount::Small)),
+					I::DepositAsset(wnd(Amount::Max), Account::Alice),
+				]),
+			),
+		],
+	};
+	assert_eq!(run_input(&input), Ok(()));
+}
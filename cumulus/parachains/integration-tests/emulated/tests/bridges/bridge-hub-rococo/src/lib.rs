@@ -54,6 +54,7 @@ mod imports {
 			BridgeHubRococoParaPallet as BridgeHubRococoPallet, BridgeHubRococoRuntimeOrigin,
 			BridgeHubRococoXcmConfig, EthereumBeaconClient, EthereumInboundQueue,
 		},
+		bridge_hub_westend_emulated_chain::BridgeHubWestendParaPallet as BridgeHubWestendPallet,
 		penpal_emulated_chain::{
 			penpal_runtime::{
 				self,
@@ -75,7 +76,7 @@ mod imports {
 		BridgeHubRococoParaReceiver as BridgeHubRococoReceiver,
 		BridgeHubRococoParaSender as BridgeHubRococoSender,
 		BridgeHubWestendPara as BridgeHubWestend, PenpalAPara as PenpalA,
-		PenpalAParaSender as PenpalASender, RococoRelay as Rococo,
+		PenpalAParaSender as PenpalASender, PenpalBPara as PenpalB, RococoRelay as Rococo,
 		RococoRelayReceiver as RococoReceiver, RococoRelaySender as RococoSender,
 	};
 
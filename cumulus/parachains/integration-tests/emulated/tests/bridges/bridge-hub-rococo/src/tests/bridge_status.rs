@@ -0,0 +1,261 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{imports::*, tests::*};
+use pallet_xcm_bridge_hub::BridgeState;
+
+#[test]
+fn bridge_status_is_consistent_at_both_bridge_hubs() {
+	// the bridge between asset hubs is opened at genesis, so both bridge hubs must report it
+	let status_at_rococo = BridgeHubRococo::execute_with(|| {
+		type XcmOverBridgeHubWestend =
+			<BridgeHubRococo as BridgeHubRococoPallet>::XcmOverBridgeHubWestend;
+		XcmOverBridgeHubWestend::bridge_status(
+			BridgeHubRococo::sibling_location_of(AssetHubRococo::para_id()),
+			asset_hub_westend_location().interior().clone().into(),
+		)
+	})
+	.expect("AHR -> AHW bridge is opened at genesis");
+	let status_at_westend = BridgeHubWestend::execute_with(|| {
+		type XcmOverBridgeHubRococo =
+			<BridgeHubWestend as BridgeHubWestendPallet>::XcmOverBridgeHubRococo;
+		XcmOverBridgeHubRococo::bridge_status(
+			BridgeHubWestend::sibling_location_of(AssetHubWestend::para_id()),
+			[
+				GlobalConsensus(ByGenesis(ROCOCO_GENESIS_HASH)),
+				Parachain(AssetHubRococo::para_id().into()),
+			]
+			.into(),
+		)
+	})
+	.expect("AHW -> AHR bridge is opened at genesis");
+
+	assert_eq!(status_at_rococo.state, Some(BridgeState::Opened));
+	assert_eq!(status_at_westend.state, Some(BridgeState::Opened));
+	assert_eq!(status_at_rococo.lane_id, status_at_westend.lane_id);
+}
+
+fn penpal_a_universal_location() -> InteriorLocation {
+	[GlobalConsensus(ByGenesis(ROCOCO_GENESIS_HASH)), Parachain(PenpalA::para_id().into())].into()
+}
+
+fn penpal_b_universal_location() -> InteriorLocation {
+	[GlobalConsensus(ByGenesis(WESTEND_GENESIS_HASH)), Parachain(PenpalB::para_id().into())].into()
+}
+
+/// Returns state and encoded lane identifier of the PenpalA -> PenpalB bridge.
+fn bridge_status_at_rococo() -> (Option<BridgeState>, Vec<u8>) {
+	type XcmOverBridgeHubWestend =
+		<BridgeHubRococo as BridgeHubRococoPallet>::XcmOverBridgeHubWestend;
+	let status = XcmOverBridgeHubWestend::bridge_status(
+		BridgeHubRococo::sibling_location_of(PenpalA::para_id()),
+		penpal_b_universal_location().into(),
+	)
+	.expect("bridge status is known for valid locations");
+	(status.state, status.lane_id.encode())
+}
+
+/// Returns state and encoded lane identifier of the PenpalB -> PenpalA bridge.
+fn bridge_status_at_westend() -> (Option<BridgeState>, Vec<u8>) {
+	type XcmOverBridgeHubRococo =
+		<BridgeHubWestend as BridgeHubWestendPallet>::XcmOverBridgeHubRococo;
+	let status = XcmOverBridgeHubRococo::bridge_status(
+		BridgeHubWestend::sibling_location_of(PenpalB::para_id()),
+		penpal_a_universal_location().into(),
+	)
+	.expect("bridge status is known for valid locations");
+	(status.state, status.lane_id.encode())
+}
+
+fn reserved_balance_at_rococo() -> u128 {
+	type Balances = <BridgeHubRococo as BridgeHubRococoPallet>::Balances;
+	Balances::reserved_balance(BridgeHubRococo::sovereign_account_id_of(
+		BridgeHubRococo::sibling_location_of(PenpalA::para_id()),
+	))
+}
+
+fn reserved_balance_at_westend() -> u128 {
+	type Balances = <BridgeHubWestend as BridgeHubWestendPallet>::Balances;
+	Balances::reserved_balance(BridgeHubWestend::sovereign_account_id_of(
+		BridgeHubWestend::sibling_location_of(PenpalB::para_id()),
+	))
+}
+
+#[test]
+fn bridge_is_opened_and_closed_by_sibling_parachains_over_xcm() {
+	use testnet_parachains_constants::{
+		rococo::currency::UNITS as ROC, westend::currency::UNITS as WND,
+	};
+
+	let penpal_a_sovereign_at_rococo = BridgeHubRococo::sovereign_account_id_of(
+		BridgeHubRococo::sibling_location_of(PenpalA::para_id()),
+	);
+	let penpal_b_sovereign_at_westend = BridgeHubWestend::sovereign_account_id_of(
+		BridgeHubWestend::sibling_location_of(PenpalB::para_id()),
+	);
+	let rococo_fee = Some(((Parent, ROC).into(), penpal_a_sovereign_at_rococo));
+	let westend_fee = Some(((Parent, WND).into(), penpal_b_sovereign_at_westend));
+	BridgeHubRococo::fund_para_sovereign(PenpalA::para_id(), ROC * 10);
+	BridgeHubWestend::fund_para_sovereign(PenpalB::para_id(), WND * 20);
+
+	// PenpalA opens the bridge at Rococo. The bridge is opening until the handshake of the
+	// bridged side is received
+	PenpalA::open_bridge(
+		PenpalA::sibling_location_of(BridgeHubRococo::para_id()),
+		penpal_b_universal_location(),
+		rococo_fee.clone(),
+	);
+	let rococo_deposit = BridgeHubRococo::execute_with(|| {
+		assert_eq!(bridge_status_at_rococo().0, Some(BridgeState::Opening));
+		reserved_balance_at_rococo()
+	});
+	assert!(rococo_deposit > 0);
+
+	// PenpalB opens the bridge at Westend. The handshake of Rococo side is still waiting for
+	// the lane, so the handshake of Westend side is the first one that is delivered
+	PenpalB::open_bridge(
+		PenpalB::sibling_location_of(BridgeHubWestend::para_id()),
+		penpal_a_universal_location(),
+		westend_fee.clone(),
+	);
+	let westend_deposit = BridgeHubWestend::execute_with(|| {
+		assert_eq!(bridge_status_at_westend().0, Some(BridgeState::Opening));
+		reserved_balance_at_westend()
+	});
+	assert!(westend_deposit > 0);
+
+	// handshake of Westend side has been delivered to Rococo and Rococo handshake is relayed
+	// to Westend
+	let lane_at_rococo = BridgeHubRococo::execute_with(|| {
+		let (state, lane_id) = bridge_status_at_rococo();
+		assert_eq!(state, Some(BridgeState::Opened));
+		lane_id
+	});
+	let lane_at_westend = BridgeHubWestend::execute_with(|| {
+		let (state, lane_id) = bridge_status_at_westend();
+		assert_eq!(state, Some(BridgeState::Opened));
+		lane_id
+	});
+	assert_eq!(lane_at_rococo, lane_at_westend);
+
+	// PenpalB closes the bridge at Westend. There are no queued messages, so the bridge is
+	// pruned immediately
+	PenpalB::close_bridge(
+		PenpalB::sibling_location_of(BridgeHubWestend::para_id()),
+		penpal_a_universal_location(),
+		16,
+		westend_fee,
+	);
+	BridgeHubWestend::execute_with(|| {
+		type RuntimeEvent = <BridgeHubWestend as Chain>::RuntimeEvent;
+		assert_expected_events!(
+			BridgeHubWestend,
+			vec![
+				RuntimeEvent::XcmOverBridgeHubRococo(
+					pallet_xcm_bridge_hub::Event::BridgePruned { lane_id, bridge_deposit, .. }
+				) => {
+					lane_id: lane_id.encode() == lane_at_westend,
+					bridge_deposit: *bridge_deposit == westend_deposit,
+				},
+			]
+		);
+		assert_eq!(bridge_status_at_westend().0, None);
+		assert_eq!(reserved_balance_at_westend(), 0);
+	});
+
+	// a message is queued at Rococo, but it can't be delivered, because the lane is already
+	// pruned at Westend
+	BridgeHubRococo::execute_with(|| {
+		type XcmOverBridgeHubWestend =
+			<BridgeHubRococo as BridgeHubRococoPallet>::XcmOverBridgeHubWestend;
+		assert_ok!(xcm_executor::traits::export_xcm::<XcmOverBridgeHubWestend>(
+			ByGenesis(WESTEND_GENESIS_HASH),
+			0,
+			penpal_a_universal_location(),
+			[Parachain(PenpalB::para_id().into())].into(),
+			Xcm(vec![ClearOrigin]),
+		));
+	});
+
+	// PenpalA closes the bridge at Rococo without pruning messages, so the bridge is closing
+	PenpalA::close_bridge(
+		PenpalA::sibling_location_of(BridgeHubRococo::para_id()),
+		penpal_b_universal_location(),
+		0,
+		rococo_fee.clone(),
+	);
+	BridgeHubRococo::execute_with(|| {
+		type RuntimeEvent = <BridgeHubRococo as Chain>::RuntimeEvent;
+		assert_expected_events!(
+			BridgeHubRococo,
+			vec![
+				RuntimeEvent::XcmOverBridgeHubWestend(
+					pallet_xcm_bridge_hub::Event::ClosingBridge { lane_id, enqueued_messages, .. }
+				) => {
+					lane_id: lane_id.encode() == lane_at_rococo,
+					enqueued_messages: *enqueued_messages == 1,
+				},
+			]
+		);
+		assert_eq!(bridge_status_at_rococo().0, Some(BridgeState::Closed));
+		assert_eq!(
+			pallet_xcm_bridge_hub::ClosingBridges::<
+				<BridgeHubRococo as Chain>::Runtime,
+				pallet_xcm_bridge_hub::Instance1,
+			>::iter()
+			.count(),
+			1,
+		);
+		assert_eq!(reserved_balance_at_rococo(), rococo_deposit);
+	});
+
+	// PenpalA prunes the remaining message, so the bridge is pruned and the deposit is returned
+	PenpalA::close_bridge(
+		PenpalA::sibling_location_of(BridgeHubRococo::para_id()),
+		penpal_b_universal_location(),
+		1,
+		rococo_fee,
+	);
+	BridgeHubRococo::execute_with(|| {
+		type RuntimeEvent = <BridgeHubRococo as Chain>::RuntimeEvent;
+		assert_expected_events!(
+			BridgeHubRococo,
+			vec![
+				RuntimeEvent::XcmOverBridgeHubWestend(
+					pallet_xcm_bridge_hub::Event::BridgePruned {
+						lane_id,
+						bridge_deposit,
+						pruned_messages,
+						..
+					}
+				) => {
+					lane_id: lane_id.encode() == lane_at_rococo,
+					bridge_deposit: *bridge_deposit == rococo_deposit,
+					pruned_messages: *pruned_messages == 1,
+				},
+			]
+		);
+		assert_eq!(bridge_status_at_rococo().0, None);
+		assert_eq!(
+			pallet_xcm_bridge_hub::ClosingBridges::<
+				<BridgeHubRococo as Chain>::Runtime,
+				pallet_xcm_bridge_hub::Instance1,
+			>::iter()
+			.count(),
+			0,
+		);
+		assert_eq!(reserved_balance_at_rococo(), 0);
+	});
+}
@@ -12,17 +12,17 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-use crate::imports::*;
+use crate::{create_pool_with_native_on, imports::*};
 use asset_hub_westend_runtime::xcm_config::bridging::to_ethereum::DefaultBridgeHubEthereumBaseFee;
 use bridge_hub_westend_runtime::EthereumInboundQueue;
 use codec::{Decode, Encode};
-use emulated_integration_tests_common::RESERVABLE_ASSET_ID;
+use emulated_integration_tests_common::{PenpalBTeleportableAssetLocation, RESERVABLE_ASSET_ID};
 use frame_support::pallet_prelude::TypeInfo;
 use hex_literal::hex;
 use rococo_westend_system_emulated_network::asset_hub_westend_emulated_chain::genesis::AssetHubWestendAssetOwner;
 use snowbridge_core::{outbound::OperatingMode, AssetMetadata, TokenIdOf};
 use snowbridge_router_primitives::inbound::{
-	Command, Destination, EthereumLocationsConverterFor, MessageV1, VersionedMessage,
+	Command, Destination, EthereumLocationsConverterFor, MessageV1, MessageV2, VersionedMessage,
 };
 use sp_core::H256;
 use testnet_parachains_constants::westend::snowbridge::EthereumNetwork;
@@ -605,3 +605,351 @@ fn transfer_ah_token() {
 		);
 	});
 }
+
+/// Tests the lock-and-mint and burn-and-unlock flows of a token, registered on Ethereum by the
+/// sibling parachain that owns it. The token is teleported to AssetHub and locked there, while a
+/// wrapped ERC20 token is minted on Ethereum.
+#[test]
+fn transfer_penpal_token() {
+	let penpal_location_on_bh = BridgeHubWestend::sibling_location_of(PenpalB::para_id());
+	let penpal_sovereign_on_bh =
+		BridgeHubWestend::sovereign_account_id_of(penpal_location_on_bh.clone());
+	let assethub_sovereign = BridgeHubWestend::sovereign_account_id_of(
+		BridgeHubWestend::sibling_location_of(AssetHubWestend::para_id()),
+	);
+	BridgeHubWestend::fund_accounts(vec![
+		(assethub_sovereign, INITIAL_FUND),
+		(penpal_sovereign_on_bh.clone(), INITIAL_FUND),
+	]);
+
+	let ethereum_destination = Location::new(2, [GlobalConsensus(Ethereum { chain_id: CHAIN_ID })]);
+
+	let ethereum_sovereign: AccountId =
+		EthereumLocationsConverterFor::<[u8; 32]>::convert_location(&ethereum_destination)
+			.unwrap()
+			.into();
+	AssetHubWestend::fund_accounts(vec![(ethereum_sovereign.clone(), INITIAL_FUND)]);
+
+	// the token has the same location in the AssetHub and BridgeHub contexts
+	let asset_id: Location = PenpalBTeleportableAssetLocation::get();
+
+	let asset_id_after_reanchored = Location::new(
+		1,
+		[GlobalConsensus(ByGenesis(WESTEND_GENESIS_HASH)), Parachain(PenpalB::para_id().into())],
+	)
+	.appended_with(asset_id.clone().interior.split_first().0)
+	.unwrap();
+
+	let token_id = TokenIdOf::convert_location(&asset_id_after_reanchored).unwrap();
+
+	let metadata = AssetMetadata {
+		name: "penpal_asset".as_bytes().to_vec().try_into().unwrap(),
+		symbol: "penpal_asset".as_bytes().to_vec().try_into().unwrap(),
+		decimals: 12,
+	};
+
+	// Register token on behalf of PenpalB
+	BridgeHubWestend::execute_with(|| {
+		type RuntimeOrigin = <BridgeHubWestend as Chain>::RuntimeOrigin;
+		type RuntimeEvent = <BridgeHubWestend as Chain>::RuntimeEvent;
+
+		let penpal_balance_before =
+			<BridgeHubWestend as BridgeHubWestendPallet>::Balances::free_balance(
+				&penpal_sovereign_on_bh,
+			);
+
+		assert_ok!(
+			<BridgeHubWestend as BridgeHubWestendPallet>::EthereumSystem::register_sibling_token(
+				RuntimeOrigin::from(pallet_xcm::Origin::Xcm(penpal_location_on_bh.clone())),
+				Box::new(VersionedLocation::from(asset_id.clone())),
+				metadata.clone(),
+			)
+		);
+
+		assert_expected_events!(
+			BridgeHubWestend,
+			vec![
+				RuntimeEvent::EthereumSystem(snowbridge_pallet_system::Event::RegisterToken {
+					foreign_token_id, ..
+				}) => { foreign_token_id: *foreign_token_id == token_id, },
+				RuntimeEvent::EthereumOutboundQueue(
+					snowbridge_pallet_outbound_queue::Event::MessageQueued { .. }
+				) => {},
+			]
+		);
+
+		// PenpalB has paid for the registration
+		assert!(
+			<BridgeHubWestend as BridgeHubWestendPallet>::Balances::free_balance(
+				&penpal_sovereign_on_bh
+			) < penpal_balance_before
+		);
+	});
+
+	// Mint some token on AssetHub, as if it has been teleported from PenpalB
+	AssetHubWestend::mint_foreign_asset(
+		<AssetHubWestend as Chain>::RuntimeOrigin::signed(
+			AssetHubWestend::sovereign_account_id_of(AssetHubWestend::sibling_location_of(
+				PenpalB::para_id(),
+			)),
+		),
+		asset_id.clone(),
+		AssetHubWestendSender::get(),
+		TOKEN_AMOUNT,
+	);
+
+	// Send token to Ethereum
+	AssetHubWestend::execute_with(|| {
+		type RuntimeOrigin = <AssetHubWestend as Chain>::RuntimeOrigin;
+		type RuntimeEvent = <AssetHubWestend as Chain>::RuntimeEvent;
+
+		let assets =
+			vec![Asset { id: AssetId(asset_id.clone()), fun: Fungible(TOKEN_AMOUNT / 10) }];
+		let versioned_assets = VersionedAssets::from(Assets::from(assets));
+
+		let beneficiary = VersionedLocation::from(Location::new(
+			0,
+			[AccountKey20 { network: None, key: ETHEREUM_DESTINATION_ADDRESS.into() }],
+		));
+
+		assert_ok!(<AssetHubWestend as AssetHubWestendPallet>::PolkadotXcm::limited_reserve_transfer_assets(
+			RuntimeOrigin::signed(AssetHubWestendSender::get()),
+			Box::new(VersionedLocation::from(ethereum_destination)),
+			Box::new(beneficiary),
+			Box::new(versioned_assets),
+			0,
+			Unlimited,
+		));
+
+		let events = AssetHubWestend::events();
+		// Check that the token is locked in the sovereign account of Ethereum
+		assert!(
+			events.iter().any(|event| matches!(
+				event,
+				RuntimeEvent::ForeignAssets(pallet_assets::Event::Transferred { asset_id: id, to, .. })
+					if *id == asset_id && *to == ethereum_sovereign.clone()
+			)),
+			"token locked in Ethereum sovereign account."
+		);
+	});
+
+	// Send token back from Ethereum
+	BridgeHubWestend::execute_with(|| {
+		type RuntimeEvent = <BridgeHubWestend as Chain>::RuntimeEvent;
+
+		// Check that the message to mint the wrapped token was queued in the Ethereum Outbound
+		// Queue
+		assert_expected_events!(
+			BridgeHubWestend,
+			vec![RuntimeEvent::EthereumOutboundQueue(snowbridge_pallet_outbound_queue::Event::MessageQueued{ .. }) => {},]
+		);
+
+		let message = VersionedMessage::V1(MessageV1 {
+			chain_id: CHAIN_ID,
+			command: Command::SendNativeToken {
+				token_id,
+				destination: Destination::AccountId32 { id: AssetHubWestendReceiver::get().into() },
+				amount: TOKEN_AMOUNT / 10,
+				fee: XCM_FEE,
+			},
+		});
+		// Convert the message to XCM
+		let (xcm, _) = EthereumInboundQueue::do_convert([0; 32].into(), message).unwrap();
+		// Send the XCM
+		let _ = EthereumInboundQueue::send_xcm(xcm, AssetHubWestend::para_id().into()).unwrap();
+
+		assert_expected_events!(
+			BridgeHubWestend,
+			vec![RuntimeEvent::XcmpQueue(cumulus_pallet_xcmp_queue::Event::XcmpMessageSent { .. }) => {},]
+		);
+	});
+
+	AssetHubWestend::execute_with(|| {
+		type RuntimeEvent = <AssetHubWestend as Chain>::RuntimeEvent;
+
+		let events = AssetHubWestend::events();
+
+		// Check that the token is unlocked from the sovereign account of Ethereum
+		assert!(
+			events.iter().any(|event| matches!(
+				event,
+				RuntimeEvent::ForeignAssets(pallet_assets::Event::Burned { asset_id: id, owner, .. })
+					if *id == asset_id && *owner == ethereum_sovereign.clone(),
+			)),
+			"token unlocked from Ethereum sovereign account."
+		);
+
+		// Check that the token was deposited to beneficiary
+		assert!(
+			events.iter().any(|event| matches!(
+				event,
+				RuntimeEvent::ForeignAssets(pallet_assets::Event::Issued { asset_id: id, owner, .. })
+					if *id == asset_id && *owner == AssetHubWestendReceiver::get()
+			)),
+			"token deposited to beneficiary."
+		);
+	});
+}
+
+const ETHER_AMOUNT: u128 = 1_000_000_000;
+
+/// Creates the Ether foreign asset on AssetHub, with a pool to pay for execution in Ether, and
+/// returns the location of the Ethereum account, used as the sender of V2 messages.
+fn create_ether_and_sender_location() -> (Location, Location) {
+	let ethereum_network_v5: NetworkId = EthereumNetwork::get().into();
+	let ether_location = Location::new(2, [GlobalConsensus(ethereum_network_v5)]);
+	let sender_location = Location::new(
+		2,
+		[
+			GlobalConsensus(ethereum_network_v5),
+			AccountKey20 { network: None, key: ETHEREUM_DESTINATION_ADDRESS },
+		],
+	);
+
+	AssetHubWestend::execute_with(|| {
+		type RuntimeOrigin = <AssetHubWestend as Chain>::RuntimeOrigin;
+
+		assert_ok!(<AssetHubWestend as AssetHubWestendPallet>::ForeignAssets::force_create(
+			RuntimeOrigin::root(),
+			ether_location.clone(),
+			AssetHubWestendAssetOwner::get().into(),
+			true,
+			1,
+		));
+	});
+	AssetHubWestend::fund_accounts(vec![(AssetHubWestendAssetOwner::get(), INITIAL_FUND)]);
+	create_pool_with_native_on!(
+		AssetHubWestend,
+		ether_location.clone(),
+		true,
+		AssetHubWestendAssetOwner::get()
+	);
+
+	(ether_location, sender_location)
+}
+
+/// Sends V2 message with the given user program from Ethereum to AssetHub.
+fn send_user_xcm_from_ethereum(user_xcm: Vec<u8>) {
+	BridgeHubWestend::execute_with(|| {
+		type RuntimeEvent = <BridgeHubWestend as Chain>::RuntimeEvent;
+
+		let message = VersionedMessage::V2(MessageV2 {
+			chain_id: CHAIN_ID,
+			origin: ETHEREUM_DESTINATION_ADDRESS.into(),
+			value: ETHER_AMOUNT,
+			fee: XCM_FEE,
+			xcm: user_xcm,
+		});
+		// execution is paid in Ether on AssetHub, so there are no fees to burn here
+		let (xcm, fee) = EthereumInboundQueue::do_convert([0; 32].into(), message).unwrap();
+		assert_eq!(fee, 0);
+		let _ = EthereumInboundQueue::send_xcm(xcm, AssetHubWestend::para_id().into()).unwrap();
+
+		assert_expected_events!(
+			BridgeHubWestend,
+			vec![RuntimeEvent::XcmpQueue(cumulus_pallet_xcmp_queue::Event::XcmpMessageSent { .. }) => {},]
+		);
+	});
+}
+
+/// Tests that the program from Ethereum account is executed on AssetHub, paying for execution in
+/// Ether, and that the Ether and leftover fees are deposited to the sender.
+#[test]
+fn send_user_xcm_from_ethereum_to_asset_hub() {
+	let (ether_location, sender_location) = create_ether_and_sender_location();
+	let receiver: AccountId = AssetHubWestendReceiver::get();
+
+	// send half of the Ether to the receiver, the rest is deposited to the sender
+	let user_xcm: Xcm<()> = vec![DepositAsset {
+		assets: Definite((ether_location.clone(), ETHER_AMOUNT / 2).into()),
+		beneficiary: AccountId32 { network: None, id: receiver.clone().into() }.into(),
+	}]
+	.into();
+	send_user_xcm_from_ethereum(VersionedXcm::from(user_xcm).encode());
+
+	AssetHubWestend::execute_with(|| {
+		type RuntimeEvent = <AssetHubWestend as Chain>::RuntimeEvent;
+		type ForeignAssets = <AssetHubWestend as AssetHubWestendPallet>::ForeignAssets;
+
+		assert_expected_events!(
+			AssetHubWestend,
+			vec![RuntimeEvent::MessageQueue(
+				pallet_message_queue::Event::Processed { success: true, .. }
+			) => {},]
+		);
+
+		let sender =
+			EthereumLocationsConverterFor::<AccountId>::convert_location(&sender_location).unwrap();
+		assert_eq!(
+			<ForeignAssets as Inspect<_>>::balance(ether_location.clone(), &receiver),
+			ETHER_AMOUNT / 2
+		);
+		// the sender gets the rest of the Ether, and the part of the fee that was not swapped
+		let sender_balance = <ForeignAssets as Inspect<_>>::balance(ether_location, &sender);
+		assert!(sender_balance > ETHER_AMOUNT / 2);
+		assert!(sender_balance < ETHER_AMOUNT / 2 + XCM_FEE);
+	});
+}
+
+/// Tests that the disallowed program from Ethereum account is not executed on AssetHub, but
+/// the Ether is not lost.
+#[test]
+fn disallowed_user_xcm_from_ethereum_is_not_executed() {
+	let (ether_location, sender_location) = create_ether_and_sender_location();
+
+	let user_xcm: Xcm<()> =
+		vec![UnpaidExecution { weight_limit: Unlimited, check_origin: None }].into();
+	send_user_xcm_from_ethereum(VersionedXcm::from(user_xcm).encode());
+
+	AssetHubWestend::execute_with(|| {
+		type RuntimeEvent = <AssetHubWestend as Chain>::RuntimeEvent;
+		type ForeignAssets = <AssetHubWestend as AssetHubWestendPallet>::ForeignAssets;
+
+		assert_expected_events!(
+			AssetHubWestend,
+			vec![RuntimeEvent::MessageQueue(
+				pallet_message_queue::Event::Processed { success: true, .. }
+			) => {},]
+		);
+
+		let sender =
+			EthereumLocationsConverterFor::<AccountId>::convert_location(&sender_location).unwrap();
+		let sender_balance = <ForeignAssets as Inspect<_>>::balance(ether_location, &sender);
+		assert!(sender_balance > ETHER_AMOUNT);
+		assert!(sender_balance < ETHER_AMOUNT + XCM_FEE);
+	});
+}
+
+/// Tests that AssetHub doesn't execute disallowed program from Ethereum account, even if it
+/// wasn't filtered by the BridgeHub.
+#[test]
+fn asset_hub_barrier_denies_disallowed_user_xcm_from_ethereum() {
+	let _ = create_ether_and_sender_location();
+
+	BridgeHubWestend::execute_with(|| {
+		let message = VersionedMessage::V2(MessageV2 {
+			chain_id: CHAIN_ID,
+			origin: ETHEREUM_DESTINATION_ADDRESS.into(),
+			value: ETHER_AMOUNT,
+			fee: XCM_FEE,
+			xcm: vec![],
+		});
+		let (mut xcm, _) = EthereumInboundQueue::do_convert([0; 32].into(), message).unwrap();
+		// emulate the bridge hub that doesn't check the user program
+		let topic = xcm.0.pop().unwrap();
+		xcm.0.push(UniversalOrigin(GlobalConsensus(Polkadot)));
+		xcm.0.push(topic);
+		let _ = EthereumInboundQueue::send_xcm(xcm, AssetHubWestend::para_id().into()).unwrap();
+	});
+
+	AssetHubWestend::execute_with(|| {
+		type RuntimeEvent = <AssetHubWestend as Chain>::RuntimeEvent;
+
+		assert_expected_events!(
+			AssetHubWestend,
+			vec![RuntimeEvent::MessageQueue(
+				pallet_message_queue::Event::ProcessingFailed { .. }
+			) => {},]
+		);
+	});
+}
@@ -1406,6 +1406,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl bp_bridge_hub_westend::ToBridgeHubWestendXcmBridgeHubRouterApi<Block> for Runtime {
+		fn bridge_state() -> pallet_xcm_bridge_hub_router::BridgeState {
+			ToWestendXcmRouter::bridge_state()
+		}
+	}
+
 	impl xcm_runtime_apis::fees::XcmPaymentApi<Block> for Runtime {
 		fn query_acceptable_payment_assets(xcm_version: xcm::Version) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
 			let native_token = xcm_config::TokenLocation::get();
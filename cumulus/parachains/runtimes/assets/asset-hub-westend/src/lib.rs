@@ -1522,6 +1522,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl bp_bridge_hub_rococo::ToBridgeHubRococoXcmBridgeHubRouterApi<Block> for Runtime {
+		fn bridge_state() -> pallet_xcm_bridge_hub_router::BridgeState {
+			ToRococoXcmRouter::bridge_state()
+		}
+	}
+
 	impl xcm_runtime_apis::fees::XcmPaymentApi<Block> for Runtime {
 		fn query_acceptable_payment_assets(xcm_version: xcm::Version) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
 			let native_token = xcm_config::WestendLocation::get();
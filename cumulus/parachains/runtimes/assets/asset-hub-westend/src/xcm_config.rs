@@ -42,7 +42,9 @@ use parachains_common::{
 };
 use polkadot_parachain_primitives::primitives::Sibling;
 use polkadot_runtime_common::xcm_sender::ExponentialPrice;
-use snowbridge_router_primitives::inbound::EthereumLocationsConverterFor;
+use snowbridge_router_primitives::inbound::{
+	DenyDisallowedXcmFromEthereum, EthereumLocationsConverterFor,
+};
 use sp_runtime::traits::{AccountIdConversion, ConvertInto, TryConvertInto};
 use xcm::latest::{prelude::*, ROCOCO_GENESIS_HASH, WESTEND_GENESIS_HASH};
 use xcm_builder::{
@@ -281,34 +283,39 @@ impl Contains<Location> for AmbassadorEntities {
 pub type Barrier = TrailingSetTopicAsId<
 	DenyThenTry<
 		DenyReserveTransferToRelayChain,
-		(
-			TakeWeightCredit,
-			// Expected responses are OK.
-			AllowKnownQueryResponses<PolkadotXcm>,
-			// Allow XCMs with some computed origins to pass through.
-			WithComputedOrigin<
-				(
-					// If the message is one that immediately attempts to pay for execution, then
-					// allow it.
-					AllowTopLevelPaidExecutionFrom<Everything>,
-					// Parent, its pluralities (i.e. governance bodies), relay treasury pallet and
-					// BridgeHub get free execution.
-					AllowExplicitUnpaidExecutionFrom<(
-						ParentOrParentsPlurality,
-						Equals<RelayTreasuryLocation>,
-						Equals<bridging::SiblingBridgeHub>,
-						FellowshipEntities,
-						AmbassadorEntities,
-					)>,
-					// Subscriptions for version tracking are OK.
-					AllowSubscriptionsFrom<Everything>,
-					// HRMP notifications from the relay chain are OK.
-					AllowHrmpNotificationsFromRelayChain,
-				),
-				UniversalLocation,
-				ConstU32<8>,
-			>,
-		),
+		DenyThenTry<
+			// Programs from Ethereum accounts are filtered by the BridgeHub, but let's not trust
+			// it blindly.
+			DenyDisallowedXcmFromEthereum,
+			(
+				TakeWeightCredit,
+				// Expected responses are OK.
+				AllowKnownQueryResponses<PolkadotXcm>,
+				// Allow XCMs with some computed origins to pass through.
+				WithComputedOrigin<
+					(
+						// If the message is one that immediately attempts to pay for execution,
+						// then allow it.
+						AllowTopLevelPaidExecutionFrom<Everything>,
+						// Parent, its pluralities (i.e. governance bodies), relay treasury pallet
+						// and BridgeHub get free execution.
+						AllowExplicitUnpaidExecutionFrom<(
+							ParentOrParentsPlurality,
+							Equals<RelayTreasuryLocation>,
+							Equals<bridging::SiblingBridgeHub>,
+							FellowshipEntities,
+							AmbassadorEntities,
+						)>,
+						// Subscriptions for version tracking are OK.
+						AllowSubscriptionsFrom<Everything>,
+						// HRMP notifications from the relay chain are OK.
+						AllowHrmpNotificationsFromRelayChain,
+					),
+					UniversalLocation,
+					ConstU32<8>,
+				>,
+			),
+		>,
 	>,
 >;
 
@@ -24,6 +24,7 @@
 use super::{weights, AccountId, Balance, Balances, BlockNumber, Runtime, RuntimeEvent};
 use bp_parachains::SingleParaStoredHeaderDataBuilder;
 use frame_support::{parameter_types, traits::ConstU32};
+use frame_system::EnsureRoot;
 
 parameter_types! {
 	pub const RelayChainHeadersToKeep: u32 = 1024;
@@ -83,6 +84,10 @@ impl pallet_bridge_relayers::Config<RelayersForLegacyLaneIdsMessagesInstance> fo
 	>;
 	type WeightInfo = weights::pallet_bridge_relayers::WeightInfo<Runtime>;
 	type LaneId = bp_messages::LegacyLaneId;
+	type SlashAmount = bp_relayers::SlashWholeStake;
+	type SlashAppealPeriod = ConstU32<0>;
+	type SlashAppealOrigin = EnsureRoot<AccountId>;
+	type SlashRecordsToKeep = ConstU32<1024>;
 }
 
 /// Allows collect and claim rewards for relayers
@@ -105,6 +110,10 @@ impl pallet_bridge_relayers::Config<RelayersForPermissionlessLanesInstance> for
 	>;
 	type WeightInfo = weights::pallet_bridge_relayers::WeightInfo<Runtime>;
 	type LaneId = bp_messages::HashedLaneId;
+	type SlashAmount = bp_relayers::SlashWholeStake;
+	type SlashAppealPeriod = ConstU32<0>;
+	type SlashAppealOrigin = EnsureRoot<AccountId>;
+	type SlashRecordsToKeep = ConstU32<1024>;
 }
 
 /// Add GRANDPA bridge pallet to track Rococo Bulletin chain.
@@ -21,9 +21,9 @@
 
 use crate::{
 	bridge_common_config::RelayersForPermissionlessLanesInstance, weights,
-	xcm_config::UniversalLocation, AccountId, Balance, Balances, BridgeRococoBulletinGrandpa,
-	BridgeRococoBulletinMessages, PolkadotXcm, Runtime, RuntimeEvent, RuntimeHoldReason,
-	XcmOverRococoBulletin, XcmRouter,
+	xcm_config::UniversalLocation, AccountId, Balance, Balances, BlockNumber,
+	BridgeRococoBulletinGrandpa, BridgeRococoBulletinMessages, PolkadotXcm, Runtime, RuntimeEvent,
+	RuntimeHoldReason, XcmOverRococoBulletin, XcmRouter,
 };
 use bp_messages::{
 	source_chain::FromBridgedChainMessagesDeliveryProof,
@@ -42,7 +42,7 @@ use pallet_bridge_relayers::extension::{
 };
 use pallet_xcm_bridge_hub::XcmAsPlainPayload;
 use polkadot_parachain_primitives::primitives::Sibling;
-use testnet_parachains_constants::rococo::currency::UNITS as ROC;
+use testnet_parachains_constants::rococo::{currency::UNITS as ROC, time::DAYS};
 use xcm::{
 	latest::prelude::*,
 	prelude::{InteriorLocation, NetworkId},
@@ -76,6 +76,8 @@ parameter_types! {
 	pub PeopleRococoLocation: Location = Location::new(1, [Parachain(rococo_runtime_constants::system_parachain::PEOPLE_ID)]);
 
 	pub storage BridgeDeposit: Balance = 5 * ROC;
+	/// Closed bridges are given a day to deliver their queued messages.
+	pub const ClosingBridgeDrainPeriod: BlockNumber = DAYS;
 }
 
 /// Proof of messages, coming from Rococo Bulletin chain.
@@ -133,6 +135,7 @@ impl pallet_bridge_messages::Config<WithRococoBulletinMessagesInstance> for Runt
 pub type XcmOverPolkadotBulletinInstance = pallet_xcm_bridge_hub::Instance2;
 impl pallet_xcm_bridge_hub::Config<XcmOverPolkadotBulletinInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = weights::pallet_xcm_bridge_hub_rococo_to_rococo_bulletin::WeightInfo<Runtime>;
 
 	type UniversalLocation = UniversalLocation;
 	type BridgedNetwork = RococoBulletinGlobalConsensusNetworkLocation;
@@ -150,6 +153,7 @@ impl pallet_xcm_bridge_hub::Config<XcmOverPolkadotBulletinInstance> for Runtime
 		(ParentIsPreset<AccountId>, SiblingParachainConvertsVia<Sibling, AccountId>);
 
 	type BridgeDeposit = BridgeDeposit;
+	type ClosingBridgeDrainPeriod = ClosingBridgeDrainPeriod;
 	type Currency = Balances;
 	type RuntimeHoldReason = RuntimeHoldReason;
 	// Do not require deposit from People parachains.
@@ -157,6 +161,10 @@ impl pallet_xcm_bridge_hub::Config<XcmOverPolkadotBulletinInstance> for Runtime
 
 	type LocalXcmChannelManager = ();
 	type BlobDispatcher = FromRococoBulletinMessageBlobDispatcher;
+
+	// Bridges with the bulletin chain may not be opened with the `open_bridge` call.
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 #[cfg(test)]
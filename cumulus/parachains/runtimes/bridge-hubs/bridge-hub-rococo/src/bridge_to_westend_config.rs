@@ -23,8 +23,8 @@ use crate::{
 	},
 	weights,
 	xcm_config::UniversalLocation,
-	AccountId, Balance, Balances, BridgeWestendMessages, PolkadotXcm, Runtime, RuntimeEvent,
-	RuntimeHoldReason, XcmOverBridgeHubWestend, XcmRouter,
+	AccountId, Balance, Balances, BlockNumber, BridgeWestendMessages, PolkadotXcm, Runtime,
+	RuntimeEvent, RuntimeHoldReason, RuntimeOrigin, XcmOverBridgeHubWestend, XcmRouter,
 };
 use bp_messages::{
 	source_chain::FromBridgedChainMessagesDeliveryProof,
@@ -34,14 +34,16 @@ use bridge_hub_common::xcm_version::XcmVersionOfDestAndRemoteBridge;
 use pallet_xcm_bridge_hub::XcmAsPlainPayload;
 
 use frame_support::{parameter_types, traits::PalletInfoAccess};
-use frame_system::{EnsureNever, EnsureRoot};
+use frame_system::EnsureRoot;
 use pallet_bridge_messages::LaneIdOf;
 use pallet_bridge_relayers::extension::{
 	BridgeRelayersTransactionExtension, WithMessagesExtensionConfig,
 };
-use parachains_common::xcm_config::{AllSiblingSystemParachains, RelayOrOtherSystemParachains};
+use parachains_common::xcm_config::{
+	AllSiblingSystemParachains, ParentRelayOrSiblingParachains, RelayOrOtherSystemParachains,
+};
 use polkadot_parachain_primitives::primitives::Sibling;
-use testnet_parachains_constants::rococo::currency::UNITS as ROC;
+use testnet_parachains_constants::rococo::{currency::UNITS as ROC, time::DAYS};
 use xcm::{
 	latest::{prelude::*, WESTEND_GENESIS_HASH},
 	prelude::{InteriorLocation, NetworkId},
@@ -71,6 +73,8 @@ parameter_types! {
 	);
 
 	pub storage BridgeDeposit: Balance = 5 * ROC;
+	/// Closed bridges are given a day to deliver their queued messages.
+	pub const ClosingBridgeDrainPeriod: BlockNumber = DAYS;
 }
 
 /// Proof of messages, coming from Westend.
@@ -133,6 +137,7 @@ impl pallet_bridge_messages::Config<WithBridgeHubWestendMessagesInstance> for Ru
 pub type XcmOverBridgeHubWestendInstance = pallet_xcm_bridge_hub::Instance1;
 impl pallet_xcm_bridge_hub::Config<XcmOverBridgeHubWestendInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = weights::pallet_xcm_bridge_hub_rococo_to_westend::WeightInfo<Runtime>;
 
 	type UniversalLocation = UniversalLocation;
 	type BridgedNetwork = WestendGlobalConsensusNetworkLocation;
@@ -143,13 +148,16 @@ impl pallet_xcm_bridge_hub::Config<XcmOverBridgeHubWestendInstance> for Runtime
 		XcmVersionOfDestAndRemoteBridge<PolkadotXcm, BridgeHubWestendLocation>;
 
 	type ForceOrigin = EnsureRoot<AccountId>;
-	// We don't want to allow creating bridges for this instance with `LegacyLaneId`.
-	type OpenBridgeOrigin = EnsureNever<Location>;
+	// Sibling parachains and the relay chain may open bridges by sending the `Transact`
+	// instruction. The `LegacyLaneId` of the bridge is derived from the bridge endpoints, so it
+	// is the same at both sides.
+	type OpenBridgeOrigin = pallet_xcm::EnsureXcm<ParentRelayOrSiblingParachains>;
 	// Converter aligned with `OpenBridgeOrigin`.
 	type BridgeOriginAccountIdConverter =
 		(ParentIsPreset<AccountId>, SiblingParachainConvertsVia<Sibling, AccountId>);
 
 	type BridgeDeposit = BridgeDeposit;
+	type ClosingBridgeDrainPeriod = ClosingBridgeDrainPeriod;
 	type Currency = Balances;
 	type RuntimeHoldReason = RuntimeHoldReason;
 	// Do not require deposit from system parachains or relay chain
@@ -159,6 +167,20 @@ impl pallet_xcm_bridge_hub::Config<XcmOverBridgeHubWestendInstance> for Runtime
 	// TODO:(bridges-v2) - add `LocalXcmChannelManager` impl - https://github.com/paritytech/parity-bridges-common/issues/3047
 	type LocalXcmChannelManager = ();
 	type BlobDispatcher = FromWestendMessageBlobDispatcher;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = OpenBridgeBenchmarkHelper;
+}
+
+/// Benchmark helper that opens bridges from a non-system sibling parachain.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct OpenBridgeBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_xcm_bridge_hub::BenchmarkHelper<RuntimeOrigin> for OpenBridgeBenchmarkHelper {
+	fn open_bridge_origin() -> Option<RuntimeOrigin> {
+		Some(pallet_xcm::Origin::Xcm(Location::new(1, [Parachain(8765)])).into())
+	}
 }
 
 #[cfg(feature = "runtime-benchmarks")]
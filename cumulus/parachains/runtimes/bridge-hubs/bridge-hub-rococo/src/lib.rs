@@ -181,6 +181,14 @@ pub type Migrations = (
 		RocksDbWeight,
 	>,
 	pallet_bridge_relayers::migration::v1::MigrationToV1<Runtime, ()>,
+	pallet_xcm_bridge_hub::migration::v1::MigrationToV1<
+		Runtime,
+		bridge_to_westend_config::XcmOverBridgeHubWestendInstance,
+	>,
+	pallet_xcm_bridge_hub::migration::v1::MigrationToV1<
+		Runtime,
+		bridge_to_bulletin_config::XcmOverPolkadotBulletinInstance,
+	>,
 	// permanent
 	pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 );
@@ -679,6 +687,8 @@ mod benches {
 		[pallet_bridge_messages, RococoToRococoBulletin]
 		[pallet_bridge_relayers, Legacy]
 		[pallet_bridge_relayers, PermissionlessLanes]
+		[pallet_xcm_bridge_hub, XcmOverBridgeHubWestend]
+		[pallet_xcm_bridge_hub, XcmOverPolkadotBulletin]
 		// Ethereum Bridge
 		[snowbridge_pallet_inbound_queue, EthereumInboundQueue]
 		[snowbridge_pallet_outbound_queue, EthereumOutboundQueue]
@@ -959,6 +969,44 @@ impl_runtime_apis! {
 		}
 	}
 
+	// This is exposed by BridgeHubRococo
+	impl bp_bridge_hub_westend::ToBridgeHubWestendXcmBridgeHubApi<Block, BlockNumber> for Runtime {
+		fn bridges() -> Vec<(
+			pallet_xcm_bridge_hub::BridgeId,
+			pallet_xcm_bridge_hub::BridgeHealthOf<Runtime, bridge_to_westend_config::XcmOverBridgeHubWestendInstance>,
+		)> {
+			XcmOverBridgeHubWestend::bridges_health()
+		}
+
+		fn bridge_status(
+			bridge_origin_relative_location: VersionedLocation,
+			bridge_destination_universal_location: xcm::VersionedInteriorLocation,
+		) -> Option<pallet_xcm_bridge_hub::BridgeStatus<LaneIdOf<Runtime, bridge_to_westend_config::WithBridgeHubWestendMessagesInstance>>> {
+			XcmOverBridgeHubWestend::bridge_status(
+				bridge_origin_relative_location.try_into().ok()?,
+				bridge_destination_universal_location,
+			)
+			.ok()
+		}
+	}
+
+	// Slashes of relayers that are serving lanes with Bridge Hub Westend
+	impl bp_relayers::RelayersSlashesApi<Block, AccountId, BlockNumber, Balance, bp_messages::LegacyLaneId> for Runtime {
+		fn relayer_slashes(
+			relayer: AccountId,
+		) -> Vec<(bp_relayers::SlashId, pallet_bridge_relayers::SlashRecordOf<Runtime, ()>)> {
+			BridgeRelayers::relayer_slashes(&relayer)
+		}
+	}
+
+	impl bp_relayers::PermissionlessLanesRelayersSlashesApi<Block, AccountId, BlockNumber, Balance, bp_messages::HashedLaneId> for Runtime {
+		fn relayer_slashes(
+			relayer: AccountId,
+		) -> Vec<(bp_relayers::SlashId, pallet_bridge_relayers::SlashRecordOf<Runtime, bridge_common_config::RelayersForPermissionlessLanesInstance>)> {
+			BridgeRelayersForPermissionlessLanes::relayer_slashes(&relayer)
+		}
+	}
+
 	impl bp_polkadot_bulletin::PolkadotBulletinFinalityApi<Block> for Runtime {
 		fn best_finalized() -> Option<bp_runtime::HeaderId<bp_polkadot_bulletin::Hash, bp_polkadot_bulletin::BlockNumber>> {
 			BridgePolkadotBulletinGrandpa::best_finalized()
@@ -1017,6 +1065,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl snowbridge_ethereum_client_runtime_api::EthereumClientApi<Block> for Runtime {
+		fn ancestry_proof_kind(block_slot: u64) -> Option<snowbridge_beacon_primitives::AncestryProofKind> {
+			snowbridge_pallet_ethereum_client::api::ancestry_proof_kind::<Runtime>(block_slot)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
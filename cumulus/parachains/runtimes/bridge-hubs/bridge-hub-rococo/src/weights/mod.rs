@@ -42,6 +42,8 @@ pub mod pallet_timestamp;
 pub mod pallet_transaction_payment;
 pub mod pallet_utility;
 pub mod pallet_xcm;
+pub mod pallet_xcm_bridge_hub_rococo_to_rococo_bulletin;
+pub mod pallet_xcm_bridge_hub_rococo_to_westend;
 pub mod paritydb_weights;
 pub mod rocksdb_weights;
 pub mod snowbridge_pallet_ethereum_client;
@@ -55,6 +57,7 @@ pub use extrinsic_weights::constants::ExtrinsicBaseWeight;
 pub use rocksdb_weights::constants::RocksDbWeight;
 
 use crate::Runtime;
+use bp_messages::MessageNonce;
 use frame_support::weights::Weight;
 
 // import trait from dependency module
@@ -85,6 +88,13 @@ impl MessagesWeightInfoExt
 	fn receive_messages_delivery_proof_overhead_from_runtime() -> Weight {
 		pallet_bridge_relayers::WeightInfo::<Runtime>::receive_messages_delivery_proof_overhead_from_runtime()
 	}
+
+	fn receive_messages_delivery_proof_messages_overhead_from_runtime(
+		messages: MessageNonce,
+	) -> Weight {
+		// `pallet-xcm-bridge-hub` forgets about every confirmed message
+		crate::XcmOverPolkadotBulletin::messages_delivered_weight(messages)
+	}
 }
 
 impl MessagesWeightInfoExt
@@ -102,6 +112,13 @@ impl MessagesWeightInfoExt
 	fn receive_messages_delivery_proof_overhead_from_runtime() -> Weight {
 		pallet_bridge_relayers::WeightInfo::<Runtime>::receive_messages_delivery_proof_overhead_from_runtime()
 	}
+
+	fn receive_messages_delivery_proof_messages_overhead_from_runtime(
+		messages: MessageNonce,
+	) -> Weight {
+		// `pallet-xcm-bridge-hub` forgets about every confirmed message
+		crate::XcmOverBridgeHubWestend::messages_delivered_weight(messages)
+	}
 }
 
 impl ParachainsWeightInfoExt for pallet_bridge_parachains::WeightInfo<crate::Runtime> {
@@ -0,0 +1,165 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_xcm_bridge_hub`
+//!
+//! Storage accesses and proof sizes of these weights follow the `pallet_xcm_bridge_hub`
+//! benchmarks, running against `bridge-hub-rococo-dev`. Execution times have not been measured on the
+//! reference hardware yet, so this file must be regenerated with the benchmark CLI before the
+//! runtime is released:
+//!
+//! ```text
+//! frame-omni-bencher v1 benchmark pallet \
+//!     --runtime=target/production/wbuild/bridge-hub-rococo-runtime/bridge_hub_rococo_runtime.compact.compressed.wasm \
+//!     --pallet=pallet_xcm_bridge_hub --extrinsic=* --steps=50 --repeat=20 \
+//!     --header=./cumulus/file_header.txt \
+//!     --output=./cumulus/parachains/runtimes/bridge-hubs/bridge-hub-rococo/src/weights/
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_xcm_bridge_hub`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_xcm_bridge_hub::WeightInfo for WeightInfo<T> {
+	/// Storage: `XcmOverBridgeHubWestend::Bridges` (r:1 w:1)
+	/// Proof: `XcmOverBridgeHubWestend::Bridges` (`max_values`: None, `max_size`: Some(1918), added: 4393, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::LaneToBridge` (r:1 w:1)
+	/// Proof: `XcmOverBridgeHubWestend::LaneToBridge` (`max_values`: None, `max_size`: Some(65), added: 2540, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::InboundLanes` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::InboundLanes` (`max_values`: None, `max_size`: Some(49209), added: 51684, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::OutboundLanes` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::OutboundLanes` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::OutboundMessages` (r:0 w:1)
+	/// Proof: `BridgeWestendMessages::OutboundMessages` (`max_values`: None, `max_size`: Some(65597), added: 68072, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::OutboundMessagesEnqueuedAt` (r:0 w:1)
+	/// Proof: `XcmOverBridgeHubWestend::OutboundMessagesEnqueuedAt` (`max_values`: None, `max_size`: Some(53), added: 2528, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn open_bridge() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `52674`
+		Weight::from_parts(72_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 52674))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	/// Storage: `XcmOverBridgeHubWestend::Bridges` (r:1 w:1)
+	/// Proof: `XcmOverBridgeHubWestend::Bridges` (`max_values`: None, `max_size`: Some(1918), added: 4393, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::OutboundLanes` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::OutboundLanes` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::InboundLanes` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::InboundLanes` (`max_values`: None, `max_size`: Some(49209), added: 51684, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::ClosingBridges` (r:0 w:1)
+	/// Proof: `XcmOverBridgeHubWestend::ClosingBridges` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::OutboundMessages` (r:0 w:1024)
+	/// Proof: `BridgeWestendMessages::OutboundMessages` (`max_values`: None, `max_size`: Some(65597), added: 68072, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::OutboundMessagesEnqueuedAt` (r:0 w:1024)
+	/// Proof: `XcmOverBridgeHubWestend::OutboundMessagesEnqueuedAt` (`max_values`: None, `max_size`: Some(53), added: 2528, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::LaneToBridge` (r:0 w:1)
+	/// Proof: `XcmOverBridgeHubWestend::LaneToBridge` (`max_values`: None, `max_size`: Some(65), added: 2540, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 1024]`.
+	fn close_bridge(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `52674`
+		Weight::from_parts(80_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 52674))
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `XcmOverBridgeHubWestend::OutboundMessagesEnqueuedAt` (r:0 w:1)
+	/// Proof: `XcmOverBridgeHubWestend::OutboundMessagesEnqueuedAt` (`max_values`: None, `max_size`: Some(53), added: 2528, mode: `MaxEncodedLen`)
+	fn note_enqueued_message() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `0`
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `BridgeWestendMessages::OutboundLanes` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::OutboundLanes` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::OutboundMessagesEnqueuedAt` (r:n+1 w:n)
+	/// Proof: `XcmOverBridgeHubWestend::OutboundMessagesEnqueuedAt` (`max_values`: None, `max_size`: Some(53), added: 2528, mode: `Measured`)
+	/// The range of component `n` is `[1, 4096]`.
+	fn forget_delivered_messages(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `3539 + n * (80 ±0)`
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3539))
+			.saturating_add(Weight::from_parts(3_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 80).saturating_mul(n.into()))
+	}
+	/// Storage: `XcmOverBridgeHubWestend::ClosingBridges` (r:1 w:0)
+	/// Proof: `XcmOverBridgeHubWestend::ClosingBridges` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::Bridges` (r:1 w:0)
+	/// Proof: `XcmOverBridgeHubWestend::Bridges` (`max_values`: None, `max_size`: Some(1918), added: 4393, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::OutboundLanes` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::OutboundLanes` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::InboundLanes` (r:1 w:0)
+	/// Proof: `BridgeWestendMessages::InboundLanes` (`max_values`: None, `max_size`: Some(49209), added: 51684, mode: `MaxEncodedLen`)
+	fn check_closing_bridge() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `52674`
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 52674))
+			.saturating_add(T::DbWeight::get().reads(4))
+	}
+	/// Storage: `BridgeWestendMessages::InboundLanes` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::InboundLanes` (`max_values`: None, `max_size`: Some(49209), added: 51684, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::OutboundLanes` (r:1 w:1)
+	/// Proof: `BridgeWestendMessages::OutboundLanes` (`max_values`: None, `max_size`: Some(74), added: 2549, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeWestendMessages::OutboundMessages` (r:0 w:1024)
+	/// Proof: `BridgeWestendMessages::OutboundMessages` (`max_values`: None, `max_size`: Some(65597), added: 68072, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::OutboundMessagesEnqueuedAt` (r:0 w:1024)
+	/// Proof: `XcmOverBridgeHubWestend::OutboundMessagesEnqueuedAt` (`max_values`: None, `max_size`: Some(53), added: 2528, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::Bridges` (r:0 w:1)
+	/// Proof: `XcmOverBridgeHubWestend::Bridges` (`max_values`: None, `max_size`: Some(1918), added: 4393, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::LaneToBridge` (r:0 w:1)
+	/// Proof: `XcmOverBridgeHubWestend::LaneToBridge` (`max_values`: None, `max_size`: Some(65), added: 2540, mode: `MaxEncodedLen`)
+	/// Storage: `XcmOverBridgeHubWestend::ClosingBridges` (r:0 w:1)
+	/// Proof: `XcmOverBridgeHubWestend::ClosingBridges` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 1024]`.
+	fn prune_closing_bridge(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `52674`
+		Weight::from_parts(75_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 52674))
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+}
@@ -223,7 +223,10 @@ impl<Call> XcmWeightInfo<Call> for BridgeHubRococoXcmWeight<Call> {
 	}
 	fn export_message(_: &NetworkId, _: &Junctions, inner: &Xcm<()>) -> Weight {
 		let inner_encoded_len = inner.encode().len() as u32;
-		XcmGeneric::<Runtime>::export_message(inner_encoded_len)
+		// `pallet-xcm-bridge-hub` remembers when every exported message has been enqueued
+		let bookkeeping_weight = crate::XcmOverBridgeHubWestend::message_exported_weight()
+			.max(crate::XcmOverPolkadotBulletin::message_exported_weight());
+		XcmGeneric::<Runtime>::export_message(inner_encoded_len).saturating_add(bookkeeping_weight)
 	}
 	fn lock_asset(_: &Asset, _: &Location) -> Weight {
 		Weight::MAX
@@ -335,6 +335,21 @@ mod bridge_hub_westend_tests {
 		)
 	}
 
+	#[test]
+	fn open_and_close_bridge_works() {
+		bridge_hub_test_utils::test_cases::open_and_close_bridge_works::<
+			Runtime,
+			XcmOverBridgeHubWestendInstance,
+			LocationToAccountId,
+			TokenLocation,
+		>(
+			collator_session_keys(),
+			bp_bridge_hub_rococo::BRIDGE_HUB_ROCOCO_PARACHAIN_ID,
+			SiblingParachainLocation::get(),
+			BridgedUniversalLocation::get(),
+		)
+	}
+
 	#[test]
 	fn message_dispatch_routing_works() {
 		// from Westend
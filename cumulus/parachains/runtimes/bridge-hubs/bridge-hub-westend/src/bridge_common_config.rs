@@ -23,7 +23,8 @@
 
 use super::{weights, AccountId, Balance, Balances, BlockNumber, Runtime, RuntimeEvent};
 use bp_messages::LegacyLaneId;
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::ConstU32};
+use frame_system::EnsureRoot;
 
 parameter_types! {
 	pub storage RequiredStakeForStakeAndSlash: Balance = 1_000_000;
@@ -53,4 +54,8 @@ impl pallet_bridge_relayers::Config<RelayersForLegacyLaneIdsMessagesInstance> fo
 	>;
 	type WeightInfo = weights::pallet_bridge_relayers::WeightInfo<Runtime>;
 	type LaneId = LegacyLaneId;
+	type SlashAmount = bp_relayers::SlashWholeStake;
+	type SlashAppealPeriod = ConstU32<0>;
+	type SlashAppealOrigin = EnsureRoot<AccountId>;
+	type SlashRecordsToKeep = ConstU32<1024>;
 }
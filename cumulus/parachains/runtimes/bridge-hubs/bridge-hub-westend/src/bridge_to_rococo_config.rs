@@ -20,8 +20,8 @@ use crate::{
 	bridge_common_config::{DeliveryRewardInBalance, RelayersForLegacyLaneIdsMessagesInstance},
 	weights,
 	xcm_config::UniversalLocation,
-	AccountId, Balance, Balances, BridgeRococoMessages, PolkadotXcm, Runtime, RuntimeEvent,
-	RuntimeHoldReason, XcmOverBridgeHubRococo, XcmRouter,
+	AccountId, Balance, Balances, BlockNumber, BridgeRococoMessages, PolkadotXcm, Runtime,
+	RuntimeEvent, RuntimeHoldReason, RuntimeOrigin, XcmOverBridgeHubRococo, XcmRouter,
 };
 use bp_messages::{
 	source_chain::FromBridgedChainMessagesDeliveryProof,
@@ -35,14 +35,16 @@ use frame_support::{
 	parameter_types,
 	traits::{ConstU32, PalletInfoAccess},
 };
-use frame_system::{EnsureNever, EnsureRoot};
+use frame_system::EnsureRoot;
 use pallet_bridge_messages::LaneIdOf;
 use pallet_bridge_relayers::extension::{
 	BridgeRelayersTransactionExtension, WithMessagesExtensionConfig,
 };
-use parachains_common::xcm_config::{AllSiblingSystemParachains, RelayOrOtherSystemParachains};
+use parachains_common::xcm_config::{
+	AllSiblingSystemParachains, ParentRelayOrSiblingParachains, RelayOrOtherSystemParachains,
+};
 use polkadot_parachain_primitives::primitives::Sibling;
-use testnet_parachains_constants::westend::currency::UNITS as WND;
+use testnet_parachains_constants::westend::{currency::UNITS as WND, time::DAYS};
 use xcm::{
 	latest::{prelude::*, ROCOCO_GENESIS_HASH},
 	prelude::{InteriorLocation, NetworkId},
@@ -78,6 +80,8 @@ parameter_types! {
 	);
 
 	pub storage BridgeDeposit: Balance = 10 * WND;
+	/// Closed bridges are given a day to deliver their queued messages.
+	pub const ClosingBridgeDrainPeriod: BlockNumber = DAYS;
 }
 
 /// Proof of messages, coming from Rococo.
@@ -163,6 +167,7 @@ impl pallet_bridge_messages::Config<WithBridgeHubRococoMessagesInstance> for Run
 pub type XcmOverBridgeHubRococoInstance = pallet_xcm_bridge_hub::Instance1;
 impl pallet_xcm_bridge_hub::Config<XcmOverBridgeHubRococoInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = weights::pallet_xcm_bridge_hub::WeightInfo<Runtime>;
 
 	type UniversalLocation = UniversalLocation;
 	type BridgedNetwork = RococoGlobalConsensusNetworkLocation;
@@ -172,13 +177,16 @@ impl pallet_xcm_bridge_hub::Config<XcmOverBridgeHubRococoInstance> for Runtime {
 	type DestinationVersion = XcmVersionOfDestAndRemoteBridge<PolkadotXcm, BridgeHubRococoLocation>;
 
 	type ForceOrigin = EnsureRoot<AccountId>;
-	// We don't want to allow creating bridges for this instance with `LegacyLaneId`.
-	type OpenBridgeOrigin = EnsureNever<Location>;
+	// Sibling parachains and the relay chain may open bridges by sending the `Transact`
+	// instruction. The `LegacyLaneId` of the bridge is derived from the bridge endpoints, so it
+	// is the same at both sides.
+	type OpenBridgeOrigin = pallet_xcm::EnsureXcm<ParentRelayOrSiblingParachains>;
 	// Converter aligned with `OpenBridgeOrigin`.
 	type BridgeOriginAccountIdConverter =
 		(ParentIsPreset<AccountId>, SiblingParachainConvertsVia<Sibling, AccountId>);
 
 	type BridgeDeposit = BridgeDeposit;
+	type ClosingBridgeDrainPeriod = ClosingBridgeDrainPeriod;
 	type Currency = Balances;
 	type RuntimeHoldReason = RuntimeHoldReason;
 	// Do not require deposit from system parachains or relay chain
@@ -188,6 +196,20 @@ impl pallet_xcm_bridge_hub::Config<XcmOverBridgeHubRococoInstance> for Runtime {
 	// TODO:(bridges-v2) - add `LocalXcmChannelManager` impl - https://github.com/paritytech/parity-bridges-common/issues/3047
 	type LocalXcmChannelManager = ();
 	type BlobDispatcher = FromRococoMessageBlobDispatcher;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = OpenBridgeBenchmarkHelper;
+}
+
+/// Benchmark helper that opens bridges from a non-system sibling parachain.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct OpenBridgeBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_xcm_bridge_hub::BenchmarkHelper<RuntimeOrigin> for OpenBridgeBenchmarkHelper {
+	fn open_bridge_origin() -> Option<RuntimeOrigin> {
+		Some(pallet_xcm::Origin::Xcm(Location::new(1, [Parachain(8765)])).into())
+	}
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -161,6 +161,10 @@ pub type Migrations = (
 		RocksDbWeight,
 	>,
 	pallet_bridge_relayers::migration::v1::MigrationToV1<Runtime, ()>,
+	pallet_xcm_bridge_hub::migration::v1::MigrationToV1<
+		Runtime,
+		bridge_to_rococo_config::XcmOverBridgeHubRococoInstance,
+	>,
 	snowbridge_pallet_system::migration::v0::InitializeOnUpgrade<
 		Runtime,
 		ConstU32<BRIDGE_HUB_ID>,
@@ -616,6 +620,7 @@ mod benches {
 		[pallet_bridge_grandpa, RococoFinality]
 		[pallet_bridge_parachains, WithinRococo]
 		[pallet_bridge_messages, WestendToRococo]
+		[pallet_xcm_bridge_hub, XcmOverBridgeHubRococo]
 		// Ethereum Bridge
 		[snowbridge_pallet_inbound_queue, EthereumInboundQueue]
 		[snowbridge_pallet_outbound_queue, EthereumOutboundQueue]
@@ -889,6 +894,35 @@ impl_runtime_apis! {
 		}
 	}
 
+	// This is exposed by BridgeHubWestend
+	impl bp_bridge_hub_rococo::ToBridgeHubRococoXcmBridgeHubApi<Block, BlockNumber> for Runtime {
+		fn bridges() -> Vec<(
+			pallet_xcm_bridge_hub::BridgeId,
+			pallet_xcm_bridge_hub::BridgeHealthOf<Runtime, bridge_to_rococo_config::XcmOverBridgeHubRococoInstance>,
+		)> {
+			XcmOverBridgeHubRococo::bridges_health()
+		}
+
+		fn bridge_status(
+			bridge_origin_relative_location: VersionedLocation,
+			bridge_destination_universal_location: xcm::VersionedInteriorLocation,
+		) -> Option<pallet_xcm_bridge_hub::BridgeStatus<LaneIdOf<Runtime, bridge_to_rococo_config::WithBridgeHubRococoMessagesInstance>>> {
+			XcmOverBridgeHubRococo::bridge_status(
+				bridge_origin_relative_location.try_into().ok()?,
+				bridge_destination_universal_location,
+			)
+			.ok()
+		}
+	}
+
+	impl bp_relayers::RelayersSlashesApi<Block, AccountId, BlockNumber, Balance, bp_messages::LegacyLaneId> for Runtime {
+		fn relayer_slashes(
+			relayer: AccountId,
+		) -> Vec<(bp_relayers::SlashId, pallet_bridge_relayers::SlashRecordOf<Runtime, ()>)> {
+			BridgeRelayers::relayer_slashes(&relayer)
+		}
+	}
+
 	impl snowbridge_outbound_queue_runtime_api::OutboundQueueApi<Block, Balance> for Runtime {
 		fn prove_message(leaf_index: u64) -> Option<snowbridge_pallet_outbound_queue::MerkleProof> {
 			snowbridge_pallet_outbound_queue::api::prove_message::<Runtime>(leaf_index)
@@ -905,6 +939,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl snowbridge_ethereum_client_runtime_api::EthereumClientApi<Block> for Runtime {
+		fn ancestry_proof_kind(block_slot: u64) -> Option<snowbridge_beacon_primitives::AncestryProofKind> {
+			snowbridge_pallet_ethereum_client::api::ancestry_proof_kind::<Runtime>(block_slot)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
@@ -41,6 +41,7 @@ pub mod pallet_timestamp;
 pub mod pallet_transaction_payment;
 pub mod pallet_utility;
 pub mod pallet_xcm;
+pub mod pallet_xcm_bridge_hub;
 pub mod paritydb_weights;
 pub mod rocksdb_weights;
 pub mod xcm;
@@ -55,6 +56,7 @@ pub use extrinsic_weights::constants::ExtrinsicBaseWeight;
 pub use rocksdb_weights::constants::RocksDbWeight;
 
 use crate::Runtime;
+use bp_messages::MessageNonce;
 use frame_support::weights::Weight;
 
 // import trait from dependency module
@@ -83,6 +85,13 @@ impl MessagesWeightInfoExt for pallet_bridge_messages::WeightInfo<crate::Runtime
 	fn receive_messages_delivery_proof_overhead_from_runtime() -> Weight {
 		pallet_bridge_relayers::WeightInfo::<Runtime>::receive_messages_delivery_proof_overhead_from_runtime()
 	}
+
+	fn receive_messages_delivery_proof_messages_overhead_from_runtime(
+		messages: MessageNonce,
+	) -> Weight {
+		// `pallet-xcm-bridge-hub` forgets about every confirmed message
+		crate::XcmOverBridgeHubRococo::messages_delivered_weight(messages)
+	}
 }
 
 impl ParachainsWeightInfoExt for pallet_bridge_parachains::WeightInfo<crate::Runtime> {
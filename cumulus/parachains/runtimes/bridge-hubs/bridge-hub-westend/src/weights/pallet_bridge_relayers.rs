@@ -14,28 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Autogenerated weights for `pallet_bridge_relayers`
+//! Weights for `pallet_bridge_relayers`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 32.0.0
-//! DATE: 2024-08-15, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `runner-696hpswk-project-674-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
-//! WASM-EXECUTION: `Compiled`, CHAIN: `Some("bridge-hub-westend-dev")`, DB CACHE: 1024
-
-// Executed Command:
-// target/production/polkadot-parachain
-// benchmark
-// pallet
-// --steps=50
-// --repeat=20
-// --extrinsic=*
-// --wasm-execution=compiled
-// --heap-pages=4096
-// --json-file=/builds/parity/mirrors/polkadot-sdk/.git/.artifacts/bench.json
-// --pallet=pallet_bridge_relayers
-// --chain=bridge-hub-westend-dev
-// --header=./cumulus/file_header.txt
-// --output=./cumulus/parachains/runtimes/bridge-hubs/bridge-hub-westend/src/weights/
+//! Storage accesses and proof sizes of these weights follow the `pallet_bridge_relayers`
+//! benchmarks, running against `bridge-hub-westend-dev`. Execution times of `claim_rewards`, `register`,
+//! `deregister` and `register_relayer_reward` have been measured on 2024-08-15 (STEPS: `50`,
+//! REPEAT: `20`). Execution times of the slash-related calls have not been measured on the
+//! reference hardware yet, so this file must be regenerated with the benchmark CLI before the
+//! runtime is released:
+//!
+//! ```text
+//! frame-omni-bencher v1 benchmark pallet \
+//!     --runtime=target/production/wbuild/bridge-hub-westend-runtime/bridge_hub_westend_runtime.compact.compressed.wasm \
+//!     --pallet=pallet_bridge_relayers --extrinsic=* --steps=50 --repeat=20 \
+//!     --header=./cumulus/file_header.txt \
+//!     --output=./cumulus/parachains/runtimes/bridge-hubs/bridge-hub-westend/src/weights/
+//! ```
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -94,19 +88,23 @@ impl<T: frame_system::Config> pallet_bridge_relayers::WeightInfo for WeightInfo<
 	}
 	/// Storage: `BridgeRelayers::RegisteredRelayers` (r:1 w:1)
 	/// Proof: `BridgeRelayers::RegisteredRelayers` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeRelayers::NextSlashId` (r:1 w:1)
+	/// Proof: `BridgeRelayers::NextSlashId` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeRelayers::SlashOwners` (r:1 w:2)
+	/// Proof: `BridgeRelayers::SlashOwners` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeRelayers::Slashes` (r:1 w:2)
+	/// Proof: `BridgeRelayers::Slashes` (`max_values`: None, `max_size`: Some(165), added: 2640, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Reserves` (r:1 w:1)
 	/// Proof: `Balances::Reserves` (`max_values`: None, `max_size`: Some(1249), added: 3724, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	fn slash_and_deregister() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `300`
-		//  Estimated: `4714`
-		// Minimum execution time: 32_618_000 picoseconds.
-		Weight::from_parts(33_528_000, 0)
-			.saturating_add(Weight::from_parts(0, 4714))
-			.saturating_add(T::DbWeight::get().reads(3))
-			.saturating_add(T::DbWeight::get().writes(3))
+		//  Estimated: `6196`
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(9))
 	}
 	/// Storage: `BridgeRelayers::RelayerRewards` (r:1 w:1)
 	/// Proof: `BridgeRelayers::RelayerRewards` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
@@ -120,4 +118,50 @@ impl<T: frame_system::Config> pallet_bridge_relayers::WeightInfo for WeightInfo<
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `BridgeRelayers::Slashes` (r:1 w:1)
+	/// Proof: `BridgeRelayers::Slashes` (`max_values`: None, `max_size`: Some(165), added: 2640, mode: `MaxEncodedLen`)
+	fn appeal_slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `3630`
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3630))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `BridgeRelayers::Slashes` (r:1 w:1)
+	/// Proof: `BridgeRelayers::Slashes` (`max_values`: None, `max_size`: Some(165), added: 2640, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeRelayers::NextSlashId` (r:1 w:0)
+	/// Proof: `BridgeRelayers::NextSlashId` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeRelayers::SlashOwners` (r:0 w:1)
+	/// Proof: `BridgeRelayers::SlashOwners` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Reserves` (r:1 w:1)
+	/// Proof: `Balances::Reserves` (`max_values`: None, `max_size`: Some(1249), added: 3724, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn resolve_slash_appeal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `6196`
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `BridgeRelayers::Slashes` (r:1 w:1)
+	/// Proof: `BridgeRelayers::Slashes` (`max_values`: None, `max_size`: Some(165), added: 2640, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeRelayers::NextSlashId` (r:1 w:0)
+	/// Proof: `BridgeRelayers::NextSlashId` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `BridgeRelayers::SlashOwners` (r:0 w:1)
+	/// Proof: `BridgeRelayers::SlashOwners` (`max_values`: None, `max_size`: Some(40), added: 2515, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Reserves` (r:1 w:1)
+	/// Proof: `Balances::Reserves` (`max_values`: None, `max_size`: Some(1249), added: 3724, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn apply_slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `6196`
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
 }
@@ -227,7 +227,9 @@ impl<Call> XcmWeightInfo<Call> for BridgeHubWestendXcmWeight<Call> {
 	}
 	fn export_message(_: &NetworkId, _: &Junctions, inner: &Xcm<()>) -> Weight {
 		let inner_encoded_len = inner.encode().len() as u32;
+		// `pallet-xcm-bridge-hub` remembers when every exported message has been enqueued
 		XcmGeneric::<Runtime>::export_message(inner_encoded_len)
+			.saturating_add(crate::XcmOverBridgeHubRococo::message_exported_weight())
 	}
 	fn lock_asset(_: &Asset, _: &Location) -> Weight {
 		Weight::MAX
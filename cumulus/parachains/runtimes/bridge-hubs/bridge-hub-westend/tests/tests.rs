@@ -256,6 +256,21 @@ fn handle_export_message_from_system_parachain_add_to_outbound_queue_works() {
 		)
 }
 
+#[test]
+fn open_and_close_bridge_works() {
+	bridge_hub_test_utils::test_cases::open_and_close_bridge_works::<
+		Runtime,
+		XcmOverBridgeHubRococoInstance,
+		LocationToAccountId,
+		WestendLocation,
+	>(
+		collator_session_keys(),
+		bp_bridge_hub_westend::BRIDGE_HUB_WESTEND_PARACHAIN_ID,
+		SiblingParachainLocation::get(),
+		BridgedUniversalLocation::get(),
+	)
+}
+
 #[test]
 fn message_dispatch_routing_works() {
 	bridge_hub_test_utils::test_cases::message_dispatch_routing_works::<
@@ -734,7 +734,7 @@ pub fn open_and_close_bridge_works<Runtime, XcmOverBridgePalletInstance, Locatio
 				bridge_destination_universal_location: Box::new(
 					locations.bridge_destination_universal_location().clone().into()
 				),
-				state: BridgeState::Opened,
+				state: BridgeState::Opening,
 				bridge_owner_account: LocationToAccountId::convert_location(&source)
 					.expect("valid location")
 					.into(),
@@ -750,6 +750,13 @@ pub fn open_and_close_bridge_works<Runtime, XcmOverBridgePalletInstance, Locatio
 			lanes_manager.active_outbound_lane(expected_lane_id).map(|lane| lane.state()),
 			Ok(LaneState::Opened)
 		);
+		// the handshake is queued for the bridged side
+		assert_eq!(
+			lanes_manager
+				.active_outbound_lane(expected_lane_id)
+				.map(|lane| lane.queued_messages().count()),
+			Ok(1)
+		);
 
 		// close bridge with Transact call from sibling
 		helpers::close_bridge::<
@@ -148,6 +148,14 @@ pub struct Cli<Config: CliConfig> {
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
 
+	/// Disable pacing of block production by the slot-based collator.
+	///
+	/// By default the time and PoV size budget of each block is scaled down when the blocks built
+	/// by this collator are backed late or not at all. With this flag, every block gets the full
+	/// budget.
+	#[arg(long, requires = "experimental_use_slot_based")]
+	pub no_block_production_pacing: bool,
+
 	/// Export all `PoVs` build by this collator to the given folder.
 	///
 	/// This is useful for debugging issues that are occurring while validating these `PoVs` on the
@@ -167,6 +175,7 @@ impl<Config: CliConfig> Cli<Config> {
 	pub(crate) fn node_extra_args(&self) -> NodeExtraArgs {
 		NodeExtraArgs {
 			use_slot_based_consensus: self.experimental_use_slot_based,
+			pace_block_production: !self.no_block_production_pacing,
 			export_pov: self.export_pov_to_path.clone(),
 		}
 	}
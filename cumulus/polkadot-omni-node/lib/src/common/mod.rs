@@ -103,6 +103,9 @@ where
 pub struct NodeExtraArgs {
 	pub use_slot_based_consensus: bool,
 
+	/// Whether the slot-based collator paces block production by the observed backing latency.
+	pub pace_block_production: bool,
+
 	/// If set, each `PoV` build by the node will be exported to this folder.
 	pub export_pov: Option<PathBuf>,
 }
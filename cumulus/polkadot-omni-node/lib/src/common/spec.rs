@@ -324,6 +324,7 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 					relay_chain_slot_duration,
 					recovery_handle: Box::new(overseer_handle.clone()),
 					sync_service,
+					network: Some(network.clone()),
 				})?;
 
 				if validator {
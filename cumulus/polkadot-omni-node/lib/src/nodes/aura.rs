@@ -299,7 +299,7 @@ where
 		_overseer_handle: OverseerHandle,
 		announce_block: Arc<dyn Fn(Hash, Option<Vec<u8>>) + Send + Sync>,
 		backend: Arc<ParachainBackend<Block>>,
-		_node_extra_args: NodeExtraArgs,
+		node_extra_args: NodeExtraArgs,
 	) -> Result<(), Error> {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::with_proof_recording(
 			task_manager.spawn_handle(),
@@ -333,8 +333,10 @@ where
 			proposer,
 			collator_service,
 			authoring_duration: Duration::from_millis(2000),
+			pace_block_production: node_extra_args.pace_block_production,
 			reinitialize: false,
 			slot_drift: Duration::from_secs(1),
+			prometheus_registry: prometheus_registry.cloned(),
 		};
 
 		// We have a separate function only to be able to use `docify::export` on this piece of
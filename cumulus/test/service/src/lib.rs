@@ -440,6 +440,7 @@ where
 		relay_chain_slot_duration,
 		recovery_handle,
 		sync_service: sync_service.clone(),
+		network: Some(network.clone()),
 	})?;
 
 	if let Some(collator_key) = collator_key {
@@ -495,8 +496,10 @@ where
 					proposer,
 					collator_service,
 					authoring_duration: Duration::from_millis(2000),
+					pace_block_production: true,
 					reinitialize: false,
 					slot_drift: Duration::from_secs(1),
+					prometheus_registry: prometheus_registry.clone(),
 				};
 
 				let (collation_future, block_builder_future) =
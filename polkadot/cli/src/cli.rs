@@ -56,6 +56,164 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Prepare the PVFs of the current session into a shared artifact store.
+	PrecompilePvfs(PrecompilePvfsCmd),
+
+	/// Export the available data of candidates from the availability store.
+	ExportAvailableData(ExportAvailableDataCmd),
+
+	/// Check, inspect and repair the parachains database.
+	InspectParachainsDb(InspectParachainsDbCmd),
+}
+
+/// The `precompile-pvfs` subcommand.
+///
+/// Prepares the PVFs of all the parachains scheduled or pending availability at the best block
+/// of the local database, so that the nodes started with `--pvf-shared-artifacts-path` pointing to
+/// the same store don't have to.
+#[derive(Debug, Clone, Parser)]
+pub struct PrecompilePvfsCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+
+	/// Path to the shared artifact store to add the prepared PVFs to.
+	#[arg(long, value_name = "PATH")]
+	pub shared_artifacts_path: PathBuf,
+
+	/// Path to a file holding the secret URI of the ed25519 key to sign the prepared PVFs with.
+	///
+	/// The nodes using the store must be started with its public key as
+	/// `--pvf-shared-artifacts-key`.
+	#[arg(long, value_name = "PATH")]
+	pub signing_key_file: PathBuf,
+
+	/// Path to the directory where auxiliary worker binaries reside.
+	///
+	/// If not specified, the main binary's directory is searched first, then
+	/// `/usr/lib/polkadot` is searched.
+	#[arg(long, value_name = "PATH")]
+	pub workers_path: Option<PathBuf>,
+
+	/// The number of PVFs to prepare in parallel.
+	#[arg(long, default_value_t = 2)]
+	pub prepare_workers_num: usize,
+
+	/// Allows preparing the PVFs outside of Secure Validator Mode. Only use this if the
+	/// validators sharing the store are running insecurely too.
+	#[arg(long = "insecure-validator-i-know-what-i-do")]
+	pub insecure_validator: bool,
+}
+
+impl sc_cli::CliConfiguration for PrecompilePvfsCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The `export-available-data` subcommand.
+///
+/// Writes the SCALE encoded `AvailableData` of the given candidates, as long as the availability
+/// store of the local database still retains it, to one file per candidate. The node must not be
+/// running. See `--av-store-archive-para` for retaining the data for longer.
+#[derive(Debug, Clone, Parser)]
+pub struct ExportAvailableDataCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+
+	/// Hash of a candidate to export. Can be passed multiple times.
+	#[arg(long = "candidate", value_name = "HASH", required = true, value_parser = parse_hash)]
+	pub candidates: Vec<sp_core::H256>,
+
+	/// Directory to write the files to.
+	#[arg(long, value_name = "PATH")]
+	pub output: PathBuf,
+}
+
+impl sc_cli::CliConfiguration for ExportAvailableDataCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// The `inspect-parachains-db` subcommand.
+///
+/// Checks the consistency of the approval-voting, chain selection and dispute coordinator data in
+/// the parachains database of the local node, and prints the entries of the given blocks and
+/// candidates. The node must not be running.
+#[derive(Debug, Clone, Parser)]
+pub struct InspectParachainsDbCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+
+	/// Hash of a relay chain block to print the entries of. Can be passed multiple times.
+	#[arg(long = "block", value_name = "HASH", value_parser = parse_hash)]
+	pub blocks: Vec<sp_core::H256>,
+
+	/// Hash of a candidate to print the entries of. Can be passed multiple times.
+	#[arg(long = "candidate", value_name = "HASH", value_parser = parse_hash)]
+	pub candidates: Vec<sp_core::H256>,
+
+	/// Prune the entries involved in the inconsistencies found.
+	///
+	/// Corrupted blocks are forgotten by approval voting and chain selection, and corrupted votes
+	/// by the dispute coordinator, as if they had been pruned.
+	#[arg(long)]
+	pub repair: bool,
+}
+
+impl sc_cli::CliConfiguration for InspectParachainsDbCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+fn parse_hash(s: &str) -> Result<sp_core::H256, String> {
+	s.parse().map_err(|err| format!("Invalid hash {}: {:?}", s, err))
+}
+
+fn parse_archived_para(s: &str) -> Result<(u32, u64), String> {
+	let (para_id, hours) = s
+		.split_once(':')
+		.ok_or_else(|| format!("Expected <PARA_ID>:<HOURS>, got {}", s))?;
+	let para_id = para_id.parse().map_err(|err| format!("Invalid para id {}: {}", para_id, err))?;
+	let hours = hours.parse().map_err(|err| format!("Invalid hours {}: {}", hours, err))?;
+	Ok((para_id, hours))
 }
 
 #[allow(missing_docs)]
@@ -142,6 +300,37 @@ pub struct RunCmd {
 	///  **Dangerous!** Do not touch unless explicitly advised to.
 	#[arg(long)]
 	pub prepare_workers_hard_max_num: Option<usize>,
+
+	/// Path to a read-only store of prepared PVF artifacts shared with other nodes on this host.
+	///
+	/// Artifacts found there signed by `--pvf-shared-artifacts-key` are copied into the node's
+	/// cache instead of being prepared again. The store can be populated with the
+	/// `precompile-pvfs` subcommand, and must only be shared between machines with the same CPU.
+	#[arg(long, value_name = "PATH", requires = "pvf_shared_artifacts_key")]
+	pub pvf_shared_artifacts_path: Option<PathBuf>,
+
+	/// The SS58-encoded ed25519 public key the artifacts in `--pvf-shared-artifacts-path` must be
+	/// signed with.
+	#[arg(
+		long,
+		value_name = "PUBLIC_KEY",
+		value_parser = parse_ed25519_public,
+		requires = "pvf_shared_artifacts_path"
+	)]
+	pub pvf_shared_artifacts_key: Option<sp_core::ed25519::Public>,
+
+	/// Keep the finalized availability data of all paras for the given number of hours.
+	///
+	/// By default, the data is pruned 25 hours after finalization. Useful for indexers and for
+	/// debugging parachain blocks, see the `export-available-data` subcommand.
+	#[arg(long, value_name = "HOURS")]
+	pub av_store_keep_finalized_for: Option<u64>,
+
+	/// Keep the finalized availability data of a para for the given number of hours, overriding
+	/// `--av-store-keep-finalized-for`. Can be passed multiple times.
+	#[arg(long, value_name = "PARA_ID:HOURS", value_parser = parse_archived_para)]
+	pub av_store_archive_para: Vec<(u32, u64)>,
+
 	/// TESTING ONLY: disable the version check between nodes and workers.
 	#[arg(long, hide = true)]
 	pub disable_worker_version_check: bool,
@@ -166,3 +355,8 @@ pub struct Cli {
 	#[clap(flatten)]
 	pub storage_monitor: sc_storage_monitor::StorageMonitorParams,
 }
+
+fn parse_ed25519_public(public: &str) -> Result<sp_core::ed25519::Public, String> {
+	use sp_core::crypto::Ss58Codec;
+	sp_core::ed25519::Public::from_ss58check(public).map_err(|e| format!("{e:?}"))
+}
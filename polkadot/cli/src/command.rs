@@ -203,6 +203,21 @@ where
 
 	let secure_validator_mode = cli.run.base.validator && !cli.run.insecure_validator;
 
+	let hours = |hours: u64| {
+		hours.checked_mul(60 * 60).map(std::time::Duration::from_secs).ok_or_else(|| {
+			Error::Other(format!("Availability data retention period of {hours} hours is too long"))
+		})
+	};
+	let availability_archival = polkadot_service::AvailabilityArchivalConfig {
+		keep_finalized_for: cli.run.av_store_keep_finalized_for.map(hours).transpose()?,
+		keep_finalized_for_para: cli
+			.run
+			.av_store_archive_para
+			.iter()
+			.map(|&(para_id, keep_for)| Ok((para_id.into(), hours(keep_for)?)))
+			.collect::<Result<_>>()?,
+	};
+
 	runner.run_node_until_exit(move |config| async move {
 		let hwbench = (!cli.run.no_hardware_benchmarks)
 			.then(|| {
@@ -234,6 +249,9 @@ where
 				execute_workers_max_num: cli.run.execute_workers_max_num,
 				prepare_workers_hard_max_num: cli.run.prepare_workers_hard_max_num,
 				prepare_workers_soft_max_num: cli.run.prepare_workers_soft_max_num,
+				pvf_shared_artifacts_path: cli.run.pvf_shared_artifacts_path,
+				pvf_shared_artifacts_key: cli.run.pvf_shared_artifacts_key,
+				availability_archival,
 				enable_approval_voting_parallel: cli.run.enable_approval_voting_parallel,
 			},
 		)
@@ -461,6 +479,126 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			Ok(runner.sync_run(|config| cmd.run::<polkadot_service::Block>(&config))?)
 		},
+		Some(Subcommand::PrecompilePvfs(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			let chain_spec = &runner.config().chain_spec;
+
+			set_default_ss58_version(chain_spec);
+
+			let signing_key = std::fs::read_to_string(&cmd.signing_key_file)
+				.map_err(|e| Error::Other(format!("Failed to read the signing key: {e}")))?;
+			let signing_key =
+				<sp_core::ed25519::Pair as sp_core::Pair>::from_string(signing_key.trim(), None)
+					.map_err(|e| Error::Other(format!("Invalid signing key: {e:?}")))?;
+			let params = polkadot_service::precompile_pvfs::PrecompilePvfsParams {
+				shared_artifacts_path: cmd.shared_artifacts_path.clone(),
+				signing_key,
+				workers_path: cmd.workers_path.clone(),
+				workers_names: None,
+				secure_validator_mode: !cmd.insecure_validator,
+				prepare_workers_num: cmd.prepare_workers_num,
+			};
+			Ok(runner.async_run(|mut config| {
+				let (client, _, _, task_manager) = polkadot_service::new_chain_ops(&mut config)?;
+				let task = async move {
+					let outcome =
+						polkadot_service::precompile_pvfs::precompile_pvfs(client, params).await?;
+					info!(
+						"Prepared {} PVFs of session {} into the shared artifact store",
+						outcome.prepared.len(),
+						outcome.session,
+					);
+					for (code_hash, error) in &outcome.failed {
+						log::warn!("Failed to prepare PVF {:?}: {}", code_hash, error);
+					}
+					Ok::<_, Error>(())
+				};
+				Ok((task, task_manager))
+			})?)
+		},
+		Some(Subcommand::ExportAvailableData(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			Ok(runner.sync_run(|config| {
+				let outcome = polkadot_service::export_available_data(
+					&config.database,
+					cmd.candidates.iter().copied().map(polkadot_service::CandidateHash),
+					&cmd.output,
+				)?;
+				for exported in &outcome.exported {
+					info!(
+						"Exported available data of candidate {:?} to {}",
+						exported.candidate_hash,
+						exported.path.display(),
+					);
+				}
+				for candidate_hash in &outcome.missing {
+					log::warn!("No available data stored for candidate {:?}", candidate_hash);
+				}
+				Ok::<_, Error>(())
+			})?)
+		},
+		Some(Subcommand::InspectParachainsDb(cmd)) => {
+			use polkadot_service::inspect_parachains_db as inspect;
+
+			let runner = cli.create_runner(cmd)?;
+			Ok(runner.sync_run(|config| {
+				let db = inspect::open_existing_database(&config.database)?;
+
+				for block_hash in &cmd.blocks {
+					let descriptions = inspect::describe_block(&*db, block_hash)?;
+					if descriptions.is_empty() {
+						info!("No entries stored for block {:?}", block_hash);
+					}
+					for description in descriptions {
+						info!("Block {:?}, {}", block_hash, description);
+					}
+				}
+				for candidate_hash in &cmd.candidates {
+					let candidate_hash = polkadot_service::CandidateHash(*candidate_hash);
+					let descriptions = inspect::describe_candidate(&*db, &candidate_hash)?;
+					if descriptions.is_empty() {
+						info!("No entries stored for candidate {:?}", candidate_hash);
+					}
+					for description in descriptions {
+						info!("Candidate {:?}, {}", candidate_hash, description);
+					}
+				}
+
+				let inconsistencies = inspect::check_consistency(&*db)?;
+				for inconsistency in &inconsistencies.approval_voting {
+					log::warn!("Approval voting: {}", inconsistency);
+				}
+				for inconsistency in &inconsistencies.chain_selection {
+					log::warn!("Chain selection: {}", inconsistency);
+				}
+				for inconsistency in &inconsistencies.dispute_coordinator {
+					log::warn!("Dispute coordinator: {}", inconsistency);
+				}
+
+				if inconsistencies.is_empty() {
+					info!("No inconsistencies found in the parachains database");
+				} else if cmd.repair {
+					let remaining = inspect::repair(db.clone(), &inconsistencies)?;
+					if remaining.is_empty() {
+						info!("Repaired the parachains database");
+					} else {
+						for inconsistency in &remaining.approval_voting {
+							log::warn!("Approval voting, after repair: {}", inconsistency);
+						}
+						for inconsistency in &remaining.chain_selection {
+							log::warn!("Chain selection, after repair: {}", inconsistency);
+						}
+						for inconsistency in &remaining.dispute_coordinator {
+							log::warn!("Dispute coordinator, after repair: {}", inconsistency);
+						}
+						log::warn!("Inconsistencies remain after repairing, run the command again");
+					}
+				} else {
+					info!("Run with `--repair` to prune the entries involved");
+				}
+				Ok::<_, Error>(())
+			})?)
+		},
 	}?;
 
 	#[cfg(feature = "pyroscope")]
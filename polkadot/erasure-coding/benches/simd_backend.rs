@@ -0,0 +1,107 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Compares the `novelpoly` encoder against every SIMD kernel supported by the running CPU.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use novelpoly::{CodeParams, WrappedShard};
+use polkadot_erasure_coding::simd::{self, Kernel};
+use std::time::Duration;
+
+const N_VALIDATORS: [usize; 5] = [200, 500, 1000, 2000, 10_000];
+
+fn novelpoly_chunks(params: &CodeParams, payload: &[u8]) -> Vec<Vec<u8>> {
+	params
+		.make_encoder()
+		.encode::<WrappedShard>(payload)
+		.unwrap()
+		.into_iter()
+		.map(|shard| shard.into_inner())
+		.collect()
+}
+
+fn encode_5mb_pov(c: &mut Criterion) {
+	const MB: usize = 1024 * 1024;
+
+	let payload: Vec<u8> = (0..5 * MB).map(|i| (i % 251) as u8).collect();
+
+	let mut group = c.benchmark_group("encode");
+	group.throughput(Throughput::Bytes(payload.len() as u64));
+	for n_validators in N_VALIDATORS {
+		let threshold = polkadot_erasure_coding::recovery_threshold(n_validators).unwrap();
+		let params = CodeParams::derive_parameters(n_validators, threshold).unwrap();
+		let expected = novelpoly_chunks(&params, &payload);
+
+		group.bench_with_input(
+			BenchmarkId::new("novelpoly", n_validators),
+			&params,
+			|b, params| b.iter(|| novelpoly_chunks(params, &payload)),
+		);
+
+		for kernel in Kernel::available() {
+			assert!(simd::encode_with_kernel(kernel, n_validators, &payload).unwrap() == expected);
+			group.bench_with_input(
+				BenchmarkId::new(kernel.name(), n_validators),
+				&n_validators,
+				|b, &n| b.iter(|| simd::encode_with_kernel(kernel, n, &payload).unwrap()),
+			);
+		}
+	}
+	group.finish();
+
+	let mut group = c.benchmark_group("systematic");
+	group.throughput(Throughput::Bytes(payload.len() as u64));
+	for n_validators in N_VALIDATORS {
+		let k = polkadot_erasure_coding::systematic_recovery_threshold(n_validators).unwrap();
+		let chunks: Vec<_> =
+			simd::encode(n_validators, &payload).unwrap().into_iter().take(k).collect();
+		let threshold = polkadot_erasure_coding::recovery_threshold(n_validators).unwrap();
+		let params = CodeParams::derive_parameters(n_validators, threshold).unwrap();
+
+		group.bench_with_input(
+			BenchmarkId::new("novelpoly", n_validators),
+			&chunks,
+			|b, chunks| {
+				b.iter(|| {
+					params
+						.make_encoder()
+						.reconstruct_from_systematic(
+							chunks.iter().cloned().map(WrappedShard::new).collect(),
+						)
+						.unwrap()
+				})
+			},
+		);
+		group.bench_with_input(BenchmarkId::new("simd", n_validators), &chunks, |b, chunks| {
+			b.iter(|| simd::reconstruct_from_systematic(n_validators, chunks.clone()).unwrap())
+		});
+	}
+	group.finish();
+}
+
+fn criterion_config() -> Criterion {
+	Criterion::default()
+		.sample_size(15)
+		.warm_up_time(Duration::from_millis(200))
+		.measurement_time(Duration::from_secs(3))
+}
+
+criterion_group!(
+	name = simd_backend;
+	config = criterion_config();
+	targets = encode_5mb_pov,
+);
+criterion_main!(simd_backend);
@@ -23,6 +23,9 @@
 //! Each of n validators stores their piece of data. We assume `n = 3f + k`, `0 < k ≤ 3`.
 //! f is the maximum number of faulty validators in the system.
 //! The data is coded so any f+1 chunks can be used to reconstruct the full data.
+//!
+//! With the `simd` feature enabled, encoding and systematic recovery go through the vectorized
+//! backend in [`simd`], which produces the very same chunks as the default `novelpoly` backend.
 
 use codec::{Decode, Encode};
 use polkadot_node_primitives::{AvailableData, Proof};
@@ -36,6 +39,9 @@ use thiserror::Error;
 
 use novelpoly::{CodeParams, WrappedShard};
 
+#[cfg(feature = "simd")]
+pub mod simd;
+
 // we are limited to the field order of GF(2^16), which is 65536
 const MAX_VALIDATORS: usize = novelpoly::f2e16::FIELD_SIZE;
 
@@ -166,6 +172,9 @@ pub fn reconstruct_from_systematic<T: Decode>(
 		}
 	}
 
+	#[cfg(feature = "simd")]
+	let bytes = simd::reconstruct_from_systematic(n_validators, chunks)?;
+	#[cfg(not(feature = "simd"))]
 	let bytes = code_params.make_encoder().reconstruct_from_systematic(
 		chunks.into_iter().take(k).map(|data| WrappedShard::new(data)).collect(),
 	)?;
@@ -184,19 +193,25 @@ pub fn obtain_chunks_v1(n_validators: usize, data: &AvailableData) -> Result<Vec
 ///
 /// Works only up to 65536 validators, and `n_validators` must be non-zero.
 pub fn obtain_chunks<T: Encode>(n_validators: usize, data: &T) -> Result<Vec<Vec<u8>>, Error> {
-	let params = code_params(n_validators)?;
-	let encoded = data.encode();
-
-	if encoded.is_empty() {
-		return Err(Error::BadPayload)
+	#[cfg(feature = "simd")]
+	{
+		simd::encode(n_validators, &data.encode()[..])
 	}
+	#[cfg(not(feature = "simd"))]
+	{
+		let params = code_params(n_validators)?;
+		let encoded = data.encode();
+
+		if encoded.is_empty() {
+			return Err(Error::BadPayload)
+		}
 
-	let shards = params
-		.make_encoder()
-		.encode::<WrappedShard>(&encoded[..])
-		.expect("Payload non-empty, shard sizes are uniform, and validator numbers checked; qed");
+		let shards = params.make_encoder().encode::<WrappedShard>(&encoded[..]).expect(
+			"Payload non-empty, shard sizes are uniform, and validator numbers checked; qed",
+		);
 
-	Ok(shards.into_iter().map(|w: WrappedShard| w.into_inner()).collect())
+		Ok(shards.into_iter().map(|w: WrappedShard| w.into_inner()).collect())
+	}
 }
 
 /// Reconstruct the v1 available data from a set of chunks.
@@ -0,0 +1,133 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Arithmetic in `GF(2^16)` represented in the Cantor basis, using the very same tables as the
+//! `novelpoly` backend so both produce identical chunks.
+
+use std::sync::OnceLock;
+
+const FIELD_BITS: usize = 16;
+const FIELD_SIZE: usize = 1 << FIELD_BITS;
+
+/// The all ones element, which doubles as the marker of a zero skew factor.
+pub(super) const ONEMASK: u16 = (FIELD_SIZE - 1) as u16;
+
+// x^16 + x^5 + x^3 + x^2 + 1
+const GENERATOR: u16 = 0x2D;
+
+// Cantor basis of `GF(2^16)`.
+const BASE: [u16; FIELD_BITS] = [
+	1, 44234, 15374, 5694, 50562, 60718, 37196, 16402, 27800, 4312, 27250, 47360, 64952, 64308,
+	65336, 39198,
+];
+
+/// Logarithm, exponent and additive FFT skew factor tables.
+pub(super) struct Tables {
+	log: Vec<u16>,
+	exp: Vec<u16>,
+	skews: Vec<u16>,
+}
+
+/// Returns the lazily initialized field tables.
+pub(super) fn tables() -> &'static Tables {
+	static TABLES: OnceLock<Tables> = OnceLock::new();
+	TABLES.get_or_init(Tables::new)
+}
+
+impl Tables {
+	fn new() -> Self {
+		let mut log = vec![0u16; FIELD_SIZE];
+		let mut exp = vec![0u16; FIELD_SIZE];
+
+		// Logarithms of the elements in the polynomial basis.
+		let mas = (1usize << (FIELD_BITS - 1)) - 1;
+		let mut state = 1usize;
+		for i in 0..ONEMASK as usize {
+			exp[state] = i as u16;
+			if state >> (FIELD_BITS - 1) != 0 {
+				state &= mas;
+				state = (state << 1) ^ GENERATOR as usize;
+			} else {
+				state <<= 1;
+			}
+		}
+		exp[0] = ONEMASK;
+
+		// Switch to the Cantor basis.
+		log[0] = 0;
+		for i in 0..FIELD_BITS {
+			for j in 0..(1 << i) {
+				log[j + (1 << i)] = log[j] ^ BASE[i];
+			}
+		}
+		for i in 0..FIELD_SIZE {
+			log[i] = exp[log[i] as usize];
+		}
+		for i in 0..FIELD_SIZE {
+			exp[log[i] as usize] = i as u16;
+		}
+		exp[ONEMASK as usize] = exp[0];
+
+		let mut tables = Tables { log, exp, skews: Vec::new() };
+		tables.skews = tables.skew_factors();
+		tables
+	}
+
+	// Skew factors of the additive FFT, as logarithms.
+	fn skew_factors(&self) -> Vec<u16> {
+		let mut skews = vec![0u16; ONEMASK as usize];
+		let mut base = [0u16; FIELD_BITS - 1];
+		for i in 1..FIELD_BITS {
+			base[i - 1] = 1 << i;
+		}
+
+		for m in 0..(FIELD_BITS - 1) {
+			let step = 1usize << (m + 1);
+			skews[(1 << m) - 1] = 0;
+			for i in m..(FIELD_BITS - 1) {
+				let s = 1usize << (i + 1);
+				let mut j = (1usize << m) - 1;
+				while j < s {
+					skews[j + s] = skews[j] ^ base[i];
+					j += step;
+				}
+			}
+
+			let idx = self.mul(base[m], self.log[(base[m] ^ 1) as usize]);
+			base[m] = ONEMASK - self.log[idx as usize];
+			for i in (m + 1)..(FIELD_BITS - 1) {
+				let b = (self.log[(base[i] ^ 1) as usize] as u32 + base[m] as u32) % ONEMASK as u32;
+				base[i] = self.mul(base[i], b as u16);
+			}
+		}
+
+		skews.into_iter().map(|skew| self.log[skew as usize]).collect()
+	}
+
+	/// Multiplies `x` with the element whose logarithm is `log`.
+	pub(super) fn mul(&self, x: u16, log: u16) -> u16 {
+		if x == 0 {
+			return 0
+		}
+		let sum = self.log[x as usize] as u32 + log as u32;
+		self.exp[((sum & ONEMASK as u32) + (sum >> FIELD_BITS)) as usize]
+	}
+
+	/// Returns the skew factor at `index`, `ONEMASK` standing for a zero factor.
+	pub(super) fn skew(&self, index: usize) -> u16 {
+		self.skews[index]
+	}
+}
@@ -0,0 +1,249 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Vectorized multiply-accumulate kernels over rows of field elements.
+//!
+//! Rows are stored in blocks of [`BLOCK_SYMBOLS`] symbols: the low bytes of all symbols of a
+//! block come first, followed by their high bytes. Multiplication by a constant is linear over
+//! `GF(2)`, so the product of a symbol splits into the sum of the products of its four nibbles,
+//! which are looked up with byte shuffles.
+
+use super::field::Tables;
+
+/// Number of symbols in a block of a row.
+pub(super) const BLOCK_SYMBOLS: usize = 32;
+
+/// Number of bytes in a block of a row.
+pub(super) const BLOCK_BYTES: usize = 2 * BLOCK_SYMBOLS;
+
+/// Implementation used for the field arithmetic.
+///
+/// Only kernels supported by the running CPU can be obtained.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Kernel(Kind);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+	Portable,
+	#[cfg(target_arch = "x86_64")]
+	Avx2,
+	#[cfg(target_arch = "aarch64")]
+	Neon,
+}
+
+impl Kernel {
+	/// The fastest kernel supported by the running CPU.
+	pub fn detect() -> Self {
+		*Self::available().last().expect("the portable kernel is always available; qed")
+	}
+
+	/// The scalar kernel, available everywhere.
+	pub fn portable() -> Self {
+		Kernel(Kind::Portable)
+	}
+
+	/// All kernels supported by the running CPU, slowest first.
+	pub fn available() -> Vec<Self> {
+		#[allow(unused_mut)]
+		let mut kernels = vec![Kernel(Kind::Portable)];
+		#[cfg(target_arch = "x86_64")]
+		if std::is_x86_feature_detected!("avx2") {
+			kernels.push(Kernel(Kind::Avx2));
+		}
+		#[cfg(target_arch = "aarch64")]
+		if std::arch::is_aarch64_feature_detected!("neon") {
+			kernels.push(Kernel(Kind::Neon));
+		}
+		kernels
+	}
+
+	/// Human readable name of the kernel.
+	pub fn name(&self) -> &'static str {
+		match self.0 {
+			Kind::Portable => "portable",
+			#[cfg(target_arch = "x86_64")]
+			Kind::Avx2 => "avx2",
+			#[cfg(target_arch = "aarch64")]
+			Kind::Neon => "neon",
+		}
+	}
+
+	/// Computes `dst ^= src * c`, with `c` given by `factor`.
+	pub(super) fn mul_add(&self, tables: &Tables, dst: &mut [u8], src: &[u8], factor: &Factor) {
+		debug_assert_eq!(dst.len(), src.len());
+		debug_assert_eq!(dst.len() % BLOCK_BYTES, 0);
+		match self.0 {
+			Kind::Portable => portable_mul_add(tables, dst, src, factor.log),
+			// SAFETY: the kernel can only be obtained if the CPU supports AVX2.
+			#[cfg(target_arch = "x86_64")]
+			Kind::Avx2 => unsafe { avx2::mul_add(dst, src, factor) },
+			// SAFETY: the kernel can only be obtained if the CPU supports NEON.
+			#[cfg(target_arch = "aarch64")]
+			Kind::Neon => unsafe { neon::mul_add(dst, src, factor) },
+		}
+	}
+}
+
+/// Computes `dst ^= src`.
+pub(super) fn add(dst: &mut [u8], src: &[u8]) {
+	debug_assert_eq!(dst.len(), src.len());
+	for (d, s) in dst.iter_mut().zip(src) {
+		*d ^= s;
+	}
+}
+
+/// A constant factor together with its nibble product tables.
+pub(super) struct Factor {
+	log: u16,
+	// Low and high bytes of the products of each nibble position with the factor.
+	lo: [[u8; 16]; 4],
+	hi: [[u8; 16]; 4],
+}
+
+impl Factor {
+	/// Prepares the element whose logarithm is `log`.
+	pub(super) fn new(tables: &Tables, log: u16) -> Self {
+		let mut lo = [[0u8; 16]; 4];
+		let mut hi = [[0u8; 16]; 4];
+		for position in 0..4 {
+			for nibble in 0..16u16 {
+				let product = tables.mul(nibble << (4 * position), log);
+				lo[position][nibble as usize] = product as u8;
+				hi[position][nibble as usize] = (product >> 8) as u8;
+			}
+		}
+		Factor { log, lo, hi }
+	}
+}
+
+fn portable_mul_add(tables: &Tables, dst: &mut [u8], src: &[u8], log: u16) {
+	for (d, s) in dst.chunks_exact_mut(BLOCK_BYTES).zip(src.chunks_exact(BLOCK_BYTES)) {
+		for i in 0..BLOCK_SYMBOLS {
+			let x = s[i] as u16 | (s[BLOCK_SYMBOLS + i] as u16) << 8;
+			let product = tables.mul(x, log);
+			d[i] ^= product as u8;
+			d[BLOCK_SYMBOLS + i] ^= (product >> 8) as u8;
+		}
+	}
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+	use super::{Factor, BLOCK_BYTES, BLOCK_SYMBOLS};
+	use std::arch::x86_64::*;
+
+	#[target_feature(enable = "avx2")]
+	unsafe fn table(t: &[u8; 16]) -> __m256i {
+		_mm256_broadcastsi128_si256(_mm_loadu_si128(t.as_ptr() as *const __m128i))
+	}
+
+	#[target_feature(enable = "avx2")]
+	unsafe fn lookup(t: [__m256i; 4], n: [__m256i; 4]) -> __m256i {
+		_mm256_xor_si256(
+			_mm256_xor_si256(_mm256_shuffle_epi8(t[0], n[0]), _mm256_shuffle_epi8(t[1], n[1])),
+			_mm256_xor_si256(_mm256_shuffle_epi8(t[2], n[2]), _mm256_shuffle_epi8(t[3], n[3])),
+		)
+	}
+
+	#[target_feature(enable = "avx2")]
+	pub(super) unsafe fn mul_add(dst: &mut [u8], src: &[u8], factor: &Factor) {
+		let lo = [
+			table(&factor.lo[0]),
+			table(&factor.lo[1]),
+			table(&factor.lo[2]),
+			table(&factor.lo[3]),
+		];
+		let hi = [
+			table(&factor.hi[0]),
+			table(&factor.hi[1]),
+			table(&factor.hi[2]),
+			table(&factor.hi[3]),
+		];
+		let mask = _mm256_set1_epi8(0x0f);
+
+		for (d, s) in dst.chunks_exact_mut(BLOCK_BYTES).zip(src.chunks_exact(BLOCK_BYTES)) {
+			let s_lo = _mm256_loadu_si256(s.as_ptr() as *const __m256i);
+			let s_hi = _mm256_loadu_si256(s.as_ptr().add(BLOCK_SYMBOLS) as *const __m256i);
+			let nibbles = [
+				_mm256_and_si256(s_lo, mask),
+				_mm256_and_si256(_mm256_srli_epi64(s_lo, 4), mask),
+				_mm256_and_si256(s_hi, mask),
+				_mm256_and_si256(_mm256_srli_epi64(s_hi, 4), mask),
+			];
+
+			let d_lo = d.as_mut_ptr() as *mut __m256i;
+			let d_hi = d.as_mut_ptr().add(BLOCK_SYMBOLS) as *mut __m256i;
+			_mm256_storeu_si256(
+				d_lo,
+				_mm256_xor_si256(_mm256_loadu_si256(d_lo), lookup(lo, nibbles)),
+			);
+			_mm256_storeu_si256(
+				d_hi,
+				_mm256_xor_si256(_mm256_loadu_si256(d_hi), lookup(hi, nibbles)),
+			);
+		}
+	}
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+	use super::{Factor, BLOCK_BYTES, BLOCK_SYMBOLS};
+	use std::arch::aarch64::*;
+
+	#[target_feature(enable = "neon")]
+	unsafe fn lookup(t: [uint8x16_t; 4], n: [uint8x16_t; 4]) -> uint8x16_t {
+		veorq_u8(
+			veorq_u8(vqtbl1q_u8(t[0], n[0]), vqtbl1q_u8(t[1], n[1])),
+			veorq_u8(vqtbl1q_u8(t[2], n[2]), vqtbl1q_u8(t[3], n[3])),
+		)
+	}
+
+	#[target_feature(enable = "neon")]
+	pub(super) unsafe fn mul_add(dst: &mut [u8], src: &[u8], factor: &Factor) {
+		let lo = [
+			vld1q_u8(factor.lo[0].as_ptr()),
+			vld1q_u8(factor.lo[1].as_ptr()),
+			vld1q_u8(factor.lo[2].as_ptr()),
+			vld1q_u8(factor.lo[3].as_ptr()),
+		];
+		let hi = [
+			vld1q_u8(factor.hi[0].as_ptr()),
+			vld1q_u8(factor.hi[1].as_ptr()),
+			vld1q_u8(factor.hi[2].as_ptr()),
+			vld1q_u8(factor.hi[3].as_ptr()),
+		];
+		let mask = vdupq_n_u8(0x0f);
+
+		for (d, s) in dst.chunks_exact_mut(BLOCK_BYTES).zip(src.chunks_exact(BLOCK_BYTES)) {
+			// A block spans two 16 byte lanes per half.
+			for lane in [0, 16] {
+				let s_lo = vld1q_u8(s.as_ptr().add(lane));
+				let s_hi = vld1q_u8(s.as_ptr().add(BLOCK_SYMBOLS + lane));
+				let nibbles = [
+					vandq_u8(s_lo, mask),
+					vshrq_n_u8::<4>(s_lo),
+					vandq_u8(s_hi, mask),
+					vshrq_n_u8::<4>(s_hi),
+				];
+
+				let d_lo = d.as_mut_ptr().add(lane);
+				let d_hi = d.as_mut_ptr().add(BLOCK_SYMBOLS + lane);
+				vst1q_u8(d_lo, veorq_u8(vld1q_u8(d_lo), lookup(lo, nibbles)));
+				vst1q_u8(d_hi, veorq_u8(vld1q_u8(d_hi), lookup(hi, nibbles)));
+			}
+		}
+	}
+}
@@ -0,0 +1,236 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Vectorized Reed-Solomon encoder, enabled by the `simd` feature.
+//!
+//! The encoder evaluates the same additive FFT over `GF(2^16)` as the `novelpoly` backend and
+//! produces bit-for-bit identical chunks. Instead of transforming one codeword at a time, the
+//! payload is transposed so that every FFT position becomes a row holding that position's symbol
+//! of all codewords. Each butterfly then turns into a multiply-accumulate of two whole rows, which
+//! is carried out by a [`Kernel`] selected at runtime from the features of the CPU.
+//!
+//! Only encoding and systematic recovery are provided here, recovery from an arbitrary subset of
+//! chunks is always done by `novelpoly`.
+
+use std::sync::OnceLock;
+
+use crate::{code_params, Error};
+
+mod field;
+mod kernel;
+
+#[cfg(test)]
+mod tests;
+
+use field::{tables, Tables, ONEMASK};
+use kernel::{Factor, BLOCK_BYTES, BLOCK_SYMBOLS};
+
+pub use kernel::Kernel;
+
+/// The kernel used by [`encode`], detected once per process.
+pub fn detected_kernel() -> Kernel {
+	static KERNEL: OnceLock<Kernel> = OnceLock::new();
+	*KERNEL.get_or_init(Kernel::detect)
+}
+
+/// Erasure code the encoded `payload` into one chunk for each validator.
+pub fn encode(n_validators: usize, payload: &[u8]) -> Result<Vec<Vec<u8>>, Error> {
+	encode_with_kernel(detected_kernel(), n_validators, payload)
+}
+
+/// Erasure code the encoded `payload` into one chunk for each validator, using `kernel`.
+pub fn encode_with_kernel(
+	kernel: Kernel,
+	n_validators: usize,
+	payload: &[u8],
+) -> Result<Vec<Vec<u8>>, Error> {
+	let k = code_params(n_validators)?.k();
+	if payload.is_empty() {
+		return Err(Error::BadPayload)
+	}
+
+	// Every codeword covers `k` symbols of the payload, the last one being zero padded.
+	let codewords = payload.len().div_ceil(2 * k);
+	let symbol = |codeword: usize, position: usize| -> [u8; 2] {
+		let offset = codeword * 2 * k + 2 * position;
+		[payload.get(offset).copied().unwrap_or(0), payload.get(offset + 1).copied().unwrap_or(0)]
+	};
+
+	// The systematic chunks are the payload itself.
+	let mut chunks: Vec<Vec<u8>> = (0..k.min(n_validators))
+		.map(|position| (0..codewords).flat_map(|codeword| symbol(codeword, position)).collect())
+		.collect();
+	if n_validators <= k {
+		return Ok(chunks)
+	}
+
+	let mut rows = Rows::new(n_validators.div_ceil(k) * k, codewords);
+	for position in 0..k {
+		for codeword in 0..codewords {
+			rows.set(position, codeword, symbol(codeword, position));
+		}
+	}
+
+	let tables = tables();
+	let transform = Transform { kernel, tables };
+	transform.inverse_afft(&mut rows, 0, k);
+	for shift in (k..n_validators).step_by(k) {
+		rows.copy_within(0, shift, k);
+		transform.afft(&mut rows, shift, k);
+	}
+
+	chunks.extend((k..n_validators).map(|position| {
+		(0..codewords).flat_map(|codeword| rows.get(position, codeword)).collect()
+	}));
+	Ok(chunks)
+}
+
+/// Recover the encoded payload from the first `k` chunks, in order.
+///
+/// The result may contain trailing zero padding.
+pub fn reconstruct_from_systematic(
+	n_validators: usize,
+	chunks: Vec<Vec<u8>>,
+) -> Result<Vec<u8>, Error> {
+	let k = code_params(n_validators)?.k();
+	if chunks.len() < k {
+		return Err(Error::NotEnoughChunks)
+	}
+
+	let chunks = &chunks[..k];
+	let chunk_len = chunks[0].len();
+	if chunk_len == 0 || chunks.iter().any(|chunk| chunk.len() != chunk_len) {
+		return Err(Error::NonUniformChunks)
+	}
+	if chunk_len % 2 != 0 {
+		return Err(Error::UnevenLength)
+	}
+
+	let mut payload = vec![0u8; chunk_len * k];
+	for (position, chunk) in chunks.iter().enumerate() {
+		for (codeword, symbol) in chunk.chunks_exact(2).enumerate() {
+			let offset = codeword * 2 * k + 2 * position;
+			payload[offset..offset + 2].copy_from_slice(symbol);
+		}
+	}
+	Ok(payload)
+}
+
+// Symbols of all codewords, one row per FFT position, in the block layout of the kernels.
+struct Rows {
+	data: Vec<u8>,
+	row_len: usize,
+}
+
+impl Rows {
+	fn new(rows: usize, codewords: usize) -> Self {
+		let row_len = codewords.div_ceil(BLOCK_SYMBOLS) * BLOCK_BYTES;
+		Rows { data: vec![0u8; rows * row_len], row_len }
+	}
+
+	fn offsets(&self, codeword: usize) -> (usize, usize) {
+		let block = codeword / BLOCK_SYMBOLS * BLOCK_BYTES;
+		let lane = codeword % BLOCK_SYMBOLS;
+		(block + BLOCK_SYMBOLS + lane, block + lane)
+	}
+
+	// Sets a big endian symbol.
+	fn set(&mut self, row: usize, codeword: usize, symbol: [u8; 2]) {
+		let (hi, lo) = self.offsets(codeword);
+		let row = &mut self.data[row * self.row_len..][..self.row_len];
+		row[hi] = symbol[0];
+		row[lo] = symbol[1];
+	}
+
+	// Gets a big endian symbol.
+	fn get(&self, row: usize, codeword: usize) -> [u8; 2] {
+		let (hi, lo) = self.offsets(codeword);
+		let row = &self.data[row * self.row_len..][..self.row_len];
+		[row[hi], row[lo]]
+	}
+
+	fn copy_within(&mut self, from: usize, to: usize, count: usize) {
+		let len = self.row_len;
+		self.data.copy_within(from * len..(from + count) * len, to * len);
+	}
+
+	// Mutable access to `dst` along with shared access to a different row `src`.
+	fn pair(&mut self, dst: usize, src: usize) -> (&mut [u8], &[u8]) {
+		debug_assert_ne!(dst, src);
+		let len = self.row_len;
+		if dst < src {
+			let (head, tail) = self.data.split_at_mut(src * len);
+			(&mut head[dst * len..][..len], &tail[..len])
+		} else {
+			let (head, tail) = self.data.split_at_mut(dst * len);
+			(&mut tail[..len], &head[src * len..][..len])
+		}
+	}
+}
+
+struct Transform<'a> {
+	kernel: Kernel,
+	tables: &'a Tables,
+}
+
+impl Transform<'_> {
+	// Inverse additive FFT of the `size` rows starting at `index`.
+	fn inverse_afft(&self, rows: &mut Rows, index: usize, size: usize) {
+		let mut depart = 1;
+		while depart < size {
+			let mut j = depart;
+			while j < size {
+				for i in (j - depart)..j {
+					let (dst, src) = rows.pair(index + i + depart, index + i);
+					kernel::add(dst, src);
+				}
+				self.mul_add(rows, index, j, depart);
+				j += depart << 1;
+			}
+			depart <<= 1;
+		}
+	}
+
+	// Additive FFT of the `size` rows starting at `index`.
+	fn afft(&self, rows: &mut Rows, index: usize, size: usize) {
+		let mut depart = size >> 1;
+		while depart > 0 {
+			let mut j = depart;
+			while j < size {
+				self.mul_add(rows, index, j, depart);
+				for i in (j - depart)..j {
+					let (dst, src) = rows.pair(index + i + depart, index + i);
+					kernel::add(dst, src);
+				}
+				j += depart << 1;
+			}
+			depart >>= 1;
+		}
+	}
+
+	// The skewed half of a butterfly layer, skipped for a zero skew factor.
+	fn mul_add(&self, rows: &mut Rows, index: usize, j: usize, depart: usize) {
+		let skew = self.tables.skew(j + index - 1);
+		if skew == ONEMASK {
+			return
+		}
+		let factor = Factor::new(self.tables, skew);
+		for i in (j - depart)..j {
+			let (dst, src) = rows.pair(index + i, index + i + depart);
+			self.kernel.mul_add(self.tables, dst, src, &factor);
+		}
+	}
+}
@@ -0,0 +1,140 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Differential tests against the `novelpoly` backend.
+
+use super::*;
+use novelpoly::WrappedShard;
+use quickcheck::{Arbitrary, Gen, QuickCheck};
+
+fn novelpoly_chunks(n_validators: usize, payload: &[u8]) -> Vec<Vec<u8>> {
+	code_params(n_validators)
+		.unwrap()
+		.make_encoder()
+		.encode::<WrappedShard>(payload)
+		.unwrap()
+		.into_iter()
+		.map(|shard| shard.into_inner())
+		.collect()
+}
+
+fn novelpoly_systematic(n_validators: usize, chunks: Vec<Vec<u8>>) -> Vec<u8> {
+	let params = code_params(n_validators).unwrap();
+	params
+		.make_encoder()
+		.reconstruct_from_systematic(
+			chunks.into_iter().take(params.k()).map(WrappedShard::new).collect(),
+		)
+		.unwrap()
+}
+
+fn assert_matches_novelpoly(n_validators: usize, payload: &[u8]) {
+	let expected = novelpoly_chunks(n_validators, payload);
+	for kernel in Kernel::available() {
+		let chunks = encode_with_kernel(kernel, n_validators, payload).unwrap();
+		assert!(
+			chunks == expected,
+			"{} kernel differs for {} validators and {} bytes",
+			kernel.name(),
+			n_validators,
+			payload.len(),
+		);
+	}
+
+	assert_eq!(
+		reconstruct_from_systematic(n_validators, expected.clone()).unwrap(),
+		novelpoly_systematic(n_validators, expected),
+	);
+}
+
+#[derive(Clone, Debug)]
+struct Payload(Vec<u8>);
+
+impl Arbitrary for Payload {
+	fn arbitrary(g: &mut Gen) -> Self {
+		let len = (u32::arbitrary(g) % (256 * 1024)).max(1);
+		Payload((0..len).map(|_| u8::arbitrary(g)).collect())
+	}
+}
+
+#[test]
+fn detected_kernel_is_available() {
+	assert!(Kernel::available().contains(&detected_kernel()));
+}
+
+#[test]
+fn chunks_match_novelpoly() {
+	fn property(payload: Payload, n_validators: u16) {
+		assert_matches_novelpoly(n_validators.max(2) as usize, &payload.0);
+	}
+
+	QuickCheck::new().quickcheck(property as fn(Payload, u16))
+}
+
+#[test]
+fn chunks_match_novelpoly_at_boundaries() {
+	let payload: Vec<u8> = (0..70_000u32).map(|i| (i * 7 + i / 251) as u8).collect();
+	for n_validators in [2, 3, 4, 5, 6, 7, 10, 100, 200, 300, 500, 1000, 1023, 1024, 1025, 2000] {
+		let k = code_params(n_validators).unwrap().k();
+		// Around the size of a codeword and of a kernel block of codewords.
+		let lens = [1, 2, 3, 2 * k - 1, 2 * k, 2 * k + 1, 64 * k - 1, 64 * k + 3, payload.len()];
+		for len in lens {
+			assert_matches_novelpoly(n_validators, &payload[..len.min(payload.len())]);
+		}
+	}
+}
+
+#[test]
+fn chunks_match_novelpoly_with_many_validators() {
+	let payload: Vec<u8> = (0..10_000u32).map(|i| (i ^ (i >> 8)) as u8).collect();
+	for n_validators in [10_000, 65_536] {
+		assert_matches_novelpoly(n_validators, &payload);
+	}
+}
+
+#[test]
+fn all_ones_payload_matches_novelpoly() {
+	// Exercises the highest nibble products of the lookup tables.
+	assert_matches_novelpoly(1000, &vec![0xff; 5 * 1024]);
+}
+
+#[test]
+fn encode_rejects_bad_input() {
+	assert_eq!(encode(10, &[]), Err(Error::BadPayload));
+	assert_eq!(encode(1, &[1, 2]), Err(Error::NotEnoughValidators));
+	assert_eq!(encode(65_537, &[1, 2]), Err(Error::TooManyValidators));
+}
+
+#[test]
+fn systematic_recovery_rejects_bad_chunks() {
+	let chunks = encode(10, &[1; 100]).unwrap();
+	let k = code_params(10).unwrap().k();
+
+	assert_eq!(
+		reconstruct_from_systematic(10, chunks[..k - 1].to_vec()),
+		Err(Error::NotEnoughChunks)
+	);
+
+	let mut non_uniform = chunks.clone();
+	non_uniform[1].pop();
+	assert_eq!(reconstruct_from_systematic(10, non_uniform), Err(Error::NonUniformChunks));
+
+	let mut uneven = chunks;
+	for chunk in uneven.iter_mut() {
+		chunk.pop();
+	}
+	assert_eq!(reconstruct_from_systematic(10, uneven), Err(Error::UnevenLength));
+}
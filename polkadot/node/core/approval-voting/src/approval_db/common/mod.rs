@@ -34,7 +34,7 @@ use super::{
 
 pub mod migration_helpers;
 
-const STORED_BLOCKS_KEY: &[u8] = b"Approvals_StoredBlocks";
+pub(crate) const STORED_BLOCKS_KEY: &[u8] = b"Approvals_StoredBlocks";
 
 /// A range from earliest..last block number stored within the DB.
 #[derive(Encode, Decode, Debug, Clone, PartialEq)]
@@ -0,0 +1,354 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline consistency checks and repair of the approval-voting data.
+//!
+//! The approval-voting column is not ordered, so all entries are found by walking the
+//! blocks-at-height index over the stored block range, like the subsystem itself does. Entries
+//! which aren't reachable that way are not checked.
+//!
+//! Must only be used on the database of a node which isn't running.
+
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	fmt,
+	sync::Arc,
+};
+
+use polkadot_node_subsystem_util::database::Database;
+use polkadot_primitives::{BlockNumber, CandidateHash, Hash};
+
+use super::{
+	common::{
+		block_entry_key, blocks_at_height_key, candidate_entry_key, load_decode, Config, DbBackend,
+		Error, Result, StoredBlockRange, STORED_BLOCKS_KEY,
+	},
+	v3::{BlockEntry, CandidateEntry},
+};
+use crate::backend::{Backend, OverlayedBackend};
+
+/// An inconsistency in the approval-voting data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inconsistency {
+	/// The stored block range can't be decoded, so none of the entries can be found.
+	UndecodableStoredBlockRange,
+	/// The blocks-at-height index of a block number can't be decoded.
+	UndecodableBlocksAtHeight(BlockNumber),
+	/// A block is indexed at a height, but has no block entry.
+	MissingBlockEntry { block_number: BlockNumber, block_hash: Hash },
+	/// A block is indexed at a height, but its block entry can't be decoded.
+	UndecodableBlockEntry { block_number: BlockNumber, block_hash: Hash },
+	/// A block is indexed at a height which is not its block number.
+	BlockAtWrongHeight { block_number: BlockNumber, block_hash: Hash },
+	/// A block includes a candidate which has no candidate entry.
+	MissingCandidateEntry {
+		block_number: BlockNumber,
+		block_hash: Hash,
+		candidate_hash: CandidateHash,
+	},
+	/// A block includes a candidate whose candidate entry can't be decoded.
+	UndecodableCandidateEntry {
+		block_number: BlockNumber,
+		block_hash: Hash,
+		candidate_hash: CandidateHash,
+	},
+	/// A block includes a candidate which has no approval entry for the block.
+	MissingApprovalEntry {
+		block_number: BlockNumber,
+		block_hash: Hash,
+		candidate_hash: CandidateHash,
+	},
+	/// A candidate has an approval entry for a block which is not stored.
+	DanglingApprovalEntry { candidate_hash: CandidateHash, block_hash: Hash },
+}
+
+impl fmt::Display for Inconsistency {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::UndecodableStoredBlockRange => write!(f, "stored block range can't be decoded"),
+			Self::UndecodableBlocksAtHeight(number) =>
+				write!(f, "blocks at height {} can't be decoded", number),
+			Self::MissingBlockEntry { block_number, block_hash } =>
+				write!(f, "block {:?} at height {} has no block entry", block_hash, block_number),
+			Self::UndecodableBlockEntry { block_number, block_hash } => write!(
+				f,
+				"block entry of block {:?} at height {} can't be decoded",
+				block_hash, block_number
+			),
+			Self::BlockAtWrongHeight { block_number, block_hash } => write!(
+				f,
+				"block {:?} is indexed at height {}, which is not its number",
+				block_hash, block_number
+			),
+			Self::MissingCandidateEntry { block_hash, candidate_hash, .. } => write!(
+				f,
+				"candidate {:?} of block {:?} has no candidate entry",
+				candidate_hash, block_hash
+			),
+			Self::UndecodableCandidateEntry { block_hash, candidate_hash, .. } => write!(
+				f,
+				"candidate entry of candidate {:?} of block {:?} can't be decoded",
+				candidate_hash, block_hash
+			),
+			Self::MissingApprovalEntry { block_hash, candidate_hash, .. } => write!(
+				f,
+				"candidate {:?} has no approval entry for block {:?}",
+				candidate_hash, block_hash
+			),
+			Self::DanglingApprovalEntry { candidate_hash, block_hash } => write!(
+				f,
+				"candidate {:?} has an approval entry for unknown block {:?}",
+				candidate_hash, block_hash
+			),
+		}
+	}
+}
+
+// Load an entry, returning `Err(None)` if it can't be decoded.
+fn load_checked<D: codec::Decode>(
+	store: &dyn Database,
+	config: &Config,
+	key: &[u8],
+) -> Result<std::result::Result<Option<D>, ()>> {
+	match load_decode(store, config.col_approval_data, key) {
+		Ok(entry) => Ok(Ok(entry)),
+		Err(Error::InvalidDecoding(_)) => Ok(Err(())),
+		Err(err) => Err(err),
+	}
+}
+
+/// Check the consistency of the approval-voting data.
+pub fn check_consistency(store: &dyn Database, config: &Config) -> Result<Vec<Inconsistency>> {
+	let mut inconsistencies = Vec::new();
+
+	let range: StoredBlockRange = match load_checked(store, config, STORED_BLOCKS_KEY)? {
+		Ok(Some(range)) => range,
+		Ok(None) => return Ok(inconsistencies),
+		Err(()) => {
+			inconsistencies.push(Inconsistency::UndecodableStoredBlockRange);
+			return Ok(inconsistencies)
+		},
+	};
+
+	let mut blocks = HashMap::new();
+	for block_number in range.0..range.1 {
+		let hashes: Vec<Hash> =
+			match load_checked(store, config, &blocks_at_height_key(block_number))? {
+				Ok(hashes) => hashes.unwrap_or_default(),
+				Err(()) => {
+					inconsistencies.push(Inconsistency::UndecodableBlocksAtHeight(block_number));
+					continue
+				},
+			};
+
+		for block_hash in hashes {
+			match load_checked::<BlockEntry>(store, config, &block_entry_key(&block_hash))? {
+				Ok(Some(entry)) =>
+					if entry.block_number != block_number {
+						inconsistencies
+							.push(Inconsistency::BlockAtWrongHeight { block_number, block_hash });
+					} else {
+						blocks.insert(block_hash, entry);
+					},
+				Ok(None) => inconsistencies
+					.push(Inconsistency::MissingBlockEntry { block_number, block_hash }),
+				Err(()) => inconsistencies
+					.push(Inconsistency::UndecodableBlockEntry { block_number, block_hash }),
+			}
+		}
+	}
+
+	let mut candidates = BTreeMap::new();
+	for (block_hash, block_entry) in &blocks {
+		let (block_hash, block_number) = (*block_hash, block_entry.block_number);
+		for (_, candidate_hash) in &block_entry.candidates {
+			let candidate_hash = *candidate_hash;
+			let entry = match candidates.get(&candidate_hash) {
+				Some(entry) => entry,
+				None => {
+					let entry = load_checked::<CandidateEntry>(
+						store,
+						config,
+						&candidate_entry_key(&candidate_hash),
+					)?;
+					candidates.entry(candidate_hash).or_insert(entry)
+				},
+			};
+
+			match entry {
+				Ok(Some(entry)) if entry.block_assignments.contains_key(&block_hash) => {},
+				Ok(Some(_)) => inconsistencies.push(Inconsistency::MissingApprovalEntry {
+					block_number,
+					block_hash,
+					candidate_hash,
+				}),
+				Ok(None) => inconsistencies.push(Inconsistency::MissingCandidateEntry {
+					block_number,
+					block_hash,
+					candidate_hash,
+				}),
+				Err(()) => inconsistencies.push(Inconsistency::UndecodableCandidateEntry {
+					block_number,
+					block_hash,
+					candidate_hash,
+				}),
+			}
+		}
+	}
+
+	for (candidate_hash, entry) in &candidates {
+		let Ok(Some(entry)) = entry else { continue };
+		for block_hash in entry.block_assignments.keys() {
+			if !blocks.contains_key(block_hash) {
+				inconsistencies.push(Inconsistency::DanglingApprovalEntry {
+					candidate_hash: *candidate_hash,
+					block_hash: *block_hash,
+				});
+			}
+		}
+	}
+
+	Ok(inconsistencies)
+}
+
+/// Repair the given inconsistencies, as found by [`check_consistency`].
+///
+/// Blocks with a missing or corrupted entry, or which include a candidate with a missing or
+/// corrupted entry, are pruned along with their approval entries, the same way finalization
+/// prunes them. Candidate entries left without approval entries are deleted. An undecodable
+/// stored block range is deleted, leaving the entries it covered unreachable.
+pub fn repair(
+	store: Arc<dyn Database>,
+	config: Config,
+	inconsistencies: &[Inconsistency],
+) -> Result<()> {
+	let mut backend = DbBackend::new(store, config);
+	let mut overlay = OverlayedBackend::new(&backend);
+
+	let mut unindexed = HashMap::<BlockNumber, HashSet<Hash>>::new();
+	let mut pruned_blocks = HashSet::new();
+	let mut undecodable_blocks = HashSet::new();
+	let mut undecodable_candidates = HashSet::new();
+	let mut dangling = Vec::new();
+
+	for inconsistency in inconsistencies {
+		match *inconsistency {
+			Inconsistency::UndecodableStoredBlockRange => overlay.delete_stored_block_range(),
+			Inconsistency::UndecodableBlocksAtHeight(block_number) =>
+				overlay.delete_blocks_at_height(block_number),
+			Inconsistency::MissingBlockEntry { block_number, block_hash } |
+			Inconsistency::BlockAtWrongHeight { block_number, block_hash } => {
+				unindexed.entry(block_number).or_default().insert(block_hash);
+			},
+			Inconsistency::UndecodableBlockEntry { block_number, block_hash } => {
+				unindexed.entry(block_number).or_default().insert(block_hash);
+				undecodable_blocks.insert(block_hash);
+			},
+			Inconsistency::MissingCandidateEntry { block_number, block_hash, .. } |
+			Inconsistency::MissingApprovalEntry { block_number, block_hash, .. } => {
+				unindexed.entry(block_number).or_default().insert(block_hash);
+				pruned_blocks.insert(block_hash);
+			},
+			Inconsistency::UndecodableCandidateEntry {
+				block_number,
+				block_hash,
+				candidate_hash,
+			} => {
+				unindexed.entry(block_number).or_default().insert(block_hash);
+				pruned_blocks.insert(block_hash);
+				undecodable_candidates.insert(candidate_hash);
+			},
+			Inconsistency::DanglingApprovalEntry { candidate_hash, block_hash } =>
+				dangling.push((candidate_hash, block_hash)),
+		}
+	}
+
+	// Candidate entries losing approval entries, `None` if not stored.
+	let mut touched_candidates = BTreeMap::new();
+	let mut remove_approval_entry = |overlay: &OverlayedBackend<'_, DbBackend>,
+	                                 candidate_hash: CandidateHash,
+	                                 block_hash: Hash| {
+		if undecodable_candidates.contains(&candidate_hash) {
+			return Ok(())
+		}
+		if !touched_candidates.contains_key(&candidate_hash) {
+			let entry =
+				overlay.load_candidate_entry(&candidate_hash).map_err(Error::InternalError)?;
+			touched_candidates.insert(candidate_hash, entry);
+		}
+		if let Some(Some(entry)) = touched_candidates.get_mut(&candidate_hash) {
+			entry.block_assignments.remove(&block_hash);
+		}
+		Ok::<_, Error>(())
+	};
+
+	for block_hash in pruned_blocks {
+		if undecodable_blocks.contains(&block_hash) {
+			continue
+		}
+		let Some(entry) = overlay.load_block_entry(&block_hash).map_err(Error::InternalError)?
+		else {
+			continue
+		};
+		for (_, candidate_hash) in entry.candidates() {
+			remove_approval_entry(&overlay, *candidate_hash, block_hash)?;
+		}
+		// The children are left in place, finalization prunes them through the blocks-at-height
+		// index.
+		let parent_hash = entry.parent_hash();
+		if !undecodable_blocks.contains(&parent_hash) {
+			if let Some(mut parent) =
+				overlay.load_block_entry(&parent_hash).map_err(Error::InternalError)?
+			{
+				parent.children.retain(|child| child != &block_hash);
+				overlay.write_block_entry(parent);
+			}
+		}
+		overlay.delete_block_entry(&block_hash);
+	}
+	for block_hash in undecodable_blocks {
+		overlay.delete_block_entry(&block_hash);
+	}
+	for (candidate_hash, block_hash) in dangling {
+		remove_approval_entry(&overlay, candidate_hash, block_hash)?;
+	}
+	for candidate_hash in undecodable_candidates {
+		overlay.delete_candidate_entry(&candidate_hash);
+	}
+	for (candidate_hash, entry) in touched_candidates {
+		match entry {
+			Some(entry) if !entry.block_assignments.is_empty() =>
+				overlay.write_candidate_entry(entry),
+			Some(_) => overlay.delete_candidate_entry(&candidate_hash),
+			None => {},
+		}
+	}
+
+	for (block_number, hashes) in unindexed {
+		// Undecodable heights are already deleted by now.
+		let mut at_height =
+			overlay.load_blocks_at_height(&block_number).map_err(Error::InternalError)?;
+		at_height.retain(|hash| !hashes.contains(hash));
+		if at_height.is_empty() {
+			overlay.delete_blocks_at_height(block_number);
+		} else {
+			overlay.write_blocks_at_height(block_number, at_height);
+		}
+	}
+
+	let ops = overlay.into_write_ops().collect::<Vec<_>>();
+	backend.write(ops).map_err(Error::InternalError)
+}
@@ -31,6 +31,7 @@
 //! time being we share the same DB with the rest of Substrate.
 
 pub mod common;
+pub mod inspect;
 pub mod v1;
 pub mod v2;
 pub mod v3;
@@ -19,6 +19,7 @@
 use crate::{
 	approval_db::{
 		common::{migration_helpers::make_bitvec, DbBackend, StoredBlockRange, *},
+		inspect::{check_consistency, repair, Inconsistency},
 		v3::*,
 	},
 	backend::{Backend, OverlayedBackend},
@@ -576,3 +577,86 @@ fn load_all_blocks_works() {
 		vec![block_hash_a, block_hash_b, block_hash_c],
 	)
 }
+
+#[test]
+fn check_consistency_and_repair_works() {
+	let (mut db, store) = make_db();
+
+	let parent_hash = Hash::repeat_byte(1);
+	let block_hash_a = Hash::repeat_byte(2);
+	let block_hash_b = Hash::repeat_byte(3);
+	let block_hash_c = Hash::repeat_byte(4);
+
+	let candidate_receipt_a = make_candidate(ParaId::from(1_u32), parent_hash);
+	let candidate_receipt_b = make_candidate(ParaId::from(2_u32), parent_hash);
+
+	let candidate_hash_a = candidate_receipt_a.hash();
+	let candidate_hash_b = candidate_receipt_b.hash();
+
+	let block_number = 10;
+
+	let block_entry_a = make_block_entry(
+		block_hash_a,
+		parent_hash,
+		block_number,
+		vec![(CoreIndex(0), candidate_hash_a)],
+	);
+	let block_entry_b = make_block_entry(
+		block_hash_b,
+		parent_hash,
+		block_number,
+		vec![(CoreIndex(0), candidate_hash_a), (CoreIndex(1), candidate_hash_b)],
+	);
+
+	let mut new_candidate_info = HashMap::new();
+	new_candidate_info
+		.insert(candidate_hash_a, NewCandidateInfo::new(candidate_receipt_a, GroupIndex(0), None));
+	new_candidate_info
+		.insert(candidate_hash_b, NewCandidateInfo::new(candidate_receipt_b, GroupIndex(1), None));
+
+	let mut overlay_db = OverlayedBackend::new(&db);
+	for block_entry in [block_entry_a, block_entry_b] {
+		add_block_entry(&mut overlay_db, block_entry.into(), 10, |h| {
+			new_candidate_info.get(h).map(|x| x.clone())
+		})
+		.unwrap();
+	}
+	let write_ops = overlay_db.into_write_ops();
+	db.write(write_ops).unwrap();
+
+	assert!(check_consistency(store.as_ref(), &TEST_CONFIG).unwrap().is_empty());
+
+	// Lose the candidate entry of `b` and index a block without an entry.
+	let mut overlay_db = OverlayedBackend::new(&db);
+	overlay_db.delete_candidate_entry(&candidate_hash_b);
+	overlay_db.write_blocks_at_height(block_number, vec![block_hash_a, block_hash_b, block_hash_c]);
+	let write_ops = overlay_db.into_write_ops();
+	db.write(write_ops).unwrap();
+
+	let inconsistencies = check_consistency(store.as_ref(), &TEST_CONFIG).unwrap();
+	assert_eq!(
+		inconsistencies,
+		vec![
+			Inconsistency::MissingBlockEntry { block_number, block_hash: block_hash_c },
+			Inconsistency::MissingCandidateEntry {
+				block_number,
+				block_hash: block_hash_b,
+				candidate_hash: candidate_hash_b,
+			},
+		]
+	);
+
+	repair(store.clone(), TEST_CONFIG, &inconsistencies).unwrap();
+
+	assert!(check_consistency(store.as_ref(), &TEST_CONFIG).unwrap().is_empty());
+	assert_eq!(
+		load_blocks_at_height(store.as_ref(), &TEST_CONFIG, &block_number).unwrap(),
+		vec![block_hash_a]
+	);
+	assert!(load_block_entry(store.as_ref(), &TEST_CONFIG, &block_hash_b).unwrap().is_none());
+
+	let candidate_entry_a = load_candidate_entry(store.as_ref(), &TEST_CONFIG, &candidate_hash_a)
+		.unwrap()
+		.unwrap();
+	assert_eq!(candidate_entry_a.block_assignments.keys().collect::<Vec<_>>(), vec![&block_hash_a]);
+}
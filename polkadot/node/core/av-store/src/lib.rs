@@ -22,6 +22,7 @@
 use std::{
 	collections::{BTreeSet, HashMap, HashSet},
 	io,
+	path::{Path, PathBuf},
 	sync::Arc,
 	time::{Duration, SystemTime, SystemTimeError, UNIX_EPOCH},
 };
@@ -48,7 +49,8 @@ use polkadot_node_subsystem::{
 use polkadot_node_subsystem_util as util;
 use polkadot_primitives::{
 	vstaging::{CandidateEvent, CandidateReceiptV2 as CandidateReceipt},
-	BlockNumber, CandidateHash, ChunkIndex, CoreIndex, Hash, Header, NodeFeatures, ValidatorIndex,
+	BlockNumber, CandidateHash, ChunkIndex, CoreIndex, Hash, Header, Id as ParaId, NodeFeatures,
+	ValidatorIndex,
 };
 use util::availability_chunks::availability_chunk_indices;
 
@@ -67,6 +69,7 @@ const CHUNK_PREFIX: &[u8; 5] = b"chunk";
 const META_PREFIX: &[u8; 4] = b"meta";
 const UNFINALIZED_PREFIX: &[u8; 11] = b"unfinalized";
 const PRUNE_BY_TIME_PREFIX: &[u8; 13] = b"prune_by_time";
+const PARA_PREFIX: &[u8; 4] = b"para";
 
 // We have some keys we want to map to empty values because existence of the key is enough. We use
 // this because rocksdb doesn't support empty values.
@@ -260,6 +263,28 @@ fn delete_meta(tx: &mut DBTransaction, config: &Config, hash: &CandidateHash) {
 	tx.delete(config.col_meta, &key[..])
 }
 
+// The para of a candidate is only recorded for the paras archived for longer than the default.
+fn load_para(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	hash: &CandidateHash,
+) -> Result<Option<ParaId>, Error> {
+	let key = (PARA_PREFIX, hash).encode();
+
+	query_inner(db, config.col_meta, &key)
+}
+
+fn write_para(tx: &mut DBTransaction, config: &Config, hash: &CandidateHash, para_id: ParaId) {
+	let key = (PARA_PREFIX, hash).encode();
+
+	tx.put_vec(config.col_meta, &key, para_id.encode());
+}
+
+fn delete_para(tx: &mut DBTransaction, config: &Config, hash: &CandidateHash) {
+	let key = (PARA_PREFIX, hash).encode();
+	tx.delete(config.col_meta, &key[..])
+}
+
 fn delete_unfinalized_height(tx: &mut DBTransaction, config: &Config, block_number: BlockNumber) {
 	let prefix = (UNFINALIZED_PREFIX, BEBlockNumber(block_number)).encode();
 	tx.delete_prefix(config.col_meta, &prefix);
@@ -421,6 +446,9 @@ struct PruningConfig {
 
 	/// How often to perform data pruning.
 	pruning_interval: Duration,
+
+	/// Longer retention of finalized data of some paras.
+	archival: ArchivalConfig,
 }
 
 impl Default for PruningConfig {
@@ -429,10 +457,45 @@ impl Default for PruningConfig {
 			keep_unavailable_for: KEEP_UNAVAILABLE_FOR,
 			keep_finalized_for: KEEP_FINALIZED_FOR,
 			pruning_interval: PRUNING_INTERVAL,
+			archival: ArchivalConfig::default(),
 		}
 	}
 }
 
+impl PruningConfig {
+	/// How long finalized data of the given para should be kept.
+	fn keep_finalized_for(&self, para_id: Option<ParaId>) -> Duration {
+		para_id
+			.and_then(|para_id| self.archival.keep_finalized_for(para_id))
+			.map_or(self.keep_finalized_for, |archived| archived.max(self.keep_finalized_for))
+	}
+}
+
+/// Opt-in archival of finalized availability data.
+///
+/// By default, the available data and chunks of a candidate are kept for 25 hours after the
+/// block including it is finalized. The archival configuration extends this window, either for
+/// all paras or for some of them. Retention shorter than the default is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct ArchivalConfig {
+	/// How long finalized data of any para is kept.
+	pub keep_finalized_for: Option<Duration>,
+	/// How long finalized data of the given paras is kept, overriding `keep_finalized_for`.
+	pub keep_finalized_for_para: HashMap<ParaId, Duration>,
+}
+
+impl ArchivalConfig {
+	/// Whether archival is enabled at all.
+	pub fn is_enabled(&self) -> bool {
+		self.keep_finalized_for.is_some() || !self.keep_finalized_for_para.is_empty()
+	}
+
+	/// How long finalized data of the given para is kept, if archived.
+	pub fn keep_finalized_for(&self, para_id: ParaId) -> Option<Duration> {
+		self.keep_finalized_for_para.get(&para_id).copied().or(self.keep_finalized_for)
+	}
+}
+
 /// Configuration for the availability store.
 #[derive(Debug, Clone, Copy)]
 pub struct Config {
@@ -505,6 +568,15 @@ impl AvailabilityStoreSubsystem {
 			finalized_number: None,
 		}
 	}
+
+	/// Keep finalized data for longer, according to the given archival configuration.
+	pub fn with_archival(mut self, archival: ArchivalConfig) -> Self {
+		if archival.is_enabled() {
+			gum::info!(target: LOG_TARGET, ?archival, "Availability data archival enabled");
+		}
+		self.pruning_config.archival = archival;
+		self
+	}
 }
 
 /// We keep the hashes and numbers of all unfinalized
@@ -841,6 +913,12 @@ fn note_block_included(
 					let prune_at = at_d + pruning_config.keep_unavailable_for;
 					delete_pruning_key(db_transaction, config, prune_at, &candidate_hash);
 
+					// Remember the para for determining the retention once finalized.
+					let para_id = candidate.descriptor.para_id();
+					if pruning_config.archival.keep_finalized_for(para_id).is_some() {
+						write_para(db_transaction, config, &candidate_hash, para_id);
+					}
+
 					State::Unfinalized(at, vec![be_block])
 				},
 				State::Unfinalized(at, mut within) => {
@@ -1053,12 +1131,19 @@ fn update_blocks_at_finalized_height(
 
 			meta.state = State::Finalized(now.into());
 
+			let keep_finalized_for = if subsystem.pruning_config.archival.is_enabled() {
+				let para_id = load_para(&subsystem.db, &subsystem.config, &candidate_hash)?;
+				subsystem.pruning_config.keep_finalized_for(para_id)
+			} else {
+				subsystem.pruning_config.keep_finalized_for
+			};
+
 			// Write the meta and a pruning record.
 			write_meta(db_transaction, &subsystem.config, &candidate_hash, &meta);
 			write_pruning_key(
 				db_transaction,
 				&subsystem.config,
-				now + subsystem.pruning_config.keep_finalized_for,
+				now + keep_finalized_for,
 				&candidate_hash,
 			);
 		} else {
@@ -1376,6 +1461,7 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(
 		};
 
 		delete_meta(&mut tx, config, &candidate_hash);
+		delete_para(&mut tx, config, &candidate_hash);
 
 		// Clean up all attached data of the candidate.
 		if let Some(meta) = load_meta(db, config, &candidate_hash)? {
@@ -1410,3 +1496,61 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(
 	db.write(tx)?;
 	Ok(())
 }
+
+/// A candidate whose available data was written by [`export_available_data`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedCandidate {
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The para of the candidate, known only for archived paras.
+	pub para_id: Option<ParaId>,
+	/// The file holding the SCALE encoded `AvailableData`.
+	pub path: PathBuf,
+}
+
+/// The outcome of [`export_available_data`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportOutcome {
+	/// Candidates whose available data was exported.
+	pub exported: Vec<ExportedCandidate>,
+	/// Candidates whose available data is not in the store.
+	pub missing: Vec<CandidateHash>,
+}
+
+/// The name of the file [`export_available_data`] writes the available data of a candidate to.
+pub fn export_file_name(candidate_hash: &CandidateHash) -> String {
+	format!("{:?}.available_data", candidate_hash.0)
+}
+
+/// Write the available data of the given candidates to files in `dir`, one per candidate.
+///
+/// The files hold the SCALE encoded `AvailableData`, which is what parachain tooling such as
+/// `pvf-replay` expects. This only reads from the database, so it can be used on the database of
+/// a stopped node while the data is still retained.
+pub fn export_available_data(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	candidates: impl IntoIterator<Item = CandidateHash>,
+	dir: &Path,
+) -> Result<ExportOutcome, Error> {
+	std::fs::create_dir_all(dir)?;
+
+	let mut outcome = ExportOutcome::default();
+	for candidate_hash in candidates {
+		let Some(available_data) = load_available_data(db, config, &candidate_hash)? else {
+			outcome.missing.push(candidate_hash);
+			continue
+		};
+
+		let path = dir.join(export_file_name(&candidate_hash));
+		std::fs::write(&path, available_data.encode())?;
+
+		outcome.exported.push(ExportedCandidate {
+			candidate_hash,
+			para_id: load_para(db, config, &candidate_hash)?,
+			path,
+		});
+	}
+
+	Ok(outcome)
+}
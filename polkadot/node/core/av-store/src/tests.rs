@@ -97,6 +97,7 @@ impl Default for TestState {
 			keep_unavailable_for: Duration::from_secs(1),
 			keep_finalized_for: Duration::from_secs(2),
 			pruning_interval: Duration::from_millis(250),
+			archival: ArchivalConfig::default(),
 		};
 
 		let clock = TestClock { inner: Arc::new(Mutex::new(Duration::from_secs(0))) };
@@ -899,6 +900,118 @@ fn stored_data_kept_until_finalized() {
 	});
 }
 
+#[test]
+fn archived_paras_are_kept_for_longer_and_exported() {
+	let store = test_store();
+	let mut test_state = TestState::default();
+	let keep_archived_for = test_state.pruning_config.keep_finalized_for * 5;
+	test_state.pruning_config.archival = ArchivalConfig {
+		keep_finalized_for: None,
+		keep_finalized_for_para: [(ParaId::from(1), keep_archived_for)].into_iter().collect(),
+	};
+
+	test_harness(test_state.clone(), store.clone(), |mut virtual_overseer| async move {
+		let n_validators = 10;
+		let export_dir = tempfile::tempdir().unwrap();
+
+		let mut candidates = Vec::new();
+		for para_id in [1u32, 2] {
+			let pov = PoV { block_data: BlockData(vec![para_id as u8; 3]) };
+			let candidate = TestCandidateBuilder {
+				para_id: para_id.into(),
+				pov_hash: pov.hash(),
+				..Default::default()
+			}
+			.build();
+			let available_data = AvailableData {
+				pov: Arc::new(pov),
+				validation_data: test_state.persisted_validation_data.clone(),
+			};
+
+			let chunks =
+				polkadot_erasure_coding::obtain_chunks_v1(n_validators as _, &available_data)
+					.unwrap();
+			let branches = polkadot_erasure_coding::branches(chunks.as_ref());
+
+			let (tx, rx) = oneshot::channel();
+			let block_msg = AvailabilityStoreMessage::StoreAvailableData {
+				candidate_hash: candidate.hash(),
+				n_validators,
+				available_data: available_data.clone(),
+				tx,
+				node_features: NodeFeatures::EMPTY,
+				core_index: CoreIndex(1),
+				expected_erasure_root: branches.root(),
+			};
+
+			virtual_overseer.send(FromOrchestra::Communication { msg: block_msg }).await;
+			rx.await.unwrap().unwrap();
+
+			candidates.push((candidate, available_data));
+		}
+
+		let archived = candidates[0].0.hash();
+		let not_archived = candidates[1].0.hash();
+
+		let block_number = 10;
+		let a_leaf = import_leaf(
+			&mut virtual_overseer,
+			Hash::repeat_byte(2),
+			block_number,
+			candidates
+				.iter()
+				.map(|(candidate, _)| candidate_included(candidate.clone()))
+				.collect(),
+			(0..n_validators).map(|_| Sr25519Keyring::Alice.public().into()).collect(),
+		)
+		.await;
+
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::BlockFinalized(a_leaf, block_number),
+		)
+		.await;
+
+		// Past the default retention, only the archived para is left.
+		test_state.clock.inc(test_state.pruning_config.keep_finalized_for * 2);
+		test_state.wait_for_pruning().await;
+
+		assert_eq!(
+			query_available_data(&mut virtual_overseer, archived).await.unwrap(),
+			candidates[0].1,
+		);
+		assert!(has_all_chunks(&mut virtual_overseer, archived, n_validators, true).await);
+		assert!(query_available_data(&mut virtual_overseer, not_archived).await.is_none());
+		assert!(has_all_chunks(&mut virtual_overseer, not_archived, n_validators, false).await);
+
+		let outcome = export_available_data(
+			&store,
+			&TEST_CONFIG,
+			[archived, not_archived],
+			export_dir.path(),
+		)
+		.unwrap();
+		assert_eq!(outcome.missing, vec![not_archived]);
+		assert_eq!(outcome.exported.len(), 1);
+		assert_eq!(outcome.exported[0].candidate_hash, archived);
+		assert_eq!(outcome.exported[0].para_id, Some(ParaId::from(1)));
+		assert_eq!(outcome.exported[0].path, export_dir.path().join(export_file_name(&archived)));
+
+		let exported = std::fs::read(&outcome.exported[0].path).unwrap();
+		assert_eq!(AvailableData::decode(&mut &exported[..]).unwrap(), candidates[0].1);
+
+		// Past the archival retention, the archived para is gone as well.
+		test_state.clock.inc(keep_archived_for);
+		test_state.wait_for_pruning().await;
+
+		assert!(query_available_data(&mut virtual_overseer, archived).await.is_none());
+		assert!(has_all_chunks(&mut virtual_overseer, archived, n_validators, false).await);
+		assert_eq!(load_para(&store, &TEST_CONFIG, &archived).unwrap(), None);
+
+		virtual_overseer
+	});
+}
+
 #[test]
 fn we_dont_miss_anything_if_import_notifications_are_missed() {
 	let store = test_store();
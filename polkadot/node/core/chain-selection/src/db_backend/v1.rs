@@ -46,9 +46,9 @@ use polkadot_node_subsystem_util::database::{DBTransaction, Database};
 use std::sync::Arc;
 
 const BLOCK_ENTRY_PREFIX: &[u8; 14] = b"CS_block_entry";
-const BLOCK_HEIGHT_PREFIX: &[u8; 15] = b"CS_block_height";
-const STAGNANT_AT_PREFIX: &[u8; 14] = b"CS_stagnant_at";
-const LEAVES_KEY: &[u8; 9] = b"CS_leaves";
+pub(crate) const BLOCK_HEIGHT_PREFIX: &[u8; 15] = b"CS_block_height";
+pub(crate) const STAGNANT_AT_PREFIX: &[u8; 14] = b"CS_stagnant_at";
+pub(crate) const LEAVES_KEY: &[u8; 9] = b"CS_leaves";
 
 type Timestamp = u64;
 
@@ -133,7 +133,7 @@ impl From<LeafEntry> for crate::LeafEntry {
 }
 
 #[derive(Encode, Decode)]
-struct LeafEntrySet {
+pub(crate) struct LeafEntrySet {
 	inner: Vec<LeafEntry>,
 }
 
@@ -150,7 +150,7 @@ impl From<LeafEntrySet> for crate::LeafEntrySet {
 }
 
 #[derive(Debug, Encode, Decode, Clone, PartialEq)]
-struct BlockEntry {
+pub(crate) struct BlockEntry {
 	block_hash: Hash,
 	block_number: BlockNumber,
 	parent_hash: Hash,
@@ -336,7 +336,7 @@ impl Backend for DbBackend {
 	}
 }
 
-fn load_decode<D: Decode>(
+pub(crate) fn load_decode<D: Decode>(
 	db: &dyn Database,
 	col_data: u32,
 	key: &[u8],
@@ -347,7 +347,7 @@ fn load_decode<D: Decode>(
 	}
 }
 
-fn block_entry_key(hash: &Hash) -> [u8; 14 + 32] {
+pub(crate) fn block_entry_key(hash: &Hash) -> [u8; 14 + 32] {
 	let mut key = [0; 14 + 32];
 	key[..14].copy_from_slice(BLOCK_ENTRY_PREFIX);
 	hash.using_encoded(|s| key[14..].copy_from_slice(s));
@@ -368,7 +368,7 @@ fn stagnant_at_key(timestamp: Timestamp) -> [u8; 14 + 8] {
 	key
 }
 
-fn decode_block_height_key(key: &[u8]) -> Option<BlockNumber> {
+pub(crate) fn decode_block_height_key(key: &[u8]) -> Option<BlockNumber> {
 	if key.len() != 15 + 4 {
 		return None
 	}
@@ -381,7 +381,7 @@ fn decode_block_height_key(key: &[u8]) -> Option<BlockNumber> {
 	Some(BlockNumber::from_be_bytes(bytes))
 }
 
-fn decode_stagnant_at_key(key: &[u8]) -> Option<Timestamp> {
+pub(crate) fn decode_stagnant_at_key(key: &[u8]) -> Option<Timestamp> {
 	if key.len() != 14 + 8 {
 		return None
 	}
@@ -627,4 +627,63 @@ mod tests {
 
 		assert_eq!(backend.load_blocks_by_number(10).unwrap(), vec![Hash::repeat_byte(3)]);
 	}
+
+	#[test]
+	fn check_consistency_and_repair() {
+		use crate::inspect::{check_consistency, repair, Inconsistency};
+
+		let db = test_db();
+		let config = Config { col_data: 0 };
+
+		let mut backend = DbBackend::new(db.clone(), config);
+
+		let block_hash_a = Hash::repeat_byte(1);
+		let block_hash_b = Hash::repeat_byte(2);
+
+		let block_entry = BlockEntry {
+			block_hash: block_hash_a,
+			block_number: 1,
+			parent_hash: Hash::repeat_byte(0),
+			children: vec![block_hash_b],
+			viability: ViabilityCriteria {
+				earliest_unviable_ancestor: None,
+				explicitly_reverted: false,
+				approval: Approval::Unapproved,
+			},
+			weight: 100,
+		};
+
+		// `b` is indexed and a leaf, but its entry is lost.
+		backend
+			.write(vec![
+				BackendWriteOp::WriteBlockEntry(block_entry.clone().into()),
+				BackendWriteOp::WriteBlocksByNumber(1, vec![block_hash_a]),
+				BackendWriteOp::WriteBlocksByNumber(2, vec![block_hash_b]),
+				BackendWriteOp::WriteViableLeaves(crate::LeafEntrySet {
+					inner: vec![crate::LeafEntry {
+						weight: 200,
+						block_number: 2,
+						block_hash: block_hash_b,
+					}],
+				}),
+			])
+			.unwrap();
+
+		let inconsistencies = check_consistency(&*db, &config).unwrap();
+		assert_eq!(
+			inconsistencies,
+			vec![
+				Inconsistency::MissingBlockEntry { block_number: 2, block_hash: block_hash_b },
+				Inconsistency::MissingChild { block_hash: block_hash_a, child_hash: block_hash_b },
+				Inconsistency::DanglingLeaf(block_hash_b),
+			],
+		);
+
+		repair(db.clone(), config, &inconsistencies).unwrap();
+
+		assert!(check_consistency(&*db, &config).unwrap().is_empty());
+		assert!(backend.load_blocks_by_number(2).unwrap().is_empty());
+		assert!(backend.load_leaves().unwrap().inner.is_empty());
+		assert!(backend.load_block_entry(&block_hash_a).unwrap().unwrap().children.is_empty());
+	}
 }
@@ -0,0 +1,244 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline consistency checks and repair of the chain selection data.
+//!
+//! Must only be used on the database of a node which isn't running.
+
+use std::{
+	collections::{HashMap, HashSet},
+	fmt,
+	sync::Arc,
+};
+
+use polkadot_node_subsystem_util::database::Database;
+use polkadot_primitives::{BlockNumber, Hash};
+
+use crate::{
+	backend::{Backend, OverlayedBackend},
+	db_backend::v1::{
+		self, block_entry_key, decode_block_height_key, decode_stagnant_at_key, load_decode,
+		DbBackend, BLOCK_HEIGHT_PREFIX, LEAVES_KEY, STAGNANT_AT_PREFIX,
+	},
+	BlockEntry, Error, LeafEntrySet, Timestamp,
+};
+
+pub use crate::db_backend::v1::Config;
+
+/// An inconsistency in the chain selection data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inconsistency {
+	/// The set of viable leaves can't be decoded.
+	UndecodableLeaves,
+	/// A viable leaf has no block entry.
+	DanglingLeaf(Hash),
+	/// The blocks-by-number index of a block number can't be decoded.
+	UndecodableBlocksByNumber(BlockNumber),
+	/// A block is indexed by a number, but has no block entry.
+	MissingBlockEntry { block_number: BlockNumber, block_hash: Hash },
+	/// A block is indexed by a number, but its block entry can't be decoded.
+	UndecodableBlockEntry { block_number: BlockNumber, block_hash: Hash },
+	/// A block is indexed by a number which is not its block number.
+	BlockAtWrongHeight { block_number: BlockNumber, block_hash: Hash },
+	/// A block lists a child which has no decodable block entry.
+	MissingChild { block_hash: Hash, child_hash: Hash },
+	/// The stagnant blocks of a timestamp can't be decoded.
+	UndecodableStagnantAt(Timestamp),
+}
+
+impl fmt::Display for Inconsistency {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::UndecodableLeaves => write!(f, "viable leaves can't be decoded"),
+			Self::DanglingLeaf(block_hash) =>
+				write!(f, "viable leaf {:?} has no block entry", block_hash),
+			Self::UndecodableBlocksByNumber(number) =>
+				write!(f, "blocks by number {} can't be decoded", number),
+			Self::MissingBlockEntry { block_number, block_hash } =>
+				write!(f, "block {:?} at height {} has no block entry", block_hash, block_number),
+			Self::UndecodableBlockEntry { block_number, block_hash } => write!(
+				f,
+				"block entry of block {:?} at height {} can't be decoded",
+				block_hash, block_number
+			),
+			Self::BlockAtWrongHeight { block_number, block_hash } => write!(
+				f,
+				"block {:?} is indexed at height {}, which is not its number",
+				block_hash, block_number
+			),
+			Self::MissingChild { block_hash, child_hash } => write!(
+				f,
+				"block {:?} lists child {:?}, which has no block entry",
+				block_hash, child_hash
+			),
+			Self::UndecodableStagnantAt(timestamp) =>
+				write!(f, "stagnant blocks at {} can't be decoded", timestamp),
+		}
+	}
+}
+
+// Load a block entry, returning `Err(())` if it can't be decoded.
+fn load_block_entry(
+	db: &dyn Database,
+	config: &Config,
+	block_hash: &Hash,
+) -> Result<Result<Option<BlockEntry>, ()>, Error> {
+	match load_decode::<v1::BlockEntry>(db, config.col_data, &block_entry_key(block_hash)) {
+		Ok(entry) => Ok(Ok(entry.map(Into::into))),
+		Err(Error::Codec(_)) => Ok(Err(())),
+		Err(err) => Err(err),
+	}
+}
+
+/// Check the consistency of the chain selection data.
+pub fn check_consistency(db: &dyn Database, config: &Config) -> Result<Vec<Inconsistency>, Error> {
+	let mut inconsistencies = Vec::new();
+
+	let mut blocks = HashMap::new();
+	for item in db.iter_with_prefix(config.col_data, &BLOCK_HEIGHT_PREFIX[..]) {
+		let (key, value) = item?;
+		let Some(block_number) = decode_block_height_key(&key) else { continue };
+		let Ok(hashes) = <Vec<Hash> as codec::Decode>::decode(&mut &value[..]) else {
+			inconsistencies.push(Inconsistency::UndecodableBlocksByNumber(block_number));
+			continue
+		};
+
+		for block_hash in hashes {
+			match load_block_entry(db, config, &block_hash)? {
+				Ok(Some(entry)) if entry.block_number == block_number => {
+					blocks.insert(block_hash, entry);
+				},
+				Ok(Some(_)) => inconsistencies
+					.push(Inconsistency::BlockAtWrongHeight { block_number, block_hash }),
+				Ok(None) => inconsistencies
+					.push(Inconsistency::MissingBlockEntry { block_number, block_hash }),
+				Err(()) => inconsistencies
+					.push(Inconsistency::UndecodableBlockEntry { block_number, block_hash }),
+			}
+		}
+	}
+
+	for (block_hash, entry) in &blocks {
+		for child_hash in &entry.children {
+			if blocks.contains_key(child_hash) {
+				continue
+			}
+			if !matches!(load_block_entry(db, config, child_hash)?, Ok(Some(_))) {
+				inconsistencies.push(Inconsistency::MissingChild {
+					block_hash: *block_hash,
+					child_hash: *child_hash,
+				});
+			}
+		}
+	}
+
+	match load_decode::<v1::LeafEntrySet>(db, config.col_data, &LEAVES_KEY[..]) {
+		Ok(leaves) =>
+			for leaf in leaves.map(LeafEntrySet::from).unwrap_or_default().into_hashes_descending()
+			{
+				if blocks.contains_key(&leaf) {
+					continue
+				}
+				if !matches!(load_block_entry(db, config, &leaf)?, Ok(Some(_))) {
+					inconsistencies.push(Inconsistency::DanglingLeaf(leaf));
+				}
+			},
+		Err(Error::Codec(_)) => inconsistencies.push(Inconsistency::UndecodableLeaves),
+		Err(err) => return Err(err),
+	}
+
+	for item in db.iter_with_prefix(config.col_data, &STAGNANT_AT_PREFIX[..]) {
+		let (key, value) = item?;
+		let Some(timestamp) = decode_stagnant_at_key(&key) else { continue };
+		if <Vec<Hash> as codec::Decode>::decode(&mut &value[..]).is_err() {
+			inconsistencies.push(Inconsistency::UndecodableStagnantAt(timestamp));
+		}
+	}
+
+	Ok(inconsistencies)
+}
+
+/// Repair the given inconsistencies, as found by [`check_consistency`].
+///
+/// Corrupted entries are deleted and references to missing blocks are removed. Undecodable leaves
+/// are reset, the set is repopulated as new blocks are imported.
+pub fn repair(
+	db: Arc<dyn Database>,
+	config: Config,
+	inconsistencies: &[Inconsistency],
+) -> Result<(), Error> {
+	let mut backend = DbBackend::new(db, config);
+	let mut overlay = OverlayedBackend::new(&backend);
+
+	let mut unindexed = HashMap::<BlockNumber, HashSet<Hash>>::new();
+	let mut removed_leaves = HashSet::new();
+
+	for inconsistency in inconsistencies {
+		match *inconsistency {
+			Inconsistency::UndecodableLeaves => overlay.write_leaves(Default::default()),
+			Inconsistency::DanglingLeaf(block_hash) => {
+				removed_leaves.insert(block_hash);
+			},
+			Inconsistency::UndecodableBlocksByNumber(block_number) =>
+				overlay.delete_blocks_by_number(block_number),
+			Inconsistency::MissingBlockEntry { block_number, block_hash } |
+			Inconsistency::BlockAtWrongHeight { block_number, block_hash } => {
+				unindexed.entry(block_number).or_default().insert(block_hash);
+			},
+			Inconsistency::UndecodableBlockEntry { block_number, block_hash } => {
+				unindexed.entry(block_number).or_default().insert(block_hash);
+				removed_leaves.insert(block_hash);
+				overlay.delete_block_entry(&block_hash);
+			},
+			Inconsistency::MissingChild { block_hash, child_hash } => {
+				if let Some(mut entry) = overlay.load_block_entry(&block_hash)? {
+					entry.children.retain(|child| child != &child_hash);
+					overlay.write_block_entry(entry);
+				}
+			},
+			Inconsistency::UndecodableStagnantAt(timestamp) =>
+				overlay.delete_stagnant_at(timestamp),
+		}
+	}
+
+	for (block_number, hashes) in unindexed {
+		// Undecodable numbers are already deleted by now.
+		let mut by_number = overlay.load_blocks_by_number(block_number)?;
+		by_number.retain(|hash| !hashes.contains(hash));
+		overlay.write_blocks_by_number(block_number, by_number);
+	}
+
+	if !removed_leaves.is_empty() {
+		let mut leaves = overlay.load_leaves()?;
+		for leaf in removed_leaves {
+			leaves.remove(&leaf);
+		}
+		overlay.write_leaves(leaves);
+	}
+
+	let ops = overlay.into_write_ops().collect::<Vec<_>>();
+	backend.write(ops)
+}
+
+/// Describe the chain selection entry of a block, if there is one.
+pub fn describe_block(
+	db: &dyn Database,
+	config: &Config,
+	block_hash: &Hash,
+) -> Result<Option<String>, Error> {
+	Ok(load_decode::<v1::BlockEntry>(db, config.col_data, &block_entry_key(block_hash))?
+		.map(|entry| format!("{:#?}", BlockEntry::from(entry))))
+}
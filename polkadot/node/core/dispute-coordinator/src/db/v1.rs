@@ -41,9 +41,9 @@ use crate::{
 	LOG_TARGET,
 };
 
-const RECENT_DISPUTES_KEY: &[u8; 15] = b"recent-disputes";
-const EARLIEST_SESSION_KEY: &[u8; 16] = b"earliest-session";
-const CANDIDATE_VOTES_SUBKEY: &[u8; 15] = b"candidate-votes";
+pub(crate) const RECENT_DISPUTES_KEY: &[u8; 15] = b"recent-disputes";
+pub(crate) const EARLIEST_SESSION_KEY: &[u8; 16] = b"earliest-session";
+pub(crate) const CANDIDATE_VOTES_SUBKEY: &[u8; 15] = b"candidate-votes";
 /// Until what session have votes been cleaned up already?
 const CLEANED_VOTES_WATERMARK_KEY: &[u8; 23] = b"cleaned-votes-watermark";
 
@@ -188,7 +188,10 @@ impl Backend for DbBackend {
 	}
 }
 
-fn candidate_votes_key(session: SessionIndex, candidate_hash: &CandidateHash) -> [u8; 15 + 4 + 32] {
+pub(crate) fn candidate_votes_key(
+	session: SessionIndex,
+	candidate_hash: &CandidateHash,
+) -> [u8; 15 + 4 + 32] {
 	let mut buf = [0u8; 15 + 4 + 32];
 	buf[..15].copy_from_slice(CANDIDATE_VOTES_SUBKEY);
 
@@ -199,6 +202,17 @@ fn candidate_votes_key(session: SessionIndex, candidate_hash: &CandidateHash) ->
 	buf
 }
 
+/// Decode the session and candidate hash of a candidate votes key.
+pub(crate) fn decode_candidate_votes_key(key: &[u8]) -> Option<(SessionIndex, CandidateHash)> {
+	if key.len() != 15 + 4 + 32 || !key.starts_with(CANDIDATE_VOTES_SUBKEY) {
+		return None
+	}
+
+	let mut session = [0u8; 4];
+	session.copy_from_slice(&key[15..][..4]);
+	Some((SessionIndex::from_be_bytes(session), CandidateHash(Hash::from_slice(&key[(15 + 4)..]))))
+}
+
 fn candidate_votes_session_prefix(session: SessionIndex) -> [u8; 15 + 4] {
 	let mut buf = [0u8; 15 + 4];
 	buf[..15].copy_from_slice(CANDIDATE_VOTES_SUBKEY);
@@ -274,7 +288,7 @@ impl From<Error> for crate::error::Error {
 /// Result alias for DB errors.
 pub type Result<T> = std::result::Result<T, Error>;
 
-fn load_decode<D: Decode>(
+pub(crate) fn load_decode<D: Decode>(
 	db: &dyn Database,
 	col_dispute_data: u32,
 	key: &[u8],
@@ -687,4 +701,62 @@ mod tests {
 			.is_some());
 		assert!(overlay_db.load_candidate_votes(very_recent, &hash_d).unwrap().is_some());
 	}
+
+	#[test]
+	fn check_consistency_and_repair() {
+		use crate::inspect::{check_consistency, repair, Inconsistency};
+
+		let mut backend = make_db();
+		let store = backend.inner.clone();
+
+		let candidate_receipt = dummy_candidate_receipt_v2(dummy_hash());
+		let candidate_hash = candidate_receipt.hash();
+		let other_hash = CandidateHash(Hash::repeat_byte(1));
+
+		// Votes on `candidate_receipt` are stored under `other_hash` too.
+		let mut overlay_db = OverlayedBackend::new(&backend);
+		overlay_db.write_earliest_session(1);
+		for hash in [candidate_hash, other_hash] {
+			overlay_db.write_candidate_votes(
+				1,
+				hash,
+				CandidateVotes {
+					candidate_receipt: candidate_receipt.clone(),
+					valid: Vec::new(),
+					invalid: Vec::new(),
+				},
+			);
+		}
+		overlay_db.write_recent_disputes(
+			vec![
+				((0, candidate_hash), DisputeStatus::Active),
+				((1, candidate_hash), DisputeStatus::Active),
+				((1, other_hash), DisputeStatus::Active),
+			]
+			.into_iter()
+			.collect(),
+		);
+		let write_ops = overlay_db.into_write_ops();
+		backend.write(write_ops).unwrap();
+
+		let config = ColumnConfiguration { col_dispute_data: 0 };
+		let inconsistencies = check_consistency(&*store, &config).unwrap();
+		assert_eq!(
+			inconsistencies,
+			vec![
+				Inconsistency::MismatchedCandidateVotes { session: 1, candidate_hash: other_hash },
+				Inconsistency::StaleRecentDispute { session: 0, candidate_hash },
+				Inconsistency::DisputeWithoutVotes { session: 1, candidate_hash: other_hash },
+			]
+		);
+
+		repair(&*store, &config, &inconsistencies).unwrap();
+
+		assert!(check_consistency(&*store, &config).unwrap().is_empty());
+		assert!(backend.load_candidate_votes(1, &other_hash).unwrap().is_none());
+		assert_eq!(
+			backend.load_recent_disputes().unwrap().unwrap().into_keys().collect::<Vec<_>>(),
+			vec![(1, candidate_hash)]
+		);
+	}
 }
@@ -0,0 +1,202 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline consistency checks and repair of the dispute coordinator data.
+//!
+//! Must only be used on the database of a node which isn't running.
+
+use std::{collections::HashSet, fmt};
+
+use codec::{Decode, Encode};
+use polkadot_node_subsystem_util::database::{DBTransaction, Database};
+use polkadot_primitives::{CandidateHash, SessionIndex};
+
+use crate::db::v1::{
+	candidate_votes_key, decode_candidate_votes_key, load_decode, CandidateVotes, RecentDisputes,
+	CANDIDATE_VOTES_SUBKEY, EARLIEST_SESSION_KEY, RECENT_DISPUTES_KEY,
+};
+
+pub use crate::db::v1::{ColumnConfiguration, Error};
+
+/// An inconsistency in the dispute coordinator data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inconsistency {
+	/// The earliest session can't be decoded.
+	UndecodableEarliestSession,
+	/// The recent disputes can't be decoded.
+	UndecodableRecentDisputes,
+	/// A recent dispute is older than the earliest session, so it should have been pruned.
+	StaleRecentDispute { session: SessionIndex, candidate_hash: CandidateHash },
+	/// A recent dispute has no votes.
+	DisputeWithoutVotes { session: SessionIndex, candidate_hash: CandidateHash },
+	/// The votes on a candidate can't be decoded.
+	UndecodableCandidateVotes { session: SessionIndex, candidate_hash: CandidateHash },
+	/// The votes on a candidate are stored under the hash of another candidate.
+	MismatchedCandidateVotes { session: SessionIndex, candidate_hash: CandidateHash },
+}
+
+impl fmt::Display for Inconsistency {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::UndecodableEarliestSession => write!(f, "earliest session can't be decoded"),
+			Self::UndecodableRecentDisputes => write!(f, "recent disputes can't be decoded"),
+			Self::StaleRecentDispute { session, candidate_hash } => write!(
+				f,
+				"recent dispute of candidate {:?} in session {} is older than the earliest session",
+				candidate_hash, session
+			),
+			Self::DisputeWithoutVotes { session, candidate_hash } => write!(
+				f,
+				"recent dispute of candidate {:?} in session {} has no votes",
+				candidate_hash, session
+			),
+			Self::UndecodableCandidateVotes { session, candidate_hash } => write!(
+				f,
+				"votes on candidate {:?} in session {} can't be decoded",
+				candidate_hash, session
+			),
+			Self::MismatchedCandidateVotes { session, candidate_hash } => write!(
+				f,
+				"votes stored for candidate {:?} in session {} are for another candidate",
+				candidate_hash, session
+			),
+		}
+	}
+}
+
+/// Check the consistency of the dispute coordinator data.
+pub fn check_consistency(
+	db: &dyn Database,
+	config: &ColumnConfiguration,
+) -> Result<Vec<Inconsistency>, Error> {
+	let mut inconsistencies = Vec::new();
+
+	let earliest_session =
+		match load_decode::<SessionIndex>(db, config.col_dispute_data, EARLIEST_SESSION_KEY) {
+			Ok(earliest_session) => earliest_session,
+			Err(Error::Codec(_)) => {
+				inconsistencies.push(Inconsistency::UndecodableEarliestSession);
+				None
+			},
+			Err(err) => return Err(err),
+		};
+
+	let mut voted = HashSet::new();
+	for item in db.iter_with_prefix(config.col_dispute_data, &CANDIDATE_VOTES_SUBKEY[..]) {
+		let (key, value) = item?;
+		let Some((session, candidate_hash)) = decode_candidate_votes_key(&key) else { continue };
+		match CandidateVotes::decode(&mut &value[..]) {
+			Ok(votes) if votes.candidate_receipt.hash() == candidate_hash => {
+				voted.insert((session, candidate_hash));
+			},
+			Ok(_) => inconsistencies
+				.push(Inconsistency::MismatchedCandidateVotes { session, candidate_hash }),
+			Err(_) => inconsistencies
+				.push(Inconsistency::UndecodableCandidateVotes { session, candidate_hash }),
+		}
+	}
+
+	match load_decode::<RecentDisputes>(db, config.col_dispute_data, RECENT_DISPUTES_KEY) {
+		Ok(recent_disputes) =>
+			for (session, candidate_hash) in recent_disputes.unwrap_or_default().into_keys() {
+				if earliest_session.map_or(false, |earliest| session < earliest) {
+					inconsistencies
+						.push(Inconsistency::StaleRecentDispute { session, candidate_hash });
+				} else if !voted.contains(&(session, candidate_hash)) {
+					inconsistencies
+						.push(Inconsistency::DisputeWithoutVotes { session, candidate_hash });
+				}
+			},
+		Err(Error::Codec(_)) => inconsistencies.push(Inconsistency::UndecodableRecentDisputes),
+		Err(err) => return Err(err),
+	}
+
+	Ok(inconsistencies)
+}
+
+/// Repair the given inconsistencies, as found by [`check_consistency`].
+///
+/// Corrupted votes are deleted, along with the recent disputes relying on them. An undecodable
+/// earliest session is deleted, it is written again once the node imports a block. Undecodable
+/// recent disputes are reset, which drops the tracking of all ongoing disputes.
+pub fn repair(
+	db: &dyn Database,
+	config: &ColumnConfiguration,
+	inconsistencies: &[Inconsistency],
+) -> Result<(), Error> {
+	let col = config.col_dispute_data;
+	let mut tx = DBTransaction::new();
+
+	let mut recent_disputes = None;
+	let mut pruned_disputes = Vec::new();
+	for inconsistency in inconsistencies {
+		match *inconsistency {
+			Inconsistency::UndecodableEarliestSession => tx.delete(col, EARLIEST_SESSION_KEY),
+			Inconsistency::UndecodableRecentDisputes =>
+				recent_disputes = Some(RecentDisputes::default()),
+			Inconsistency::StaleRecentDispute { session, candidate_hash } |
+			Inconsistency::DisputeWithoutVotes { session, candidate_hash } =>
+				pruned_disputes.push((session, candidate_hash)),
+			Inconsistency::UndecodableCandidateVotes { session, candidate_hash } |
+			Inconsistency::MismatchedCandidateVotes { session, candidate_hash } => {
+				tx.delete(col, &candidate_votes_key(session, &candidate_hash));
+				pruned_disputes.push((session, candidate_hash));
+			},
+		}
+	}
+
+	if recent_disputes.is_some() || !pruned_disputes.is_empty() {
+		let mut recent_disputes = match recent_disputes {
+			Some(recent_disputes) => recent_disputes,
+			None =>
+				load_decode::<RecentDisputes>(db, col, RECENT_DISPUTES_KEY)?.unwrap_or_default(),
+		};
+		for dispute in pruned_disputes {
+			recent_disputes.remove(&dispute);
+		}
+		tx.put_vec(col, RECENT_DISPUTES_KEY, recent_disputes.encode());
+	}
+
+	db.write(tx).map_err(Into::into)
+}
+
+/// Describe the votes on a candidate and its disputes, in all sessions they are stored for.
+pub fn describe_candidate(
+	db: &dyn Database,
+	config: &ColumnConfiguration,
+	candidate_hash: &CandidateHash,
+) -> Result<Vec<String>, Error> {
+	let col = config.col_dispute_data;
+	let recent_disputes = load_decode::<RecentDisputes>(db, col, RECENT_DISPUTES_KEY)?;
+
+	let mut descriptions = Vec::new();
+	for item in db.iter_with_prefix(col, &CANDIDATE_VOTES_SUBKEY[..]) {
+		let (key, value) = item?;
+		let Some((session, hash)) = decode_candidate_votes_key(&key) else { continue };
+		if &hash != candidate_hash {
+			continue
+		}
+
+		let status = recent_disputes.as_ref().and_then(|disputes| disputes.get(&(session, hash)));
+		let votes = match CandidateVotes::decode(&mut &value[..]) {
+			Ok(votes) => format!("{:#?}", votes),
+			Err(err) => format!("undecodable votes: {}", err),
+		};
+		descriptions.push(format!("session {}, dispute status {:?}: {}", session, status, votes));
+	}
+
+	Ok(descriptions)
+}
@@ -67,6 +67,7 @@ use self::{
 pub(crate) mod backend;
 pub(crate) mod db;
 pub(crate) mod error;
+pub mod inspect;
 
 /// Subsystem after receiving the first active leaf.
 mod initialized;
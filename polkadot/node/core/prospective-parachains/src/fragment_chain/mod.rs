@@ -130,14 +130,16 @@ use std::{
 };
 
 use super::LOG_TARGET;
-use polkadot_node_subsystem::messages::Ancestors;
+use polkadot_node_subsystem::messages::{
+	Ancestors, ConstraintsIntrospection, FragmentChainIntrospection, IntrospectedCandidate,
+};
 use polkadot_node_subsystem_util::inclusion_emulator::{
 	self, ConstraintModifications, Constraints, Fragment, HypotheticalOrConcreteCandidate,
 	ProspectiveCandidate, RelayChainBlockInfo,
 };
 use polkadot_primitives::{
 	vstaging::CommittedCandidateReceiptV2 as CommittedCandidateReceipt, BlockNumber,
-	CandidateCommitments, CandidateHash, Hash, HeadData, PersistedValidationData,
+	CandidateCommitments, CandidateHash, Hash, HeadData, Id as ParaId, PersistedValidationData,
 	ValidationCodeHash,
 };
 use thiserror::Error;
@@ -366,6 +368,16 @@ impl CandidateEntry {
 		self.candidate_hash
 	}
 
+	fn introspect(&self) -> IntrospectedCandidate {
+		IntrospectedCandidate {
+			candidate_hash: self.candidate_hash,
+			relay_parent: self.relay_parent,
+			parent_head_data_hash: self.parent_head_data_hash,
+			output_head_data_hash: self.output_head_data_hash,
+			backed: self.state == CandidateState::Backed,
+		}
+	}
+
 	fn new(
 		candidate_hash: CandidateHash,
 		candidate: CommittedCandidateReceipt,
@@ -766,6 +778,46 @@ impl FragmentChain {
 			)
 	}
 
+	/// Take a snapshot of the [`FragmentChain`] of `para_id` under the active leaf `leaf`.
+	///
+	/// The rejected candidates are not tracked by the fragment chain and left empty.
+	pub fn introspect(&self, para_id: ParaId, leaf: Hash) -> FragmentChainIntrospection {
+		let constraints = &self.scope.base_constraints;
+
+		FragmentChainIntrospection {
+			para_id,
+			leaf,
+			earliest_relay_parent_number: self.scope.earliest_relay_parent().number,
+			max_depth: self.scope.max_depth,
+			constraints: ConstraintsIntrospection {
+				min_relay_parent_number: constraints.min_relay_parent_number,
+				max_pov_size: constraints.max_pov_size,
+				max_code_size: constraints.max_code_size,
+				ump_remaining: constraints.ump_remaining,
+				ump_remaining_bytes: constraints.ump_remaining_bytes,
+				dmp_remaining_messages: constraints.dmp_remaining_messages.len(),
+				required_parent_hash: constraints.required_parent.hash(),
+				validation_code_hash: constraints.validation_code_hash,
+				upgrade_restricted: constraints.upgrade_restriction.is_some(),
+				future_validation_code: constraints.future_validation_code,
+			},
+			pending_availability: self
+				.scope
+				.pending_availability
+				.iter()
+				.map(|candidate| candidate.candidate_hash)
+				.collect(),
+			best_chain: self
+				.best_chain
+				.chain
+				.iter()
+				.map(|node| CandidateEntry::from(node).introspect())
+				.collect(),
+			unconnected: self.unconnected.candidates().map(CandidateEntry::introspect).collect(),
+			rejected: Vec::new(),
+		}
+	}
+
 	/// Mark a candidate as backed. This can trigger a recreation of the best backable chain.
 	pub fn candidate_backed(&mut self, newly_backed_candidate: &CandidateHash) {
 		// Already backed.
@@ -28,17 +28,17 @@
 
 #![deny(unused_crate_dependencies)]
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use fragment_chain::CandidateStorage;
 use futures::{channel::oneshot, prelude::*};
 
 use polkadot_node_subsystem::{
 	messages::{
-		Ancestors, ChainApiMessage, HypotheticalCandidate, HypotheticalMembership,
-		HypotheticalMembershipRequest, IntroduceSecondedCandidateRequest, ParentHeadData,
-		ProspectiveParachainsMessage, ProspectiveValidationDataRequest, RuntimeApiMessage,
-		RuntimeApiRequest,
+		Ancestors, ChainApiMessage, FragmentChainIntrospection, HypotheticalCandidate,
+		HypotheticalMembership, HypotheticalMembershipRequest, IntroduceSecondedCandidateRequest,
+		ParentHeadData, ProspectiveParachainsMessage, ProspectiveValidationDataRequest,
+		RejectedCandidate, RuntimeApiMessage, RuntimeApiRequest,
 	},
 	overseer, ActiveLeavesUpdate, FromOrchestra, OverseerSignal, SpawnedSubsystem, SubsystemError,
 };
@@ -73,6 +73,9 @@ use self::metrics::Metrics;
 
 const LOG_TARGET: &str = "parachain::prospective-parachains";
 
+/// The number of rejected candidates remembered per para, for introspection.
+const MAX_REJECTED_CANDIDATES_PER_PARA: usize = 16;
+
 struct RelayBlockViewData {
 	// The fragment chains for current and upcoming scheduled paras.
 	fragment_chains: HashMap<ParaId, FragmentChain>,
@@ -86,6 +89,9 @@ struct View {
 	active_leaves: HashSet<Hash>,
 	// The backing implicit view.
 	implicit_view: ImplicitView,
+	// The most recently rejected seconded candidates per para, newest last. Only kept for paras
+	// which have a fragment chain under some relay parent.
+	rejected_candidates: HashMap<ParaId, VecDeque<RejectedCandidate>>,
 }
 
 impl View {
@@ -95,7 +101,17 @@ impl View {
 			per_relay_parent: HashMap::new(),
 			active_leaves: HashSet::new(),
 			implicit_view: ImplicitView::default(),
+			rejected_candidates: HashMap::new(),
+		}
+	}
+
+	// Remember a seconded candidate which couldn't be introduced.
+	fn note_rejected_candidate(&mut self, para: ParaId, rejected: RejectedCandidate) {
+		let rejected_candidates = self.rejected_candidates.entry(para).or_default();
+		if rejected_candidates.len() >= MAX_REJECTED_CANDIDATES_PER_PARA {
+			rejected_candidates.pop_front();
 		}
+		rejected_candidates.push_back(rejected);
 	}
 
 	// Get the fragment chains of this leaf.
@@ -174,6 +190,8 @@ async fn run_iteration<Context>(
 					answer_minimum_relay_parents_request(&view, relay_parent, tx),
 				ProspectiveParachainsMessage::GetProspectiveValidationData(request, tx) =>
 					answer_prospective_validation_data_request(&view, request, tx),
+				ProspectiveParachainsMessage::GetFragmentChainsIntrospection(para, tx) =>
+					answer_fragment_chains_introspection_request(&view, para, tx),
 			},
 		}
 	}
@@ -404,6 +422,13 @@ async fn handle_active_leaves_update<Context>(
 		view.per_relay_parent.retain(|r, _| remaining.contains(&r));
 	}
 
+	{
+		let per_relay_parent = &view.per_relay_parent;
+		view.rejected_candidates.retain(|para, _| {
+			per_relay_parent.values().any(|data| data.fragment_chains.contains_key(para))
+		});
+	}
+
 	if metrics.0.is_some() {
 		let mut active_connected = 0;
 		let mut active_unconnected = 0;
@@ -509,6 +534,7 @@ async fn handle_introduce_seconded_candidate(
 	} = request;
 
 	let candidate_hash = candidate.hash();
+	let candidate_relay_parent = candidate.descriptor.relay_parent();
 	let candidate_entry = match CandidateEntry::new_seconded(candidate_hash, candidate, pvd) {
 		Ok(candidate) => candidate,
 		Err(err) => {
@@ -519,6 +545,14 @@ async fn handle_introduce_seconded_candidate(
 				err
 			);
 
+			view.note_rejected_candidate(
+				para,
+				RejectedCandidate {
+					candidate_hash,
+					relay_parent: candidate_relay_parent,
+					reason: err.to_string(),
+				},
+			);
 			let _ = tx.send(false);
 			return
 		},
@@ -526,6 +560,8 @@ async fn handle_introduce_seconded_candidate(
 
 	let mut added = Vec::with_capacity(view.per_relay_parent.len());
 	let mut para_scheduled = false;
+	// The reason the candidate couldn't be added under an active leaf, if any.
+	let mut rejection_reason = None;
 	// We don't iterate only through the active leaves. We also update the deactivated parents in
 	// the implicit view, so that their upcoming children may see these candidates.
 	for (relay_parent, rp_data) in view.per_relay_parent.iter_mut() {
@@ -558,7 +594,11 @@ async fn handle_introduce_seconded_candidate(
 					?is_active_leaf,
 					"Cannot introduce seconded candidate: {}",
 					err
-				)
+				);
+
+				if is_active_leaf || rejection_reason.is_none() {
+					rejection_reason = Some(err.to_string());
+				}
 			},
 		}
 	}
@@ -579,6 +619,13 @@ async fn handle_introduce_seconded_candidate(
 			candidate = ?candidate_hash,
 			"Newly-seconded candidate cannot be kept under any relay parent",
 		);
+
+		if let Some(reason) = rejection_reason {
+			view.note_rejected_candidate(
+				para,
+				RejectedCandidate { candidate_hash, relay_parent: candidate_relay_parent, reason },
+			);
+		}
 	} else {
 		gum::debug!(
 			target: LOG_TARGET,
@@ -825,6 +872,33 @@ fn answer_minimum_relay_parents_request(
 	let _ = tx.send(v);
 }
 
+fn answer_fragment_chains_introspection_request(
+	view: &View,
+	para: Option<ParaId>,
+	tx: oneshot::Sender<Vec<FragmentChainIntrospection>>,
+) {
+	let mut introspection = Vec::new();
+	for leaf in &view.active_leaves {
+		let Some(leaf_data) = view.per_relay_parent.get(leaf) else { continue };
+
+		for (para_id, fragment_chain) in &leaf_data.fragment_chains {
+			if para.map_or(false, |para| para != *para_id) {
+				continue
+			}
+
+			let mut chain_introspection = fragment_chain.introspect(*para_id, *leaf);
+			chain_introspection.rejected = view
+				.rejected_candidates
+				.get(para_id)
+				.map(|rejected| rejected.iter().cloned().collect())
+				.unwrap_or_default();
+			introspection.push(chain_introspection);
+		}
+	}
+
+	let _ = tx.send(introspection);
+}
+
 fn answer_prospective_validation_data_request(
 	view: &View,
 	request: ProspectiveValidationDataRequest,
@@ -2763,3 +2763,113 @@ fn uses_ancestry_only_within_session() {
 		virtual_overseer
 	});
 }
+
+#[test]
+fn fragment_chains_introspection() {
+	let test_state = TestState::default();
+	let view = test_harness(|mut virtual_overseer| async move {
+		// Leaf A
+		let leaf_a = TestLeaf {
+			number: 100,
+			hash: Hash::from_low_u64_be(130),
+			para_data: vec![
+				(1.into(), PerParaData::new(97, HeadData(vec![1, 2, 3]))),
+				(2.into(), PerParaData::new(100, HeadData(vec![2, 3, 4]))),
+			],
+		};
+		activate_leaf(&mut virtual_overseer, &leaf_a, &test_state).await;
+
+		// Candidate C uses the wrong validation code. It's introduced first, so that it's checked
+		// against the constraints and not rejected by the fork selection rule.
+		let (candidate_c, pvd_c) = make_candidate(
+			leaf_a.hash,
+			leaf_a.number,
+			1.into(),
+			HeadData(vec![1, 2, 3]),
+			HeadData(vec![2]),
+			Hash::repeat_byte(1).into(),
+		);
+		let candidate_hash_c = candidate_c.hash();
+		introduce_seconded_candidate_failed(&mut virtual_overseer, candidate_c, pvd_c).await;
+
+		// Candidate A is backed.
+		let (candidate_a, pvd_a) = make_candidate(
+			leaf_a.hash,
+			leaf_a.number,
+			1.into(),
+			HeadData(vec![1, 2, 3]),
+			HeadData(vec![1]),
+			test_state.validation_code_hash,
+		);
+		let candidate_hash_a = candidate_a.hash();
+		introduce_seconded_candidate(&mut virtual_overseer, candidate_a.clone(), pvd_a).await;
+		back_candidate(&mut virtual_overseer, &candidate_a, candidate_hash_a).await;
+
+		// Candidate B doesn't have a known parent yet.
+		let (candidate_b, pvd_b) = make_candidate(
+			leaf_a.hash,
+			leaf_a.number,
+			1.into(),
+			HeadData(vec![5]),
+			HeadData(vec![6]),
+			test_state.validation_code_hash,
+		);
+		let candidate_hash_b = candidate_b.hash();
+		introduce_seconded_candidate(&mut virtual_overseer, candidate_b, pvd_b).await;
+
+		let (tx, rx) = oneshot::channel();
+		virtual_overseer
+			.send(overseer::FromOrchestra::Communication {
+				msg: ProspectiveParachainsMessage::GetFragmentChainsIntrospection(
+					Some(1.into()),
+					tx,
+				),
+			})
+			.await;
+		let introspection = rx.await.unwrap();
+
+		assert_eq!(introspection.len(), 1);
+		let chain = &introspection[0];
+		assert_eq!(chain.para_id, 1.into());
+		assert_eq!(chain.leaf, leaf_a.hash);
+		assert_eq!(chain.constraints.min_relay_parent_number, 97);
+		assert_eq!(chain.constraints.required_parent_hash, HeadData(vec![1, 2, 3]).hash());
+		assert_eq!(
+			chain
+				.best_chain
+				.iter()
+				.map(|candidate| (candidate.candidate_hash, candidate.backed))
+				.collect::<Vec<_>>(),
+			vec![(candidate_hash_a, true)]
+		);
+		assert_eq!(
+			chain
+				.unconnected
+				.iter()
+				.map(|candidate| (candidate.candidate_hash, candidate.backed))
+				.collect::<Vec<_>>(),
+			vec![(candidate_hash_b, false)]
+		);
+		assert_eq!(chain.rejected.len(), 1);
+		assert_eq!(chain.rejected[0].candidate_hash, candidate_hash_c);
+		assert_eq!(chain.rejected[0].relay_parent, leaf_a.hash);
+		assert!(chain.rejected[0].reason.contains("violates constraints"));
+
+		// Without a para filter, the chains of all scheduled paras are returned.
+		let (tx, rx) = oneshot::channel();
+		virtual_overseer
+			.send(overseer::FromOrchestra::Communication {
+				msg: ProspectiveParachainsMessage::GetFragmentChainsIntrospection(None, tx),
+			})
+			.await;
+		let mut paras =
+			rx.await.unwrap().into_iter().map(|chain| chain.para_id).collect::<Vec<_>>();
+		paras.sort();
+		assert_eq!(paras, vec![1.into(), 2.into()]);
+
+		virtual_overseer
+	});
+
+	assert_eq!(view.active_leaves.len(), 1);
+	assert_eq!(view.rejected_candidates.get(&1.into()).map(|r| r.len()), Some(1));
+}
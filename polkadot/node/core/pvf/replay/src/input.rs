@@ -0,0 +1,87 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Loading the inputs of a replay from disk.
+//!
+//! All inputs are SCALE-encoded, either as raw bytes or hex with a `0x` prefix, as they are
+//! returned by the node's RPC and runtime APIs.
+
+use codec::Decode;
+use color_eyre::eyre::{self, eyre, WrapErr};
+use polkadot_node_primitives::{AvailableData, PoV, VALIDATION_CODE_BOMB_LIMIT};
+use polkadot_parachain_primitives::primitives::ValidationCode;
+use polkadot_primitives::{ExecutorParams, PersistedValidationData, ValidationCodeHash};
+use std::{path::Path, sync::Arc};
+
+/// The candidate to replay.
+pub struct Candidate {
+	/// Hash of the validation code, as it was found on chain.
+	pub code_hash: ValidationCodeHash,
+	/// The decompressed validation code.
+	pub code: Vec<u8>,
+	pub pov: Arc<PoV>,
+	pub pvd: Arc<PersistedValidationData>,
+}
+
+impl Candidate {
+	/// Loads a candidate from its validation code and available data, as stored in `av-store` and
+	/// recovered during disputes.
+	pub fn from_available_data(code: &Path, available_data: &Path) -> eyre::Result<Self> {
+		let AvailableData { pov, validation_data } = read_scale(available_data)?;
+		Self::new(code, pov, validation_data)
+	}
+
+	/// Loads a candidate from its validation code, PoV and persisted validation data.
+	pub fn from_parts(code: &Path, pov: &Path, pvd: &Path) -> eyre::Result<Self> {
+		Self::new(code, Arc::new(read_scale(pov)?), read_scale(pvd)?)
+	}
+
+	fn new(code: &Path, pov: Arc<PoV>, pvd: PersistedValidationData) -> eyre::Result<Self> {
+		let compressed_code = read_bytes(code)?;
+		let code_hash = ValidationCode(compressed_code.clone()).hash();
+		let code =
+			sp_maybe_compressed_blob::decompress(&compressed_code, VALIDATION_CODE_BOMB_LIMIT)
+				.map_err(|e| eyre!("failed to decompress the validation code: {e:?}"))?
+				.into_owned();
+		Ok(Self { code_hash, code, pov, pvd: Arc::new(pvd) })
+	}
+}
+
+/// Loads the executor parameters stored at `path`.
+pub fn read_executor_params(path: &Path) -> eyre::Result<ExecutorParams> {
+	read_scale(path)
+}
+
+/// Reads the SCALE-encoded value stored at `path`.
+pub fn read_scale<T: Decode>(path: &Path) -> eyre::Result<T> {
+	let bytes = read_bytes(path)?;
+	T::decode(&mut &bytes[..]).wrap_err_with(|| format!("failed to decode {}", path.display()))
+}
+
+/// Reads the bytes stored at `path`, decoding them from hex if they start with `0x`.
+fn read_bytes(path: &Path) -> eyre::Result<Vec<u8>> {
+	let bytes =
+		std::fs::read(path).wrap_err_with(|| format!("failed to read {}", path.display()))?;
+	match bytes.strip_prefix(b"0x") {
+		Some(hex) => {
+			let hex = std::str::from_utf8(hex)
+				.wrap_err_with(|| format!("{} is not valid hex", path.display()))?;
+			array_bytes::hex2bytes(hex.trim())
+				.map_err(|e| eyre!("{} is not valid hex: {e:?}", path.display()))
+		},
+		None => Ok(bytes),
+	}
+}
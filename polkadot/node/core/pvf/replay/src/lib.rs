@@ -0,0 +1,32 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Replays the execution of a candidate outside of a node.
+//!
+//! The candidate's validation code is prepared and executed by the same workers a validator uses,
+//! repeatedly and with several executor configurations. Any disagreement between the executions is
+//! reported, along with their timings and the memory used for preparation. This helps reproducing
+//! disputes and checking whether they were caused by nondeterministic execution.
+
+pub mod input;
+pub mod outcome;
+pub mod replay;
+
+pub use input::Candidate;
+pub use outcome::{ConfigReport, Outcome, PreparationMemory, Report, Run, Timings};
+pub use replay::{replay, ExecutorConfig, ReplayOptions};
+
+const LOG_TARGET: &str = "parachain::pvf-replay";
@@ -0,0 +1,198 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Replays the execution of a candidate with the PVF workers and reports any nondeterminism.
+
+use clap::{Parser, ValueEnum};
+use color_eyre::eyre;
+use colored::Colorize;
+use polkadot_node_core_pvf::{EXECUTE_BINARY_NAME, PREPARE_BINARY_NAME};
+use polkadot_node_core_pvf_replay::{
+	input::read_executor_params, replay, Candidate, ConfigReport, ExecutorConfig,
+	PreparationMemory, ReplayOptions, Report,
+};
+use polkadot_primitives::PvfExecKind;
+use std::path::PathBuf;
+
+/// The kind of job to execute the candidate as.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ExecKind {
+	Backing,
+	Approval,
+}
+
+#[derive(Debug, Parser)]
+#[clap(about = "Replays the execution of a candidate and reports any nondeterminism", version)]
+#[command(rename_all = "kebab-case")]
+struct Cli {
+	/// The validation code, compressed or not.
+	#[clap(long)]
+	code: PathBuf,
+
+	/// The available data of the candidate, i.e. its PoV and persisted validation data.
+	#[clap(long, conflicts_with_all = ["pov", "pvd"], required_unless_present_all = ["pov", "pvd"])]
+	available_data: Option<PathBuf>,
+
+	/// The PoV of the candidate.
+	#[clap(long, requires = "pvd")]
+	pov: Option<PathBuf>,
+
+	/// The persisted validation data of the candidate.
+	#[clap(long, requires = "pov")]
+	pvd: Option<PathBuf>,
+
+	/// The executor parameters of the session the candidate was validated in. The defaults are
+	/// used if omitted.
+	#[clap(long)]
+	executor_params: Option<PathBuf>,
+
+	/// Additional executor parameters to replay the candidate with, compared against the first
+	/// ones.
+	#[clap(long)]
+	alt_executor_params: Vec<PathBuf>,
+
+	/// How many times to execute the candidate with every set of executor parameters.
+	#[clap(long, default_value_t = 10)]
+	runs: usize,
+
+	/// Which execution timeout and priority to use.
+	#[clap(long, value_enum, default_value_t = ExecKind::Approval)]
+	exec_kind: ExecKind,
+
+	/// The directory containing the prepare and execute workers. Defaults to the directory of this
+	/// binary.
+	#[clap(long)]
+	workers_path: Option<PathBuf>,
+
+	/// Allows running the workers without all of the sandboxing validators require.
+	#[clap(long)]
+	insecure_validator_i_know_what_i_do: bool,
+}
+
+fn main() -> eyre::Result<()> {
+	color_eyre::install()?;
+	sp_tracing::try_init_simple();
+
+	let cli = Cli::parse();
+	let candidate = match (&cli.available_data, &cli.pov, &cli.pvd) {
+		(Some(available_data), _, _) => Candidate::from_available_data(&cli.code, available_data)?,
+		(None, Some(pov), Some(pvd)) => Candidate::from_parts(&cli.code, pov, pvd)?,
+		_ => eyre::bail!("either `--available-data` or both `--pov` and `--pvd` are required"),
+	};
+
+	let mut executor_configs = vec![match &cli.executor_params {
+		Some(path) => ExecutorConfig {
+			label: path.display().to_string(),
+			executor_params: read_executor_params(path)?,
+		},
+		None => ExecutorConfig { label: "default".into(), executor_params: Default::default() },
+	}];
+	for path in &cli.alt_executor_params {
+		executor_configs.push(ExecutorConfig {
+			label: path.display().to_string(),
+			executor_params: read_executor_params(path)?,
+		});
+	}
+
+	let workers_path = match cli.workers_path {
+		Some(path) => path,
+		None => {
+			let mut path = std::env::current_exe()?;
+			path.pop();
+			path
+		},
+	};
+	let options = ReplayOptions {
+		prepare_worker_path: workers_path.join(PREPARE_BINARY_NAME),
+		execute_worker_path: workers_path.join(EXECUTE_BINARY_NAME),
+		secure_validator_mode: !cli.insecure_validator_i_know_what_i_do,
+		runs: cli.runs,
+		exec_kind: match cli.exec_kind {
+			ExecKind::Backing => PvfExecKind::Backing,
+			ExecKind::Approval => PvfExecKind::Approval,
+		},
+	};
+
+	println!("Replaying candidate with code hash {:?}", candidate.code_hash);
+	let runtime = tokio::runtime::Runtime::new()?;
+	let report = runtime.block_on(replay(&options, &candidate, executor_configs))?;
+	print_report(&report);
+
+	if !report.is_deterministic() {
+		eyre::bail!("nondeterministic execution detected")
+	}
+	Ok(())
+}
+
+fn print_report(report: &Report) {
+	for config in &report.configs {
+		print_config(config);
+	}
+
+	println!();
+	for config in report.nondeterministic_configs() {
+		println!("{} executions with {} disagree", "NONDETERMINISTIC".red().bold(), config.label);
+	}
+	for config in report.diverging_configs() {
+		println!(
+			"{} executions with {} disagree with {}",
+			"DIVERGENT".red().bold(),
+			config.label,
+			report.configs[0].label
+		);
+	}
+	if report.is_deterministic() {
+		println!("{} all executions agree", "OK".green().bold());
+	}
+}
+
+fn print_config(config: &ConfigReport) {
+	println!();
+	println!("{}", config.label.bold());
+	match &config.preparation {
+		Ok(elapsed) => println!("  preparation: {elapsed:?}"),
+		Err(e) => println!("  preparation: {}", format!("failed: {e}").red()),
+	}
+	if let Some(memory) = format_memory(&config.preparation_memory) {
+		println!("  preparation memory: {memory}");
+	}
+	if let Some(timings) = config.execution_timings() {
+		println!(
+			"  execution ({} runs): min {:?}, median {:?}, max {:?}",
+			config.runs.len(),
+			timings.min,
+			timings.median,
+			timings.max
+		);
+	}
+	for (outcome, count) in config.outcomes() {
+		println!("  {count} × {outcome}");
+	}
+}
+
+fn format_memory(memory: &PreparationMemory) -> Option<String> {
+	let stats = [
+		("max rss", memory.max_rss),
+		("max resident", memory.max_resident),
+		("max allocated", memory.max_allocated),
+		("peak tracked allocation", memory.peak_tracked_allocation),
+	];
+	let stats: Vec<_> = stats
+		.into_iter()
+		.filter_map(|(name, kb)| Some(format!("{name} {} KB", kb?)))
+		.collect();
+	(!stats.is_empty()).then(|| stats.join(", "))
+}
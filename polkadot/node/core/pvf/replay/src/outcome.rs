@@ -0,0 +1,272 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Outcomes of replayed executions and their analysis.
+
+use codec::Encode;
+use polkadot_node_core_pvf::ValidationError;
+use polkadot_parachain_primitives::primitives::ValidationResult;
+use polkadot_primitives::Hash;
+use sp_core::hashing::blake2_256;
+use std::{collections::BTreeMap, time::Duration};
+
+/// The outcome of a single execution, reduced to what has to be identical across executions.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Outcome {
+	/// The candidate is valid. Holds the hash of the encoded [`ValidationResult`].
+	Valid(Hash),
+	/// The candidate is invalid.
+	Invalid(String),
+	/// The candidate is possibly invalid, the error may be transient.
+	PossiblyInvalid(String),
+	/// Preparation of the PVF failed.
+	Preparation(String),
+	/// An internal error occurred, the node would not have voted on the candidate.
+	Internal(String),
+	/// The execution deadline was reached before the job could start.
+	ExecutionDeadline,
+}
+
+impl From<Result<ValidationResult, ValidationError>> for Outcome {
+	fn from(result: Result<ValidationResult, ValidationError>) -> Self {
+		match result {
+			Ok(result) => Outcome::Valid(blake2_256(&result.encode()).into()),
+			Err(ValidationError::Invalid(e)) => Outcome::Invalid(e.to_string()),
+			Err(ValidationError::PossiblyInvalid(e)) => Outcome::PossiblyInvalid(e.to_string()),
+			Err(ValidationError::Preparation(e)) => Outcome::Preparation(e.to_string()),
+			Err(ValidationError::Internal(e)) => Outcome::Internal(e.to_string()),
+			Err(ValidationError::ExecutionDeadline) => Outcome::ExecutionDeadline,
+		}
+	}
+}
+
+impl std::fmt::Display for Outcome {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Outcome::Valid(hash) => write!(f, "valid (result hash {hash:?})"),
+			Outcome::Invalid(e) => write!(f, "invalid: {e}"),
+			Outcome::PossiblyInvalid(e) => write!(f, "possibly invalid: {e}"),
+			Outcome::Preparation(e) => write!(f, "preparation failed: {e}"),
+			Outcome::Internal(e) => write!(f, "internal error: {e}"),
+			Outcome::ExecutionDeadline => write!(f, "execution deadline reached"),
+		}
+	}
+}
+
+/// A single execution of the PVF.
+#[derive(Debug, Clone)]
+pub struct Run {
+	/// What the execution resulted in.
+	pub outcome: Outcome,
+	/// The wall clock time from submitting the job to the host to receiving its result.
+	pub elapsed: Duration,
+}
+
+/// Peak memory usage of the preparation job, in kilobytes, as reported by the prepare worker.
+///
+/// Each of the stats is only available on some platforms, and none of them is reported if the
+/// artifact was already prepared for another executor configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreparationMemory {
+	/// `ru_maxrss` of the preparation job.
+	pub max_rss: Option<u64>,
+	/// Max resident memory reported by the memory tracker.
+	pub max_resident: Option<u64>,
+	/// Max allocated memory reported by the memory tracker.
+	pub max_allocated: Option<u64>,
+	/// Peak allocation tracked by the worker's allocator.
+	pub peak_tracked_allocation: Option<u64>,
+}
+
+/// Minimum, median and maximum of a set of durations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+	pub min: Duration,
+	pub median: Duration,
+	pub max: Duration,
+}
+
+impl Timings {
+	/// Computes the timings of `durations`, `None` if it is empty.
+	pub fn of(durations: impl IntoIterator<Item = Duration>) -> Option<Self> {
+		let mut durations: Vec<_> = durations.into_iter().collect();
+		durations.sort();
+		Some(Self {
+			min: *durations.first()?,
+			median: durations[durations.len() / 2],
+			max: *durations.last()?,
+		})
+	}
+}
+
+/// Everything observed while replaying the candidate with one executor configuration.
+#[derive(Debug, Clone)]
+pub struct ConfigReport {
+	/// Human readable name of the executor configuration.
+	pub label: String,
+	/// How long preparing the PVF took, or why it failed.
+	pub preparation: Result<Duration, String>,
+	/// Memory used by the preparation.
+	pub preparation_memory: PreparationMemory,
+	/// The executions, in the order they were run. Empty if preparation failed.
+	pub runs: Vec<Run>,
+}
+
+impl ConfigReport {
+	/// The distinct outcomes of the executions, along with how often each of them occurred.
+	pub fn outcomes(&self) -> BTreeMap<&Outcome, usize> {
+		let mut outcomes = BTreeMap::new();
+		for run in &self.runs {
+			*outcomes.entry(&run.outcome).or_default() += 1;
+		}
+		outcomes
+	}
+
+	/// The outcome every execution agreed on. `None` if they disagreed or there were none.
+	pub fn agreed_outcome(&self) -> Option<&Outcome> {
+		let outcomes = self.outcomes();
+		match outcomes.len() {
+			1 => outcomes.into_keys().next(),
+			_ => None,
+		}
+	}
+
+	/// Whether the executions disagreed with each other.
+	pub fn is_nondeterministic(&self) -> bool {
+		self.outcomes().len() > 1
+	}
+
+	/// The execution timings, `None` if there were no executions.
+	pub fn execution_timings(&self) -> Option<Timings> {
+		Timings::of(self.runs.iter().map(|run| run.elapsed))
+	}
+}
+
+/// The report of a whole replay.
+#[derive(Debug, Clone)]
+pub struct Report {
+	/// One entry per executor configuration. The first one is the baseline the other ones are
+	/// compared against.
+	pub configs: Vec<ConfigReport>,
+}
+
+impl Report {
+	/// The configurations whose executions disagreed with each other.
+	pub fn nondeterministic_configs(&self) -> impl Iterator<Item = &ConfigReport> {
+		self.configs.iter().filter(|config| config.is_nondeterministic())
+	}
+
+	/// The configurations whose executions agreed with each other but not with the baseline.
+	///
+	/// Configurations that are nondeterministic on their own, or have no executions, are not
+	/// compared. Neither is anything if the baseline is nondeterministic.
+	pub fn diverging_configs(&self) -> impl Iterator<Item = &ConfigReport> {
+		let baseline = self.configs.first().and_then(ConfigReport::agreed_outcome);
+		self.configs.iter().skip(1).filter(move |config| {
+			matches!(
+				(baseline, config.agreed_outcome()),
+				(Some(baseline), Some(outcome)) if baseline != outcome
+			)
+		})
+	}
+
+	/// Whether all executions of all configurations agreed with each other.
+	pub fn is_deterministic(&self) -> bool {
+		self.nondeterministic_configs().next().is_none() &&
+			self.diverging_configs().next().is_none()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn valid(n: u8) -> Outcome {
+		Outcome::Valid(Hash::repeat_byte(n))
+	}
+
+	fn config(label: &str, outcomes: impl IntoIterator<Item = Outcome>) -> ConfigReport {
+		ConfigReport {
+			label: label.into(),
+			preparation: Ok(Duration::from_secs(1)),
+			preparation_memory: Default::default(),
+			runs: outcomes
+				.into_iter()
+				.enumerate()
+				.map(|(i, outcome)| Run { outcome, elapsed: Duration::from_millis(i as u64) })
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn identical_outcomes_are_deterministic() {
+		let report = Report {
+			configs: vec![
+				config("default", [valid(1), valid(1), valid(1)]),
+				config("alt", [valid(1), valid(1)]),
+			],
+		};
+		assert_eq!(report.configs[0].agreed_outcome(), Some(&valid(1)));
+		assert!(report.is_deterministic());
+	}
+
+	#[test]
+	fn disagreeing_runs_are_nondeterministic() {
+		let report = Report {
+			configs: vec![
+				config("default", [valid(1), Outcome::Invalid("trap".into()), valid(1)]),
+				config("alt", [valid(1)]),
+			],
+		};
+		assert_eq!(report.configs[0].outcomes().get(&valid(1)), Some(&2));
+		assert_eq!(report.configs[0].agreed_outcome(), None);
+		assert_eq!(
+			report.nondeterministic_configs().map(|c| c.label.as_str()).collect::<Vec<_>>(),
+			vec!["default"],
+		);
+		// nothing to compare against
+		assert_eq!(report.diverging_configs().count(), 0);
+		assert!(!report.is_deterministic());
+	}
+
+	#[test]
+	fn configs_diverging_from_baseline_are_reported() {
+		let report = Report {
+			configs: vec![
+				config("default", [valid(1), valid(1)]),
+				config("same", [valid(1)]),
+				config("other", [valid(2), valid(2)]),
+				config("unprepared", []),
+			],
+		};
+		assert_eq!(report.nondeterministic_configs().count(), 0);
+		assert_eq!(
+			report.diverging_configs().map(|c| c.label.as_str()).collect::<Vec<_>>(),
+			vec!["other"],
+		);
+		assert!(!report.is_deterministic());
+	}
+
+	#[test]
+	fn timings_are_computed() {
+		assert_eq!(Timings::of([]), None);
+		let ms = Duration::from_millis;
+		assert_eq!(
+			Timings::of([ms(30), ms(10), ms(20), ms(50)]),
+			Some(Timings { min: ms(10), median: ms(30), max: ms(50) }),
+		);
+	}
+}
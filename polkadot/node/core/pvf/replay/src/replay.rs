@@ -0,0 +1,197 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Replaying a candidate on a PVF validation host.
+
+use crate::{
+	input::Candidate,
+	outcome::{ConfigReport, Outcome, PreparationMemory, Report, Run},
+	LOG_TARGET,
+};
+use color_eyre::eyre::{self, eyre};
+use futures::channel::oneshot;
+use polkadot_node_core_pvf::{start, Config, Metrics, PrepareJobKind, Priority, PvfPrepData};
+use polkadot_node_metrics::metrics::{prometheus::Registry, Metrics as _};
+use polkadot_node_primitives::NODE_VERSION;
+use polkadot_node_subsystem::messages::PvfExecKind;
+use polkadot_primitives::{
+	executor_params::{
+		DEFAULT_APPROVAL_EXECUTION_TIMEOUT, DEFAULT_BACKING_EXECUTION_TIMEOUT,
+		DEFAULT_LENIENT_PREPARATION_TIMEOUT,
+	},
+	ExecutorParams, Hash, PvfExecKind as RuntimePvfExecKind, PvfPrepKind,
+};
+use std::{path::PathBuf, time::Instant};
+
+/// An executor configuration to replay the candidate with.
+#[derive(Debug, Clone)]
+pub struct ExecutorConfig {
+	/// Human readable name of the configuration, e.g. the file it was loaded from.
+	pub label: String,
+	pub executor_params: ExecutorParams,
+}
+
+/// How to run the validation host.
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+	pub prepare_worker_path: PathBuf,
+	pub execute_worker_path: PathBuf,
+	/// Whether to fail if the workers cannot be fully sandboxed, as validators do by default.
+	pub secure_validator_mode: bool,
+	/// How many times to execute the candidate with every executor configuration.
+	pub runs: usize,
+	/// Whether to execute the candidate like backing or like approval checking does. Only the
+	/// execution timeout and the job priority depend on it.
+	pub exec_kind: RuntimePvfExecKind,
+}
+
+/// Prepares and executes `candidate` with each of `executor_configs` in turn.
+///
+/// Jobs are submitted one at a time, so that their timings are not skewed by each other, and the
+/// artifacts are compiled in a temporary cache, so that nothing prepared by a node is reused.
+pub async fn replay(
+	options: &ReplayOptions,
+	candidate: &Candidate,
+	executor_configs: Vec<ExecutorConfig>,
+) -> eyre::Result<Report> {
+	let cache_dir = tempfile::tempdir()?;
+	let registry = Registry::new();
+	let metrics = Metrics::try_register(&registry)?;
+	let host_config = Config::new(
+		cache_dir.path().to_owned(),
+		Some(NODE_VERSION.to_owned()),
+		options.secure_validator_mode,
+		options.prepare_worker_path.clone(),
+		options.execute_worker_path.clone(),
+		1,
+		1,
+		1,
+	);
+	let (mut host, task) = start(host_config, metrics)
+		.await
+		.map_err(|e| eyre!("failed to start the validation host: {e}"))?;
+	let task = tokio::spawn(task);
+
+	let mut configs = Vec::with_capacity(executor_configs.len());
+	for ExecutorConfig { label, executor_params } in executor_configs {
+		gum::info!(target: LOG_TARGET, %label, "Preparing PVF");
+		let pvf = PvfPrepData::from_code(
+			candidate.code.clone(),
+			executor_params.clone(),
+			executor_params
+				.pvf_prep_timeout(PvfPrepKind::Prepare)
+				.unwrap_or(DEFAULT_LENIENT_PREPARATION_TIMEOUT),
+			PrepareJobKind::Compilation,
+		);
+
+		let memory_before = MemorySamples::gather(&registry);
+		let started = Instant::now();
+		let (tx, rx) = oneshot::channel();
+		host.precheck_pvf(pvf.clone(), tx).await.map_err(|e| eyre!(e))?;
+		let preparation = rx.await?.map(|()| started.elapsed()).map_err(|e| e.to_string());
+		let preparation_memory = MemorySamples::gather(&registry).since(&memory_before);
+
+		let mut runs = Vec::with_capacity(options.runs);
+		if preparation.is_ok() {
+			let (exec_kind, priority, default_timeout) = match options.exec_kind {
+				RuntimePvfExecKind::Backing => (
+					// the relay parent is only used to drop jobs outside of the active leaves,
+					// which are never updated here
+					PvfExecKind::Backing(Hash::zero()),
+					Priority::Normal,
+					DEFAULT_BACKING_EXECUTION_TIMEOUT,
+				),
+				RuntimePvfExecKind::Approval =>
+					(PvfExecKind::Approval, Priority::Critical, DEFAULT_APPROVAL_EXECUTION_TIMEOUT),
+			};
+			let exec_timeout =
+				executor_params.pvf_exec_timeout(options.exec_kind).unwrap_or(default_timeout);
+
+			for run in 0..options.runs {
+				let started = Instant::now();
+				let (tx, rx) = oneshot::channel();
+				host.execute_pvf(
+					pvf.clone(),
+					exec_timeout,
+					candidate.pvd.clone(),
+					candidate.pov.clone(),
+					priority,
+					exec_kind,
+					tx,
+				)
+				.await
+				.map_err(|e| eyre!(e))?;
+				let outcome = Outcome::from(rx.await?);
+				let elapsed = started.elapsed();
+				gum::debug!(target: LOG_TARGET, %label, run, %outcome, ?elapsed, "Executed PVF");
+				runs.push(Run { outcome, elapsed });
+			}
+		}
+
+		configs.push(ConfigReport { label, preparation, preparation_memory, runs });
+	}
+
+	task.abort();
+	Ok(Report { configs })
+}
+
+/// The count and sum of the preparation memory histograms of the host metrics.
+///
+/// Every preparation job observes each histogram at most once, so the difference between two
+/// samples taken around a single job is the memory used by that job.
+#[derive(Default)]
+struct MemorySamples {
+	max_rss: (u64, f64),
+	max_resident: (u64, f64),
+	max_allocated: (u64, f64),
+	peak_tracked_allocation: (u64, f64),
+}
+
+impl MemorySamples {
+	fn gather(registry: &Registry) -> Self {
+		let mut samples = Self::default();
+		for family in registry.gather() {
+			let sample = match family.get_name() {
+				"polkadot_pvf_preparation_max_rss" => &mut samples.max_rss,
+				"polkadot_pvf_preparation_max_resident" => &mut samples.max_resident,
+				"polkadot_pvf_preparation_max_allocated" => &mut samples.max_allocated,
+				"polkadot_pvf_preparation_peak_tracked_allocation" =>
+					&mut samples.peak_tracked_allocation,
+				_ => continue,
+			};
+			if let Some(metric) = family.get_metric().first() {
+				let histogram = metric.get_histogram();
+				*sample = (histogram.get_sample_count(), histogram.get_sample_sum());
+			}
+		}
+		samples
+	}
+
+	fn since(&self, before: &Self) -> PreparationMemory {
+		let delta = |after: (u64, f64), before: (u64, f64)| {
+			(after.0 == before.0 + 1).then(|| (after.1 - before.1) as u64)
+		};
+		PreparationMemory {
+			max_rss: delta(self.max_rss, before.max_rss),
+			max_resident: delta(self.max_resident, before.max_resident),
+			max_allocated: delta(self.max_allocated, before.max_allocated),
+			peak_tracked_allocation: delta(
+				self.peak_tracked_allocation,
+				before.peak_tracked_allocation,
+			),
+		}
+	}
+}
@@ -24,7 +24,9 @@ use crate::{
 	artifacts::{ArtifactId, ArtifactPathId, ArtifactState, Artifacts, ArtifactsCleanupConfig},
 	execute::{self, PendingExecutionRequest},
 	metrics::Metrics,
-	prepare, Priority, SecurityStatus, ValidationError, LOG_TARGET,
+	prepare,
+	shared_artifacts::{self, SharedArtifactsConfig},
+	Priority, SecurityStatus, ValidationError, LOG_TARGET,
 };
 use always_assert::never;
 use futures::{
@@ -204,6 +206,9 @@ pub struct Config {
 	pub execute_worker_spawn_timeout: Duration,
 	/// The maximum number of execute workers that can run at the same time.
 	pub execute_workers_max_num: usize,
+
+	/// The shared store to seed artifacts from, if any.
+	pub shared_artifacts: Option<SharedArtifactsConfig>,
 }
 
 impl Config {
@@ -231,6 +236,8 @@ impl Config {
 			execute_worker_program_path,
 			execute_worker_spawn_timeout: Duration::from_secs(3),
 			execute_workers_max_num,
+
+			shared_artifacts: None,
 		}
 	}
 }
@@ -303,6 +310,22 @@ pub async fn start(
 		from_prepare_pool,
 	);
 
+	// If there is a shared store, the host reaches the prepare queue through it.
+	let (to_prepare_queue_tx, from_prepare_queue_rx, run_shared_artifacts) =
+		match config.shared_artifacts.clone() {
+			Some(shared_artifacts) => {
+				let (to_queue_tx, from_queue_rx, run) = shared_artifacts::start(
+					shared_artifacts,
+					config.cache_path.clone(),
+					to_prepare_queue_tx,
+					from_prepare_queue_rx,
+				);
+				(to_queue_tx, from_queue_rx, run.boxed())
+			},
+			None =>
+				(to_prepare_queue_tx, from_prepare_queue_rx, futures::future::pending().boxed()),
+		};
+
 	let (to_execute_queue_tx, from_execute_queue_rx, run_execute_queue) = execute::start(
 		metrics,
 		config.execute_worker_program_path.to_owned(),
@@ -338,6 +361,7 @@ pub async fn start(
 			_ = run_host.fuse() => {},
 			_ = run_prepare_queue.fuse() => {},
 			_ = run_prepare_pool.fuse() => {},
+			_ = run_shared_artifacts.fuse() => {},
 			_ = run_execute_queue.fuse() => {},
 			_ = run_sweeper.fuse() => {},
 		};
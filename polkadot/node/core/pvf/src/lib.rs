@@ -84,6 +84,9 @@
 //! A pruning task will run at a fixed interval of time. This task will remove all artifacts that
 //! weren't used or received a heads up signal for a while.
 //!
+//! Optionally, artifacts can be seeded from a read-only store shared between several nodes, see
+//! [`SharedArtifactsConfig`].
+//!
 //! ## Execution
 //!
 //! The execute workers will be fed by the requests from the execution queue, which is basically a
@@ -99,6 +102,7 @@ mod prepare;
 mod priority;
 #[cfg(target_os = "linux")]
 mod security;
+mod shared_artifacts;
 mod worker_interface;
 
 #[cfg(feature = "test-utils")]
@@ -111,6 +115,7 @@ pub use host::{
 };
 pub use metrics::Metrics;
 pub use priority::Priority;
+pub use shared_artifacts::SharedArtifactsConfig;
 pub use worker_interface::{framed_recv, framed_send, JOB_TIMEOUT_WALL_CLOCK_FACTOR};
 
 // Re-export some common types.
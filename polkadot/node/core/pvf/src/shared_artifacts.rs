@@ -0,0 +1,509 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A store of prepared artifacts shared between nodes.
+//!
+//! Artifacts only depend on the validation code, the preparation-related executor parameters and
+//! the node version, which determines the compiler used by the prepare worker. Operators running
+//! several validators on one host can thus let them seed their caches from a common store instead
+//! of each of them preparing all the PVFs again after every restart. The store is a directory laid
+//! out as:
+//!
+//! ```text
+//! <store>/<node version>-<arch>/<code hash>_<executor params prep hash>.pvf
+//! <store>/<node version>-<arch>/<code hash>_<executor params prep hash>.pvf.sig
+//! ```
+//!
+//! where the `.sig` file holds the hex-encoded ed25519 signature of the populating host over the
+//! location of the artifact in the store and its BLAKE3 checksum. Executing an artifact amounts to
+//! running native code, so an artifact is only used if it is signed by the key the node has been
+//! configured to trust, see [`SharedArtifactsConfig::trusted_key`]. Anyone able to write to the
+//! store but lacking that key can thus neither plant an artifact nor swap one for another. Seeded
+//! artifacts are copied into the node's own cache first, so nodes only need read access to the
+//! store.
+//!
+//! Nodes never write to the store. It is populated by a host started with
+//! [`SharedArtifactsConfig::signing_key`] set, e.g. by the `precompile-pvfs` subcommand.
+//!
+//! The store is accessed by a task sitting between the host and the prepare queue: artifacts the
+//! host sends to be prepared are looked up in the store first, and artifacts prepared by the queue
+//! are added to it before the host learns about them. The file IO and the checksums are done on
+//! the blocking thread pool, so they never hold up the host.
+//!
+//! Compiled artifacts may make use of the CPU features of the machine they were prepared on, so a
+//! store must only be shared between machines with the same CPU.
+
+use crate::{
+	artifacts::{generate_artifact_path, ArtifactId},
+	prepare, LOG_TARGET,
+};
+use futures::{channel::mpsc, stream::FuturesUnordered, Future, FutureExt, SinkExt, StreamExt};
+use polkadot_node_core_pvf_common::prepare::PrepareSuccess;
+use polkadot_node_primitives::NODE_VERSION;
+use sp_core::{ed25519, Pair};
+use std::{
+	fmt,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+/// The extension of the files holding the signatures of the artifacts.
+const SIGNATURE_EXTENSION: &str = "pvf.sig";
+
+/// Domain separator of the signed payloads.
+const SIGNING_CONTEXT: &[u8] = b"polkadot-pvf-shared-artifact";
+
+/// Configuration of the shared artifact store.
+#[derive(Clone)]
+pub struct SharedArtifactsConfig {
+	/// The directory of the store.
+	pub path: PathBuf,
+	/// The key the artifacts in the store must be signed with. Artifacts without a valid signature
+	/// by this key are ignored.
+	pub trusted_key: ed25519::Public,
+	/// The key to sign the artifacts prepared by the host with. If set, they are added to the
+	/// store.
+	pub signing_key: Option<ed25519::Pair>,
+}
+
+impl fmt::Debug for SharedArtifactsConfig {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("SharedArtifactsConfig")
+			.field("path", &self.path)
+			.field("trusted_key", &self.trusted_key)
+			.field("populate", &self.signing_key.is_some())
+			.finish()
+	}
+}
+
+/// Access to the shared artifact store.
+struct SharedArtifacts {
+	/// The directory holding the artifacts of the current node version.
+	dir: PathBuf,
+	/// The name of `dir`, which is part of the signed payloads.
+	version: String,
+	trusted_key: ed25519::Public,
+	signing_key: Option<ed25519::Pair>,
+}
+
+impl SharedArtifacts {
+	fn with_version(
+		SharedArtifactsConfig { path, trusted_key, signing_key }: SharedArtifactsConfig,
+		node_version: &str,
+	) -> Self {
+		let version = format!("{node_version}-{}", std::env::consts::ARCH);
+		Self { dir: path.join(&version), version, trusted_key, signing_key }
+	}
+
+	fn file_name(artifact_id: &ArtifactId) -> String {
+		format!("{:x}_{:x}.pvf", artifact_id.code_hash, artifact_id.executor_params_prep_hash)
+	}
+
+	fn artifact_path(&self, artifact_id: &ArtifactId) -> PathBuf {
+		self.dir.join(Self::file_name(artifact_id))
+	}
+
+	/// The payload signed for the artifact with the given ID and contents.
+	///
+	/// It binds the contents to the location of the artifact, so that a signed artifact can't be
+	/// passed off as another one.
+	fn signed_payload(&self, artifact_id: &ArtifactId, artifact: &[u8]) -> Vec<u8> {
+		let mut payload = SIGNING_CONTEXT.to_vec();
+		payload.extend_from_slice(self.version.as_bytes());
+		payload.push(b'/');
+		payload.extend_from_slice(Self::file_name(artifact_id).as_bytes());
+		payload.extend_from_slice(blake3::hash(artifact).as_bytes());
+		payload
+	}
+
+	/// Copies the artifact into `cache_path`, if the store holds a copy of it signed by the trusted
+	/// key.
+	///
+	/// Returns the path of the copy and its size.
+	fn seed(&self, artifact_id: &ArtifactId, cache_path: &Path) -> Option<(PathBuf, u64)> {
+		let shared_path = self.artifact_path(artifact_id);
+		let artifact = std::fs::read(&shared_path).ok()?;
+		let signature = std::fs::read_to_string(shared_path.with_extension(SIGNATURE_EXTENSION))
+			.ok()
+			.and_then(|signature| array_bytes::hex2bytes(signature.trim()).ok())
+			.and_then(|signature| ed25519::Signature::try_from(&signature[..]).ok());
+		let Some(signature) = signature else {
+			gum::warn!(
+				target: LOG_TARGET,
+				?artifact_id,
+				?shared_path,
+				"shared artifact has no readable signature, ignoring it",
+			);
+			return None
+		};
+		if !ed25519::Pair::verify(
+			&signature,
+			self.signed_payload(artifact_id, &artifact),
+			&self.trusted_key,
+		) {
+			gum::warn!(
+				target: LOG_TARGET,
+				?artifact_id,
+				?shared_path,
+				"shared artifact is not signed by the trusted key, ignoring it",
+			);
+			return None
+		}
+
+		let path = generate_artifact_path(cache_path);
+		if let Err(error) = std::fs::write(&path, &artifact) {
+			gum::warn!(
+				target: LOG_TARGET,
+				?artifact_id,
+				?path,
+				%error,
+				"failed to copy shared artifact into the cache",
+			);
+			let _ = std::fs::remove_file(&path);
+			return None
+		}
+
+		gum::debug!(target: LOG_TARGET, ?artifact_id, ?shared_path, "seeded artifact from the shared store");
+		Some((path, artifact.len() as u64))
+	}
+
+	/// Signs the artifact prepared at `path` and adds it to the store, unless the store is not to
+	/// be populated or already holds it.
+	///
+	/// Failures are only logged, the artifact is still usable from the node's own cache.
+	fn add(&self, artifact_id: &ArtifactId, path: &Path) {
+		let Some(signing_key) = &self.signing_key else { return };
+		let shared_path = self.artifact_path(artifact_id);
+		if shared_path.exists() {
+			return
+		}
+		if let Err(error) = self.write(signing_key, artifact_id, &shared_path, path) {
+			gum::warn!(
+				target: LOG_TARGET,
+				?artifact_id,
+				?shared_path,
+				%error,
+				"failed to add artifact to the shared store",
+			);
+		}
+	}
+
+	fn write(
+		&self,
+		signing_key: &ed25519::Pair,
+		artifact_id: &ArtifactId,
+		shared_path: &Path,
+		path: &Path,
+	) -> std::io::Result<()> {
+		std::fs::create_dir_all(&self.dir)?;
+		let artifact = std::fs::read(path)?;
+		let signature = signing_key.sign(&self.signed_payload(artifact_id, &artifact));
+		std::fs::write(
+			shared_path.with_extension(SIGNATURE_EXTENSION),
+			array_bytes::bytes2hex("", signature),
+		)?;
+		// other nodes may read the store concurrently, so the artifact is moved into place
+		// atomically once it has been fully written
+		let tmp_path = generate_artifact_path(&self.dir).with_extension("tmp");
+		std::fs::write(&tmp_path, &artifact)?;
+		std::fs::rename(&tmp_path, shared_path)
+	}
+}
+
+/// The outcome of a job run against the store on the blocking thread pool.
+enum Job {
+	/// The artifact has been looked up in the store, it is to be prepared if it wasn't found.
+	Seeded { artifact_id: ArtifactId, to_queue: prepare::ToQueue, seeded: Option<(PathBuf, u64)> },
+	/// The freshly prepared artifact has been added to the store.
+	Shared(prepare::FromQueue),
+}
+
+/// Starts the task giving the host access to the shared store.
+///
+/// It takes the channels of the prepare queue and returns the ones the host is to use instead.
+pub fn start(
+	config: SharedArtifactsConfig,
+	cache_path: PathBuf,
+	to_prepare_queue_tx: mpsc::Sender<prepare::ToQueue>,
+	from_prepare_queue_rx: mpsc::UnboundedReceiver<prepare::FromQueue>,
+) -> (
+	mpsc::Sender<prepare::ToQueue>,
+	mpsc::UnboundedReceiver<prepare::FromQueue>,
+	impl Future<Output = ()>,
+) {
+	let (to_queue_tx, to_queue_rx) = mpsc::channel(150);
+	let (from_queue_tx, from_queue_rx) = mpsc::unbounded();
+	let run = run(
+		Arc::new(SharedArtifacts::with_version(config, NODE_VERSION)),
+		cache_path,
+		to_queue_rx,
+		from_queue_tx,
+		to_prepare_queue_tx,
+		from_prepare_queue_rx,
+	);
+	(to_queue_tx, from_queue_rx, run)
+}
+
+async fn run(
+	store: Arc<SharedArtifacts>,
+	cache_path: PathBuf,
+	to_queue_rx: mpsc::Receiver<prepare::ToQueue>,
+	from_queue_tx: mpsc::UnboundedSender<prepare::FromQueue>,
+	mut to_prepare_queue_tx: mpsc::Sender<prepare::ToQueue>,
+	from_prepare_queue_rx: mpsc::UnboundedReceiver<prepare::FromQueue>,
+) {
+	let mut to_queue_rx = to_queue_rx.fuse();
+	let mut from_prepare_queue_rx = from_prepare_queue_rx.fuse();
+	let mut jobs = FuturesUnordered::new();
+
+	loop {
+		let from_queue = futures::select! {
+			to_queue = to_queue_rx.next() => {
+				// The host has shut down.
+				let Some(to_queue) = to_queue else { break };
+				let prepare::ToQueue::Enqueue { pvf, .. } = &to_queue;
+				let artifact_id = ArtifactId::from_pvf_prep_data(pvf);
+				let (store, cache_path) = (store.clone(), cache_path.clone());
+				jobs.push(
+					tokio::task::spawn_blocking(move || {
+						let seeded = store.seed(&artifact_id, &cache_path);
+						Job::Seeded { artifact_id, to_queue, seeded }
+					})
+					.boxed(),
+				);
+				continue
+			},
+			from_queue = from_prepare_queue_rx.next() => {
+				// The prepare queue has shut down.
+				let Some(from_queue) = from_queue else { break };
+				match &from_queue.result {
+					// Share the artifact before reporting it as prepared, so that it is in the
+					// store once the requesters learn about it.
+					Ok(PrepareSuccess { path, .. }) if store.signing_key.is_some() => {
+						let (store, artifact_id, path) =
+							(store.clone(), from_queue.artifact_id.clone(), path.clone());
+						jobs.push(
+							tokio::task::spawn_blocking(move || {
+								store.add(&artifact_id, &path);
+								Job::Shared(from_queue)
+							})
+							.boxed(),
+						);
+						continue
+					},
+					_ => from_queue,
+				}
+			},
+			job = jobs.select_next_some() => match job {
+				Ok(Job::Seeded { artifact_id, seeded: Some((path, size)), .. }) =>
+					prepare::FromQueue {
+						artifact_id,
+						result: Ok(PrepareSuccess { path, size, stats: Default::default() }),
+					},
+				Ok(Job::Seeded { seeded: None, to_queue, .. }) => {
+					// The artifact is not in the store, so it needs to be prepared.
+					if to_prepare_queue_tx.send(to_queue).await.is_err() {
+						break
+					}
+					continue
+				},
+				Ok(Job::Shared(from_queue)) => from_queue,
+				Err(error) => {
+					gum::error!(
+						target: LOG_TARGET,
+						%error,
+						"shared artifact store job failed, terminating",
+					);
+					break
+				},
+			},
+		};
+
+		if from_queue_tx.unbounded_send(from_queue).is_err() {
+			break
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{testing::artifact_id, Priority};
+	use polkadot_node_core_pvf_common::pvf::PvfPrepData;
+
+	fn signing_key(seed: u8) -> ed25519::Pair {
+		ed25519::Pair::from_seed(&[seed; 32])
+	}
+
+	fn store(path: &Path, trusted: u8, signing: Option<u8>) -> SharedArtifacts {
+		SharedArtifacts::with_version(
+			SharedArtifactsConfig {
+				path: path.to_owned(),
+				trusted_key: signing_key(trusted).public(),
+				signing_key: signing.map(signing_key),
+			},
+			NODE_VERSION,
+		)
+	}
+
+	fn prepared_artifact(cache_path: &Path, contents: &[u8]) -> PathBuf {
+		let path = generate_artifact_path(cache_path);
+		std::fs::write(&path, contents).unwrap();
+		path
+	}
+
+	#[test]
+	fn populated_artifacts_are_seeded() {
+		let store_dir = tempfile::tempdir().unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let path = prepared_artifact(cache_dir.path(), b"artifact");
+
+		// nodes do not populate the store
+		store(store_dir.path(), 1, None).add(&artifact_id(1), &path);
+		assert_eq!(store(store_dir.path(), 1, None).seed(&artifact_id(1), cache_dir.path()), None);
+
+		store(store_dir.path(), 1, Some(1)).add(&artifact_id(1), &path);
+		let (seeded, size) = store(store_dir.path(), 1, None)
+			.seed(&artifact_id(1), cache_dir.path())
+			.unwrap();
+		assert_ne!(seeded, path);
+		assert_eq!(size, 8);
+		assert_eq!(std::fs::read(seeded).unwrap(), b"artifact");
+		assert_eq!(store(store_dir.path(), 1, None).seed(&artifact_id(2), cache_dir.path()), None);
+	}
+
+	#[test]
+	fn artifacts_are_keyed_by_node_version() {
+		let store_dir = tempfile::tempdir().unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let path = prepared_artifact(cache_dir.path(), b"artifact");
+		let config = SharedArtifactsConfig {
+			path: store_dir.path().to_owned(),
+			trusted_key: signing_key(1).public(),
+			signing_key: Some(signing_key(1)),
+		};
+
+		SharedArtifacts::with_version(config.clone(), "0.0.1").add(&artifact_id(1), &path);
+		assert_eq!(store(store_dir.path(), 1, None).seed(&artifact_id(1), cache_dir.path()), None);
+		assert!(SharedArtifacts::with_version(config, "0.0.1")
+			.seed(&artifact_id(1), cache_dir.path())
+			.is_some());
+	}
+
+	#[test]
+	fn artifacts_not_signed_by_the_trusted_key_are_not_seeded() {
+		let store_dir = tempfile::tempdir().unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let path = prepared_artifact(cache_dir.path(), b"artifact");
+		let shared = store(store_dir.path(), 1, Some(1));
+		for discriminator in 1..=4 {
+			shared.add(&artifact_id(discriminator), &path);
+		}
+
+		// signed by another key
+		assert_eq!(store(store_dir.path(), 2, None).seed(&artifact_id(1), cache_dir.path()), None);
+
+		// tampered with
+		std::fs::write(shared.artifact_path(&artifact_id(1)), b"corrupted").unwrap();
+		assert_eq!(shared.seed(&artifact_id(1), cache_dir.path()), None);
+
+		// carrying the signature of another artifact
+		std::fs::copy(
+			shared.artifact_path(&artifact_id(2)).with_extension(SIGNATURE_EXTENSION),
+			shared.artifact_path(&artifact_id(3)).with_extension(SIGNATURE_EXTENSION),
+		)
+		.unwrap();
+		assert_eq!(shared.seed(&artifact_id(3), cache_dir.path()), None);
+		assert!(shared.seed(&artifact_id(2), cache_dir.path()).is_some());
+
+		// signature missing
+		std::fs::remove_file(
+			shared.artifact_path(&artifact_id(4)).with_extension(SIGNATURE_EXTENSION),
+		)
+		.unwrap();
+		assert_eq!(shared.seed(&artifact_id(4), cache_dir.path()), None);
+	}
+
+	#[tokio::test]
+	async fn prepare_requests_are_served_from_the_store() {
+		let store_dir = tempfile::tempdir().unwrap();
+		let cache_dir = tempfile::tempdir().unwrap();
+		let path = prepared_artifact(cache_dir.path(), b"artifact");
+		let config = |signing| SharedArtifactsConfig {
+			path: store_dir.path().to_owned(),
+			trusted_key: signing_key(1).public(),
+			signing_key: signing,
+		};
+		let enqueue = |discriminator| prepare::ToQueue::Enqueue {
+			priority: Priority::Normal,
+			pvf: PvfPrepData::from_discriminator(discriminator),
+		};
+
+		// A host populating the store prepares the artifact and adds it to the store before
+		// reporting it as prepared.
+		let (to_prepare_queue_tx, mut to_prepare_queue_rx) = mpsc::channel(10);
+		let (from_prepare_queue_tx, from_prepare_queue_rx) = mpsc::unbounded();
+		let (mut to_queue_tx, mut from_queue_rx, run) = start(
+			config(Some(signing_key(1))),
+			cache_dir.path().to_owned(),
+			to_prepare_queue_tx,
+			from_prepare_queue_rx,
+		);
+		let handle = tokio::spawn(run);
+		to_queue_tx.send(enqueue(1)).await.unwrap();
+		assert!(matches!(to_prepare_queue_rx.next().await, Some(prepare::ToQueue::Enqueue { .. })));
+		from_prepare_queue_tx
+			.unbounded_send(prepare::FromQueue {
+				artifact_id: artifact_id(1),
+				result: Ok(PrepareSuccess { path: path.clone(), size: 8, ..Default::default() }),
+			})
+			.unwrap();
+		let from_queue = from_queue_rx.next().await.unwrap();
+		assert_eq!(from_queue.artifact_id, artifact_id(1));
+		assert!(store(store_dir.path(), 1, None).artifact_path(&artifact_id(1)).exists());
+		drop(to_queue_tx);
+		handle.await.unwrap();
+
+		// Another host seeds it from the store, without preparing it, and still prepares the
+		// artifacts missing from the store.
+		let (to_prepare_queue_tx, mut to_prepare_queue_rx) = mpsc::channel(10);
+		let (_from_prepare_queue_tx, from_prepare_queue_rx) = mpsc::unbounded();
+		let (mut to_queue_tx, mut from_queue_rx, run) = start(
+			config(None),
+			cache_dir.path().to_owned(),
+			to_prepare_queue_tx,
+			from_prepare_queue_rx,
+		);
+		let handle = tokio::spawn(run);
+		to_queue_tx.send(enqueue(1)).await.unwrap();
+		let prepare::FromQueue { artifact_id: seeded_id, result } =
+			from_queue_rx.next().await.unwrap();
+		assert_eq!(seeded_id, artifact_id(1));
+		let PrepareSuccess { path: seeded, size, .. } = result.unwrap();
+		assert_ne!(seeded, path);
+		assert_eq!(size, 8);
+		assert_eq!(std::fs::read(seeded).unwrap(), b"artifact");
+
+		to_queue_tx.send(enqueue(2)).await.unwrap();
+		assert!(matches!(
+			to_prepare_queue_rx.next().await,
+			Some(prepare::ToQueue::Enqueue { pvf, .. })
+				if ArtifactId::from_pvf_prep_data(&pvf) == artifact_id(2)
+		));
+		drop(to_queue_tx);
+		handle.await.unwrap();
+	}
+}
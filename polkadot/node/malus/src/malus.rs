@@ -42,6 +42,10 @@ enum NemesisVariant {
 	DisputeFinalizedCandidates(DisputeFinalizedCandidatesOptions),
 	/// Spam many request statements instead of sending a single one.
 	SpamStatementRequests(SpamStatementRequestsOptions),
+	/// Withhold, misdirect or delay our own approvals and assignments.
+	MisbehaveInApprovals(MisbehaveInApprovalsOptions),
+	/// Withhold or corrupt the availability chunks we serve.
+	MisbehaveInAvailability(MisbehaveInAvailabilityOptions),
 }
 
 #[derive(Debug, Parser)]
@@ -105,6 +109,29 @@ impl MalusCli {
 
 				polkadot_cli::run_node(cli, SpamStatementRequests { spam_factor }, finality_delay)?
 			},
+			NemesisVariant::MisbehaveInApprovals(opts) => {
+				let MisbehaveInApprovalsOptions { behaviour, percentage, assignment_delay_ms, cli } =
+					opts;
+
+				polkadot_cli::run_node(
+					cli,
+					MisbehaveInApprovals {
+						behaviour,
+						percentage,
+						assignment_delay: std::time::Duration::from_millis(assignment_delay_ms),
+					},
+					finality_delay,
+				)?
+			},
+			NemesisVariant::MisbehaveInAvailability(opts) => {
+				let MisbehaveInAvailabilityOptions { behaviour, percentage, cli } = opts;
+
+				polkadot_cli::run_node(
+					cli,
+					MisbehaveInAvailability { behaviour, percentage },
+					finality_delay,
+				)?
+			},
 		}
 		Ok(())
 	}
@@ -244,4 +271,49 @@ mod tests {
 			assert!(opts.cli.run.base.bob);
 		});
 	}
+
+	#[test]
+	fn misbehave_in_approvals_behaviour_works() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"misbehave-in-approvals",
+			"--behaviour",
+			"delay-assignments",
+			"--assignment-delay-ms",
+			"6000",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::MisbehaveInApprovals(opts),
+			..
+		} => {
+			assert_eq!(opts.behaviour, ApprovalMisbehaviour::DelayAssignments);
+			assert_eq!(opts.assignment_delay_ms, 6000);
+			assert_eq!(opts.percentage, 100);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
+
+	#[test]
+	fn misbehave_in_availability_behaviour_works() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"misbehave-in-availability",
+			"--behaviour",
+			"corrupt-chunks",
+			"--percentage",
+			"50",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::MisbehaveInAvailability(opts),
+			..
+		} => {
+			assert_eq!(opts.behaviour, AvailabilityMisbehaviour::CorruptChunks);
+			assert_eq!(opts.percentage, 50);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
 }
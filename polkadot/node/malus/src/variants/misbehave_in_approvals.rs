@@ -0,0 +1,318 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that misbehaves in approval voting.
+//!
+//! This malus variant behaves honestly in backing and checks candidates as usual. The
+//! maliciousness comes from what happens to its own assignments and approvals on their way from
+//! approval voting to approval distribution, depending on the chosen behaviour:
+//! - `withhold-approvals`: assignments are sent, but approvals never are, so the node ends up as a
+//!   no-show for the candidates it was assigned to.
+//! - `approve-wrong-candidate`: approvals claim the candidate following the one actually approved,
+//!   so they fail the signature check of the receiving validators.
+//! - `delay-assignments`: assignments are only sent after a delay, so the node can be counted as a
+//!   no-show before anyone knows it was assigned.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_primitives::approval::v2::{
+	CandidateBitfield, IndirectAssignmentCertV2, IndirectSignedApprovalVoteV2,
+};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use polkadot_primitives::{CandidateIndex, Hash};
+use rand::distributions::{Bernoulli, Distribution};
+use sp_core::traits::SpawnNamed;
+
+// Filter wrapping related types.
+use crate::{interceptor::*, shared::MALUS};
+
+use std::{
+	collections::HashSet,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+#[value(rename_all = "kebab-case")]
+pub enum ApprovalMisbehaviour {
+	/// Never send our approvals.
+	WithholdApprovals,
+	/// Send our approvals for the wrong candidates.
+	ApproveWrongCandidate,
+	/// Send our assignments late.
+	DelayAssignments,
+}
+
+/// Sends a message to approval distribution as if it came from approval voting.
+type ApprovalDistributionSender = Arc<dyn Fn(ApprovalDistributionMessage) + Send + Sync>;
+
+/// Wraps around `ApprovalVotingSubsystem` only to get hold of a sender to approval distribution,
+/// which is needed to send our assignments once they were delayed.
+#[derive(Clone)]
+struct SenderCapture {
+	sender: Arc<Mutex<Option<ApprovalDistributionSender>>>,
+}
+
+impl<Sender> MessageInterceptor<Sender> for SenderCapture
+where
+	Sender: overseer::ApprovalVotingSenderTrait + Clone + Send + 'static,
+{
+	type Message = ApprovalVotingMessage;
+
+	fn intercept_incoming(
+		&self,
+		subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		let mut captured = self.sender.lock().expect("Sender capture lock is never poisoned");
+		if captured.is_none() {
+			let sender = Mutex::new(subsystem_sender.clone());
+			*captured = Some(Arc::new(move |msg| {
+				sender
+					.lock()
+					.expect("Approval voting sender lock is never poisoned")
+					.send_unbounded_message(msg)
+			}));
+		}
+
+		Some(msg)
+	}
+}
+
+/// Wraps around `ApprovalDistributionSubsystem` and replaces it.
+/// Tampers with our own assignments and approvals before they get distributed.
+#[derive(Clone)]
+struct ApprovalMisbehaver<Spawner> {
+	spawner: Spawner,
+	behaviour: ApprovalMisbehaviour,
+	distribution: Bernoulli,
+	assignment_delay: Duration,
+	sender: Arc<Mutex<Option<ApprovalDistributionSender>>>,
+	/// Assignments which were held back, to let them through once they are sent again.
+	delayed: Arc<Mutex<HashSet<(Hash, Vec<usize>)>>>,
+}
+
+impl<Spawner> ApprovalMisbehaver<Spawner>
+where
+	Spawner: overseer::gen::Spawner + Clone + 'static,
+{
+	fn intercept_approval(
+		&self,
+		mut vote: IndirectSignedApprovalVoteV2,
+	) -> Option<IndirectSignedApprovalVoteV2> {
+		if !self.distribution.sample(&mut rand::thread_rng()) {
+			return Some(vote)
+		}
+
+		match self.behaviour {
+			ApprovalMisbehaviour::WithholdApprovals => {
+				gum::info!(
+					target: MALUS,
+					block_hash = ?vote.block_hash,
+					candidates = ?vote.candidate_indices,
+					"😈 Withholding approval.",
+				);
+				None
+			},
+			ApprovalMisbehaviour::ApproveWrongCandidate => {
+				let wrong_candidates: Vec<CandidateIndex> = vote
+					.candidate_indices
+					.iter_ones()
+					.map(|index| index as CandidateIndex + 1)
+					.collect();
+				if let Ok(wrong_candidates) = CandidateBitfield::try_from(wrong_candidates) {
+					gum::info!(
+						target: MALUS,
+						block_hash = ?vote.block_hash,
+						candidates = ?vote.candidate_indices,
+						?wrong_candidates,
+						"😈 Sending approval for the wrong candidates.",
+					);
+					vote.candidate_indices = wrong_candidates;
+				}
+				Some(vote)
+			},
+			ApprovalMisbehaviour::DelayAssignments => Some(vote),
+		}
+	}
+
+	fn intercept_assignment(
+		&self,
+		cert: IndirectAssignmentCertV2,
+		claimed_candidates: CandidateBitfield,
+	) -> Option<(IndirectAssignmentCertV2, CandidateBitfield)> {
+		if self.behaviour != ApprovalMisbehaviour::DelayAssignments {
+			return Some((cert, claimed_candidates))
+		}
+
+		let key = (cert.block_hash, claimed_candidates.iter_ones().collect::<Vec<_>>());
+		let mut delayed = self.delayed.lock().expect("Delayed assignments lock is never poisoned");
+		// This is the assignment we held back coming around again.
+		if delayed.remove(&key) {
+			return Some((cert, claimed_candidates))
+		}
+
+		if !self.distribution.sample(&mut rand::thread_rng()) {
+			return Some((cert, claimed_candidates))
+		}
+
+		let sender =
+			match self.sender.lock().expect("Sender capture lock is never poisoned").clone() {
+				Some(sender) => sender,
+				None => return Some((cert, claimed_candidates)),
+			};
+
+		gum::info!(
+			target: MALUS,
+			block_hash = ?cert.block_hash,
+			candidates = ?claimed_candidates,
+			delay = ?self.assignment_delay,
+			"😈 Delaying assignment.",
+		);
+		delayed.insert(key);
+
+		let assignment_delay = self.assignment_delay;
+		self.spawner.spawn(
+			"malus-delay-assignment",
+			Some("malus"),
+			Box::pin(async move {
+				futures_timer::Delay::new(assignment_delay).await;
+				sender(ApprovalDistributionMessage::DistributeAssignment(cert, claimed_candidates));
+			}),
+		);
+
+		None
+	}
+}
+
+impl<Sender, Spawner> MessageInterceptor<Sender> for ApprovalMisbehaver<Spawner>
+where
+	Sender: overseer::ApprovalDistributionSenderTrait + Clone + Send + 'static,
+	Spawner: overseer::gen::Spawner + Clone + 'static,
+{
+	type Message = ApprovalDistributionMessage;
+
+	/// Intercept our own assignments and approvals and pass the rest as normal.
+	fn intercept_incoming(
+		&self,
+		_subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg: ApprovalDistributionMessage::DistributeApproval(vote),
+			} => self.intercept_approval(vote).map(|vote| FromOrchestra::Communication {
+				msg: ApprovalDistributionMessage::DistributeApproval(vote),
+			}),
+			FromOrchestra::Communication {
+				msg: ApprovalDistributionMessage::DistributeAssignment(cert, claimed_candidates),
+			} => self.intercept_assignment(cert, claimed_candidates).map(
+				|(cert, claimed_candidates)| FromOrchestra::Communication {
+					msg: ApprovalDistributionMessage::DistributeAssignment(
+						cert,
+						claimed_candidates,
+					),
+				},
+			),
+			msg => Some(msg),
+		}
+	}
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct MisbehaveInApprovalsOptions {
+	/// How to misbehave with our own assignments and approvals.
+	#[arg(long, value_enum, ignore_case = true, default_value_t = ApprovalMisbehaviour::WithholdApprovals)]
+	pub behaviour: ApprovalMisbehaviour,
+
+	/// Determines the percentage of our assignments or approvals to misbehave with, depending on
+	/// the behaviour. Value must be in the range [0..=100].
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	/// How long to hold back our assignments with `--behaviour delay-assignments`, in
+	/// milliseconds.
+	#[clap(long, ignore_case = true, default_value_t = 24_000)]
+	pub assignment_delay_ms: u64,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// MisbehaveInApprovals implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct MisbehaveInApprovals {
+	/// How to misbehave with our own assignments and approvals.
+	pub behaviour: ApprovalMisbehaviour,
+	/// The probability of behaving maliciously.
+	pub percentage: u8,
+	/// How long to hold back our assignments.
+	pub assignment_delay: Duration,
+}
+
+impl OverseerGen for MisbehaveInApprovals {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node that misbehaves in approvals: {:?} ({}% of the time).",
+			&self.behaviour,
+			&self.percentage,
+		);
+
+		let sender = Arc::new(Mutex::new(None));
+		let sender_capture = SenderCapture { sender: sender.clone() };
+		let approval_misbehaver = ApprovalMisbehaver {
+			spawner: SpawnGlue(args.spawner.clone()),
+			behaviour: self.behaviour,
+			distribution: Bernoulli::new(f64::from(self.percentage) / 100.0)
+				.expect("Invalid probability! Percentage must be in range [0..=100]."),
+			assignment_delay: self.assignment_delay,
+			sender,
+			delayed: Arc::new(Mutex::new(HashSet::new())),
+		};
+
+		validator_overseer_builder(
+			args,
+			ext_args.expect("Extended arguments required to build validator overseer are provided"),
+		)?
+		.replace_approval_voting(move |cb| InterceptedSubsystem::new(cb, sender_capture))
+		.replace_approval_distribution(move |cb| InterceptedSubsystem::new(cb, approval_misbehaver))
+		.build_with_connector(connector)
+		.map_err(|e| e.into())
+	}
+}
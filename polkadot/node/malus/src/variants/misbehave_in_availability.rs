@@ -0,0 +1,200 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that misbehaves when serving availability chunks.
+//!
+//! This malus variant behaves honestly in everything except when answering chunk requests of
+//! other validators, which are served from the availability store. Depending on the chosen
+//! behaviour, it either:
+//! - `withhold-chunks`: claims not to have the requested chunk, or
+//! - `corrupt-chunks`: serves the requested chunk with its data flipped, so it doesn't match the
+//!   erasure root anymore and fails chunk validation of the requester.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use futures::channel::oneshot;
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use rand::distributions::{Bernoulli, Distribution};
+use sp_core::traits::SpawnNamed;
+
+// Filter wrapping related types.
+use crate::{interceptor::*, shared::MALUS};
+
+use std::sync::Arc;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+#[value(rename_all = "kebab-case")]
+pub enum AvailabilityMisbehaviour {
+	/// Pretend not to have the requested chunks.
+	WithholdChunks,
+	/// Serve chunks with corrupted data.
+	CorruptChunks,
+}
+
+/// Wraps around `AvailabilityStoreSubsystem` and replaces it.
+/// Tampers with the chunks queried to answer chunk requests.
+#[derive(Clone)]
+struct ChunkMisbehaver<Spawner> {
+	spawner: Spawner,
+	behaviour: AvailabilityMisbehaviour,
+	distribution: Bernoulli,
+}
+
+impl<Sender, Spawner> MessageInterceptor<Sender> for ChunkMisbehaver<Spawner>
+where
+	Sender: overseer::AvailabilityStoreSenderTrait + Clone + Send + 'static,
+	Spawner: overseer::gen::Spawner + Clone + 'static,
+{
+	type Message = AvailabilityStoreMessage;
+
+	/// Intercept `AvailabilityStoreMessage::QueryChunk`, which is only used to answer chunk
+	/// requests, and pass the rest as normal.
+	fn intercept_incoming(
+		&self,
+		_subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryChunk(candidate_hash, validator_index, tx),
+			} => {
+				if !self.distribution.sample(&mut rand::thread_rng()) {
+					return Some(FromOrchestra::Communication {
+						msg: AvailabilityStoreMessage::QueryChunk(
+							candidate_hash,
+							validator_index,
+							tx,
+						),
+					})
+				}
+
+				match self.behaviour {
+					AvailabilityMisbehaviour::WithholdChunks => {
+						gum::info!(
+							target: MALUS,
+							?candidate_hash,
+							?validator_index,
+							"😈 Withholding chunk.",
+						);
+						let _ = tx.send(None);
+						None
+					},
+					AvailabilityMisbehaviour::CorruptChunks => {
+						let (chunk_tx, chunk_rx) = oneshot::channel();
+						self.spawner.spawn(
+							"malus-corrupt-chunk",
+							Some("malus"),
+							Box::pin(async move {
+								let chunk = chunk_rx.await.ok().flatten().map(|mut chunk| {
+									gum::info!(
+										target: MALUS,
+										?candidate_hash,
+										?validator_index,
+										"😈 Corrupting chunk.",
+									);
+									chunk.chunk.iter_mut().for_each(|byte| *byte = !*byte);
+									chunk
+								});
+								let _ = tx.send(chunk);
+							}),
+						);
+
+						Some(FromOrchestra::Communication {
+							msg: AvailabilityStoreMessage::QueryChunk(
+								candidate_hash,
+								validator_index,
+								chunk_tx,
+							),
+						})
+					},
+				}
+			},
+			msg => Some(msg),
+		}
+	}
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct MisbehaveInAvailabilityOptions {
+	/// How to misbehave when serving chunks.
+	#[arg(long, value_enum, ignore_case = true, default_value_t = AvailabilityMisbehaviour::WithholdChunks)]
+	pub behaviour: AvailabilityMisbehaviour,
+
+	/// Determines the percentage of chunk requests to misbehave with. Value must be in the range
+	/// [0..=100].
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// MisbehaveInAvailability implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct MisbehaveInAvailability {
+	/// How to misbehave when serving chunks.
+	pub behaviour: AvailabilityMisbehaviour,
+	/// The probability of behaving maliciously.
+	pub percentage: u8,
+}
+
+impl OverseerGen for MisbehaveInAvailability {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node that misbehaves when serving chunks: {:?} ({}% of the time).",
+			&self.behaviour,
+			&self.percentage,
+		);
+
+		let chunk_misbehaver = ChunkMisbehaver {
+			spawner: SpawnGlue(args.spawner.clone()),
+			behaviour: self.behaviour,
+			distribution: Bernoulli::new(f64::from(self.percentage) / 100.0)
+				.expect("Invalid probability! Percentage must be in range [0..=100]."),
+		};
+
+		validator_overseer_builder(
+			args,
+			ext_args.expect("Extended arguments required to build validator overseer are provided"),
+		)?
+		.replace_availability_store(move |cb| InterceptedSubsystem::new(cb, chunk_misbehaver))
+		.build_with_connector(connector)
+		.map_err(|e| e.into())
+	}
+}
@@ -20,6 +20,8 @@ mod back_garbage_candidate;
 mod common;
 mod dispute_finalized_candidates;
 mod dispute_valid_candidates;
+mod misbehave_in_approvals;
+mod misbehave_in_availability;
 mod spam_statement_requests;
 mod suggest_garbage_candidate;
 mod support_disabled;
@@ -28,6 +30,12 @@ pub(crate) use self::{
 	back_garbage_candidate::{BackGarbageCandidateOptions, BackGarbageCandidates},
 	dispute_finalized_candidates::{DisputeFinalizedCandidates, DisputeFinalizedCandidatesOptions},
 	dispute_valid_candidates::{DisputeAncestorOptions, DisputeValidCandidates},
+	misbehave_in_approvals::{
+		ApprovalMisbehaviour, MisbehaveInApprovals, MisbehaveInApprovalsOptions,
+	},
+	misbehave_in_availability::{
+		AvailabilityMisbehaviour, MisbehaveInAvailability, MisbehaveInAvailabilityOptions,
+	},
 	spam_statement_requests::{SpamStatementRequests, SpamStatementRequestsOptions},
 	suggest_garbage_candidate::{SuggestGarbageCandidateOptions, SuggestGarbageCandidates},
 	support_disabled::{SupportDisabled, SupportDisabledOptions},
@@ -24,10 +24,20 @@
 //!    5. If it turns to be invalid or async backing allows seconding another candidate, carry on
 //!       with the next advertisement, otherwise we're done with this relay parent.
 //!
+//! Advertisements waiting to be fetched are queued per para. The next one to fetch is taken from
+//! the para which got the smallest share of its claims in the claim queue seconded so far, so
+//! that a para advertising a lot of collations can't starve the others sharing the core.
+//!
 //!    ┌──────────────────────────────────────────┐
 //!    └─▶Advertised ─▶ Pending ─▶ Fetched ─▶ Validated
 
-use std::{collections::VecDeque, future::Future, pin::Pin, task::Poll};
+use std::{
+	cmp::Reverse,
+	collections::{BTreeMap, VecDeque},
+	future::Future,
+	pin::Pin,
+	task::Poll,
+};
 
 use futures::{future::BoxFuture, FutureExt};
 use polkadot_node_network_protocol::{
@@ -217,7 +227,6 @@ impl CollationStatus {
 }
 
 /// Information about collations per relay parent.
-#[derive(Default)]
 pub struct Collations {
 	/// What is the current status in regards to a collation for this relay parent?
 	pub status: CollationStatus,
@@ -226,16 +235,47 @@ pub struct Collations {
 	/// This is the currently last started fetch, which did not exceed `MAX_UNSHARED_DOWNLOAD_TIME`
 	/// yet.
 	pub fetching_from: Option<(CollatorId, Option<CandidateHash>)>,
-	/// Collation that were advertised to us, but we did not yet fetch.
-	pub waiting_queue: VecDeque<(PendingCollation, CollatorId)>,
+	/// Collations that were advertised to us, but we did not yet fetch, per para.
+	pub waiting_queue: BTreeMap<ParaId, VecDeque<(PendingCollation, CollatorId)>>,
 	/// How many collations have been seconded.
 	pub seconded_count: usize,
+	/// How many collations have been seconded per para.
+	seconded_per_para: BTreeMap<ParaId, usize>,
+	/// How many claims each para has in the claim queue of our core.
+	claims_per_para: BTreeMap<ParaId, usize>,
 }
 
 impl Collations {
+	/// Create a new instance for a relay parent, given the paras in the claim queue of the core
+	/// assigned to us. A para may be present multiple times.
+	pub(super) fn new(assignments: &[ParaId]) -> Self {
+		let mut claims_per_para = BTreeMap::new();
+		for para_id in assignments {
+			*claims_per_para.entry(*para_id).or_default() += 1;
+		}
+
+		Self {
+			status: CollationStatus::default(),
+			fetching_from: None,
+			waiting_queue: BTreeMap::new(),
+			seconded_count: 0,
+			seconded_per_para: BTreeMap::new(),
+			claims_per_para,
+		}
+	}
+
 	/// Note a seconded collation for a given para.
-	pub(super) fn note_seconded(&mut self) {
-		self.seconded_count += 1
+	pub(super) fn note_seconded(&mut self, para_id: ParaId) {
+		self.seconded_count += 1;
+		*self.seconded_per_para.entry(para_id).or_default() += 1;
+	}
+
+	/// Queue an advertised collation to be fetched later.
+	pub(super) fn queue_collation(&mut self, pending_collation: PendingCollation, id: CollatorId) {
+		self.waiting_queue
+			.entry(pending_collation.para_id)
+			.or_default()
+			.push_back((pending_collation, id));
 	}
 
 	/// Returns the next collation to fetch from the `waiting_queue`.
@@ -244,10 +284,15 @@ impl Collations {
 	///
 	/// Returns `Some(_)` if there is any collation to fetch, the `status` is not `Seconded` and
 	/// the passed in `finished_one` is the currently `waiting_collation`.
+	///
+	/// The para is picked with [`Self::next_para_to_fetch`]. Among the collations queued for it,
+	/// the one advertised by the peer with the highest `peer_score` is returned, the oldest
+	/// advertisement winning ties.
 	pub(super) fn get_next_collation_to_fetch(
 		&mut self,
 		finished_one: &(CollatorId, Option<CandidateHash>),
 		relay_parent_mode: ProspectiveParachainsMode,
+		peer_score: impl Fn(&PeerId) -> i32,
 	) -> Option<(PendingCollation, CollatorId)> {
 		// If finished one does not match waiting_collation, then we already dequeued another fetch
 		// to replace it.
@@ -270,31 +315,93 @@ impl Collations {
 		match self.status {
 			// We don't need to fetch any other collation when we already have seconded one.
 			CollationStatus::Seconded => None,
-			CollationStatus::Waiting =>
-				if self.is_seconded_limit_reached(relay_parent_mode) {
-					None
-				} else {
-					self.waiting_queue.pop_front()
-				},
+			CollationStatus::Waiting => {
+				let para_id = self.next_para_to_fetch(relay_parent_mode)?;
+				let queue = self.waiting_queue.get_mut(&para_id)?;
+				let (index, _) =
+					queue.iter().enumerate().max_by_key(|(index, (pending_collation, _))| {
+						(peer_score(&pending_collation.peer_id), Reverse(*index))
+					})?;
+				let next = queue.remove(index);
+				if queue.is_empty() {
+					self.waiting_queue.remove(&para_id);
+				}
+				next
+			},
 			CollationStatus::WaitingOnValidation | CollationStatus::Fetching =>
 				unreachable!("We have reset the status above!"),
 		}
 	}
 
-	/// Checks the limit of seconded candidates.
+	/// Returns the para to fetch the next collation for.
+	///
+	/// Only paras with queued advertisements which didn't reach their seconded limit are
+	/// considered. The one with the lowest ratio of seconded collations to claims wins, then the
+	/// one with more claims and finally the lowest para id.
+	pub(super) fn next_para_to_fetch(
+		&self,
+		relay_parent_mode: ProspectiveParachainsMode,
+	) -> Option<ParaId> {
+		if self.seconded_count >= Self::seconded_limit(relay_parent_mode) {
+			return None
+		}
+
+		self.waiting_queue
+			.iter()
+			.filter(|(para_id, queue)| {
+				!queue.is_empty() && !self.is_para_limit_reached(relay_parent_mode, **para_id)
+			})
+			.map(|(para_id, _)| *para_id)
+			.min_by(|a, b| {
+				let (seconded_a, claims_a) = (self.seconded_for(a), self.claims_for(a).max(1));
+				let (seconded_b, claims_b) = (self.seconded_for(b), self.claims_for(b).max(1));
+				(seconded_a * claims_b)
+					.cmp(&(seconded_b * claims_a))
+					.then_with(|| claims_b.cmp(&claims_a))
+			})
+	}
+
+	/// Checks the limit of seconded candidates, both in total and for the given para.
 	pub(super) fn is_seconded_limit_reached(
 		&self,
 		relay_parent_mode: ProspectiveParachainsMode,
+		para_id: ParaId,
 	) -> bool {
-		let seconded_limit =
-			if let ProspectiveParachainsMode::Enabled { max_candidate_depth, .. } =
-				relay_parent_mode
-			{
-				max_candidate_depth + 1
-			} else {
-				1
-			};
-		self.seconded_count >= seconded_limit
+		self.seconded_count >= Self::seconded_limit(relay_parent_mode) ||
+			self.is_para_limit_reached(relay_parent_mode, para_id)
+	}
+
+	/// Checks the share of the seconded limit the para is entitled to, proportionally to its
+	/// claims and rounded up.
+	fn is_para_limit_reached(
+		&self,
+		relay_parent_mode: ProspectiveParachainsMode,
+		para_id: ParaId,
+	) -> bool {
+		let total_claims: usize = self.claims_per_para.values().sum();
+		if total_claims == 0 {
+			return true
+		}
+
+		let limit = Self::seconded_limit(relay_parent_mode);
+		let para_limit = (limit * self.claims_for(&para_id)).div_ceil(total_claims);
+		self.seconded_for(&para_id) >= para_limit
+	}
+
+	fn seconded_limit(relay_parent_mode: ProspectiveParachainsMode) -> usize {
+		if let ProspectiveParachainsMode::Enabled { max_candidate_depth, .. } = relay_parent_mode {
+			max_candidate_depth + 1
+		} else {
+			1
+		}
+	}
+
+	fn seconded_for(&self, para_id: &ParaId) -> usize {
+		self.seconded_per_para.get(para_id).copied().unwrap_or(0)
+	}
+
+	fn claims_for(&self, para_id: &ParaId) -> usize {
+		self.claims_per_para.get(para_id).copied().unwrap_or(0)
 	}
 }
 
@@ -322,6 +429,8 @@ pub(super) struct CollationFetchRequest {
 	pub cancellation_token: CancellationToken,
 	/// A metric histogram for the lifetime of the request
 	pub _lifetime_timer: Option<HistogramTimer>,
+	/// A metric histogram for the fetch latency of the para
+	pub _para_latency_timer: Option<HistogramTimer>,
 }
 
 impl Future for CollationFetchRequest {
@@ -15,6 +15,7 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use polkadot_node_subsystem_util::metrics::{self, prometheus};
+use polkadot_primitives::Id as ParaId;
 
 #[derive(Clone, Default)]
 pub struct Metrics(Option<MetricsInner>);
@@ -57,6 +58,19 @@ impl Metrics {
 		self.0.as_ref().map(|metrics| metrics.collation_request_duration.start_timer())
 	}
 
+	/// Provide a timer for the fetch of a collation of the given para which observes on drop.
+	pub fn time_collation_fetch_latency(
+		&self,
+		para_id: ParaId,
+	) -> Option<metrics::prometheus::prometheus::HistogramTimer> {
+		self.0.as_ref().map(|metrics| {
+			metrics
+				.collation_fetch_latency
+				.with_label_values(&[&para_id.to_string()])
+				.start_timer()
+		})
+	}
+
 	/// Provide a timer for `request_unblocked_collations` which observes on drop.
 	pub fn time_request_unblocked_collations(
 		&self,
@@ -74,6 +88,7 @@ struct MetricsInner {
 	handle_collation_request_result: prometheus::Histogram,
 	collator_peer_count: prometheus::Gauge<prometheus::U64>,
 	collation_request_duration: prometheus::Histogram,
+	collation_fetch_latency: prometheus::HistogramVec,
 	request_unblocked_collations: prometheus::Histogram,
 }
 
@@ -126,6 +141,16 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			collation_fetch_latency: prometheus::register(
+				prometheus::HistogramVec::new(
+					prometheus::HistogramOpts::new(
+						"polkadot_parachain_collator_protocol_validator_collation_fetch_latency",
+						"Time it took to fetch a collation from a collator, per para",
+					).buckets(vec![0.05, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.75, 0.9, 1.0, 1.2, 1.5, 1.75]),
+					&["para_id"],
+				)?,
+				registry,
+			)?,
 			request_unblocked_collations: prometheus::register(
 				prometheus::Histogram::with_opts(
 					prometheus::HistogramOpts::new(
@@ -155,6 +155,10 @@ struct PeerData {
 	view: View,
 	state: PeerState,
 	version: CollationVersion,
+	/// Local score of the peer, built from the outcomes of the collations fetched from it.
+	///
+	/// Used to prefer well-behaving collators when picking the next collation of a para to fetch.
+	score: i32,
 }
 
 impl PeerData {
@@ -524,10 +528,12 @@ where
 		}
 	}
 
+	let assignment: Vec<ParaId> = paras_now.into_iter().collect();
+
 	Ok(Some(PerRelayParent {
 		prospective_parachains_mode: relay_parent_mode,
-		assignment: GroupAssignments { current: paras_now.into_iter().collect() },
-		collations: Collations::default(),
+		assignment: GroupAssignments { current: assignment.clone() },
+		collations: Collations::new(&assignment),
 		v2_receipts,
 		session_index,
 		current_core: core_now,
@@ -600,14 +606,22 @@ async fn fetch_collation(
 	}
 }
 
+/// Apply the reputation change caused by a collation fetched from the peer to its local score.
+fn note_peer_score(peer_data: &mut HashMap<PeerId, PeerData>, peer_id: &PeerId, rep: &Rep) {
+	if let Some(peer_data) = peer_data.get_mut(peer_id) {
+		peer_data.score = peer_data.score.saturating_add(rep.cost_or_benefit());
+	}
+}
+
 /// Report a collator for some malicious actions.
 async fn report_collator(
 	reputation: &mut ReputationAggregator,
 	sender: &mut impl overseer::CollatorProtocolSenderTrait,
-	peer_data: &HashMap<PeerId, PeerData>,
+	peer_data: &mut HashMap<PeerId, PeerData>,
 	id: CollatorId,
 ) {
 	if let Some(peer_id) = collator_peer_id(peer_data, &id) {
+		note_peer_score(peer_data, &peer_id, &COST_REPORT_BAD);
 		modify_reputation(reputation, sender, peer_id, COST_REPORT_BAD).await;
 	}
 }
@@ -616,10 +630,11 @@ async fn report_collator(
 async fn note_good_collation(
 	reputation: &mut ReputationAggregator,
 	sender: &mut impl overseer::CollatorProtocolSenderTrait,
-	peer_data: &HashMap<PeerId, PeerData>,
+	peer_data: &mut HashMap<PeerId, PeerData>,
 	id: CollatorId,
 ) {
 	if let Some(peer_id) = collator_peer_id(peer_data, &id) {
+		note_peer_score(peer_data, &peer_id, &BENEFIT_NOTIFY_GOOD);
 		modify_reputation(reputation, sender, peer_id, BENEFIT_NOTIFY_GOOD).await;
 	}
 }
@@ -722,6 +737,7 @@ async fn request_collation(
 		from_collator: response_recv,
 		cancellation_token: cancellation_token.clone(),
 		_lifetime_timer: state.metrics.time_collation_request_duration(),
+		_para_latency_timer: state.metrics.time_collation_fetch_latency(para_id),
 	};
 
 	state.collation_requests.push(collation_request);
@@ -1095,7 +1111,10 @@ where
 		)
 		.map_err(AdvertisementError::Invalid)?;
 
-	if per_relay_parent.collations.is_seconded_limit_reached(relay_parent_mode) {
+	if per_relay_parent
+		.collations
+		.is_seconded_limit_reached(relay_parent_mode, collator_para_id)
+	{
 		return Err(AdvertisementError::SecondedLimitReached)
 	}
 
@@ -1187,7 +1206,7 @@ where
 		});
 
 	let collations = &mut per_relay_parent.collations;
-	if collations.is_seconded_limit_reached(relay_parent_mode) {
+	if collations.is_seconded_limit_reached(relay_parent_mode, para_id) {
 		gum::trace!(
 			target: LOG_TARGET,
 			peer_id = ?peer_id,
@@ -1210,7 +1229,7 @@ where
 				?relay_parent,
 				"Added collation to the pending list"
 			);
-			collations.waiting_queue.push_back((pending_collation, collator_id));
+			collations.queue_collation(pending_collation, collator_id);
 		},
 		CollationStatus::Waiting => {
 			fetch_collation(sender, state, pending_collation, collator_id).await?;
@@ -1409,6 +1428,7 @@ async fn handle_network_msg<Context>(
 				view: View::default(),
 				state: PeerState::Connected(Instant::now()),
 				version,
+				score: 0,
 			});
 			state.metrics.note_collator_peer_count(state.peer_data.len());
 		},
@@ -1463,7 +1483,7 @@ async fn process_msg<Context>(
 			);
 		},
 		ReportCollator(id) => {
-			report_collator(&mut state.reputation, ctx.sender(), &state.peer_data, id).await;
+			report_collator(&mut state.reputation, ctx.sender(), &mut state.peer_data, id).await;
 		},
 		NetworkBridgeUpdate(event) => {
 			if let Err(e) = handle_network_msg(ctx, state, keystore, event).await {
@@ -1498,7 +1518,7 @@ async fn process_msg<Context>(
 				note_good_collation(
 					&mut state.reputation,
 					ctx.sender(),
-					&state.peer_data,
+					&mut state.peer_data,
 					collator_id.clone(),
 				)
 				.await;
@@ -1515,7 +1535,7 @@ async fn process_msg<Context>(
 
 				if let Some(rp_state) = state.per_relay_parent.get_mut(&parent) {
 					rp_state.collations.status = CollationStatus::Seconded;
-					rp_state.collations.note_seconded();
+					rp_state.collations.note_seconded(fetched_collation.para_id);
 				}
 
 				// See if we've unblocked other collations for seconding.
@@ -1572,7 +1592,7 @@ async fn process_msg<Context>(
 				Entry::Vacant(_) => return,
 			};
 
-			report_collator(&mut state.reputation, ctx.sender(), &state.peer_data, id.clone())
+			report_collator(&mut state.reputation, ctx.sender(), &mut state.peer_data, id.clone())
 				.await;
 
 			dequeue_next_collation_and_fetch(ctx, state, parent, (id, Some(candidate_hash))).await;
@@ -1651,6 +1671,7 @@ async fn run_inner<Context>(
 					&mut canceled_freq,
 				).await {
 					Err(Some((peer_id, rep))) => {
+						note_peer_score(&mut state.peer_data, &peer_id, &rep);
 						modify_reputation(&mut state.reputation, ctx.sender(), peer_id, rep).await;
 						continue
 					},
@@ -1675,6 +1696,7 @@ async fn run_inner<Context>(
 
 						if err.is_malicious() {
 							// Report malicious peer.
+							note_peer_score(&mut state.peer_data, &pending_collation.peer_id, &COST_REPORT_BAD);
 							modify_reputation(&mut state.reputation, ctx.sender(), pending_collation.peer_id, COST_REPORT_BAD).await;
 						}
 						let maybe_candidate_hash =
@@ -1733,11 +1755,20 @@ async fn dequeue_next_collation_and_fetch<Context>(
 	// The collator we tried to fetch from last, optionally which candidate.
 	previous_fetch: (CollatorId, Option<CandidateHash>),
 ) {
-	while let Some((next, id)) = state.per_relay_parent.get_mut(&relay_parent).and_then(|state| {
-		state
-			.collations
-			.get_next_collation_to_fetch(&previous_fetch, state.prospective_parachains_mode)
-	}) {
+	loop {
+		let peer_data = &state.peer_data;
+		let peer_score =
+			|peer_id: &PeerId| peer_data.get(peer_id).map_or(0, |peer_data| peer_data.score);
+		let Some((next, id)) = state.per_relay_parent.get_mut(&relay_parent).and_then(|rp_state| {
+			rp_state.collations.get_next_collation_to_fetch(
+				&previous_fetch,
+				rp_state.prospective_parachains_mode,
+				peer_score,
+			)
+		}) else {
+			break
+		};
+
 		gum::debug!(
 			target: LOG_TARGET,
 			?relay_parent,
@@ -0,0 +1,130 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the fetch scheduling of advertised collations.
+
+use super::*;
+
+const ASYNC_BACKING_MODE: ProspectiveParachainsMode =
+	ProspectiveParachainsMode::Enabled { max_candidate_depth: 2, allowed_ancestry_len: 3 };
+
+fn queue_advertisements(
+	collations: &mut Collations,
+	para_id: ParaId,
+	peers: &[PeerId],
+) -> Vec<CandidateHash> {
+	peers
+		.iter()
+		.enumerate()
+		.map(|(i, peer_id)| {
+			let candidate_hash = CandidateHash(Hash::repeat_byte(i as u8 + 1));
+			let prospective_candidate =
+				ProspectiveCandidate { candidate_hash, parent_head_data_hash: dummy_hash() };
+			let pending_collation =
+				PendingCollation::new(dummy_hash(), para_id, peer_id, Some(prospective_candidate));
+			let collator_id = CollatorPair::generate().0.public();
+			collations.queue_collation(pending_collation, collator_id);
+			candidate_hash
+		})
+		.collect()
+}
+
+// Fetches and seconds collations until there is nothing left to fetch, returning the para of
+// each fetched collation in order.
+fn fetch_and_second_all(
+	collations: &mut Collations,
+	peer_score: impl Fn(&PeerId) -> i32 + Copy,
+) -> Vec<ParaId> {
+	let finished_one = (CollatorPair::generate().0.public(), None);
+	let mut fetched = Vec::new();
+	while let Some((pending_collation, _)) =
+		collations.get_next_collation_to_fetch(&finished_one, ASYNC_BACKING_MODE, peer_score)
+	{
+		collations.note_seconded(pending_collation.para_id);
+		collations.status = CollationStatus::Seconded;
+		fetched.push(pending_collation.para_id);
+	}
+	fetched
+}
+
+#[test]
+fn fetches_are_shared_proportionally_to_claims() {
+	let para_a = ParaId::from(1);
+	let para_b = ParaId::from(2);
+	let mut collations = Collations::new(&[para_a, para_b, para_a]);
+
+	// The noisy para advertises first and a lot.
+	let peers: Vec<_> = (0..10).map(|_| PeerId::random()).collect();
+	queue_advertisements(&mut collations, para_a, &peers);
+	queue_advertisements(&mut collations, para_b, &peers[..2]);
+
+	// 3 collations can be seconded in total, para A holding 2 of the 3 claims.
+	assert_eq!(fetch_and_second_all(&mut collations, |_| 0), vec![para_a, para_b, para_a]);
+	assert!(collations.is_seconded_limit_reached(ASYNC_BACKING_MODE, para_a));
+	assert!(collations.is_seconded_limit_reached(ASYNC_BACKING_MODE, para_b));
+}
+
+#[test]
+fn para_cannot_exceed_its_share_of_claims() {
+	let para_a = ParaId::from(1);
+	let para_b = ParaId::from(2);
+	let mut collations = Collations::new(&[para_a, para_b, para_b]);
+
+	let peers: Vec<_> = (0..10).map(|_| PeerId::random()).collect();
+	queue_advertisements(&mut collations, para_a, &peers);
+
+	// Para B doesn't advertise anything, but para A still only gets its own share.
+	assert_eq!(fetch_and_second_all(&mut collations, |_| 0), vec![para_a]);
+	assert!(collations.is_seconded_limit_reached(ASYNC_BACKING_MODE, para_a));
+	assert!(!collations.is_seconded_limit_reached(ASYNC_BACKING_MODE, para_b));
+
+	queue_advertisements(&mut collations, para_b, &peers[..3]);
+	assert_eq!(fetch_and_second_all(&mut collations, |_| 0), vec![para_b, para_b]);
+}
+
+#[test]
+fn collations_of_better_scored_peers_are_fetched_first() {
+	let para_id = ParaId::from(1);
+	let mut collations = Collations::new(&[para_id]);
+
+	let good_peer = PeerId::random();
+	let bad_peer = PeerId::random();
+	let unknown_peer = PeerId::random();
+	let candidates =
+		queue_advertisements(&mut collations, para_id, &[bad_peer, unknown_peer, good_peer]);
+
+	let peer_score = |peer_id: &PeerId| {
+		if peer_id == &good_peer {
+			BENEFIT_NOTIFY_GOOD.cost_or_benefit()
+		} else if peer_id == &bad_peer {
+			COST_REPORT_BAD.cost_or_benefit()
+		} else {
+			0
+		}
+	};
+
+	let finished_one = (CollatorPair::generate().0.public(), None);
+	let fetched: Vec<_> = iter::from_fn(|| {
+		collations
+			.get_next_collation_to_fetch(&finished_one, ASYNC_BACKING_MODE, peer_score)
+			.map(|(pending_collation, _)| {
+				pending_collation.prospective_candidate.unwrap().candidate_hash
+			})
+	})
+	.collect();
+
+	assert_eq!(fetched, vec![candidates[2], candidates[1], candidates[0]]);
+}
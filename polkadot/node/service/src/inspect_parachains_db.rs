@@ -0,0 +1,232 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline inspection and repair of the parachains database.
+//!
+//! Checks the consistency of the data of approval voting, chain selection and the dispute
+//! coordinator, prunes corrupted entries and describes the entries of blocks and candidates.
+//!
+//! The node must not be running. Both RocksDB and ParityDB take an exclusive lock on the database
+//! when opening it, so [`open_existing_database`] fails while the node is running, and the node
+//! can't start while the database is inspected or repaired.
+
+use super::{parachains_db::REAL_COLUMNS, DatabaseSource, Error};
+use polkadot_node_core_approval_voting::approval_db::{
+	common::{self as approval_db, Config as ApprovalDbConfig},
+	inspect as approval_voting,
+};
+use polkadot_node_core_chain_selection::inspect as chain_selection;
+use polkadot_node_core_dispute_coordinator::inspect as dispute_coordinator;
+use polkadot_node_subsystem_util::database::Database;
+use polkadot_primitives::{CandidateHash, Hash};
+use std::{path::PathBuf, sync::Arc};
+
+// Repairing may surface new inconsistencies, so the database is verified and repaired again, up
+// to this number of times.
+const MAX_REPAIR_PASSES: usize = 3;
+
+const APPROVAL_DB_CONFIG: ApprovalDbConfig =
+	ApprovalDbConfig { col_approval_data: REAL_COLUMNS.col_approval_data };
+const CHAIN_SELECTION_CONFIG: chain_selection::Config =
+	chain_selection::Config { col_data: REAL_COLUMNS.col_chain_selection_data };
+
+fn dispute_coordinator_config() -> dispute_coordinator::ColumnConfiguration {
+	dispute_coordinator::ColumnConfiguration {
+		col_dispute_data: REAL_COLUMNS.col_dispute_coordinator_data,
+	}
+}
+
+fn inspection_error(err: impl std::fmt::Display) -> Error {
+	Error::ParachainsDbInspection(err.to_string())
+}
+
+// The directory of the parachains database of the given source.
+fn parachains_db_path(db_source: &DatabaseSource) -> Result<PathBuf, Error> {
+	Ok(match db_source {
+		DatabaseSource::RocksDb { path, .. } => path.join("parachains").join("db"),
+		DatabaseSource::ParityDb { path, .. } =>
+			path.parent().ok_or(Error::DatabasePathRequired)?.join("parachains"),
+		DatabaseSource::Auto { paritydb_path, rocksdb_path, .. } =>
+			if paritydb_path.is_dir() {
+				paritydb_path.parent().ok_or(Error::DatabasePathRequired)?.join("parachains")
+			} else {
+				rocksdb_path.join("parachains").join("db")
+			},
+		DatabaseSource::Custom { .. } => return Err(Error::DatabasePathRequired),
+	})
+}
+
+/// Open the existing parachains database of the given source.
+///
+/// Unlike [`open_database`](super::open_database), fails if there is no database at the path
+/// instead of creating an empty one.
+///
+/// The returned database holds the exclusive lock of the database until it's dropped, so no
+/// other process can write to it meanwhile. Fails if the database is already open, e.g. by a
+/// running node.
+pub fn open_existing_database(db_source: &DatabaseSource) -> Result<Arc<dyn Database>, Error> {
+	let path = parachains_db_path(db_source)?;
+	if !path.is_dir() {
+		return Err(inspection_error(format!("no parachains database found at {}", path.display())))
+	}
+	super::open_database(db_source).map_err(|err| {
+		inspection_error(format!(
+			"failed to open the parachains database at {}, make sure the node is not running: {}",
+			path.display(),
+			err,
+		))
+	})
+}
+
+/// The inconsistencies found in the parachains database, per subsystem.
+#[derive(Debug, Clone, Default)]
+pub struct Inconsistencies {
+	/// Inconsistencies of the approval-voting data.
+	pub approval_voting: Vec<approval_voting::Inconsistency>,
+	/// Inconsistencies of the chain selection data.
+	pub chain_selection: Vec<chain_selection::Inconsistency>,
+	/// Inconsistencies of the dispute coordinator data.
+	pub dispute_coordinator: Vec<dispute_coordinator::Inconsistency>,
+}
+
+impl Inconsistencies {
+	/// Whether no inconsistency was found.
+	pub fn is_empty(&self) -> bool {
+		self.approval_voting.is_empty() &&
+			self.chain_selection.is_empty() &&
+			self.dispute_coordinator.is_empty()
+	}
+}
+
+/// Check the consistency of the approval-voting, chain selection and dispute coordinator data.
+pub fn check_consistency(db: &dyn Database) -> Result<Inconsistencies, Error> {
+	Ok(Inconsistencies {
+		approval_voting: approval_voting::check_consistency(db, &APPROVAL_DB_CONFIG)
+			.map_err(inspection_error)?,
+		chain_selection: chain_selection::check_consistency(db, &CHAIN_SELECTION_CONFIG)
+			.map_err(inspection_error)?,
+		dispute_coordinator: dispute_coordinator::check_consistency(
+			db,
+			&dispute_coordinator_config(),
+		)
+		.map_err(inspection_error)?,
+	})
+}
+
+/// Prune the entries involved in the given inconsistencies, as found by [`check_consistency`].
+///
+/// Pruning may leave other entries inconsistent, e.g. un-indexing a block stored at a wrong height
+/// or deleting an undecodable blocks-at-height index leaves the approval entries of the affected
+/// blocks dangling. So the database is verified again after each pass and repaired until it's
+/// consistent, up to `MAX_REPAIR_PASSES` times. Returns the inconsistencies left after the last
+/// pass.
+pub fn repair(
+	db: Arc<dyn Database>,
+	inconsistencies: &Inconsistencies,
+) -> Result<Inconsistencies, Error> {
+	let mut inconsistencies = inconsistencies.clone();
+	for _ in 0..MAX_REPAIR_PASSES {
+		if inconsistencies.is_empty() {
+			break
+		}
+		repair_pass(db.clone(), &inconsistencies)?;
+		inconsistencies = check_consistency(&*db)?;
+	}
+	Ok(inconsistencies)
+}
+
+// Repair each subsystem in its own transaction.
+fn repair_pass(db: Arc<dyn Database>, inconsistencies: &Inconsistencies) -> Result<(), Error> {
+	if !inconsistencies.approval_voting.is_empty() {
+		approval_voting::repair(db.clone(), APPROVAL_DB_CONFIG, &inconsistencies.approval_voting)
+			.map_err(inspection_error)?;
+	}
+	if !inconsistencies.chain_selection.is_empty() {
+		chain_selection::repair(
+			db.clone(),
+			CHAIN_SELECTION_CONFIG,
+			&inconsistencies.chain_selection,
+		)
+		.map_err(inspection_error)?;
+	}
+	if !inconsistencies.dispute_coordinator.is_empty() {
+		dispute_coordinator::repair(
+			&*db,
+			&dispute_coordinator_config(),
+			&inconsistencies.dispute_coordinator,
+		)
+		.map_err(inspection_error)?;
+	}
+	Ok(())
+}
+
+/// Describe the approval-voting and chain selection entries of a block.
+pub fn describe_block(db: &dyn Database, block_hash: &Hash) -> Result<Vec<String>, Error> {
+	let mut descriptions = Vec::new();
+	if let Some(entry) = approval_db::load_block_entry(db, &APPROVAL_DB_CONFIG, block_hash)
+		.map_err(inspection_error)?
+	{
+		descriptions.push(format!("approval voting: {:#?}", entry));
+	}
+	if let Some(entry) = chain_selection::describe_block(db, &CHAIN_SELECTION_CONFIG, block_hash)
+		.map_err(inspection_error)?
+	{
+		descriptions.push(format!("chain selection: {}", entry));
+	}
+	Ok(descriptions)
+}
+
+/// Describe the approval-voting and dispute coordinator entries of a candidate.
+pub fn describe_candidate(
+	db: &dyn Database,
+	candidate_hash: &CandidateHash,
+) -> Result<Vec<String>, Error> {
+	let mut descriptions = Vec::new();
+	if let Some(entry) = approval_db::load_candidate_entry(db, &APPROVAL_DB_CONFIG, candidate_hash)
+		.map_err(inspection_error)?
+	{
+		descriptions.push(format!("approval voting: {:#?}", entry));
+	}
+	for votes in
+		dispute_coordinator::describe_candidate(db, &dispute_coordinator_config(), candidate_hash)
+			.map_err(inspection_error)?
+	{
+		descriptions.push(format!("dispute coordinator: {}", votes));
+	}
+	Ok(descriptions)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::parachains_db::{open_creating_rocksdb, CacheSizes};
+
+	#[test]
+	fn open_database_is_refused() {
+		let db_dir = tempfile::tempdir().unwrap();
+		let db_source = DatabaseSource::RocksDb { path: db_dir.path().into(), cache_size: 128 };
+
+		// as the node does
+		let db = open_creating_rocksdb(db_dir.path().into(), CacheSizes::default()).unwrap();
+		assert!(matches!(
+			open_existing_database(&db_source),
+			Err(Error::ParachainsDbInspection(_))
+		));
+
+		drop(db);
+		assert!(open_existing_database(&db_source).is_ok());
+	}
+}
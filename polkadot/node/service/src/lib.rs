@@ -25,9 +25,13 @@ mod grandpa_support;
 mod parachains_db;
 mod relay_chain_selection;
 
+#[cfg(feature = "full-node")]
+pub mod inspect_parachains_db;
 #[cfg(feature = "full-node")]
 pub mod overseer;
 #[cfg(feature = "full-node")]
+pub mod precompile_pvfs;
+#[cfg(feature = "full-node")]
 pub mod workers;
 
 #[cfg(feature = "full-node")]
@@ -66,6 +70,9 @@ use polkadot_overseer::SpawnGlue;
 
 #[cfg(feature = "full-node")]
 pub use {
+	polkadot_node_core_av_store::{
+		ArchivalConfig as AvailabilityArchivalConfig, ExportOutcome as AvailableDataExport,
+	},
 	polkadot_overseer::{Handle, Overseer, OverseerConnector, OverseerHandle},
 	polkadot_primitives::runtime_api::ParachainHost,
 	relay_chain_selection::SelectRelayChain,
@@ -89,7 +96,9 @@ pub use chain_spec::{GenericChainSpec, RococoChainSpec, WestendChainSpec};
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
 use mmr_gadget::MmrGadget;
 use polkadot_node_subsystem_types::DefaultSubsystemClient;
-pub use polkadot_primitives::{Block, BlockId, BlockNumber, CollatorPair, Hash, Id as ParaId};
+pub use polkadot_primitives::{
+	Block, BlockId, BlockNumber, CandidateHash, CollatorPair, Hash, Id as ParaId,
+};
 pub use sc_client_api::{Backend, CallExecutor};
 pub use sc_consensus::{BlockImport, LongestChain};
 pub use sc_executor::NativeExecutionDispatch;
@@ -252,6 +261,14 @@ pub enum Error {
 		node_version: String,
 		worker_path: PathBuf,
 	},
+
+	#[cfg(feature = "full-node")]
+	#[error("Failed to precompile PVFs: {0}")]
+	PvfPrecompilation(String),
+
+	#[cfg(feature = "full-node")]
+	#[error("Failed to inspect the parachains database: {0}")]
+	ParachainsDbInspection(String),
 }
 
 /// Identifies the variant of the chain.
@@ -358,6 +375,23 @@ pub fn open_database(db_source: &DatabaseSource) -> Result<Arc<dyn Database>, Er
 	Ok(parachains_db)
 }
 
+/// Write the available data of the given candidates, as retained by the availability store in the
+/// node's database, to one file per candidate in `output`.
+#[cfg(feature = "full-node")]
+pub fn export_available_data(
+	db_source: &DatabaseSource,
+	candidates: impl IntoIterator<Item = CandidateHash>,
+	output: &std::path::Path,
+) -> Result<AvailableDataExport, Error> {
+	let parachains_db = open_database(db_source)?;
+	Ok(polkadot_node_core_av_store::export_available_data(
+		&parachains_db,
+		&AVAILABILITY_CONFIG,
+		candidates,
+		output,
+	)?)
+}
+
 #[cfg(feature = "full-node")]
 type FullSelectChain = relay_chain_selection::SelectRelayChain<FullBackend>;
 #[cfg(feature = "full-node")]
@@ -444,6 +478,7 @@ fn new_partial<ChainSelection>(
 	config: &mut Configuration,
 	Basics { task_manager, backend, client, keystore_container, telemetry }: Basics,
 	select_chain: ChainSelection,
+	overseer_handle: Option<Handle>,
 ) -> Result<
 	sc_service::PartialComponents<
 		FullClient,
@@ -586,6 +621,7 @@ where
 					subscription_executor,
 				},
 				backend: backend.clone(),
+				overseer_handle: overseer_handle.clone(),
 			};
 
 			polkadot_rpc::create_full(deps).map_err(Into::into)
@@ -628,6 +664,13 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub prepare_workers_soft_max_num: Option<usize>,
 	/// An optional absolute number of pvf workers that can be spawned in the pvf prepare pool.
 	pub prepare_workers_hard_max_num: Option<usize>,
+	/// An optional read-only store of prepared PVF artifacts shared with other nodes.
+	pub pvf_shared_artifacts_path: Option<std::path::PathBuf>,
+	/// The key the artifacts in the shared store must be signed with. The store is only used if
+	/// this is set.
+	pub pvf_shared_artifacts_key: Option<sp_core::ed25519::Public>,
+	/// Retention of finalized availability data beyond the default.
+	pub availability_archival: AvailabilityArchivalConfig,
 	pub overseer_gen: OverseerGenerator,
 	pub overseer_message_channel_capacity_override: Option<usize>,
 	#[allow(dead_code)]
@@ -727,6 +770,9 @@ pub fn new_full<
 		execute_workers_max_num,
 		prepare_workers_soft_max_num,
 		prepare_workers_hard_max_num,
+		pvf_shared_artifacts_path,
+		pvf_shared_artifacts_key,
+		availability_archival,
 		enable_approval_voting_parallel,
 	}: NewFullParams<OverseerGenerator>,
 ) -> Result<NewFull, Error> {
@@ -803,7 +849,14 @@ pub fn new_full<
 		import_queue,
 		transaction_pool,
 		other: (rpc_extensions_builder, import_setup, rpc_setup, slot_duration, mut telemetry),
-	} = new_partial::<SelectRelayChain<_>>(&mut config, basics, select_chain)?;
+	} = new_partial::<SelectRelayChain<_>>(
+		&mut config,
+		basics,
+		select_chain,
+		// The overseer only runs on nodes that need the parachain subsystems.
+		(role.is_authority() || is_parachain_node.is_running_alongside_parachain_node())
+			.then(|| overseer_handle.clone()),
+	)?;
 
 	let metrics = Network::register_notification_metrics(
 		config.prometheus_config.as_ref().map(|cfg| &cfg.registry),
@@ -954,6 +1007,13 @@ pub fn new_full<
 				),
 				pvf_prepare_workers_soft_max_num: prepare_workers_soft_max_num.unwrap_or(1),
 				pvf_prepare_workers_hard_max_num: prepare_workers_hard_max_num.unwrap_or(2),
+				pvf_shared_artifacts: pvf_shared_artifacts_path.zip(pvf_shared_artifacts_key).map(
+					|(path, trusted_key)| polkadot_node_core_pvf::SharedArtifactsConfig {
+						path,
+						trusted_key,
+						signing_key: None,
+					},
+				),
 			})
 		} else {
 			None
@@ -989,6 +1049,7 @@ pub fn new_full<
 			parachains_db,
 			candidate_validation_config,
 			availability_config: AVAILABILITY_CONFIG,
+			availability_archival,
 			pov_req_receiver,
 			chunk_req_v1_receiver,
 			chunk_req_v2_receiver,
@@ -1406,7 +1467,7 @@ macro_rules! chain_ops {
 		let chain_selection = LongestChain::new(basics.backend.clone());
 
 		let sc_service::PartialComponents { client, backend, import_queue, task_manager, .. } =
-			new_partial::<LongestChain<_, Block>>(&mut config, basics, chain_selection)?;
+			new_partial::<LongestChain<_, Block>>(&mut config, basics, chain_selection, None)?;
 		Ok((client, backend, import_queue, task_manager))
 	}};
 }
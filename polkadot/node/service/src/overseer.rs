@@ -21,7 +21,7 @@ use sp_core::traits::SpawnNamed;
 
 use polkadot_availability_distribution::IncomingRequestReceivers;
 use polkadot_node_core_approval_voting::{Config as ApprovalVotingConfig, RealAssignmentCriteria};
-use polkadot_node_core_av_store::Config as AvailabilityConfig;
+use polkadot_node_core_av_store::{ArchivalConfig, Config as AvailabilityConfig};
 use polkadot_node_core_candidate_validation::Config as CandidateValidationConfig;
 use polkadot_node_core_chain_selection::Config as ChainSelectionConfig;
 use polkadot_node_core_dispute_coordinator::Config as DisputeCoordinatorConfig;
@@ -120,6 +120,8 @@ pub struct ExtendedOverseerGenArgs {
 	pub candidate_validation_config: Option<CandidateValidationConfig>,
 	/// Configuration for the availability store subsystem.
 	pub availability_config: AvailabilityConfig,
+	/// Retention of finalized availability data beyond the default.
+	pub availability_archival: ArchivalConfig,
 	/// POV request receiver.
 	pub pov_req_receiver: IncomingRequestReceiver<request_v1::PoVFetchingRequest>,
 	/// Erasure chunk request v1 receiver.
@@ -174,6 +176,7 @@ pub fn validator_overseer_builder<Spawner, RuntimeClient>(
 		parachains_db,
 		candidate_validation_config,
 		availability_config,
+		availability_archival,
 		pov_req_receiver,
 		chunk_req_v1_receiver,
 		chunk_req_v2_receiver,
@@ -272,12 +275,15 @@ where
 			&req_protocol_names,
 			Metrics::register(registry)?,
 		))
-		.availability_store(AvailabilityStoreSubsystem::new(
-			parachains_db.clone(),
-			availability_config,
-			Box::new(sync_service.clone()),
-			Metrics::register(registry)?,
-		))
+		.availability_store(
+			AvailabilityStoreSubsystem::new(
+				parachains_db.clone(),
+				availability_config,
+				Box::new(sync_service.clone()),
+				Metrics::register(registry)?,
+			)
+			.with_archival(availability_archival),
+		)
 		.bitfield_distribution(BitfieldDistributionSubsystem::new(Metrics::register(registry)?))
 		.bitfield_signing(BitfieldSigningSubsystem::new(
 			keystore.clone(),
@@ -398,6 +404,7 @@ pub fn validator_with_parallel_overseer_builder<Spawner, RuntimeClient>(
 		parachains_db,
 		candidate_validation_config,
 		availability_config,
+		availability_archival,
 		pov_req_receiver,
 		chunk_req_v1_receiver,
 		chunk_req_v2_receiver,
@@ -496,12 +503,15 @@ where
 			&req_protocol_names,
 			Metrics::register(registry)?,
 		))
-		.availability_store(AvailabilityStoreSubsystem::new(
-			parachains_db.clone(),
-			availability_config,
-			Box::new(sync_service.clone()),
-			Metrics::register(registry)?,
-		))
+		.availability_store(
+			AvailabilityStoreSubsystem::new(
+				parachains_db.clone(),
+				availability_config,
+				Box::new(sync_service.clone()),
+				Metrics::register(registry)?,
+			)
+			.with_archival(availability_archival),
+		)
 		.bitfield_distribution(BitfieldDistributionSubsystem::new(Metrics::register(registry)?))
 		.bitfield_signing(BitfieldSigningSubsystem::new(
 			keystore.clone(),
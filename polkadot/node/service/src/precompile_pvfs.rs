@@ -0,0 +1,181 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline preparation of the PVFs of the current session into a shared artifact store.
+//!
+//! See [`polkadot_node_core_pvf::SharedArtifactsConfig`] for how nodes make use of the store.
+
+use super::{workers, Error, FullClient, HeaderBackend, ParachainHost};
+use futures::{channel::oneshot, future::Either};
+use polkadot_node_core_pvf::{PrepareJobKind, PvfPrepData, SharedArtifactsConfig};
+use polkadot_node_primitives::{NODE_VERSION, VALIDATION_CODE_BOMB_LIMIT};
+use polkadot_primitives::{
+	executor_params::DEFAULT_LENIENT_PREPARATION_TIMEOUT, vstaging::CoreState,
+	OccupiedCoreAssumption, PvfPrepKind, SessionIndex, ValidationCode, ValidationCodeHash,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_core::{ed25519, Pair};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	path::PathBuf,
+	sync::Arc,
+};
+
+/// Parameters of [`precompile_pvfs`].
+pub struct PrecompilePvfsParams {
+	/// The shared artifact store to add the prepared artifacts to.
+	pub shared_artifacts_path: PathBuf,
+	/// The key to sign the prepared artifacts with, the nodes using the store must trust its
+	/// public key.
+	pub signing_key: ed25519::Pair,
+	/// An optional path to a directory containing the workers.
+	pub workers_path: Option<PathBuf>,
+	/// Optional custom names for the prepare and execute workers.
+	pub workers_names: Option<(String, String)>,
+	/// Whether to require the workers to be fully sandboxed, as validators do.
+	pub secure_validator_mode: bool,
+	/// The maximum number of PVFs to prepare in parallel.
+	pub prepare_workers_num: usize,
+}
+
+/// The outcome of [`precompile_pvfs`].
+#[derive(Debug, Default)]
+pub struct PrecompiledPvfs {
+	/// The session whose executor parameters the PVFs were prepared with.
+	pub session: SessionIndex,
+	/// The PVFs that are now in the store.
+	pub prepared: Vec<ValidationCodeHash>,
+	/// The PVFs that could not be prepared, along with the reason.
+	pub failed: Vec<(ValidationCodeHash, String)>,
+}
+
+/// Prepares the PVFs of all the parachains scheduled or pending availability at the best block,
+/// with the executor parameters of the current session, and adds them to the shared artifact store.
+///
+/// Both the current code of every parachain and the code it is going to upgrade to once its
+/// pending candidate is included are prepared. PVFs already in the store are not prepared again.
+pub async fn precompile_pvfs(
+	client: Arc<FullClient>,
+	params: PrecompilePvfsParams,
+) -> Result<PrecompiledPvfs, Error> {
+	let best_hash = client.info().best_hash;
+	let api = client.runtime_api();
+	let session = api.session_index_for_child(best_hash).map_err(sp_blockchain::Error::from)?;
+	let executor_params = api
+		.session_executor_params(best_hash, session)
+		.map_err(sp_blockchain::Error::from)?
+		.unwrap_or_default();
+
+	let mut paras = BTreeSet::new();
+	// Older runtimes don't expose the claim queue, the availability cores are enough then.
+	if let Ok(claim_queue) = api.claim_queue(best_hash) {
+		paras.extend(claim_queue.into_values().flatten());
+	}
+	for core in api.availability_cores(best_hash).map_err(sp_blockchain::Error::from)? {
+		match core {
+			CoreState::Occupied(core) => paras.insert(core.candidate_descriptor.para_id()),
+			CoreState::Scheduled(core) => paras.insert(core.para_id),
+			CoreState::Free => false,
+		};
+	}
+
+	let mut codes = BTreeMap::new();
+	for para_id in paras {
+		for assumption in [OccupiedCoreAssumption::TimedOut, OccupiedCoreAssumption::Included] {
+			if let Some(code) = api
+				.validation_code(best_hash, para_id, assumption)
+				.map_err(sp_blockchain::Error::from)?
+			{
+				codes.insert(code.hash(), code);
+			}
+		}
+	}
+	gum::info!(
+		target: LOG_TARGET,
+		session,
+		pvfs = codes.len(),
+		"Preparing PVFs of the current session",
+	);
+
+	let (prep_worker_path, exec_worker_path) = workers::determine_workers_paths(
+		params.workers_path,
+		params.workers_names,
+		Some(NODE_VERSION.to_owned()),
+	)?;
+	// The artifacts only need to outlive the host, those worth keeping are in the shared store.
+	let cache_dir = tempfile::tempdir()?;
+	let mut config = polkadot_node_core_pvf::Config::new(
+		cache_dir.path().to_owned(),
+		Some(NODE_VERSION.to_owned()),
+		params.secure_validator_mode,
+		prep_worker_path,
+		exec_worker_path,
+		1,
+		params.prepare_workers_num,
+		params.prepare_workers_num,
+	);
+	config.shared_artifacts = Some(SharedArtifactsConfig {
+		path: params.shared_artifacts_path,
+		trusted_key: params.signing_key.public(),
+		signing_key: Some(params.signing_key),
+	});
+	let (mut host, host_task) = polkadot_node_core_pvf::start(config, Default::default()).await?;
+
+	let prep_timeout = executor_params
+		.pvf_prep_timeout(PvfPrepKind::Prepare)
+		.unwrap_or(DEFAULT_LENIENT_PREPARATION_TIMEOUT);
+	let prepare_all = async move {
+		let mut outcome = PrecompiledPvfs { session, ..Default::default() };
+		let mut pending = Vec::with_capacity(codes.len());
+		for (code_hash, ValidationCode(code)) in codes {
+			let code = match sp_maybe_compressed_blob::decompress(&code, VALIDATION_CODE_BOMB_LIMIT)
+			{
+				Ok(code) => code.into_owned(),
+				Err(e) => {
+					outcome.failed.push((code_hash, format!("invalid code: {e:?}")));
+					continue
+				},
+			};
+			let pvf = PvfPrepData::from_code(
+				code,
+				executor_params.clone(),
+				prep_timeout,
+				PrepareJobKind::Compilation,
+			);
+			let (result_tx, result_rx) = oneshot::channel();
+			host.precheck_pvf(pvf, result_tx).await.map_err(Error::PvfPrecompilation)?;
+			pending.push((code_hash, result_rx));
+		}
+
+		for (code_hash, result_rx) in pending {
+			match result_rx.await {
+				Ok(Ok(())) => outcome.prepared.push(code_hash),
+				Ok(Err(e)) => outcome.failed.push((code_hash, e.to_string())),
+				Err(_) => return Err(Error::PvfPrecompilation("the PVF host shut down".into())),
+			}
+		}
+		Ok(outcome)
+	};
+
+	futures::pin_mut!(prepare_all, host_task);
+	match futures::future::select(prepare_all, host_task).await {
+		Either::Left((outcome, _)) => outcome,
+		Either::Right(((), _)) =>
+			Err(Error::PvfPrecompilation("the PVF host terminated unexpectedly".into())),
+	}
+}
+
+const LOG_TARGET: &str = "parachain::pvf-precompile";
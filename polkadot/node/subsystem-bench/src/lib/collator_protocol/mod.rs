@@ -0,0 +1,281 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmark of the validator side of the collator-protocol subsystem.
+//!
+//! Emulated collators of a single para connect to the node under test and advertise their
+//! collations at every relay chain block. The node under test fetches them over the emulated
+//! network and hands them over to candidate-backing until its seconding limit is reached.
+
+use crate::{
+	dummy_builder,
+	environment::{TestEnvironment, TestEnvironmentDependencies},
+	mock::{
+		candidate_backing::MockCandidateBacking,
+		chain_api::{ChainApiState, MockChainApi},
+		network_bridge::{MockNetworkBridgeRx, MockNetworkBridgeTx},
+		prospective_parachains::MockProspectiveParachains,
+		runtime_api::{session_info_for_peers, MockRuntimeApi, MockRuntimeApiCoreState},
+		AlwaysSupportsParachains,
+	},
+	network::{new_network, NetworkEmulatorHandle, NetworkInterface, NetworkInterfaceReceiver},
+	usage::BenchmarkUsage,
+	NODE_UNDER_TEST,
+};
+use colored::Colorize;
+use polkadot_collator_protocol::{CollatorEvictionPolicy, CollatorProtocolSubsystem, ProtocolSide};
+use polkadot_node_metrics::metrics::Metrics;
+use polkadot_node_network_protocol::{
+	peer_set::CollationVersion, v1 as protocol_v1, v2 as protocol_v2, ObservedRole, OurView,
+	Versioned,
+};
+use polkadot_node_subsystem::messages::{AllMessages, CollatorProtocolMessage, NetworkBridgeEvent};
+use polkadot_overseer::{
+	Handle as OverseerHandle, Overseer, OverseerConnector, OverseerMetrics, SpawnGlue,
+};
+use polkadot_primitives::{Id, ValidatorId, ValidatorIndex};
+use sc_keystore::LocalKeystore;
+use sc_network_types::PeerId;
+use sc_service::SpawnTaskHandle;
+use serde::{Deserialize, Serialize};
+use sp_core::Pair;
+use sp_keystore::{Keystore, KeystorePtr};
+use sp_runtime::RuntimeAppPublic;
+use std::{sync::Arc, time::Instant};
+pub use test_state::TestState;
+
+mod test_state;
+
+const LOG_TARGET: &str = "subsystem-bench::collator-protocol";
+
+/// Parameters specific to the collator-protocol benchmark
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct CollatorProtocolOptions {
+	#[clap(short, long, default_value_t = 10)]
+	/// The number of emulated collators of the para assigned to the node under test.
+	pub collators_per_para: usize,
+	#[clap(long, default_value_t = 1)]
+	/// The number of collations every collator advertises at each relay chain block. More than
+	/// one emulates elastic scaling.
+	pub collations_per_collator: usize,
+}
+
+fn make_keystore() -> KeystorePtr {
+	let keystore: KeystorePtr = Arc::new(LocalKeystore::in_memory());
+	Keystore::sr25519_generate_new(&*keystore, ValidatorId::ID, Some("//Node0"))
+		.expect("Insert key into keystore");
+	keystore
+}
+
+fn build_overseer(
+	state: &TestState,
+	network: NetworkEmulatorHandle,
+	network_interface: NetworkInterface,
+	network_receiver: NetworkInterfaceReceiver,
+	dependencies: &TestEnvironmentDependencies,
+) -> (Overseer<SpawnGlue<SpawnTaskHandle>, AlwaysSupportsParachains>, OverseerHandle) {
+	let overseer_connector = OverseerConnector::with_event_capacity(64000);
+	let overseer_metrics = OverseerMetrics::try_register(&dependencies.registry).unwrap();
+	let spawn_task_handle = dependencies.task_manager.spawn_handle();
+	let mock_runtime_api = MockRuntimeApi::new(
+		state.config.clone(),
+		state.test_authorities.clone(),
+		state.candidate_receipts.clone(),
+		Default::default(),
+		Default::default(),
+		0,
+		MockRuntimeApiCoreState::Scheduled,
+	);
+	let chain_api_state = ChainApiState { block_headers: state.block_headers.clone() };
+	let mock_chain_api = MockChainApi::new(chain_api_state);
+	let mock_prospective_parachains = MockProspectiveParachains::with_validation_data(
+		state.minimum_relay_parents.clone(),
+		state.pvd.clone(),
+	);
+	let own_backing_group = session_info_for_peers(&state.config, &state.test_authorities)
+		.validator_groups
+		.iter()
+		.find(|g| g.contains(&ValidatorIndex(NODE_UNDER_TEST)))
+		.unwrap()
+		.clone();
+	let mock_candidate_backing = MockCandidateBacking::new(
+		state.config.clone(),
+		state
+			.test_authorities
+			.validator_pairs
+			.get(NODE_UNDER_TEST as usize)
+			.unwrap()
+			.clone(),
+		state.pvd.clone(),
+		own_backing_group,
+	)
+	.with_collations(state.collations.clone());
+	let subsystem = CollatorProtocolSubsystem::new(ProtocolSide::Validator {
+		keystore: make_keystore(),
+		eviction_policy: CollatorEvictionPolicy::default(),
+		metrics: Metrics::try_register(&dependencies.registry).unwrap(),
+	});
+	// Collators are reached through the emulated network as well.
+	let network_bridge_tx = MockNetworkBridgeTx::new(
+		network,
+		network_interface.subsystem_sender(),
+		state.network_authorities.clone(),
+	);
+	let network_bridge_rx = MockNetworkBridgeRx::new(network_receiver, None, false);
+
+	let dummy = dummy_builder!(spawn_task_handle, overseer_metrics)
+		.replace_runtime_api(|_| mock_runtime_api)
+		.replace_chain_api(|_| mock_chain_api)
+		.replace_prospective_parachains(|_| mock_prospective_parachains)
+		.replace_candidate_backing(|_| mock_candidate_backing)
+		.replace_collator_protocol(|_| subsystem)
+		.replace_network_bridge_tx(|_| network_bridge_tx)
+		.replace_network_bridge_rx(|_| network_bridge_rx);
+	let (overseer, raw_handle) = dummy.build_with_connector(overseer_connector).unwrap();
+	let overseer_handle = OverseerHandle::new(raw_handle);
+
+	(overseer, overseer_handle)
+}
+
+pub fn prepare_test(state: &TestState, with_prometheus_endpoint: bool) -> TestEnvironment {
+	let dependencies = TestEnvironmentDependencies::default();
+	let (network, network_interface, network_receiver) = new_network(
+		&state.network_config,
+		&dependencies,
+		&state.network_authorities,
+		vec![Arc::new(state.clone())],
+	);
+	let (overseer, overseer_handle) =
+		build_overseer(state, network.clone(), network_interface, network_receiver, &dependencies);
+
+	TestEnvironment::new(
+		dependencies,
+		state.config.clone(),
+		network,
+		overseer,
+		overseer_handle,
+		state.test_authorities.clone(),
+		with_prometheus_endpoint,
+	)
+}
+
+fn collator_message(peer_id: PeerId, message: protocol_v2::CollatorProtocolMessage) -> AllMessages {
+	AllMessages::CollatorProtocol(CollatorProtocolMessage::NetworkBridgeUpdate(
+		NetworkBridgeEvent::PeerMessage(peer_id, Versioned::V2(message)),
+	))
+}
+
+pub async fn benchmark_collator_protocol(
+	env: &mut TestEnvironment,
+	state: &TestState,
+) -> BenchmarkUsage {
+	let config = env.config().clone();
+	env.metrics().set_n_validators(config.n_validators);
+	env.metrics().set_n_cores(config.n_cores);
+
+	let para_id = Id::new(test_state::COLLATING_PARA_ID);
+	let parent_head_data_hash = state.pvd.parent_head.hash();
+	let fetches_per_block = state.expected_fetches_per_block();
+	gum::info!(target: LOG_TARGET,
+		"{} collators advertising {} collations each, {} fetches expected per block",
+		state.options.collators_per_para,
+		state.options.collations_per_collator,
+		fetches_per_block,
+	);
+
+	let test_start = Instant::now();
+	for block_info in state.block_infos.iter() {
+		let block_num = block_info.number as usize;
+		gum::info!(target: LOG_TARGET, "Current block {}/{} {:?}", block_num, config.num_blocks, block_info.hash);
+		env.metrics().set_current_block(block_num);
+
+		let block_start_ts = Instant::now();
+		env.import_block(block_info.clone()).await;
+		env.send_message(AllMessages::CollatorProtocol(
+			CollatorProtocolMessage::NetworkBridgeUpdate(NetworkBridgeEvent::OurViewChange(
+				OurView::new([block_info.hash], 0),
+			)),
+		))
+		.await;
+
+		// Collators connect once the node under test has a view to advertise in.
+		if block_num == 1 {
+			for collator in state.collators.iter() {
+				env.send_message(AllMessages::CollatorProtocol(
+					CollatorProtocolMessage::NetworkBridgeUpdate(
+						NetworkBridgeEvent::PeerConnected(
+							collator.peer_id,
+							ObservedRole::Full,
+							CollationVersion::V2.into(),
+							None,
+						),
+					),
+				))
+				.await;
+				let signature =
+					collator.pair.sign(&protocol_v1::declare_signature_payload(&collator.peer_id));
+				env.send_message(collator_message(
+					collator.peer_id,
+					protocol_v2::CollatorProtocolMessage::Declare(
+						collator.pair.public(),
+						para_id,
+						signature,
+					),
+				))
+				.await;
+			}
+		}
+
+		for collator in state.collators.iter() {
+			let collations = collator.collations.get(&block_info.hash).expect("pregenerated");
+			for candidate_hash in collations {
+				env.send_message(collator_message(
+					collator.peer_id,
+					protocol_v2::CollatorProtocolMessage::AdvertiseCollation {
+						relay_parent: block_info.hash,
+						candidate_hash: *candidate_hash,
+						parent_head_data_hash,
+					},
+				))
+				.await;
+			}
+		}
+
+		gum::info!(target: LOG_TARGET, "Waiting for the collations to be fetched ...");
+		env.wait_until_metric(
+			"polkadot_parachain_collation_requests_total",
+			Some(("success", "succeeded")),
+			|value| value >= (block_num * fetches_per_block) as f64,
+		)
+		.await;
+
+		let block_time = block_start_ts.elapsed().as_millis() as u64;
+		env.metrics().set_block_time(block_time);
+		gum::info!(target: LOG_TARGET, "All collations of the block fetched in {}", format!("{:?}ms", block_time).cyan());
+	}
+
+	let duration: u128 = test_start.elapsed().as_millis();
+	gum::info!(target: LOG_TARGET, "All blocks processed in {}", format!("{:?}ms", duration).cyan());
+	gum::info!(target: LOG_TARGET,
+		"Avg block time: {}",
+		format!("{} ms", test_start.elapsed().as_millis() / env.config().num_blocks as u128).red()
+	);
+
+	env.stop().await;
+	env.collect_resource_usage(&["collator-protocol"], false)
+}
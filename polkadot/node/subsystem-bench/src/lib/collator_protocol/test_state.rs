@@ -0,0 +1,239 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	collator_protocol::CollatorProtocolOptions,
+	configuration::{TestAuthorities, TestConfiguration},
+	network::{HandleNetworkMessage, NetworkMessage},
+};
+use codec::Encode;
+use polkadot_node_network_protocol::request_response::{v1::CollationFetchingResponse, Requests};
+use polkadot_node_primitives::{BlockData, PoV};
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	vstaging::{
+		CandidateReceiptV2 as CandidateReceipt,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, MutateDescriptorV2,
+	},
+	BlockNumber, CandidateHash, CollatorPair, CoreIndex, Hash, HeadData, Header, Id,
+	PersistedValidationData,
+};
+use polkadot_primitives_test_helpers::{
+	dummy_committed_candidate_receipt_v2, dummy_hash, dummy_head_data, dummy_pvd,
+};
+use sc_network::ProtocolName;
+use sc_network_types::PeerId;
+use sp_core::{Pair, H256};
+use std::{collections::HashMap, sync::Arc};
+
+const SESSION_INDEX: u32 = 0;
+
+/// The para all emulated collators are collating for. It is assigned to the core of the backing
+/// group of the node under test.
+pub const COLLATING_PARA_ID: u32 = 1;
+
+/// An emulated collator.
+#[derive(Clone)]
+pub struct TestCollator {
+	/// The peer id the collator is connected with.
+	pub peer_id: PeerId,
+	/// The collator keys.
+	pub pair: CollatorPair,
+	/// Collations built by the collator at every relay chain block.
+	pub collations: HashMap<H256, Vec<CandidateHash>>,
+}
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Test configuration of the emulated network, which has collators on top of validators
+	pub network_config: TestConfiguration,
+	// Benchmark specific options
+	pub options: CollatorProtocolOptions,
+	// Authority keys of the validators
+	pub test_authorities: TestAuthorities,
+	// Keys of all emulated network peers, the collators come after the validators
+	pub network_authorities: TestAuthorities,
+	// Emulated collators
+	pub collators: Vec<TestCollator>,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers
+	pub block_headers: HashMap<H256, Header>,
+	// Map from generated candidate receipts, one candidate per core
+	pub candidate_receipts: HashMap<H256, Vec<CandidateReceipt>>,
+	// PersistedValidationData, we use one for all candidates
+	pub pvd: PersistedValidationData,
+	// Collations advertised by the emulated collators
+	pub collations: HashMap<CandidateHash, CommittedCandidateReceipt>,
+	// PoV of every collation
+	pub povs: HashMap<CandidateHash, Arc<PoV>>,
+	// Minimum relay parents of the collating para at every block
+	pub minimum_relay_parents: HashMap<H256, Vec<(Id, BlockNumber)>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &CollatorProtocolOptions) -> Self {
+		let n_validators = config.n_validators;
+		let network_config = TestConfiguration {
+			n_validators: n_validators + options.collators_per_para,
+			connectivity: 100,
+			..config.clone()
+		};
+		let network_authorities = network_config.generate_authorities();
+		let test_authorities = validator_authorities(&network_authorities, n_validators);
+		let block_infos: Vec<_> = (1..=config.num_blocks).map(generate_block_info).collect();
+		let block_headers = block_infos.iter().map(generate_block_header).collect();
+		let pvd = dummy_pvd(dummy_head_data(), 0);
+
+		let mut state = Self {
+			config: config.clone(),
+			network_config,
+			options: options.clone(),
+			test_authorities,
+			network_authorities,
+			collators: Default::default(),
+			block_infos,
+			block_headers,
+			candidate_receipts: Default::default(),
+			pvd,
+			collations: Default::default(),
+			povs: Default::default(),
+			minimum_relay_parents: Default::default(),
+		};
+
+		for block_info in state.block_infos.iter() {
+			let receipts = (0..config.n_cores)
+				.map(|core_idx| {
+					let mut receipt = dummy_committed_candidate_receipt_v2(block_info.hash);
+					receipt.descriptor.set_para_id(Id::new(core_idx as u32 + 1));
+					receipt.descriptor.set_core_index(CoreIndex(core_idx as u32));
+					receipt.descriptor.set_session_index(SESSION_INDEX);
+					receipt.to_plain()
+				})
+				.collect();
+			state.candidate_receipts.insert(block_info.hash, receipts);
+			state
+				.minimum_relay_parents
+				.insert(block_info.hash, vec![(Id::new(COLLATING_PARA_ID), block_info.number)]);
+		}
+
+		// All collators build collations of the same size.
+		let pov_size = config.pov_sizes().first().copied().unwrap_or_default();
+		let pov = Arc::new(PoV { block_data: BlockData(vec![0u8; pov_size]) });
+		for collator_idx in 0..options.collators_per_para {
+			let mut collator = TestCollator {
+				peer_id: state.network_authorities.peer_ids[n_validators + collator_idx],
+				pair: CollatorPair::from_string(&format!("//Collator{}", collator_idx), None)
+					.expect("Valid seed"),
+				collations: Default::default(),
+			};
+			for block_info in state.block_infos.iter() {
+				for collation_idx in 0..options.collations_per_collator {
+					let mut receipt = dummy_committed_candidate_receipt_v2(dummy_hash());
+					receipt.descriptor.set_para_id(Id::new(COLLATING_PARA_ID));
+					receipt.descriptor.set_relay_parent(block_info.hash);
+					receipt.descriptor.set_core_index(CoreIndex(0));
+					receipt.descriptor.set_session_index(SESSION_INDEX);
+					receipt.descriptor.set_persisted_validation_data_hash(state.pvd.hash());
+					receipt.descriptor.set_pov_hash(pov.hash());
+					// Make every collation unique.
+					receipt.commitments.head_data = HeadData(
+						(collator_idx as u32, collation_idx as u32, block_info.number).encode(),
+					);
+					let candidate_hash = receipt.hash();
+					collator.collations.entry(block_info.hash).or_default().push(candidate_hash);
+					state.povs.insert(candidate_hash, pov.clone());
+					state.collations.insert(candidate_hash, receipt);
+				}
+			}
+			state.collators.push(collator);
+		}
+
+		state
+	}
+
+	/// The number of collations the node under test is expected to fetch at every block.
+	pub fn expected_fetches_per_block(&self) -> usize {
+		// With async backing the validator seconds at most `max_candidate_depth + 1` candidates
+		// per relay parent and accepts as many advertisements from every collator.
+		let limit = self.config.max_candidate_depth as usize + 1;
+		(self.options.collators_per_para * self.options.collations_per_collator.min(limit))
+			.min(limit)
+	}
+}
+
+/// Returns the authorities of the first `n_validators` peers.
+fn validator_authorities(authorities: &TestAuthorities, n_validators: usize) -> TestAuthorities {
+	let peer_ids = authorities.peer_ids[..n_validators].to_vec();
+	TestAuthorities {
+		keyring: authorities.keyring.clone(),
+		validator_public: authorities.validator_public[..n_validators].to_vec(),
+		validator_authority_id: authorities.validator_authority_id[..n_validators].to_vec(),
+		validator_babe_id: authorities.validator_babe_id[..n_validators].to_vec(),
+		validator_assignment_id: authorities.validator_assignment_id[..n_validators].to_vec(),
+		key_seeds: authorities.key_seeds[..n_validators].to_vec(),
+		peer_id_to_authority: authorities
+			.peer_id_to_authority
+			.iter()
+			.filter(|(peer_id, _)| peer_ids.contains(peer_id))
+			.map(|(peer_id, authority_id)| (*peer_id, authority_id.clone()))
+			.collect(),
+		peer_ids,
+		validator_pairs: authorities.validator_pairs[..n_validators].to_vec(),
+	}
+}
+
+fn generate_block_info(block_num: usize) -> BlockInfo {
+	new_block_import_info(Hash::repeat_byte(block_num as u8), block_num as BlockNumber)
+}
+
+fn generate_block_header(info: &BlockInfo) -> (H256, Header) {
+	(
+		info.hash,
+		Header {
+			digest: Default::default(),
+			number: info.number,
+			parent_hash: info.parent_hash,
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+#[async_trait::async_trait]
+impl HandleNetworkMessage for TestState {
+	async fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut futures::channel::mpsc::UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::RequestFromNode(_authority_id, Requests::CollationFetchingV2(req)) => {
+				let candidate_hash = req.payload.candidate_hash;
+				let receipt = self.collations.get(&candidate_hash).expect("Advertised collation");
+				let pov = self.povs.get(&candidate_hash).expect("Advertised collation");
+				let response =
+					CollationFetchingResponse::Collation(receipt.to_plain(), (**pov).clone());
+				let _ = req.pending_response.send(Ok((response.encode(), ProtocolName::from(""))));
+				None
+			},
+			_ => Some(message),
+		}
+	}
+}
@@ -16,10 +16,10 @@
 
 //! Test configuration definition and helpers.
 
-use crate::keyring::Keyring;
+use crate::{keyring::Keyring, network_profile::NetworkProfile};
 use itertools::Itertools;
 use polkadot_primitives::{AssignmentId, AuthorityDiscoveryId, ValidatorId, ValidatorPair};
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 use rand_distr::{Distribution, Normal, Uniform};
 use sc_network_types::PeerId;
 use serde::{Deserialize, Serialize};
@@ -156,6 +156,9 @@ pub struct TestConfiguration {
 	/// Async Backing allowed_ancestry_len
 	#[serde(default = "default_allowed_ancestry_len")]
 	pub allowed_ancestry_len: u32,
+	/// Optional network emulation profile, applied on top of the settings above
+	#[serde(default)]
+	pub network_profile: Option<NetworkProfile>,
 }
 
 impl Default for TestConfiguration {
@@ -180,6 +183,7 @@ impl Default for TestConfiguration {
 			minimum_backing_votes: default_minimum_backing_votes(),
 			max_candidate_depth: default_max_candidate_depth(),
 			allowed_ancestry_len: default_allowed_ancestry_len(),
+			network_profile: None,
 		}
 	}
 }
@@ -194,7 +198,13 @@ impl TestConfiguration {
 	}
 	/// Return the number of peers connected to our node.
 	pub fn connected_count(&self) -> usize {
-		((self.n_validators - 1) as f64 / (100.0 / self.connectivity as f64)) as usize
+		let connected = (self.n_validators - 1) as f64 / (100.0 / self.connectivity as f64);
+		// Offline validators can't be connected.
+		let online = self
+			.network_profile
+			.as_ref()
+			.map_or(1.0, |profile| 100usize.saturating_sub(profile.offline) as f64 / 100.0);
+		(connected * online) as usize
 	}
 
 	/// Generates the authority keys we need for the network emulation.
@@ -279,12 +289,12 @@ pub struct TestAuthorities {
 
 /// Sample latency (in milliseconds) from a normal distribution with parameters
 /// specified in `maybe_peer_latency`.
-pub fn random_latency(maybe_peer_latency: Option<&PeerLatency>) -> usize {
+pub fn sample_latency(maybe_peer_latency: Option<&PeerLatency>, rng: &mut impl Rng) -> usize {
 	maybe_peer_latency
 		.map(|latency_config| {
 			Normal::new(latency_config.mean_latency_ms as f64, latency_config.std_dev)
 				.expect("normal distribution parameters are good")
-				.sample(&mut thread_rng())
+				.sample(rng)
 		})
 		.unwrap_or(0.0) as usize
 }
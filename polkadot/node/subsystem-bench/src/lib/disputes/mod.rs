@@ -0,0 +1,293 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmark of the dispute-coordinator and dispute-distribution subsystems.
+//!
+//! Emulated validators raise disputes for candidates included in every block and send their
+//! votes to the node under test, which participates and distributes its own vote to all of them.
+
+use crate::{
+	dummy_builder,
+	environment::{TestEnvironment, TestEnvironmentDependencies, GENESIS_HASH},
+	mock::{
+		approval_voting_parallel::MockApprovalVotingParallel,
+		authority_discovery::MockAuthorityDiscovery,
+		availability_recovery::MockAvailabilityRecovery,
+		candidate_validation::MockCandidateValidation,
+		chain_api::{ChainApiState, MockChainApi},
+		network_bridge::{MockNetworkBridgeRx, MockNetworkBridgeTx},
+		runtime_api::{MockRuntimeApi, MockRuntimeApiCoreState},
+		AlwaysSupportsParachains,
+	},
+	network::{new_network, NetworkEmulatorHandle, NetworkInterface, NetworkInterfaceReceiver},
+	usage::BenchmarkUsage,
+	NODE_UNDER_TEST,
+};
+use codec::Encode;
+use colored::Colorize;
+use futures::channel::oneshot;
+use itertools::Itertools;
+use polkadot_dispute_distribution::DisputeDistributionSubsystem;
+use polkadot_node_core_dispute_coordinator::{Config, DisputeCoordinatorSubsystem};
+use polkadot_node_metrics::metrics::Metrics;
+use polkadot_node_network_protocol::request_response::{IncomingRequest, ReqProtocolNames};
+use polkadot_node_subsystem_util::metrics::prometheus::{self, Counter, U64};
+use polkadot_overseer::{
+	Handle as OverseerHandle, Overseer, OverseerConnector, OverseerMetrics, SpawnGlue,
+};
+use polkadot_primitives::{AuthorityDiscoveryId, Block, Hash, ValidatorId, ValidatorIndex};
+use sc_keystore::LocalKeystore;
+use sc_network::request_responses::{IncomingRequest as RawIncomingRequest, ProtocolConfig};
+use sc_service::SpawnTaskHandle;
+use serde::{Deserialize, Serialize};
+use sp_keystore::Keystore;
+use sp_runtime::RuntimeAppPublic;
+use std::{
+	ops::Sub,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+pub use test_state::TestState;
+
+mod test_state;
+
+const LOG_TARGET: &str = "subsystem-bench::disputes";
+
+// Maximum time to wait for all emulated peers to receive our votes on disputes of a block.
+// Votes that are not received in time are reported by the
+// `subsystem_benchmark_disputes_unconfirmed_votes` metric.
+const MAX_VOTES_DISTRIBUTION_TIME: Duration = Duration::from_secs(60);
+
+/// Parameters specific to the disputes benchmark
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct DisputesOptions {
+	#[clap(short, long, default_value_t = 1)]
+	/// The number of included candidates disputed at every block, at most `n_cores`.
+	pub disputes_per_block: usize,
+	#[clap(short, long, default_value_t = 2)]
+	/// The number of emulated validators voting on each dispute, including the one raising it.
+	pub votes_per_dispute: usize,
+}
+
+fn build_overseer(
+	state: &TestState,
+	network: NetworkEmulatorHandle,
+	network_interface: NetworkInterface,
+	network_receiver: NetworkInterfaceReceiver,
+	dependencies: &TestEnvironmentDependencies,
+) -> (Overseer<SpawnGlue<SpawnTaskHandle>, AlwaysSupportsParachains>, OverseerHandle) {
+	let overseer_connector = OverseerConnector::with_event_capacity(64000);
+	let overseer_metrics = OverseerMetrics::try_register(&dependencies.registry).unwrap();
+	let spawn_task_handle = dependencies.task_manager.spawn_handle();
+
+	let db = kvdb_memorydb::create(1);
+	let db: polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter<kvdb_memorydb::InMemory> =
+		polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[]);
+	let keystore = LocalKeystore::in_memory();
+	let node_seed = state.test_authorities.key_seeds.get(NODE_UNDER_TEST as usize).unwrap();
+	keystore
+		.sr25519_generate_new(ValidatorId::ID, Some(node_seed.as_str()))
+		.expect("Insert key into keystore");
+	keystore
+		.sr25519_generate_new(AuthorityDiscoveryId::ID, Some(node_seed.as_str()))
+		.expect("Insert key into keystore");
+	let keystore = Arc::new(keystore);
+
+	let mock_runtime_api = MockRuntimeApi::new(
+		state.config.clone(),
+		state.test_authorities.clone(),
+		state.candidate_receipts.clone(),
+		state.candidate_events.clone(),
+		Default::default(),
+		0,
+		MockRuntimeApiCoreState::Occupied,
+	);
+	let chain_api_state = ChainApiState { block_headers: state.block_headers.clone() };
+	let mock_chain_api = MockChainApi::new(chain_api_state);
+	let (dispute_req_receiver, dispute_req_cfg) = IncomingRequest::get_config_receiver::<
+		Block,
+		sc_network::NetworkWorker<Block, Hash>,
+	>(&ReqProtocolNames::new(GENESIS_HASH, None));
+	let dispute_coordinator = DisputeCoordinatorSubsystem::new(
+		Arc::new(db),
+		Config { col_dispute_data: 0 },
+		keystore.clone(),
+		Metrics::try_register(&dependencies.registry).unwrap(),
+		true,
+	);
+	let dispute_distribution = DisputeDistributionSubsystem::new(
+		keystore,
+		dispute_req_receiver,
+		MockAuthorityDiscovery::new(&state.test_authorities),
+		Metrics::try_register(&dependencies.registry).unwrap(),
+	);
+	let network_bridge_tx = MockNetworkBridgeTx::new(
+		network,
+		network_interface.subsystem_sender(),
+		state.test_authorities.clone(),
+	);
+	let network_bridge_rx = MockNetworkBridgeRx::new(network_receiver, Some(dispute_req_cfg), true);
+
+	let dummy = dummy_builder!(spawn_task_handle, overseer_metrics)
+		.replace_runtime_api(|_| mock_runtime_api)
+		.replace_chain_api(|_| mock_chain_api)
+		.replace_availability_recovery(|_| MockAvailabilityRecovery::new())
+		.replace_candidate_validation(|_| MockCandidateValidation::new())
+		.replace_approval_voting_parallel(|_| MockApprovalVotingParallel::new())
+		.replace_dispute_coordinator(|_| dispute_coordinator)
+		.replace_dispute_distribution(|_| dispute_distribution)
+		.replace_network_bridge_tx(|_| network_bridge_tx)
+		.replace_network_bridge_rx(|_| network_bridge_rx);
+	let (overseer, raw_handle) = dummy.build_with_connector(overseer_connector).unwrap();
+	let overseer_handle = OverseerHandle::new(raw_handle);
+
+	(overseer, overseer_handle)
+}
+
+pub fn prepare_test(state: &TestState, with_prometheus_endpoint: bool) -> TestEnvironment {
+	let dependencies = TestEnvironmentDependencies::default();
+	let (network, network_interface, network_receiver) = new_network(
+		&state.config,
+		&dependencies,
+		&state.test_authorities,
+		vec![Arc::new(state.clone())],
+	);
+	let (overseer, overseer_handle) =
+		build_overseer(state, network.clone(), network_interface, network_receiver, &dependencies);
+
+	TestEnvironment::new(
+		dependencies,
+		state.config.clone(),
+		network,
+		overseer,
+		overseer_handle,
+		state.test_authorities.clone(),
+		with_prometheus_endpoint,
+	)
+}
+
+pub async fn benchmark_disputes(env: &mut TestEnvironment, state: &TestState) -> BenchmarkUsage {
+	state.reset_trackers();
+
+	let config = env.config().clone();
+	env.metrics().set_n_validators(config.n_validators);
+	env.metrics().set_n_cores(config.n_cores);
+
+	let unconfirmed_votes = prometheus::register(
+		Counter::<U64>::new(
+			"subsystem_benchmark_disputes_unconfirmed_votes",
+			"Number of our votes that reachable emulated peers have not received in time",
+		)
+		.expect("metric is valid"),
+		env.registry(),
+	)
+	.expect("metric is registered once");
+
+	let connected_validators = connected_validators(env, state);
+	assert!(connected_validators.len() >= 2, "At least two validators are needed to dispute");
+	let dispute_requests = state.generate_dispute_requests(&connected_validators);
+
+	let test_start = Instant::now();
+	for block_info in state.block_infos.iter() {
+		let block_num = block_info.number as usize;
+		gum::info!(target: LOG_TARGET, "Current block {}/{} {:?}", block_num, config.num_blocks, block_info.hash);
+		env.metrics().set_current_block(block_num);
+
+		let block_start_ts = Instant::now();
+		env.import_block(block_info.clone()).await;
+
+		let requests = dispute_requests.get(&block_info.hash).expect("pregenerated");
+		let receivers = requests
+			.iter()
+			.filter_map(|(validator_index, request)| {
+				let (pending_response, pending_response_receiver) = oneshot::channel();
+				let index = validator_index.0 as usize;
+				let request = RawIncomingRequest {
+					peer: state.test_authorities.peer_ids[index],
+					payload: request.encode(),
+					pending_response,
+				};
+				let peer = &state.test_authorities.validator_authority_id[index];
+				env.network()
+					.send_request_from_peer(peer, request)
+					.ok()
+					.map(|_| pending_response_receiver)
+			})
+			.collect_vec();
+
+		// Requests exceeding the rate limit of dispute-distribution are dropped, so not all of
+		// them are confirmed.
+		let confirmed = futures::future::join_all(receivers)
+			.await
+			.into_iter()
+			.filter(|response| response.as_ref().map_or(false, |r| r.result.is_ok()))
+			.count();
+		gum::info!(target: LOG_TARGET, "{}/{} dispute requests confirmed", confirmed, requests.len());
+
+		gum::info!(target: LOG_TARGET, "Waiting for all emulated peers to receive our votes ...");
+		let votes_deadline = Instant::now() + MAX_VOTES_DISTRIBUTION_TIME;
+		loop {
+			// Peers may disconnect while the test is running, so only the ones reachable right now
+			// are expected to receive our votes.
+			let reachable_validators = connected_validators(env, state);
+			let missing = state.votes_missing(&block_info.hash, &reachable_validators);
+			if missing == 0 {
+				break
+			}
+			if Instant::now() > votes_deadline {
+				gum::warn!(
+					target: LOG_TARGET,
+					"{} of our votes have not been received by reachable emulated peers in {}ms",
+					missing,
+					MAX_VOTES_DISTRIBUTION_TIME.as_millis()
+				);
+				unconfirmed_votes.inc_by(missing as u64);
+				break
+			}
+			tokio::time::sleep(Duration::from_millis(50)).await;
+		}
+
+		let block_time = Instant::now().sub(block_start_ts).as_millis() as u64;
+		env.metrics().set_block_time(block_time);
+		gum::info!(target: LOG_TARGET, "All disputes of the block handled in {}", format!("{:?}ms", block_time).cyan());
+	}
+
+	let duration: u128 = test_start.elapsed().as_millis();
+	gum::info!(target: LOG_TARGET, "All blocks processed in {}", format!("{:?}ms", duration).cyan());
+	gum::info!(target: LOG_TARGET,
+		"Avg block time: {}",
+		format!("{} ms", test_start.elapsed().as_millis() / env.config().num_blocks as u128).red()
+	);
+
+	gum::info!(target: LOG_TARGET, "Unconfirmed votes: {}", unconfirmed_votes.get());
+
+	env.stop().await;
+	env.collect_resource_usage(&["dispute-coordinator", "dispute-distribution"], false)
+}
+
+/// Returns the emulated validators that are currently connected to the node under test.
+fn connected_validators(env: &TestEnvironment, state: &TestState) -> Vec<ValidatorIndex> {
+	state
+		.test_authorities
+		.validator_authority_id
+		.iter()
+		.enumerate()
+		.filter(|(i, id)| *i != NODE_UNDER_TEST as usize && env.network().is_peer_connected(id))
+		.map(|(i, _)| ValidatorIndex(i as u32))
+		.collect_vec()
+}
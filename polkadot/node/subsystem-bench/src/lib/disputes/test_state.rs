@@ -0,0 +1,295 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	configuration::{TestAuthorities, TestConfiguration},
+	disputes::DisputesOptions,
+	mock::runtime_api::session_info_for_peers,
+	network::{HandleNetworkMessage, NetworkMessage},
+};
+use codec::Encode;
+use itertools::Itertools;
+use polkadot_node_network_protocol::request_response::{
+	v1::{DisputeRequest, DisputeResponse},
+	Requests,
+};
+use polkadot_node_primitives::{DisputeMessage, SignedDisputeStatement};
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	vstaging::{CandidateEvent, CandidateReceiptV2 as CandidateReceipt, MutateDescriptorV2},
+	BlockNumber, CandidateHash, CoreIndex, DisputeStatement, GroupIndex, Hash, Header, Id,
+	InvalidDisputeStatementKind, SessionIndex, SessionInfo, ValidDisputeStatementKind,
+	ValidatorIndex,
+};
+use polkadot_primitives_test_helpers::{
+	dummy_committed_candidate_receipt_v2, dummy_hash, dummy_head_data,
+};
+use sc_network::ProtocolName;
+use sp_core::{Pair, H256};
+use std::{
+	collections::HashMap,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
+
+const SESSION_INDEX: SessionIndex = 0;
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Dispute specific options
+	pub options: DisputesOptions,
+	// Authority keys for the network emulation.
+	pub test_authorities: TestAuthorities,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers
+	pub block_headers: HashMap<H256, Header>,
+	// Session info
+	pub session_info: SessionInfo,
+	// Candidates included at each block
+	pub candidate_receipts: HashMap<H256, Vec<CandidateReceipt>>,
+	// Inclusion events for the candidates at each block
+	pub candidate_events: HashMap<H256, Vec<CandidateEvent>>,
+	// Candidates disputed at each block
+	pub disputed_candidates: HashMap<H256, Vec<CandidateReceipt>>,
+	// Tracks which validators received the vote of the node under test for a disputed candidate
+	pub votes_tracker: HashMap<CandidateHash, Vec<Arc<AtomicBool>>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &DisputesOptions) -> Self {
+		assert!(
+			options.disputes_per_block <= config.n_cores,
+			"Only included candidates can be disputed, at most `n_cores` per block"
+		);
+
+		let test_authorities = config.generate_authorities();
+		let session_info = session_info_for_peers(config, &test_authorities);
+		let block_infos: Vec<BlockInfo> =
+			(1..=config.num_blocks).map(generate_block_info).collect();
+		let block_headers = block_infos.iter().map(generate_block_header).collect();
+
+		let mut state = Self {
+			config: config.clone(),
+			options: options.clone(),
+			test_authorities,
+			block_infos,
+			block_headers,
+			session_info,
+			candidate_receipts: Default::default(),
+			candidate_events: Default::default(),
+			disputed_candidates: Default::default(),
+			votes_tracker: Default::default(),
+		};
+
+		for block_info in state.block_infos.iter() {
+			for core_idx in 0..config.n_cores {
+				let mut receipt = dummy_committed_candidate_receipt_v2(dummy_hash());
+				receipt.descriptor.set_para_id(Id::new(core_idx as u32 + 1));
+				receipt.descriptor.set_relay_parent(block_info.hash);
+				receipt.descriptor.set_core_index(CoreIndex(core_idx as u32));
+				receipt.descriptor.set_session_index(SESSION_INDEX);
+				let receipt = receipt.to_plain();
+
+				state.candidate_events.entry(block_info.hash).or_default().push(
+					CandidateEvent::CandidateIncluded(
+						receipt.clone(),
+						dummy_head_data(),
+						CoreIndex(core_idx as u32),
+						GroupIndex(core_idx as u32),
+					),
+				);
+				if core_idx < options.disputes_per_block {
+					state.votes_tracker.insert(
+						receipt.hash(),
+						(0..config.n_validators)
+							.map(|_| Arc::new(AtomicBool::new(false)))
+							.collect(),
+					);
+					state
+						.disputed_candidates
+						.entry(block_info.hash)
+						.or_default()
+						.push(receipt.clone());
+				}
+				state.candidate_receipts.entry(block_info.hash).or_default().push(receipt);
+			}
+		}
+
+		state
+	}
+
+	/// Generates the dispute requests sent by the emulated peers at each block.
+	///
+	/// The first of the `votes_per_dispute` voters raises the dispute with an invalid vote, the
+	/// others send their own valid vote along with it. Voters are picked round-robin from the
+	/// `connected_validators`, so that the load is spread across peers.
+	pub fn generate_dispute_requests(
+		&self,
+		connected_validators: &[ValidatorIndex],
+	) -> HashMap<H256, Vec<(ValidatorIndex, DisputeRequest)>> {
+		let n_voters = self.options.votes_per_dispute.clamp(2, connected_validators.len());
+		let mut offset = 0;
+
+		self.block_infos
+			.iter()
+			.map(|block_info| {
+				let requests = self
+					.disputed_candidates
+					.get(&block_info.hash)
+					.expect("Disputes are generated for every block")
+					.iter()
+					.flat_map(|receipt| {
+						let voters = connected_validators
+							.iter()
+							.cycle()
+							.skip(offset)
+							.take(n_voters)
+							.cloned()
+							.collect_vec();
+						offset = (offset + n_voters) % connected_validators.len();
+
+						let invalid_vote = self.sign_dispute_statement(
+							receipt.hash(),
+							voters[0],
+							DisputeStatement::Invalid(InvalidDisputeStatementKind::Explicit),
+						);
+						voters
+							.iter()
+							.enumerate()
+							.map(|(index, &voter)| {
+								// The initiator is backed by the valid vote of the next voter.
+								let valid_voter = if index == 0 { voters[1] } else { voter };
+								let valid_vote = self.sign_dispute_statement(
+									receipt.hash(),
+									valid_voter,
+									DisputeStatement::Valid(ValidDisputeStatementKind::Explicit),
+								);
+								let message = DisputeMessage::from_signed_statements(
+									valid_vote,
+									valid_voter,
+									invalid_vote.clone(),
+									voters[0],
+									receipt.clone(),
+									&self.session_info,
+								)
+								.expect("Votes are generated for the right session");
+								(voter, DisputeRequest::from(message))
+							})
+							.collect_vec()
+					})
+					.collect_vec();
+				(block_info.hash, requests)
+			})
+			.collect()
+	}
+
+	/// Returns the number of votes of the node under test on the candidates disputed at
+	/// `block_hash` that have not been received by `validators` yet.
+	pub fn votes_missing(&self, block_hash: &H256, validators: &[ValidatorIndex]) -> usize {
+		self.disputed_candidates
+			.get(block_hash)
+			.expect("Disputes are generated for every block")
+			.iter()
+			.map(|receipt| {
+				let tracker = self.votes_tracker.get(&receipt.hash()).expect("pregenerated");
+				validators
+					.iter()
+					.filter(|v| !tracker[v.0 as usize].load(Ordering::SeqCst))
+					.count()
+			})
+			.sum()
+	}
+
+	pub fn reset_trackers(&self) {
+		self.votes_tracker
+			.values()
+			.flatten()
+			.for_each(|v| v.as_ref().store(false, Ordering::SeqCst));
+	}
+
+	fn sign_dispute_statement(
+		&self,
+		candidate_hash: CandidateHash,
+		validator_index: ValidatorIndex,
+		statement: DisputeStatement,
+	) -> SignedDisputeStatement {
+		let pair = &self.test_authorities.validator_pairs[validator_index.0 as usize];
+		let payload = statement
+			.payload_data(candidate_hash, SESSION_INDEX)
+			.expect("Explicit statements always have a payload");
+
+		SignedDisputeStatement::new_unchecked_from_trusted_source(
+			statement,
+			candidate_hash,
+			SESSION_INDEX,
+			pair.public(),
+			pair.sign(&payload[..]),
+		)
+	}
+}
+
+fn generate_block_info(block_num: usize) -> BlockInfo {
+	new_block_import_info(Hash::repeat_byte(block_num as u8), block_num as BlockNumber)
+}
+
+fn generate_block_header(info: &BlockInfo) -> (H256, Header) {
+	(
+		info.hash,
+		Header {
+			digest: Default::default(),
+			number: info.number,
+			parent_hash: info.parent_hash,
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+#[async_trait::async_trait]
+impl HandleNetworkMessage for TestState {
+	async fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut futures::channel::mpsc::UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::RequestFromNode(authority_id, Requests::DisputeSendingV1(req)) => {
+				let index = self
+					.test_authorities
+					.validator_authority_id
+					.iter()
+					.position(|v| v == &authority_id)
+					.unwrap();
+				let candidate_hash = req.payload.0.candidate_receipt.hash();
+				if let Some(tracker) = self.votes_tracker.get(&candidate_hash) {
+					tracker[index].store(true, Ordering::SeqCst);
+				}
+
+				let _ = req
+					.pending_response
+					.send(Ok((DisputeResponse::Confirmed.encode(), ProtocolName::from(""))));
+				None
+			},
+			_ => Some(message),
+		}
+	}
+}
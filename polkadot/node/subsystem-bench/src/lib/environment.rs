@@ -306,6 +306,7 @@ impl TestEnvironment {
 
 	/// Send an `ActiveLeavesUpdate` signal to all subsystems under test.
 	pub async fn import_block(&mut self, block: BlockInfo) {
+		self.network.on_block_imported(block.number);
 		self.overseer_handle
 			.block_imported(block)
 			.timeout(MAX_TIME_OF_FLIGHT)
@@ -18,6 +18,8 @@ use polkadot_node_subsystem::HeadSupportsParachains;
 use polkadot_node_subsystem_types::Hash;
 use sp_consensus::SyncOracle;
 
+pub mod approval_voting_parallel;
+pub mod authority_discovery;
 pub mod av_store;
 pub mod availability_recovery;
 pub mod candidate_backing;
@@ -21,13 +21,28 @@ use polkadot_node_subsystem::{
 	messages::ProspectiveParachainsMessage, overseer, SpawnedSubsystem, SubsystemError,
 };
 use polkadot_node_subsystem_types::OverseerSignal;
-use polkadot_primitives::Hash;
+use polkadot_primitives::{BlockNumber, Hash, Id as ParaId, PersistedValidationData};
+use std::collections::HashMap;
 
-pub struct MockProspectiveParachains {}
+pub struct MockProspectiveParachains {
+	// Minimum relay parents of the paras, per leaf
+	minimum_relay_parents: HashMap<Hash, Vec<(ParaId, BlockNumber)>>,
+	// Validation data served for every candidate
+	pvd: Option<PersistedValidationData>,
+}
 
 impl MockProspectiveParachains {
 	pub fn new() -> Self {
-		Self {}
+		Self { minimum_relay_parents: Default::default(), pvd: None }
+	}
+
+	/// A mock which knows the minimum relay parents of the paras at each leaf and serves `pvd`
+	/// as the prospective validation data of all candidates.
+	pub fn with_validation_data(
+		minimum_relay_parents: HashMap<Hash, Vec<(ParaId, BlockNumber)>>,
+		pvd: PersistedValidationData,
+	) -> Self {
+		Self { minimum_relay_parents, pvd: Some(pvd) }
 	}
 }
 
@@ -51,8 +66,17 @@ impl MockProspectiveParachains {
 						return
 					},
 				orchestra::FromOrchestra::Communication { msg } => match msg {
-					ProspectiveParachainsMessage::GetMinimumRelayParents(_relay_parent, tx) => {
-						tx.send(vec![]).unwrap();
+					ProspectiveParachainsMessage::GetMinimumRelayParents(relay_parent, tx) => {
+						tx.send(
+							self.minimum_relay_parents
+								.get(&relay_parent)
+								.cloned()
+								.unwrap_or_default(),
+						)
+						.unwrap();
+					},
+					ProspectiveParachainsMessage::GetProspectiveValidationData(_req, tx) => {
+						tx.send(self.pvd.clone()).unwrap();
 					},
 					ProspectiveParachainsMessage::GetHypotheticalMembership(req, tx) => {
 						tx.send(
@@ -34,8 +34,9 @@
 //     Subsystems under test
 
 use crate::{
-	configuration::{random_latency, TestAuthorities, TestConfiguration},
+	configuration::{sample_latency, TestAuthorities, TestConfiguration},
 	environment::TestEnvironmentDependencies,
+	network_profile::{NetworkProfile, PacketLoss},
 	NODE_UNDER_TEST,
 };
 use codec::Encode;
@@ -63,21 +64,25 @@ use polkadot_node_subsystem_util::metrics::prometheus::{
 	self, CounterVec, Opts, PrometheusError, Registry,
 };
 use polkadot_overseer::AllMessages;
-use polkadot_primitives::AuthorityDiscoveryId;
+use polkadot_primitives::{AuthorityDiscoveryId, BlockNumber};
 use prometheus_endpoint::U64;
-use rand::{seq::SliceRandom, thread_rng};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use sc_network::{
 	request_responses::{IncomingRequest, OutgoingResponse},
-	RequestFailure,
+	OutboundFailure, RequestFailure,
 };
 use sc_network_types::PeerId;
 use sc_service::SpawnTaskHandle;
 use std::{
 	collections::HashMap,
-	sync::Arc,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
 	task::Poll,
 	time::{Duration, Instant},
 };
+use tokio::sync::Notify;
 
 const LOG_TARGET: &str = "subsystem-bench::network";
 
@@ -430,6 +435,52 @@ impl NetworkInterface {
 	}
 }
 
+/// Tracks whether an emulated peer can be reached by the node under test.
+///
+/// A peer can be made unreachable by several overlapping network profile events, it is reachable
+/// again once all of them have ended.
+#[derive(Clone, Default)]
+pub struct Reachability(Arc<ReachabilityInner>);
+
+#[derive(Default)]
+struct ReachabilityInner {
+	/// Number of ongoing events which made the peer unreachable.
+	cuts: AtomicUsize,
+	/// Notified when the peer becomes reachable again.
+	restored: Notify,
+}
+
+impl Reachability {
+	/// Returns true if the peer can be reached.
+	pub fn is_reachable(&self) -> bool {
+		self.0.cuts.load(Ordering::SeqCst) == 0
+	}
+
+	/// Makes the peer unreachable until `restore` is called.
+	pub fn cut(&self) {
+		self.0.cuts.fetch_add(1, Ordering::SeqCst);
+	}
+
+	/// Ends one of the events which made the peer unreachable.
+	pub fn restore(&self) {
+		if self.0.cuts.fetch_sub(1, Ordering::SeqCst) == 1 {
+			self.0.restored.notify_waiters();
+		}
+	}
+
+	/// Waits until the peer is reachable.
+	pub async fn wait(&self) {
+		loop {
+			// Registered before the check, so a concurrent `restore` can't be missed.
+			let restored = self.0.restored.notified();
+			if self.is_reachable() {
+				return
+			}
+			restored.await;
+		}
+	}
+}
+
 /// A handle for controlling an emulated peer.
 #[derive(Clone)]
 pub struct EmulatedPeerHandle {
@@ -439,6 +490,7 @@ pub struct EmulatedPeerHandle {
 	actions_tx: UnboundedSender<NetworkMessage>,
 	peer_id: PeerId,
 	authority_id: AuthorityDiscoveryId,
+	reachability: Reachability,
 }
 
 impl EmulatedPeerHandle {
@@ -469,18 +521,30 @@ struct EmulatedPeer {
 	tx_limiter: RateLimit,
 	rx_limiter: RateLimit,
 	latency_ms: usize,
+	reachability: Reachability,
+	packet_loss: Option<PacketLoss>,
 }
 
 impl EmulatedPeer {
 	/// Send a message to the node.
+	///
+	/// Messages sent while the peer is unreachable are lost, and so are requests: the node under
+	/// test never answers them.
 	pub async fn send_message(&mut self, message: NetworkMessage) {
+		if !self.reachability.is_reachable() {
+			gum::trace!(target: LOG_TARGET, "Dropping message to the node, peer is unreachable");
+			return
+		}
 		self.tx_limiter.reap(message.size()).await;
 
-		if self.latency_ms == 0 {
+		let retransmission_delay =
+			self.packet_loss.as_mut().and_then(|packet_loss| packet_loss.sample());
+		if self.latency_ms == 0 && retransmission_delay.is_none() {
 			self.to_node.unbounded_send(message).expect("Sending to the node never fails");
 		} else {
 			let to_node = self.to_node.clone();
-			let latency_ms = std::time::Duration::from_millis(self.latency_ms as u64);
+			let latency_ms = std::time::Duration::from_millis(self.latency_ms as u64) +
+				retransmission_delay.unwrap_or_default();
 
 			// Emulate RTT latency
 			self.spawn_handle
@@ -495,6 +559,11 @@ impl EmulatedPeer {
 	pub fn rx_limiter(&mut self) -> &mut RateLimit {
 		&mut self.rx_limiter
 	}
+
+	/// Returns the reachability of the peer.
+	pub fn reachability(&self) -> &Reachability {
+		&self.reachability
+	}
 }
 
 /// Interceptor pattern for handling messages.
@@ -545,6 +614,21 @@ async fn emulated_peer_loop(
 				if let Some(peer_message) = maybe_peer_message {
 					let size = peer_message.size();
 
+					// Messages sent to an unreachable peer are lost, while requests time out.
+					if !emulated_peer.reachability().is_reachable() {
+						match peer_message {
+							NetworkMessage::RequestFromNode(_, request) => {
+								let _ = request
+									.into_response_sender()
+									.send(Err(RequestFailure::Network(OutboundFailure::Timeout)));
+							},
+							message => {
+								gum::trace!(target: LOG_TARGET, peer = ?message.peer(), "Dropping message from the node, peer is unreachable");
+							},
+						}
+						continue
+					}
+
 					emulated_peer.rx_limiter().reap(size).await;
 					stats.inc_received(size);
 
@@ -595,6 +679,11 @@ async fn emulated_peer_loop(
 							let bytes = result.encoded_size();
 							gum::trace!(target: LOG_TARGET, size = bytes, "Peer proxied request completed");
 
+							// The response of the node never reaches an unreachable peer.
+							if !emulated_peer.reachability().is_reachable() {
+								continue
+							}
+
 							emulated_peer.rx_limiter().reap(bytes).await;
 							stats.inc_received(bytes);
 
@@ -625,6 +714,7 @@ pub fn new_peer(
 	stats: Arc<PeerEmulatorStats>,
 	to_network_interface: UnboundedSender<NetworkMessage>,
 	latency_ms: usize,
+	packet_loss: Option<PacketLoss>,
 	peer_id: PeerId,
 	authority_id: AuthorityDiscoveryId,
 ) -> EmulatedPeerHandle {
@@ -639,7 +729,10 @@ pub fn new_peer(
 		tx_limiter,
 		to_node: to_network_interface.clone(),
 		latency_ms,
+		reachability: Reachability::default(),
+		packet_loss,
 	};
+	let reachability = emulated_peer.reachability.clone();
 
 	spawn_task_handle.clone().spawn(
 		"peer-emulator",
@@ -655,7 +748,7 @@ pub fn new_peer(
 		.boxed(),
 	);
 
-	EmulatedPeerHandle { messages_tx, actions_tx, peer_id, authority_id }
+	EmulatedPeerHandle { messages_tx, actions_tx, peer_id, authority_id, reachability }
 }
 
 /// Book keeping of sent and received bytes.
@@ -732,6 +825,10 @@ impl Peer {
 			Peer::Connected(handle) | Peer::Disconnected(handle) => handle.peer_id,
 		}
 	}
+
+	pub fn reachability(&self) -> &Reachability {
+		&self.handle().reachability
+	}
 }
 
 /// A ha emulated network implementation.
@@ -743,6 +840,17 @@ pub struct NetworkEmulatorHandle {
 	stats: Vec<Arc<PeerEmulatorStats>>,
 	/// Each emulated peer is a validator.
 	validator_authority_ids: HashMap<AuthorityDiscoveryId, usize>,
+	/// Churn and partitions of the network profile, if any.
+	profile_emulation: Option<Arc<ProfileEmulation>>,
+}
+
+/// Emulation of the peers made unreachable by a network profile.
+struct ProfileEmulation {
+	profile: NetworkProfile,
+	/// Indices of the peers connected to the node under test.
+	connected: Vec<usize>,
+	/// Number of events of the profile which made each peer unreachable.
+	cuts: std::sync::Mutex<HashMap<usize, usize>>,
 }
 
 impl NetworkEmulatorHandle {
@@ -796,6 +904,14 @@ pub fn new_network(
 		Metrics::new(&dependencies.registry).expect("Metrics always register successfully");
 	let mut validator_authority_id_mapping = HashMap::new();
 
+	let profile = config.network_profile.as_ref();
+	if let Some(Err(e)) = profile.map(|profile| profile.validate()) {
+		panic!("Invalid network profile: {}", e)
+	}
+	// All random choices are seeded by the network profile, if any.
+	let mut rng = profile.map_or_else(StdRng::from_entropy, |profile| profile.rng(0));
+	let regions = profile.map(|profile| profile.peer_regions(n_peers));
+
 	// Create the channel from `peer` to `NetworkInterface` .
 	let (to_network_interface, from_network) = mpsc::unbounded();
 
@@ -813,7 +929,12 @@ pub fn new_network(
 					handlers.clone(),
 					stats,
 					to_network_interface.clone(),
-					random_latency(config.latency.as_ref()),
+					match (profile, regions.as_ref()) {
+						(Some(profile), Some(regions)) =>
+							profile.sample_peer_latency(config, regions[peer_index], &mut rng),
+						_ => sample_latency(config.latency.as_ref(), &mut rng),
+					},
+					profile.and_then(|profile| profile.packet_loss(peer_index)),
 					*authorities.peer_ids.get(peer_index).unwrap(),
 					authority_id,
 				)),
@@ -824,8 +945,7 @@ pub fn new_network(
 	let connected_count = config.connected_count();
 
 	let mut peers_indices = (0..n_peers).collect_vec();
-	let (_connected, to_disconnect) =
-		peers_indices.partial_shuffle(&mut thread_rng(), connected_count);
+	let (_connected, to_disconnect) = peers_indices.partial_shuffle(&mut rng, connected_count);
 
 	// Node under test is always mark as disconnected.
 	peers[NODE_UNDER_TEST as usize].disconnect();
@@ -835,10 +955,15 @@ pub fn new_network(
 
 	gum::info!(target: LOG_TARGET, "{}",format!("Network created, connected validator count {}", connected_count).bright_black());
 
+	let profile_emulation = profile.map(|profile| {
+		let connected = (0..n_peers).filter(|index| peers[*index].is_connected()).collect_vec();
+		Arc::new(ProfileEmulation { profile: profile.clone(), connected, cuts: Default::default() })
+	});
 	let handle = NetworkEmulatorHandle {
 		peers,
 		stats,
 		validator_authority_ids: validator_authority_id_mapping,
+		profile_emulation,
 	};
 
 	// Finally create the `NetworkInterface` with the `from_network` receiver.
@@ -859,6 +984,27 @@ pub enum EmulatedPeerError {
 }
 
 impl NetworkEmulatorHandle {
+	/// Makes the peers churned or partitioned by the network profile at `block_number`
+	/// unreachable, and the other ones reachable again.
+	pub fn on_block_imported(&self, block_number: BlockNumber) {
+		let Some(emulation) = self.profile_emulation.as_ref() else { return };
+		let mut cuts = HashMap::new();
+		for peer in emulation.profile.unreachable_peers(&emulation.connected, block_number) {
+			*cuts.entry(peer).or_insert(0) += 1;
+		}
+
+		let mut previous_cuts = emulation.cuts.lock().expect("never poisoned; qed");
+		for peer in emulation.connected.iter() {
+			let previous = previous_cuts.get(peer).copied().unwrap_or_default();
+			let current = cuts.get(peer).copied().unwrap_or_default();
+			let reachability = self.peers[*peer].reachability();
+			(current..previous).for_each(|_| reachability.restore());
+			(previous..current).for_each(|_| reachability.cut());
+		}
+		gum::debug!(target: LOG_TARGET, block_number, "{} peers unreachable", cuts.len());
+		*previous_cuts = cuts;
+	}
+
 	/// Returns true if the emulated peer is connected to the node under test.
 	pub fn is_peer_connected(&self, peer: &AuthorityDiscoveryId) -> bool {
 		self.peer(peer).is_connected()
@@ -1030,8 +1176,15 @@ impl RequestExt for Requests {
 					None
 				}
 			},
+			Requests::DisputeSendingV1(request) => {
+				if let Recipient::Authority(authority_id) = &request.peer {
+					Some(authority_id)
+				} else {
+					None
+				}
+			},
 			// Requested by PeerId
-			Requests::AttestedCandidateV2(_) => None,
+			Requests::AttestedCandidateV2(_) | Requests::CollationFetchingV2(_) => None,
 			request => {
 				unimplemented!("RequestAuthority not implemented for {:?}", request)
 			},
@@ -1044,6 +1197,10 @@ impl RequestExt for Requests {
 				Recipient::Authority(_) => None,
 				Recipient::Peer(peer_id) => Some(peer_id),
 			},
+			Requests::CollationFetchingV2(request) => match &request.peer {
+				Recipient::Authority(_) => None,
+				Recipient::Peer(peer_id) => Some(peer_id),
+			},
 			request => {
 				unimplemented!("peer_id() is not implemented for {:?}", request)
 			},
@@ -1055,6 +1212,8 @@ impl RequestExt for Requests {
 			Requests::ChunkFetching(outgoing_request) => outgoing_request.pending_response,
 			Requests::AvailableDataFetchingV1(outgoing_request) =>
 				outgoing_request.pending_response,
+			Requests::DisputeSendingV1(outgoing_request) => outgoing_request.pending_response,
+			Requests::CollationFetchingV2(outgoing_request) => outgoing_request.pending_response,
 			_ => unimplemented!("unsupported request type"),
 		}
 	}
@@ -1068,6 +1227,10 @@ impl RequestExt for Requests {
 				std::mem::replace(&mut outgoing_request.pending_response, new_sender),
 			Requests::AttestedCandidateV2(outgoing_request) =>
 				std::mem::replace(&mut outgoing_request.pending_response, new_sender),
+			Requests::DisputeSendingV1(outgoing_request) =>
+				std::mem::replace(&mut outgoing_request.pending_response, new_sender),
+			Requests::CollationFetchingV2(outgoing_request) =>
+				std::mem::replace(&mut outgoing_request.pending_response, new_sender),
 			_ => unimplemented!("unsupported request type"),
 		}
 	}
@@ -1080,6 +1243,9 @@ impl RequestExt for Requests {
 				outgoing_request.payload.encoded_size(),
 			Requests::AttestedCandidateV2(outgoing_request) =>
 				outgoing_request.payload.encoded_size(),
+			Requests::DisputeSendingV1(outgoing_request) => outgoing_request.payload.encoded_size(),
+			Requests::CollationFetchingV2(outgoing_request) =>
+				outgoing_request.payload.encoded_size(),
 			_ => unimplemented!("received an unexpected request"),
 		}
 	}
@@ -1087,8 +1253,32 @@ impl RequestExt for Requests {
 
 #[cfg(test)]
 mod tests {
-	use super::RateLimit;
-	use std::time::Instant;
+	use super::{RateLimit, Reachability};
+	use futures::FutureExt;
+	use std::time::{Duration, Instant};
+
+	#[tokio::test]
+	async fn reachability_wait_resolves_when_all_cuts_are_restored() {
+		let reachability = Reachability::default();
+		assert!(reachability.wait().now_or_never().is_some());
+
+		reachability.cut();
+		reachability.cut();
+		let wait = tokio::spawn({
+			let reachability = reachability.clone();
+			async move { reachability.wait().await }
+		});
+
+		reachability.restore();
+		tokio::time::sleep(Duration::from_millis(50)).await;
+		assert!(!wait.is_finished());
+
+		reachability.restore();
+		tokio::time::timeout(Duration::from_secs(1), wait)
+			.await
+			.expect("peer is reachable again")
+			.unwrap();
+	}
 
 	#[tokio::test]
 	async fn test_expected_rate() {
@@ -0,0 +1,326 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Deterministic network emulation profiles.
+//!
+//! A [`NetworkProfile`] refines the emulated network of a [`TestConfiguration`] with regional
+//! latency clusters, packet loss, offline validators, peer churn and partitions. All random choices
+//! are derived from the profile seed, so the same profile always emulates the same network.
+
+use crate::configuration::{sample_latency, PeerLatency, TestConfiguration};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// Default TCP retransmission timeout in milliseconds.
+fn default_retransmission_timeout_ms() -> u64 {
+	200
+}
+
+/// A region validators are located in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Region {
+	/// The region name, used to look up the inter-region latencies.
+	pub name: String,
+	/// The percentage of validators located in the region.
+	pub share: usize,
+	/// The latency between peers of the region. Defaults to the `latency` of the test
+	/// configuration.
+	#[serde(default)]
+	pub latency: Option<PeerLatency>,
+}
+
+/// The latency between two regions.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InterRegionLatency {
+	/// The name of the first region.
+	pub from: String,
+	/// The name of the second region.
+	pub to: String,
+	/// The round trip time between the regions.
+	pub latency: PeerLatency,
+}
+
+/// Peers repeatedly going offline and coming back.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PeerChurn {
+	/// The percentage of connected peers that are offline at any time.
+	pub percentage: usize,
+	/// How often a new set of peers goes offline, in blocks.
+	pub period_blocks: u32,
+}
+
+/// A period of time the node under test can't reach some of its peers.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Partition {
+	/// The first block of the partition.
+	pub start_block: u32,
+	/// How long the partition lasts, in blocks.
+	pub duration_blocks: u32,
+	/// The percentage of connected peers on the other side of the partition.
+	pub percentage: usize,
+}
+
+/// Network emulation profile applied on top of the bandwidth, latency and connectivity of the test
+/// configuration.
+///
+/// Unreachable peers, either churned or partitioned, keep their connection to the node under
+/// test, but the messages exchanged with them are lost and the requests sent to them time out, as
+/// if their route to the node was broken. Churn and partitions follow the blocks imported by the
+/// test, so they do not depend on how fast the test runs.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NetworkProfile {
+	/// The seed of all random choices of the network emulation.
+	#[serde(default)]
+	pub seed: u64,
+	/// The regions validators are spread across. The node under test is always in the first
+	/// region, validators not covered by the region shares are in the first region too.
+	#[serde(default)]
+	pub regions: Vec<Region>,
+	/// Round trip times between regions. Missing pairs use the `latency` of the test
+	/// configuration.
+	#[serde(default)]
+	pub inter_region_latency: Vec<InterRegionLatency>,
+	/// The percentage of messages that are lost and need to be retransmitted.
+	#[serde(default)]
+	pub packet_loss: f64,
+	/// The delay added to the lost messages.
+	#[serde(default = "default_retransmission_timeout_ms")]
+	pub retransmission_timeout_ms: u64,
+	/// The percentage of validators which are offline for the whole test.
+	#[serde(default)]
+	pub offline: usize,
+	/// Peers going offline and coming back during the test.
+	#[serde(default)]
+	pub churn: Option<PeerChurn>,
+	/// Partitions happening during the test.
+	#[serde(default)]
+	pub partitions: Vec<Partition>,
+}
+
+// Offset of the random streams used by the emulated peers, so they don't overlap the streams used
+// by the rest of the emulation.
+const PEER_STREAM_OFFSET: u64 = 1 << 32;
+
+/// Packet loss emulation of a peer.
+pub struct PacketLoss {
+	probability: f64,
+	retransmission_timeout: Duration,
+	rng: StdRng,
+}
+
+impl PacketLoss {
+	/// Returns the extra delay of the next message, if it gets lost.
+	pub fn sample(&mut self) -> Option<Duration> {
+		self.rng.gen_bool(self.probability).then_some(self.retransmission_timeout)
+	}
+}
+
+impl NetworkProfile {
+	/// Returns a random number generator for the `stream` of random choices.
+	pub fn rng(&self, stream: u64) -> StdRng {
+		StdRng::seed_from_u64(self.seed.wrapping_add(stream))
+	}
+
+	/// Assigns each of the `n_peers` to a region, the node under test is always in the first one.
+	pub fn peer_regions(&self, n_peers: usize) -> Vec<usize> {
+		let mut regions = vec![0; n_peers];
+		let mut peers = (0..n_peers).filter(|peer| *peer != 0).collect::<Vec<_>>();
+		peers.shuffle(&mut self.rng(1));
+
+		let mut peers = peers.into_iter();
+		for (index, region) in self.regions.iter().enumerate().skip(1) {
+			let count = n_peers * region.share / 100;
+			for peer in peers.by_ref().take(count) {
+				regions[peer] = index;
+			}
+		}
+
+		regions
+	}
+
+	/// Samples the round trip time between the node under test and a peer in `region`.
+	pub fn sample_peer_latency(
+		&self,
+		config: &TestConfiguration,
+		region: usize,
+		rng: &mut impl Rng,
+	) -> usize {
+		let latency = if region == 0 {
+			self.regions.first().and_then(|region| region.latency.as_ref())
+		} else {
+			let (from, to) = (&self.regions[0].name, &self.regions[region].name);
+			self.inter_region_latency
+				.iter()
+				.find(|l| (&l.from, &l.to) == (from, to) || (&l.from, &l.to) == (to, from))
+				.map(|l| &l.latency)
+		};
+
+		sample_latency(latency.or(config.latency.as_ref()), rng)
+	}
+
+	/// Returns the packet loss emulation of the peer with `peer_index`, if any.
+	pub fn packet_loss(&self, peer_index: usize) -> Option<PacketLoss> {
+		(self.packet_loss > 0.0).then(|| PacketLoss {
+			probability: self.packet_loss / 100.0,
+			retransmission_timeout: Duration::from_millis(self.retransmission_timeout_ms),
+			rng: self.rng(PEER_STREAM_OFFSET + peer_index as u64),
+		})
+	}
+
+	/// Picks `percentage` of the `peers`, the `stream` identifies the random choice.
+	pub fn pick_peers(&self, peers: &[usize], percentage: usize, stream: u64) -> Vec<usize> {
+		peers
+			.choose_multiple(&mut self.rng(stream), peers.len() * percentage / 100)
+			.copied()
+			.collect()
+	}
+
+	/// Returns the `peers` made unreachable by churn and partitions at `block_number`, once for
+	/// every event they are affected by.
+	pub fn unreachable_peers(&self, peers: &[usize], block_number: u32) -> Vec<usize> {
+		let partitions = self
+			.partitions
+			.iter()
+			.enumerate()
+			.filter(|(_, partition)| {
+				let end = partition.start_block.saturating_add(partition.duration_blocks);
+				(partition.start_block..end).contains(&block_number)
+			})
+			.flat_map(|(index, partition)| {
+				self.pick_peers(peers, partition.percentage, 2 + index as u64)
+			});
+		let churn = self.churn.iter().flat_map(|churn| {
+			// Streams of the partitions come first.
+			let round = (block_number / churn.period_blocks) as u64;
+			self.pick_peers(peers, churn.percentage, 2 + self.partitions.len() as u64 + round)
+		});
+
+		partitions.chain(churn).collect()
+	}
+
+	/// Checks the profile is consistent.
+	pub fn validate(&self) -> Result<(), String> {
+		if self.regions.iter().map(|region| region.share).sum::<usize>() > 100 {
+			return Err("Region shares add up to more than 100%".into())
+		}
+		for latency in self.inter_region_latency.iter() {
+			for name in [&latency.from, &latency.to] {
+				if !self.regions.iter().any(|region| &region.name == name) {
+					return Err(format!("Unknown region {} in inter-region latencies", name))
+				}
+			}
+		}
+		if !(0.0..=100.0).contains(&self.packet_loss) {
+			return Err("Packet loss must be a percentage".into())
+		}
+		if self.offline > 100 ||
+			self.churn.as_ref().map_or(false, |churn| churn.percentage > 100) ||
+			self.partitions.iter().any(|partition| partition.percentage > 100)
+		{
+			return Err("Offline, churn and partition sizes must be percentages".into())
+		}
+		if self.churn.as_ref().map_or(false, |churn| churn.period_blocks == 0) {
+			return Err("Churn period must not be zero".into())
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn three_continents() -> NetworkProfile {
+		NetworkProfile {
+			seed: 42,
+			regions: ["eu", "us", "asia"]
+				.into_iter()
+				.map(|name| Region { name: name.into(), share: 33, latency: None })
+				.collect(),
+			inter_region_latency: vec![InterRegionLatency {
+				from: "us".into(),
+				to: "eu".into(),
+				latency: PeerLatency { mean_latency_ms: 100, std_dev: 0.0 },
+			}],
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn peer_regions_are_deterministic() {
+		let profile = three_continents();
+		let regions = profile.peer_regions(300);
+
+		assert_eq!(regions, profile.peer_regions(300));
+		assert_eq!(regions[0], 0);
+		assert_eq!(regions.iter().filter(|region| **region == 1).count(), 99);
+		assert_eq!(regions.iter().filter(|region| **region == 2).count(), 99);
+	}
+
+	#[test]
+	fn inter_region_latency_is_symmetric() {
+		let profile = three_continents();
+		let config = TestConfiguration { latency: None, ..Default::default() };
+		let mut rng = profile.rng(0);
+
+		assert_eq!(profile.sample_peer_latency(&config, 1, &mut rng), 100);
+		assert_eq!(profile.sample_peer_latency(&config, 2, &mut rng), 0);
+	}
+
+	#[test]
+	fn packet_loss_is_deterministic() {
+		let profile = NetworkProfile { packet_loss: 10.0, ..three_continents() };
+		let samples = |peer_index| {
+			let mut loss = profile.packet_loss(peer_index).unwrap();
+			(0..1000).map(|_| loss.sample().is_some()).collect::<Vec<_>>()
+		};
+
+		assert_eq!(samples(1), samples(1));
+		assert_ne!(samples(1), samples(2));
+		assert!((50..150).contains(&samples(1).into_iter().filter(|lost| *lost).count()));
+	}
+
+	#[test]
+	fn unreachable_peers_follow_blocks() {
+		let profile = NetworkProfile {
+			churn: Some(PeerChurn { percentage: 10, period_blocks: 2 }),
+			partitions: vec![Partition { start_block: 3, duration_blocks: 2, percentage: 50 }],
+			..three_continents()
+		};
+		let peers = (1..=100).collect::<Vec<_>>();
+		let unreachable = |block_number| profile.unreachable_peers(&peers, block_number);
+
+		assert_eq!(unreachable(1), unreachable(1));
+		assert_eq!(unreachable(2).len(), 10);
+		assert_eq!(unreachable(3).len(), 60);
+		assert_eq!(unreachable(4).len(), 60);
+		assert_eq!(unreachable(5).len(), 10);
+		// A new set of peers is churned every period.
+		assert_eq!(unreachable(2), unreachable(3)[50..]);
+		assert_ne!(unreachable(1), unreachable(2));
+	}
+
+	#[test]
+	fn validate_rejects_unknown_regions() {
+		let mut profile = three_continents();
+		assert!(profile.validate().is_ok());
+
+		profile.inter_region_latency[0].to = "mars".into();
+		assert!(profile.validate().is_err());
+	}
+}
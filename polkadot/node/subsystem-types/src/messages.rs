@@ -1372,6 +1372,82 @@ pub type HypotheticalMembership = Vec<Hash>;
 /// A collection of ancestor candidates of a parachain.
 pub type Ancestors = HashSet<CandidateHash>;
 
+/// A candidate known to a fragment chain, as returned by
+/// [`ProspectiveParachainsMessage::GetFragmentChainsIntrospection`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntrospectedCandidate {
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The relay-parent of the candidate.
+	pub relay_parent: Hash,
+	/// The hash of the head-data the candidate builds on.
+	pub parent_head_data_hash: Hash,
+	/// The hash of the head-data the candidate outputs.
+	pub output_head_data_hash: Hash,
+	/// Whether the candidate has been backed.
+	pub backed: bool,
+}
+
+/// A seconded candidate which could not be introduced into any fragment chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectedCandidate {
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The relay-parent of the candidate.
+	pub relay_parent: Hash,
+	/// Human-readable reason of the rejection.
+	pub reason: String,
+}
+
+/// Summary of the constraints a fragment chain operates under.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintsIntrospection {
+	/// The minimum relay-parent number accepted under these constraints.
+	pub min_relay_parent_number: BlockNumber,
+	/// The maximum Proof-of-Validity size allowed, in bytes.
+	pub max_pov_size: usize,
+	/// The maximum new validation code size allowed, in bytes.
+	pub max_code_size: usize,
+	/// The amount of UMP messages remaining.
+	pub ump_remaining: usize,
+	/// The amount of UMP bytes remaining.
+	pub ump_remaining_bytes: usize,
+	/// The number of DMP messages remaining in the queue.
+	pub dmp_remaining_messages: usize,
+	/// The hash of the required parent head-data of the parachain.
+	pub required_parent_hash: Hash,
+	/// The expected validation-code-hash of this parachain.
+	pub validation_code_hash: ValidationCodeHash,
+	/// Whether code upgrades are currently restricted.
+	pub upgrade_restricted: bool,
+	/// The future validation code hash, if any, and at what relay-parent
+	/// number the upgrade would be minimally applied.
+	pub future_validation_code: Option<(BlockNumber, ValidationCodeHash)>,
+}
+
+/// Snapshot of the fragment chain of a para under an active leaf.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FragmentChainIntrospection {
+	/// The para the fragment chain belongs to.
+	pub para_id: ParaId,
+	/// The active leaf the fragment chain is built under.
+	pub leaf: Hash,
+	/// The number of the earliest relay-parent candidates may build upon.
+	pub earliest_relay_parent_number: BlockNumber,
+	/// The maximum depth of the chain.
+	pub max_depth: usize,
+	/// The base constraints derived from the latest included candidate.
+	pub constraints: ConstraintsIntrospection,
+	/// The candidates pending availability at the leaf.
+	pub pending_availability: Vec<CandidateHash>,
+	/// The best chain of backable candidates.
+	pub best_chain: Vec<IntrospectedCandidate>,
+	/// Candidates which are not (yet) connected to the best chain.
+	pub unconnected: Vec<IntrospectedCandidate>,
+	/// The most recently rejected candidates of the para.
+	pub rejected: Vec<RejectedCandidate>,
+}
+
 /// Messages sent to the Prospective Parachains subsystem.
 #[derive(Debug)]
 pub enum ProspectiveParachainsMessage {
@@ -1438,4 +1514,10 @@ pub enum ProspectiveParachainsMessage {
 		ProspectiveValidationDataRequest,
 		oneshot::Sender<Option<PersistedValidationData>>,
 	),
+	/// Get a snapshot of the fragment chains under all active leaves, optionally restricted to
+	/// the given para. Meant for debugging and monitoring only.
+	GetFragmentChainsIntrospection(
+		Option<ParaId>,
+		oneshot::Sender<Vec<FragmentChainIntrospection>>,
+	),
 }
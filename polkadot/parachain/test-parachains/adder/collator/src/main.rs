@@ -96,6 +96,9 @@ fn main() -> Result<()> {
 						hwbench: None,
 						execute_workers_max_num: None,
 						prepare_workers_hard_max_num: None,
+						pvf_shared_artifacts_path: None,
+						pvf_shared_artifacts_key: None,
+						availability_archival: Default::default(),
 						prepare_workers_soft_max_num: None,
 						enable_approval_voting_parallel: false,
 					},
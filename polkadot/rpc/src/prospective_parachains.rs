@@ -0,0 +1,244 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC introspection into the fragment chains of the prospective parachains subsystem.
+//!
+//! Meant to help parachain teams understand why their candidates are not backed. All methods are
+//! unsafe, since they query the node's internal state.
+
+use std::time::Duration;
+
+use futures::{channel::oneshot, future::Either, FutureExt};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::{error::ErrorObject, ErrorObjectOwned},
+	Extensions,
+};
+use polkadot_node_subsystem_types::messages::{
+	ConstraintsIntrospection, FragmentChainIntrospection, IntrospectedCandidate,
+	ProspectiveParachainsMessage, RejectedCandidate,
+};
+use polkadot_overseer::Handle;
+use polkadot_primitives::{BlockNumber, Hash, Id as ParaId, ValidationCodeHash};
+use sc_rpc_api::check_if_safe;
+use serde::{Deserialize, Serialize};
+
+/// How long to wait for the prospective parachains subsystem to answer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+const UNAVAILABLE_ERROR: i32 = 9100;
+
+/// A candidate known to a fragment chain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Candidate {
+	/// The hash of the candidate.
+	pub candidate_hash: Hash,
+	/// The relay-parent of the candidate.
+	pub relay_parent: Hash,
+	/// The hash of the head-data the candidate builds on.
+	pub parent_head_data_hash: Hash,
+	/// The hash of the head-data the candidate outputs.
+	pub output_head_data_hash: Hash,
+	/// Whether the candidate has been backed.
+	pub backed: bool,
+}
+
+/// A seconded candidate which could not be introduced into any fragment chain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Rejection {
+	/// The hash of the candidate.
+	pub candidate_hash: Hash,
+	/// The relay-parent of the candidate.
+	pub relay_parent: Hash,
+	/// Why the candidate was rejected.
+	pub reason: String,
+}
+
+/// The constraints a fragment chain operates under.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Constraints {
+	/// The minimum relay-parent number accepted.
+	pub min_relay_parent_number: BlockNumber,
+	/// The maximum PoV size allowed, in bytes.
+	pub max_pov_size: u64,
+	/// The maximum new validation code size allowed, in bytes.
+	pub max_code_size: u64,
+	/// The amount of UMP messages remaining.
+	pub ump_remaining: u64,
+	/// The amount of UMP bytes remaining.
+	pub ump_remaining_bytes: u64,
+	/// The number of DMP messages remaining in the queue.
+	pub dmp_remaining_messages: u64,
+	/// The hash of the head-data the next candidate needs to build on.
+	pub required_parent_hash: Hash,
+	/// The expected validation code hash.
+	pub validation_code_hash: Hash,
+	/// Whether code upgrades are currently restricted.
+	pub upgrade_restricted: bool,
+	/// The relay-parent number from which the future validation code applies, and its hash.
+	pub future_validation_code: Option<(BlockNumber, Hash)>,
+}
+
+/// The fragment chain of a para under an active leaf.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FragmentChain {
+	/// The para the fragment chain belongs to.
+	pub para_id: u32,
+	/// The active leaf the fragment chain is built under.
+	pub leaf: Hash,
+	/// The number of the earliest relay-parent candidates may build upon.
+	pub earliest_relay_parent_number: BlockNumber,
+	/// The maximum depth of the chain.
+	pub max_depth: u64,
+	/// The base constraints derived from the latest included candidate.
+	pub constraints: Constraints,
+	/// The candidates pending availability at the leaf.
+	pub pending_availability: Vec<Hash>,
+	/// The best chain of backable candidates, in order.
+	pub best_chain: Vec<Candidate>,
+	/// Candidates which are not (yet) connected to the best chain.
+	pub unconnected: Vec<Candidate>,
+	/// The most recently rejected candidates of the para, oldest first.
+	pub rejected: Vec<Rejection>,
+}
+
+impl From<IntrospectedCandidate> for Candidate {
+	fn from(candidate: IntrospectedCandidate) -> Self {
+		Self {
+			candidate_hash: candidate.candidate_hash.0,
+			relay_parent: candidate.relay_parent,
+			parent_head_data_hash: candidate.parent_head_data_hash,
+			output_head_data_hash: candidate.output_head_data_hash,
+			backed: candidate.backed,
+		}
+	}
+}
+
+impl From<RejectedCandidate> for Rejection {
+	fn from(rejected: RejectedCandidate) -> Self {
+		Self {
+			candidate_hash: rejected.candidate_hash.0,
+			relay_parent: rejected.relay_parent,
+			reason: rejected.reason,
+		}
+	}
+}
+
+impl From<ConstraintsIntrospection> for Constraints {
+	fn from(constraints: ConstraintsIntrospection) -> Self {
+		Self {
+			min_relay_parent_number: constraints.min_relay_parent_number,
+			max_pov_size: constraints.max_pov_size as u64,
+			max_code_size: constraints.max_code_size as u64,
+			ump_remaining: constraints.ump_remaining as u64,
+			ump_remaining_bytes: constraints.ump_remaining_bytes as u64,
+			dmp_remaining_messages: constraints.dmp_remaining_messages as u64,
+			required_parent_hash: constraints.required_parent_hash,
+			validation_code_hash: code_hash(constraints.validation_code_hash),
+			upgrade_restricted: constraints.upgrade_restricted,
+			future_validation_code: constraints
+				.future_validation_code
+				.map(|(number, hash)| (number, code_hash(hash))),
+		}
+	}
+}
+
+impl From<FragmentChainIntrospection> for FragmentChain {
+	fn from(chain: FragmentChainIntrospection) -> Self {
+		Self {
+			para_id: chain.para_id.into(),
+			leaf: chain.leaf,
+			earliest_relay_parent_number: chain.earliest_relay_parent_number,
+			max_depth: chain.max_depth as u64,
+			constraints: chain.constraints.into(),
+			pending_availability: chain.pending_availability.into_iter().map(|c| c.0).collect(),
+			best_chain: chain.best_chain.into_iter().map(Into::into).collect(),
+			unconnected: chain.unconnected.into_iter().map(Into::into).collect(),
+			rejected: chain.rejected.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+fn code_hash(hash: ValidationCodeHash) -> Hash {
+	Hash::from_slice(hash.as_ref())
+}
+
+/// Prospective parachains introspection RPC methods.
+#[rpc(client, server)]
+pub trait ProspectiveParachainsApi {
+	/// Returns the fragment chains under all active leaves, optionally only the ones of
+	/// `para_id`.
+	#[method(name = "parachain_fragmentChains", with_extensions)]
+	async fn fragment_chains(&self, para_id: Option<u32>) -> RpcResult<Vec<FragmentChain>>;
+}
+
+/// Implements the [`ProspectiveParachainsApiServer`] RPC trait by querying the prospective
+/// parachains subsystem through the overseer.
+pub struct ProspectiveParachains {
+	overseer_handle: Handle,
+}
+
+impl ProspectiveParachains {
+	/// Create a new `ProspectiveParachains` with the given overseer handle.
+	pub fn new(overseer_handle: Handle) -> Self {
+		Self { overseer_handle }
+	}
+}
+
+#[async_trait]
+impl ProspectiveParachainsApiServer for ProspectiveParachains {
+	async fn fragment_chains(
+		&self,
+		ext: &Extensions,
+		para_id: Option<u32>,
+	) -> RpcResult<Vec<FragmentChain>> {
+		check_if_safe(ext)?;
+
+		let mut overseer_handle = self.overseer_handle.clone();
+		let request = async move {
+			let (tx, rx) = oneshot::channel();
+			overseer_handle
+				.send_msg(
+					ProspectiveParachainsMessage::GetFragmentChainsIntrospection(
+						para_id.map(ParaId::from),
+						tx,
+					),
+					"ProspectiveParachainsRpc",
+				)
+				.await;
+			rx.await
+		};
+
+		let timeout = futures_timer::Delay::new(REQUEST_TIMEOUT);
+		match futures::future::select(request.boxed(), timeout).await {
+			Either::Left((Ok(chains), _)) => Ok(chains.into_iter().map(Into::into).collect()),
+			Either::Left((Err(_), _)) | Either::Right(_) => Err(unavailable_error()),
+		}
+	}
+}
+
+fn unavailable_error() -> ErrorObjectOwned {
+	ErrorObject::owned(
+		UNAVAILABLE_ERROR,
+		"Prospective parachains subsystem unavailable",
+		Some("The node doesn't run the parachain subsystems or didn't answer in time"),
+	)
+}
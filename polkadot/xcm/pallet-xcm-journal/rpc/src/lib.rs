@@ -0,0 +1,132 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for the XCM journal pallet.
+//!
+//! Lifecycles are returned SCALE-encoded, so that clients can decode them with the metadata of
+//! the runtime they were recorded by.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Encode;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::{error::ErrorObject, ErrorObjectOwned},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use xcm_runtime_apis::journal::Error as JournalError;
+
+pub use xcm_runtime_apis::journal::XcmJournalApi as XcmJournalRuntimeApi;
+
+const RUNTIME_ERROR: i32 = 9000;
+const JOURNAL_ERROR: i32 = 9010;
+
+/// Lifecycle of a message, as recorded at a given block.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageLifecycle<BlockHash> {
+	/// Block hash the lifecycle was read at.
+	pub block_hash: BlockHash,
+	/// SCALE-encoded [`xcm_runtime_apis::journal::MessageLifecycle`].
+	pub lifecycle: Bytes,
+}
+
+/// XCM journal RPC methods.
+#[rpc(client, server)]
+pub trait XcmJournalApi<BlockHash, BlockNumber> {
+	/// Returns the lifecycle of the message with the given topic id, as known at block `at` (or
+	/// the best block, if not given).
+	#[method(name = "xcmJournal_messageLifecycle")]
+	fn message_lifecycle(
+		&self,
+		topic: H256,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<MessageLifecycle<BlockHash>>>;
+
+	/// Returns the topic ids of the messages recorded in block `block_number`.
+	#[method(name = "xcmJournal_messagesAt")]
+	fn messages_at(&self, block_number: BlockNumber, at: Option<BlockHash>)
+		-> RpcResult<Vec<H256>>;
+}
+
+/// Implements the [`XcmJournalApiServer`] RPC trait for interacting with the XCM journal.
+pub struct XcmJournal<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> XcmJournal<C, Block> {
+	/// Create new `XcmJournal` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> XcmJournalApiServer<<Block as BlockT>::Hash, NumberFor<Block>>
+	for XcmJournal<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: XcmJournalRuntimeApi<Block, NumberFor<Block>>,
+{
+	fn message_lifecycle(
+		&self,
+		topic: H256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<MessageLifecycle<<Block as BlockT>::Hash>>> {
+		let block_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let lifecycle = self
+			.client
+			.runtime_api()
+			.message_lifecycle(block_hash, topic.into())
+			.map_err(runtime_error_into_rpc_error)?
+			.map_err(journal_error_into_rpc_error)?;
+
+		Ok(lifecycle
+			.map(|lifecycle| MessageLifecycle { block_hash, lifecycle: Bytes(lifecycle.encode()) }))
+	}
+
+	fn messages_at(
+		&self,
+		block_number: NumberFor<Block>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<H256>> {
+		let block_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let topics = self
+			.client
+			.runtime_api()
+			.messages_at(block_hash, block_number)
+			.map_err(runtime_error_into_rpc_error)?
+			.map_err(journal_error_into_rpc_error)?;
+
+		Ok(topics.into_iter().map(H256::from).collect())
+	}
+}
+
+/// Converts an XCM journal error into an RPC error.
+fn journal_error_into_rpc_error(err: JournalError) -> ErrorObjectOwned {
+	ErrorObject::owned(JOURNAL_ERROR, "XCM journal error", Some(format!("{:?}", err)))
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_error(err: impl std::fmt::Debug) -> ErrorObjectOwned {
+	ErrorObject::owned(RUNTIME_ERROR, "Runtime trapped", Some(format!("{:?}", err)))
+}
@@ -0,0 +1,141 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Adapters recording sent and executed XCMs into the journal.
+
+use crate::{Config, Pallet, WeightInfo};
+use core::marker::PhantomData;
+use frame_support::dispatch::DispatchClass;
+use xcm::prelude::*;
+use xcm_runtime_apis::journal::JournalEvent;
+
+/// Details of a validated message needed to record it once it is delivered.
+pub struct SentMessage {
+	origin: Location,
+	destination: Location,
+	fees: Assets,
+}
+
+/// A [`SendXcm`] implementation that records every message delivered by `Router`.
+///
+/// The recorded topic id is the hash returned by `Router` on delivery. Since sending does not
+/// report any weight, the cost of recording is registered as extra weight of the current block.
+pub struct JournalingRouter<T, Router>(PhantomData<(T, Router)>);
+
+impl<T: Config, Router: SendXcm> SendXcm for JournalingRouter<T, Router> {
+	type Ticket = (Router::Ticket, Option<SentMessage>);
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let maybe_destination = destination.clone();
+		let maybe_origin = message.as_ref().map(local_origin_of);
+		let (ticket, fees) = Router::validate(destination, message)?;
+		let sent = maybe_origin.zip(maybe_destination).map(|(origin, destination)| SentMessage {
+			origin,
+			destination,
+			fees: fees.clone(),
+		});
+		Ok(((ticket, sent), fees))
+	}
+
+	fn deliver((ticket, sent): Self::Ticket) -> Result<XcmHash, SendError> {
+		let topic = Router::deliver(ticket)?;
+		if let Some(SentMessage { origin, destination, fees }) = sent {
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				T::WeightInfo::record(),
+				DispatchClass::Mandatory,
+			);
+			Pallet::<T>::record(
+				topic,
+				JournalEvent::Sent {
+					origin: origin.into(),
+					destination: destination.into(),
+					fees: fees.into(),
+				},
+			);
+		}
+		Ok(topic)
+	}
+}
+
+/// An [`ExecuteXcm`] implementation that records the outcome of every message executed by
+/// `Executor`.
+///
+/// The recorded topic id is the message id after execution, which is the `SetTopic` id of the
+/// message if it has one. The cost of recording is added to the weight of the message.
+pub struct JournalingExecutor<T, Executor>(PhantomData<(T, Executor)>);
+
+/// A message prepared by the executor wrapped in a [`JournalingExecutor`], weighing in the cost
+/// of recording its outcome.
+pub struct JournalingPrepared<T, Prepared>(Prepared, PhantomData<T>);
+
+impl<T: Config, Prepared: PreparedMessage> PreparedMessage for JournalingPrepared<T, Prepared> {
+	fn weight_of(&self) -> Weight {
+		self.0.weight_of().saturating_add(T::WeightInfo::record())
+	}
+}
+
+impl<T: Config, Call, Executor: ExecuteXcm<Call>> ExecuteXcm<Call>
+	for JournalingExecutor<T, Executor>
+{
+	type Prepared = JournalingPrepared<T, Executor::Prepared>;
+
+	fn prepare(message: Xcm<Call>) -> Result<Self::Prepared, Xcm<Call>> {
+		Executor::prepare(message).map(|pre| JournalingPrepared(pre, PhantomData))
+	}
+
+	fn execute(
+		origin: impl Into<Location>,
+		pre: Self::Prepared,
+		id: &mut XcmHash,
+		weight_credit: Weight,
+	) -> Outcome {
+		let origin = origin.into();
+		let outcome = Executor::execute(origin.clone(), pre.0, id, weight_credit);
+		Pallet::<T>::record(
+			*id,
+			JournalEvent::Executed { origin: origin.into(), outcome: outcome.clone() },
+		);
+
+		let record_weight = T::WeightInfo::record();
+		match outcome {
+			Outcome::Complete { used } =>
+				Outcome::Complete { used: used.saturating_add(record_weight) },
+			Outcome::Incomplete { used, error } =>
+				Outcome::Incomplete { used: used.saturating_add(record_weight), error },
+			// Callers charge the full weight of the prepared message for messages which failed to
+			// execute, which already includes the cost of recording.
+			outcome @ Outcome::Error { .. } => outcome,
+		}
+	}
+
+	fn charge_fees(location: impl Into<Location>, fees: Assets) -> XcmResult {
+		Executor::charge_fees(location, fees)
+	}
+}
+
+/// Returns the local origin of an outgoing `message`.
+///
+/// Messages sent on behalf of a local account start by descending into it, everything else is
+/// sent by the chain itself.
+fn local_origin_of(message: &Xcm<()>) -> Location {
+	match message.first() {
+		Some(DescendOrigin(interior)) => Location::new(0, interior.clone()),
+		_ => Location::here(),
+	}
+}
@@ -0,0 +1,87 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for `pallet-xcm-journal`.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use xcm::latest::MAX_ITEMS_IN_ASSETS;
+
+/// Returns a distinct topic id for every `index`.
+fn topic(index: u32) -> XcmHash {
+	let mut topic = [0xff; 32];
+	topic[..4].copy_from_slice(&index.to_le_bytes());
+	topic
+}
+
+/// A sent event with long locations and the maximum number of fee assets.
+fn worst_case_event() -> JournalEvent {
+	let location = |index: u32| {
+		Location::new(
+			1,
+			[
+				Parachain(index),
+				PalletInstance(u8::MAX),
+				GeneralIndex(u128::MAX),
+				AccountId32 { network: Some(Polkadot), id: [index as u8; 32] },
+			],
+		)
+	};
+	let fees: Vec<Asset> = (0..MAX_ITEMS_IN_ASSETS as u32)
+		.map(|index| (location(index), u128::MAX).into())
+		.collect();
+
+	JournalEvent::Sent {
+		origin: location(0).into(),
+		destination: location(1).into(),
+		fees: Assets::from(fees).into(),
+	}
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn record() {
+		let block_number = frame_system::Pallet::<T>::block_number();
+		let recorded = topic(0);
+
+		// the message has been recorded in earlier blocks up to the last free slot, and the index
+		// of the current block has room for a single message only
+		let max_records = T::MaxRecordsPerMessage::get();
+		MessageRecords::<T>::insert(recorded, RecordIndices { first: 0, next: max_records - 1 });
+		let topics = (1..T::MaxMessagesPerBlock::get()).map(topic).collect::<Vec<_>>();
+		MessagesAt::<T>::insert(
+			block_number,
+			BoundedVec::<_, T::MaxMessagesPerBlock>::truncate_from(topics),
+		);
+
+		#[block]
+		{
+			Pallet::<T>::record(recorded, worst_case_event());
+		}
+
+		assert_eq!(
+			MessageRecords::<T>::get(recorded).map(|indices| indices.len()),
+			Some(max_records)
+		);
+		assert!(Messages::<T>::contains_key(recorded, max_records - 1));
+		assert!(MessagesAt::<T>::get(block_number).contains(&recorded));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::TestRuntime);
+}
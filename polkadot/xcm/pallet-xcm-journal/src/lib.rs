@@ -0,0 +1,356 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet that keeps a journal of the XCMs sent and executed by this chain.
+//!
+//! Unlike [`xcm_executor::traits::RecordXcm`], which only remembers the last executed message
+//! for dry-runs, this pallet indexes every observed message by its topic id and keeps it around
+//! for [`Config::RetentionPeriod`] blocks. This makes it possible to answer "what happened to
+//! message X" after the fact, through the [`xcm_runtime_apis::journal::XcmJournalApi`].
+//!
+//! The pallet does not hook into anything by itself. Runtimes opt in by wrapping their router
+//! with [`JournalingRouter`] and their executor with [`JournalingExecutor`]:
+//!
+//! ```ignore
+//! pub type XcmRouter = pallet_xcm_journal::JournalingRouter<
+//! 	Runtime,
+//! 	WithUniqueTopic<(ChildParachainRouter, ...)>,
+//! >;
+//!
+//! type MessageProcessor = ProcessXcmMessage<
+//! 	AggregateMessageOrigin,
+//! 	pallet_xcm_journal::JournalingExecutor<Runtime, XcmExecutor<XcmConfig>>,
+//! 	RuntimeCall,
+//! >;
+//! ```
+//!
+//! The router should wrap `WithUniqueTopic`, so that the recorded id of a sent message is the
+//! same topic id the destination will see when executing it.
+//!
+//! Recording a message costs [`WeightInfo::record`]. For executed messages it is added to the
+//! weight of the prepared message, so it is charged along with the execution. For delivered
+//! messages it is registered as extra weight of the current block.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+mod adapters;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use sp_runtime::traits::{Saturating, Zero};
+use xcm::prelude::*;
+use xcm_runtime_apis::journal::{
+	Error as JournalApiError, JournalEvent, JournalRecord, MessageLifecycle,
+};
+
+pub use adapters::{JournalingExecutor, JournalingPrepared, JournalingRouter};
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The log target of this pallet.
+pub const LOG_TARGET: &str = "runtime::xcm-journal";
+
+/// Outcome of an executed message, as it is stored in the journal.
+///
+/// Mirrors [`Outcome`], which has no bound on its encoded size.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum StoredOutcome {
+	/// See [`Outcome::Complete`].
+	Complete { used: Weight },
+	/// See [`Outcome::Incomplete`].
+	Incomplete { used: Weight, error: XcmError },
+	/// See [`Outcome::Error`].
+	Error { error: XcmError },
+}
+
+impl From<Outcome> for StoredOutcome {
+	fn from(outcome: Outcome) -> Self {
+		match outcome {
+			Outcome::Complete { used } => Self::Complete { used },
+			Outcome::Incomplete { used, error } => Self::Incomplete { used, error },
+			Outcome::Error { error } => Self::Error { error },
+		}
+	}
+}
+
+impl From<StoredOutcome> for Outcome {
+	fn from(outcome: StoredOutcome) -> Self {
+		match outcome {
+			StoredOutcome::Complete { used } => Self::Complete { used },
+			StoredOutcome::Incomplete { used, error } => Self::Incomplete { used, error },
+			StoredOutcome::Error { error } => Self::Error { error },
+		}
+	}
+}
+
+/// A [`JournalEvent`], as it is stored in the journal.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum StoredEvent {
+	/// See [`JournalEvent::Sent`].
+	Sent { origin: VersionedLocation, destination: VersionedLocation, fees: VersionedAssets },
+	/// See [`JournalEvent::Executed`].
+	Executed { origin: VersionedLocation, outcome: StoredOutcome },
+}
+
+impl From<JournalEvent> for StoredEvent {
+	fn from(event: JournalEvent) -> Self {
+		match event {
+			JournalEvent::Sent { origin, destination, fees } =>
+				Self::Sent { origin, destination, fees },
+			JournalEvent::Executed { origin, outcome } =>
+				Self::Executed { origin, outcome: outcome.into() },
+		}
+	}
+}
+
+impl From<StoredEvent> for JournalEvent {
+	fn from(event: StoredEvent) -> Self {
+		match event {
+			StoredEvent::Sent { origin, destination, fees } =>
+				Self::Sent { origin, destination, fees },
+			StoredEvent::Executed { origin, outcome } =>
+				Self::Executed { origin, outcome: outcome.into() },
+		}
+	}
+}
+
+/// A [`JournalRecord`], as it is stored in the journal.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct StoredRecord<BlockNumber> {
+	/// The block in which the event happened.
+	pub block_number: BlockNumber,
+	/// What happened to the message.
+	pub event: StoredEvent,
+}
+
+impl<BlockNumber> From<StoredRecord<BlockNumber>> for JournalRecord<BlockNumber> {
+	fn from(record: StoredRecord<BlockNumber>) -> Self {
+		Self { block_number: record.block_number, event: record.event.into() }
+	}
+}
+
+/// Indices of the stored records of a message.
+///
+/// Records are stored in the order they are made, so the records at `first..next` are the
+/// records of the message, oldest first.
+#[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct RecordIndices {
+	/// Index of the oldest record.
+	pub first: u32,
+	/// Index of the next record.
+	pub next: u32,
+}
+
+impl RecordIndices {
+	/// Returns the number of stored records.
+	pub fn len(&self) -> u32 {
+		self.next.saturating_sub(self.first)
+	}
+
+	/// Returns true if there are no stored records.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// How many blocks a recorded message is kept for.
+		#[pallet::constant]
+		type RetentionPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Maximal number of distinct messages recorded in a single block.
+		///
+		/// Messages observed after this limit is reached are not recorded.
+		#[pallet::constant]
+		type MaxMessagesPerBlock: Get<u32>;
+
+		/// Maximal number of events recorded for a single message.
+		#[pallet::constant]
+		type MaxRecordsPerMessage: Get<u32>;
+
+		/// Weight information for the operations of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Recorded events of every known message, keyed by topic id and the index of the record.
+	#[pallet::storage]
+	pub type Messages<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		XcmHash,
+		Twox64Concat,
+		u32,
+		StoredRecord<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Indices of the records of every known message in [`Messages`].
+	#[pallet::storage]
+	pub type MessageRecords<T: Config> =
+		StorageMap<_, Blake2_128Concat, XcmHash, RecordIndices, OptionQuery>;
+
+	/// Topic ids of the messages recorded in a given block.
+	///
+	/// Used to prune [`Messages`] once the retention period of a block is over.
+	#[pallet::storage]
+	pub type MessagesAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<XcmHash, T::MaxMessagesPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let retention = T::RetentionPeriod::get();
+			if now <= retention {
+				return Weight::zero()
+			}
+
+			let expired = now.saturating_sub(retention);
+			let topics = MessagesAt::<T>::take(expired);
+			let mut pruned = 0u64;
+			for topic in &topics {
+				pruned = pruned.saturating_add(Self::prune_message(*topic, expired).into());
+			}
+
+			// every topic reads its indices and the first kept record
+			let topics = topics.len() as u64;
+			T::DbWeight::get().reads_writes(
+				1u64.saturating_add(2 * topics).saturating_add(pruned),
+				1u64.saturating_add(topics).saturating_add(pruned),
+			)
+		}
+
+		fn integrity_test() {
+			assert!(
+				!T::MaxMessagesPerBlock::get().is_zero(),
+				"`MaxMessagesPerBlock` must be non-zero, otherwise nothing is ever recorded",
+			);
+			assert!(
+				!T::MaxRecordsPerMessage::get().is_zero(),
+				"`MaxRecordsPerMessage` must be non-zero, otherwise nothing is ever recorded",
+			);
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Records `event` for the message with the given `topic` in the current block.
+	///
+	/// Recording is best-effort: if any of the configured bounds is reached, the event is
+	/// dropped and a warning is logged.
+	pub fn record(topic: XcmHash, event: JournalEvent) {
+		let block_number = frame_system::Pallet::<T>::block_number();
+
+		let indexed = MessagesAt::<T>::mutate(block_number, |topics| {
+			topics.contains(&topic) || topics.try_push(topic).is_ok()
+		});
+		if !indexed {
+			tracing::warn!(
+				target: LOG_TARGET,
+				?block_number,
+				?topic,
+				"Too many messages recorded in block, not recording message",
+			);
+			return
+		}
+
+		let mut indices = MessageRecords::<T>::get(topic).unwrap_or_default();
+		if indices.len() >= T::MaxRecordsPerMessage::get() {
+			tracing::warn!(
+				target: LOG_TARGET,
+				?topic,
+				"Too many records for message, dropping the latest one",
+			);
+			return
+		}
+
+		Messages::<T>::insert(
+			topic,
+			indices.next,
+			StoredRecord { block_number, event: event.into() },
+		);
+		indices.next = indices.next.saturating_add(1);
+		MessageRecords::<T>::insert(topic, indices);
+	}
+
+	/// Returns everything known about the message with the given `topic`.
+	pub fn message_lifecycle(topic: XcmHash) -> Option<MessageLifecycle<BlockNumberFor<T>>> {
+		let indices = MessageRecords::<T>::get(topic)?;
+		let records = (indices.first..indices.next)
+			.filter_map(|index| Messages::<T>::get(topic, index))
+			.map(Into::into)
+			.collect();
+		Some(MessageLifecycle { topic, records })
+	}
+
+	/// Returns the topic ids of the messages recorded in `block_number`.
+	///
+	/// Fails if the block is outside of the retention period.
+	pub fn messages_at(block_number: BlockNumberFor<T>) -> Result<Vec<XcmHash>, JournalApiError> {
+		let now = frame_system::Pallet::<T>::block_number();
+		if block_number.saturating_add(T::RetentionPeriod::get()) <= now {
+			return Err(JournalApiError::OutsideRetentionPeriod)
+		}
+		Ok(MessagesAt::<T>::get(block_number).into_inner())
+	}
+
+	/// Removes all records of `topic` made at or before `expired`.
+	///
+	/// Returns the number of removed records.
+	fn prune_message(topic: XcmHash, expired: BlockNumberFor<T>) -> u32 {
+		MessageRecords::<T>::mutate_exists(topic, |maybe_indices| {
+			let Some(indices) = maybe_indices else { return 0 };
+
+			let mut pruned = 0;
+			while indices.first < indices.next {
+				match Messages::<T>::get(topic, indices.first) {
+					Some(record) if record.block_number > expired => break,
+					_ => Messages::<T>::remove(topic, indices.first),
+				}
+				indices.first = indices.first.saturating_add(1);
+				pruned += 1;
+			}
+
+			if indices.is_empty() {
+				*maybe_indices = None;
+			}
+			pruned
+		})
+	}
+}
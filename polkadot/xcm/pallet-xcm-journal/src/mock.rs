@@ -0,0 +1,158 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate as pallet_xcm_journal;
+
+use codec::Encode;
+use frame_support::{construct_runtime, derive_impl, parameter_types};
+use sp_runtime::BuildStorage;
+use xcm::prelude::*;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+/// Delivery fee charged by [`TestRouter`] for every message.
+pub const DELIVERY_FEE: u128 = 10;
+/// Weight of a single instruction executed by [`TestExecutor`].
+pub const INSTRUCTION_WEIGHT: u64 = 100;
+/// Para whose messages are accepted by [`TestRouter`], but fail to be delivered, as if its queue
+/// was full.
+pub const CONGESTED_PARA: u32 = 666;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system,
+		XcmJournal: pallet_xcm_journal,
+	}
+}
+
+parameter_types! {
+	pub const RetentionPeriod: u64 = 10;
+	pub const MaxMessagesPerBlock: u32 = 2;
+	pub const MaxRecordsPerMessage: u32 = 3;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+}
+
+impl pallet_xcm_journal::Config for TestRuntime {
+	type RetentionPeriod = RetentionPeriod;
+	type MaxMessagesPerBlock = MaxMessagesPerBlock;
+	type MaxRecordsPerMessage = MaxRecordsPerMessage;
+	type WeightInfo = ();
+}
+
+/// Router that accepts every message and returns its `SetTopic` id, if any, on delivery.
+///
+/// Delivery to [`CONGESTED_PARA`] fails.
+pub struct TestRouter;
+
+impl SendXcm for TestRouter {
+	type Ticket = (Location, Xcm<()>);
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let destination = destination.take().ok_or(SendError::MissingArgument)?;
+		let message = message.take().ok_or(SendError::MissingArgument)?;
+		Ok(((destination, message), (Here, DELIVERY_FEE).into()))
+	}
+
+	fn deliver((destination, message): Self::Ticket) -> Result<XcmHash, SendError> {
+		if destination == Location::new(1, [Parachain(CONGESTED_PARA)]) {
+			return Err(SendError::Transport("queue is full"))
+		}
+		Ok(topic_of(&message))
+	}
+}
+
+/// A prepared message of [`TestExecutor`].
+pub struct TestPrepared(Xcm<()>);
+
+impl PreparedMessage for TestPrepared {
+	fn weight_of(&self) -> Weight {
+		Weight::from_parts(INSTRUCTION_WEIGHT * self.0.len() as u64, 0)
+	}
+}
+
+/// Executor that completes every message, unless it contains a `Trap` instruction.
+pub struct TestExecutor;
+
+impl ExecuteXcm<()> for TestExecutor {
+	type Prepared = TestPrepared;
+
+	fn prepare(message: Xcm<()>) -> Result<Self::Prepared, Xcm<()>> {
+		Ok(TestPrepared(message))
+	}
+
+	fn execute(
+		_origin: impl Into<Location>,
+		pre: Self::Prepared,
+		id: &mut XcmHash,
+		_weight_credit: Weight,
+	) -> Outcome {
+		let used = pre.weight_of();
+		if let Some(SetTopic(topic)) = pre.0.last() {
+			*id = *topic;
+		}
+		match pre.0.iter().find_map(|instruction| match instruction {
+			Trap(code) => Some(*code),
+			_ => None,
+		}) {
+			Some(code) => Outcome::Incomplete { used, error: XcmError::Trap(code) },
+			None => Outcome::Complete { used },
+		}
+	}
+
+	fn charge_fees(_location: impl Into<Location>, _fees: Assets) -> XcmResult {
+		Ok(())
+	}
+}
+
+/// Returns the `SetTopic` id of `message`, or its hash if it has none.
+pub fn topic_of(message: &Xcm<()>) -> XcmHash {
+	match message.last() {
+		Some(SetTopic(topic)) => *topic,
+		_ => message.using_encoded(sp_io::hashing::blake2_256),
+	}
+}
+
+/// Advances to block `n`, running the hooks of the journal.
+pub fn run_to_block(n: u64) {
+	use frame_support::traits::Hooks;
+
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		XcmJournal::on_initialize(next);
+	}
+}
+
+/// Return test externalities to use in tests.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
+}
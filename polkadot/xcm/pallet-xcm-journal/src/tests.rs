@@ -0,0 +1,208 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	mock::*, JournalingExecutor, JournalingRouter, MessageRecords, Messages, MessagesAt, Pallet,
+	RecordIndices, WeightInfo,
+};
+
+use codec::Encode;
+use xcm::prelude::*;
+use xcm_runtime_apis::journal::{Error as JournalApiError, JournalEvent, JournalRecord};
+
+type Router = JournalingRouter<TestRuntime, TestRouter>;
+type Executor = JournalingExecutor<TestRuntime, TestExecutor>;
+
+const TOPIC: XcmHash = [42; 32];
+
+fn execute(origin: Location, message: Xcm<()>) -> Outcome {
+	let mut id = message.using_encoded(sp_io::hashing::blake2_256);
+	Executor::prepare_and_execute(origin, message, &mut id, Weight::MAX, Weight::zero())
+}
+
+#[test]
+fn sent_message_is_recorded_with_local_origin_and_fees() {
+	run_test(|| {
+		let destination = Location::new(1, [Parachain(1000)]);
+		let account = AccountId32 { network: None, id: [1; 32] };
+		let message = Xcm(vec![DescendOrigin(account.into()), ClearOrigin, SetTopic(TOPIC)]);
+
+		assert_eq!(
+			send_xcm::<Router>(destination.clone(), message),
+			Ok((TOPIC, (Here, DELIVERY_FEE).into()))
+		);
+
+		let lifecycle = Pallet::<TestRuntime>::message_lifecycle(TOPIC).unwrap();
+		assert_eq!(
+			lifecycle.records,
+			vec![JournalRecord {
+				block_number: 1,
+				event: JournalEvent::Sent {
+					origin: Location::new(0, [account]).into(),
+					destination: destination.into(),
+					fees: Assets::from((Here, DELIVERY_FEE)).into(),
+				},
+			}],
+		);
+		assert_eq!(MessagesAt::<TestRuntime>::get(1).into_inner(), vec![TOPIC]);
+	});
+}
+
+#[test]
+fn executed_message_is_recorded_under_its_topic() {
+	run_test(|| {
+		let origin = Location::new(1, [Parachain(2000)]);
+		let message = Xcm(vec![ClearOrigin, Trap(7), SetTopic(TOPIC)]);
+		let used = Weight::from_parts(3 * INSTRUCTION_WEIGHT, 0);
+
+		// the cost of recording is charged on top of the execution
+		assert_eq!(
+			execute(origin.clone(), message),
+			Outcome::Incomplete {
+				used: used + <() as WeightInfo>::record(),
+				error: XcmError::Trap(7),
+			},
+		);
+
+		// while the recorded outcome is the one of the executor
+		let lifecycle = Pallet::<TestRuntime>::message_lifecycle(TOPIC).unwrap();
+		assert_eq!(
+			lifecycle.records,
+			vec![JournalRecord {
+				block_number: 1,
+				event: JournalEvent::Executed {
+					origin: origin.into(),
+					outcome: Outcome::Incomplete { used, error: XcmError::Trap(7) },
+				},
+			}],
+		);
+	});
+}
+
+#[test]
+fn recording_is_weighed() {
+	run_test(|| {
+		let message = Xcm(vec![ClearOrigin, SetTopic(TOPIC)]);
+		let record_weight = <() as WeightInfo>::record();
+
+		// executed messages weigh in the cost of recording their outcome
+		let prepared = Executor::prepare(message.clone()).unwrap();
+		assert_eq!(
+			prepared.weight_of(),
+			Weight::from_parts(2 * INSTRUCTION_WEIGHT, 0) + record_weight,
+		);
+
+		// delivered messages register it as extra weight of the block
+		let consumed = System::block_weight().total();
+		assert!(send_xcm::<Router>(Parent.into(), message).is_ok());
+		assert_eq!(System::block_weight().total(), consumed + record_weight);
+	});
+}
+
+#[test]
+fn lifecycle_spans_multiple_blocks() {
+	run_test(|| {
+		let message = Xcm(vec![ClearOrigin, SetTopic(TOPIC)]);
+		assert!(send_xcm::<Router>(Parent.into(), message.clone()).is_ok());
+		run_to_block(3);
+		assert!(execute(Parent.into(), message).ensure_complete().is_ok());
+
+		let records = Pallet::<TestRuntime>::message_lifecycle(TOPIC).unwrap().records;
+		assert_eq!(records.iter().map(|r| r.block_number).collect::<Vec<_>>(), vec![1, 3]);
+		assert!(matches!(records[0].event, JournalEvent::Sent { .. }));
+		assert!(matches!(records[1].event, JournalEvent::Executed { .. }));
+		assert_eq!(MessagesAt::<TestRuntime>::get(1).into_inner(), vec![TOPIC]);
+		assert_eq!(MessagesAt::<TestRuntime>::get(3).into_inner(), vec![TOPIC]);
+	});
+}
+
+#[test]
+fn records_are_pruned_after_retention_period() {
+	run_test(|| {
+		let message = Xcm(vec![ClearOrigin, SetTopic(TOPIC)]);
+		assert!(send_xcm::<Router>(Parent.into(), message.clone()).is_ok());
+		run_to_block(3);
+		assert!(execute(Parent.into(), message).ensure_complete().is_ok());
+
+		// the record of block 1 is pruned, the one of block 3 is kept
+		run_to_block(1 + RetentionPeriod::get());
+		let records = Pallet::<TestRuntime>::message_lifecycle(TOPIC).unwrap().records;
+		assert_eq!(records.iter().map(|r| r.block_number).collect::<Vec<_>>(), vec![3]);
+		assert_eq!(
+			MessageRecords::<TestRuntime>::get(TOPIC),
+			Some(RecordIndices { first: 1, next: 2 }),
+		);
+		assert!(!MessagesAt::<TestRuntime>::contains_key(1));
+		assert_eq!(
+			Pallet::<TestRuntime>::messages_at(1),
+			Err(JournalApiError::OutsideRetentionPeriod),
+		);
+
+		// and then the message is gone entirely
+		run_to_block(3 + RetentionPeriod::get());
+		assert_eq!(Pallet::<TestRuntime>::message_lifecycle(TOPIC), None);
+		assert!(!MessageRecords::<TestRuntime>::contains_key(TOPIC));
+		assert_eq!(Messages::<TestRuntime>::iter_prefix(TOPIC).count(), 0);
+		assert!(!MessagesAt::<TestRuntime>::contains_key(3));
+	});
+}
+
+#[test]
+fn recording_respects_bounds() {
+	run_test(|| {
+		// `MaxMessagesPerBlock` is 2, so the third distinct message is not recorded
+		for topic in [[1; 32], [2; 32], [3; 32]] {
+			assert!(send_xcm::<Router>(Parent.into(), Xcm(vec![SetTopic(topic)])).is_ok());
+		}
+		assert!(Pallet::<TestRuntime>::message_lifecycle([2; 32]).is_some());
+		assert_eq!(Pallet::<TestRuntime>::message_lifecycle([3; 32]), None);
+		assert_eq!(Pallet::<TestRuntime>::messages_at(1), Ok(vec![[1; 32], [2; 32]]));
+
+		// `MaxRecordsPerMessage` is 3, so the fourth record of the same message is dropped
+		for _ in 0..4 {
+			assert!(send_xcm::<Router>(Parent.into(), Xcm(vec![SetTopic([1; 32])])).is_ok());
+		}
+		assert_eq!(Pallet::<TestRuntime>::message_lifecycle([1; 32]).unwrap().records.len(), 3);
+	});
+}
+
+#[test]
+fn failed_delivery_is_not_recorded() {
+	run_test(|| {
+		let destination = Location::new(1, [Parachain(CONGESTED_PARA)]);
+		let (ticket, _) =
+			Router::validate(&mut Some(destination), &mut Some(Xcm(vec![SetTopic(TOPIC)])))
+				.unwrap();
+
+		let consumed = System::block_weight().total();
+		assert_eq!(Router::deliver(ticket), Err(SendError::Transport("queue is full")));
+		assert_eq!(Pallet::<TestRuntime>::message_lifecycle(TOPIC), None);
+		assert!(MessagesAt::<TestRuntime>::get(1).is_empty());
+		assert_eq!(System::block_weight().total(), consumed);
+	});
+}
+
+#[test]
+fn invalid_message_is_not_recorded() {
+	run_test(|| {
+		assert_eq!(
+			Router::validate(&mut None, &mut Some(Xcm(vec![SetTopic(TOPIC)]))).map(|_| ()),
+			Err(SendError::MissingArgument),
+		);
+		assert_eq!(Pallet::<TestRuntime>::message_lifecycle(TOPIC), None);
+		assert!(MessagesAt::<TestRuntime>::get(1).is_empty());
+	});
+}
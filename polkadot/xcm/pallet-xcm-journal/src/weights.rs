@@ -0,0 +1,79 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet-xcm-journal`.
+//!
+//! Storage accesses of these weights follow the `pallet-xcm-journal` benchmarks. Proof sizes are
+//! computed for `MaxMessagesPerBlock = 100` and 32-bit block numbers. Execution times have not
+//! been measured on the reference hardware yet, so this file must be regenerated with the
+//! benchmark CLI, using the bounds of the runtime, before the pallet is used in a production
+//! runtime:
+//!
+//! ```text
+//! frame-omni-bencher v1 benchmark pallet \
+//!     --runtime=<runtime.compact.compressed.wasm> \
+//!     --pallet=pallet_xcm_journal --extrinsic=* --steps=50 --repeat=20 \
+//!     --template=./substrate/.maintain/frame-weight-template.hbs \
+//!     --output=./polkadot/xcm/pallet-xcm-journal/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet-xcm-journal`.
+pub trait WeightInfo {
+	fn record() -> Weight;
+}
+
+/// Weights for `pallet-xcm-journal` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `XcmJournal::MessagesAt` (r:1 w:1)
+	/// Proof: `XcmJournal::MessagesAt` (`max_values`: None, `max_size`: Some(3214), added: 5689, mode: `MaxEncodedLen`)
+	/// Storage: `XcmJournal::MessageRecords` (r:1 w:1)
+	/// Proof: `XcmJournal::MessageRecords` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `XcmJournal::Messages` (r:0 w:1)
+	/// Proof: `XcmJournal::Messages` (`max_values`: None, `max_size`: Some(41347), added: 43822, mode: `MaxEncodedLen`)
+	fn record() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `8220`
+		Weight::from_parts(25_000_000, 8220)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `XcmJournal::MessagesAt` (r:1 w:1)
+	/// Proof: `XcmJournal::MessagesAt` (`max_values`: None, `max_size`: Some(3214), added: 5689, mode: `MaxEncodedLen`)
+	/// Storage: `XcmJournal::MessageRecords` (r:1 w:1)
+	/// Proof: `XcmJournal::MessageRecords` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `XcmJournal::Messages` (r:0 w:1)
+	/// Proof: `XcmJournal::Messages` (`max_values`: None, `max_size`: Some(41347), added: 43822, mode: `MaxEncodedLen`)
+	fn record() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `8220`
+		Weight::from_parts(25_000_000, 8220)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
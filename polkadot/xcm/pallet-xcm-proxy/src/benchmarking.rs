@@ -0,0 +1,144 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for `pallet-xcm-proxy`.
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller, BenchmarkError, BenchmarkResult};
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+use xcm_builder::EnsureDelivery;
+use xcm_executor::traits::FeeReason;
+
+type RuntimeOrigin<T> = <T as frame_system::Config>::RuntimeOrigin;
+
+/// Pallet we're benchmarking here.
+pub struct Pallet<T: Config>(crate::Pallet<T>);
+
+/// Trait that must be implemented by runtime to be able to benchmark pallet properly.
+pub trait Config: crate::Config {
+	/// Helper that ensures successful delivery of remote calls, e.g. by funding the delivery
+	/// fees of the sender.
+	type DeliveryHelper: EnsureDelivery;
+
+	/// A remote chain that can be reached via the `XcmRouter` of `pallet-xcm`.
+	///
+	/// If `None`, the `remote_call` benchmark will be skipped.
+	fn reachable_chain() -> Option<Location> {
+		None
+	}
+}
+
+fn remote_chain() -> RemoteChain {
+	RemoteChain {
+		fee_asset: AssetId(Location::here()),
+		account_derivation: AccountDerivation::DescribeFamily,
+	}
+}
+
+fn insert_pending_call<T: Config>(
+	who: T::AccountId,
+	chain: Location,
+	timeout: BlockNumberFor<T>,
+) -> QueryId {
+	let query_id = 0;
+	PendingCalls::<T>::insert(query_id, PendingCall { who: who.clone(), chain, timeout });
+	PendingCallCount::<T>::insert(&who, 1);
+	query_id
+}
+
+benchmarks! {
+	register_remote_chain {
+		let origin = <T as crate::Config>::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let chain = Location::new(1, [Parachain(1000)]);
+		let remote_chain = remote_chain();
+	}: _<RuntimeOrigin<T>>(
+		origin,
+		Box::new(chain.clone().into()),
+		Box::new(remote_chain.fee_asset.clone().into()),
+		remote_chain.account_derivation
+	)
+	verify {
+		assert_eq!(RemoteChains::<T>::get(&chain), Some(remote_chain));
+	}
+
+	deregister_remote_chain {
+		let origin = <T as crate::Config>::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let chain = Location::new(1, [Parachain(1000)]);
+		RemoteChains::<T>::insert(&chain, remote_chain());
+	}: _<RuntimeOrigin<T>>(origin, Box::new(chain.clone().into()))
+	verify {
+		assert!(!RemoteChains::<T>::contains_key(&chain));
+	}
+
+	remote_call {
+		let n in 0 .. T::MaxCallLen::get();
+
+		let chain = T::reachable_chain().ok_or(
+			BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX)),
+		)?;
+		RemoteChains::<T>::insert(&chain, remote_chain());
+
+		let caller: T::AccountId = whitelisted_caller();
+		let origin_location = <T as pallet_xcm::Config>::SendXcmOrigin::try_origin(
+			RawOrigin::Signed(caller.clone()).into(),
+		)
+		.map_err(|_| BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX)))?;
+		let _ = T::DeliveryHelper::ensure_successful_delivery(
+			&origin_location,
+			&chain,
+			FeeReason::ChargeFees,
+		);
+
+		let call: BoundedVec<u8, T::MaxCallLen> = vec![1u8; n as usize]
+			.try_into()
+			.map_err(|_| BenchmarkError::Weightless)?;
+	}: _(RawOrigin::Signed(caller.clone()), Box::new(chain.into()), call, u128::MAX)
+	verify {
+		assert_eq!(PendingCallCount::<T>::get(&caller), 1);
+	}
+
+	note_response {
+		let chain = Location::new(1, [Parachain(1000)]);
+		let caller: T::AccountId = whitelisted_caller();
+		let query_id = insert_pending_call::<T>(caller.clone(), chain.clone(), 1u32.into());
+		let origin: RuntimeOrigin<T> =
+			<T as crate::Config>::RuntimeOrigin::from(pallet_xcm::Origin::Response(chain)).into();
+	}: _<RuntimeOrigin<T>>(origin, query_id, Response::DispatchResult(MaybeErrorCode::Success))
+	verify {
+		assert!(!PendingCalls::<T>::contains_key(query_id));
+		assert_eq!(PendingCallCount::<T>::get(&caller), 0);
+	}
+
+	clear_expired_call {
+		let chain = Location::new(1, [Parachain(1000)]);
+		let caller: T::AccountId = whitelisted_caller();
+		let query_id = insert_pending_call::<T>(caller.clone(), chain, 0u32.into());
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), query_id)
+	verify {
+		assert!(!PendingCalls::<T>::contains_key(query_id));
+		assert_eq!(PendingCallCount::<T>::get(&caller), 0);
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::new_test_ext(),
+		crate::mock::TestRuntime
+	);
+}
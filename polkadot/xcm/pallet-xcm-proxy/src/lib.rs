@@ -0,0 +1,407 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pallet for controlling accounts on remote chains over XCM.
+//!
+//! Every local account controls an account on each remote chain: the one the remote chain
+//! derives from the location of the local account, as seen from the remote chain (e.g.
+//! `../Parachain(2000)/AccountId32(..)`). Dispatching a call as that account requires a message
+//! like:
+//!
+//! ```ignore
+//! DescendOrigin(AccountId32 { .. }),
+//! WithdrawAsset(fees),
+//! BuyExecution { fees, .. },
+//! SetAppendix(RefundSurplus, ReportTransactStatus(..), DepositAsset(.., remote account)),
+//! Transact { origin_kind: SovereignAccount, call },
+//! ```
+//!
+//! [`Pallet::remote_call`] builds and sends this message, paying the remote fees from the remote
+//! account itself. The outcome of the `Transact` is tracked through a `pallet-xcm` query and
+//! reported by [`Event::RemoteCallDispatched`] or [`Event::RemoteCallFailed`].
+//!
+//! Remote chains are registered by [`Config::AdminOrigin`], together with the asset they charge
+//! fees in and, if known, how they derive accounts from locations, so that
+//! [`Pallet::remote_account`] can tell users which remote account needs to be funded.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec};
+use frame_support::{dispatch::GetDispatchInfo, pallet_prelude::*};
+use frame_system::pallet_prelude::*;
+use sp_runtime::traits::Saturating;
+use xcm::prelude::*;
+use xcm_builder::{DescribeAllTerminal, DescribeFamily, HashedDescription};
+use xcm_executor::traits::ConvertLocation;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The log target of this pallet.
+pub const LOG_TARGET: &str = "runtime::xcm-proxy";
+
+/// How a remote chain derives accounts from the locations of remote accounts.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AccountDerivation {
+	/// `HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>`, as used by the
+	/// system chains.
+	DescribeFamily,
+	/// The remote chain derives accounts in a way that is not known to this chain.
+	Unknown,
+}
+
+/// A registered remote chain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RemoteChain {
+	/// The asset used to pay for execution, as seen from the remote chain.
+	pub fee_asset: AssetId,
+	/// How the remote chain derives accounts.
+	pub account_derivation: AccountDerivation,
+}
+
+/// A remote call waiting for its outcome to be reported.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingCall<AccountId, BlockNumber> {
+	/// The account that issued the call.
+	pub who: AccountId,
+	/// The chain the call was sent to.
+	pub chain: Location,
+	/// The block after which the outcome is not expected anymore.
+	pub timeout: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_xcm::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching origin type, used to recognize responses reported by `pallet-xcm`.
+		type RuntimeOrigin: IsType<<Self as frame_system::Config>::RuntimeOrigin>
+			+ From<pallet_xcm::Origin>
+			+ Into<Result<pallet_xcm::Origin, <Self as Config>::RuntimeOrigin>>;
+
+		/// The overarching call type, used to get notified by `pallet-xcm` of responses.
+		type RuntimeCall: IsType<<Self as pallet_xcm::Config>::RuntimeCall> + From<Call<Self>>;
+
+		/// Origin allowed to register and deregister remote chains.
+		type AdminOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// Maximal length of an encoded remote call.
+		#[pallet::constant]
+		type MaxCallLen: Get<u32>;
+
+		/// Maximal number of pending remote calls of a single account.
+		#[pallet::constant]
+		type MaxPendingCalls: Get<u32>;
+
+		/// How many blocks the outcome of a remote call is waited for.
+		#[pallet::constant]
+		type ResponseTimeout: Get<BlockNumberFor<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Registered remote chains, keyed by their location.
+	#[pallet::storage]
+	pub type RemoteChains<T: Config> =
+		StorageMap<_, Blake2_128Concat, Location, RemoteChain, OptionQuery>;
+
+	/// Remote calls waiting for their outcome, keyed by the id of the `pallet-xcm` query.
+	#[pallet::storage]
+	pub type PendingCalls<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		QueryId,
+		PendingCall<T::AccountId, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Number of pending remote calls of every account.
+	#[pallet::storage]
+	pub type PendingCallCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A remote chain was registered.
+		RemoteChainRegistered { chain: Location, remote_chain: RemoteChain },
+		/// A remote chain was deregistered.
+		RemoteChainDeregistered { chain: Location },
+		/// A remote call was sent.
+		RemoteCallSent {
+			who: T::AccountId,
+			chain: Location,
+			query_id: QueryId,
+			message_id: XcmHash,
+		},
+		/// A remote call was dispatched successfully.
+		RemoteCallDispatched { who: T::AccountId, query_id: QueryId },
+		/// A remote call failed to dispatch.
+		RemoteCallFailed { who: T::AccountId, query_id: QueryId, error: MaybeErrorCode },
+		/// The outcome of a remote call was not reported in time.
+		RemoteCallExpired { who: T::AccountId, query_id: QueryId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The given location or asset id could not be converted to the latest XCM version.
+		BadVersion,
+		/// The remote chain is already registered.
+		AlreadyRegistered,
+		/// The remote chain is not registered.
+		UnknownRemoteChain,
+		/// The origin could not be expressed as seen from the remote chain.
+		InvalidOrigin,
+		/// The account has too many pending remote calls.
+		TooManyPendingCalls,
+		/// The message could not be sent.
+		SendFailure,
+		/// There is no pending remote call with the given query id.
+		UnknownQuery,
+		/// The response does not come from the chain the call was sent to, or is not the outcome
+		/// of a call.
+		UnexpectedResponse,
+		/// The pending remote call has not expired yet.
+		NotExpired,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a remote chain, on which calls can then be dispatched with
+		/// [`Pallet::remote_call`].
+		///
+		/// - `chain`: The location of the remote chain.
+		/// - `fee_asset`: The asset used to pay for execution, as seen from the remote chain.
+		/// - `account_derivation`: How the remote chain derives accounts.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::register_remote_chain())]
+		pub fn register_remote_chain(
+			origin: OriginFor<T>,
+			chain: Box<VersionedLocation>,
+			fee_asset: Box<VersionedAssetId>,
+			account_derivation: AccountDerivation,
+		) -> DispatchResult {
+			<T as Config>::AdminOrigin::ensure_origin(origin)?;
+			let chain: Location = (*chain).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let fee_asset: AssetId =
+				(*fee_asset).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(!RemoteChains::<T>::contains_key(&chain), Error::<T>::AlreadyRegistered);
+
+			let remote_chain = RemoteChain { fee_asset, account_derivation };
+			RemoteChains::<T>::insert(&chain, remote_chain.clone());
+			Self::deposit_event(Event::RemoteChainRegistered { chain, remote_chain });
+			Ok(())
+		}
+
+		/// Deregister a remote chain.
+		///
+		/// The outcome of the calls already sent to the chain is still reported.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::deregister_remote_chain())]
+		pub fn deregister_remote_chain(
+			origin: OriginFor<T>,
+			chain: Box<VersionedLocation>,
+		) -> DispatchResult {
+			<T as Config>::AdminOrigin::ensure_origin(origin)?;
+			let chain: Location = (*chain).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			RemoteChains::<T>::take(&chain).ok_or(Error::<T>::UnknownRemoteChain)?;
+			Self::deposit_event(Event::RemoteChainDeregistered { chain });
+			Ok(())
+		}
+
+		/// Dispatch `call` on `chain` as the remote account controlled by the origin.
+		///
+		/// Up to `fees` of the fee asset of the chain are withdrawn from the remote account to
+		/// pay for execution, and whatever is left is deposited back. Delivery fees are withdrawn
+		/// from the origin by `pallet_xcm::send_xcm`, on top of the transaction fee, unless they
+		/// are waived for it. The weight of the call depends on the length of the encoded remote
+		/// call.
+		///
+		/// - `chain`: The location of a registered remote chain.
+		/// - `call`: The encoded call, as understood by the remote chain.
+		/// - `fees`: The maximal amount of the fee asset used to pay for execution.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::remote_call(call.len() as u32))]
+		pub fn remote_call(
+			origin: OriginFor<T>,
+			chain: Box<VersionedLocation>,
+			call: BoundedVec<u8, T::MaxCallLen>,
+			fees: u128,
+		) -> DispatchResult {
+			let who = ensure_signed(origin.clone())?;
+			let origin_location = <T as pallet_xcm::Config>::SendXcmOrigin::ensure_origin(origin)?;
+			let interior: Junctions =
+				origin_location.clone().try_into().map_err(|_| Error::<T>::InvalidOrigin)?;
+			let chain: Location = (*chain).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let remote_chain =
+				RemoteChains::<T>::get(&chain).ok_or(Error::<T>::UnknownRemoteChain)?;
+			let pending = PendingCallCount::<T>::get(&who);
+			ensure!(pending < T::MaxPendingCalls::get(), Error::<T>::TooManyPendingCalls);
+
+			let universal_location = <T as pallet_xcm::Config>::UniversalLocation::get();
+			let response_destination = universal_location
+				.invert_target(&chain)
+				.map_err(|()| Error::<T>::InvalidOrigin)?;
+			let remote_origin = origin_location
+				.clone()
+				.reanchored(&chain, &universal_location)
+				.map_err(|_| Error::<T>::InvalidOrigin)?;
+
+			let timeout =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::ResponseTimeout::get());
+			let notify: <T as pallet_xcm::Config>::RuntimeCall =
+				<T as Config>::RuntimeCall::from(Call::<T>::note_response {
+					query_id: 0,
+					response: Default::default(),
+				})
+				.into();
+			// `pallet-xcm` refuses to dispatch the notification if it is heavier than this
+			let max_weight = notify.get_dispatch_info().call_weight;
+			let query_id = pallet_xcm::Pallet::<T>::new_notify_query(
+				chain.clone(),
+				notify,
+				timeout,
+				origin_location,
+			);
+
+			let fees: Asset = (remote_chain.fee_asset, fees).into();
+			let response_info =
+				QueryResponseInfo { destination: response_destination, query_id, max_weight };
+			let message = Xcm(vec![
+				WithdrawAsset(fees.clone().into()),
+				BuyExecution { fees, weight_limit: Unlimited },
+				SetAppendix(Xcm(vec![
+					RefundSurplus,
+					ReportTransactStatus(response_info),
+					DepositAsset { assets: AllCounted(1).into(), beneficiary: remote_origin },
+				])),
+				Transact {
+					origin_kind: OriginKind::SovereignAccount,
+					call: call.into_inner().into(),
+				},
+			]);
+
+			let message_id = pallet_xcm::Pallet::<T>::send_xcm(interior, chain.clone(), message)
+				.map_err(|e| {
+					tracing::debug!(target: LOG_TARGET, ?e, ?chain, "Failed to send remote call");
+					Error::<T>::SendFailure
+				})?;
+
+			PendingCalls::<T>::insert(
+				query_id,
+				PendingCall { who: who.clone(), chain: chain.clone(), timeout },
+			);
+			PendingCallCount::<T>::insert(&who, pending.saturating_add(1));
+			Self::deposit_event(Event::RemoteCallSent { who, chain, query_id, message_id });
+			Ok(())
+		}
+
+		/// Note the outcome of a remote call, as reported by the remote chain.
+		///
+		/// Only callable by `pallet-xcm`, when handling the response to the query of the call.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::note_response())]
+		pub fn note_response(
+			origin: OriginFor<T>,
+			query_id: QueryId,
+			response: Response,
+		) -> DispatchResult {
+			let responder =
+				pallet_xcm::ensure_response(<T as Config>::RuntimeOrigin::from(origin))?;
+			let call = PendingCalls::<T>::get(query_id).ok_or(Error::<T>::UnknownQuery)?;
+			ensure!(responder == call.chain, Error::<T>::UnexpectedResponse);
+			let Response::DispatchResult(result) = response else {
+				return Err(Error::<T>::UnexpectedResponse.into())
+			};
+
+			Self::remove_pending_call(query_id, &call.who);
+			let who = call.who;
+			Self::deposit_event(match result {
+				MaybeErrorCode::Success => Event::RemoteCallDispatched { who, query_id },
+				error => Event::RemoteCallFailed { who, query_id, error },
+			});
+			Ok(())
+		}
+
+		/// Remove a remote call whose outcome was not reported in time.
+		///
+		/// Callable by anyone, so that expired calls do not count against the limit of pending
+		/// calls of their account forever.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as Config>::WeightInfo::clear_expired_call())]
+		pub fn clear_expired_call(origin: OriginFor<T>, query_id: QueryId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let call = PendingCalls::<T>::get(query_id).ok_or(Error::<T>::UnknownQuery)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() > call.timeout,
+				Error::<T>::NotExpired
+			);
+
+			Self::remove_pending_call(query_id, &call.who);
+			Self::deposit_event(Event::RemoteCallExpired { who: call.who, query_id });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the account controlled by `who` on `chain`, if `chain` is registered and the way
+	/// it derives accounts is known.
+	pub fn remote_account(who: &T::AccountId, chain: &Location) -> Option<[u8; 32]> {
+		let remote_chain = RemoteChains::<T>::get(chain)?;
+		let origin_location = <T as pallet_xcm::Config>::SendXcmOrigin::ensure_origin(
+			frame_system::RawOrigin::Signed(who.clone()).into(),
+		)
+		.ok()?;
+		let remote_origin = origin_location
+			.reanchored(chain, &<T as pallet_xcm::Config>::UniversalLocation::get())
+			.ok()?;
+		match remote_chain.account_derivation {
+			AccountDerivation::DescribeFamily => HashedDescription::<
+				[u8; 32],
+				DescribeFamily<DescribeAllTerminal>,
+			>::convert_location(&remote_origin),
+			AccountDerivation::Unknown => None,
+		}
+	}
+
+	fn remove_pending_call(query_id: QueryId, who: &T::AccountId) {
+		PendingCalls::<T>::remove(query_id);
+		PendingCallCount::<T>::mutate_exists(who, |count| {
+			*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+		});
+	}
+}
@@ -0,0 +1,258 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate as pallet_xcm_proxy;
+
+use codec::Encode;
+use core::cell::RefCell;
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{Everything, Nothing},
+};
+use frame_system::EnsureRoot;
+use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage};
+use xcm::prelude::*;
+use xcm_builder::{
+	AllowKnownQueryResponses, FixedWeightBounds, FrameTransactionalProcessor, IsConcrete,
+	SignedToAccountId32,
+};
+use xcm_executor::XcmExecutor;
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+pub const ALICE: AccountId = AccountId32::new([1; 32]);
+pub const BOB: AccountId = AccountId32::new([2; 32]);
+/// Delivery fee charged by [`TestRouter`] for every message.
+pub const DELIVERY_FEE: u128 = 10;
+pub const INITIAL_BALANCE: u128 = 1_000;
+
+construct_runtime! {
+	pub enum TestRuntime
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		XcmPallet: pallet_xcm,
+		XcmProxy: pallet_xcm_proxy,
+	}
+}
+
+parameter_types! {
+	pub const AnyNetwork: Option<NetworkId> = None;
+	pub HereLocation: Location = Location::here();
+	pub UniversalLocation: InteriorLocation = [GlobalConsensus(NetworkId::Polkadot), Parachain(2000)].into();
+	/// A sibling parachain calls are sent to.
+	pub RemoteChainLocation: Location = Location::new(1, [Parachain(1000)]);
+	pub const UnitWeightCost: Weight = Weight::from_parts(10, 10);
+	pub const MaxInstructions: u32 = 100;
+	pub const MaxAssetsIntoHolding: u32 = 4;
+	pub const MaxCallLen: u32 = 16;
+	pub const MaxPendingCalls: u32 = 2;
+	pub const ResponseTimeout: u64 = 10;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for TestRuntime {
+	type Block = Block;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<AccountId>;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for TestRuntime {
+	type Balance = Balance;
+	type ExistentialDeposit = frame_support::traits::ConstU128<1>;
+	type AccountStore = System;
+}
+
+thread_local! {
+	pub static SENT_XCM: RefCell<Vec<(Location, Xcm<()>)>> = RefCell::new(Vec::new());
+}
+
+/// Returns and clears the messages sent through [`TestRouter`].
+pub fn take_sent_xcm() -> Vec<(Location, Xcm<()>)> {
+	SENT_XCM.with(|q| core::mem::take(&mut *q.borrow_mut()))
+}
+
+/// Router that only routes to [`RemoteChainLocation`], and records the messages it delivers.
+pub struct TestRouter;
+
+impl SendXcm for TestRouter {
+	type Ticket = (Location, Xcm<()>);
+
+	fn validate(
+		destination: &mut Option<Location>,
+		message: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		if destination.as_ref() != Some(&RemoteChainLocation::get()) {
+			return Err(SendError::NotApplicable)
+		}
+		let ticket =
+			(destination.take().unwrap(), message.take().ok_or(SendError::MissingArgument)?);
+		Ok((ticket, (Here, DELIVERY_FEE).into()))
+	}
+
+	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+		let hash = ticket.1.using_encoded(sp_io::hashing::blake2_256);
+		SENT_XCM.with(|q| q.borrow_mut().push(ticket));
+		Ok(hash)
+	}
+}
+
+pub type LocalAssetTransactor = xcm_builder::FungibleAdapter<
+	Balances,
+	IsConcrete<HereLocation>,
+	xcm_builder::AccountId32Aliases<AnyNetwork, AccountId>,
+	AccountId,
+	(),
+>;
+
+pub struct XcmConfig;
+impl xcm_executor::Config for XcmConfig {
+	type RuntimeCall = RuntimeCall;
+	type XcmSender = TestRouter;
+	type AssetTransactor = LocalAssetTransactor;
+	type OriginConverter = ();
+	type IsReserve = ();
+	type IsTeleporter = ();
+	type UniversalLocation = UniversalLocation;
+	type Barrier = AllowKnownQueryResponses<XcmPallet>;
+	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type Trader = ();
+	type ResponseHandler = XcmPallet;
+	type AssetTrap = XcmPallet;
+	type AssetLocker = ();
+	type AssetExchanger = ();
+	type AssetClaims = XcmPallet;
+	type SubscriptionService = XcmPallet;
+	type PalletInstancesInfo = AllPalletsWithSystem;
+	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+	type FeeManager = ();
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
+	type CallDispatcher = RuntimeCall;
+	type SafeCallFilter = Everything;
+	type Aliasers = Nothing;
+	type TransactionalProcessor = FrameTransactionalProcessor;
+	type HrmpNewChannelOpenRequestHandler = ();
+	type HrmpChannelAcceptedHandler = ();
+	type HrmpChannelClosingHandler = ();
+	type XcmRecorder = XcmPallet;
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, AnyNetwork>;
+
+impl pallet_xcm::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+	type XcmRouter = TestRouter;
+	type ExecuteXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+	type XcmExecuteFilter = Nothing;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type XcmTeleportFilter = Nothing;
+	type XcmReserveTransferFilter = Nothing;
+	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type UniversalLocation = UniversalLocation;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type TrustedLockers = ();
+	type SovereignAccountOf = xcm_builder::AccountId32Aliases<AnyNetwork, AccountId>;
+	type Currency = Balances;
+	type CurrencyMatcher = IsConcrete<HereLocation>;
+	type MaxLockers = frame_support::traits::ConstU32<0>;
+	type MaxRemoteLockConsumers = frame_support::traits::ConstU32<0>;
+	type RemoteLockConsumerIdentifier = ();
+	type WeightInfo = pallet_xcm::TestWeightInfo;
+}
+
+impl pallet_xcm_proxy::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxCallLen = MaxCallLen;
+	type MaxPendingCalls = MaxPendingCalls;
+	type ResponseTimeout = ResponseTimeout;
+	type WeightInfo = ();
+}
+
+/// Funds the local account of the origin of delivered messages with [`DELIVERY_FEE`].
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestDeliveryHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl xcm_builder::EnsureDelivery for TestDeliveryHelper {
+	fn ensure_successful_delivery(
+		origin_ref: &Location,
+		_dest: &Location,
+		_fee_reason: xcm_executor::traits::FeeReason,
+	) -> (Option<xcm_executor::FeesMode>, Option<Assets>) {
+		use frame_support::traits::fungible::Mutate;
+		if let Some(Junction::AccountId32 { id, .. }) = origin_ref.last() {
+			let _ = Balances::mint_into(&(*id).into(), INITIAL_BALANCE);
+		}
+		(None, None)
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_xcm_proxy::benchmarking::Config for TestRuntime {
+	type DeliveryHelper = TestDeliveryHelper;
+
+	fn reachable_chain() -> Option<Location> {
+		Some(RemoteChainLocation::get())
+	}
+}
+
+/// Executes `message` as if it was received from `origin`.
+pub fn receive_xcm(origin: Location, message: Xcm<RuntimeCall>) -> Outcome {
+	let mut hash = message.using_encoded(sp_io::hashing::blake2_256);
+	XcmExecutor::<XcmConfig>::prepare_and_execute(
+		origin,
+		message,
+		&mut hash,
+		Weight::MAX,
+		Weight::zero(),
+	)
+}
+
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<TestRuntime> {
+		balances: vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	new_test_ext().execute_with(test)
+}
@@ -0,0 +1,295 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	mock::*, AccountDerivation, Error, Event, PendingCallCount, PendingCalls, RemoteChain,
+	RemoteChains, WeightInfo,
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungible::Inspect, BoundedVec};
+use sp_runtime::DispatchError;
+use xcm::prelude::*;
+use xcm_builder::{DescribeAllTerminal, DescribeFamily, HashedDescription};
+use xcm_executor::traits::ConvertLocation;
+
+const FEES: u128 = 100;
+
+fn remote_chain() -> Box<VersionedLocation> {
+	Box::new(RemoteChainLocation::get().into())
+}
+
+fn register(account_derivation: AccountDerivation) {
+	assert_ok!(XcmProxy::register_remote_chain(
+		RuntimeOrigin::root(),
+		remote_chain(),
+		Box::new(AssetId(Location::parent()).into()),
+		account_derivation,
+	));
+}
+
+fn call() -> BoundedVec<u8, MaxCallLen> {
+	vec![7, 0, 42].try_into().unwrap()
+}
+
+fn local_location(who: &AccountId) -> Location {
+	AccountId32 { network: None, id: who.clone().into() }.into()
+}
+
+fn respond(query_id: QueryId, result: MaybeErrorCode) -> Outcome {
+	receive_xcm(
+		RemoteChainLocation::get(),
+		Xcm(vec![QueryResponse {
+			query_id,
+			response: Response::DispatchResult(result),
+			max_weight: <() as WeightInfo>::note_response(),
+			querier: Some(local_location(&ALICE)),
+		}]),
+	)
+}
+
+fn last_event() -> Event<TestRuntime> {
+	System::events()
+		.into_iter()
+		.rev()
+		.find_map(|record| match record.event {
+			RuntimeEvent::XcmProxy(event) => Some(event),
+			_ => None,
+		})
+		.expect("an event was deposited")
+}
+
+#[test]
+fn only_admin_registers_remote_chains() {
+	run_test(|| {
+		let fee_asset = Box::new(VersionedAssetId::from(AssetId(Location::parent())));
+		assert_noop!(
+			XcmProxy::register_remote_chain(
+				RuntimeOrigin::signed(ALICE),
+				remote_chain(),
+				fee_asset.clone(),
+				AccountDerivation::DescribeFamily,
+			),
+			DispatchError::BadOrigin,
+		);
+
+		register(AccountDerivation::DescribeFamily);
+		assert_eq!(
+			RemoteChains::<TestRuntime>::get(RemoteChainLocation::get()),
+			Some(RemoteChain {
+				fee_asset: AssetId(Location::parent()),
+				account_derivation: AccountDerivation::DescribeFamily,
+			}),
+		);
+		assert_noop!(
+			XcmProxy::register_remote_chain(
+				RuntimeOrigin::root(),
+				remote_chain(),
+				fee_asset,
+				AccountDerivation::Unknown,
+			),
+			Error::<TestRuntime>::AlreadyRegistered,
+		);
+
+		assert_ok!(XcmProxy::deregister_remote_chain(RuntimeOrigin::root(), remote_chain()));
+		assert!(!RemoteChains::<TestRuntime>::contains_key(RemoteChainLocation::get()));
+		assert_noop!(
+			XcmProxy::deregister_remote_chain(RuntimeOrigin::root(), remote_chain()),
+			Error::<TestRuntime>::UnknownRemoteChain,
+		);
+	});
+}
+
+#[test]
+fn remote_call_sends_transact_paid_by_remote_account() {
+	run_test(|| {
+		assert_noop!(
+			XcmProxy::remote_call(RuntimeOrigin::signed(ALICE), remote_chain(), call(), FEES),
+			Error::<TestRuntime>::UnknownRemoteChain,
+		);
+		register(AccountDerivation::DescribeFamily);
+
+		assert_ok!(XcmProxy::remote_call(
+			RuntimeOrigin::signed(ALICE),
+			remote_chain(),
+			call(),
+			FEES
+		));
+
+		let alice_on_remote =
+			Location::new(1, [Parachain(2000), AccountId32 { network: None, id: ALICE.into() }]);
+		let fees: Asset = (Parent, FEES).into();
+		assert_eq!(
+			take_sent_xcm(),
+			vec![(
+				RemoteChainLocation::get(),
+				Xcm(vec![
+					DescendOrigin(AccountId32 { network: None, id: ALICE.into() }.into()),
+					WithdrawAsset(fees.clone().into()),
+					BuyExecution { fees, weight_limit: Unlimited },
+					SetAppendix(Xcm(vec![
+						RefundSurplus,
+						ReportTransactStatus(QueryResponseInfo {
+							destination: Location::new(1, [Parachain(2000)]),
+							query_id: 0,
+							max_weight: <() as WeightInfo>::note_response(),
+						}),
+						DepositAsset { assets: AllCounted(1).into(), beneficiary: alice_on_remote },
+					])),
+					Transact {
+						origin_kind: OriginKind::SovereignAccount,
+						call: call().into_inner().into()
+					},
+				]),
+			)],
+		);
+		// delivery fees are paid locally
+		assert_eq!(Balances::balance(&ALICE), INITIAL_BALANCE - DELIVERY_FEE);
+		assert!(matches!(last_event(), Event::RemoteCallSent { who: ALICE, query_id: 0, .. }));
+		assert_eq!(
+			PendingCalls::<TestRuntime>::get(0).unwrap().timeout,
+			1 + ResponseTimeout::get()
+		);
+		assert_eq!(PendingCallCount::<TestRuntime>::get(ALICE), 1);
+	});
+}
+
+#[test]
+fn reported_outcome_is_noted() {
+	run_test(|| {
+		register(AccountDerivation::DescribeFamily);
+		assert_ok!(XcmProxy::remote_call(
+			RuntimeOrigin::signed(ALICE),
+			remote_chain(),
+			call(),
+			FEES
+		));
+		assert_ok!(XcmProxy::remote_call(
+			RuntimeOrigin::signed(ALICE),
+			remote_chain(),
+			call(),
+			FEES
+		));
+
+		assert!(respond(0, MaybeErrorCode::Success).ensure_complete().is_ok());
+		assert_eq!(last_event(), Event::RemoteCallDispatched { who: ALICE, query_id: 0 });
+		assert!(!PendingCalls::<TestRuntime>::contains_key(0));
+		assert_eq!(PendingCallCount::<TestRuntime>::get(ALICE), 1);
+
+		let error = MaybeErrorCode::from(vec![1, 2]);
+		assert!(respond(1, error.clone()).ensure_complete().is_ok());
+		assert_eq!(last_event(), Event::RemoteCallFailed { who: ALICE, query_id: 1, error });
+		assert!(!PendingCalls::<TestRuntime>::contains_key(1));
+		assert!(!PendingCallCount::<TestRuntime>::contains_key(ALICE));
+	});
+}
+
+#[test]
+fn outcome_is_only_accepted_from_remote_chain() {
+	run_test(|| {
+		register(AccountDerivation::DescribeFamily);
+		assert_ok!(XcmProxy::remote_call(
+			RuntimeOrigin::signed(ALICE),
+			remote_chain(),
+			call(),
+			FEES
+		));
+
+		// not even through `pallet-xcm`
+		let outcome = receive_xcm(
+			Location::new(1, [Parachain(3000)]),
+			Xcm(vec![QueryResponse {
+				query_id: 0,
+				response: Response::DispatchResult(MaybeErrorCode::Success),
+				max_weight: <() as WeightInfo>::note_response(),
+				querier: Some(local_location(&ALICE)),
+			}]),
+		);
+		assert!(outcome.ensure_complete().is_err());
+
+		// nor directly
+		assert_noop!(
+			XcmProxy::note_response(RuntimeOrigin::signed(ALICE), 0, Response::Null),
+			DispatchError::BadOrigin,
+		);
+		assert!(PendingCalls::<TestRuntime>::contains_key(0));
+	});
+}
+
+#[test]
+fn pending_calls_are_limited_and_expire() {
+	run_test(|| {
+		register(AccountDerivation::DescribeFamily);
+		for _ in 0..MaxPendingCalls::get() {
+			assert_ok!(XcmProxy::remote_call(
+				RuntimeOrigin::signed(ALICE),
+				remote_chain(),
+				call(),
+				FEES
+			));
+		}
+		assert_noop!(
+			XcmProxy::remote_call(RuntimeOrigin::signed(ALICE), remote_chain(), call(), FEES),
+			Error::<TestRuntime>::TooManyPendingCalls,
+		);
+		// other accounts are not affected
+		assert_ok!(XcmProxy::remote_call(RuntimeOrigin::signed(BOB), remote_chain(), call(), FEES));
+
+		assert_noop!(
+			XcmProxy::clear_expired_call(RuntimeOrigin::signed(BOB), 0),
+			Error::<TestRuntime>::NotExpired,
+		);
+		run_to_block(2 + ResponseTimeout::get());
+		assert_ok!(XcmProxy::clear_expired_call(RuntimeOrigin::signed(BOB), 0));
+		assert_eq!(last_event(), Event::RemoteCallExpired { who: ALICE, query_id: 0 });
+		assert_eq!(PendingCallCount::<TestRuntime>::get(ALICE), 1);
+		assert_noop!(
+			XcmProxy::clear_expired_call(RuntimeOrigin::signed(BOB), 0),
+			Error::<TestRuntime>::UnknownQuery,
+		);
+
+		assert_ok!(XcmProxy::remote_call(
+			RuntimeOrigin::signed(ALICE),
+			remote_chain(),
+			call(),
+			FEES
+		));
+	});
+}
+
+#[test]
+fn remote_account_follows_account_derivation() {
+	run_test(|| {
+		assert_eq!(XcmProxy::remote_account(&ALICE, &RemoteChainLocation::get()), None);
+
+		register(AccountDerivation::DescribeFamily);
+		let alice_on_remote =
+			Location::new(1, [Parachain(2000), AccountId32 { network: None, id: ALICE.into() }]);
+		let expected =
+			HashedDescription::<[u8; 32], DescribeFamily<DescribeAllTerminal>>::convert_location(
+				&alice_on_remote,
+			);
+		assert!(expected.is_some());
+		assert_eq!(XcmProxy::remote_account(&ALICE, &RemoteChainLocation::get()), expected);
+		assert_ne!(
+			XcmProxy::remote_account(&BOB, &RemoteChainLocation::get()),
+			XcmProxy::remote_account(&ALICE, &RemoteChainLocation::get()),
+		);
+
+		assert_ok!(XcmProxy::deregister_remote_chain(RuntimeOrigin::root(), remote_chain()));
+		register(AccountDerivation::Unknown);
+		assert_eq!(XcmProxy::remote_account(&ALICE, &RemoteChainLocation::get()), None);
+	});
+}
@@ -0,0 +1,185 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet-xcm-proxy`.
+//!
+//! Storage accesses of these weights follow the `pallet-xcm-proxy` benchmarks. Proof sizes are
+//! computed for 32-byte account ids and 32-bit block numbers, and do not include the storage
+//! items of `pallet-xcm` that have no bounded size. Execution times have not been measured on the
+//! reference hardware yet, so this file must be regenerated with the benchmark CLI before the
+//! pallet is used in a production runtime:
+//!
+//! ```text
+//! frame-omni-bencher v1 benchmark pallet \
+//!     --runtime=<runtime.compact.compressed.wasm> \
+//!     --pallet=pallet_xcm_proxy --extrinsic=* --steps=50 --repeat=20 \
+//!     --template=./substrate/.maintain/frame-weight-template.hbs \
+//!     --output=./polkadot/xcm/pallet-xcm-proxy/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet-xcm-proxy`.
+pub trait WeightInfo {
+	fn register_remote_chain() -> Weight;
+	fn deregister_remote_chain() -> Weight;
+	fn remote_call(n: u32, ) -> Weight;
+	fn note_response() -> Weight;
+	fn clear_expired_call() -> Weight;
+}
+
+/// Weights for `pallet-xcm-proxy` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `XcmProxy::RemoteChains` (r:1 w:1)
+	/// Proof: `XcmProxy::RemoteChains` (`max_values`: None, `max_size`: Some(1221), added: 3696, mode: `MaxEncodedLen`)
+	fn register_remote_chain() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4686`
+		Weight::from_parts(15_000_000, 4_686)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmProxy::RemoteChains` (r:1 w:1)
+	/// Proof: `XcmProxy::RemoteChains` (`max_values`: None, `max_size`: Some(1221), added: 3696, mode: `MaxEncodedLen`)
+	fn deregister_remote_chain() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4686`
+		Weight::from_parts(15_000_000, 4_686)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmProxy::RemoteChains` (r:1 w:0)
+	/// Proof: `XcmProxy::RemoteChains` (`max_values`: None, `max_size`: Some(1221), added: 3696, mode: `MaxEncodedLen`)
+	/// Storage: `XcmProxy::PendingCallCount` (r:1 w:1)
+	/// Proof: `XcmProxy::PendingCallCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `XcmPallet::QueryCounter` (r:1 w:1)
+	/// Proof: `XcmPallet::QueryCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmPallet::SupportedVersion` (r:1 w:0)
+	/// Proof: `XcmPallet::SupportedVersion` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `XcmPallet::Queries` (r:0 w:1)
+	/// Proof: `XcmPallet::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmProxy::PendingCalls` (r:0 w:1)
+	/// Proof: `XcmProxy::PendingCalls` (`max_values`: None, `max_size`: Some(654), added: 3129, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, MaxCallLen]`.
+	fn remote_call(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4686`
+		Weight::from_parts(80_000_000, 4_686)
+			// encoding and sending the message through the router
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `XcmProxy::PendingCalls` (r:1 w:1)
+	/// Proof: `XcmProxy::PendingCalls` (`max_values`: None, `max_size`: Some(654), added: 3129, mode: `MaxEncodedLen`)
+	/// Storage: `XcmProxy::PendingCallCount` (r:1 w:1)
+	/// Proof: `XcmProxy::PendingCallCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn note_response() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4119`
+		Weight::from_parts(20_000_000, 4_119)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `XcmProxy::PendingCalls` (r:1 w:1)
+	/// Proof: `XcmProxy::PendingCalls` (`max_values`: None, `max_size`: Some(654), added: 3129, mode: `MaxEncodedLen`)
+	/// Storage: `XcmProxy::PendingCallCount` (r:1 w:1)
+	/// Proof: `XcmProxy::PendingCallCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn clear_expired_call() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4119`
+		Weight::from_parts(20_000_000, 4_119)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `XcmProxy::RemoteChains` (r:1 w:1)
+	/// Proof: `XcmProxy::RemoteChains` (`max_values`: None, `max_size`: Some(1221), added: 3696, mode: `MaxEncodedLen`)
+	fn register_remote_chain() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4686`
+		Weight::from_parts(15_000_000, 4_686)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmProxy::RemoteChains` (r:1 w:1)
+	/// Proof: `XcmProxy::RemoteChains` (`max_values`: None, `max_size`: Some(1221), added: 3696, mode: `MaxEncodedLen`)
+	fn deregister_remote_chain() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4686`
+		Weight::from_parts(15_000_000, 4_686)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmProxy::RemoteChains` (r:1 w:0)
+	/// Proof: `XcmProxy::RemoteChains` (`max_values`: None, `max_size`: Some(1221), added: 3696, mode: `MaxEncodedLen`)
+	/// Storage: `XcmProxy::PendingCallCount` (r:1 w:1)
+	/// Proof: `XcmProxy::PendingCallCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `XcmPallet::QueryCounter` (r:1 w:1)
+	/// Proof: `XcmPallet::QueryCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmPallet::SupportedVersion` (r:1 w:0)
+	/// Proof: `XcmPallet::SupportedVersion` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `XcmPallet::Queries` (r:0 w:1)
+	/// Proof: `XcmPallet::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmProxy::PendingCalls` (r:0 w:1)
+	/// Proof: `XcmProxy::PendingCalls` (`max_values`: None, `max_size`: Some(654), added: 3129, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, MaxCallLen]`.
+	fn remote_call(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4686`
+		Weight::from_parts(80_000_000, 4_686)
+			// encoding and sending the message through the router
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `XcmProxy::PendingCalls` (r:1 w:1)
+	/// Proof: `XcmProxy::PendingCalls` (`max_values`: None, `max_size`: Some(654), added: 3129, mode: `MaxEncodedLen`)
+	/// Storage: `XcmProxy::PendingCallCount` (r:1 w:1)
+	/// Proof: `XcmProxy::PendingCallCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn note_response() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4119`
+		Weight::from_parts(20_000_000, 4_119)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `XcmProxy::PendingCalls` (r:1 w:1)
+	/// Proof: `XcmProxy::PendingCalls` (`max_values`: None, `max_size`: Some(654), added: 3129, mode: `MaxEncodedLen`)
+	/// Storage: `XcmProxy::PendingCallCount` (r:1 w:1)
+	/// Proof: `XcmProxy::PendingCallCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn clear_expired_call() -> Weight {
+		// Proof Size summary in bytes:
+		//  Estimated: `4119`
+		Weight::from_parts(20_000_000, 4_119)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
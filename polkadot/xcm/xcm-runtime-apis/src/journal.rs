@@ -0,0 +1,92 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for querying the journal of sent and received XCMs.
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use frame_support::pallet_prelude::TypeInfo;
+use xcm::prelude::*;
+
+/// A single step in the lifecycle of an XCM, as observed by this chain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum JournalEvent {
+	/// The message was validated and handed over to the transport for delivery.
+	#[codec(index = 0)]
+	Sent {
+		/// The local origin that sent the message, as seen from this chain.
+		origin: VersionedLocation,
+		/// The destination the message is delivered to.
+		destination: VersionedLocation,
+		/// The delivery fees paid for sending the message.
+		fees: VersionedAssets,
+	},
+	/// The message was received from `origin` and executed locally.
+	#[codec(index = 1)]
+	Executed {
+		/// The origin the message was executed with.
+		origin: VersionedLocation,
+		/// The outcome of the execution, including the weight used.
+		outcome: Outcome,
+	},
+}
+
+/// A [`JournalEvent`] together with the block it was recorded in.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct JournalRecord<BlockNumber> {
+	/// The block in which the event happened.
+	pub block_number: BlockNumber,
+	/// What happened to the message.
+	pub event: JournalEvent,
+}
+
+/// Everything this chain knows about a single message, identified by its topic id.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct MessageLifecycle<BlockNumber> {
+	/// The topic id of the message.
+	pub topic: XcmHash,
+	/// The recorded events, oldest first.
+	pub records: Vec<JournalRecord<BlockNumber>>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for looking up the journal of XCMs sent and executed by this chain.
+	///
+	/// Messages are indexed by their topic id, which is the `SetTopic` id of the message or,
+	/// in its absence, the message hash. Only messages recorded within the retention period
+	/// configured by the runtime are available.
+	pub trait XcmJournalApi<BlockNumber>
+	where
+		BlockNumber: Decode,
+	{
+		/// Returns the lifecycle of the message with the given `topic`, if it is known.
+		fn message_lifecycle(topic: XcmHash) -> Result<Option<MessageLifecycle<BlockNumber>>, Error>;
+
+		/// Returns the topic ids of all messages recorded in the given block.
+		fn messages_at(block_number: BlockNumber) -> Result<Vec<XcmHash>, Error>;
+	}
+}
+
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// An API call is unsupported.
+	#[codec(index = 0)]
+	Unimplemented,
+
+	/// The requested block is outside of the retention period.
+	#[codec(index = 1)]
+	OutsideRetentionPeriod,
+}
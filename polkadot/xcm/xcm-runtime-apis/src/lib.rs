@@ -31,6 +31,10 @@ pub mod dry_run;
 /// Given an XCM program, it will return the fees needed to execute it properly or send it.
 pub mod fees;
 
+/// Journal API.
+/// Given the topic id of an XCM, it returns what happened to it on this chain.
+pub mod journal;
+
 // Exposes runtime API for querying whether a Location is trusted as a reserve or teleporter for a
 // given Asset.
 pub mod trusted_query;
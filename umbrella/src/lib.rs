@@ -737,6 +737,18 @@ pub use pallet_xcm_bridge_hub;
 #[cfg(feature = "pallet-xcm-bridge-hub-router")]
 pub use pallet_xcm_bridge_hub_router;
 
+/// A pallet keeping a journal of sent and executed XCMs.
+#[cfg(feature = "pallet-xcm-journal")]
+pub use pallet_xcm_journal;
+
+/// RPC interface for the XCM journal pallet.
+#[cfg(feature = "pallet-xcm-journal-rpc")]
+pub use pallet_xcm_journal_rpc;
+
+/// A pallet for controlling accounts on remote chains over XCM.
+#[cfg(feature = "pallet-xcm-proxy")]
+pub use pallet_xcm_proxy;
+
 /// Logic which is common to all parachain runtimes.
 #[cfg(feature = "parachains-common")]
 pub use parachains_common;
@@ -1188,6 +1200,10 @@ pub use snowbridge_core;
 #[cfg(feature = "snowbridge-ethereum")]
 pub use snowbridge_ethereum;
 
+/// Snowbridge Ethereum Client Runtime API.
+#[cfg(feature = "snowbridge-ethereum-client-runtime-api")]
+pub use snowbridge_ethereum_client_runtime_api;
+
 /// Snowbridge Outbound Queue Merkle Tree.
 #[cfg(feature = "snowbridge-outbound-queue-merkle-tree")]
 pub use snowbridge_outbound_queue_merkle_tree;